@@ -1,6 +1,7 @@
 pub mod bangumi;
 mod collection;
 mod favorite;
+mod ranking;
 mod submission;
 mod watch_later;
 
@@ -26,11 +27,13 @@ use sea_orm::DatabaseConnection;
 #[rustfmt::skip]
 use bili_sync_entity::collection::Model as Collection;
 use bili_sync_entity::favorite::Model as Favorite;
+use bili_sync_entity::ranking::Model as Ranking;
 use bili_sync_entity::submission::Model as Submission;
 use bili_sync_entity::watch_later::Model as WatchLater;
 
 use crate::adapter::collection::collection_from;
 use crate::adapter::favorite::favorite_from;
+use crate::adapter::ranking::ranking_from;
 use crate::adapter::submission::submission_from;
 use crate::adapter::watch_later::watch_later_from;
 use crate::bilibili::{BiliClient, CollectionItem, VideoInfo};
@@ -42,6 +45,7 @@ pub enum VideoSourceEnum {
     Submission,
     WatchLater,
     BangumiSource,
+    Ranking,
 }
 
 #[enum_dispatch(VideoSourceEnum)]
@@ -270,6 +274,10 @@ pub enum Args {
         media_id: Option<String>,
         ep_id: Option<String>,
     },
+    Ranking {
+        ranking_type: String,
+        rid: i32,
+    },
 }
 
 pub async fn video_source_from<'a>(
@@ -294,15 +302,62 @@ pub async fn video_source_from<'a>(
             media_id,
             ep_id,
         } => bangumi_from(season_id, media_id, ep_id, path, bili_client, connection).await,
+        Args::Ranking { ranking_type, rid } => ranking_from(ranking_type, *rid, path, bili_client, connection).await,
     }
 }
 
+/// 所有视频源类型的名称
+pub const SOURCE_TYPES: &[&str] = &[
+    "favorite",
+    "collection",
+    "submission",
+    "watch_later",
+    "bangumi",
+    "ranking",
+];
+
+/// 按视频源类型与 id 筛选视频的条件，未知的视频源类型返回 None
+pub fn video_source_condition(source_type: &str, id: i32) -> Option<SimpleExpr> {
+    use bili_sync_entity::video::Column;
+
+    Some(match source_type {
+        "favorite" => Column::FavoriteId.eq(id),
+        "collection" => Column::CollectionId.eq(id),
+        "submission" => Column::SubmissionId.eq(id),
+        "watch_later" => Column::WatchLaterId.eq(id),
+        "ranking" => Column::RankingId.eq(id),
+        "bangumi" => Column::SourceId.eq(id).and(Column::SourceType.eq(1)),
+        _ => return None,
+    })
+}
+
+/// 视频所属的视频源类型与 id，与 [`video_source_condition`] 互为逆映射
+pub fn video_source_of(model: &bili_sync_entity::video::Model) -> (Option<String>, Option<i32>) {
+    let (source_type, id) = if let Some(id) = model.collection_id {
+        ("collection", id)
+    } else if let Some(id) = model.favorite_id {
+        ("favorite", id)
+    } else if let Some(id) = model.watch_later_id {
+        ("watch_later", id)
+    } else if let Some(id) = model.submission_id {
+        ("submission", id)
+    } else if let Some(id) = model.ranking_id {
+        ("ranking", id)
+    } else if let (Some(id), Some(1)) = (model.source_id, model.source_type) {
+        ("bangumi", id)
+    } else {
+        return (None, None);
+    };
+    (Some(source_type.to_string()), Some(id))
+}
+
 pub enum _ActiveModel {
     Favorite(bili_sync_entity::favorite::ActiveModel),
     Collection(bili_sync_entity::collection::ActiveModel),
     Submission(bili_sync_entity::submission::ActiveModel),
     WatchLater(bili_sync_entity::watch_later::ActiveModel),
     Bangumi(Box<bili_sync_entity::video_source::ActiveModel>),
+    Ranking(bili_sync_entity::ranking::ActiveModel),
 }
 
 impl _ActiveModel {
@@ -323,6 +378,9 @@ impl _ActiveModel {
             _ActiveModel::Bangumi(model) => {
                 model.save(connection).await?;
             }
+            _ActiveModel::Ranking(model) => {
+                model.save(connection).await?;
+            }
         }
         Ok(())
     }
@@ -464,6 +522,73 @@ mod tests {
         assert_eq!(submission.upper_name, "自定义UP名");
         assert_eq!(submission.path, "/new/submission");
     }
+
+    #[tokio::test]
+    async fn video_source_condition_matches_video_source_of() {
+        use bili_sync_entity::video;
+        use sea_orm::{ActiveModelTrait, IntoActiveModel};
+
+        let db = create_test_db("video-source-condition").await;
+        let videos = [
+            video::Model {
+                id: 1,
+                bvid: "BV1".to_string(),
+                collection_id: Some(7),
+                ..Default::default()
+            },
+            video::Model {
+                id: 2,
+                bvid: "BV2".to_string(),
+                favorite_id: Some(7),
+                ..Default::default()
+            },
+            video::Model {
+                id: 3,
+                bvid: "BV3".to_string(),
+                watch_later_id: Some(7),
+                ..Default::default()
+            },
+            video::Model {
+                id: 4,
+                bvid: "BV4".to_string(),
+                submission_id: Some(7),
+                ..Default::default()
+            },
+            video::Model {
+                id: 5,
+                bvid: "BV5".to_string(),
+                ranking_id: Some(7),
+                ..Default::default()
+            },
+            video::Model {
+                id: 6,
+                bvid: "BV6".to_string(),
+                source_id: Some(7),
+                source_type: Some(1),
+                ..Default::default()
+            },
+        ];
+        for model in videos {
+            model
+                .into_active_model()
+                .reset_all()
+                .insert(&db)
+                .await
+                .expect("插入视频应成功");
+        }
+
+        for source_type in SOURCE_TYPES {
+            let condition = video_source_condition(source_type, 7).expect("已知视频源类型应有筛选条件");
+            let matched = video::Entity::find()
+                .filter(condition)
+                .all(&db)
+                .await
+                .expect("查询视频应成功");
+            assert_eq!(matched.len(), 1, "{} 应恰好匹配一个视频", source_type);
+            assert_eq!(video_source_of(&matched[0]), (Some((*source_type).to_owned()), Some(7)));
+        }
+        assert!(video_source_condition("unknown", 7).is_none());
+    }
 }
 
 pub async fn bangumi_from<'a>(
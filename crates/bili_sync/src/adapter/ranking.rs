@@ -0,0 +1,240 @@
+use std::path::Path;
+use std::pin::Pin;
+
+use anyhow::{Context, Result};
+use bili_sync_entity::*;
+use chrono::Utc;
+use futures::Stream;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{DatabaseConnection, Unchanged};
+
+use crate::adapter::{_ActiveModel, VideoSource, VideoSourceEnum};
+use crate::bilibili::{BiliClient, Ranking, RankingType, VideoInfo};
+
+impl VideoSource for ranking::Model {
+    fn filter_expr(&self) -> SimpleExpr {
+        video::Column::RankingId.eq(self.id)
+    }
+
+    fn set_relation_id(&self, video_model: &mut video::ActiveModel) {
+        video_model.ranking_id = Set(Some(self.id));
+    }
+
+    fn path(&self) -> &Path {
+        Path::new(self.path.as_str())
+    }
+
+    fn get_latest_row_at(&self) -> String {
+        self.latest_row_at.clone()
+    }
+
+    fn update_latest_row_at(&self, datetime: String) -> _ActiveModel {
+        _ActiveModel::Ranking(ranking::ActiveModel {
+            id: Unchanged(self.id),
+            latest_row_at: Set(datetime),
+            ..Default::default()
+        })
+    }
+
+    fn should_take(&self, _release_datetime: &chrono::DateTime<Utc>, _latest_row_at_string: &str) -> bool {
+        // 榜单不按发布时间排序，且每次只有固定数量的条目，每次都全量拉取
+        true
+    }
+
+    fn log_refresh_video_start(&self) {
+        info!("开始扫描榜单「{}」..", self.name);
+    }
+
+    fn log_refresh_video_end(&self, count: usize) {
+        if count > 0 {
+            info!("扫描榜单「{}」完成，获取到 {} 条新视频", self.name, count);
+        } else {
+            info!("榜单「{}」无新视频", self.name);
+        }
+    }
+
+    fn log_fetch_video_start(&self) {
+        debug!("开始填充榜单「{}」视频详情..", self.name);
+    }
+
+    fn log_fetch_video_end(&self) {
+        debug!("填充榜单「{}」视频详情完成", self.name);
+    }
+
+    fn log_download_video_start(&self) {
+        debug!("开始下载榜单「{}」视频..", self.name);
+    }
+
+    fn log_download_video_end(&self) {
+        debug!("下载榜单「{}」视频完成", self.name);
+    }
+
+    fn scan_deleted_videos(&self) -> bool {
+        self.scan_deleted_videos || self.scan_deleted_videos_once
+    }
+
+    fn source_type_display(&self) -> String {
+        "榜单".to_string()
+    }
+
+    fn source_name_display(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_keyword_filters(&self) -> Option<String> {
+        self.keyword_filters.clone()
+    }
+
+    fn get_keyword_filter_mode(&self) -> Option<String> {
+        self.keyword_filter_mode.clone()
+    }
+
+    fn get_blacklist_keywords(&self) -> Option<String> {
+        self.blacklist_keywords.clone()
+    }
+
+    fn get_whitelist_keywords(&self) -> Option<String> {
+        self.whitelist_keywords.clone()
+    }
+
+    fn get_keyword_case_sensitive(&self) -> bool {
+        self.keyword_case_sensitive
+    }
+
+    fn get_min_duration_seconds(&self) -> Option<i32> {
+        self.min_duration_seconds
+    }
+
+    fn get_max_duration_seconds(&self) -> Option<i32> {
+        self.max_duration_seconds
+    }
+
+    fn get_published_after(&self) -> Option<String> {
+        self.published_after.clone()
+    }
+
+    fn get_published_before(&self) -> Option<String> {
+        self.published_before.clone()
+    }
+
+    fn filter_option(&self) -> Option<&serde_json::Value> {
+        self.filter_option.as_ref()
+    }
+
+    fn audio_only(&self) -> bool {
+        self.audio_only
+    }
+
+    fn audio_only_m4a_only(&self) -> bool {
+        self.audio_only_m4a_only
+    }
+
+    fn flat_folder(&self) -> bool {
+        self.flat_folder
+    }
+
+    fn split_chapters_after_download(&self) -> bool {
+        self.split_chapters_after_download
+    }
+
+    fn download_charge_videos(&self) -> bool {
+        self.download_charge_videos
+    }
+
+    fn download_danmaku(&self) -> bool {
+        self.download_danmaku
+    }
+
+    fn download_subtitle(&self) -> bool {
+        self.download_subtitle
+    }
+
+    fn download_ai_subtitle(&self) -> bool {
+        self.download_ai_subtitle
+    }
+
+    fn ai_subtitle_language(&self) -> &str {
+        &self.ai_subtitle_language
+    }
+
+    fn ai_rename(&self) -> bool {
+        self.ai_rename
+    }
+
+    fn ai_rename_video_prompt(&self) -> &str {
+        &self.ai_rename_video_prompt
+    }
+
+    fn ai_rename_audio_prompt(&self) -> &str {
+        &self.ai_rename_audio_prompt
+    }
+
+    fn ai_rename_enable_multi_page(&self) -> bool {
+        self.ai_rename_enable_multi_page
+    }
+
+    fn ai_rename_enable_collection(&self) -> bool {
+        self.ai_rename_enable_collection
+    }
+
+    fn ai_rename_enable_bangumi(&self) -> bool {
+        self.ai_rename_enable_bangumi
+    }
+
+    fn ai_rename_rename_parent_dir(&self) -> bool {
+        self.ai_rename_rename_parent_dir
+    }
+
+    fn source_key(&self) -> String {
+        format!("ranking_{}", self.id)
+    }
+}
+
+pub(super) async fn ranking_from<'a>(
+    ranking_type: &str,
+    rid: i32,
+    path: &Path,
+    bili_client: &'a BiliClient,
+    connection: &DatabaseConnection,
+) -> Result<(
+    VideoSourceEnum,
+    Pin<Box<dyn Stream<Item = Result<VideoInfo>> + 'a + Send>>,
+)> {
+    let parsed_type = ranking_type.parse::<RankingType>()?;
+    let ranking = Ranking::new(bili_client, parsed_type, rid);
+
+    if let Some(existing) = ranking::Entity::find()
+        .filter(ranking::Column::RankingType.eq(ranking_type))
+        .filter(ranking::Column::Rid.eq(rid))
+        .one(connection)
+        .await?
+    {
+        return Ok((existing.into(), Box::pin(ranking.into_video_stream())));
+    }
+
+    let result = ranking::Entity::insert(ranking::ActiveModel {
+        name: Set(parsed_type.source_name(rid)),
+        ranking_type: Set(ranking_type.to_string()),
+        rid: Set(rid),
+        path: Set(path.to_string_lossy().to_string()),
+        created_at: Set(crate::utils::time_format::now_standard_string()),
+        latest_row_at: Set("1970-01-01 00:00:00".to_string()),
+        enabled: Set(true),
+        scan_deleted_videos: Set(false),
+        scan_deleted_videos_once: Set(false),
+        ..Default::default()
+    })
+    .exec(connection)
+    .await?;
+
+    Ok((
+        ranking::Entity::find_by_id(result.last_insert_id)
+            .one(connection)
+            .await?
+            .context("ranking not found")?
+            .into(),
+        Box::pin(ranking.into_video_stream()),
+    ))
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
-use axum::extract::Request;
-use axum::http::HeaderMap;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use base64::prelude::BASE64_URL_SAFE_NO_PAD;
@@ -10,7 +10,11 @@ use reqwest::StatusCode;
 use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
 use utoipa::Modify;
 
-use crate::api::wrapper::ApiResponse;
+use crate::api::error::InnerApiError;
+use crate::api::wrapper::{ApiError, ApiResponse};
+use crate::auth::api_keys::{self, ApiScope};
+use crate::auth::stream_links::{self, StreamResource, StreamSignature};
+use crate::auth::users::{self, Identity, Role};
 
 fn is_public_video_cover_path(path: &str) -> bool {
     let Some(rest) = path.strip_prefix("/api/videos/") else {
@@ -22,79 +26,225 @@ fn is_public_video_cover_path(path: &str) -> bool {
     !video_id.is_empty() && video_id.chars().all(|ch| ch.is_ascii_digit())
 }
 
-pub async fn auth(headers: HeaderMap, request: Request, next: Next) -> Result<Response, StatusCode> {
-    // 排除不需要认证的路径
-    let path = request.uri().path();
-    tracing::debug!("认证中间件: 检查路径 {}", path);
+/// 不需要认证即可访问的路径前缀
+const EXCLUDED_PATH_PREFIXES: &[&str] = &[
+    "/api/search",                // 搜索API不需要认证
+    "/api/proxy/image",           // 图片代理不需要认证
+    "/api/setup/check",           // 初始设置检查不需要认证
+    "/api/setup/auth-token",      // 设置auth token不需要认证
+    "/api/videos/stream",         // 视频流API由处理函数校验登录身份或分享链接签名（供播放器使用）
+    "/api/videos/proxy-stream",   // 视频流代理API同样由处理函数校验登录身份或分享链接签名
+    "/api/auth/login",            // 用户登录不需要认证
+    "/api/auth/qr/generate",      // 生成登录二维码不需要认证
+    "/api/auth/qr/poll",          // 轮询登录状态不需要认证
+    "/api/auth/current-user",     // 获取当前用户信息不需要认证
+    "/api/auth/clear-credential", // 清除凭证不需要认证
+    "/api/test/risk-control",     // 测试风控API不需要认证
+    "/captcha",                   // 风控验证页面不需要认证
+    "/api/captcha/info",          // 获取验证码信息不需要认证（风控验证页面使用）
+    "/api/captcha/submit",        // 提交验证码结果不需要认证（风控验证页面使用）
+    "/api/ws",                    // WebSocket使用协议头认证，不使用Authorization头
+];
 
-    let excluded_paths = [
-        "/api/search",                // 搜索API不需要认证
-        "/api/proxy/image",           // 图片代理不需要认证
-        "/api/setup/check",           // 初始设置检查不需要认证
-        "/api/setup/auth-token",      // 设置auth token不需要认证
-        "/api/credential",            // 更新凭证在初始设置时不需要认证
-        "/api/videos/stream",         // 视频流API不需要认证（供播放器使用）
-        "/api/videos/proxy-stream",   // 视频流代理API不需要认证（供在线播放器使用）
-        "/api/auth/qr/generate",      // 生成登录二维码不需要认证
-        "/api/auth/qr/poll",          // 轮询登录状态不需要认证
-        "/api/auth/current-user",     // 获取当前用户信息不需要认证
-        "/api/auth/clear-credential", // 清除凭证不需要认证
-        "/api/test/risk-control",     // 测试风控API不需要认证
-        "/captcha",                   // 风控验证页面不需要认证
-        "/api/captcha/info",          // 获取验证码信息不需要认证（风控验证页面使用）
-        "/api/captcha/submit",        // 提交验证码结果不需要认证（风控验证页面使用）
-        "/api/ws",                    // WebSocket使用协议头认证，不使用Authorization头
-    ];
+/// 只按完整路径放行的接口（其子路径仍需认证）
+const EXCLUDED_EXACT_PATHS: &[&str] = &[
+    "/api/credential", // 更新凭证在初始设置时不需要认证
+];
 
-    let current_config = crate::config::reload_config();
-    let token = current_config.auth_token.as_deref().unwrap_or("");
+fn is_public_path(path: &str) -> bool {
+    EXCLUDED_EXACT_PATHS.contains(&path)
+        || EXCLUDED_PATH_PREFIXES
+            .iter()
+            .any(|&excluded| path.starts_with(excluded))
+        || is_public_video_cover_path(path)
+}
 
-    if (path.starts_with("/api/logs/stream")
+/// 从请求中提取候选令牌：Authorization 头、SSE 的 token 查询参数、WebSocket 协议头
+fn request_tokens(headers: &HeaderMap, request: &Request) -> Vec<String> {
+    let path = request.uri().path();
+    let mut tokens = Vec::new();
+
+    if path.starts_with("/api/logs/stream")
         || path.starts_with("/api/videos/live")
         || path.starts_with("/api/video-sources/live")
-        || path.starts_with("/api/queue/live"))
-        && request
+        || path.starts_with("/api/queue/live")
+    {
+        if let Some(query_token) = request
             .uri()
             .query()
             .and_then(|query| serde_urlencoded::from_str::<HashMap<String, String>>(query).ok())
-            .and_then(|params| params.get("token").cloned())
-            .is_some_and(|query_token| query_token == token)
-    {
-        return Ok(next.run(request).await);
+            .and_then(|mut params| params.remove("token"))
+        {
+            tokens.push(query_token);
+        }
     }
 
-    // 检查标准的Authorization头
-    if headers
-        .get("Authorization")
-        .and_then(|v| v.to_str().ok())
-        .is_some_and(|s| s == token)
-    {
-        return Ok(next.run(request).await);
+    // 检查标准的Authorization头，会话令牌允许带 Bearer 前缀
+    if let Some(value) = headers.get("Authorization").and_then(|v| v.to_str().ok()) {
+        tokens.push(value.to_string());
+        if let Some(bearer) = value.strip_prefix("Bearer ") {
+            tokens.push(bearer.to_string());
+        }
     }
 
     // 检查WebSocket协议头（用于WebSocket认证）
     if let Some(protocol) = headers.get("Sec-WebSocket-Protocol") {
         tracing::debug!("WebSocket协议头: {:?}", protocol);
-        if let Ok(protocol_str) = protocol.to_str() {
-            tracing::debug!("协议字符串: {}", protocol_str);
-            if let Ok(decoded) = BASE64_URL_SAFE_NO_PAD.decode(protocol_str) {
-                tracing::debug!("解码结果: {:?}, 期望: {:?}", decoded, token.as_bytes());
-                if decoded == token.as_bytes() {
-                    tracing::debug!("WebSocket认证成功");
-                    return Ok(next.run(request).await);
-                }
+        if let Some(decoded) = protocol
+            .to_str()
+            .ok()
+            .and_then(|protocol_str| BASE64_URL_SAFE_NO_PAD.decode(protocol_str).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+        {
+            tokens.push(decoded);
+        }
+    }
+
+    tokens
+}
+
+/// 依次用全局 auth_token 与用户会话校验候选令牌
+async fn resolve_identity(tokens: &[String]) -> Option<Identity> {
+    let current_config = crate::config::reload_config();
+    let auth_token = current_config.auth_token.as_deref().unwrap_or("");
+    if tokens.iter().any(|token| token == auth_token) {
+        return Some(Identity::legacy_token());
+    }
+
+    let db = crate::database::get_global_db()?;
+    for token in tokens {
+        if token.starts_with(api_keys::API_KEY_PREFIX) {
+            match api_keys::resolve_api_key(&db, token).await {
+                Ok(Some(identity)) => return Some(identity),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("校验 API 密钥失败: {:#}", e),
             }
+            continue;
+        }
+        match users::resolve_session(&db, token).await {
+            Ok(Some(identity)) => return Some(identity),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("校验登录会话失败: {:#}", e),
         }
     }
+    None
+}
+
+fn is_write_method(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+pub async fn auth(headers: HeaderMap, mut request: Request, next: Next) -> Result<Response, StatusCode> {
+    // 排除不需要认证的路径
+    let path = request.uri().path().to_string();
+    tracing::debug!("认证中间件: 检查路径 {}", path);
 
-    let needs_auth = path.starts_with("/api/")
-        && !excluded_paths.iter().any(|&excluded| path.starts_with(excluded))
-        && !is_public_video_cover_path(path);
+    let tokens = request_tokens(&headers, &request);
+    let identity = resolve_identity(&tokens).await;
 
-    if needs_auth {
-        return Ok(ApiResponse::unauthorized(()).into_response());
+    let Some(identity) = identity else {
+        if path.starts_with("/api/") && !is_public_path(&path) {
+            return Ok(ApiResponse::unauthorized(()).into_response());
+        }
+        return Ok(next.run(request).await);
+    };
+
+    let method = request.method().clone();
+    request.extensions_mut().insert(identity.clone());
+    let response = users::with_actor(identity.username.clone(), next.run(request)).await;
+
+    // 记录写操作审计日志
+    if path.starts_with("/api/") && is_write_method(&method) {
+        if let Some(db) = crate::database::get_global_db() {
+            let status_code = response.status().as_u16();
+            tokio::spawn(async move {
+                if let Err(e) = users::record_audit(&db, &identity, method.as_str(), &path, status_code).await {
+                    tracing::warn!("写入审计日志失败: {:#}", e);
+                }
+            });
+        }
     }
-    Ok(next.run(request).await)
+    Ok(response)
+}
+
+/// 路由分组的访问要求：用户需达到的角色，以及 API 密钥需具备的权限范围
+#[derive(Debug, Clone, Copy)]
+pub struct RouteAccess {
+    pub role: Role,
+    /// 为 None 时该分组不对 API 密钥开放
+    pub scope: Option<ApiScope>,
+}
+
+impl RouteAccess {
+    pub const fn new(role: Role, scope: Option<ApiScope>) -> Self {
+        Self { role, scope }
+    }
+}
+
+fn check_access(identity: &Identity, access: RouteAccess) -> Result<(), InnerApiError> {
+    if let Some(scopes) = &identity.scopes {
+        return match access.scope {
+            Some(required) if scopes.contains(&required) => Ok(()),
+            Some(required) => Err(InnerApiError::OutOfScope(format!(
+                "{} 缺少「{}」权限范围",
+                identity.username,
+                required.label()
+            ))),
+            None => Err(InnerApiError::OutOfScope(format!(
+                "{} 无法访问仅限管理员的接口",
+                identity.username
+            ))),
+        };
+    }
+    if identity.role < access.role {
+        return Err(InnerApiError::Forbidden(format!(
+            "用户「{}」的角色为{}，该操作需要{}权限",
+            identity.username,
+            identity.role.label(),
+            access.role.label()
+        )));
+    }
+    Ok(())
+}
+
+/// 按路由分组校验角色与 API 密钥权限范围，需配合 `middleware::from_fn_with_state` 作为 route_layer 使用
+pub async fn require_access(State(access): State<RouteAccess>, request: Request, next: Next) -> Response {
+    let Some(identity) = request.extensions().get::<Identity>() else {
+        return ApiResponse::unauthorized(()).into_response();
+    };
+    match check_access(identity, access) {
+        Ok(()) => next.run(request).await,
+        Err(e) => {
+            tracing::debug!("拒绝访问 {}: {}", request.uri().path(), e);
+            ApiError::from(e).into_response()
+        }
+    }
+}
+
+/// 视频源写接口对应的视频源；扫描类操作（如一次性扫描已删除视频）不改变设置，不受只读限制
+fn declarative_source_target(path: &str) -> Option<(&str, i32)> {
+    let rest = path
+        .strip_prefix("/api/video-sources/")
+        .or_else(|| path.strip_prefix("/api/v1/sources/"))?;
+    let mut segments = rest.split('/');
+    let source_type = segments.next()?;
+    let id = segments.next()?.parse().ok()?;
+    match segments.next() {
+        Some("scan-deleted-once" | "retry-charge-videos") => None,
+        _ => Some((source_type, id)),
+    }
+}
+
+/// 拒绝修改或删除由声明式配置文件管理的视频源
+pub async fn guard_declarative_sources(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        if let Some((source_type, id)) = declarative_source_target(request.uri().path()) {
+            if let Err(e) = crate::config::ensure_source_writable(source_type, id) {
+                return ApiError::from(e).into_response();
+            }
+        }
+    }
+    next.run(request).await
 }
 
 pub(super) struct OpenAPIAuth;
@@ -106,9 +256,93 @@ impl Modify for OpenAPIAuth {
                 "Token",
                 SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
                     "Authorization",
-                    "与配置文件中的 auth_token 相同",
+                    "与配置文件中的 auth_token 相同，或登录接口返回的会话令牌、API 密钥",
                 ))),
             );
         }
     }
 }
+
+/// 视频流接口的访问控制：已登录身份需具备只读权限，否则必须携带有效的分享链接签名
+pub fn authorize_stream(
+    identity: Option<&Identity>,
+    resource: StreamResource<'_>,
+    signature: &StreamSignature,
+) -> anyhow::Result<()> {
+    if let Some(identity) = identity {
+        return Ok(check_access(
+            identity,
+            RouteAccess::new(Role::Viewer, Some(ApiScope::ReadOnly)),
+        )?);
+    }
+    Ok(stream_links::verify_with_config(resource, signature)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key_identity(scopes: Vec<ApiScope>) -> Identity {
+        Identity {
+            user_id: None,
+            username: "api_key:homeassistant".to_string(),
+            role: Role::Operator,
+            scopes: Some(scopes),
+        }
+    }
+
+    #[test]
+    fn api_keys_are_limited_to_their_scopes() {
+        let identity = api_key_identity(vec![ApiScope::QueueControl]);
+        assert!(check_access(
+            &identity,
+            RouteAccess::new(Role::Operator, Some(ApiScope::QueueControl))
+        )
+        .is_ok());
+        assert!(matches!(
+            check_access(&identity, RouteAccess::new(Role::Viewer, Some(ApiScope::ReadOnly))),
+            Err(InnerApiError::OutOfScope(_))
+        ));
+        assert!(matches!(
+            check_access(&identity, RouteAccess::new(Role::Admin, None)),
+            Err(InnerApiError::OutOfScope(_))
+        ));
+    }
+
+    #[test]
+    fn users_are_checked_by_role() {
+        let mut identity = Identity::legacy_token();
+        assert!(check_access(&identity, RouteAccess::new(Role::Admin, None)).is_ok());
+        identity.role = Role::Viewer;
+        assert!(check_access(&identity, RouteAccess::new(Role::Viewer, Some(ApiScope::ReadOnly))).is_ok());
+        assert!(matches!(
+            check_access(
+                &identity,
+                RouteAccess::new(Role::Operator, Some(ApiScope::QueueControl))
+            ),
+            Err(InnerApiError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn declarative_guard_targets_source_writes() {
+        assert_eq!(
+            declarative_source_target("/api/video-sources/favorite/3/enabled"),
+            Some(("favorite", 3))
+        );
+        assert_eq!(
+            declarative_source_target("/api/video-sources/submission/7"),
+            Some(("submission", 7))
+        );
+        assert_eq!(
+            declarative_source_target("/api/video-sources/bangumi/2/scan-deleted-once"),
+            None
+        );
+        assert_eq!(
+            declarative_source_target("/api/v1/sources/ranking/4/enabled"),
+            Some(("ranking", 4))
+        );
+        assert_eq!(declarative_source_target("/api/video-sources/preview"), None);
+        assert_eq!(declarative_source_target("/api/video-sources"), None);
+    }
+}
@@ -1,4 +1,19 @@
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+/// 错误响应中的机器可读错误码
+#[derive(Serialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Unauthorized,
+    Forbidden,
+    OutOfScope,
+    NotFound,
+    BadRequest,
+    DatabaseError,
+    Internal,
+}
 
 #[derive(Error, Debug)]
 pub enum InnerApiError {
@@ -6,6 +21,22 @@ pub enum InnerApiError {
     NotFound(i32),
     #[error("Bad request: {0}")]
     BadRequest(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Out of scope: {0}")]
+    OutOfScope(String),
     #[error("Database error: {0}")]
     DatabaseError(#[from] sea_orm::DbErr),
 }
+
+impl InnerApiError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            InnerApiError::NotFound(_) => ErrorCode::NotFound,
+            InnerApiError::BadRequest(_) => ErrorCode::BadRequest,
+            InnerApiError::Forbidden(_) => ErrorCode::Forbidden,
+            InnerApiError::OutOfScope(_) => ErrorCode::OutOfScope,
+            InnerApiError::DatabaseError(_) => ErrorCode::DatabaseError,
+        }
+    }
+}
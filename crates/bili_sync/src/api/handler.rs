@@ -13,12 +13,12 @@ use html_escape::decode_html_entities;
 
 use crate::http::headers::{create_api_headers, create_image_headers};
 use crate::utils::time_format::{now_standard_string, to_standard_string};
-use bili_sync_entity::{collection, favorite, page, submission, video, video_source, watch_later};
+use bili_sync_entity::{collection, favorite, page, ranking, submission, video, video_source, watch_later};
 use bili_sync_migration::Expr;
 use reqwest;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, FromQueryResult, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set, Statement, Unchanged,
+    QueryFilter, QueryOrder, QuerySelect, Set, Unchanged,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -79,19 +79,79 @@ fn ai_subtitle_language_from_request(language: &Option<String>, fallback: &str)
 fn resolve_source_filter_option_update(
     current: Option<serde_json::Value>,
     requested: &Option<Option<FilterOption>>,
+    requested_profile: &Option<Option<String>>,
 ) -> Result<Option<serde_json::Value>, ApiError> {
+    // 画质档案与自定义流过滤互斥，同时提供时以画质档案为准；空字符串表示取消引用档案
+    let requested_profile = requested_profile
+        .as_ref()
+        .map(|profile| profile.as_deref().map(str::trim).filter(|profile| !profile.is_empty()));
+    if let Some(Some(profile)) = requested_profile {
+        return source_quality_profile_to_json(profile).map(Some);
+    }
     match requested {
-        Some(Some(filter_option)) => Ok(Some(serde_json::to_value(filter_option)?)),
+        Some(Some(filter_option)) => {
+            filter_option.validate().map_err(ApiError::from)?;
+            Ok(Some(serde_json::to_value(filter_option)?))
+        }
         Some(None) => Ok(None),
+        None if requested_profile.is_some() && source_quality_profile_from_json(&current).is_some() => Ok(None),
         None => Ok(current),
     }
 }
 
 fn source_filter_option_to_response(value: Option<serde_json::Value>) -> Result<Option<FilterOption>, ApiError> {
-    value.map(serde_json::from_value).transpose().map_err(ApiError::from)
+    match value
+        .as_ref()
+        .map(crate::config::SourceFilterOption::from_json)
+        .transpose()?
+    {
+        Some(crate::config::SourceFilterOption::Custom(filter_option)) => Ok(Some(filter_option)),
+        _ => Ok(None),
+    }
+}
+
+/// 视频源引用的画质档案名称；未引用档案时返回 None
+fn source_quality_profile_from_json(value: &Option<serde_json::Value>) -> Option<String> {
+    value
+        .as_ref()
+        .and_then(|value| crate::config::SourceFilterOption::from_json(value).ok())
+        .and_then(|setting| setting.profile_name().map(str::to_string))
+}
+
+fn source_quality_profile_to_json(profile: &str) -> Result<serde_json::Value, ApiError> {
+    let profile = profile.trim();
+    if !crate::config::reload_config()
+        .quality_profiles
+        .iter()
+        .any(|item| item.name == profile)
+    {
+        return Err(anyhow!("画质档案「{}」不存在", profile).into());
+    }
+    Ok(serde_json::to_value(crate::config::SourceFilterOption::Profile {
+        profile: profile.to_string(),
+    })?)
+}
+
+/// 校验视频源绑定的B站账号是否存在，`None` 表示使用默认账号
+fn validate_source_credential_id(credential_id: Option<i32>) -> Result<Option<i32>, ApiError> {
+    if let Some(id) = credential_id {
+        if crate::config::reload_config().bili_account(id).is_none() {
+            return Err(anyhow!("B站账号 {} 不存在", id).into());
+        }
+    }
+    Ok(credential_id)
 }
 
-fn source_filter_option_to_json(value: &Option<FilterOption>) -> Result<Option<serde_json::Value>, ApiError> {
+fn source_filter_option_to_json(
+    value: &Option<FilterOption>,
+    profile: &Option<String>,
+) -> Result<Option<serde_json::Value>, ApiError> {
+    if let Some(profile) = profile.as_deref().filter(|profile| !profile.trim().is_empty()) {
+        return source_quality_profile_to_json(profile).map(Some);
+    }
+    if let Some(filter_option) = value {
+        filter_option.validate().map_err(ApiError::from)?;
+    }
     value
         .as_ref()
         .map(serde_json::to_value)
@@ -124,6 +184,7 @@ struct SourceChargeVisibilityFilters {
     favorite: Option<i32>,
     submission: Option<i32>,
     watch_later: Option<i32>,
+    ranking: Option<i32>,
     bangumi: Option<i32>,
 }
 
@@ -134,6 +195,7 @@ impl From<&VideosRequest> for SourceChargeVisibilityFilters {
             favorite: params.favorite,
             submission: params.submission,
             watch_later: params.watch_later,
+            ranking: params.ranking,
             bangumi: params.bangumi,
         }
     }
@@ -146,6 +208,7 @@ impl From<&ResetSpecificTasksRequest> for SourceChargeVisibilityFilters {
             favorite: params.favorite,
             submission: params.submission,
             watch_later: params.watch_later,
+            ranking: params.ranking,
             bangumi: params.bangumi,
         }
     }
@@ -157,6 +220,7 @@ impl SourceChargeVisibilityFilters {
             || self.favorite.is_some()
             || self.submission.is_some()
             || self.watch_later.is_some()
+            || self.ranking.is_some()
             || self.bangumi.is_some()
     }
 }
@@ -199,6 +263,14 @@ async fn source_download_charge_videos_enabled(
                 .one(db)
                 .await?
         }
+        "ranking" => {
+            ranking::Entity::find_by_id(source_id)
+                .select_only()
+                .column(ranking::Column::DownloadChargeVideos)
+                .into_tuple::<bool>()
+                .one(db)
+                .await?
+        }
         "bangumi" => {
             video_source::Entity::find_by_id(source_id)
                 .select_only()
@@ -226,6 +298,7 @@ async fn should_hide_charge_videos_for_source_filters(
         ("favorite", filters.favorite),
         ("submission", filters.submission),
         ("watch_later", filters.watch_later),
+        ("ranking", filters.ranking),
     ] {
         if let Some(id) = id {
             if !source_download_charge_videos_enabled(db, source_type, id).await? {
@@ -263,6 +336,11 @@ fn visible_charge_video_source_expr() -> sea_orm::sea_query::SimpleExpr {
                 WHERE source_watch_later.id = video.watch_later_id
                   AND source_watch_later.download_charge_videos = 0
             ))
+            OR (ranking_id IS NOT NULL AND NOT EXISTS (
+                SELECT 1 FROM ranking source_ranking
+                WHERE source_ranking.id = video.ranking_id
+                  AND source_ranking.download_charge_videos = 0
+            ))
             OR (source_type = 1 AND source_id IS NOT NULL AND NOT EXISTS (
                 SELECT 1 FROM video_source source_bangumi
                 WHERE source_bangumi.id = video.source_id
@@ -273,6 +351,7 @@ fn visible_charge_video_source_expr() -> sea_orm::sea_query::SimpleExpr {
                 AND favorite_id IS NULL
                 AND submission_id IS NULL
                 AND watch_later_id IS NULL
+                AND ranking_id IS NULL
                 AND (source_type IS NULL OR source_type <> 1 OR source_id IS NULL)
             )
         )
@@ -873,6 +952,12 @@ async fn collect_video_source_base_paths(
         }
     }
 
+    if let Some(ranking_id) = video.ranking_id {
+        if let Some(ranking) = ranking::Entity::find_by_id(ranking_id).one(conn).await? {
+            push_path(ranking.path);
+        }
+    }
+
     if let Some(source_id) = video.source_id {
         if let Some(source) = video_source::Entity::find_by_id(source_id).one(conn).await? {
             push_path(source.path);
@@ -1037,6 +1122,7 @@ mod rename_tests {
             favorite_id: Set(None),
             watch_later_id: Set(None),
             submission_id: Set(None),
+            ranking_id: Set(None),
             source_id: Set(None),
             source_type: Set(None),
             upper_id: Set(1000),
@@ -1103,6 +1189,7 @@ mod rename_tests {
             danmaku_cid_snapshot: Set(None),
             danmaku_last_write_count: Set(0),
             ai_renamed: sea_orm::ActiveValue::NotSet,
+            quality_checked_at: Set(None),
         }
         .insert(db.as_ref())
         .await
@@ -1282,6 +1369,7 @@ mod reset_path_tests {
             favorite_id: None,
             watch_later_id: Some(1),
             submission_id: None,
+            ranking_id: None,
             source_id: None,
             source_type: None,
             upper_id: 1000,
@@ -1347,6 +1435,7 @@ mod reset_path_tests {
             danmaku_cid_snapshot: None,
             danmaku_last_write_count: 0,
             ai_renamed: None,
+            quality_checked_at: None,
         }
     }
 
@@ -1414,7 +1503,9 @@ mod queue_sse_tests {
     use axum::Router;
     use bili_sync_migration::{Migrator, MigratorTrait};
     use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
-    use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseBackend, Set, SqlxSqliteConnector, Statement};
+    use sea_orm::{
+        ActiveModelTrait, ConnectionTrait, DatabaseBackend, IntoActiveModel, Set, SqlxSqliteConnector, Statement,
+    };
     use serde_json::Value;
     use std::fs;
     use std::path::PathBuf;
@@ -1494,6 +1585,7 @@ mod queue_sse_tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             selected_videos: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
@@ -1510,6 +1602,7 @@ mod queue_sse_tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -1545,6 +1638,7 @@ mod queue_sse_tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             cover: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
@@ -1564,6 +1658,7 @@ mod queue_sse_tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -1592,6 +1687,7 @@ mod queue_sse_tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
             blacklist_keywords: Set(None),
@@ -1607,6 +1703,7 @@ mod queue_sse_tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -1633,6 +1730,7 @@ mod queue_sse_tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
             blacklist_keywords: Set(None),
@@ -1648,6 +1746,7 @@ mod queue_sse_tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -1686,6 +1785,7 @@ mod queue_sse_tests {
             favorite_id: Set(None),
             watch_later_id: Set(None),
             submission_id: Set(Some(1)),
+            ranking_id: Set(None),
             source_id: Set(None),
             source_type: Set(Some(4)),
             upper_id: Set(2000 + i64::from(id)),
@@ -1787,6 +1887,7 @@ mod queue_sse_tests {
             danmaku_cid_snapshot: Set(None),
             danmaku_last_write_count: Set(0),
             ai_renamed: sea_orm::ActiveValue::NotSet,
+            quality_checked_at: Set(None),
         }
         .insert(db)
         .await
@@ -1814,6 +1915,7 @@ mod queue_sse_tests {
             favorite_id: Set(favorite_id),
             watch_later_id: Set(watch_later_id),
             submission_id: Set(submission_id),
+            ranking_id: Set((source_type == "ranking").then_some(source_id)),
             source_id: Set(None),
             source_type: Set(Some(4)),
             upper_id: Set(2000 + i64::from(id)),
@@ -1884,6 +1986,7 @@ mod queue_sse_tests {
             danmaku_cid_snapshot: Set(None),
             danmaku_last_write_count: Set(0),
             ai_renamed: sea_orm::ActiveValue::NotSet,
+            quality_checked_at: Set(None),
         }
         .insert(db)
         .await
@@ -1895,6 +1998,17 @@ mod queue_sse_tests {
         insert_retry_charge_test_collection(db.as_ref(), 1, "测试合集源").await;
         insert_retry_charge_test_favorite(db.as_ref(), 1, "测试收藏夹源").await;
         insert_retry_charge_test_watch_later(db.as_ref(), 1).await;
+        ranking::Model {
+            id: 1,
+            name: "排行榜·全站（近三日）".to_string(),
+            ranking_type: "rank".to_string(),
+            path: "/tmp/ranking".to_string(),
+            ..Default::default()
+        }
+        .into_active_model()
+        .insert(db.as_ref())
+        .await
+        .expect("应能插入测试榜单源");
         insert_test_submission(db.as_ref(), 1, "测试投稿源一").await;
         insert_test_submission(db.as_ref(), 2, "测试投稿源二").await;
 
@@ -1905,6 +2019,7 @@ mod queue_sse_tests {
         insert_retry_charge_test_video(db.as_ref(), 5, "collection", 1, true, true).await;
         insert_retry_charge_test_video(db.as_ref(), 6, "favorite", 1, true, true).await;
         insert_retry_charge_test_video(db.as_ref(), 7, "watch_later", 1, true, true).await;
+        insert_retry_charge_test_video(db.as_ref(), 8, "ranking", 1, true, true).await;
         insert_retry_charge_test_page(db.as_ref(), 1, 1).await;
         insert_retry_charge_test_page(db.as_ref(), 2, 2).await;
         insert_retry_charge_test_page(db.as_ref(), 3, 3).await;
@@ -1912,6 +2027,7 @@ mod queue_sse_tests {
         insert_retry_charge_test_page(db.as_ref(), 5, 5).await;
         insert_retry_charge_test_page(db.as_ref(), 6, 6).await;
         insert_retry_charge_test_page(db.as_ref(), 7, 7).await;
+        insert_retry_charge_test_page(db.as_ref(), 8, 8).await;
 
         db
     }
@@ -2077,6 +2193,7 @@ mod queue_sse_tests {
                 favorite: None,
                 submission: Some(1),
                 watch_later: None,
+                ranking: None,
                 bangumi: None,
                 query: None,
                 page: Some(0),
@@ -2308,6 +2425,7 @@ mod queue_sse_tests {
             ("favorite", 6, 6),
             ("submission", 1, 1),
             ("watch_later", 7, 7),
+            ("ranking", 8, 8),
         ] {
             let db = setup_retry_charge_video_test_db().await;
 
@@ -2368,7 +2486,7 @@ mod queue_sse_tests {
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(get_video_sources, get_videos, get_video, get_video_local_cover, refresh_video_danmaku, refresh_page_danmaku, reset_video, reset_all_videos, reset_specific_tasks, update_video_status, add_video_source, update_video_source_enabled, update_video_source_scan_deleted, update_video_source_scan_deleted_once, retry_charge_videos_for_source, reset_video_source_path, delete_video_source, reload_config, get_config, update_config, preview_filename_templates, get_bangumi_seasons, search_bilibili, get_user_favorites, get_user_collections, get_user_followings, get_subscribed_collections, get_submission_videos, get_logs, get_queue_status, cancel_queue_task, proxy_image, get_config_item, get_config_history, get_config_migration_status, migrate_config_schema, validate_config, get_hot_reload_status, check_initial_setup, setup_auth_token, update_credential, test_credential_refresh, generate_qr_code, poll_qr_status, get_current_user, clear_credential, pause_scanning_endpoint, resume_scanning_endpoint, get_task_control_status, get_video_play_info, proxy_video_stream, validate_favorite, get_user_favorites_by_uid, get_latest_ingests, get_recent_ingests, test_notification_handler, get_notification_config, update_notification_config, get_notification_status, test_risk_control_handler, get_beta_image_update_status),
+    paths(get_video_sources, get_videos, get_video, get_video_local_cover, refresh_video_danmaku, refresh_page_danmaku, get_page_danmaku_highlights, rerender_danmaku, reset_video, reset_all_videos, reset_specific_tasks, update_video_status, add_video_source, update_video_source_enabled, update_video_source_scan_deleted, update_video_source_scan_deleted_once, update_video_source_credential, retry_charge_videos_for_source, reset_video_source_path, delete_video_source, reload_config, get_config, update_config, preview_filename_templates, get_bangumi_seasons, search_bilibili, get_user_favorites, get_user_collections, get_user_followings, get_subscribed_collections, get_submission_videos, get_logs, get_queue_status, cancel_queue_task, proxy_image, get_config_item, get_config_history, get_config_migration_status, migrate_config_schema, validate_config, get_hot_reload_status, check_initial_setup, setup_auth_token, update_credential, get_bili_accounts, add_bili_account, update_bili_account, delete_bili_account, test_credential_refresh, login, logout, get_current_identity, get_api_users, add_api_user, update_api_user, delete_api_user, get_api_keys, add_api_key, revoke_api_key, create_stream_link, get_audit_log, get_events, get_webhooks, add_webhook, update_webhook, delete_webhook, get_webhook_deliveries, retry_webhook_delivery, get_database_backups, create_database_backup, restore_database_backup, delete_database_backup, export_bundle, import_bundle, get_declarative_config, generate_qr_code, poll_qr_status, get_current_user, clear_credential, pause_scanning_endpoint, resume_scanning_endpoint, get_task_control_status, get_video_play_info, proxy_video_stream, validate_favorite, get_user_favorites_by_uid, get_latest_ingests, get_recent_ingests, test_notification_handler, get_notification_config, update_notification_config, get_notification_status, get_quality_profiles, update_quality_profiles, dry_run_quality_profile, preview_video_source, test_risk_control_handler, get_beta_image_update_status),
     modifiers(&OpenAPIAuth),
     security(
         ("Token" = []),
@@ -2507,6 +2625,8 @@ pub async fn get_video_sources(
                 latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
                 scan_deleted_videos: model.scan_deleted_videos,
                 scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
                 filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
                 f_id: None,
                 s_id: Some(model.s_id),
@@ -2533,6 +2653,7 @@ pub async fn get_video_sources(
                 split_chapters_after_download: model.split_chapters_after_download,
                 download_charge_videos: model.download_charge_videos,
                 download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
                 download_subtitle: model.download_subtitle,
                 download_ai_subtitle: model.download_ai_subtitle,
                 ai_subtitle_language: model.ai_subtitle_language,
@@ -2544,6 +2665,8 @@ pub async fn get_video_sources(
                 ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
                 ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
                 use_dynamic_api: None,
+                ranking_type: None,
+                rid: None,
             }
         })
         .collect();
@@ -2573,6 +2696,8 @@ pub async fn get_video_sources(
                 latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
                 scan_deleted_videos: model.scan_deleted_videos,
                 scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
                 filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
                 f_id: Some(model.f_id),
                 s_id: None,
@@ -2599,6 +2724,7 @@ pub async fn get_video_sources(
                 split_chapters_after_download: model.split_chapters_after_download,
                 download_charge_videos: model.download_charge_videos,
                 download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
                 download_subtitle: model.download_subtitle,
                 download_ai_subtitle: model.download_ai_subtitle,
                 ai_subtitle_language: model.ai_subtitle_language,
@@ -2610,6 +2736,8 @@ pub async fn get_video_sources(
                 ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
                 ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
                 use_dynamic_api: None,
+                ranking_type: None,
+                rid: None,
             }
         })
         .collect();
@@ -2639,6 +2767,8 @@ pub async fn get_video_sources(
                 latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
                 scan_deleted_videos: model.scan_deleted_videos,
                 scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
                 filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
                 f_id: None,
                 s_id: None,
@@ -2665,6 +2795,7 @@ pub async fn get_video_sources(
                 split_chapters_after_download: model.split_chapters_after_download,
                 download_charge_videos: model.download_charge_videos,
                 download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
                 download_subtitle: model.download_subtitle,
                 download_ai_subtitle: model.download_ai_subtitle,
                 ai_subtitle_language: model.ai_subtitle_language,
@@ -2676,6 +2807,8 @@ pub async fn get_video_sources(
                 ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
                 ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
                 use_dynamic_api: Some(model.use_dynamic_api),
+                ranking_type: None,
+                rid: None,
             }
         })
         .collect();
@@ -2705,6 +2838,79 @@ pub async fn get_video_sources(
                 latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
                 scan_deleted_videos: model.scan_deleted_videos,
                 scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
+                filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
+                f_id: None,
+                s_id: None,
+                m_id: None,
+                collection_type: None,
+                collection_aggregate_enabled: false,
+                collection_aggregate_season_number: None,
+                upper_id: None,
+                season_id: None,
+                media_id: None,
+                selected_seasons: None,
+                blacklist_keywords,
+                whitelist_keywords,
+                case_sensitive: model.keyword_case_sensitive,
+                min_duration_seconds: model.min_duration_seconds,
+                max_duration_seconds: model.max_duration_seconds,
+                published_after: model.published_after,
+                published_before: model.published_before,
+                keyword_filters,
+                keyword_filter_mode: model.keyword_filter_mode,
+                audio_only: model.audio_only,
+                audio_only_m4a_only: model.audio_only_m4a_only,
+                flat_folder: model.flat_folder,
+                split_chapters_after_download: model.split_chapters_after_download,
+                download_charge_videos: model.download_charge_videos,
+                download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
+                download_subtitle: model.download_subtitle,
+                download_ai_subtitle: model.download_ai_subtitle,
+                ai_subtitle_language: model.ai_subtitle_language,
+                ai_rename: model.ai_rename,
+                ai_rename_video_prompt: model.ai_rename_video_prompt,
+                ai_rename_audio_prompt: model.ai_rename_audio_prompt,
+                ai_rename_enable_multi_page: model.ai_rename_enable_multi_page,
+                ai_rename_enable_collection: model.ai_rename_enable_collection,
+                ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
+                ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
+                use_dynamic_api: None,
+                ranking_type: None,
+                rid: None,
+            }
+        })
+        .collect();
+
+    let ranking_sources: Vec<VideoSource> = ranking::Entity::find()
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .map(|model| {
+            let keyword_filters = model
+                .keyword_filters
+                .as_ref()
+                .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok());
+            let blacklist_keywords = model
+                .blacklist_keywords
+                .as_ref()
+                .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok());
+            let whitelist_keywords = model
+                .whitelist_keywords
+                .as_ref()
+                .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok());
+            VideoSource {
+                id: model.id,
+                name: model.name,
+                enabled: model.enabled,
+                path: model.path,
+                latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
+                scan_deleted_videos: model.scan_deleted_videos,
+                scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
                 filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
                 f_id: None,
                 s_id: None,
@@ -2731,6 +2937,7 @@ pub async fn get_video_sources(
                 split_chapters_after_download: model.split_chapters_after_download,
                 download_charge_videos: model.download_charge_videos,
                 download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
                 download_subtitle: model.download_subtitle,
                 download_ai_subtitle: model.download_ai_subtitle,
                 ai_subtitle_language: model.ai_subtitle_language,
@@ -2742,6 +2949,8 @@ pub async fn get_video_sources(
                 ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
                 ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
                 use_dynamic_api: None,
+                ranking_type: Some(model.ranking_type),
+                rid: Some(model.rid),
             }
         })
         .collect();
@@ -2790,6 +2999,8 @@ pub async fn get_video_sources(
                 latest_row_at: normalize_video_source_latest_row_at(&model.latest_row_at),
                 scan_deleted_videos: model.scan_deleted_videos,
                 scan_deleted_videos_once: model.scan_deleted_videos_once,
+                quality_profile: source_quality_profile_from_json(&model.filter_option),
+                credential_id: model.credential_id,
                 filter_option: model.filter_option.and_then(|value| serde_json::from_value(value).ok()),
                 f_id: None,
                 s_id: None,
@@ -2816,6 +3027,7 @@ pub async fn get_video_sources(
                 split_chapters_after_download: model.split_chapters_after_download,
                 download_charge_videos: model.download_charge_videos,
                 download_danmaku: model.download_danmaku,
+                archive_comments: model.archive_comments,
                 download_subtitle: model.download_subtitle,
                 download_ai_subtitle: model.download_ai_subtitle,
                 ai_subtitle_language: model.ai_subtitle_language,
@@ -2827,6 +3039,8 @@ pub async fn get_video_sources(
                 ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
                 ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
                 use_dynamic_api: None,
+                ranking_type: None,
+                rid: None,
             }
         })
         .collect();
@@ -2837,6 +3051,7 @@ pub async fn get_video_sources(
         favorite: favorite_sources,
         submission: submission_sources,
         watch_later: watch_later_sources,
+        ranking: ranking_sources,
         bangumi: bangumi_sources,
     }))
 }
@@ -2951,6 +3166,7 @@ pub async fn get_videos(
             (params.favorite, video::Column::FavoriteId),
             (params.submission, video::Column::SubmissionId),
             (params.watch_later, video::Column::WatchLaterId),
+            (params.ranking, video::Column::RankingId),
         ] {
             if let Some(id) = field {
                 query = query.filter(column.eq(id));
@@ -2987,7 +3203,8 @@ pub async fn get_videos(
             // 提取第offset个子任务状态: (download_status >> shift) & 7
             // 检查是否为失败状态: >= 1 AND <= 6
             conditions.push(Expr::cust(format!(
-                "((download_status >> {}) & 7) BETWEEN 1 AND 6",
+                "(({} >> {}) & 7) BETWEEN 1 AND 6",
+                crate::database::dialect::current().unsigned_column("download_status"),
                 shift
             )));
         }
@@ -3596,6 +3813,31 @@ async fn resolve_video_source_tag(db: &DatabaseConnection, video: &video::Model)
         )));
     }
 
+    if let Some(source_id) = video.ranking_id {
+        let source = ranking::Entity::find_by_id(source_id).one(db).await?;
+        let (source_name, split_chapters_after_download, audio_only, audio_only_m4a_only, flat_folder) = source
+            .map(|source| {
+                (
+                    source.name,
+                    source.split_chapters_after_download,
+                    source.audio_only,
+                    source.audio_only_m4a_only,
+                    source.flat_folder,
+                )
+            })
+            .unwrap_or_else(|| (format!("已删除榜单源 #{}", source_id), false, false, false, false));
+        return Ok(Some(build_video_source_tag(
+            source_id,
+            "ranking",
+            "榜单",
+            source_name,
+            split_chapters_after_download,
+            audio_only,
+            audio_only_m4a_only,
+            flat_folder,
+        )));
+    }
+
     Ok(None)
 }
 
@@ -3795,6 +4037,113 @@ pub async fn refresh_page_danmaku(
     }))
 }
 
+/// 获取分页的弹幕密度直方图与高能时刻
+#[utoipa::path(
+    get,
+    path = "/api/pages/{id}/danmaku/highlights",
+    params(
+        ("id" = i32, Path, description = "Page ID"),
+        crate::api::request::DanmakuHighlightsRequest,
+    ),
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::DanmakuHighlightsResponse>),
+        (status = 404, description = "分页不存在"),
+    )
+)]
+pub async fn get_page_danmaku_highlights(
+    Path(id): Path<i32>,
+    Query(params): Query<crate::api::request::DanmakuHighlightsRequest>,
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::DanmakuHighlightsResponse>, ApiError> {
+    let Some(page_model) = page::Entity::find_by_id(id).one(db.as_ref()).await? else {
+        return Err(InnerApiError::NotFound(id).into());
+    };
+    let option = crate::config::reload_config().danmaku_highlight;
+    let top = params.top.unwrap_or(option.chapter_count).clamp(1, 50);
+    let min_gap_seconds = params.min_gap_seconds.unwrap_or(option.min_gap_seconds);
+
+    let response = match crate::workflow_highlight::load_danmaku_histogram(db.as_ref(), id).await? {
+        Some((model, histogram, chapters)) => crate::api::response::DanmakuHighlightsResponse {
+            page_id: id,
+            video_id: page_model.video_id,
+            bucket_seconds: histogram.bucket_seconds,
+            total: histogram.total(),
+            peaks: histogram.peaks(top, min_gap_seconds),
+            histogram: histogram.counts,
+            chapters,
+            updated_at: Some(model.updated_at),
+        },
+        None => crate::api::response::DanmakuHighlightsResponse {
+            page_id: id,
+            video_id: page_model.video_id,
+            bucket_seconds: option.bucket_seconds,
+            total: 0,
+            histogram: Vec::new(),
+            peaks: Vec::new(),
+            chapters: Vec::new(),
+            updated_at: None,
+        },
+    };
+    Ok(ApiResponse::ok(response))
+}
+
+/// 用归档的原始弹幕和当前弹幕样式重新生成 ASS，可限定视频源或处理整个媒体库
+#[utoipa::path(
+    post,
+    path = "/api/danmaku/rerender",
+    request_body = crate::api::request::RerenderDanmakuRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::RerenderDanmakuResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+    )
+)]
+pub async fn rerender_danmaku(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::RerenderDanmakuRequest>,
+) -> Result<ApiResponse<crate::api::response::RerenderDanmakuResponse>, ApiError> {
+    match (&params.source_type, params.source_id) {
+        (Some(source_type), Some(source_id)) => {
+            crate::api::v1::video_source_condition(source_type, source_id)?;
+        }
+        (None, None) => {}
+        _ => {
+            return Err(InnerApiError::BadRequest("source_type 与 source_id 必须同时指定".to_string()).into());
+        }
+    }
+
+    let task = crate::task::RerenderDanmakuTask {
+        source_type: params.source_type,
+        source_id: params.source_id,
+        task_id: uuid::Uuid::new_v4().to_string(),
+    };
+    let description = task.description();
+    let task_id = task.task_id.clone();
+    let created = crate::task::enqueue_rerender_danmaku_task(task, db.as_ref()).await?;
+
+    let message = if crate::task::is_scanning() {
+        format!("已加入队列：{}，将在本轮扫描结束后执行", description)
+    } else {
+        // 不在扫描时立即在后台执行，进度可在队列状态中查看
+        let db = db.clone();
+        tokio::spawn(async move {
+            if let Err(e) = crate::task::process_rerender_danmaku_tasks(db).await {
+                error!("处理弹幕重新渲染任务队列失败: {:#}", e);
+            }
+        });
+        format!("已开始：{}", description)
+    };
+
+    Ok(ApiResponse::ok(crate::api::response::RerenderDanmakuResponse {
+        success: true,
+        task_id: created.then_some(task_id),
+        message: if created {
+            message
+        } else {
+            format!("{}的任务已在队列中", description)
+        },
+    }))
+}
+
 /// 重置视频的下载状态
 #[utoipa::path(
     post,
@@ -3954,6 +4303,7 @@ pub async fn reset_video(
         ("submission" = Option<i32>, Query, description = "UP主投稿ID"),
         ("bangumi" = Option<i32>, Query, description = "番剧ID"),
         ("watch_later" = Option<i32>, Query, description = "稍后观看ID"),
+        ("ranking" = Option<i32>, Query, description = "榜单ID"),
     ),
     responses(
         (status = 200, body = ApiResponse<ResetAllVideosResponse>),
@@ -3985,6 +4335,7 @@ pub async fn reset_all_videos(
             (params.favorite, video::Column::FavoriteId),
             (params.submission, video::Column::SubmissionId),
             (params.watch_later, video::Column::WatchLaterId),
+            (params.ranking, video::Column::RankingId),
         ] {
             if let Some(id) = field {
                 video_query = video_query.filter(column.eq(id));
@@ -4014,7 +4365,8 @@ pub async fn reset_all_videos(
         for offset in 0..5 {
             let shift = offset * 3;
             conditions.push(Expr::cust(format!(
-                "((download_status >> {}) & 7) BETWEEN 1 AND 6",
+                "(({} >> {}) & 7) BETWEEN 1 AND 6",
+                crate::database::dialect::current().unsigned_column("download_status"),
                 shift
             )));
         }
@@ -4277,6 +4629,7 @@ pub async fn reset_specific_tasks(
             (request.favorite, video::Column::FavoriteId),
             (request.submission, video::Column::SubmissionId),
             (request.watch_later, video::Column::WatchLaterId),
+            (request.ranking, video::Column::RankingId),
         ] {
             if let Some(id) = field {
                 video_query = video_query.filter(column.eq(id));
@@ -4306,7 +4659,8 @@ pub async fn reset_specific_tasks(
         for offset in 0..5 {
             let shift = offset * 3;
             conditions.push(Expr::cust(format!(
-                "((download_status >> {}) & 7) BETWEEN 1 AND 6",
+                "(({} >> {}) & 7) BETWEEN 1 AND 6",
+                crate::database::dialect::current().unsigned_column("download_status"),
                 shift
             )));
         }
@@ -4899,7 +5253,10 @@ pub async fn add_video_source(
             up_id: params.up_id.clone(),
             collection_type: params.collection_type.clone(),
             collection_aggregate_enabled: params.collection_aggregate_enabled,
+            ranking_type: params.ranking_type.clone(),
             filter_option: params.filter_option.clone(),
+            quality_profile: params.quality_profile.clone(),
+            credential_id: params.credential_id,
             download_charge_videos: params.download_charge_videos,
             media_id: params.media_id.clone(),
             ep_id: params.ep_id.clone(),
@@ -4940,7 +5297,8 @@ pub async fn add_video_source_internal(
     let txn = crate::database::begin_traced_transaction(&db, "api.handler.add_video_source").await?;
     let ai_subtitle_language =
         ai_subtitle_language_from_request(&params.ai_subtitle_language, DEFAULT_AI_SUBTITLE_LANGUAGE);
-    let source_filter_option = source_filter_option_to_json(&params.filter_option)?;
+    let source_filter_option = source_filter_option_to_json(&params.filter_option, &params.quality_profile)?;
+    let credential_id = validate_source_credential_id(params.credential_id)?;
 
     let result = match params.source_type.as_str() {
         "collection" => {
@@ -5009,7 +5367,7 @@ pub async fn add_video_source_internal(
                             )
                         })
                         .unwrap_or_default();
-                    let client = crate::bilibili::BiliClient::new(cookie);
+                    let client = crate::bilibili::BiliClient::new(cookie).with_credential_id(credential_id);
                     match get_collection_cover_from_api(up_id, s_id, collection_type, &client).await {
                         Ok(cover) => {
                             info!("成功从API获取合集「{}」封面: {}", collection_name, cover);
@@ -5072,7 +5430,9 @@ pub async fn add_video_source_internal(
                 flat_folder: sea_orm::Set(params.flat_folder.unwrap_or(false)),
                 split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
                 download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                credential_id: sea_orm::Set(credential_id),
                 download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
                 download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
                 download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -5128,7 +5488,7 @@ pub async fn add_video_source_internal(
                         )
                     })
                     .unwrap_or_default();
-                let client = crate::bilibili::BiliClient::new(cookie);
+                let client = crate::bilibili::BiliClient::new(cookie).with_credential_id(credential_id);
                 match crate::bilibili::FavoriteList::new(&client, f_id.to_string())
                     .get_info()
                     .await
@@ -5176,7 +5536,9 @@ pub async fn add_video_source_internal(
                 flat_folder: sea_orm::Set(params.flat_folder.unwrap_or(false)),
                 split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
                 download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                credential_id: sea_orm::Set(credential_id),
                 download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
                 download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
                 download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -5261,6 +5623,7 @@ pub async fn add_video_source_internal(
                 published_before: sea_orm::Set(None),
                 audio_only: sea_orm::Set(params.audio_only.unwrap_or(false)),
                 download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
                 download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
                 download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -5275,6 +5638,7 @@ pub async fn add_video_source_internal(
                 flat_folder: sea_orm::Set(params.flat_folder.unwrap_or(false)),
                 split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
                 download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                credential_id: sea_orm::Set(credential_id),
                 use_dynamic_api: sea_orm::Set(params.use_dynamic_api.unwrap_or(false)),
                 dynamic_api_full_synced: sea_orm::Set(params.use_dynamic_api.unwrap_or(false)),
             };
@@ -5597,7 +5961,9 @@ pub async fn add_video_source_internal(
                     audio_only: sea_orm::Set(params.audio_only.unwrap_or(false)),
                     split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
                     download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                    credential_id: sea_orm::Set(credential_id),
                     download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                    archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
                     download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
                     download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
                     ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -5683,6 +6049,7 @@ pub async fn add_video_source_internal(
                 published_before: sea_orm::Set(None),
                 audio_only: sea_orm::Set(params.audio_only.unwrap_or(false)),
                 download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
                 download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
                 download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -5697,6 +6064,7 @@ pub async fn add_video_source_internal(
                 flat_folder: sea_orm::Set(params.flat_folder.unwrap_or(false)),
                 split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
                 download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                credential_id: sea_orm::Set(credential_id),
             };
 
             let insert_result = watch_later::Entity::insert(watch_later).exec(&txn).await?;
@@ -5710,6 +6078,100 @@ pub async fn add_video_source_internal(
                 message: "稍后观看添加成功".to_string(),
             }
         }
+        "ranking" => {
+            let ranking_type = params
+                .ranking_type
+                .as_deref()
+                .unwrap_or("popular")
+                .parse::<crate::bilibili::RankingType>()?;
+            // 分区ID仅对排行榜有意义，热门与每周必看统一记为0
+            let rid = match ranking_type {
+                crate::bilibili::RankingType::Rank if !params.source_id.trim().is_empty() => params
+                    .source_id
+                    .trim()
+                    .parse::<i32>()
+                    .map_err(|_| anyhow!("无效的排行榜分区ID"))?,
+                _ => 0,
+            };
+
+            let existing = ranking::Entity::find()
+                .filter(ranking::Column::RankingType.eq(ranking_type.as_str()))
+                .filter(ranking::Column::Rid.eq(rid))
+                .one(&txn)
+                .await?;
+            if let Some(existing) = existing {
+                return Err(anyhow!(
+                    "榜单已存在！榜单名称：\"{}\"，保存路径：{}。如需修改设置，请先删除现有榜单再重新添加。",
+                    existing.name,
+                    existing.path
+                )
+                .into());
+            }
+
+            let keyword_filters_json = params
+                .keyword_filters
+                .as_ref()
+                .filter(|kf| !kf.is_empty())
+                .map(|kf| serde_json::to_string(kf).unwrap_or_default());
+
+            let name = if params.name.trim().is_empty() {
+                ranking_type.source_name(rid)
+            } else {
+                params.name.clone()
+            };
+
+            let ranking = ranking::ActiveModel {
+                id: sea_orm::ActiveValue::NotSet,
+                name: sea_orm::Set(name.clone()),
+                ranking_type: sea_orm::Set(ranking_type.as_str().to_string()),
+                rid: sea_orm::Set(rid),
+                path: sea_orm::Set(params.path.clone()),
+                created_at: sea_orm::Set(crate::utils::time_format::now_standard_string()),
+                latest_row_at: sea_orm::Set(crate::utils::time_format::now_standard_string()),
+                enabled: sea_orm::Set(true),
+                scan_deleted_videos: sea_orm::Set(false),
+                scan_deleted_videos_once: sea_orm::Set(false),
+                filter_option: sea_orm::Set(source_filter_option.clone()),
+                keyword_filters: sea_orm::Set(keyword_filters_json),
+                keyword_filter_mode: sea_orm::Set(params.keyword_filter_mode.clone()),
+                blacklist_keywords: sea_orm::Set(None),
+                whitelist_keywords: sea_orm::Set(None),
+                keyword_case_sensitive: sea_orm::Set(true),
+                min_duration_seconds: sea_orm::Set(None),
+                max_duration_seconds: sea_orm::Set(None),
+                published_after: sea_orm::Set(None),
+                published_before: sea_orm::Set(None),
+                audio_only: sea_orm::Set(params.audio_only.unwrap_or(false)),
+                download_danmaku: sea_orm::Set(params.download_danmaku.unwrap_or(true)),
+                archive_comments: sea_orm::Set(params.archive_comments.unwrap_or(false)),
+                download_subtitle: sea_orm::Set(params.download_subtitle.unwrap_or(true)),
+                download_ai_subtitle: sea_orm::Set(params.download_ai_subtitle.unwrap_or(true)),
+                ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
+                ai_rename: sea_orm::Set(params.ai_rename.unwrap_or(false)),
+                ai_rename_video_prompt: sea_orm::Set(params.ai_rename_video_prompt.clone().unwrap_or_default()),
+                ai_rename_audio_prompt: sea_orm::Set(params.ai_rename_audio_prompt.clone().unwrap_or_default()),
+                ai_rename_enable_multi_page: sea_orm::Set(params.ai_rename_enable_multi_page.unwrap_or(false)),
+                ai_rename_enable_collection: sea_orm::Set(params.ai_rename_enable_collection.unwrap_or(false)),
+                ai_rename_enable_bangumi: sea_orm::Set(params.ai_rename_enable_bangumi.unwrap_or(false)),
+                ai_rename_rename_parent_dir: sea_orm::Set(params.ai_rename_rename_parent_dir.unwrap_or(false)),
+                audio_only_m4a_only: sea_orm::Set(params.audio_only_m4a_only.unwrap_or(false)),
+                flat_folder: sea_orm::Set(params.flat_folder.unwrap_or(false)),
+                split_chapters_after_download: sea_orm::Set(params.split_chapters_after_download.unwrap_or(false)),
+                download_charge_videos: sea_orm::Set(params.download_charge_videos.unwrap_or(true)),
+                credential_id: sea_orm::Set(credential_id),
+            };
+
+            let insert_result = ranking::Entity::insert(ranking).exec(&txn).await?;
+
+            info!("榜单「{}」添加成功，保存路径: {}", name, params.path);
+
+            AddVideoSourceResponse {
+                success: true,
+                source_id: insert_result.last_insert_id,
+                source_type: "ranking".to_string(),
+                message: format!("榜单「{}」添加成功", name),
+            }
+        }
         _ => return Err(anyhow!("不支持的视频源类型: {}", params.source_type).into()),
     };
 
@@ -5718,6 +6180,14 @@ pub async fn add_video_source_internal(
 
     txn.commit().await?;
     notify_video_sources_changed();
+    crate::events::publish(
+        crate::events::EventKind::SourceAdded,
+        serde_json::json!({
+            "source_type": result.source_type,
+            "source_id": result.source_id,
+            "path": params.path,
+        }),
+    );
 
     Ok(result)
 }
@@ -5927,6 +6397,28 @@ pub async fn update_video_source_enabled_internal(
                 message: format!("稍后观看已{}", if enabled { "启用" } else { "禁用" }),
             }
         }
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            ranking::Entity::update(ranking::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                enabled: sea_orm::Set(enabled),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+
+            crate::api::response::UpdateVideoSourceEnabledResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                enabled,
+                message: format!("榜单「{}」已{}", ranking.name, if enabled { "启用" } else { "禁用" }),
+            }
+        }
         "bangumi" => {
             let bangumi = video_source::Entity::find_by_id(id)
                 .one(&txn)
@@ -5956,6 +6448,16 @@ pub async fn update_video_source_enabled_internal(
 
     txn.commit().await?;
     notify_video_sources_changed();
+    if !enabled {
+        crate::events::publish(
+            crate::events::EventKind::SourceDisabled,
+            serde_json::json!({
+                "source_type": result.source_type,
+                "source_id": result.source_id,
+                "reason": "manual",
+            }),
+        );
+    }
     Ok(result)
 }
 
@@ -6948,7 +7450,7 @@ async fn delete_orphaned_videos_from_db(
 fn is_supported_delete_video_source_type(source_type: &str) -> bool {
     matches!(
         source_type,
-        "collection" | "favorite" | "submission" | "watch_later" | "bangumi"
+        "collection" | "favorite" | "submission" | "watch_later" | "ranking" | "bangumi"
     )
 }
 
@@ -6958,6 +7460,7 @@ fn delete_video_source_missing_message(source_type: &str) -> String {
         "favorite" => "未找到指定的收藏夹".to_string(),
         "submission" => "未找到指定的UP主投稿".to_string(),
         "watch_later" => "未找到指定的稍后再看".to_string(),
+        "ranking" => "未找到指定的榜单".to_string(),
         "bangumi" => "未找到指定的番剧".to_string(),
         _ => format!("不支持的视频源类型: {}", source_type),
     }
@@ -6969,6 +7472,7 @@ async fn delete_video_source_record_exists(db: &impl ConnectionTrait, source_typ
         "favorite" => Ok(favorite::Entity::find_by_id(id).one(db).await?.is_some()),
         "submission" => Ok(submission::Entity::find_by_id(id).one(db).await?.is_some()),
         "watch_later" => Ok(watch_later::Entity::find_by_id(id).one(db).await?.is_some()),
+        "ranking" => Ok(ranking::Entity::find_by_id(id).one(db).await?.is_some()),
         "bangumi" => Ok(video_source::Entity::find_by_id(id).one(db).await?.is_some()),
         _ => Err(anyhow!("不支持的视频源类型: {}", source_type)),
     }
@@ -6984,6 +7488,7 @@ async fn find_videos_by_source_relation(
         "favorite" => video::Entity::find().filter(video::Column::FavoriteId.eq(id)),
         "submission" => video::Entity::find().filter(video::Column::SubmissionId.eq(id)),
         "watch_later" => video::Entity::find().filter(video::Column::WatchLaterId.eq(id)),
+        "ranking" => video::Entity::find().filter(video::Column::RankingId.eq(id)),
         "bangumi" => video::Entity::find()
             .filter(video::Column::SourceId.eq(id))
             .filter(video::Column::SourceType.eq(1)),
@@ -7035,6 +7540,16 @@ async fn clear_video_source_relation(conn: &impl ConnectionTrait, source_type: &
                 .exec(conn)
                 .await?;
         }
+        "ranking" => {
+            video::Entity::update_many()
+                .col_expr(
+                    video::Column::RankingId,
+                    sea_orm::sea_query::Expr::value(sea_orm::Value::Int(None)),
+                )
+                .filter(video::Column::RankingId.eq(id))
+                .exec(conn)
+                .await?;
+        }
         "bangumi" => {
             video::Entity::update_many()
                 .col_expr(
@@ -7068,6 +7583,7 @@ async fn find_orphaned_videos_by_ids(conn: &impl ConnectionTrait, video_ids: Vec
                 .is_null()
                 .and(video::Column::FavoriteId.is_null())
                 .and(video::Column::WatchLaterId.is_null())
+                .and(video::Column::RankingId.is_null())
                 .and(video::Column::SubmissionId.is_null())
                 .and(video::Column::SourceId.is_null()),
         )
@@ -7202,6 +7718,7 @@ pub async fn delete_video_source_internal(
                         .is_null()
                         .and(video::Column::FavoriteId.is_null())
                         .and(video::Column::WatchLaterId.is_null())
+                        .and(video::Column::RankingId.is_null())
                         .and(video::Column::SubmissionId.is_null())
                         .and(video::Column::SourceId.is_null()),
                 )
@@ -7265,6 +7782,7 @@ pub async fn delete_video_source_internal(
                         .is_null()
                         .and(video::Column::FavoriteId.is_null())
                         .and(video::Column::WatchLaterId.is_null())
+                        .and(video::Column::RankingId.is_null())
                         .and(video::Column::SubmissionId.is_null())
                         .and(video::Column::SourceId.is_null()),
                 )
@@ -7331,6 +7849,7 @@ pub async fn delete_video_source_internal(
                         .is_null()
                         .and(video::Column::FavoriteId.is_null())
                         .and(video::Column::WatchLaterId.is_null())
+                        .and(video::Column::RankingId.is_null())
                         .and(video::Column::SubmissionId.is_null())
                         .and(video::Column::SourceId.is_null()),
                 )
@@ -7394,6 +7913,7 @@ pub async fn delete_video_source_internal(
                         .is_null()
                         .and(video::Column::FavoriteId.is_null())
                         .and(video::Column::WatchLaterId.is_null())
+                        .and(video::Column::RankingId.is_null())
                         .and(video::Column::SubmissionId.is_null())
                         .and(video::Column::SourceId.is_null()),
                 )
@@ -7427,7 +7947,47 @@ pub async fn delete_video_source_internal(
                 message: "稍后再看已成功删除".to_string(),
             }
         }
-        "bangumi" => {
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            let videos = video::Entity::find()
+                .filter(video::Column::RankingId.eq(id))
+                .all(&txn)
+                .await?;
+
+            // 清空榜单关联，同一视频若仍属于其它视频源则保留
+            clear_video_source_relation(&txn, "ranking", id).await?;
+            let orphaned_videos = find_orphaned_videos_by_ids(&txn, videos.iter().map(|v| v.id).collect()).await?;
+
+            if delete_local_files {
+                cleanup_plan = Some(
+                    build_local_source_cleanup_plan(
+                        &txn,
+                        format!("榜单「{}」", ranking.name),
+                        ranking.path.clone(),
+                        "榜单基础目录",
+                        ranking.flat_folder,
+                        &orphaned_videos,
+                    )
+                    .await?,
+                );
+            }
+
+            delete_orphaned_videos_from_db(&txn, &orphaned_videos).await?;
+
+            ranking::Entity::delete_by_id(id).exec(&txn).await?;
+
+            crate::api::response::DeleteVideoSourceResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                message: format!("榜单「{}」已成功删除", ranking.name),
+            }
+        }
+        "bangumi" => {
             // 查找要删除的番剧
             let bangumi = video_source::Entity::find_by_id(id)
                 .one(&txn)
@@ -7463,6 +8023,7 @@ pub async fn delete_video_source_internal(
                         .is_null()
                         .and(video::Column::FavoriteId.is_null())
                         .and(video::Column::WatchLaterId.is_null())
+                        .and(video::Column::RankingId.is_null())
                         .and(video::Column::SubmissionId.is_null())
                         .and(video::Column::SourceId.is_null()),
                 )
@@ -7595,6 +8156,133 @@ pub async fn update_video_source_scan_deleted_once(
         .map(ApiResponse::ok)
 }
 
+/// 更新视频源绑定的B站账号
+#[utoipa::path(
+    put,
+    path = "/api/video-sources/{source_type}/{id}/credential",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = crate::api::request::UpdateVideoSourceCredentialRequest,
+    responses(
+        (status = 200, body = ApiResponse<crate::api::response::UpdateVideoSourceCredentialResponse>),
+    )
+)]
+pub async fn update_video_source_credential(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateVideoSourceCredentialRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateVideoSourceCredentialResponse>, ApiError> {
+    let credential_id = validate_source_credential_id(params.credential_id)?;
+    let txn = crate::database::begin_traced_transaction(&db, "api.handler.update_video_source_credential").await?;
+
+    let (source_label, source_name) = match source_type.as_str() {
+        "collection" => {
+            let collection = collection::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的合集"))?;
+            collection::Entity::update(collection::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("合集", collection.name)
+        }
+        "favorite" => {
+            let favorite = favorite::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的收藏夹"))?;
+            favorite::Entity::update(favorite::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("收藏夹", favorite.name)
+        }
+        "submission" => {
+            let submission = submission::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的UP主投稿"))?;
+            submission::Entity::update(submission::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("UP主投稿", submission.upper_name)
+        }
+        "watch_later" => {
+            watch_later::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的稍后再看"))?;
+            watch_later::Entity::update(watch_later::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("稍后再看", "稍后再看".to_string())
+        }
+        "bangumi" => {
+            let video_source = video_source::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的番剧"))?;
+            video_source::Entity::update(video_source::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("番剧", video_source.name)
+        }
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+            ranking::Entity::update(ranking::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                credential_id: sea_orm::Set(credential_id),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+            ("榜单", ranking.name)
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+    notify_video_sources_changed();
+
+    let account_label = match credential_id {
+        Some(account_id) => format!("B站账号 {}", account_id),
+        None => "默认账号".to_string(),
+    };
+    Ok(ApiResponse::ok(
+        crate::api::response::UpdateVideoSourceCredentialResponse {
+            success: true,
+            source_id: id,
+            source_type,
+            credential_id,
+            message: format!("{}「{}」已改用{}扫描和下载", source_label, source_name, account_label),
+        },
+    ))
+}
+
 /// 重试 UP 投稿源下已识别的充电视频
 #[utoipa::path(
     post,
@@ -7668,6 +8356,17 @@ pub async fn retry_charge_videos_for_source_internal(
                 video::Entity::find().filter(video::Column::WatchLaterId.eq(id)),
             )
         }
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+            (
+                "榜单",
+                Some(ranking.name),
+                video::Entity::find().filter(video::Column::RankingId.eq(id)),
+            )
+        }
         "bangumi" => return Err(anyhow!("番剧源不支持重试充电视频").into()),
         _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
     };
@@ -8001,6 +8700,42 @@ pub async fn update_video_source_scan_deleted_internal(
                 ),
             }
         }
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            let (scan_deleted_videos, scan_deleted_videos_once) = resolve_scan_deleted_modes(
+                ranking.scan_deleted_videos,
+                ranking.scan_deleted_videos_once,
+                requested_scan_deleted_videos,
+                requested_scan_deleted_videos_once,
+            )?;
+
+            ranking::Entity::update(ranking::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                scan_deleted_videos: sea_orm::Set(scan_deleted_videos),
+                scan_deleted_videos_once: sea_orm::Set(scan_deleted_videos_once),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+
+            crate::api::response::UpdateVideoSourceScanDeletedResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                scan_deleted_videos,
+                scan_deleted_videos_once,
+                message: build_scan_deleted_message(
+                    "榜单",
+                    Some(&ranking.name),
+                    requested_scan_deleted_videos,
+                    requested_scan_deleted_videos_once,
+                ),
+            }
+        }
         _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
     };
 
@@ -8059,6 +8794,7 @@ pub async fn update_video_source_download_options_internal(
                 .download_charge_videos
                 .unwrap_or(collection.download_charge_videos);
             let download_danmaku = params.download_danmaku.unwrap_or(collection.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(collection.archive_comments);
             let download_subtitle = params.download_subtitle.unwrap_or(collection.download_subtitle);
             let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(collection.download_ai_subtitle);
             let ai_subtitle_language =
@@ -8100,8 +8836,12 @@ pub async fn update_video_source_download_options_internal(
             let ai_rename_rename_parent_dir = params
                 .ai_rename_rename_parent_dir
                 .unwrap_or(collection.ai_rename_rename_parent_dir);
-            let filter_option =
-                resolve_source_filter_option_update(collection.filter_option.clone(), &params.filter_option)?;
+            let filter_option = resolve_source_filter_option_update(
+                collection.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
             let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
 
             collection::Entity::update(collection::ActiveModel {
@@ -8114,6 +8854,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
                 download_charge_videos: sea_orm::Set(download_charge_videos),
                 download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
                 download_subtitle: sea_orm::Set(download_subtitle),
                 download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -8142,6 +8883,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download,
                 download_charge_videos,
                 download_danmaku,
+                archive_comments,
                 download_subtitle,
                 download_ai_subtitle,
                 ai_subtitle_language,
@@ -8154,6 +8896,7 @@ pub async fn update_video_source_download_options_internal(
                 ai_rename_rename_parent_dir,
                 use_dynamic_api: false,
                 filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
                 message: format!("合集 {} 的下载选项已更新", collection.name),
             }
         }
@@ -8171,6 +8914,7 @@ pub async fn update_video_source_download_options_internal(
                 .unwrap_or(favorite.split_chapters_after_download);
             let download_charge_videos = params.download_charge_videos.unwrap_or(favorite.download_charge_videos);
             let download_danmaku = params.download_danmaku.unwrap_or(favorite.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(favorite.archive_comments);
             let download_subtitle = params.download_subtitle.unwrap_or(favorite.download_subtitle);
             let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(favorite.download_ai_subtitle);
             let ai_subtitle_language =
@@ -8196,8 +8940,12 @@ pub async fn update_video_source_download_options_internal(
             let ai_rename_rename_parent_dir = params
                 .ai_rename_rename_parent_dir
                 .unwrap_or(favorite.ai_rename_rename_parent_dir);
-            let filter_option =
-                resolve_source_filter_option_update(favorite.filter_option.clone(), &params.filter_option)?;
+            let filter_option = resolve_source_filter_option_update(
+                favorite.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
             let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
 
             favorite::Entity::update(favorite::ActiveModel {
@@ -8208,6 +8956,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
                 download_charge_videos: sea_orm::Set(download_charge_videos),
                 download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
                 download_subtitle: sea_orm::Set(download_subtitle),
                 download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -8236,6 +8985,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download,
                 download_charge_videos,
                 download_danmaku,
+                archive_comments,
                 download_subtitle,
                 download_ai_subtitle,
                 ai_subtitle_language,
@@ -8248,6 +8998,7 @@ pub async fn update_video_source_download_options_internal(
                 ai_rename_rename_parent_dir,
                 use_dynamic_api: false,
                 filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
                 message: format!("收藏夹 {} 的下载选项已更新", favorite.name),
             }
         }
@@ -8267,6 +9018,7 @@ pub async fn update_video_source_download_options_internal(
                 .download_charge_videos
                 .unwrap_or(submission.download_charge_videos);
             let download_danmaku = params.download_danmaku.unwrap_or(submission.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(submission.archive_comments);
             let download_subtitle = params.download_subtitle.unwrap_or(submission.download_subtitle);
             let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(submission.download_ai_subtitle);
             let ai_subtitle_language =
@@ -8293,8 +9045,12 @@ pub async fn update_video_source_download_options_internal(
                 .ai_rename_rename_parent_dir
                 .unwrap_or(submission.ai_rename_rename_parent_dir);
             let use_dynamic_api = params.use_dynamic_api.unwrap_or(submission.use_dynamic_api);
-            let filter_option =
-                resolve_source_filter_option_update(submission.filter_option.clone(), &params.filter_option)?;
+            let filter_option = resolve_source_filter_option_update(
+                submission.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
             let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
             let mut dynamic_api_full_synced = submission.dynamic_api_full_synced;
             let mut latest_row_at_override: Option<String> = None;
@@ -8316,6 +9072,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
                 download_charge_videos: sea_orm::Set(download_charge_videos),
                 download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
                 download_subtitle: sea_orm::Set(download_subtitle),
                 download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -8350,6 +9107,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download,
                 download_charge_videos,
                 download_danmaku,
+                archive_comments,
                 download_subtitle,
                 download_ai_subtitle,
                 ai_subtitle_language,
@@ -8362,6 +9120,7 @@ pub async fn update_video_source_download_options_internal(
                 ai_rename_rename_parent_dir,
                 use_dynamic_api,
                 filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
                 message: format!("UP主投稿 {} 的下载选项已更新", submission.upper_name),
             }
         }
@@ -8381,6 +9140,7 @@ pub async fn update_video_source_download_options_internal(
                 .download_charge_videos
                 .unwrap_or(watch_later.download_charge_videos);
             let download_danmaku = params.download_danmaku.unwrap_or(watch_later.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(watch_later.archive_comments);
             let download_subtitle = params.download_subtitle.unwrap_or(watch_later.download_subtitle);
             let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(watch_later.download_ai_subtitle);
             let ai_subtitle_language =
@@ -8406,8 +9166,12 @@ pub async fn update_video_source_download_options_internal(
             let ai_rename_rename_parent_dir = params
                 .ai_rename_rename_parent_dir
                 .unwrap_or(watch_later.ai_rename_rename_parent_dir);
-            let filter_option =
-                resolve_source_filter_option_update(watch_later.filter_option.clone(), &params.filter_option)?;
+            let filter_option = resolve_source_filter_option_update(
+                watch_later.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
             let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
 
             watch_later::Entity::update(watch_later::ActiveModel {
@@ -8418,6 +9182,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
                 download_charge_videos: sea_orm::Set(download_charge_videos),
                 download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
                 download_subtitle: sea_orm::Set(download_subtitle),
                 download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -8446,6 +9211,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download,
                 download_charge_videos,
                 download_danmaku,
+                archive_comments,
                 download_subtitle,
                 download_ai_subtitle,
                 ai_subtitle_language,
@@ -8458,6 +9224,7 @@ pub async fn update_video_source_download_options_internal(
                 ai_rename_rename_parent_dir,
                 use_dynamic_api: false,
                 filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
                 message: "稍后观看的下载选项已更新".to_string(),
             }
         }
@@ -8477,6 +9244,7 @@ pub async fn update_video_source_download_options_internal(
                 .download_charge_videos
                 .unwrap_or(video_source.download_charge_videos);
             let download_danmaku = params.download_danmaku.unwrap_or(video_source.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(video_source.archive_comments);
             let download_subtitle = params.download_subtitle.unwrap_or(video_source.download_subtitle);
             let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(video_source.download_ai_subtitle);
             let ai_subtitle_language =
@@ -8502,8 +9270,12 @@ pub async fn update_video_source_download_options_internal(
             let ai_rename_rename_parent_dir = params
                 .ai_rename_rename_parent_dir
                 .unwrap_or(video_source.ai_rename_rename_parent_dir);
-            let filter_option =
-                resolve_source_filter_option_update(video_source.filter_option.clone(), &params.filter_option)?;
+            let filter_option = resolve_source_filter_option_update(
+                video_source.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
             let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
 
             video_source::Entity::update(video_source::ActiveModel {
@@ -8514,6 +9286,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
                 download_charge_videos: sea_orm::Set(download_charge_videos),
                 download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
                 download_subtitle: sea_orm::Set(download_subtitle),
                 download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
                 ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
@@ -8542,6 +9315,7 @@ pub async fn update_video_source_download_options_internal(
                 split_chapters_after_download,
                 download_charge_videos,
                 download_danmaku,
+                archive_comments,
                 download_subtitle,
                 download_ai_subtitle,
                 ai_subtitle_language,
@@ -8554,9 +9328,112 @@ pub async fn update_video_source_download_options_internal(
                 ai_rename_rename_parent_dir,
                 use_dynamic_api: false,
                 filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
                 message: format!("番剧 {} 的下载选项已更新", video_source.name),
             }
         }
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            let audio_only = params.audio_only.unwrap_or(ranking.audio_only);
+            let audio_only_m4a_only = params.audio_only_m4a_only.unwrap_or(ranking.audio_only_m4a_only);
+            let flat_folder = params.flat_folder.unwrap_or(ranking.flat_folder);
+            let split_chapters_after_download = params
+                .split_chapters_after_download
+                .unwrap_or(ranking.split_chapters_after_download);
+            let download_charge_videos = params.download_charge_videos.unwrap_or(ranking.download_charge_videos);
+            let download_danmaku = params.download_danmaku.unwrap_or(ranking.download_danmaku);
+            let archive_comments = params.archive_comments.unwrap_or(ranking.archive_comments);
+            let download_subtitle = params.download_subtitle.unwrap_or(ranking.download_subtitle);
+            let download_ai_subtitle = params.download_ai_subtitle.unwrap_or(ranking.download_ai_subtitle);
+            let ai_subtitle_language =
+                ai_subtitle_language_from_request(&params.ai_subtitle_language, &ranking.ai_subtitle_language);
+            let ai_rename = params.ai_rename.unwrap_or(ranking.ai_rename);
+            let ai_rename_video_prompt = params
+                .ai_rename_video_prompt
+                .clone()
+                .unwrap_or(ranking.ai_rename_video_prompt.clone());
+            let ai_rename_audio_prompt = params
+                .ai_rename_audio_prompt
+                .clone()
+                .unwrap_or(ranking.ai_rename_audio_prompt.clone());
+            let ai_rename_enable_multi_page = params
+                .ai_rename_enable_multi_page
+                .unwrap_or(ranking.ai_rename_enable_multi_page);
+            let ai_rename_enable_collection = params
+                .ai_rename_enable_collection
+                .unwrap_or(ranking.ai_rename_enable_collection);
+            let ai_rename_enable_bangumi = params
+                .ai_rename_enable_bangumi
+                .unwrap_or(ranking.ai_rename_enable_bangumi);
+            let ai_rename_rename_parent_dir = params
+                .ai_rename_rename_parent_dir
+                .unwrap_or(ranking.ai_rename_rename_parent_dir);
+            let filter_option = resolve_source_filter_option_update(
+                ranking.filter_option.clone(),
+                &params.filter_option,
+                &params.quality_profile,
+            )?;
+            let response_quality_profile = source_quality_profile_from_json(&filter_option);
+            let response_filter_option = source_filter_option_to_response(filter_option.clone())?;
+
+            ranking::Entity::update(ranking::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                audio_only: sea_orm::Set(audio_only),
+                audio_only_m4a_only: sea_orm::Set(audio_only_m4a_only),
+                flat_folder: sea_orm::Set(flat_folder),
+                split_chapters_after_download: sea_orm::Set(split_chapters_after_download),
+                download_charge_videos: sea_orm::Set(download_charge_videos),
+                download_danmaku: sea_orm::Set(download_danmaku),
+                archive_comments: sea_orm::Set(archive_comments),
+                download_subtitle: sea_orm::Set(download_subtitle),
+                download_ai_subtitle: sea_orm::Set(download_ai_subtitle),
+                ai_subtitle_language: sea_orm::Set(ai_subtitle_language.clone()),
+                ai_rename: sea_orm::Set(ai_rename),
+                ai_rename_video_prompt: sea_orm::Set(ai_rename_video_prompt.clone()),
+                ai_rename_audio_prompt: sea_orm::Set(ai_rename_audio_prompt.clone()),
+                ai_rename_enable_multi_page: sea_orm::Set(ai_rename_enable_multi_page),
+                ai_rename_enable_collection: sea_orm::Set(ai_rename_enable_collection),
+                ai_rename_enable_bangumi: sea_orm::Set(ai_rename_enable_bangumi),
+                ai_rename_rename_parent_dir: sea_orm::Set(ai_rename_rename_parent_dir),
+                filter_option: sea_orm::Set(filter_option),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+
+            crate::api::response::UpdateVideoSourceDownloadOptionsResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                collection_aggregate_enabled: false,
+                collection_aggregate_season_number: None,
+                audio_only,
+                audio_only_m4a_only,
+                flat_folder,
+                split_chapters_after_download,
+                download_charge_videos,
+                download_danmaku,
+                archive_comments,
+                download_subtitle,
+                download_ai_subtitle,
+                ai_subtitle_language,
+                ai_rename,
+                ai_rename_video_prompt,
+                ai_rename_audio_prompt,
+                ai_rename_enable_multi_page,
+                ai_rename_enable_collection,
+                ai_rename_enable_bangumi,
+                ai_rename_rename_parent_dir,
+                use_dynamic_api: false,
+                filter_option: response_filter_option,
+                quality_profile: response_quality_profile,
+                message: format!("榜单 {} 的下载选项已更新", ranking.name),
+            }
+        }
         _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
     };
 
@@ -9131,6 +10008,7 @@ async fn validate_path_reset_safety(
                 danmaku_cid_snapshot: None,
                 danmaku_last_write_count: 0,
                 ai_renamed: None,
+                quality_checked_at: None,
             };
 
             let api_title = if let Some(current_path) = std::path::Path::new(&video.path).parent() {
@@ -9564,27 +10442,100 @@ pub async fn reset_video_source_path_internal(
                 message: format!("番剧 {} 路径重设完成", bangumi.name),
             }
         }
-        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
-    };
-
-    txn.commit().await?;
-    notify_video_sources_changed();
-    notify_videos_changed();
-    Ok(result)
-}
-
-/// 使用四步重命名原则移动文件夹（直接移动到指定目标路径）
-async fn move_files_with_four_step_rename(old_path: &str, target_path: &str) -> Result<String, std::io::Error> {
-    use std::path::Path;
-
-    let old_path = Path::new(old_path);
-    let target_path = Path::new(target_path);
+        "ranking" => {
+            let ranking = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+            let old_path = ranking.path.clone();
 
-    if !old_path.exists() {
-        return Ok(target_path.to_string_lossy().to_string()); // 如果原路径不存在，返回目标路径
-    }
+            if request.apply_rename_rules {
+                // 获取所有相关视频，按新路径规则移动文件
+                let videos = video::Entity::find()
+                    .filter(video::Column::RankingId.eq(id))
+                    .all(&txn)
+                    .await?;
 
-    // 如果目标路径已存在且和源路径相同，无需移动
+                for video in &videos {
+                    // 移动视频文件到新路径结构
+                    let moved_video_path = match move_video_files_to_new_path(
+                        video,
+                        &old_path,
+                        &request.new_path,
+                        ranking.flat_folder,
+                        request.clean_empty_folders,
+                        &txn,
+                    )
+                    .await
+                    {
+                        Ok((moved, cleaned, moved_path)) => {
+                            moved_files_count += moved;
+                            cleaned_folders_count += cleaned;
+                            moved_path
+                        }
+                        Err(e) => {
+                            warn!("移动视频 {} 文件失败: {}", video.id, e);
+                            None
+                        }
+                    };
+
+                    let update_result = if let Some(actual_path) = moved_video_path {
+                        update_video_and_page_paths_to_actual_path(&txn, video.id, &video.path, &actual_path).await
+                    } else {
+                        regenerate_video_and_page_paths_correctly(
+                            &txn,
+                            video.id,
+                            &request.new_path,
+                            ranking.flat_folder,
+                        )
+                        .await
+                    };
+                    if let Err(e) = update_result {
+                        warn!("更新视频 {} 路径失败: {:?}", video.id, e);
+                    }
+                }
+                updated_videos_count = videos.len();
+            }
+
+            ranking::Entity::update_many()
+                .filter(ranking::Column::Id.eq(id))
+                .col_expr(ranking::Column::Path, Expr::value(request.new_path.clone()))
+                .exec(&txn)
+                .await?;
+
+            ResetVideoSourcePathResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                old_path,
+                new_path: request.new_path,
+                moved_files_count,
+                updated_videos_count,
+                cleaned_folders_count,
+                message: format!("榜单 {} 路径重设完成", ranking.name),
+            }
+        }
+        _ => return Err(anyhow!("不支持的视频源类型: {}", source_type).into()),
+    };
+
+    txn.commit().await?;
+    notify_video_sources_changed();
+    notify_videos_changed();
+    Ok(result)
+}
+
+/// 使用四步重命名原则移动文件夹（直接移动到指定目标路径）
+async fn move_files_with_four_step_rename(old_path: &str, target_path: &str) -> Result<String, std::io::Error> {
+    use std::path::Path;
+
+    let old_path = Path::new(old_path);
+    let target_path = Path::new(target_path);
+
+    if !old_path.exists() {
+        return Ok(target_path.to_string_lossy().to_string()); // 如果原路径不存在，返回目标路径
+    }
+
+    // 如果目标路径已存在且和源路径相同，无需移动
     if old_path == target_path {
         return Ok(target_path.to_string_lossy().to_string());
     }
@@ -10144,6 +11095,7 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         danmaku_bold: config.danmaku_option.bold,
         danmaku_outline: config.danmaku_option.outline,
         danmaku_time_offset: config.danmaku_option.time_offset,
+        danmaku_font_path: config.danmaku_option.font_path.clone(),
         danmaku_update_enabled: config.danmaku_update_policy.enabled,
         danmaku_update_fresh_days: config.danmaku_update_policy.fresh_days,
         danmaku_update_fresh_interval_hours: config.danmaku_update_policy.fresh_interval_hours,
@@ -10151,6 +11103,10 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         danmaku_update_mature_interval_days: config.danmaku_update_policy.mature_interval_days,
         danmaku_update_cold_days: config.danmaku_update_policy.cold_days,
         danmaku_update_cold_interval_days: config.danmaku_update_policy.cold_interval_days,
+        quality_upgrade_enabled: config.quality_upgrade_policy.enabled,
+        quality_upgrade_window_hours: config.quality_upgrade_policy.window_hours,
+        quality_upgrade_check_interval_hours: config.quality_upgrade_policy.check_interval_hours,
+        quality_upgrade_max_pages_per_run: config.quality_upgrade_policy.max_pages_per_run,
         // 并发控制设置
         concurrent_video: config.concurrent_limit.video,
         concurrent_page: config.concurrent_limit.page,
@@ -10191,6 +11147,7 @@ pub async fn get_config() -> Result<ApiResponse<crate::api::response::ConfigResp
         submission_adaptive_scan: config.submission_scan_strategy.adaptive_enabled,
         submission_adaptive_max_hours: config.submission_scan_strategy.adaptive_max_hours,
         scan_deleted_videos: config.scan_deleted_videos,
+        cross_source_dedup: config.cross_source_dedup,
         // aria2监控配置
         enable_aria2_health_check: config.enable_aria2_health_check,
         enable_aria2_auto_restart: config.enable_aria2_auto_restart,
@@ -10737,6 +11694,7 @@ pub async fn update_config(
             danmaku_bold: params.danmaku_bold,
             danmaku_outline: params.danmaku_outline,
             danmaku_time_offset: params.danmaku_time_offset,
+            danmaku_font_path: params.danmaku_font_path.clone(),
             danmaku_update_enabled: params.danmaku_update_enabled,
             danmaku_update_fresh_days: params.danmaku_update_fresh_days,
             danmaku_update_fresh_interval_hours: params.danmaku_update_fresh_interval_hours,
@@ -10744,6 +11702,10 @@ pub async fn update_config(
             danmaku_update_mature_interval_days: params.danmaku_update_mature_interval_days,
             danmaku_update_cold_days: params.danmaku_update_cold_days,
             danmaku_update_cold_interval_days: params.danmaku_update_cold_interval_days,
+            quality_upgrade_enabled: params.quality_upgrade_enabled,
+            quality_upgrade_window_hours: params.quality_upgrade_window_hours,
+            quality_upgrade_check_interval_hours: params.quality_upgrade_check_interval_hours,
+            quality_upgrade_max_pages_per_run: params.quality_upgrade_max_pages_per_run,
             // 并发控制设置
             concurrent_video: params.concurrent_video,
             concurrent_page: params.concurrent_page,
@@ -10858,6 +11820,7 @@ fn config_update_field_display_name(field: &str) -> String {
         "danmaku_bold" => Some("弹幕加粗"),
         "danmaku_outline" => Some("弹幕描边"),
         "danmaku_time_offset" => Some("弹幕时间偏移"),
+        "danmaku_font_path" => Some("弹幕测量字体"),
         "danmaku_update_enabled" => Some("弹幕增量更新开关"),
         "danmaku_update_fresh_days" => Some("弹幕新鲜期天数"),
         "danmaku_update_fresh_interval_hours" => Some("弹幕新鲜期刷新间隔"),
@@ -10865,12 +11828,17 @@ fn config_update_field_display_name(field: &str) -> String {
         "danmaku_update_mature_interval_days" => Some("弹幕成熟期刷新间隔"),
         "danmaku_update_cold_days" => Some("弹幕老化期天数"),
         "danmaku_update_cold_interval_days" => Some("弹幕老化期刷新间隔"),
+        "quality_upgrade_enabled" => Some("画质升级重下载开关"),
+        "quality_upgrade_window_hours" => Some("画质升级检查窗口"),
+        "quality_upgrade_check_interval_hours" => Some("画质升级检查间隔"),
+        "quality_upgrade_max_pages_per_run" => Some("画质升级每轮分页上限"),
         "concurrent_video" => Some("同时处理视频数"),
         "concurrent_page" => Some("每视频并发分页数"),
         "rate_limit" => Some("请求频率限制"),
         "rate_duration" => Some("请求时间窗口"),
         "cdn_sorting" => Some("CDN优先级排序"),
         "scan_deleted_videos" => Some("扫描已删除视频"),
+        "cross_source_dedup" => Some("跨视频源去重"),
         "enable_aria2_health_check" => Some("aria2健康检查开关"),
         "enable_aria2_auto_restart" => Some("aria2自动重启开关"),
         "aria2_health_check_interval" => Some("aria2健康检查间隔"),
@@ -11485,6 +12453,15 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(font_path) = params.danmaku_font_path {
+        // 空字符串表示清除，回退到估算宽度
+        let font_path = Some(font_path.trim().to_string()).filter(|path| !path.is_empty());
+        if font_path != config.danmaku_option.font_path {
+            config.danmaku_option.font_path = font_path;
+            updated_fields.push("danmaku_font_path");
+        }
+    }
+
     if let Some(enabled) = params.danmaku_update_enabled {
         if enabled != config.danmaku_update_policy.enabled {
             config.danmaku_update_policy.enabled = enabled;
@@ -11547,6 +12524,38 @@ pub async fn update_config_internal(
         return Err(anyhow!("弹幕增量更新策略无效：{}", err).into());
     }
 
+    if let Some(enabled) = params.quality_upgrade_enabled {
+        if enabled != config.quality_upgrade_policy.enabled {
+            config.quality_upgrade_policy.enabled = enabled;
+            updated_fields.push("quality_upgrade_enabled");
+        }
+    }
+
+    if let Some(hours) = params.quality_upgrade_window_hours {
+        if hours != config.quality_upgrade_policy.window_hours {
+            config.quality_upgrade_policy.window_hours = hours;
+            updated_fields.push("quality_upgrade_window_hours");
+        }
+    }
+
+    if let Some(hours) = params.quality_upgrade_check_interval_hours {
+        if hours != config.quality_upgrade_policy.check_interval_hours {
+            config.quality_upgrade_policy.check_interval_hours = hours;
+            updated_fields.push("quality_upgrade_check_interval_hours");
+        }
+    }
+
+    if let Some(max_pages) = params.quality_upgrade_max_pages_per_run {
+        if max_pages != config.quality_upgrade_policy.max_pages_per_run {
+            config.quality_upgrade_policy.max_pages_per_run = max_pages;
+            updated_fields.push("quality_upgrade_max_pages_per_run");
+        }
+    }
+
+    if let Err(err) = config.quality_upgrade_policy.validate() {
+        return Err(anyhow!("画质升级重下载策略无效：{}", err).into());
+    }
+
     // 处理并发控制设置
     if let Some(concurrent_video) = params.concurrent_video {
         if concurrent_video > 0 && concurrent_video != config.concurrent_limit.video {
@@ -11622,6 +12631,13 @@ pub async fn update_config_internal(
         }
     }
 
+    if let Some(cross_source_dedup) = params.cross_source_dedup {
+        if cross_source_dedup != config.cross_source_dedup {
+            config.cross_source_dedup = cross_source_dedup;
+            updated_fields.push("cross_source_dedup");
+        }
+    }
+
     // 处理aria2监控配置
     if let Some(enable_health_check) = params.enable_aria2_health_check {
         if enable_health_check != config.enable_aria2_health_check {
@@ -12205,6 +13221,13 @@ pub async fn update_config_internal(
         });
     }
 
+    // 由声明式配置文件管理的配置项只读
+    crate::config::ensure_config_writable(
+        updated_fields
+            .iter()
+            .map(|field| crate::config::config_key_of_field(field)),
+    )?;
+
     let updated_field_labels = format_config_update_fields_display(&updated_fields);
     let updated_fields_display = updated_field_labels.join("、");
     let should_initialize_danmaku_baseline = !original_danmaku_update_enabled && config.danmaku_update_policy.enabled;
@@ -12474,7 +13497,8 @@ pub async fn update_config_internal(
                 | "danmaku_opacity"
                 | "danmaku_bold"
                 | "danmaku_outline"
-                | "danmaku_time_offset" => {
+                | "danmaku_time_offset"
+                | "danmaku_font_path" => {
                     manager
                         .update_config_item("danmaku_option", serde_json::to_value(&config.danmaku_option)?)
                         .await
@@ -12493,6 +13517,17 @@ pub async fn update_config_internal(
                         )
                         .await
                 }
+                "quality_upgrade_enabled"
+                | "quality_upgrade_window_hours"
+                | "quality_upgrade_check_interval_hours"
+                | "quality_upgrade_max_pages_per_run" => {
+                    manager
+                        .update_config_item(
+                            "quality_upgrade_policy",
+                            serde_json::to_value(&config.quality_upgrade_policy)?,
+                        )
+                        .await
+                }
                 // NFO配置字段
                 "nfo_config" => {
                     manager
@@ -12572,6 +13607,11 @@ pub async fn update_config_internal(
                         )
                         .await
                 }
+                "cross_source_dedup" => {
+                    manager
+                        .update_config_item("cross_source_dedup", serde_json::to_value(config.cross_source_dedup)?)
+                        .await
+                }
                 // API Token
                 "auth_token" => {
                     manager
@@ -14562,6 +15602,11 @@ pub struct QueueStatusResponse {
     pub video_delete_queue: QueueInfo,
     pub add_queue: QueueInfo,
     pub danmaku_queue: QueueInfo,
+    pub danmaku_rerender_queue: QueueInfo,
+    /// 正在执行的弹幕重新渲染任务进度
+    pub danmaku_rerender_progress: Option<crate::task::RerenderDanmakuProgress>,
+    pub subtitle_generation_queue: QueueInfo,
+    pub quality_upgrade_queue: QueueInfo,
     pub config_queue: ConfigQueueInfo,
 }
 
@@ -14606,8 +15651,8 @@ pub async fn get_queue_status() -> Result<ApiResponse<QueueStatusResponse>, ApiE
 
 async fn load_queue_status_response() -> QueueStatusResponse {
     use crate::task::{
-        ADD_TASK_QUEUE, CONFIG_TASK_QUEUE, DELETE_TASK_QUEUE, REFRESH_DANMAKU_TASK_QUEUE, TASK_CONTROLLER,
-        VIDEO_DELETE_TASK_QUEUE,
+        ADD_TASK_QUEUE, CONFIG_TASK_QUEUE, DELETE_TASK_QUEUE, QUALITY_UPGRADE_TASK_QUEUE, REFRESH_DANMAKU_TASK_QUEUE,
+        RERENDER_DANMAKU_TASK_QUEUE, SUBTITLE_GENERATION_TASK_QUEUE, TASK_CONTROLLER, VIDEO_DELETE_TASK_QUEUE,
     };
 
     // 获取扫描状态
@@ -14674,6 +15719,42 @@ async fn load_queue_status_response() -> QueueStatusResponse {
         })
         .collect();
 
+    let rerender_raw_tasks = RERENDER_DANMAKU_TASK_QUEUE.list_tasks().await;
+    let rerender_queue_length = rerender_raw_tasks.len();
+    let rerender_tasks = rerender_raw_tasks
+        .into_iter()
+        .map(|task| QueueTaskInfo {
+            description: task.description(),
+            task_id: task.task_id,
+            task_type: "rerender_danmaku".to_string(),
+            created_at: now_standard_string(),
+        })
+        .collect();
+
+    let subtitle_raw_tasks = SUBTITLE_GENERATION_TASK_QUEUE.list_tasks().await;
+    let subtitle_queue_length = subtitle_raw_tasks.len();
+    let subtitle_tasks = subtitle_raw_tasks
+        .into_iter()
+        .map(|task| QueueTaskInfo {
+            task_id: task.task_id,
+            task_type: "generate_subtitle".to_string(),
+            description: format!("语音识别字幕 分页ID={}", task.page_id),
+            created_at: now_standard_string(),
+        })
+        .collect();
+
+    let upgrade_raw_tasks = QUALITY_UPGRADE_TASK_QUEUE.list_tasks().await;
+    let upgrade_queue_length = upgrade_raw_tasks.len();
+    let upgrade_tasks = upgrade_raw_tasks
+        .into_iter()
+        .map(|task| QueueTaskInfo {
+            task_id: task.task_id,
+            task_type: "upgrade_page_quality".to_string(),
+            description: format!("画质升级复查 分页ID={}", task.page_id),
+            created_at: now_standard_string(),
+        })
+        .collect();
+
     // 获取配置队列状态
     let config_update_raw_tasks = CONFIG_TASK_QUEUE.list_update_tasks().await;
     let config_reload_raw_tasks = CONFIG_TASK_QUEUE.list_reload_tasks().await;
@@ -14723,6 +15804,22 @@ async fn load_queue_status_response() -> QueueStatusResponse {
             is_processing: danmaku_is_processing,
             tasks: danmaku_tasks,
         },
+        danmaku_rerender_queue: QueueInfo {
+            length: rerender_queue_length,
+            is_processing: RERENDER_DANMAKU_TASK_QUEUE.is_processing(),
+            tasks: rerender_tasks,
+        },
+        danmaku_rerender_progress: RERENDER_DANMAKU_TASK_QUEUE.progress(),
+        subtitle_generation_queue: QueueInfo {
+            length: subtitle_queue_length,
+            is_processing: SUBTITLE_GENERATION_TASK_QUEUE.is_processing(),
+            tasks: subtitle_tasks,
+        },
+        quality_upgrade_queue: QueueInfo {
+            length: upgrade_queue_length,
+            is_processing: QUALITY_UPGRADE_TASK_QUEUE.is_processing(),
+            tasks: upgrade_tasks,
+        },
         config_queue: ConfigQueueInfo {
             update_length: config_update_length,
             reload_length: config_reload_length,
@@ -14820,13 +15917,8 @@ async fn maybe_cleanup_proxy_image_cache(db: &DatabaseConnection, now: DateTime<
         DELETE FROM image_proxy_cache
         WHERE expires_at_unix <= ?
     "#;
-    let backend = db.get_database_backend();
     let result = db
-        .execute(Statement::from_sql_and_values(
-            backend,
-            cleanup_sql,
-            vec![now.timestamp().into()],
-        ))
+        .execute(crate::database::statement(cleanup_sql, vec![now.timestamp().into()]))
         .await;
 
     match result {
@@ -14852,15 +15944,10 @@ async fn load_proxy_image_cache_meta(
         WHERE cache_key = ?
         LIMIT 1
     "#;
-    let backend = db.get_database_backend();
     debug!("图片缓存查询开始: key={}, url={}", cache_key, summarize_image_url(url));
 
     let row = match db
-        .query_one(Statement::from_sql_and_values(
-            backend,
-            select_sql,
-            vec![cache_key.clone().into()],
-        ))
+        .query_one(crate::database::statement(select_sql, vec![cache_key.clone().into()]))
         .await
     {
         Ok(row) => row,
@@ -14912,11 +15999,7 @@ async fn load_proxy_image_cache_meta(
         );
         let delete_sql = "DELETE FROM image_proxy_cache WHERE cache_key = ?";
         if let Err(e) = db
-            .execute(Statement::from_sql_and_values(
-                backend,
-                delete_sql,
-                vec![cache_key.into()],
-            ))
+            .execute(crate::database::statement(delete_sql, vec![cache_key.into()]))
             .await
         {
             debug!("图片缓存过期删除失败（不影响继续回源）: error={}", e);
@@ -14964,9 +16047,7 @@ async fn store_proxy_image_cache(
             updated_at_unix = excluded.updated_at_unix
     "#;
 
-    let backend = db.get_database_backend();
-    db.execute(Statement::from_sql_and_values(
-        backend,
+    db.execute(crate::database::statement(
         upsert_sql,
         vec![
             cache_key.clone().into(),
@@ -15409,6 +16490,7 @@ pub async fn update_config_item_internal(
 
     // 创建配置管理器
     let manager = ConfigManager::new(db.as_ref().clone());
+    crate::config::ensure_config_writable([key.as_str()])?;
 
     // 更新配置项
     if let Err(e) = manager.update_config_item(&key, request.value.clone()).await {
@@ -15440,6 +16522,7 @@ pub async fn batch_update_config_internal(
     use crate::config::ConfigManager;
 
     let manager = ConfigManager::new(db.as_ref().clone());
+    crate::config::ensure_config_writable(request.items.keys().map(String::as_str))?;
 
     // 批量更新配置项
     for (key, value) in request.items {
@@ -15514,6 +16597,7 @@ pub async fn get_config_history(
             old_value: change.old_value,
             new_value: change.new_value,
             changed_at: change.changed_at,
+            changed_by: change.changed_by,
         })
         .collect();
 
@@ -15758,21 +16842,10 @@ pub async fn setup_auth_token(
     Ok(ApiResponse::ok(response))
 }
 
-/// 更新B站登录凭证
-#[utoipa::path(
-    put,
-    path = "/api/credential",
-    request_body = UpdateCredentialRequest,
-    responses(
-        (status = 200, description = "凭证更新成功", body = UpdateCredentialResponse),
-        (status = 400, description = "请求参数错误", body = String),
-        (status = 500, description = "服务器内部错误", body = String)
-    )
-)]
-pub async fn update_credential(
-    Extension(db): Extension<Arc<DatabaseConnection>>,
-    axum::Json(params): axum::Json<crate::api::request::UpdateCredentialRequest>,
-) -> Result<ApiResponse<crate::api::response::UpdateCredentialResponse>, ApiError> {
+/// 校验请求中的凭证字段并补全 buvid4
+async fn credential_from_request(
+    params: crate::api::request::UpdateCredentialRequest,
+) -> Result<crate::bilibili::Credential, ApiError> {
     // 验证必填字段
     if params.sessdata.trim().is_empty()
         || params.bili_jct.trim().is_empty()
@@ -15830,6 +16903,26 @@ pub async fn update_credential(
         tracing::debug!("使用用户提供的 DedeUserID__ckMd5");
     }
 
+    Ok(new_credential)
+}
+
+/// 更新B站登录凭证
+#[utoipa::path(
+    put,
+    path = "/api/credential",
+    request_body = UpdateCredentialRequest,
+    responses(
+        (status = 200, description = "凭证更新成功", body = UpdateCredentialResponse),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn update_credential(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateCredentialRequest>,
+) -> Result<ApiResponse<crate::api::response::UpdateCredentialResponse>, ApiError> {
+    let new_credential = credential_from_request(params).await?;
+
     // 更新配置中的凭证
     let config = crate::config::reload_config();
     config.credential.store(Some(std::sync::Arc::new(new_credential)));
@@ -15872,12 +16965,1232 @@ pub async fn update_credential(
         }
     }
 
-    let response = crate::api::response::UpdateCredentialResponse {
-        success: true,
-        message: "B站凭证更新成功".to_string(),
-    };
+    let response = crate::api::response::UpdateCredentialResponse {
+        success: true,
+        message: "B站凭证更新成功".to_string(),
+    };
+
+    Ok(ApiResponse::ok(response))
+}
+
+/// 统计绑定到指定B站账号的视频源数量
+async fn count_sources_bound_to_account(db: &DatabaseConnection, account_id: i32) -> Result<u64> {
+    Ok(collection::Entity::find()
+        .filter(collection::Column::CredentialId.eq(account_id))
+        .count(db)
+        .await?
+        + favorite::Entity::find()
+            .filter(favorite::Column::CredentialId.eq(account_id))
+            .count(db)
+            .await?
+        + submission::Entity::find()
+            .filter(submission::Column::CredentialId.eq(account_id))
+            .count(db)
+            .await?
+        + watch_later::Entity::find()
+            .filter(watch_later::Column::CredentialId.eq(account_id))
+            .count(db)
+            .await?
+        + video_source::Entity::find()
+            .filter(video_source::Column::CredentialId.eq(account_id))
+            .count(db)
+            .await?
+        + ranking::Entity::find()
+            .filter(ranking::Column::CredentialId.eq(account_id))
+            .count(db)
+            .await?)
+}
+
+async fn bili_accounts_response(db: &DatabaseConnection) -> Result<crate::api::response::BiliAccountsResponse> {
+    let config = crate::config::reload_config();
+    let mut accounts = Vec::with_capacity(config.bili_accounts.len());
+    for account in &config.bili_accounts {
+        accounts.push(crate::api::response::BiliAccountInfo {
+            id: account.id,
+            name: account.name.clone(),
+            dedeuserid: account.credential.dedeuserid.clone(),
+            bound_sources: count_sources_bound_to_account(db, account.id).await?,
+        });
+    }
+    Ok(crate::api::response::BiliAccountsResponse { accounts })
+}
+
+/// 保存额外B站账号列表并重新加载配置
+async fn save_bili_accounts(db: &DatabaseConnection, accounts: &[crate::config::BiliAccount]) -> Result<(), ApiError> {
+    let config_manager = crate::config::ConfigManager::new(db.clone());
+    config_manager
+        .update_config_item("bili_accounts", serde_json::to_value(accounts)?)
+        .await
+        .map_err(|e| ApiError::from(anyhow!("保存B站账号失败: {}", e)))?;
+    crate::config::reload_config_bundle()
+        .await
+        .map_err(|e| ApiError::from(anyhow!("重新加载配置失败: {}", e)))?;
+    Ok(())
+}
+
+/// 获取额外绑定的B站账号列表
+#[utoipa::path(
+    get,
+    path = "/api/credential/accounts",
+    responses(
+        (status = 200, description = "B站账号列表", body = ApiResponse<crate::api::response::BiliAccountsResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn get_bili_accounts(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::BiliAccountsResponse>, ApiError> {
+    Ok(ApiResponse::ok(bili_accounts_response(db.as_ref()).await?))
+}
+
+/// 新增额外B站账号，视频源可通过 credential_id 绑定该账号
+#[utoipa::path(
+    post,
+    path = "/api/credential/accounts",
+    request_body = crate::api::request::UpsertBiliAccountRequest,
+    responses(
+        (status = 200, description = "B站账号新增成功", body = ApiResponse<crate::api::response::BiliAccountsResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn add_bili_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::UpsertBiliAccountRequest>,
+) -> Result<ApiResponse<crate::api::response::BiliAccountsResponse>, ApiError> {
+    let credential = credential_from_request(params.credential).await?;
+    let config = crate::config::reload_config();
+    let mut accounts = config.bili_accounts.clone();
+    let id = config.next_bili_account_id();
+    // 先推进ID序列再保存账号，即使保存账号失败也不会把同一个ID分配两次
+    crate::config::ConfigManager::new(db.as_ref().clone())
+        .update_config_item("bili_account_next_id", serde_json::json!(id + 1))
+        .await
+        .map_err(|e| ApiError::from(anyhow!("保存B站账号ID序列失败: {}", e)))?;
+    let user_id = credential.dedeuserid.parse::<i64>().ok();
+    accounts.push(crate::config::BiliAccount {
+        id,
+        name: params.name.trim().to_string(),
+        credential,
+    });
+    save_bili_accounts(db.as_ref(), &accounts).await?;
+
+    if let Some(user_id) = user_id {
+        if let Err(e) = crate::hardware::HardwareFingerprint::init_for_account(id, user_id, db.as_ref()).await {
+            warn!("B站账号 {} 的硬件指纹初始化失败: {:#}", id, e);
+        }
+    }
+    info!("已新增B站账号 {}", id);
+
+    Ok(ApiResponse::ok(bili_accounts_response(db.as_ref()).await?))
+}
+
+/// 更新额外B站账号的备注名与凭证
+#[utoipa::path(
+    put,
+    path = "/api/credential/accounts/{id}",
+    params(("id" = i32, Path, description = "B站账号ID")),
+    request_body = crate::api::request::UpsertBiliAccountRequest,
+    responses(
+        (status = 200, description = "B站账号更新成功", body = ApiResponse<crate::api::response::BiliAccountsResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn update_bili_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+    axum::Json(params): axum::Json<crate::api::request::UpsertBiliAccountRequest>,
+) -> Result<ApiResponse<crate::api::response::BiliAccountsResponse>, ApiError> {
+    let credential = credential_from_request(params.credential).await?;
+    let mut accounts = crate::config::reload_config().bili_accounts.clone();
+    let account = accounts
+        .iter_mut()
+        .find(|account| account.id == id)
+        .ok_or_else(|| anyhow!("B站账号 {} 不存在", id))?;
+    let user_changed = account.credential.dedeuserid != credential.dedeuserid;
+    let user_id = credential.dedeuserid.parse::<i64>().ok();
+    account.name = params.name.trim().to_string();
+    account.credential = credential;
+    save_bili_accounts(db.as_ref(), &accounts).await?;
+
+    if user_changed {
+        crate::hardware::HardwareFingerprint::forget_account(id);
+        if let Some(user_id) = user_id {
+            if let Err(e) = crate::hardware::HardwareFingerprint::init_for_account(id, user_id, db.as_ref()).await {
+                warn!("B站账号 {} 的硬件指纹初始化失败: {:#}", id, e);
+            }
+        }
+    }
+    info!("已更新B站账号 {}", id);
+
+    Ok(ApiResponse::ok(bili_accounts_response(db.as_ref()).await?))
+}
+
+/// 删除额外B站账号，仍被视频源绑定时拒绝删除
+#[utoipa::path(
+    delete,
+    path = "/api/credential/accounts/{id}",
+    params(("id" = i32, Path, description = "B站账号ID")),
+    responses(
+        (status = 200, description = "B站账号删除成功", body = ApiResponse<crate::api::response::BiliAccountsResponse>),
+        (status = 400, description = "账号仍被视频源绑定", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn delete_bili_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::BiliAccountsResponse>, ApiError> {
+    let mut accounts = crate::config::reload_config().bili_accounts.clone();
+    if !accounts.iter().any(|account| account.id == id) {
+        return Err(anyhow!("B站账号 {} 不存在", id).into());
+    }
+    let bound_sources = count_sources_bound_to_account(db.as_ref(), id).await?;
+    if bound_sources > 0 {
+        return Err(anyhow!("B站账号 {} 仍被 {} 个视频源绑定，请先解除绑定", id, bound_sources).into());
+    }
+    accounts.retain(|account| account.id != id);
+    save_bili_accounts(db.as_ref(), &accounts).await?;
+    crate::hardware::HardwareFingerprint::forget_account(id);
+    info!("已删除B站账号 {}", id);
+
+    Ok(ApiResponse::ok(bili_accounts_response(db.as_ref()).await?))
+}
+
+/// 使用用户名与密码登录管理页，返回会话令牌
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    request_body = crate::api::request::LoginRequest,
+    responses(
+        (status = 200, description = "登录成功", body = ApiResponse<crate::api::response::LoginResponse>),
+        (status = 401, description = "用户名或密码错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn login(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::LoginRequest>,
+) -> Result<axum::response::Response, ApiError> {
+    use axum::response::IntoResponse;
+
+    let username = params.username.trim();
+    let Some((token, expires_at, user)) = crate::auth::users::login(db.as_ref(), username, &params.password).await?
+    else {
+        warn!("管理页用户 {} 登录失败", username);
+        return Ok(ApiResponse::unauthorized("用户名或密码错误".to_string()).into_response());
+    };
+    let role = crate::auth::users::Role::parse(&user.role).ok_or_else(|| anyhow!("用户角色无效: {}", user.role))?;
+    info!("管理页用户 {} 登录成功", user.username);
+
+    Ok(ApiResponse::ok(crate::api::response::LoginResponse {
+        token,
+        expires_at,
+        username: user.username,
+        role,
+    })
+    .into_response())
+}
+
+/// 注销当前会话
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    responses(
+        (status = 200, description = "注销成功", body = ApiResponse<bool>),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn logout(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    headers: HeaderMap,
+) -> Result<ApiResponse<bool>, ApiError> {
+    let Some(token) = headers.get("Authorization").and_then(|value| value.to_str().ok()) else {
+        return Ok(ApiResponse::ok(false));
+    };
+    crate::auth::users::logout(db.as_ref(), token.strip_prefix("Bearer ").unwrap_or(token)).await?;
+    Ok(ApiResponse::ok(true))
+}
+
+/// 获取当前请求的身份与角色
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    responses(
+        (status = 200, description = "成功获取当前身份", body = ApiResponse<crate::api::response::CurrentIdentityResponse>)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_current_identity(
+    Extension(identity): Extension<crate::auth::users::Identity>,
+) -> Result<ApiResponse<crate::api::response::CurrentIdentityResponse>, ApiError> {
+    Ok(ApiResponse::ok(crate::api::response::CurrentIdentityResponse {
+        user_id: identity.user_id,
+        username: identity.username,
+        role: identity.role,
+    }))
+}
+
+async fn api_users_response(db: &DatabaseConnection) -> Result<crate::api::response::ApiUsersResponse> {
+    let users = bili_sync_entity::api_user::Entity::find()
+        .order_by_asc(bili_sync_entity::api_user::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| crate::api::response::ApiUserInfo {
+            id: user.id,
+            username: user.username,
+            role: user.role,
+            enabled: user.enabled,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        })
+        .collect();
+    Ok(crate::api::response::ApiUsersResponse { users })
+}
+
+/// 获取管理页用户列表
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    responses(
+        (status = 200, description = "成功获取用户列表", body = ApiResponse<crate::api::response::ApiUsersResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_api_users(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::ApiUsersResponse>, ApiError> {
+    Ok(ApiResponse::ok(api_users_response(db.as_ref()).await?))
+}
+
+/// 新增管理页用户
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    request_body = crate::api::request::CreateApiUserRequest,
+    responses(
+        (status = 200, description = "用户新增成功", body = ApiResponse<crate::api::response::ApiUsersResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn add_api_user(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::CreateApiUserRequest>,
+) -> Result<ApiResponse<crate::api::response::ApiUsersResponse>, ApiError> {
+    use bili_sync_entity::api_user;
+
+    let username = params.username.trim();
+    crate::auth::users::validate_username(username).map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    crate::auth::users::validate_password(&params.password).map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    let exists = api_user::Entity::find()
+        .filter(api_user::Column::Username.eq(username))
+        .count(db.as_ref())
+        .await?
+        > 0;
+    if exists {
+        return Err(InnerApiError::BadRequest(format!("用户名 {} 已存在", username)).into());
+    }
+
+    let now = now_standard_string();
+    api_user::ActiveModel {
+        username: Set(username.to_string()),
+        password_hash: Set(crate::auth::users::hash_password(&params.password)),
+        role: Set(params.role.as_str().to_string()),
+        enabled: Set(true),
+        created_at: Set(now.clone()),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db.as_ref())
+    .await?;
+    info!("已新增管理页用户 {}（{}）", username, params.role.label());
+
+    Ok(ApiResponse::ok(api_users_response(db.as_ref()).await?))
+}
+
+/// 更新管理页用户的密码、角色或启用状态
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}",
+    params(("id" = i32, Path, description = "用户ID")),
+    request_body = crate::api::request::UpdateApiUserRequest,
+    responses(
+        (status = 200, description = "用户更新成功", body = ApiResponse<crate::api::response::ApiUsersResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 404, description = "用户不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn update_api_user(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Extension(identity): Extension<crate::auth::users::Identity>,
+    Path(id): Path<i32>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateApiUserRequest>,
+) -> Result<ApiResponse<crate::api::response::ApiUsersResponse>, ApiError> {
+    use bili_sync_entity::api_user;
+
+    let user = api_user::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    let demotes_self = identity.user_id == Some(id)
+        && (params.enabled == Some(false) || params.role.is_some_and(|role| role < crate::auth::users::Role::Admin));
+    if demotes_self {
+        return Err(InnerApiError::BadRequest("不能禁用或降级当前登录的用户".to_string()).into());
+    }
+
+    let mut revoke_sessions = false;
+    let mut active: api_user::ActiveModel = user.into();
+    if let Some(password) = params.password.as_deref() {
+        crate::auth::users::validate_password(password).map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+        active.password_hash = Set(crate::auth::users::hash_password(password));
+        revoke_sessions = true;
+    }
+    if let Some(role) = params.role {
+        active.role = Set(role.as_str().to_string());
+    }
+    if let Some(enabled) = params.enabled {
+        active.enabled = Set(enabled);
+        revoke_sessions |= !enabled;
+    }
+    active.updated_at = Set(now_standard_string());
+    let user = active.update(db.as_ref()).await?;
+    if revoke_sessions {
+        crate::auth::users::revoke_user_sessions(db.as_ref(), id).await?;
+    }
+    info!("已更新管理页用户 {}", user.username);
+
+    Ok(ApiResponse::ok(api_users_response(db.as_ref()).await?))
+}
+
+/// 删除管理页用户及其全部会话
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    params(("id" = i32, Path, description = "用户ID")),
+    responses(
+        (status = 200, description = "用户删除成功", body = ApiResponse<crate::api::response::ApiUsersResponse>),
+        (status = 400, description = "不能删除当前登录的用户", body = String),
+        (status = 404, description = "用户不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn delete_api_user(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Extension(identity): Extension<crate::auth::users::Identity>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::ApiUsersResponse>, ApiError> {
+    use bili_sync_entity::api_user;
+
+    if identity.user_id == Some(id) {
+        return Err(InnerApiError::BadRequest("不能删除当前登录的用户".to_string()).into());
+    }
+    let user = api_user::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    crate::auth::users::revoke_user_sessions(db.as_ref(), id).await?;
+    api_user::Entity::delete_by_id(id).exec(db.as_ref()).await?;
+    info!("已删除管理页用户 {}", user.username);
+
+    Ok(ApiResponse::ok(api_users_response(db.as_ref()).await?))
+}
+
+fn api_key_info(model: bili_sync_entity::api_key::Model) -> crate::api::response::ApiKeyInfo {
+    crate::api::response::ApiKeyInfo {
+        id: model.id,
+        name: model.name,
+        key_prefix: model.key_prefix,
+        scopes: crate::auth::api_keys::decode_scopes(&model.scopes),
+        expires_at: model.expires_at,
+        last_used_at: model.last_used_at,
+        revoked_at: model.revoked_at,
+        created_by: model.created_by,
+        created_at: model.created_at,
+    }
+}
+
+/// 获取 API 密钥列表（不含密钥明文）
+#[utoipa::path(
+    get,
+    path = "/api/api-keys",
+    responses(
+        (status = 200, description = "成功获取 API 密钥列表", body = ApiResponse<crate::api::response::ApiKeysResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_api_keys(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::ApiKeysResponse>, ApiError> {
+    let keys = bili_sync_entity::api_key::Entity::find()
+        .order_by_desc(bili_sync_entity::api_key::Column::Id)
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .map(api_key_info)
+        .collect();
+    Ok(ApiResponse::ok(crate::api::response::ApiKeysResponse { keys }))
+}
+
+/// 新增 API 密钥，密钥明文只在本次响应中返回
+#[utoipa::path(
+    post,
+    path = "/api/api-keys",
+    request_body = crate::api::request::CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "API 密钥创建成功", body = ApiResponse<crate::api::response::CreateApiKeyResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn add_api_key(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Extension(identity): Extension<crate::auth::users::Identity>,
+    axum::Json(params): axum::Json<crate::api::request::CreateApiKeyRequest>,
+) -> Result<ApiResponse<crate::api::response::CreateApiKeyResponse>, ApiError> {
+    let name = params.name.trim();
+    if name.is_empty() || name.chars().count() > 64 {
+        return Err(InnerApiError::BadRequest("API 密钥名称长度需在 1-64 个字符之间".to_string()).into());
+    }
+    if params.scopes.is_empty() {
+        return Err(InnerApiError::BadRequest("至少需要选择一个权限范围".to_string()).into());
+    }
+    let expires_at = match params.expires_in_days {
+        Some(0) => return Err(InnerApiError::BadRequest("有效天数必须大于 0".to_string()).into()),
+        Some(days) => Some(to_standard_string(
+            crate::utils::time_format::beijing_now() + chrono::Duration::days(days as i64),
+        )),
+        None => None,
+    };
+
+    let (key, model) =
+        crate::auth::api_keys::create_api_key(db.as_ref(), name, &params.scopes, expires_at, &identity.username)
+            .await?;
+    info!(
+        "{} 新增了 API 密钥 {}（{}）",
+        identity.username, model.name, model.scopes
+    );
+
+    Ok(ApiResponse::ok(crate::api::response::CreateApiKeyResponse {
+        key,
+        info: api_key_info(model),
+    }))
+}
+
+/// 吊销 API 密钥，吊销后立即失效
+#[utoipa::path(
+    delete,
+    path = "/api/api-keys/{id}",
+    params(("id" = i32, Path, description = "API 密钥ID")),
+    responses(
+        (status = 200, description = "API 密钥已吊销", body = ApiResponse<crate::api::response::ApiKeyInfo>),
+        (status = 404, description = "API 密钥不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn revoke_api_key(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::ApiKeyInfo>, ApiError> {
+    if bili_sync_entity::api_key::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .is_none()
+    {
+        return Err(InnerApiError::NotFound(id).into());
+    }
+    let model = crate::auth::api_keys::revoke_api_key(db.as_ref(), id).await?;
+    info!("已吊销 API 密钥 {}", model.name);
+    Ok(ApiResponse::ok(api_key_info(model)))
+}
+
+/// 生成带签名、会过期的视频流链接，可分享给未登录的家人播放单个视频
+#[utoipa::path(
+    post,
+    path = "/api/stream-links",
+    request_body = crate::api::request::CreateStreamLinkRequest,
+    responses(
+        (status = 200, description = "分享链接生成成功", body = ApiResponse<crate::api::response::StreamLinkResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn create_stream_link(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::CreateStreamLinkRequest>,
+) -> Result<ApiResponse<crate::api::response::StreamLinkResponse>, ApiError> {
+    use crate::auth::stream_links::{signed_query, StreamResource, DEFAULT_LINK_TTL_HOURS, MAX_LINK_TTL_HOURS};
+
+    let ttl_hours = params.expires_in_hours.unwrap_or(DEFAULT_LINK_TTL_HOURS);
+    if ttl_hours == 0 || ttl_hours > MAX_LINK_TTL_HOURS {
+        return Err(InnerApiError::BadRequest(format!("有效时长需在 1-{} 小时之间", MAX_LINK_TTL_HOURS)).into());
+    }
+
+    let (path, expires) = match (params.video_id.as_deref(), params.url.as_deref()) {
+        (Some(video_id), None) => {
+            crate::api::video_stream::find_video_file(video_id, db.as_ref())
+                .await
+                .map_err(|e| InnerApiError::BadRequest(format!("视频 {} 无法播放: {:#}", video_id, e)))?;
+            let (query, expires) = signed_query(db.as_ref(), StreamResource::LocalVideo(video_id), ttl_hours).await?;
+            (format!("/api/videos/stream/{}?{}", video_id, query), expires)
+        }
+        (None, Some(url)) => {
+            let (query, expires) = signed_query(db.as_ref(), StreamResource::Proxy(url), ttl_hours).await?;
+            let encoded_url = serde_urlencoded::to_string([("url", url)])?;
+            (format!("/api/videos/proxy-stream?{}&{}", encoded_url, query), expires)
+        }
+        _ => return Err(InnerApiError::BadRequest("video_id 与 url 需且仅需提供一个".to_string()).into()),
+    };
+    Ok(ApiResponse::ok(crate::api::response::StreamLinkResponse {
+        path,
+        expires_at: crate::utils::time_format::timestamp_to_beijing_string(expires),
+    }))
+}
+
+/// 获取写操作审计日志
+#[utoipa::path(
+    get,
+    path = "/api/audit-log",
+    params(crate::api::request::AuditLogRequest),
+    responses(
+        (status = 200, description = "成功获取审计日志", body = ApiResponse<crate::api::response::AuditLogResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_audit_log(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<crate::api::request::AuditLogRequest>,
+) -> Result<ApiResponse<crate::api::response::AuditLogResponse>, ApiError> {
+    use bili_sync_entity::audit_log;
+
+    let mut query = audit_log::Entity::find().order_by_desc(audit_log::Column::Id);
+    if let Some(actor) = params.actor.as_deref().filter(|actor| !actor.is_empty()) {
+        query = query.filter(audit_log::Column::Actor.eq(actor));
+    }
+    let entries: Vec<_> = query
+        .limit(params.limit.unwrap_or(100).min(1000))
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .map(|entry| crate::api::response::AuditLogEntry {
+            id: entry.id,
+            actor: entry.actor,
+            role: entry.role,
+            method: entry.method,
+            path: entry.path,
+            status_code: entry.status_code,
+            created_at: entry.created_at,
+        })
+        .collect();
+
+    Ok(ApiResponse::ok(crate::api::response::AuditLogResponse {
+        total: entries.len(),
+        entries,
+    }))
+}
+
+/// 解析逗号分隔的事件类型，为空表示全部
+fn parse_event_kinds(value: Option<&str>) -> Result<Vec<crate::events::EventKind>, InnerApiError> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+        .map(|kind| {
+            crate::events::EventKind::parse(kind)
+                .ok_or_else(|| InnerApiError::BadRequest(format!("未知的事件类型: {}", kind)))
+        })
+        .collect()
+}
+
+fn event_record_to_sse(record: &crate::events::EventRecord) -> Option<Event> {
+    match serde_json::to_string(record) {
+        Ok(payload) => Some(
+            Event::default()
+                .id(record.id.to_string())
+                .event(record.kind.as_str())
+                .data(payload),
+        ),
+        Err(err) => {
+            warn!("序列化事件 {} 失败: {}", record.id, err);
+            None
+        }
+    }
+}
+
+/// 获取事件列表，指定 since 时按 ID 正序返回其后的事件，否则返回最近的事件
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    params(crate::api::request::EventsRequest),
+    responses(
+        (status = 200, description = "成功获取事件列表", body = ApiResponse<crate::api::response::EventsResponse>),
+        (status = 400, description = "未知的事件类型", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_events(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<crate::api::request::EventsRequest>,
+) -> Result<ApiResponse<crate::api::response::EventsResponse>, ApiError> {
+    let kinds = parse_event_kinds(params.kinds.as_deref())?;
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    let events = match params.since {
+        Some(since) => crate::events::list_events(db.as_ref(), Some(since), &kinds, limit).await?,
+        None => crate::events::latest_events(db.as_ref(), &kinds, limit).await?,
+    };
+    Ok(ApiResponse::ok(crate::api::response::EventsResponse { events }))
+}
+
+/// 实时推送事件，断线重连时根据 since 或 Last-Event-ID 补发错过的事件
+pub async fn stream_events(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    headers: HeaderMap,
+    Query(params): Query<crate::api::request::EventStreamRequest>,
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    const REPLAY_BATCH_SIZE: u64 = 500;
+
+    let kinds = parse_event_kinds(params.kinds.as_deref())?;
+    let since = params.since.or_else(|| {
+        headers
+            .get("last-event-id")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+    });
+    // 先订阅再确定起点，避免两者之间产生的事件被遗漏
+    let mut receiver = crate::events::subscribe();
+    let mut last_id = match since {
+        Some(since) => Some(since),
+        None => crate::events::latest_events(db.as_ref(), &[], 1)
+            .await?
+            .first()
+            .map(|record| record.id),
+    };
+
+    let stream = async_stream::stream! {
+        yield Ok(Event::default().event("ready").data("connected"));
+
+        let mut need_replay = since.is_some();
+        loop {
+            if need_replay {
+                need_replay = false;
+                loop {
+                    match crate::events::list_events(db.as_ref(), last_id, &kinds, REPLAY_BATCH_SIZE).await {
+                        Ok(records) => {
+                            let done = (records.len() as u64) < REPLAY_BATCH_SIZE;
+                            for record in records {
+                                last_id = Some(record.id);
+                                if let Some(event) = event_record_to_sse(&record) {
+                                    yield Ok(event);
+                                }
+                            }
+                            if done {
+                                break;
+                            }
+                        }
+                        Err(err) => {
+                            warn!("补发事件失败: {:#}", err);
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+
+            match receiver.recv().await {
+                Ok(record) => {
+                    if last_id.is_some_and(|id| record.id <= id)
+                        || !(kinds.is_empty() || kinds.contains(&record.kind))
+                    {
+                        continue;
+                    }
+                    last_id = Some(record.id);
+                    if let Some(event) = event_record_to_sse(&record) {
+                        yield Ok(event);
+                    }
+                }
+                // 实时通道积压时从数据库补齐，保证事件不丢失
+                Err(broadcast::error::RecvError::Lagged(_)) => need_replay = true,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(StdDuration::from_secs(15)).text("keep-alive")))
+}
+
+fn webhook_info(model: bili_sync_entity::webhook::Model) -> crate::api::response::WebhookInfo {
+    crate::api::response::WebhookInfo {
+        id: model.id,
+        name: model.name,
+        url: model.url,
+        event_kinds: crate::events::decode_kinds(&model.event_kinds),
+        enabled: model.enabled,
+        created_at: model.created_at,
+    }
+}
+
+fn validate_webhook_fields(name: &str, url: &str) -> Result<(), InnerApiError> {
+    if name.is_empty() || name.chars().count() > 64 {
+        return Err(InnerApiError::BadRequest(
+            "Webhook 名称长度需在 1-64 个字符之间".to_string(),
+        ));
+    }
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        _ => Err(InnerApiError::BadRequest(format!(
+            "Webhook 地址无效，需要以 http:// 或 https:// 开头: {}",
+            url
+        ))),
+    }
+}
+
+/// 获取 Webhook 订阅列表（不含签名密钥）
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    responses(
+        (status = 200, description = "成功获取 Webhook 列表", body = ApiResponse<crate::api::response::WebhooksResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_webhooks(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::WebhooksResponse>, ApiError> {
+    let webhooks = bili_sync_entity::webhook::Entity::find()
+        .order_by_asc(bili_sync_entity::webhook::Column::Id)
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .map(webhook_info)
+        .collect();
+    Ok(ApiResponse::ok(crate::api::response::WebhooksResponse { webhooks }))
+}
+
+/// 新增 Webhook 订阅，签名密钥只在本次响应中返回
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    request_body = crate::api::request::CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook 创建成功", body = ApiResponse<crate::api::response::SaveWebhookResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn add_webhook(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::CreateWebhookRequest>,
+) -> Result<ApiResponse<crate::api::response::SaveWebhookResponse>, ApiError> {
+    let name = params.name.trim();
+    let url = params.url.trim();
+    validate_webhook_fields(name, url)?;
+
+    let secret = crate::events::generate_secret();
+    let model = bili_sync_entity::webhook::ActiveModel {
+        name: Set(name.to_string()),
+        url: Set(url.to_string()),
+        secret: Set(secret.clone()),
+        event_kinds: Set(crate::events::encode_kinds(&params.event_kinds)),
+        enabled: Set(true),
+        created_at: Set(now_standard_string()),
+        ..Default::default()
+    }
+    .insert(db.as_ref())
+    .await?;
+    info!("已新增 Webhook {}（{}）", model.name, model.url);
+
+    Ok(ApiResponse::ok(crate::api::response::SaveWebhookResponse {
+        info: webhook_info(model),
+        secret: Some(secret),
+    }))
+}
+
+/// 修改 Webhook 订阅，可选择轮换签名密钥
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    params(("id" = i32, Path, description = "Webhook ID")),
+    request_body = crate::api::request::UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook 修改成功", body = ApiResponse<crate::api::response::SaveWebhookResponse>),
+        (status = 400, description = "请求参数错误", body = String),
+        (status = 404, description = "Webhook 不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn update_webhook(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+    axum::Json(params): axum::Json<crate::api::request::UpdateWebhookRequest>,
+) -> Result<ApiResponse<crate::api::response::SaveWebhookResponse>, ApiError> {
+    use sea_orm::IntoActiveModel;
+
+    let model = bili_sync_entity::webhook::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    let name = params.name.as_deref().map(str::trim).unwrap_or(&model.name).to_string();
+    let url = params.url.as_deref().map(str::trim).unwrap_or(&model.url).to_string();
+    validate_webhook_fields(&name, &url)?;
+
+    let secret = params.rotate_secret.then(crate::events::generate_secret);
+    let mut active = model.into_active_model();
+    active.name = Set(name);
+    active.url = Set(url);
+    if let Some(event_kinds) = &params.event_kinds {
+        active.event_kinds = Set(crate::events::encode_kinds(event_kinds));
+    }
+    if let Some(enabled) = params.enabled {
+        active.enabled = Set(enabled);
+    }
+    if let Some(secret) = &secret {
+        active.secret = Set(secret.clone());
+    }
+    let model = active.update(db.as_ref()).await?;
+    info!("已修改 Webhook {}", model.name);
+
+    Ok(ApiResponse::ok(crate::api::response::SaveWebhookResponse {
+        info: webhook_info(model),
+        secret,
+    }))
+}
+
+/// 删除 Webhook 订阅及其投递记录
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    params(("id" = i32, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook 已删除", body = ApiResponse<crate::api::response::WebhooksResponse>),
+        (status = 404, description = "Webhook 不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn delete_webhook(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::WebhooksResponse>, ApiError> {
+    use bili_sync_entity::{webhook, webhook_delivery};
+
+    let model = webhook::Entity::find_by_id(id)
+        .one(db.as_ref())
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    webhook_delivery::Entity::delete_many()
+        .filter(webhook_delivery::Column::WebhookId.eq(id))
+        .exec(db.as_ref())
+        .await?;
+    webhook::Entity::delete_by_id(id).exec(db.as_ref()).await?;
+    info!("已删除 Webhook {}", model.name);
+
+    get_webhooks(Extension(db)).await
+}
+
+fn webhook_delivery_info(
+    model: bili_sync_entity::webhook_delivery::Model,
+) -> crate::api::response::WebhookDeliveryInfo {
+    crate::api::response::WebhookDeliveryInfo {
+        id: model.id,
+        webhook_id: model.webhook_id,
+        event_id: model.event_id,
+        status: model.status,
+        attempts: model.attempts,
+        next_attempt_at: model.next_attempt_at,
+        response_status: model.response_status,
+        last_error: model.last_error,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// 获取 Webhook 的投递记录
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    params(("id" = i32, Path, description = "Webhook ID"), crate::api::request::WebhookDeliveriesRequest),
+    responses(
+        (status = 200, description = "成功获取投递记录", body = ApiResponse<crate::api::response::WebhookDeliveriesResponse>),
+        (status = 400, description = "未知的投递状态", body = String),
+        (status = 404, description = "Webhook 不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_webhook_deliveries(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+    Query(params): Query<crate::api::request::WebhookDeliveriesRequest>,
+) -> Result<ApiResponse<crate::api::response::WebhookDeliveriesResponse>, ApiError> {
+    use crate::events::{DELIVERY_FAILED, DELIVERY_PENDING, DELIVERY_SUCCEEDED};
+    use bili_sync_entity::{webhook, webhook_delivery};
+
+    if webhook::Entity::find_by_id(id).one(db.as_ref()).await?.is_none() {
+        return Err(InnerApiError::NotFound(id).into());
+    }
+    let mut query = webhook_delivery::Entity::find()
+        .filter(webhook_delivery::Column::WebhookId.eq(id))
+        .order_by_desc(webhook_delivery::Column::Id);
+    if let Some(status) = params.status.as_deref().filter(|status| !status.is_empty()) {
+        if ![DELIVERY_PENDING, DELIVERY_SUCCEEDED, DELIVERY_FAILED].contains(&status) {
+            return Err(InnerApiError::BadRequest(format!("未知的投递状态: {}", status)).into());
+        }
+        query = query.filter(webhook_delivery::Column::Status.eq(status));
+    }
+    let deliveries = query
+        .limit(params.limit.unwrap_or(100).clamp(1, 1000))
+        .all(db.as_ref())
+        .await?
+        .into_iter()
+        .map(webhook_delivery_info)
+        .collect();
+    Ok(ApiResponse::ok(crate::api::response::WebhookDeliveriesResponse {
+        deliveries,
+    }))
+}
+
+/// 立即重新投递一条记录，重置尝试次数
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/deliveries/{id}/retry",
+    params(("id" = i32, Path, description = "投递记录ID")),
+    responses(
+        (status = 200, description = "已重新加入投递队列", body = ApiResponse<crate::api::response::WebhookDeliveryInfo>),
+        (status = 404, description = "投递记录不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn retry_webhook_delivery(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<crate::api::response::WebhookDeliveryInfo>, ApiError> {
+    let model = crate::events::retry_delivery(db.as_ref(), id)
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    Ok(ApiResponse::ok(webhook_delivery_info(model)))
+}
+
+/// 导出全部视频源与不含密钥的配置，用于迁移到新设备
+#[utoipa::path(
+    get,
+    path = "/api/bundle/export",
+    params(crate::api::request::ExportBundleRequest),
+    responses(
+        (status = 200, description = "导出成功", body = ApiResponse<crate::api::response::ExportBundleResponse>),
+        (status = 400, description = "不支持的导出格式", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn export_bundle(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<crate::api::request::ExportBundleRequest>,
+) -> Result<ApiResponse<crate::api::response::ExportBundleResponse>, ApiError> {
+    use crate::utils::source_bundle::BundleFormat;
+
+    let format = BundleFormat::parse(params.format.as_deref()).map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    let bundle = crate::utils::source_bundle::export_bundle(db.as_ref()).await?;
+    let content = bundle.to_text(format)?;
+    Ok(ApiResponse::ok(crate::api::response::ExportBundleResponse {
+        format: format.extension().to_string(),
+        file_name: format!(
+            "bili-sync-bundle-{}.{}",
+            crate::utils::time_format::beijing_now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ),
+        content,
+        source_count: bundle.sources.len(),
+        config_count: bundle.config.len(),
+    }))
+}
+
+/// 导入视频源与配置，可先以 dry_run 预览差异
+#[utoipa::path(
+    post,
+    path = "/api/bundle/import",
+    request_body = crate::api::request::ImportBundleRequest,
+    responses(
+        (status = 200, description = "导入完成或预演结果", body = ApiResponse<crate::api::response::ImportBundleResponse>),
+        (status = 400, description = "导入包无效或正在扫描", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn import_bundle(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::ImportBundleRequest>,
+) -> Result<ApiResponse<crate::api::response::ImportBundleResponse>, ApiError> {
+    let bundle = crate::utils::source_bundle::SourceBundle::parse(&params.content)
+        .map_err(|e| InnerApiError::BadRequest(format!("{:#}", e)))?;
+    // 扫描期间添加视频源会进入任务队列并丢失大部分设置，因此直接拒绝
+    if !params.dry_run && crate::task::is_scanning() {
+        return Err(InnerApiError::BadRequest("正在扫描中，请在扫描完成后再导入".to_string()).into());
+    }
+    let response =
+        crate::utils::source_bundle::import_bundle(db, bundle, params.strategy, params.include_config, params.dry_run)
+            .await?;
+    Ok(ApiResponse::ok(response))
+}
+
+/// 获取声明式配置文件的同步状态及其管理的配置项与视频源
+#[utoipa::path(
+    get,
+    path = "/api/config/declarative",
+    responses(
+        (status = 200, description = "成功获取声明式配置状态", body = ApiResponse<crate::api::response::DeclarativeConfigResponse>),
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_declarative_config() -> Result<ApiResponse<crate::api::response::DeclarativeConfigResponse>, ApiError>
+{
+    use crate::api::response::{DeclarativeConfigKey, DeclarativeConfigResponse, DeclarativeConfigSource};
+
+    let status = crate::config::declarative_status();
+    Ok(ApiResponse::ok(DeclarativeConfigResponse {
+        enabled: status.path.is_some(),
+        path: status.path.map(|path| path.display().to_string()),
+        last_reconciled_at: status.last_reconciled_at,
+        last_error: status.last_error,
+        config_keys: status
+            .state
+            .config_keys
+            .into_iter()
+            .map(|key| DeclarativeConfigKey {
+                label: crate::config::describe_config_key(&key).to_string(),
+                key,
+            })
+            .collect(),
+        sources: status
+            .state
+            .sources
+            .into_iter()
+            .map(|source| DeclarativeConfigSource {
+                source_type: source.source_type,
+                id: source.id,
+                name: source.name,
+            })
+            .collect(),
+    }))
+}
+
+fn database_backups_response() -> Result<crate::api::response::DatabaseBackupsResponse, ApiError> {
+    use crate::database::backup;
+
+    backup::ensure_supported().map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    let backups = backup::list_backups()?
+        .into_iter()
+        .map(|file| crate::api::response::DatabaseBackupInfo {
+            name: file.name,
+            size: file.size,
+            created_at: file.created_at,
+        })
+        .collect();
+    Ok(crate::api::response::DatabaseBackupsResponse {
+        backups,
+        dir: backup::backup_dir().display().to_string(),
+        restore_pending: backup::restore_pending(),
+    })
+}
+
+/// 获取数据库备份列表
+#[utoipa::path(
+    get,
+    path = "/api/backups",
+    responses(
+        (status = 200, description = "成功获取备份列表", body = ApiResponse<crate::api::response::DatabaseBackupsResponse>),
+        (status = 400, description = "当前数据库不支持内置备份", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn get_database_backups() -> Result<ApiResponse<crate::api::response::DatabaseBackupsResponse>, ApiError> {
+    Ok(ApiResponse::ok(database_backups_response()?))
+}
+
+/// 立即备份数据库
+#[utoipa::path(
+    post,
+    path = "/api/backups",
+    responses(
+        (status = 200, description = "备份成功", body = ApiResponse<crate::api::response::DatabaseBackupsResponse>),
+        (status = 400, description = "当前数据库不支持内置备份", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn create_database_backup(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<crate::api::response::DatabaseBackupsResponse>, ApiError> {
+    use crate::database::backup;
+
+    backup::ensure_supported().map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    backup::create_backup(db.as_ref(), backup::BackupKind::Manual).await?;
+    Ok(ApiResponse::ok(database_backups_response()?))
+}
+
+/// 从备份恢复数据库，校验迁移版本后暂存，重启后生效
+#[utoipa::path(
+    post,
+    path = "/api/backups/{name}/restore",
+    params(("name" = String, Path, description = "备份文件名")),
+    responses(
+        (status = 200, description = "备份已通过校验，重启后恢复", body = ApiResponse<crate::api::response::RestoreDatabaseBackupResponse>),
+        (status = 400, description = "备份无效或来自更新版本的程序", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn restore_database_backup(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(name): Path<String>,
+) -> Result<ApiResponse<crate::api::response::RestoreDatabaseBackupResponse>, ApiError> {
+    let schema = crate::database::backup::stage_restore(db.as_ref(), &name)
+        .await
+        .map_err(|e| InnerApiError::BadRequest(format!("{:#}", e)))?;
+    Ok(ApiResponse::ok(crate::api::response::RestoreDatabaseBackupResponse {
+        name,
+        latest_migration: schema.latest_migration,
+        pending_migrations: schema.pending_migrations,
+        restart_required: true,
+    }))
+}
 
-    Ok(ApiResponse::ok(response))
+/// 删除数据库备份
+#[utoipa::path(
+    delete,
+    path = "/api/backups/{name}",
+    params(("name" = String, Path, description = "备份文件名")),
+    responses(
+        (status = 200, description = "备份已删除", body = ApiResponse<crate::api::response::DatabaseBackupsResponse>),
+        (status = 400, description = "备份文件名无效或不存在", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    ),
+    security(("Token" = []))
+)]
+pub async fn delete_database_backup(
+    Path(name): Path<String>,
+) -> Result<ApiResponse<crate::api::response::DatabaseBackupsResponse>, ApiError> {
+    crate::database::backup::delete_backup(&name).map_err(|e| InnerApiError::BadRequest(e.to_string()))?;
+    Ok(ApiResponse::ok(database_backups_response()?))
 }
 
 fn credential_field_status(credential: Option<&crate::bilibili::Credential>) -> CredentialFieldStatus {
@@ -17374,16 +19687,20 @@ fn get_video_codecs_description(codecs: crate::bilibili::VideoCodecs) -> String
     path = "/api/videos/proxy-stream",
     params(
         ("url" = String, Query, description = "要代理的视频流URL"),
-        ("referer" = Option<String>, Query, description = "可选的Referer头")
+        ("referer" = Option<String>, Query, description = "可选的Referer头"),
+        ("expires" = Option<i64>, Query, description = "分享链接过期时间（未登录时必填）"),
+        ("sig" = Option<String>, Query, description = "分享链接签名（未登录时必填）")
     ),
     responses(
         (status = 200, description = "视频流代理成功"),
         (status = 400, description = "参数错误"),
+        (status = 403, description = "未登录且分享链接签名无效或已过期"),
         (status = 500, description = "代理失败")
     )
 )]
 pub async fn proxy_video_stream(
     Query(params): Query<std::collections::HashMap<String, String>>,
+    identity: Option<Extension<crate::auth::users::Identity>>,
     headers: axum::http::HeaderMap,
 ) -> impl axum::response::IntoResponse {
     use axum::http::{header, HeaderValue, StatusCode};
@@ -17400,6 +19717,20 @@ pub async fn proxy_video_stream(
         }
     };
 
+    // 未登录时只允许代理签名时指定的地址，避免被当作开放代理
+    let signature = crate::auth::stream_links::StreamSignature {
+        expires: params.get("expires").and_then(|expires| expires.parse().ok()),
+        sig: params.get("sig").cloned(),
+    };
+    if let Err(e) = crate::api::auth::authorize_stream(
+        identity.as_ref().map(|Extension(identity)| identity),
+        crate::auth::stream_links::StreamResource::Proxy(stream_url),
+        &signature,
+    ) {
+        warn!("拒绝视频流代理请求: {:#}", e);
+        return (StatusCode::FORBIDDEN, e.to_string()).into_response();
+    }
+
     // 检查认证信息
     let config = crate::config::reload_config();
     let credential = config.credential.load();
@@ -18299,6 +20630,7 @@ async fn update_bangumi_video_path_in_database(
             danmaku_cid_snapshot: None,
             danmaku_last_write_count: 0,
             ai_renamed: None,
+            quality_checked_at: None,
         };
 
         // 🚨 修复路径提取逻辑：处理混合路径分隔符问题
@@ -18457,6 +20789,7 @@ async fn move_bangumi_files_to_new_path(
             danmaku_cid_snapshot: None,
             danmaku_last_write_count: 0,
             ai_renamed: None,
+            quality_checked_at: None,
         };
 
         // 修复路径提取逻辑：处理混合路径分隔符问题
@@ -19028,30 +21361,30 @@ pub async fn get_dashboard_data(
         video_source::Entity::find()
             .filter(video_source::Column::Type.eq(1))
             .count(db.as_ref()),
-        crate::api::response::DayCountPair::find_by_statement(sea_orm::Statement::from_string(
-            db.get_database_backend(),
+        crate::api::response::DayCountPair::find_by_statement(crate::database::statement(
             // 用 SeaORM 太复杂了，直接写个裸 SQL
-            // 修复时区处理：created_at 存储的是北京时间，直接使用日期比较
+            // created_at 存储的是北京时间字符串，取前 10 位即为日期，日期列表在程序中生成以兼容各数据库
             "
 SELECT
     dates.day AS day,
     COUNT(video.id) AS cnt
 FROM
     (
-        SELECT
-            DATE('now', '-' || n || ' days', 'localtime') AS day
-        FROM
-            (
-                SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6
-            )
+        SELECT ? AS day UNION ALL SELECT ? UNION ALL SELECT ? UNION ALL SELECT ? UNION ALL SELECT ? UNION ALL SELECT ? UNION ALL SELECT ?
     ) AS dates
 LEFT JOIN
-    video ON DATE(video.created_at) = dates.day
+    video ON SUBSTR(video.created_at, 1, 10) = dates.day
 GROUP BY
     dates.day
 ORDER BY
     dates.day;
-    "
+    ",
+            (0..7).map(|n| {
+                (crate::utils::time_format::beijing_now() - chrono::Duration::days(n))
+                    .format("%Y-%m-%d")
+                    .to_string()
+                    .into()
+            }),
         ))
         .all(db.as_ref()),
     )?;
@@ -19557,6 +21890,378 @@ pub async fn get_notification_status() -> Result<ApiResponse<crate::api::respons
     Ok(ApiResponse::ok(status))
 }
 
+/// 获取画质档案列表
+#[utoipa::path(
+    get,
+    path = "/api/config/quality-profiles",
+    responses(
+        (status = 200, description = "画质档案列表", body = ApiResponse<crate::api::response::QualityProfilesResponse>),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn get_quality_profiles() -> Result<ApiResponse<crate::api::response::QualityProfilesResponse>, ApiError> {
+    Ok(ApiResponse::ok(crate::api::response::QualityProfilesResponse {
+        profiles: crate::config::reload_config().quality_profiles.clone(),
+    }))
+}
+
+/// 收集所有视频源当前引用的画质档案名称
+async fn quality_profiles_in_use(db: &DatabaseConnection) -> Result<HashSet<String>> {
+    let mut values: Vec<Option<serde_json::Value>> = Vec::new();
+    values.extend(
+        collection::Entity::find()
+            .select_only()
+            .column(collection::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    values.extend(
+        favorite::Entity::find()
+            .select_only()
+            .column(favorite::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    values.extend(
+        submission::Entity::find()
+            .select_only()
+            .column(submission::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    values.extend(
+        watch_later::Entity::find()
+            .select_only()
+            .column(watch_later::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    values.extend(
+        video_source::Entity::find()
+            .select_only()
+            .column(video_source::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    values.extend(
+        ranking::Entity::find()
+            .select_only()
+            .column(ranking::Column::FilterOption)
+            .into_tuple::<Option<serde_json::Value>>()
+            .all(db)
+            .await?,
+    );
+    Ok(values.iter().filter_map(source_quality_profile_from_json).collect())
+}
+
+/// 整体替换画质档案列表
+#[utoipa::path(
+    put,
+    path = "/api/config/quality-profiles",
+    request_body = crate::api::request::UpdateQualityProfilesRequest,
+    responses(
+        (status = 200, description = "画质档案更新成功", body = ApiResponse<crate::api::response::QualityProfilesResponse>),
+        (status = 400, description = "画质档案验证失败", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn update_quality_profiles(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<crate::api::request::UpdateQualityProfilesRequest>,
+) -> Result<ApiResponse<crate::api::response::QualityProfilesResponse>, ApiError> {
+    crate::config::validate_quality_profiles(&request.profiles).map_err(ApiError::from)?;
+
+    // 仍被视频源引用的档案不允许删除，避免视频源静默回退为全局配置
+    let in_use = quality_profiles_in_use(db.as_ref()).await?;
+    let mut missing: Vec<&String> = in_use
+        .iter()
+        .filter(|name| !request.profiles.iter().any(|profile| &profile.name == *name))
+        .collect();
+    if !missing.is_empty() {
+        missing.sort();
+        return Err(anyhow!(
+            "以下画质档案仍被视频源引用，无法删除: {}",
+            missing.iter().map(|name| name.as_str()).collect::<Vec<_>>().join(", ")
+        )
+        .into());
+    }
+
+    let config_manager = crate::config::ConfigManager::new(db.as_ref().clone());
+    config_manager
+        .update_config_item("quality_profiles", serde_json::to_value(&request.profiles)?)
+        .await
+        .map_err(|e| ApiError::from(anyhow!("更新画质档案失败: {}", e)))?;
+    crate::config::reload_config_bundle()
+        .await
+        .map_err(|e| ApiError::from(anyhow!("重新加载配置失败: {}", e)))?;
+
+    Ok(ApiResponse::ok(crate::api::response::QualityProfilesResponse {
+        profiles: request.profiles,
+    }))
+}
+
+/// 选流预览：获取指定视频的播放地址，展示 best_stream 在给定筛选条件下会选中的流
+#[utoipa::path(
+    post,
+    path = "/api/config/quality-profiles/dry-run",
+    request_body = crate::api::request::QualityProfileDryRunRequest,
+    responses(
+        (status = 200, description = "选流结果", body = ApiResponse<crate::api::response::QualityProfileDryRunResponse>),
+        (status = 400, description = "参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn dry_run_quality_profile(
+    axum::Json(request): axum::Json<crate::api::request::QualityProfileDryRunRequest>,
+) -> Result<ApiResponse<crate::api::response::QualityProfileDryRunResponse>, ApiError> {
+    use crate::api::response::{DryRunAudioStream, DryRunVideoStream, QualityProfileDryRunResponse};
+    use crate::bilibili::{BestStream, Stream};
+    use crate::workflow::{
+        get_cached_audio_quality_description, get_cached_video_codecs_description, get_cached_video_quality_description,
+    };
+
+    let bvid = request.bvid.trim().to_string();
+    if bvid.is_empty() {
+        return Err(anyhow!("bvid 不能为空").into());
+    }
+    let config = crate::config::reload_config();
+    let profile = request
+        .profile
+        .as_deref()
+        .map(str::trim)
+        .filter(|profile| !profile.is_empty());
+    let filter_option = match profile {
+        Some(name) => config
+            .quality_profiles
+            .iter()
+            .find(|item| item.name == name)
+            .map(|item| item.filter_option.clone())
+            .ok_or_else(|| anyhow!("画质档案「{}」不存在", name))?,
+        None => request
+            .filter_option
+            .clone()
+            .unwrap_or_else(|| config.filter_option.clone()),
+    };
+    filter_option.validate().map_err(ApiError::from)?;
+
+    let bili_client = crate::bilibili::BiliClient::new(String::new());
+    let bili_video = crate::bilibili::Video::new(&bili_client, bvid.clone());
+    let page_number = request.page.unwrap_or(1);
+    let page_info = bili_video
+        .get_pages()
+        .await?
+        .into_iter()
+        .find(|page| page.page == page_number)
+        .ok_or_else(|| anyhow!("视频 {} 不存在第 {} 页", bvid, page_number))?;
+    let (max_qn, min_qn) = crate::bilibili::effective_playurl_qn_range(
+        filter_option.video_max_quality as u32,
+        filter_option.video_min_quality as u32,
+        false,
+        config.submission_risk_control.audio_only_use_low_qn_for_playurl,
+    );
+    let mut analyzer = bili_video
+        .get_page_analyzer_with_api_fallback_in_range(&page_info, None, max_qn, min_qn)
+        .await?;
+
+    let available_video_streams = analyzer
+        .available_video_streams()
+        .into_iter()
+        .map(|(quality, codecs)| DryRunVideoStream {
+            quality: quality as u32,
+            quality_description: get_cached_video_quality_description(quality).to_string(),
+            codecs: get_cached_video_codecs_description(codecs).to_string(),
+        })
+        .collect();
+    let (best_stream, matched_rule) = analyzer.best_stream_with_rule(&filter_option)?;
+    let (mixed, video, audio) = match best_stream {
+        BestStream::Mixed(_) => (true, None, None),
+        BestStream::VideoAudio { video, audio } => {
+            let video = match video {
+                Stream::DashVideo { quality, codecs, .. } => Some(DryRunVideoStream {
+                    quality: quality as u32,
+                    quality_description: get_cached_video_quality_description(quality).to_string(),
+                    codecs: get_cached_video_codecs_description(codecs).to_string(),
+                }),
+                _ => None,
+            };
+            let audio = match audio {
+                Some(Stream::DashAudio { quality, .. }) => Some(DryRunAudioStream {
+                    quality: quality as u32,
+                    quality_description: get_cached_audio_quality_description(quality).to_string(),
+                }),
+                _ => None,
+            };
+            (false, video, audio)
+        }
+    };
+
+    Ok(ApiResponse::ok(QualityProfileDryRunResponse {
+        bvid,
+        cid: page_info.cid,
+        page: page_info.page,
+        profile: profile.map(str::to_string),
+        matched_rule: matched_rule.map(|index| index + 1),
+        mixed,
+        video,
+        audio,
+        available_video_streams,
+    }))
+}
+
+/// 视频源订阅前预览：按给定过滤条件与当前命名模板列出将会生成的文件，不写数据库和磁盘
+#[utoipa::path(
+    post,
+    path = "/api/video-sources/preview",
+    request_body = crate::api::request::PreviewVideoSourceRequest,
+    responses(
+        (status = 200, description = "预览结果", body = ApiResponse<crate::api::response::VideoSourcePreviewResponse>),
+        (status = 400, description = "参数错误", body = String),
+        (status = 500, description = "服务器内部错误", body = String)
+    )
+)]
+pub async fn preview_video_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(params): axum::Json<crate::api::request::PreviewVideoSourceRequest>,
+) -> Result<ApiResponse<crate::api::response::VideoSourcePreviewResponse>, ApiError> {
+    use crate::utils::keyword_filter::validate_regex;
+    use crate::utils::model::VideoListFilter;
+    use crate::workflow_preview::{PreviewSourceArgs, SourcePreviewOptions, MAX_PREVIEW_LIMIT};
+    use chrono::NaiveDate;
+
+    let path = params.path.trim();
+    if path.is_empty() {
+        return Err(anyhow!("保存路径不能为空").into());
+    }
+    let source_id = params.source_id.trim().to_string();
+    let args = match params.source_type.as_str() {
+        "favorite" => PreviewSourceArgs::Favorite { fid: source_id },
+        "collection" => {
+            let up_id = params
+                .up_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|up_id| !up_id.is_empty())
+                .ok_or_else(|| anyhow!("预览合集需要提供UP主ID"))?;
+            let collection_type = match params.collection_type.as_deref().unwrap_or("season") {
+                "series" => crate::bilibili::CollectionType::Series,
+                "season" => crate::bilibili::CollectionType::Season,
+                other => return Err(anyhow!("无效的合集类型: {}", other).into()),
+            };
+            PreviewSourceArgs::Collection {
+                collection_item: crate::bilibili::CollectionItem {
+                    mid: up_id.to_string(),
+                    sid: source_id,
+                    collection_type,
+                },
+            }
+        }
+        "submission" => PreviewSourceArgs::Submission { upper_id: source_id },
+        "watch_later" => PreviewSourceArgs::WatchLater,
+        "ranking" => {
+            let ranking_type = params
+                .ranking_type
+                .as_deref()
+                .unwrap_or("popular")
+                .parse::<crate::bilibili::RankingType>()?;
+            let rid = match ranking_type {
+                crate::bilibili::RankingType::Rank if !source_id.is_empty() => {
+                    source_id.parse::<i32>().map_err(|_| anyhow!("无效的排行榜分区ID"))?
+                }
+                _ => 0,
+            };
+            PreviewSourceArgs::Ranking { ranking_type, rid }
+        }
+        "bangumi" => return Err(anyhow!("番剧源暂不支持预览").into()),
+        other => return Err(anyhow!("不支持的视频源类型: {}", other).into()),
+    };
+    if matches!(
+        args,
+        PreviewSourceArgs::Favorite { .. } | PreviewSourceArgs::Submission { .. }
+    ) && params.source_id.trim().is_empty()
+    {
+        return Err(anyhow!("视频源ID不能为空").into());
+    }
+
+    for (label, patterns) in [
+        ("黑名单", &params.blacklist_keywords),
+        ("白名单", &params.whitelist_keywords),
+    ] {
+        for pattern in patterns.iter().flatten() {
+            if let Err(e) = validate_regex(pattern) {
+                return Err(anyhow!("{}正则表达式验证失败: {} - {}", label, pattern, e).into());
+            }
+        }
+    }
+    if let (Some(min), Some(max)) = (params.min_duration_seconds, params.max_duration_seconds) {
+        if min > max {
+            return Err(anyhow!("最短时长不能大于最长时长").into());
+        }
+    }
+    let normalize_date = |value: &Option<String>, label: &str| -> Result<Option<String>, ApiError> {
+        match value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            Some(date) => {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| anyhow!("{}格式无效，必须为 YYYY-MM-DD", label))?;
+                Ok(Some(date.to_string()))
+            }
+            None => Ok(None),
+        }
+    };
+    let keywords_json = |keywords: &Option<Vec<String>>| {
+        keywords
+            .as_ref()
+            .filter(|v| !v.is_empty())
+            .map(|v| serde_json::to_string(v).unwrap_or_default())
+    };
+    let list_filter = VideoListFilter {
+        blacklist_keywords: keywords_json(&params.blacklist_keywords),
+        whitelist_keywords: keywords_json(&params.whitelist_keywords),
+        keyword_case_sensitive: params.keyword_case_sensitive.unwrap_or(true),
+        min_duration_seconds: params.min_duration_seconds,
+        max_duration_seconds: params.max_duration_seconds,
+        published_after: normalize_date(&params.published_after, "投稿起始日期")?,
+        published_before: normalize_date(&params.published_before, "投稿截止日期")?,
+        ..Default::default()
+    };
+
+    let config = crate::config::reload_config();
+    let filter_option = match params
+        .quality_profile
+        .as_deref()
+        .map(str::trim)
+        .filter(|profile| !profile.is_empty())
+    {
+        Some(name) => config
+            .quality_profiles
+            .iter()
+            .find(|item| item.name == name)
+            .map(|item| item.filter_option.clone())
+            .ok_or_else(|| anyhow!("画质档案「{}」不存在", name))?,
+        None => params
+            .filter_option
+            .clone()
+            .unwrap_or_else(|| config.filter_option.clone()),
+    };
+
+    let options = SourcePreviewOptions {
+        path: std::path::PathBuf::from(path),
+        list_filter,
+        limit: params.limit.unwrap_or(30).clamp(1, MAX_PREVIEW_LIMIT),
+        fetch_pages: params.fetch_pages.unwrap_or(true),
+        audio_only: params.audio_only.unwrap_or(false),
+        flat_folder: params.flat_folder.unwrap_or(false),
+        filter_option,
+    };
+    let bili_client = crate::bilibili::BiliClient::new(String::new());
+    let preview = crate::workflow_preview::preview_video_source(&args, &options, &bili_client, db.as_ref()).await?;
+    Ok(ApiResponse::ok(preview))
+}
+
 /// 从番剧标题中提取系列名称
 /// 例如：《灵笼 第二季》第1话 末世桃源 -> 灵笼
 fn extract_bangumi_series_title(full_title: &str) -> String {
@@ -20114,6 +22819,40 @@ pub async fn update_video_source_keyword_filters(
                 ),
             }
         }
+        "ranking" => {
+            let record = ranking::Entity::find_by_id(id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            ranking::Entity::update(ranking::ActiveModel {
+                id: sea_orm::ActiveValue::Unchanged(id),
+                blacklist_keywords: sea_orm::Set(blacklist_json),
+                whitelist_keywords: sea_orm::Set(whitelist_json),
+                keyword_filters: sea_orm::Set(keyword_filters_json),
+                keyword_filter_mode: sea_orm::Set(keyword_filter_mode.clone()),
+                keyword_case_sensitive: sea_orm::Set(case_sensitive),
+                min_duration_seconds: sea_orm::Set(min_duration_seconds),
+                max_duration_seconds: sea_orm::Set(max_duration_seconds),
+                published_after: sea_orm::Set(published_after.clone()),
+                published_before: sea_orm::Set(published_before.clone()),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await?;
+
+            crate::api::response::UpdateKeywordFiltersResponse {
+                success: true,
+                source_id: id,
+                source_type: "ranking".to_string(),
+                blacklist_count,
+                whitelist_count,
+                message: format!(
+                    "榜单「{}」的关键词过滤器已更新，黑名单 {} 个，白名单 {} 个",
+                    record.name, blacklist_count, whitelist_count
+                ),
+            }
+        }
         "bangumi" => {
             let record = video_source::Entity::find_by_id(id)
                 .one(&txn)
@@ -20366,6 +23105,36 @@ pub async fn get_video_source_keyword_filters(
                 legacy_mode: record.keyword_filter_mode,
             }
         }
+        "ranking" => {
+            let record = ranking::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            FilterInfo {
+                blacklist: record
+                    .blacklist_keywords
+                    .as_ref()
+                    .and_then(|json_str| serde_json::from_str(json_str).ok())
+                    .unwrap_or_default(),
+                whitelist: record
+                    .whitelist_keywords
+                    .as_ref()
+                    .and_then(|json_str| serde_json::from_str(json_str).ok())
+                    .unwrap_or_default(),
+                case_sensitive: record.keyword_case_sensitive,
+                min_duration_seconds: record.min_duration_seconds,
+                max_duration_seconds: record.max_duration_seconds,
+                published_after: record.published_after,
+                published_before: record.published_before,
+                legacy_filters: record
+                    .keyword_filters
+                    .as_ref()
+                    .and_then(|json_str| serde_json::from_str(json_str).ok())
+                    .unwrap_or_default(),
+                legacy_mode: record.keyword_filter_mode,
+            }
+        }
         "bangumi" => {
             let record = video_source::Entity::find_by_id(id)
                 .one(db.as_ref())
@@ -20636,6 +23405,22 @@ pub async fn ai_rename_history(
                 source.ai_rename_rename_parent_dir,
             )
         }
+        "ranking" => {
+            let source = ranking::Entity::find_by_id(id)
+                .one(db.as_ref())
+                .await?
+                .ok_or_else(|| anyhow!("未找到指定的榜单"))?;
+
+            let videos_with_pages = get_videos_with_pages_for_source(db.as_ref(), "ranking", id).await?;
+
+            (
+                source.ai_rename_video_prompt,
+                source.ai_rename_audio_prompt,
+                videos_with_pages,
+                source.flat_folder,
+                source.ai_rename_rename_parent_dir,
+            )
+        }
         _ => {
             return Ok(ApiResponse::ok(crate::api::response::BatchRenameResponse {
                 success: false,
@@ -20774,6 +23559,13 @@ async fn get_videos_with_pages_for_source(
                 .all(db)
                 .await?
         }
+        "ranking" => {
+            video::Entity::find()
+                .filter(video::Column::RankingId.eq(source_id))
+                .order_by_asc(video::Column::Pubtime)
+                .all(db)
+                .await?
+        }
         _ => return Err(anyhow!("不支持的视频源类型: {}", source_type)),
     };
 
@@ -2,10 +2,11 @@ pub mod auth;
 pub mod handler;
 pub mod request;
 pub mod response;
+pub mod v1;
 pub mod video_stream;
 pub mod ws;
 
-mod error;
+pub(crate) mod error;
 pub mod wrapper;
 
 pub use response::UserFavoriteFolder;
@@ -2,7 +2,10 @@ use serde::Deserialize;
 use utoipa::IntoParams;
 use utoipa::ToSchema;
 
+use crate::auth::api_keys::ApiScope;
+use crate::auth::users::Role;
 use crate::bilibili::FilterOption;
+use crate::events::EventKind;
 
 #[derive(Clone, Deserialize, IntoParams, Default)]
 pub struct VideosRequest {
@@ -10,6 +13,7 @@ pub struct VideosRequest {
     pub favorite: Option<i32>,
     pub submission: Option<i32>,
     pub watch_later: Option<i32>,
+    pub ranking: Option<i32>,
     pub bangumi: Option<i32>,
     pub query: Option<String>,
     pub page: Option<u64>,
@@ -33,9 +37,9 @@ pub struct SubmissionVideosRequest {
 // 添加新视频源的请求结构体
 #[derive(Deserialize, IntoParams, ToSchema)]
 pub struct AddVideoSourceRequest {
-    // 视频源类型: "collection", "favorite", "submission", "watch_later", "bangumi"
+    // 视频源类型: "collection", "favorite", "submission", "watch_later", "ranking", "bangumi"
     pub source_type: String,
-    // 视频源ID: 收藏夹ID、合集ID、UP主ID等
+    // 视频源ID: 收藏夹ID、合集ID、UP主ID等；榜单源为排行榜分区ID（可为空，默认0即全站）
     pub source_id: String,
     // UP主ID: 仅当source_type为"collection"时需要
     pub up_id: Option<String>,
@@ -45,11 +49,19 @@ pub struct AddVideoSourceRequest {
     pub path: String,
     // 合集类型: "season"(视频合集) 或 "series"(视频列表)，仅当source_type为"collection"时有效
     pub collection_type: Option<String>,
+    // 榜单类型: "popular"(综合热门)、"weekly"(每周必看) 或 "rank"(排行榜)，仅当source_type为"ranking"时有效
+    pub ranking_type: Option<String>,
     // 是否启用合集聚合（仅当source_type为"collection"时有效）
     pub collection_aggregate_enabled: Option<bool>,
     /// 视频源级流过滤配置；缺失或 null 表示继承全局配置
     #[serde(default)]
     pub filter_option: Option<FilterOption>,
+    /// 引用的画质档案名称，设置后优先于 filter_option
+    #[serde(default)]
+    pub quality_profile: Option<String>,
+    /// 绑定的B站账号ID，缺失表示使用默认账号
+    #[serde(default)]
+    pub credential_id: Option<i32>,
     // 番剧特有字段
     pub media_id: Option<String>,
     pub ep_id: Option<String>,
@@ -71,6 +83,8 @@ pub struct AddVideoSourceRequest {
     pub audio_only: Option<bool>,
     // 是否下载弹幕文件（ASS）
     pub download_danmaku: Option<bool>,
+    // 是否归档评论区（热门与置顶评论及其回复）
+    pub archive_comments: Option<bool>,
     // 是否下载字幕文件（SRT）
     pub download_subtitle: Option<bool>,
     // 是否下载 B 站 AI 字幕
@@ -122,6 +136,13 @@ pub struct UpdateVideoSourceScanDeletedRequest {
     pub scan_deleted_videos_once: Option<bool>,
 }
 
+// 更新视频源绑定B站账号的请求结构体
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVideoSourceCredentialRequest {
+    /// 绑定的B站账号ID，null 表示使用默认账号
+    pub credential_id: Option<i32>,
+}
+
 // 更新视频源下载选项的请求结构体
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateVideoSourceDownloadOptionsRequest {
@@ -137,6 +158,8 @@ pub struct UpdateVideoSourceDownloadOptionsRequest {
     pub download_charge_videos: Option<bool>,
     /// 是否下载弹幕文件（ASS）
     pub download_danmaku: Option<bool>,
+    /// 是否归档评论区（热门与置顶评论及其回复）
+    pub archive_comments: Option<bool>,
     /// 是否下载字幕文件（SRT）
     pub download_subtitle: Option<bool>,
     /// 是否下载 B 站 AI 字幕
@@ -164,6 +187,9 @@ pub struct UpdateVideoSourceDownloadOptionsRequest {
     /// 视频源级流过滤配置：字段缺失保持不变，null 表示继承全局，对象表示使用自定义配置
     #[serde(default)]
     pub filter_option: Option<Option<FilterOption>>,
+    /// 引用的画质档案名称：字段缺失保持不变，空字符串表示取消引用，设置后优先于 filter_option
+    #[serde(default)]
+    pub quality_profile: Option<Option<String>>,
 }
 
 // 更新投稿源选中视频列表的请求结构体
@@ -173,6 +199,22 @@ pub struct UpdateSubmissionSelectedVideosRequest {
     pub selected_videos: Vec<String>,
 }
 
+/// 重新渲染弹幕 ASS 的请求，source_type 与 source_id 都为空时处理整个媒体库
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RerenderDanmakuRequest {
+    pub source_type: Option<String>,
+    pub source_id: Option<i32>,
+}
+
+/// 查询分页弹幕高能时刻的参数，未指定时使用 `danmaku_highlight` 配置
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct DanmakuHighlightsRequest {
+    /// 返回的高能时刻数量
+    pub top: Option<usize>,
+    /// 两个高能时刻之间的最小间隔（秒）
+    pub min_gap_seconds: Option<u32>,
+}
+
 // 重设视频源路径的请求结构体
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ResetVideoSourcePathRequest {
@@ -248,6 +290,8 @@ pub struct UpdateConfigRequest {
     pub danmaku_bold: Option<bool>,
     pub danmaku_outline: Option<f64>,
     pub danmaku_time_offset: Option<f64>,
+    /// 用于测量弹幕宽度的字体文件路径，空字符串表示清除
+    pub danmaku_font_path: Option<String>,
     pub danmaku_update_enabled: Option<bool>,
     pub danmaku_update_fresh_days: Option<u32>,
     pub danmaku_update_fresh_interval_hours: Option<u32>,
@@ -255,6 +299,10 @@ pub struct UpdateConfigRequest {
     pub danmaku_update_mature_interval_days: Option<u32>,
     pub danmaku_update_cold_days: Option<u32>,
     pub danmaku_update_cold_interval_days: Option<u32>,
+    pub quality_upgrade_enabled: Option<bool>,
+    pub quality_upgrade_window_hours: Option<u32>,
+    pub quality_upgrade_check_interval_hours: Option<u32>,
+    pub quality_upgrade_max_pages_per_run: Option<u32>,
     // 并发控制设置
     pub concurrent_video: Option<usize>,
     pub concurrent_page: Option<usize>,
@@ -296,6 +344,7 @@ pub struct UpdateConfigRequest {
     pub submission_adaptive_max_hours: Option<u64>,
     // 系统配置
     pub scan_deleted_videos: Option<bool>,
+    pub cross_source_dedup: Option<bool>,
     // aria2监控配置
     pub enable_aria2_health_check: Option<bool>,
     pub enable_aria2_auto_restart: Option<bool>,
@@ -422,6 +471,60 @@ pub struct UpdateNotificationConfigRequest {
     pub notification_retry_count: Option<u8>,
 }
 
+// 画质档案整体替换请求
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateQualityProfilesRequest {
+    pub profiles: Vec<crate::config::QualityProfile>,
+}
+
+// 选流预览请求：按画质档案/自定义筛选条件/全局配置的优先级确定筛选条件
+#[derive(Deserialize, ToSchema)]
+pub struct QualityProfileDryRunRequest {
+    pub bvid: String,
+    /// 分页序号（从 1 开始），默认第 1 页
+    pub page: Option<i32>,
+    /// 画质档案名称
+    pub profile: Option<String>,
+    /// 自定义筛选条件（未指定画质档案时生效）
+    pub filter_option: Option<FilterOption>,
+}
+
+// 视频源订阅前预览请求：只读取B站列表并在内存中套用过滤与命名模板，不写数据库和磁盘
+#[derive(Deserialize, ToSchema)]
+pub struct PreviewVideoSourceRequest {
+    // 视频源类型: "collection", "favorite", "submission", "watch_later", "ranking"
+    pub source_type: String,
+    // 视频源ID，含义与添加视频源时一致
+    #[serde(default)]
+    pub source_id: String,
+    // UP主ID: 仅当source_type为"collection"时需要
+    pub up_id: Option<String>,
+    // 合集类型: "season" 或 "series"
+    pub collection_type: Option<String>,
+    // 榜单类型: "popular"、"weekly" 或 "rank"
+    pub ranking_type: Option<String>,
+    // 保存路径
+    pub path: String,
+    /// 最多检查的列表条目数，默认 30，上限 200
+    pub limit: Option<usize>,
+    /// 是否逐个请求分P列表以渲染分页文件名（默认开启）
+    pub fetch_pages: Option<bool>,
+    pub blacklist_keywords: Option<Vec<String>>,
+    pub whitelist_keywords: Option<Vec<String>>,
+    pub keyword_case_sensitive: Option<bool>,
+    pub min_duration_seconds: Option<i32>,
+    pub max_duration_seconds: Option<i32>,
+    /// YYYY-MM-DD
+    pub published_after: Option<String>,
+    /// YYYY-MM-DD
+    pub published_before: Option<String>,
+    pub audio_only: Option<bool>,
+    pub flat_folder: Option<bool>,
+    /// 画质档案名称，优先于 filter_option，仅用于估算体积
+    pub quality_profile: Option<String>,
+    pub filter_option: Option<FilterOption>,
+}
+
 // 测试推送请求（可选消息内容）
 #[derive(Deserialize, ToSchema)]
 pub struct TestNotificationRequest {
@@ -471,6 +574,7 @@ pub struct ResetSpecificTasksRequest {
     pub favorite: Option<i32>,
     pub submission: Option<i32>,
     pub watch_later: Option<i32>,
+    pub ranking: Option<i32>,
     pub bangumi: Option<i32>,
     // 与 /api/videos 的过滤参数保持一致，便于“按当前筛选批量重置”
     pub query: Option<String>,
@@ -523,6 +627,143 @@ pub struct UpdateCredentialRequest {
     pub dedeuserid_ckmd5: Option<String>,
 }
 
+// 新增/更新额外B站账号请求
+#[derive(Deserialize, ToSchema)]
+pub struct UpsertBiliAccountRequest {
+    /// 便于区分账号的备注名
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub credential: UpdateCredentialRequest,
+}
+
+// 管理页用户登录请求
+#[derive(Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+// 新增管理页用户请求
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApiUserRequest {
+    pub username: String,
+    pub password: String,
+    pub role: Role,
+}
+
+// 更新管理页用户请求，未提供的字段保持不变
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateApiUserRequest {
+    pub password: Option<String>,
+    pub role: Option<Role>,
+    pub enabled: Option<bool>,
+}
+
+// 新增 API 密钥请求
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// 有效天数，不填表示永不过期
+    pub expires_in_days: Option<u32>,
+}
+
+// 生成视频流分享链接请求，video_id 与 url 二选一
+#[derive(Deserialize, ToSchema)]
+pub struct CreateStreamLinkRequest {
+    /// 本地视频或分页ID，对应 /api/videos/stream/{video_id}
+    pub video_id: Option<String>,
+    /// 需要经由 /api/videos/proxy-stream 代理的B站视频流地址
+    pub url: Option<String>,
+    /// 有效时长（小时），默认 24 小时，最长 30 天
+    pub expires_in_hours: Option<u32>,
+}
+
+// 导出视频源与设置请求
+#[derive(Deserialize, IntoParams)]
+pub struct ExportBundleRequest {
+    /// 导出格式：json（默认）或 yaml
+    pub format: Option<String>,
+}
+
+// 导入视频源与设置请求
+#[derive(Deserialize, ToSchema)]
+pub struct ImportBundleRequest {
+    /// 导出包内容（JSON 或 YAML 文本）
+    pub content: String,
+    /// 已存在的视频源与配置项的处理方式，默认 skip
+    #[serde(default)]
+    pub strategy: crate::utils::source_bundle::ConflictStrategy,
+    /// 只返回差异，不写入
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 是否导入配置项，默认导入
+    #[serde(default = "default_include_config")]
+    pub include_config: bool,
+}
+
+fn default_include_config() -> bool {
+    true
+}
+
+// 审计日志查询请求
+#[derive(Deserialize, IntoParams)]
+pub struct AuditLogRequest {
+    /// 只查看指定操作者的记录
+    pub actor: Option<String>,
+    pub limit: Option<u64>,
+}
+
+// 事件查询请求
+#[derive(Deserialize, IntoParams)]
+pub struct EventsRequest {
+    /// 只返回该 ID 之后的事件（按 ID 正序），不填时返回最近的事件（按 ID 倒序）
+    pub since: Option<i32>,
+    /// 逗号分隔的事件类型，不填表示全部
+    pub kinds: Option<String>,
+    pub limit: Option<u64>,
+}
+
+// 事件流订阅请求，断线重连时也可以通过 Last-Event-ID 请求头指定 since
+#[derive(Deserialize, IntoParams)]
+pub struct EventStreamRequest {
+    /// 先补发该 ID 之后的事件，再推送实时事件
+    pub since: Option<i32>,
+    /// 逗号分隔的事件类型，不填表示全部
+    pub kinds: Option<String>,
+}
+
+// 新增 Webhook 订阅请求
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub name: String,
+    pub url: String,
+    /// 订阅的事件类型，为空表示全部
+    #[serde(default)]
+    pub event_kinds: Vec<EventKind>,
+}
+
+// 修改 Webhook 订阅请求，未填写的字段保持不变
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub event_kinds: Option<Vec<EventKind>>,
+    pub enabled: Option<bool>,
+    /// 重新生成签名密钥
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+// Webhook 投递记录查询请求
+#[derive(Deserialize, IntoParams)]
+pub struct WebhookDeliveriesRequest {
+    /// pending / succeeded / failed
+    pub status: Option<String>,
+    pub limit: Option<u64>,
+}
+
 #[derive(Deserialize, ToSchema, Default)]
 pub struct CredentialRefreshTestRequest {
     #[serde(default)]
@@ -2,7 +2,10 @@ use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::auth::api_keys::ApiScope;
+use crate::auth::users::Role;
 use crate::bilibili::FilterOption;
+use crate::events::{EventKind, EventRecord};
 use crate::utils::status::{PageStatus, VideoStatus};
 
 #[derive(Debug, Serialize, ToSchema, Default)]
@@ -16,6 +19,8 @@ pub struct VideoSourcesResponse {
     #[serde(default)]
     pub watch_later: Vec<VideoSource>,
     #[serde(default)]
+    pub ranking: Vec<VideoSource>,
+    #[serde(default)]
     pub bangumi: Vec<VideoSource>,
 }
 
@@ -73,6 +78,31 @@ pub struct RefreshDanmakuResponse {
     pub message: String,
 }
 
+/// 分页的弹幕密度直方图与高能时刻
+#[derive(Serialize, ToSchema)]
+pub struct DanmakuHighlightsResponse {
+    pub page_id: i32,
+    pub video_id: i32,
+    /// 直方图时间桶长度（秒）
+    pub bucket_seconds: u32,
+    pub total: u32,
+    /// 每个时间桶的弹幕数，尚未同步弹幕时为空
+    pub histogram: Vec<u32>,
+    /// 按时间排序的高能时刻
+    pub peaks: Vec<crate::bilibili::DanmakuPeak>,
+    /// 最近一次写入 NFO 与视频文件的高能章节
+    pub chapters: Vec<crate::bilibili::HighlightChapter>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RerenderDanmakuResponse {
+    pub success: bool,
+    /// 新建任务的ID，已有相同范围的待处理任务时为空
+    pub task_id: Option<String>,
+    pub message: String,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct ResetAllVideosResponse {
     pub resetted: bool,
@@ -153,6 +183,15 @@ pub struct UpdateVideoSourceScanDeletedResponse {
     pub message: String,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct UpdateVideoSourceCredentialResponse {
+    pub success: bool,
+    pub source_id: i32,
+    pub source_type: String,
+    pub credential_id: Option<i32>,
+    pub message: String,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct UpdateVideoSourceDownloadOptionsResponse {
     pub success: bool,
@@ -167,6 +206,7 @@ pub struct UpdateVideoSourceDownloadOptionsResponse {
     pub split_chapters_after_download: bool,
     pub download_charge_videos: bool,
     pub download_danmaku: bool,
+    pub archive_comments: bool,
     pub download_subtitle: bool,
     pub download_ai_subtitle: bool,
     pub ai_subtitle_language: String,
@@ -179,6 +219,7 @@ pub struct UpdateVideoSourceDownloadOptionsResponse {
     pub ai_rename_rename_parent_dir: bool,
     pub use_dynamic_api: bool,
     pub filter_option: Option<FilterOption>,
+    pub quality_profile: Option<String>,
     pub message: String,
 }
 
@@ -273,6 +314,7 @@ pub struct VideoSource {
     pub split_chapters_after_download: bool, // 是否在下载后按播放器章节切分为独立视频
     pub download_charge_videos: bool,        // 是否下载充电专享视频
     pub download_danmaku: bool,              // 是否下载弹幕文件
+    pub archive_comments: bool,              // 是否归档评论区
     pub download_subtitle: bool,             // 是否下载字幕文件
     pub download_ai_subtitle: bool,          // 是否下载 B 站 AI 字幕
     pub ai_subtitle_language: String,        // AI 字幕语言，默认 zh-CN
@@ -286,7 +328,15 @@ pub struct VideoSource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter_option: Option<FilterOption>, // 视频源级流过滤配置，None 表示继承全局
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_profile: Option<String>, // 引用的画质档案名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<i32>, // 绑定的B站账号ID，None 表示默认账号
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_dynamic_api: Option<bool>, // 投稿源：是否使用动态API
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_type: Option<String>, // 榜单源：popular/weekly/rank
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rid: Option<i32>, // 榜单源：排行榜分区ID
 }
 
 #[derive(Serialize, ToSchema)]
@@ -510,6 +560,7 @@ pub struct ConfigResponse {
     pub danmaku_bold: bool,
     pub danmaku_outline: f64,
     pub danmaku_time_offset: f64,
+    pub danmaku_font_path: Option<String>,
     pub danmaku_update_enabled: bool,
     pub danmaku_update_fresh_days: u32,
     pub danmaku_update_fresh_interval_hours: u32,
@@ -517,6 +568,10 @@ pub struct ConfigResponse {
     pub danmaku_update_mature_interval_days: u32,
     pub danmaku_update_cold_days: u32,
     pub danmaku_update_cold_interval_days: u32,
+    pub quality_upgrade_enabled: bool,
+    pub quality_upgrade_window_hours: u32,
+    pub quality_upgrade_check_interval_hours: u32,
+    pub quality_upgrade_max_pages_per_run: u32,
     // 并发控制设置
     pub concurrent_video: usize,
     pub concurrent_page: usize,
@@ -558,6 +613,7 @@ pub struct ConfigResponse {
     pub submission_adaptive_max_hours: u64,
     // 系统设置
     pub scan_deleted_videos: bool,
+    pub cross_source_dedup: bool,
     // aria2监控配置
     pub enable_aria2_health_check: bool,
     pub enable_aria2_auto_restart: bool,
@@ -669,6 +725,7 @@ pub struct ConfigChangeInfo {
     pub old_value: Option<String>,
     pub new_value: String,
     pub changed_at: String,
+    pub changed_by: Option<String>,
 }
 
 // 配置验证响应
@@ -845,6 +902,254 @@ pub struct UpdateCredentialResponse {
     pub message: String,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct BiliAccountInfo {
+    pub id: i32,
+    pub name: String,
+    pub dedeuserid: String,
+    /// 绑定该账号的视频源数量
+    pub bound_sources: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BiliAccountsResponse {
+    pub accounts: Vec<BiliAccountInfo>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LoginResponse {
+    /// 会话令牌，放入 Authorization 头（可带 Bearer 前缀）使用
+    pub token: String,
+    pub expires_at: String,
+    pub username: String,
+    pub role: Role,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CurrentIdentityResponse {
+    /// 使用全局 auth_token 访问时为空
+    pub user_id: Option<i32>,
+    pub username: String,
+    pub role: Role,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiUserInfo {
+    pub id: i32,
+    pub username: String,
+    pub role: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiUsersResponse {
+    pub users: Vec<ApiUserInfo>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeyInfo {
+    pub id: i32,
+    pub name: String,
+    /// 密钥明文的前几位，便于辨认
+    pub key_prefix: String,
+    pub scopes: Vec<ApiScope>,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeysResponse {
+    pub keys: Vec<ApiKeyInfo>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    /// 密钥明文，只在创建时返回一次
+    pub key: String,
+    pub info: ApiKeyInfo,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ExportBundleResponse {
+    pub format: String,
+    /// 建议的下载文件名
+    pub file_name: String,
+    /// 导出包内容
+    pub content: String,
+    pub source_count: usize,
+    pub config_count: usize,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BundleFieldChange {
+    pub field: String,
+    pub current: serde_json::Value,
+    pub incoming: serde_json::Value,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BundleSourceResult {
+    pub source_type: String,
+    pub source_id: String,
+    pub name: String,
+    /// create、update、skipped、unchanged 或 failed
+    pub action: String,
+    pub changes: Vec<BundleFieldChange>,
+    pub note: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BundleConfigResult {
+    pub key: String,
+    pub label: String,
+    /// update、skipped 或 ignored
+    pub action: String,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportBundleResponse {
+    pub dry_run: bool,
+    pub strategy: crate::utils::source_bundle::ConflictStrategy,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub sources: Vec<BundleSourceResult>,
+    pub config: Vec<BundleConfigResult>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeclarativeConfigKey {
+    pub key: String,
+    pub label: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeclarativeConfigSource {
+    pub source_type: String,
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeclarativeConfigResponse {
+    /// 是否通过 --config-file 启用了声明式配置文件
+    pub enabled: bool,
+    pub path: Option<String>,
+    pub last_reconciled_at: Option<String>,
+    pub last_error: Option<String>,
+    /// 由配置文件管理、在 API 中只读的配置项
+    pub config_keys: Vec<DeclarativeConfigKey>,
+    /// 由配置文件管理、在 API 中只读的视频源
+    pub sources: Vec<DeclarativeConfigSource>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DatabaseBackupInfo {
+    pub name: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DatabaseBackupsResponse {
+    pub backups: Vec<DatabaseBackupInfo>,
+    /// 备份目录
+    pub dir: String,
+    /// 是否有等待重启后应用的恢复
+    pub restore_pending: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RestoreDatabaseBackupResponse {
+    pub name: String,
+    /// 备份中最新的迁移版本
+    pub latest_migration: String,
+    /// 恢复后启动时需要补齐的迁移数量
+    pub pending_migrations: usize,
+    /// 恢复在重启后生效
+    pub restart_required: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct StreamLinkResponse {
+    /// 带签名的相对路径，拼接管理页地址即可分享
+    pub path: String,
+    pub expires_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditLogEntry {
+    pub id: i32,
+    pub actor: String,
+    pub role: String,
+    pub method: String,
+    pub path: String,
+    pub status_code: i32,
+    pub created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub total: usize,
+    pub entries: Vec<AuditLogEntry>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct EventsResponse {
+    pub events: Vec<EventRecord>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookInfo {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    /// 订阅的事件类型，为空表示全部
+    pub event_kinds: Vec<EventKind>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhooksResponse {
+    pub webhooks: Vec<WebhookInfo>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SaveWebhookResponse {
+    pub info: WebhookInfo,
+    /// 签名密钥，只在创建或轮换时返回
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveryInfo {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_id: i32,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryInfo>,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct CredentialFieldStatus {
     pub has_credential: bool,
@@ -1057,6 +1362,91 @@ pub struct NotificationConfigResponse {
     pub notification_retry_count: u8,
 }
 
+// 画质档案列表响应
+#[derive(Serialize, ToSchema)]
+pub struct QualityProfilesResponse {
+    pub profiles: Vec<crate::config::QualityProfile>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DryRunVideoStream {
+    pub quality: u32,
+    pub quality_description: String,
+    pub codecs: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DryRunAudioStream {
+    pub quality: u32,
+    pub quality_description: String,
+}
+
+// 选流预览响应
+#[derive(Serialize, ToSchema)]
+pub struct QualityProfileDryRunResponse {
+    pub bvid: String,
+    pub cid: i64,
+    pub page: i32,
+    /// 实际使用的画质档案名称；None 表示使用自定义筛选条件或全局配置
+    pub profile: Option<String>,
+    /// 命中的回退规则序号（从 1 开始）；None 表示按基础条件选择
+    pub matched_rule: Option<usize>,
+    /// 是否为 FLV/MP4 混合流（无法区分画质与编码）
+    pub mixed: bool,
+    pub video: Option<DryRunVideoStream>,
+    pub audio: Option<DryRunAudioStream>,
+    /// 接口返回的全部视频流（未经筛选）
+    pub available_video_streams: Vec<DryRunVideoStream>,
+}
+
+// 视频源预览响应
+#[derive(Serialize, ToSchema)]
+pub struct VideoSourcePreviewResponse {
+    pub source_name: Option<String>,
+    /// 实际检查的列表条目数
+    pub scanned: usize,
+    /// 列表在达到 limit 后被截断（后面还有更多视频）
+    pub truncated: bool,
+    pub videos: Vec<PreviewVideoItem>,
+    pub skipped: Vec<PreviewSkippedItem>,
+    pub estimated_total_bytes: u64,
+    /// 体积估算依据说明
+    pub estimate_basis: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PreviewVideoItem {
+    pub bvid: String,
+    pub title: String,
+    pub upper_name: String,
+    pub pubtime: String,
+    pub duration_seconds: Option<i32>,
+    /// 视频所在目录
+    pub folder: String,
+    pub files: Vec<PreviewPageFile>,
+    pub estimated_bytes: u64,
+    /// 数据库中已存在同一 BVID 的视频（可能来自其它视频源）
+    pub already_in_library: bool,
+    /// 获取分P列表失败时的错误信息，此时按单P预览
+    pub page_error: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PreviewPageFile {
+    pub pid: i32,
+    pub name: String,
+    pub path: String,
+    pub duration_seconds: u32,
+    pub estimated_bytes: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PreviewSkippedItem {
+    pub bvid: String,
+    pub title: String,
+    pub reason: String,
+}
+
 // 测试推送响应
 #[derive(Serialize, ToSchema)]
 pub struct TestNotificationResponse {
@@ -0,0 +1,1093 @@
+//! 版本化的对外 REST API（`/api/v1`）
+//!
+//! 与管理页使用的内部接口不同，v1 接口的请求参数和响应结构保持稳定：列表接口统一使用
+//! `page`/`page_size`/`sort`/`order` 分页排序参数并返回 [`Page`]，错误响应携带 [`ErrorCode`]。
+//! 接口文档见 `/api-docs/v1/openapi.json`，工作区中的 `bili_sync_client` 据此生成。
+//!
+//! v1 覆盖视频源、视频、配置、任务队列、推送通知与B站账号。管理页专用的登录与扫码、用户与 API Key、
+//! Webhook、备份与导入导出、B站搜索以及视频流代理不属于 v1，仅在内部接口文档 `/api-docs/openapi.json` 中描述。
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use axum::extract::{Extension, Path, Query};
+use axum::routing::{delete, get, post, put};
+use axum::{middleware, Router};
+use bili_sync_entity::{collection, favorite, page, ranking, submission, video, video_source, watch_later};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::adapter::SOURCE_TYPES;
+use crate::api::auth::{self, OpenAPIAuth, RouteAccess};
+use crate::api::error::{ErrorCode, InnerApiError};
+use crate::api::handler::{self, CancelQueueTaskResponse, QueueStatusResponse};
+use crate::api::request::{
+    AddVideoSourceRequest, TestNotificationRequest, UpdateConfigRequest, UpdateNotificationConfigRequest,
+    UpsertBiliAccountRequest,
+};
+use crate::api::response::{
+    BiliAccountsResponse, ConfigResponse, NotificationConfigResponse, NotificationStatusResponse,
+    TaskControlStatusResponse, TestNotificationResponse, UpdateConfigResponse,
+};
+use crate::api::wrapper::{ApiError, ApiResponse};
+use crate::auth::api_keys::ApiScope;
+use crate::auth::users::Role;
+use crate::utils::status::{PageStatus, VideoStatus, STATUS_COMPLETED};
+
+pub const API_VERSION: &str = "v1";
+
+const MAX_PAGE_SIZE: u64 = 100;
+
+#[derive(Deserialize, Serialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// 所有列表接口共用的分页与排序参数
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// 页码，从 1 开始，默认 1
+    #[serde(default = "default_page")]
+    pub page: u64,
+    /// 每页数量，1~100，默认 20
+    #[serde(default = "default_page_size")]
+    pub page_size: u64,
+    /// 排序字段，可选值见各接口说明
+    pub sort: Option<String>,
+    /// 排序方向，默认 desc
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_page_size() -> u64 {
+    20
+}
+
+impl PageParams {
+    fn validate(&self) -> Result<(), InnerApiError> {
+        if self.page == 0 {
+            return Err(InnerApiError::BadRequest("page 从 1 开始".to_string()));
+        }
+        if self.page_size == 0 || self.page_size > MAX_PAGE_SIZE {
+            return Err(InnerApiError::BadRequest(format!(
+                "page_size 需在 1~{} 之间",
+                MAX_PAGE_SIZE
+            )));
+        }
+        Ok(())
+    }
+
+    /// 校验排序字段，未指定时使用第一个可选值
+    fn sort_field(&self, allowed: &[&'static str]) -> Result<&'static str, InnerApiError> {
+        match self.sort.as_deref() {
+            None => Ok(allowed[0]),
+            Some(sort) => allowed.iter().copied().find(|field| *field == sort).ok_or_else(|| {
+                InnerApiError::BadRequest(format!("不支持的排序字段 {}，可选值: {}", sort, allowed.join(", ")))
+            }),
+        }
+    }
+
+    fn offset(&self) -> u64 {
+        (self.page - 1) * self.page_size
+    }
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+}
+
+impl<T> Page<T> {
+    fn new(items: Vec<T>, total: u64, params: &PageParams) -> Self {
+        Self {
+            items,
+            total,
+            page: params.page,
+            page_size: params.page_size,
+            total_pages: total.div_ceil(params.page_size),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct ApiInfo {
+    pub api_version: String,
+    pub app_version: String,
+}
+
+#[derive(Serialize, ToSchema, Debug, Clone)]
+pub struct Source {
+    /// favorite、collection、submission、watch_later、bangumi 或 ranking
+    pub source_type: String,
+    pub id: i32,
+    /// 收藏夹ID、合集ID、UP主ID、番剧 Season ID 或榜单分区ID
+    pub remote_id: String,
+    pub name: String,
+    pub path: String,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct SourceFilter {
+    /// 按视频源类型筛选
+    pub source_type: Option<String>,
+    /// 按启用状态筛选
+    pub enabled: Option<bool>,
+    /// 名称或路径包含的关键词
+    pub query: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema, Debug)]
+pub struct UpdateSourceEnabled {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct Video {
+    pub id: i32,
+    pub bvid: String,
+    pub name: String,
+    pub upper_id: i64,
+    pub upper_name: String,
+    pub cover: String,
+    pub path: String,
+    pub category: i32,
+    pub pubtime: String,
+    pub created_at: String,
+    pub valid: bool,
+    pub deleted: bool,
+    pub source_type: Option<String>,
+    pub source_id: Option<i32>,
+    /// 全部子任务是否已结束（成功或达到重试上限）
+    pub completed: bool,
+    /// 五个子任务的状态：0 未开始，1~4 失败次数，7 成功
+    pub download_status: Vec<u32>,
+    pub total_file_size_bytes: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct VideoPage {
+    pub id: i32,
+    pub pid: i32,
+    pub cid: i64,
+    pub name: String,
+    pub duration: u32,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub path: Option<String>,
+    pub file_size_bytes: Option<i64>,
+    pub completed: bool,
+    pub download_status: Vec<u32>,
+}
+
+#[derive(Serialize, ToSchema, Debug)]
+pub struct VideoDetail {
+    pub video: Video,
+    pub pages: Vec<VideoPage>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoStatusFilter {
+    Completed,
+    Pending,
+    Failed,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct VideoFilter {
+    /// 视频源类型，需与 source_id 同时使用
+    pub source_type: Option<String>,
+    /// 视频源ID
+    pub source_id: Option<i32>,
+    /// 名称或路径包含的关键词
+    pub query: Option<String>,
+    /// 按下载状态筛选
+    pub status: Option<VideoStatusFilter>,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteSourceParams {
+    /// 是否同时删除已下载的本地文件
+    #[serde(default)]
+    pub delete_local_files: bool,
+}
+
+#[derive(Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct ResetVideoParams {
+    /// 是否连同已成功的子任务一起重置
+    #[serde(default)]
+    pub force: bool,
+}
+
+fn validate_source_type(source_type: &str) -> Result<(), InnerApiError> {
+    if SOURCE_TYPES.contains(&source_type) {
+        Ok(())
+    } else {
+        Err(unsupported_source_type(source_type))
+    }
+}
+
+fn unsupported_source_type(source_type: &str) -> InnerApiError {
+    InnerApiError::BadRequest(format!(
+        "不支持的视频源类型 {}，可选值: {}",
+        source_type,
+        SOURCE_TYPES.join(", ")
+    ))
+}
+
+fn source(
+    source_type: &str,
+    id: i32,
+    remote_id: String,
+    name: &str,
+    path: &str,
+    enabled: bool,
+    created_at: &str,
+) -> Source {
+    Source {
+        source_type: source_type.to_string(),
+        id,
+        remote_id,
+        name: name.to_string(),
+        path: path.to_string(),
+        enabled,
+        created_at: created_at.to_string(),
+    }
+}
+
+async fn all_sources(db: &DatabaseConnection) -> Result<Vec<Source>, ApiError> {
+    let (favorites, collections, submissions, watch_laters, bangumis, rankings) = tokio::try_join!(
+        favorite::Entity::find().all(db),
+        collection::Entity::find().all(db),
+        submission::Entity::find().all(db),
+        watch_later::Entity::find().all(db),
+        video_source::Entity::find()
+            .filter(video_source::Column::Type.eq(1))
+            .all(db),
+        ranking::Entity::find().all(db),
+    )?;
+
+    let mut sources = Vec::new();
+    sources.extend(favorites.iter().map(|m| {
+        source(
+            "favorite",
+            m.id,
+            m.f_id.to_string(),
+            &m.name,
+            &m.path,
+            m.enabled,
+            &m.created_at,
+        )
+    }));
+    sources.extend(collections.iter().map(|m| {
+        source(
+            "collection",
+            m.id,
+            m.s_id.to_string(),
+            &m.name,
+            &m.path,
+            m.enabled,
+            &m.created_at,
+        )
+    }));
+    sources.extend(submissions.iter().map(|m| {
+        source(
+            "submission",
+            m.id,
+            m.upper_id.to_string(),
+            &m.upper_name,
+            &m.path,
+            m.enabled,
+            &m.created_at,
+        )
+    }));
+    sources.extend(watch_laters.iter().map(|m| {
+        source(
+            "watch_later",
+            m.id,
+            String::new(),
+            "稍后再看",
+            &m.path,
+            m.enabled,
+            &m.created_at,
+        )
+    }));
+    sources.extend(bangumis.iter().map(|m| {
+        let remote_id = m.season_id.clone().unwrap_or_default();
+        source("bangumi", m.id, remote_id, &m.name, &m.path, m.enabled, &m.created_at)
+    }));
+    sources.extend(rankings.iter().map(|m| {
+        source(
+            "ranking",
+            m.id,
+            m.rid.to_string(),
+            &m.name,
+            &m.path,
+            m.enabled,
+            &m.created_at,
+        )
+    }));
+    Ok(sources)
+}
+
+async fn find_source(db: &DatabaseConnection, source_type: &str, id: i32) -> Result<Source, ApiError> {
+    validate_source_type(source_type)?;
+    all_sources(db)
+        .await?
+        .into_iter()
+        .find(|source| source.source_type == source_type && source.id == id)
+        .ok_or_else(|| InnerApiError::NotFound(id).into())
+}
+
+impl From<video::Model> for Video {
+    fn from(model: video::Model) -> Self {
+        let (source_type, source_id) = crate::adapter::video_source_of(&model);
+        let status = VideoStatus::from(model.download_status);
+        Self {
+            id: model.id,
+            bvid: model.bvid,
+            name: model.name,
+            upper_id: model.upper_id,
+            upper_name: model.upper_name,
+            cover: model.cover,
+            path: model.path,
+            category: model.category,
+            pubtime: model.pubtime.format("%Y-%m-%d %H:%M:%S").to_string(),
+            created_at: model.created_at,
+            valid: model.valid,
+            deleted: model.deleted != 0,
+            source_type,
+            source_id,
+            completed: status.get_completed(),
+            download_status: <[u32; 5]>::from(status).to_vec(),
+            total_file_size_bytes: model.total_file_size_bytes,
+        }
+    }
+}
+
+impl From<page::Model> for VideoPage {
+    fn from(model: page::Model) -> Self {
+        let status = PageStatus::from(model.download_status);
+        Self {
+            id: model.id,
+            pid: model.pid,
+            cid: model.cid,
+            name: model.name,
+            duration: model.duration,
+            width: model.width,
+            height: model.height,
+            path: model.path,
+            file_size_bytes: model.file_size_bytes,
+            completed: status.get_completed(),
+            download_status: <[u32; 5]>::from(status).to_vec(),
+        }
+    }
+}
+
+/// 任一子任务处于失败重试状态
+fn failed_condition() -> Condition {
+    (0..5).fold(Condition::any(), |condition, offset| {
+        condition.add(Expr::cust(format!(
+            "(({} >> {}) & 7) BETWEEN 1 AND 6",
+            crate::database::dialect::current().unsigned_column("download_status"),
+            offset * 3
+        )))
+    })
+}
+
+pub(crate) fn video_source_condition(source_type: &str, id: i32) -> Result<SimpleExpr, InnerApiError> {
+    crate::adapter::video_source_condition(source_type, id).ok_or_else(|| unsupported_source_type(source_type))
+}
+
+async fn video_detail(db: &DatabaseConnection, id: i32) -> Result<VideoDetail, ApiError> {
+    let video = video::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or(InnerApiError::NotFound(id))?;
+    let pages = page::Entity::find()
+        .filter(page::Column::VideoId.eq(id))
+        .order_by_asc(page::Column::Pid)
+        .all(db)
+        .await?;
+    Ok(VideoDetail {
+        video: video.into(),
+        pages: pages.into_iter().map(VideoPage::from).collect(),
+    })
+}
+
+/// 获取 API 版本信息
+#[utoipa::path(
+    get,
+    path = "/api/v1/info",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<ApiInfo>),
+    )
+)]
+pub async fn get_info() -> ApiResponse<ApiInfo> {
+    ApiResponse::ok(ApiInfo {
+        api_version: API_VERSION.to_string(),
+        app_version: crate::config::version().to_string(),
+    })
+}
+
+/// 分页列出视频源，可按 id、name、source_type、created_at 排序
+#[utoipa::path(
+    get,
+    path = "/api/v1/sources",
+    tag = "v1",
+    params(PageParams, SourceFilter),
+    responses(
+        (status = 200, body = ApiResponse<Page<Source>>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn list_sources(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<PageParams>,
+    Query(filter): Query<SourceFilter>,
+) -> Result<ApiResponse<Page<Source>>, ApiError> {
+    params.validate()?;
+    let sort = params.sort_field(&["id", "name", "source_type", "created_at"])?;
+    if let Some(source_type) = filter.source_type.as_deref() {
+        validate_source_type(source_type)?;
+    }
+
+    let mut sources: Vec<Source> = all_sources(&db)
+        .await?
+        .into_iter()
+        .filter(|source| filter.source_type.as_deref().is_none_or(|t| source.source_type == t))
+        .filter(|source| filter.enabled.is_none_or(|enabled| source.enabled == enabled))
+        .filter(|source| {
+            filter
+                .query
+                .as_deref()
+                .is_none_or(|query| source.name.contains(query) || source.path.contains(query))
+        })
+        .collect();
+    sources.sort_by(|a, b| {
+        let ordering = match sort {
+            "name" => a.name.cmp(&b.name),
+            "source_type" => a.source_type.cmp(&b.source_type),
+            "created_at" => a.created_at.cmp(&b.created_at),
+            _ => Ordering::Equal,
+        }
+        .then_with(|| a.id.cmp(&b.id))
+        .then_with(|| a.source_type.cmp(&b.source_type));
+        match params.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+
+    let total = sources.len() as u64;
+    let items = sources
+        .into_iter()
+        .skip(params.offset() as usize)
+        .take(params.page_size as usize)
+        .collect();
+    Ok(ApiResponse::ok(Page::new(items, total, &params)))
+}
+
+/// 获取单个视频源
+#[utoipa::path(
+    get,
+    path = "/api/v1/sources/{source_type}/{id}",
+    tag = "v1",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    responses(
+        (status = 200, body = ApiResponse<Source>),
+        (status = 404, description = "视频源不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn get_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+) -> Result<ApiResponse<Source>, ApiError> {
+    Ok(ApiResponse::ok(find_source(&db, &source_type, id).await?))
+}
+
+/// 启用或禁用视频源
+#[utoipa::path(
+    put,
+    path = "/api/v1/sources/{source_type}/{id}/enabled",
+    tag = "v1",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+    ),
+    request_body = UpdateSourceEnabled,
+    responses(
+        (status = 200, body = ApiResponse<Source>),
+        (status = 403, description = "视频源由声明式配置文件管理（forbidden）", body = ApiResponse<String>),
+        (status = 404, description = "视频源不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn update_source_enabled(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    axum::Json(request): axum::Json<UpdateSourceEnabled>,
+) -> Result<ApiResponse<Source>, ApiError> {
+    find_source(&db, &source_type, id).await?;
+    crate::api::handler::update_video_source_enabled_internal(db.clone(), source_type.clone(), id, request.enabled)
+        .await?;
+    Ok(ApiResponse::ok(find_source(&db, &source_type, id).await?))
+}
+
+/// 分页列出视频，可按 id、name、pubtime、created_at 排序
+#[utoipa::path(
+    get,
+    path = "/api/v1/videos",
+    tag = "v1",
+    params(PageParams, VideoFilter),
+    responses(
+        (status = 200, body = ApiResponse<Page<Video>>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn list_videos(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Query(params): Query<PageParams>,
+    Query(filter): Query<VideoFilter>,
+) -> Result<ApiResponse<Page<Video>>, ApiError> {
+    params.validate()?;
+    let sort = params.sort_field(&["id", "name", "pubtime", "created_at"])?;
+
+    let mut query = video::Entity::find();
+    if !crate::config::with_config(|bundle| bundle.config.scan_deleted_videos) {
+        query = query.filter(video::Column::Deleted.eq(0));
+    }
+    match (filter.source_type.as_deref(), filter.source_id) {
+        (Some(source_type), Some(id)) => query = query.filter(video_source_condition(source_type, id)?),
+        (None, None) => {}
+        _ => {
+            return Err(InnerApiError::BadRequest("source_type 与 source_id 需同时提供".to_string()).into());
+        }
+    }
+    if let Some(word) = filter.query.as_deref() {
+        query = query.filter(
+            video::Column::Name
+                .contains(word)
+                .or(video::Column::Path.contains(word)),
+        );
+    }
+    query = match filter.status {
+        Some(VideoStatusFilter::Completed) => query.filter(video::Column::DownloadStatus.gte(STATUS_COMPLETED)),
+        Some(VideoStatusFilter::Pending) => query.filter(video::Column::DownloadStatus.lt(STATUS_COMPLETED)),
+        Some(VideoStatusFilter::Failed) => query.filter(failed_condition()),
+        None => query,
+    };
+
+    let column = match sort {
+        "name" => video::Column::Name,
+        "pubtime" => video::Column::Pubtime,
+        "created_at" => video::Column::CreatedAt,
+        _ => video::Column::Id,
+    };
+    let order = match params.order {
+        SortOrder::Asc => sea_orm::Order::Asc,
+        SortOrder::Desc => sea_orm::Order::Desc,
+    };
+    query = query.order_by(column, order.clone()).order_by(video::Column::Id, order);
+
+    let paginator = query.paginate(db.as_ref(), params.page_size);
+    let total = paginator.num_items().await?;
+    let items = paginator
+        .fetch_page(params.page - 1)
+        .await?
+        .into_iter()
+        .map(Video::from)
+        .collect();
+    Ok(ApiResponse::ok(Page::new(items, total, &params)))
+}
+
+/// 获取视频及其分页
+#[utoipa::path(
+    get,
+    path = "/api/v1/videos/{id}",
+    tag = "v1",
+    params(("id" = i32, Path, description = "视频ID")),
+    responses(
+        (status = 200, body = ApiResponse<VideoDetail>),
+        (status = 404, description = "视频不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn get_video(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<VideoDetail>, ApiError> {
+    Ok(ApiResponse::ok(video_detail(&db, id).await?))
+}
+
+/// 重置视频的失败子任务，force 为 true 时重置全部子任务
+#[utoipa::path(
+    post,
+    path = "/api/v1/videos/{id}/reset",
+    tag = "v1",
+    params(("id" = i32, Path, description = "视频ID"), ResetVideoParams),
+    responses(
+        (status = 200, body = ApiResponse<VideoDetail>),
+        (status = 404, description = "视频不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn reset_video(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+    Query(params): Query<ResetVideoParams>,
+) -> Result<ApiResponse<VideoDetail>, ApiError> {
+    video_detail(&db, id).await?;
+    let query = std::collections::HashMap::from([("force".to_string(), params.force.to_string())]);
+    crate::api::handler::reset_video(Path(id), Query(query), Extension(db.clone())).await?;
+    Ok(ApiResponse::ok(video_detail(&db, id).await?))
+}
+
+/// 添加视频源
+#[utoipa::path(
+    post,
+    path = "/api/v1/sources",
+    tag = "v1",
+    request_body = AddVideoSourceRequest,
+    responses(
+        (status = 200, body = ApiResponse<Source>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn create_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<AddVideoSourceRequest>,
+) -> Result<ApiResponse<Source>, ApiError> {
+    validate_source_type(&request.source_type)?;
+    let created = handler::add_video_source(Extension(db.clone()), axum::Json(request))
+        .await?
+        .into_data();
+    Ok(ApiResponse::ok(
+        find_source(&db, &created.source_type, created.source_id).await?,
+    ))
+}
+
+/// 删除视频源，返回删除前的视频源信息
+#[utoipa::path(
+    delete,
+    path = "/api/v1/sources/{source_type}/{id}",
+    tag = "v1",
+    params(
+        ("source_type" = String, Path, description = "视频源类型"),
+        ("id" = i32, Path, description = "视频源ID"),
+        DeleteSourceParams,
+    ),
+    responses(
+        (status = 200, body = ApiResponse<Source>),
+        (status = 403, description = "视频源由声明式配置文件管理（forbidden）", body = ApiResponse<String>),
+        (status = 404, description = "视频源不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn delete_source(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path((source_type, id)): Path<(String, i32)>,
+    Query(params): Query<DeleteSourceParams>,
+) -> Result<ApiResponse<Source>, ApiError> {
+    let source = find_source(&db, &source_type, id).await?;
+    handler::delete_video_source(
+        Extension(db),
+        Path((source_type, id)),
+        Query(crate::api::request::DeleteVideoSourceRequest {
+            delete_local_files: params.delete_local_files,
+        }),
+    )
+    .await?;
+    Ok(ApiResponse::ok(source))
+}
+
+/// 获取当前配置
+#[utoipa::path(
+    get,
+    path = "/api/v1/config",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<ConfigResponse>),
+    )
+)]
+pub async fn get_config() -> Result<ApiResponse<ConfigResponse>, ApiError> {
+    handler::get_config().await
+}
+
+/// 更新配置，只修改请求中提供的字段
+#[utoipa::path(
+    put,
+    path = "/api/v1/config",
+    tag = "v1",
+    request_body = UpdateConfigRequest,
+    responses(
+        (status = 200, body = ApiResponse<UpdateConfigResponse>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn update_config(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<UpdateConfigRequest>,
+) -> Result<ApiResponse<UpdateConfigResponse>, ApiError> {
+    handler::update_config(Extension(db), axum::Json(request)).await
+}
+
+/// 获取各任务队列的状态
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<QueueStatusResponse>),
+    )
+)]
+pub async fn get_task_queues() -> Result<ApiResponse<QueueStatusResponse>, ApiError> {
+    handler::get_queue_status().await
+}
+
+/// 取消尚未开始处理的队列任务
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tasks/{task_id}",
+    tag = "v1",
+    params(("task_id" = String, Path, description = "任务ID")),
+    responses(
+        (status = 200, body = ApiResponse<CancelQueueTaskResponse>),
+        (status = 400, description = "任务不存在或已进入处理（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn cancel_task(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(task_id): Path<String>,
+) -> Result<ApiResponse<CancelQueueTaskResponse>, ApiError> {
+    handler::cancel_queue_task(Path(task_id), Extension(db)).await
+}
+
+/// 获取扫描任务的暂停与运行状态
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/control",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<TaskControlStatusResponse>),
+    )
+)]
+pub async fn get_task_control() -> Result<ApiResponse<TaskControlStatusResponse>, ApiError> {
+    handler::get_task_control_status().await
+}
+
+/// 暂停所有扫描和下载任务
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/control/pause",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<TaskControlStatusResponse>),
+    )
+)]
+pub async fn pause_tasks() -> Result<ApiResponse<TaskControlStatusResponse>, ApiError> {
+    handler::pause_scanning_endpoint().await?;
+    handler::get_task_control_status().await
+}
+
+/// 恢复扫描和下载任务
+#[utoipa::path(
+    post,
+    path = "/api/v1/tasks/control/resume",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<TaskControlStatusResponse>),
+    )
+)]
+pub async fn resume_tasks() -> Result<ApiResponse<TaskControlStatusResponse>, ApiError> {
+    handler::resume_scanning_endpoint().await?;
+    handler::get_task_control_status().await
+}
+
+/// 获取推送通知配置
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/config",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<NotificationConfigResponse>),
+    )
+)]
+pub async fn get_notification_config() -> Result<ApiResponse<NotificationConfigResponse>, ApiError> {
+    handler::get_notification_config().await
+}
+
+/// 更新推送通知配置，返回更新后的配置
+#[utoipa::path(
+    put,
+    path = "/api/v1/notifications/config",
+    tag = "v1",
+    request_body = UpdateNotificationConfigRequest,
+    responses(
+        (status = 200, body = ApiResponse<NotificationConfigResponse>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn update_notification_config(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<UpdateNotificationConfigRequest>,
+) -> Result<ApiResponse<NotificationConfigResponse>, ApiError> {
+    handler::update_notification_config(Extension(db), axum::Json(request)).await?;
+    handler::get_notification_config().await
+}
+
+/// 发送一条测试推送
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/test",
+    tag = "v1",
+    request_body = TestNotificationRequest,
+    responses(
+        (status = 200, body = ApiResponse<TestNotificationResponse>),
+        (status = 400, description = "推送未配置（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn test_notification(
+    axum::Json(request): axum::Json<TestNotificationRequest>,
+) -> Result<ApiResponse<TestNotificationResponse>, ApiError> {
+    handler::test_notification_handler(axum::Json(request)).await
+}
+
+/// 获取推送通知的发送状态
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/status",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<NotificationStatusResponse>),
+    )
+)]
+pub async fn get_notification_status() -> Result<ApiResponse<NotificationStatusResponse>, ApiError> {
+    handler::get_notification_status().await
+}
+
+/// 列出额外的B站账号
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts",
+    tag = "v1",
+    responses(
+        (status = 200, body = ApiResponse<BiliAccountsResponse>),
+    )
+)]
+pub async fn list_accounts(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+) -> Result<ApiResponse<BiliAccountsResponse>, ApiError> {
+    handler::get_bili_accounts(Extension(db)).await
+}
+
+/// 新增B站账号，返回全部账号
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts",
+    tag = "v1",
+    request_body = UpsertBiliAccountRequest,
+    responses(
+        (status = 200, body = ApiResponse<BiliAccountsResponse>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+    )
+)]
+pub async fn create_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    axum::Json(request): axum::Json<UpsertBiliAccountRequest>,
+) -> Result<ApiResponse<BiliAccountsResponse>, ApiError> {
+    handler::add_bili_account(Extension(db), axum::Json(request)).await
+}
+
+/// 更新B站账号，返回全部账号
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{id}",
+    tag = "v1",
+    params(("id" = i32, Path, description = "B站账号ID")),
+    request_body = UpsertBiliAccountRequest,
+    responses(
+        (status = 200, body = ApiResponse<BiliAccountsResponse>),
+        (status = 400, description = "参数无效（bad_request）", body = ApiResponse<String>),
+        (status = 404, description = "账号不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn update_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+    axum::Json(request): axum::Json<UpsertBiliAccountRequest>,
+) -> Result<ApiResponse<BiliAccountsResponse>, ApiError> {
+    handler::update_bili_account(Extension(db), Path(id), axum::Json(request)).await
+}
+
+/// 删除B站账号，返回剩余账号
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{id}",
+    tag = "v1",
+    params(("id" = i32, Path, description = "B站账号ID")),
+    responses(
+        (status = 200, body = ApiResponse<BiliAccountsResponse>),
+        (status = 400, description = "账号仍被视频源绑定（bad_request）", body = ApiResponse<String>),
+        (status = 404, description = "账号不存在（not_found）", body = ApiResponse<String>),
+    )
+)]
+pub async fn delete_account(
+    Extension(db): Extension<Arc<DatabaseConnection>>,
+    Path(id): Path<i32>,
+) -> Result<ApiResponse<BiliAccountsResponse>, ApiError> {
+    handler::delete_bili_account(Extension(db), Path(id)).await
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "bili-sync", description = "bili-sync 对外 REST API", version = "1"),
+    paths(
+        get_info,
+        list_sources,
+        get_source,
+        create_source,
+        update_source_enabled,
+        delete_source,
+        list_videos,
+        get_video,
+        reset_video,
+        get_config,
+        update_config,
+        get_task_queues,
+        cancel_task,
+        get_task_control,
+        pause_tasks,
+        resume_tasks,
+        get_notification_config,
+        update_notification_config,
+        test_notification,
+        get_notification_status,
+        list_accounts,
+        create_account,
+        update_account,
+        delete_account
+    ),
+    components(schemas(ErrorCode, SortOrder, VideoStatusFilter)),
+    modifiers(&OpenAPIAuth),
+    security(
+        ("Token" = []),
+    )
+)]
+pub struct V1ApiDoc;
+
+/// v1 接口路由，权限分组与内部接口一致
+pub fn router() -> Router {
+    let viewer = Router::new()
+        .route("/api/v1/info", get(get_info))
+        .route("/api/v1/sources", get(list_sources))
+        .route("/api/v1/sources/{source_type}/{id}", get(get_source))
+        .route("/api/v1/videos", get(list_videos))
+        .route("/api/v1/videos/{id}", get(get_video))
+        .route("/api/v1/tasks", get(get_task_queues))
+        .route("/api/v1/tasks/control", get(get_task_control))
+        .route("/api/v1/notifications/status", get(get_notification_status))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Viewer, Some(ApiScope::ReadOnly)),
+            auth::require_access,
+        ));
+    let source_management = Router::new()
+        .route("/api/v1/sources", post(create_source))
+        .route("/api/v1/sources/{source_type}/{id}", delete(delete_source))
+        .route("/api/v1/sources/{source_type}/{id}/enabled", put(update_source_enabled))
+        .route_layer(middleware::from_fn(auth::guard_declarative_sources))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Operator, Some(ApiScope::SourceManagement)),
+            auth::require_access,
+        ));
+    let queue_control = Router::new()
+        .route("/api/v1/videos/{id}/reset", post(reset_video))
+        .route("/api/v1/tasks/{task_id}", delete(cancel_task))
+        .route("/api/v1/tasks/control/pause", post(pause_tasks))
+        .route("/api/v1/tasks/control/resume", post(resume_tasks))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Operator, Some(ApiScope::QueueControl)),
+            auth::require_access,
+        ));
+    let admin = Router::new()
+        .route("/api/v1/config", get(get_config).put(update_config))
+        .route(
+            "/api/v1/notifications/config",
+            get(get_notification_config).put(update_notification_config),
+        )
+        .route("/api/v1/notifications/test", post(test_notification))
+        .route("/api/v1/accounts", get(list_accounts).post(create_account))
+        .route("/api/v1/accounts/{id}", put(update_account).delete(delete_account))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Admin, None),
+            auth::require_access,
+        ));
+    viewer.merge(source_management).merge(queue_control).merge(admin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(page: u64, page_size: u64, sort: Option<&str>) -> PageParams {
+        PageParams {
+            page,
+            page_size,
+            sort: sort.map(str::to_string),
+            order: SortOrder::Desc,
+        }
+    }
+
+    #[test]
+    fn page_params_are_validated() {
+        assert!(params(1, 20, None).validate().is_ok());
+        assert!(params(0, 20, None).validate().is_err());
+        assert!(params(1, 0, None).validate().is_err());
+        assert!(params(1, MAX_PAGE_SIZE + 1, None).validate().is_err());
+        assert_eq!(params(3, 20, None).offset(), 40);
+
+        let allowed = ["id", "name"];
+        assert_eq!(params(1, 20, None).sort_field(&allowed).unwrap(), "id");
+        assert_eq!(params(1, 20, Some("name")).sort_field(&allowed).unwrap(), "name");
+        assert!(params(1, 20, Some("path")).sort_field(&allowed).is_err());
+
+        let page = Page::new(vec![1, 2], 41, &params(1, 20, None));
+        assert_eq!(page.total_pages, 3);
+    }
+
+    /// 客户端 crate 依据此文件生成；接口变化后使用 `UPDATE_OPENAPI=1 cargo test` 重新生成
+    #[test]
+    fn client_openapi_spec_is_up_to_date() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../bili_sync_client/openapi.json");
+        let spec = V1ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(&path, &spec).unwrap();
+        }
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == spec,
+            "{} 已过期，请使用 UPDATE_OPENAPI=1 cargo test 重新生成",
+            path.display()
+        );
+    }
+}
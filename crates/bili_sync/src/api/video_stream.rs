@@ -1,5 +1,5 @@
 use anyhow::{bail, Context, Result};
-use axum::extract::Path;
+use axum::extract::{Path, Query};
 use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Extension;
@@ -13,6 +13,10 @@ use tracing::{debug, error, info, warn};
 
 use bili_sync_entity::entities::{page, video};
 
+use crate::api::error::InnerApiError;
+use crate::auth::stream_links::{StreamLinkError, StreamResource, StreamSignature};
+use crate::auth::users::Identity;
+
 /// Range请求参数
 #[derive(Debug)]
 pub struct RangeSpec {
@@ -66,15 +70,22 @@ pub fn parse_range_header(range_header: &str, file_size: u64) -> Result<RangeSpe
     })
 }
 
-/// 流式传输视频文件
+/// 流式传输视频文件，需要登录身份或有效的分享链接签名
 pub async fn stream_video(
     Path(video_id): Path<String>,
+    Query(signature): Query<StreamSignature>,
+    identity: Option<Extension<Identity>>,
     headers: HeaderMap,
     Extension(db): Extension<Arc<DatabaseConnection>>,
 ) -> impl IntoResponse {
-    match stream_video_impl(video_id, headers, db).await {
+    let identity = identity.map(|Extension(identity)| identity);
+    match stream_video_impl(video_id, identity.as_ref(), &signature, headers, db).await {
         Ok(response) => response,
         Err(e) => {
+            if e.downcast_ref::<StreamLinkError>().is_some() || e.downcast_ref::<InnerApiError>().is_some() {
+                warn!("拒绝视频流请求: {:#}", e);
+                return (StatusCode::FORBIDDEN, e.to_string()).into_response();
+            }
             let error_text = format!("{:#}", e);
             if error_text.contains("分页记录存在但没有有效的文件路径")
                 || error_text.contains("分页记录对应的视频已删除")
@@ -89,9 +100,18 @@ pub async fn stream_video(
     }
 }
 
-async fn stream_video_impl(video_id: String, headers: HeaderMap, db: Arc<DatabaseConnection>) -> Result<Response> {
+async fn stream_video_impl(
+    video_id: String,
+    identity: Option<&Identity>,
+    signature: &StreamSignature,
+    headers: HeaderMap,
+    db: Arc<DatabaseConnection>,
+) -> Result<Response> {
     debug!("请求视频流: {}", video_id);
 
+    // 未登录时只允许通过签名的分享链接访问，避免按ID枚举播放任意视频
+    crate::api::auth::authorize_stream(identity, StreamResource::LocalVideo(&video_id), signature)?;
+
     // 从数据库查询视频文件路径
     let video_path = find_video_file(&video_id, &db).await?;
 
@@ -190,7 +210,7 @@ async fn maybe_remux_flv_in_mp4(video_path: &PathBuf) -> Result<()> {
 }
 
 /// 查找视频文件路径
-async fn find_video_file(video_id: &str, db: &DatabaseConnection) -> Result<PathBuf> {
+pub(crate) async fn find_video_file(video_id: &str, db: &DatabaseConnection) -> Result<PathBuf> {
     debug!("查找视频文件: {}", video_id);
 
     // 首先尝试作为分页ID查找
@@ -5,33 +5,54 @@ use reqwest::StatusCode;
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::api::error::InnerApiError;
+use crate::api::error::{ErrorCode, InnerApiError};
 
 #[derive(ToSchema, Serialize)]
 pub struct ApiResponse<T: Serialize> {
     status_code: u16,
+    /// 仅错误响应携带的错误码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<ErrorCode>,
     data: T,
 }
 
 impl<T: Serialize> ApiResponse<T> {
+    fn new(status_code: u16, code: Option<ErrorCode>, data: T) -> Self {
+        Self {
+            status_code,
+            code,
+            data,
+        }
+    }
+
     pub fn ok(data: T) -> Self {
-        Self { status_code: 200, data }
+        Self::new(200, None, data)
     }
 
     pub fn unauthorized(data: T) -> Self {
-        Self { status_code: 401, data }
+        Self::new(401, Some(ErrorCode::Unauthorized), data)
+    }
+
+    pub fn forbidden(data: T) -> Self {
+        Self::new(403, Some(ErrorCode::Forbidden), data)
     }
 
     pub fn not_found(data: T) -> Self {
-        Self { status_code: 404, data }
+        Self::new(404, Some(ErrorCode::NotFound), data)
     }
 
     pub fn bad_request(data: T) -> Self {
-        Self { status_code: 400, data }
+        Self::new(400, Some(ErrorCode::BadRequest), data)
     }
 
     pub fn internal_server_error(data: T) -> Self {
-        Self { status_code: 500, data }
+        Self::new(500, Some(ErrorCode::Internal), data)
+    }
+
+    /// 替换默认的错误码
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
     }
 
     pub fn into_data(self) -> T {
@@ -52,6 +73,12 @@ impl<T: Serialize> IntoResponse for ApiResponse<T> {
 #[derive(Debug)]
 pub struct ApiError(Error);
 
+impl ApiError {
+    pub fn into_inner(self) -> Error {
+        self.0
+    }
+}
+
 impl<E> From<E> for ApiError
 where
     E: Into<anyhow::Error>,
@@ -64,13 +91,14 @@ where
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
         if let Some(inner_error) = self.0.downcast_ref::<InnerApiError>() {
-            match inner_error {
-                InnerApiError::NotFound(_) => return ApiResponse::not_found(self.0.to_string()).into_response(),
-                InnerApiError::BadRequest(_) => return ApiResponse::bad_request(self.0.to_string()).into_response(),
-                InnerApiError::DatabaseError(_) => {
-                    return ApiResponse::internal_server_error(self.0.to_string()).into_response()
-                }
-            }
+            let message = self.0.to_string();
+            let response = match inner_error {
+                InnerApiError::NotFound(_) => ApiResponse::not_found(message),
+                InnerApiError::BadRequest(_) => ApiResponse::bad_request(message),
+                InnerApiError::Forbidden(_) | InnerApiError::OutOfScope(_) => ApiResponse::forbidden(message),
+                InnerApiError::DatabaseError(_) => ApiResponse::internal_server_error(message),
+            };
+            return response.with_code(inner_error.code()).into_response();
         }
         ApiResponse::internal_server_error(self.0.to_string()).into_response()
     }
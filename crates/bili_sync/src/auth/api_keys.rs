@@ -0,0 +1,171 @@
+//! 自动化脚本使用的 API 密钥：按权限范围授权，可设置过期时间并随时吊销
+
+use anyhow::{anyhow, Result};
+use bili_sync_entity::api_key;
+use rand::RngCore;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use super::users::{Identity, Role};
+use crate::utils::time_format::now_standard_string;
+
+/// 密钥明文前缀，用于和会话令牌、全局 auth_token 区分
+pub const API_KEY_PREFIX: &str = "bsk_";
+/// 列表中展示的密钥前缀长度
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// API 密钥的权限范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// 只读：浏览视频源、视频、队列状态与日志
+    ReadOnly,
+    /// 队列控制：暂停/恢复扫描、取消队列任务、重置视频任务
+    QueueControl,
+    /// 视频源管理：添加、修改、删除视频源
+    SourceManagement,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ReadOnly => "read_only",
+            ApiScope::QueueControl => "queue_control",
+            ApiScope::SourceManagement => "source_management",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read_only" => Some(ApiScope::ReadOnly),
+            "queue_control" => Some(ApiScope::QueueControl),
+            "source_management" => Some(ApiScope::SourceManagement),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ApiScope::ReadOnly => "只读",
+            ApiScope::QueueControl => "队列控制",
+            ApiScope::SourceManagement => "视频源管理",
+        }
+    }
+}
+
+pub fn encode_scopes(scopes: &[ApiScope]) -> String {
+    let mut names: Vec<&str> = scopes.iter().map(ApiScope::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    names.join(",")
+}
+
+/// 解析数据库中保存的权限范围，忽略无法识别的值
+pub fn decode_scopes(value: &str) -> Vec<ApiScope> {
+    value
+        .split(',')
+        .filter_map(|scope| ApiScope::parse(scope.trim()))
+        .collect()
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// 生成新的 API 密钥，返回 (明文密钥, 已保存的记录)；明文只在创建时返回一次
+pub async fn create_api_key(
+    db: &DatabaseConnection,
+    name: &str,
+    scopes: &[ApiScope],
+    expires_at: Option<String>,
+    created_by: &str,
+) -> Result<(String, api_key::Model)> {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let key = format!("{}{}", API_KEY_PREFIX, hex::encode(bytes));
+
+    let model = api_key::ActiveModel {
+        name: Set(name.to_string()),
+        key_prefix: Set(key[..DISPLAY_PREFIX_LEN].to_string()),
+        key_hash: Set(hash_key(&key)),
+        scopes: Set(encode_scopes(scopes)),
+        expires_at: Set(expires_at),
+        last_used_at: Set(None),
+        revoked_at: Set(None),
+        created_by: Set(created_by.to_string()),
+        created_at: Set(now_standard_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok((key, model))
+}
+
+/// 吊销 API 密钥，已吊销的密钥保留记录以便审计
+pub async fn revoke_api_key(db: &DatabaseConnection, id: i32) -> Result<api_key::Model> {
+    let model = api_key::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| anyhow!("API 密钥 {} 不存在", id))?;
+    if model.revoked_at.is_some() {
+        return Ok(model);
+    }
+    let mut active = model.into_active_model();
+    active.revoked_at = Set(Some(now_standard_string()));
+    Ok(active.update(db).await?)
+}
+
+/// 根据密钥解析身份；密钥不存在、已过期或已吊销时返回 None
+pub async fn resolve_api_key(db: &DatabaseConnection, key: &str) -> Result<Option<Identity>> {
+    if !key.starts_with(API_KEY_PREFIX) {
+        return Ok(None);
+    }
+    let Some(model) = api_key::Entity::find()
+        .filter(api_key::Column::KeyHash.eq(hash_key(key)))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let now = now_standard_string();
+    if model.revoked_at.is_some() || model.expires_at.as_ref().is_some_and(|expires_at| *expires_at <= now) {
+        return Ok(None);
+    }
+
+    let identity = Identity {
+        user_id: None,
+        username: format!("api_key:{}", model.name),
+        // 密钥最多具备操作员权限，实际可访问的接口由 scopes 决定
+        role: Role::Operator,
+        scopes: Some(decode_scopes(&model.scopes)),
+    };
+
+    // 最近使用时间精确到分钟即可，避免每个请求都写库
+    let stale = model
+        .last_used_at
+        .as_deref()
+        .is_none_or(|last_used_at| last_used_at.get(..16) != now.get(..16));
+    if stale {
+        let mut active = model.into_active_model();
+        active.last_used_at = Set(Some(now));
+        active.update(db).await?;
+    }
+    Ok(Some(identity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_round_trip_through_storage_format() {
+        let encoded = encode_scopes(&[ApiScope::SourceManagement, ApiScope::ReadOnly, ApiScope::ReadOnly]);
+        assert_eq!(encoded, "read_only,source_management");
+        assert_eq!(
+            decode_scopes(&format!("{},unknown", encoded)),
+            vec![ApiScope::ReadOnly, ApiScope::SourceManagement]
+        );
+    }
+}
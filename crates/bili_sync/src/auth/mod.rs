@@ -1,3 +1,7 @@
+pub mod api_keys;
+pub mod stream_links;
+pub mod users;
+
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,190 @@
+//! 视频流分享链接：使用 HMAC-SHA256 对资源与过期时间签名，无需登录即可在有效期内播放单个视频
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use sha2::Sha256;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use utoipa::IntoParams;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 未指定有效期时的默认时长（小时）
+pub const DEFAULT_LINK_TTL_HOURS: u32 = 24;
+/// 分享链接的最长有效期（小时）
+pub const MAX_LINK_TTL_HOURS: u32 = 24 * 30;
+
+/// 避免并发生成签名密钥时相互覆盖
+static SECRET_INIT_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// 可被分享的流资源
+#[derive(Debug, Clone, Copy)]
+pub enum StreamResource<'a> {
+    /// 本地已下载的视频或分页，对应 `/api/videos/stream/{video_id}`
+    LocalVideo(&'a str),
+    /// 经由 `/api/videos/proxy-stream` 代理的B站视频流地址
+    Proxy(&'a str),
+}
+
+impl StreamResource<'_> {
+    fn canonical(&self) -> String {
+        match self {
+            StreamResource::LocalVideo(video_id) => format!("video:{}", video_id),
+            StreamResource::Proxy(url) => format!("proxy:{}", url),
+        }
+    }
+}
+
+/// 分享链接附带的签名参数
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct StreamSignature {
+    /// 过期时间（Unix 秒）
+    pub expires: Option<i64>,
+    /// 十六进制 HMAC 签名
+    pub sig: Option<String>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StreamLinkError {
+    #[error("缺少分享链接签名")]
+    Missing,
+    #[error("分享链接已过期")]
+    Expired,
+    #[error("分享链接签名无效")]
+    InvalidSignature,
+}
+
+fn new_mac(secret: &str, resource: StreamResource<'_>, expires: i64) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC 可以接受任意长度的密钥");
+    mac.update(format!("{}\n{}", resource.canonical(), expires).as_bytes());
+    mac
+}
+
+/// 生成十六进制签名
+pub fn sign(secret: &str, resource: StreamResource<'_>, expires: i64) -> String {
+    hex::encode(new_mac(secret, resource, expires).finalize().into_bytes())
+}
+
+/// 校验签名与过期时间
+pub fn verify(
+    secret: &str,
+    resource: StreamResource<'_>,
+    signature: &StreamSignature,
+    now: i64,
+) -> Result<(), StreamLinkError> {
+    let (Some(expires), Some(sig)) = (signature.expires, signature.sig.as_deref()) else {
+        return Err(StreamLinkError::Missing);
+    };
+    if secret.is_empty() {
+        return Err(StreamLinkError::InvalidSignature);
+    }
+    if expires <= now {
+        return Err(StreamLinkError::Expired);
+    }
+    let Ok(sig) = hex::decode(sig) else {
+        return Err(StreamLinkError::InvalidSignature);
+    };
+    // verify_slice 为常量时间比较
+    new_mac(secret, resource, expires)
+        .verify_slice(&sig)
+        .map_err(|_| StreamLinkError::InvalidSignature)
+}
+
+/// 使用当前配置中的密钥校验分享链接
+pub fn verify_with_config(resource: StreamResource<'_>, signature: &StreamSignature) -> Result<(), StreamLinkError> {
+    let config = crate::config::reload_config();
+    verify(
+        &config.stream_link_secret,
+        resource,
+        signature,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// 获取签名密钥，尚未配置时生成并持久化
+pub async fn ensure_signing_secret(db: &DatabaseConnection) -> Result<String> {
+    let secret = crate::config::reload_config().stream_link_secret.clone();
+    if !secret.is_empty() {
+        return Ok(secret);
+    }
+
+    let _guard = SECRET_INIT_LOCK.lock().await;
+    let secret = crate::config::reload_config().stream_link_secret.clone();
+    if !secret.is_empty() {
+        return Ok(secret);
+    }
+
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let secret = hex::encode(bytes);
+    crate::config::ConfigManager::new(db.clone())
+        .update_config_item("stream_link_secret", serde_json::Value::String(secret.clone()))
+        .await
+        .context("保存分享链接签名密钥失败")?;
+    crate::config::reload_config_bundle()
+        .await
+        .context("重新加载配置失败")?;
+    tracing::info!("已生成视频分享链接签名密钥");
+    Ok(secret)
+}
+
+/// 为资源生成带签名的查询参数 `expires=..&sig=..`，返回 (查询参数, 过期时间)
+pub async fn signed_query(
+    db: &DatabaseConnection,
+    resource: StreamResource<'_>,
+    ttl_hours: u32,
+) -> Result<(String, i64)> {
+    let secret = ensure_signing_secret(db).await?;
+    let expires = chrono::Utc::now().timestamp() + i64::from(ttl_hours) * 3600;
+    let sig = sign(&secret, resource, expires);
+    Ok((format!("expires={}&sig={}", expires, sig), expires))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(expires: i64, sig: String) -> StreamSignature {
+        StreamSignature {
+            expires: Some(expires),
+            sig: Some(sig),
+        }
+    }
+
+    #[test]
+    fn signed_link_is_bound_to_resource_and_expiry() {
+        let secret = "secret";
+        let video = StreamResource::LocalVideo("42");
+        let sig = sign(secret, video, 2_000);
+
+        assert_eq!(verify(secret, video, &signature(2_000, sig.clone()), 1_000), Ok(()));
+        assert_eq!(
+            verify(
+                secret,
+                StreamResource::LocalVideo("43"),
+                &signature(2_000, sig.clone()),
+                1_000
+            ),
+            Err(StreamLinkError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(secret, video, &signature(3_000, sig.clone()), 1_000),
+            Err(StreamLinkError::InvalidSignature)
+        );
+        assert_eq!(
+            verify("other", video, &signature(2_000, sig.clone()), 1_000),
+            Err(StreamLinkError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(secret, video, &signature(2_000, sig), 2_000),
+            Err(StreamLinkError::Expired)
+        );
+        assert_eq!(
+            verify(secret, video, &StreamSignature::default(), 1_000),
+            Err(StreamLinkError::Missing)
+        );
+    }
+}
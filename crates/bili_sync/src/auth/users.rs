@@ -0,0 +1,290 @@
+//! 管理页多用户：角色、密码哈希、登录会话与写操作审计
+
+use std::future::Future;
+
+use anyhow::{bail, Result};
+use bili_sync_entity::{api_session, api_user, audit_log};
+use rand::RngCore;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use super::api_keys::ApiScope;
+use crate::utils::time_format::{beijing_now, now_standard_string, to_standard_string};
+
+/// 会话有效期（小时）
+pub const SESSION_TTL_HOURS: i64 = 24 * 7;
+/// 使用全局 auth_token 访问时记录的操作者名称
+pub const LEGACY_TOKEN_ACTOR: &str = "auth_token";
+
+const PASSWORD_HASH_SCHEME: &str = "pbkdf2_sha256";
+const PASSWORD_HASH_ROUNDS: u32 = 100_000;
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// 用户角色，按权限从低到高排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// 只能浏览与播放
+    Viewer,
+    /// 可添加视频源、重置视频、控制任务
+    Operator,
+    /// 全部权限，包括配置、凭证与用户管理
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "viewer" => Some(Role::Viewer),
+            "operator" => Some(Role::Operator),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Viewer => "只读用户",
+            Role::Operator => "操作员",
+            Role::Admin => "管理员",
+        }
+    }
+}
+
+/// 认证中间件解析出的请求身份
+#[derive(Debug, Clone)]
+pub struct Identity {
+    /// 通过全局 auth_token 访问时为 None
+    pub user_id: Option<i32>,
+    pub username: String,
+    pub role: Role,
+    /// 通过 API 密钥访问时的权限范围，用户会话与全局 auth_token 为 None
+    pub scopes: Option<Vec<ApiScope>>,
+}
+
+impl Identity {
+    pub fn legacy_token() -> Self {
+        Self {
+            user_id: None,
+            username: LEGACY_TOKEN_ACTOR.to_string(),
+            role: Role::Admin,
+            scopes: None,
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_ACTOR: String;
+}
+
+/// 在请求处理期间记录当前操作者，供配置变更历史等写入使用
+pub async fn with_actor<F: Future>(actor: String, future: F) -> F::Output {
+    CURRENT_ACTOR.scope(actor, future).await
+}
+
+/// 当前请求的操作者；后台任务中调用时返回 None
+pub fn current_actor() -> Option<String> {
+    CURRENT_ACTOR.try_with(|actor| actor.clone()).ok()
+}
+
+pub fn validate_username(username: &str) -> Result<()> {
+    if username.is_empty() || username.len() > 64 {
+        bail!("用户名长度需在 1-64 个字符之间");
+    }
+    if username == LEGACY_TOKEN_ACTOR {
+        bail!("用户名「{}」为保留名称", LEGACY_TOKEN_ACTOR);
+    }
+    if !username
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+    {
+        bail!("用户名只能包含字母、数字、下划线、连字符和点");
+    }
+    Ok(())
+}
+
+pub fn validate_password(password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        bail!("密码长度不能少于 {} 个字符", MIN_PASSWORD_LEN);
+    }
+    Ok(())
+}
+
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// 生成 PBKDF2-HMAC-SHA256 密码哈希
+pub fn hash_password(password: &str) -> String {
+    hash_password_with(password, &random_hex(16), PASSWORD_HASH_ROUNDS)
+}
+
+fn hash_password_with(password: &str, salt: &str, rounds: u32) -> String {
+    let mut output = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), rounds, &mut output);
+    format!("{}${}${}${}", PASSWORD_HASH_SCHEME, rounds, salt, hex::encode(output))
+}
+
+pub fn verify_password(password: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(PASSWORD_HASH_SCHEME), Some(rounds), Some(salt), Some(_), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let Ok(rounds) = rounds.parse::<u32>() else {
+        return false;
+    };
+    constant_time_eq(hash_password_with(password, salt, rounds).as_bytes(), stored.as_bytes())
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 会话令牌为 64 位十六进制字符串，其他形式的令牌无需查询数据库
+fn looks_like_session_token(token: &str) -> bool {
+    token.len() == 64 && token.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+/// 校验用户名与密码，成功时创建会话并返回 (令牌, 过期时间, 用户)
+pub async fn login(
+    db: &DatabaseConnection,
+    username: &str,
+    password: &str,
+) -> Result<Option<(String, String, api_user::Model)>> {
+    let Some(user) = api_user::Entity::find()
+        .filter(api_user::Column::Username.eq(username))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if !user.enabled || !verify_password(password, &user.password_hash) {
+        return Ok(None);
+    }
+
+    let token = random_hex(32);
+    let expires_at = to_standard_string(beijing_now() + chrono::Duration::hours(SESSION_TTL_HOURS));
+    api_session::ActiveModel {
+        token_hash: Set(hash_token(&token)),
+        user_id: Set(user.id),
+        expires_at: Set(expires_at.clone()),
+        created_at: Set(now_standard_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(Some((token, expires_at, user)))
+}
+
+/// 根据会话令牌解析身份；令牌不存在、已过期或用户被禁用时返回 None
+pub async fn resolve_session(db: &DatabaseConnection, token: &str) -> Result<Option<Identity>> {
+    if !looks_like_session_token(token) {
+        return Ok(None);
+    }
+    let Some(session) = api_session::Entity::find()
+        .filter(api_session::Column::TokenHash.eq(hash_token(token)))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if session.expires_at <= now_standard_string() {
+        api_session::Entity::delete_by_id(session.id).exec(db).await?;
+        return Ok(None);
+    }
+    let Some(user) = api_user::Entity::find_by_id(session.user_id).one(db).await? else {
+        return Ok(None);
+    };
+    let Some(role) = Role::parse(&user.role).filter(|_| user.enabled) else {
+        return Ok(None);
+    };
+    Ok(Some(Identity {
+        user_id: Some(user.id),
+        username: user.username,
+        role,
+        scopes: None,
+    }))
+}
+
+pub async fn logout(db: &DatabaseConnection, token: &str) -> Result<()> {
+    api_session::Entity::delete_many()
+        .filter(api_session::Column::TokenHash.eq(hash_token(token)))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 删除用户的全部会话（修改密码、禁用或删除用户时调用）
+pub async fn revoke_user_sessions(db: &DatabaseConnection, user_id: i32) -> Result<()> {
+    api_session::Entity::delete_many()
+        .filter(api_session::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 记录一次写操作
+pub async fn record_audit(
+    db: &DatabaseConnection,
+    identity: &Identity,
+    method: &str,
+    path: &str,
+    status_code: u16,
+) -> Result<()> {
+    audit_log::ActiveModel {
+        actor: Set(identity.username.clone()),
+        role: Set(identity.role.as_str().to_string()),
+        method: Set(method.to_string()),
+        path: Set(path.to_string()),
+        status_code: Set(status_code as i32),
+        created_at: Set(now_standard_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_hash_round_trips_and_rejects_tampering() {
+        let stored = hash_password_with("correct horse", "salt", 1000);
+        assert!(verify_password("correct horse", &stored));
+        assert!(!verify_password("wrong horse", &stored));
+        assert!(!verify_password(
+            "correct horse",
+            &stored.replace("pbkdf2_sha256", "md5")
+        ));
+        assert!(!verify_password("correct horse", "garbage"));
+        assert_ne!(hash_password("same"), hash_password("same"));
+    }
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        assert!(Role::Admin > Role::Operator);
+        assert!(Role::Operator > Role::Viewer);
+        assert_eq!(Role::parse(Role::Operator.as_str()), Some(Role::Operator));
+        assert_eq!(Role::parse("root"), None);
+    }
+}
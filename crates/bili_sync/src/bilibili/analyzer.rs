@@ -133,6 +133,84 @@ pub struct FilterOption {
     pub no_dolby_audio: bool,
     pub no_hdr: bool,
     pub no_hires: bool,
+    /// 按顺序尝试的回退规则（如“优先 AV1 且 ≥1080P，否则 HEVC，否则 AVC”）；
+    /// 全部未命中时按上面的基础条件选择最佳流
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rules: Vec<QualityFallbackRule>,
+}
+
+/// 回退阶梯中的一条规则：在基础画质范围内，进一步限定编码与画质区间
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct QualityFallbackRule {
+    /// 本条规则接受的编码，按偏好排序
+    pub codecs: Vec<VideoCodecs>,
+    /// 本条规则要求的最低画质，缺省时沿用基础配置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_quality: Option<VideoQuality>,
+    /// 本条规则允许的最高画质，缺省时沿用基础配置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_quality: Option<VideoQuality>,
+}
+
+impl FilterOption {
+    /// 校验画质范围、编码列表与回退规则是否自洽
+    pub fn validate(&self) -> Result<()> {
+        if self.video_min_quality > self.video_max_quality {
+            bail!(
+                "视频最低画质 {:?} 不能高于最高画质 {:?}",
+                self.video_min_quality,
+                self.video_max_quality
+            );
+        }
+        if self.audio_min_quality > self.audio_max_quality {
+            bail!(
+                "音频最低音质 {:?} 不能高于最高音质 {:?}",
+                self.audio_min_quality,
+                self.audio_max_quality
+            );
+        }
+        if self.codecs.is_empty() {
+            bail!("编码偏好列表不能为空");
+        }
+        for (index, rule) in self.fallback_rules.iter().enumerate() {
+            let (min_quality, max_quality) = self.rule_quality_range(rule);
+            if rule.codecs.is_empty() {
+                bail!("第 {} 条回退规则的编码列表不能为空", index + 1);
+            }
+            if min_quality > max_quality {
+                bail!(
+                    "第 {} 条回退规则的画质范围 {:?} - {:?} 与基础画质范围没有交集",
+                    index + 1,
+                    min_quality,
+                    max_quality
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 规则画质区间与基础画质区间取交集
+    fn rule_quality_range(&self, rule: &QualityFallbackRule) -> (VideoQuality, VideoQuality) {
+        let min_quality = rule
+            .min_quality
+            .map_or(self.video_min_quality, |quality| quality.max(self.video_min_quality));
+        let max_quality = rule
+            .max_quality
+            .map_or(self.video_max_quality, |quality| quality.min(self.video_max_quality));
+        (min_quality, max_quality)
+    }
+
+    /// 将某条回退规则展开为不含回退规则的普通筛选条件
+    fn for_rule(&self, rule: &QualityFallbackRule) -> FilterOption {
+        let (video_min_quality, video_max_quality) = self.rule_quality_range(rule);
+        FilterOption {
+            video_max_quality,
+            video_min_quality,
+            codecs: rule.codecs.clone(),
+            fallback_rules: Vec::new(),
+            ..self.clone()
+        }
+    }
 }
 
 impl Default for FilterOption {
@@ -147,6 +225,7 @@ impl Default for FilterOption {
             no_dolby_audio: false,
             no_hdr: false,
             no_hires: false,
+            fallback_rules: Vec::new(),
         }
     }
 }
@@ -231,8 +310,26 @@ impl PageAnalyzer {
             && self.info["is_html5"].as_bool().is_none_or(|b| !b)
     }
 
+    /// 接口返回的全部 DASH 视频流（画质、编码），不做任何筛选，用于选流预览
+    pub fn available_video_streams(&self) -> Vec<(VideoQuality, VideoCodecs)> {
+        self.info
+            .pointer("/dash/video")
+            .and_then(|v| v.as_array())
+            .map(|videos| {
+                videos
+                    .iter()
+                    .filter_map(|video| {
+                        let quality = VideoQuality::from_repr(video["id"].as_u64()? as usize)?;
+                        let codecs = VideoCodecs::try_from(video["codecid"].as_u64()?).ok()?;
+                        Some((quality, codecs))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// 获取所有的视频、音频流，并根据条件筛选
-    fn streams(&mut self, filter_option: &FilterOption) -> Result<Vec<Stream>> {
+    fn streams(&mut self, filter_option: &FilterOption, report_empty: bool) -> Result<Vec<Stream>> {
         if self.is_flv_stream() {
             return Ok(vec![Stream::Flv(
                 self.info["durl"][0]["url"]
@@ -368,7 +465,7 @@ impl PageAnalyzer {
             filtered_count
         );
 
-        if video_stream_count == 0 {
+        if video_stream_count == 0 && report_empty {
             // 分析筛选失败的原因
             let max_quality_requested = filter_option.video_max_quality as u32;
             if max_quality_requested >= 120 {
@@ -549,7 +646,32 @@ impl PageAnalyzer {
     }
 
     pub fn best_stream(&mut self, filter_option: &FilterOption) -> Result<BestStream> {
-        let streams = self.streams(filter_option)?;
+        self.best_stream_with_rule(filter_option).map(|(stream, _)| stream)
+    }
+
+    /// 按回退规则依次选流，返回选中的流以及命中的规则下标（None 表示使用基础条件）
+    pub fn best_stream_with_rule(&mut self, filter_option: &FilterOption) -> Result<(BestStream, Option<usize>)> {
+        let is_dash = !(self.is_flv_stream() || self.is_html5_mp4_stream() || self.is_episode_try_mp4_stream());
+        if is_dash {
+            for (index, rule) in filter_option.fallback_rules.iter().enumerate() {
+                // 选流会取走 backup_url，规则试探在副本上进行，命中后再在原数据上正式选择
+                let rule_filter = filter_option.for_rule(rule);
+                let mut probe = PageAnalyzer::new(self.info.clone());
+                if probe.select_best_stream(&rule_filter, false).is_ok() {
+                    tracing::debug!("命中第 {} 条回退规则: {:?}", index + 1, rule);
+                    return self
+                        .select_best_stream(&rule_filter, true)
+                        .map(|stream| (stream, Some(index)));
+                }
+                tracing::debug!("第 {} 条回退规则未命中: {:?}", index + 1, rule);
+            }
+        }
+        self.select_best_stream(filter_option, true)
+            .map(|stream| (stream, None))
+    }
+
+    fn select_best_stream(&mut self, filter_option: &FilterOption, report_empty: bool) -> Result<BestStream> {
+        let streams = self.streams(filter_option, report_empty)?;
         if self.is_flv_stream() || self.is_html5_mp4_stream() || self.is_episode_try_mp4_stream() {
             // 按照 streams 中的假设，符合这三种情况的流只有一个，直接取
             return Ok(BestStream::Mixed(
@@ -561,7 +683,9 @@ impl PageAnalyzer {
 
         tracing::debug!("=== 最佳流选择 ===");
         if videos.is_empty() {
-            tracing::error!("错误: 没有可用的视频流！");
+            if report_empty {
+                tracing::error!("错误: 没有可用的视频流！");
+            }
             return Err(anyhow!("no video stream found"));
         }
 
@@ -706,6 +830,123 @@ mod tests {
         }
     }
 
+    fn ladder_test_analyzer() -> PageAnalyzer {
+        PageAnalyzer::new(json!({
+            "dash": {
+                "video": [
+                    {"id": 64, "codecid": 13, "base_url": "https://example.com/av1-720.m4s", "backup_url": []},
+                    {"id": 80, "codecid": 12, "base_url": "https://example.com/hevc-1080.m4s", "backup_url": ["https://backup.example.com/hevc-1080.m4s"]},
+                    {"id": 80, "codecid": 7, "base_url": "https://example.com/avc-1080.m4s", "backup_url": []}
+                ],
+                "audio": [
+                    {"id": 30280, "base_url": "https://example.com/audio.m4s", "backup_url": []}
+                ]
+            }
+        }))
+    }
+
+    #[test]
+    fn test_fallback_rules_pick_first_matching_rule() {
+        let filter_option = FilterOption {
+            fallback_rules: vec![
+                QualityFallbackRule {
+                    codecs: vec![VideoCodecs::AV1],
+                    min_quality: Some(VideoQuality::Quality1080p),
+                    max_quality: None,
+                },
+                QualityFallbackRule {
+                    codecs: vec![VideoCodecs::HEV],
+                    min_quality: None,
+                    max_quality: None,
+                },
+                QualityFallbackRule {
+                    codecs: vec![VideoCodecs::AVC],
+                    min_quality: None,
+                    max_quality: None,
+                },
+            ],
+            ..Default::default()
+        };
+        filter_option.validate().unwrap();
+
+        let (best, matched_rule) = ladder_test_analyzer().best_stream_with_rule(&filter_option).unwrap();
+        assert_eq!(matched_rule, Some(1));
+        match best {
+            BestStream::VideoAudio {
+                video:
+                    Stream::DashVideo {
+                        quality,
+                        codecs,
+                        backup_url,
+                        ..
+                    },
+                audio: Some(_),
+            } => {
+                assert_eq!(quality, VideoQuality::Quality1080p);
+                assert_eq!(codecs, VideoCodecs::HEV);
+                // 规则试探不应吃掉正式选流所需的备用地址
+                assert_eq!(backup_url, vec!["https://backup.example.com/hevc-1080.m4s".to_string()]);
+            }
+            other => panic!("unexpected best stream: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fallback_rules_fall_back_to_base_filter() {
+        let filter_option = FilterOption {
+            fallback_rules: vec![QualityFallbackRule {
+                codecs: vec![VideoCodecs::AV1],
+                min_quality: Some(VideoQuality::Quality4k),
+                max_quality: None,
+            }],
+            ..Default::default()
+        };
+        let (best, matched_rule) = ladder_test_analyzer().best_stream_with_rule(&filter_option).unwrap();
+        assert_eq!(matched_rule, None);
+        match best {
+            BestStream::VideoAudio {
+                video: Stream::DashVideo { quality, codecs, .. },
+                ..
+            } => {
+                assert_eq!(quality, VideoQuality::Quality1080p);
+                // 基础条件下同画质按 codecs 偏好（默认 AVC 优先）
+                assert_eq!(codecs, VideoCodecs::AVC);
+            }
+            other => panic!("unexpected best stream: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_filter_option_validate_rejects_inconsistent_ranges() {
+        let inverted = FilterOption {
+            video_max_quality: VideoQuality::Quality720p,
+            video_min_quality: VideoQuality::Quality1080p,
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+
+        let unreachable_rule = FilterOption {
+            video_max_quality: VideoQuality::Quality1080p,
+            fallback_rules: vec![QualityFallbackRule {
+                codecs: vec![VideoCodecs::HEV],
+                min_quality: Some(VideoQuality::Quality4k),
+                max_quality: None,
+            }],
+            ..Default::default()
+        };
+        assert!(unreachable_rule.validate().is_err());
+
+        let empty_codecs = FilterOption {
+            fallback_rules: vec![QualityFallbackRule {
+                codecs: Vec::new(),
+                min_quality: None,
+                max_quality: None,
+            }],
+            ..Default::default()
+        };
+        assert!(empty_codecs.validate().is_err());
+    }
+
     #[test]
     fn test_missing_dash_video_is_not_risk_control() {
         let mut analyzer = PageAnalyzer::new(json!({
@@ -260,6 +260,8 @@ pub struct BiliClient {
     limiter: Option<Arc<RateLimiter>>,
     /// 缓存的gaia_vtoken，用于绕过风控
     gaia_vtoken: Arc<ArcSwapOption<String>>,
+    /// 绑定的B站账号ID，`None` 表示使用默认账号
+    credential_id: Option<i32>,
 }
 
 impl BiliClient {
@@ -284,13 +286,39 @@ impl BiliClient {
             client,
             limiter,
             gaia_vtoken: Arc::new(ArcSwapOption::empty()),
+            credential_id: None,
         }
     }
 
+    /// 返回绑定到指定账号的客户端，共享速率限制与 gaia_vtoken 缓存
+    pub fn with_credential_id(&self, credential_id: Option<i32>) -> Self {
+        Self {
+            credential_id,
+            ..self.clone()
+        }
+    }
+
+    pub fn credential_id(&self) -> Option<i32> {
+        self.credential_id
+    }
+
+    /// 当前绑定账号的登录凭据
+    fn credential(&self) -> Option<Arc<Credential>> {
+        crate::config::reload_config().credential_for(self.credential_id)
+    }
+
+    /// 当前绑定账号的 WBI 签名 mixin key
+    pub(crate) fn mixin_key(&self) -> Option<Arc<String>> {
+        crate::bilibili::mixin_key_for(self.credential_id)
+    }
+
+    pub(crate) fn set_mixin_key(&self, key: String) {
+        crate::bilibili::set_mixin_key_for(self.credential_id, key);
+    }
+
     /// 获取当前用户ID的辅助函数
     fn get_current_user_id(&self) -> Result<i64, anyhow::Error> {
-        let config = crate::config::reload_config();
-        let credential = config.credential.load();
+        let credential = self.credential();
         match credential.as_ref() {
             Some(cred) => cred.dedeuserid.parse::<i64>().map_err(|_| anyhow!("无效的用户ID")),
             None => Err(anyhow!("未设置登录凭据")),
@@ -302,8 +330,7 @@ impl BiliClient {
         if let Some(limiter) = &self.limiter {
             limiter.acquire_one().await;
         }
-        let config = crate::config::reload_config();
-        let credential = config.credential.load();
+        let credential = self.credential();
         let gaia_vtoken = self.get_gaia_vtoken();
         self.client
             .request_with_gaia_vtoken(method, url, credential.as_deref(), gaia_vtoken.as_deref())
@@ -326,8 +353,7 @@ impl BiliClient {
                 _ = limiter.acquire_one() => {},
             }
         }
-        let config = crate::config::reload_config();
-        let credential = config.credential.load();
+        let credential = self.credential();
         let request_builder = self.client.request(Method::GET, url, credential.as_deref());
 
         let response = tokio::select! {
@@ -366,7 +392,7 @@ impl BiliClient {
 
     pub async fn refresh_credential(&self, force: bool) -> Result<bool> {
         let config = crate::config::reload_config();
-        let credential = config.credential.load();
+        let credential = self.credential();
         let Some(credential) = credential.as_deref() else {
             return Ok(false);
         };
@@ -393,19 +419,38 @@ impl BiliClient {
         }
 
         let new_credential = credential.refresh(&self.client, refresh_info.timestamp).await?;
-        config.credential.store(Some(Arc::new(new_credential.clone())));
-
-        self.persist_refreshed_credential(&config).await?;
-        info!("credential已刷新并保存到数据库");
+        let account_id = self.credential_id.filter(|id| config.bili_account(*id).is_some());
+        match account_id {
+            Some(id) => {
+                let mut accounts = config.bili_accounts.clone();
+                if let Some(account) = accounts.iter_mut().find(|account| account.id == id) {
+                    account.credential = new_credential;
+                }
+                let accounts_json = serde_json::to_value(&accounts).context("序列化刷新后的B站账号列表失败")?;
+                self.persist_refreshed_credential("bili_accounts", accounts_json)
+                    .await?;
+                info!("B站账号 {} 的credential已刷新并保存到数据库", id);
+            }
+            None => {
+                config.credential.store(Some(Arc::new(new_credential)));
+                let credential_json =
+                    serde_json::to_value(&config.credential).context("序列化刷新后的 credential 失败")?;
+                self.persist_refreshed_credential("credential", credential_json).await?;
+                info!("credential已刷新并保存到数据库");
+            }
+        }
+        crate::events::publish(
+            crate::events::EventKind::CredentialRefreshed,
+            serde_json::json!({ "account_id": account_id, "forced": force }),
+        );
 
         Ok(true)
     }
 
-    async fn persist_refreshed_credential(&self, config: &crate::config::Config) -> Result<()> {
+    async fn persist_refreshed_credential(&self, key: &str, value: serde_json::Value) -> Result<()> {
         let manager = crate::config::get_config_manager().context("配置管理器未初始化，无法保存刷新后的 credential")?;
-        let credential_json = serde_json::to_value(&config.credential).context("序列化刷新后的 credential 失败")?;
         manager
-            .update_config_item("credential", credential_json)
+            .update_config_item(key, value)
             .await
             .context("保存刷新后的 credential 到数据库失败")?;
         crate::config::reload_config_bundle()
@@ -416,8 +461,7 @@ impl BiliClient {
 
     /// 获取 wbi img，用于生成请求签名
     pub async fn wbi_img(&self) -> Result<WbiImg> {
-        let config = crate::config::reload_config();
-        let credential = config.credential.load();
+        let credential = self.credential();
         let mut res = self
             .client
             .request(
@@ -1430,8 +1474,6 @@ impl BiliClient {
 
     /// 获取csrf token (bili_jct)
     pub fn get_csrf_token(&self) -> Option<String> {
-        let config = crate::config::reload_config();
-        let credential = config.credential.load();
-        credential.as_ref().map(|cred| cred.bili_jct.clone())
+        self.credential().map(|cred| cred.bili_jct.clone())
     }
 }
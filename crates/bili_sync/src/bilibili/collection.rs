@@ -10,7 +10,7 @@ use serde_json::Value;
 use tracing::{debug, warn};
 
 use crate::bilibili::credential::encoded_query;
-use crate::bilibili::{BiliClient, Validate, VideoInfo, MIXIN_KEY};
+use crate::bilibili::{BiliClient, Validate, VideoInfo};
 
 const COLLECTION_PAGE_MAX_ATTEMPTS: usize = 3;
 const COLLECTION_PAGE_RETRY_DELAYS_SECONDS: [u64; COLLECTION_PAGE_MAX_ATTEMPTS - 1] = [2, 5];
@@ -163,7 +163,7 @@ impl<'a> Collection<'a> {
                         ("pn", page.as_str()),
                         ("ps", "30"),
                     ],
-                    MIXIN_KEY.load().as_deref(),
+                    self.client.mixin_key().as_deref(),
                 ),
             ),
             CollectionType::Season => (
@@ -175,7 +175,7 @@ impl<'a> Collection<'a> {
                         ("page_num", page.as_str()),
                         ("page_size", "30"),
                     ],
-                    MIXIN_KEY.load().as_deref(),
+                    self.client.mixin_key().as_deref(),
                 ),
             ),
         };
@@ -329,7 +329,7 @@ impl<'a> Collection<'a> {
                             ("pn", page_str.as_str()),
                             ("ps", "30"),
                         ],
-                        MIXIN_KEY.load().as_deref(),
+                        self.client.mixin_key().as_deref(),
                     ),
                 ),
                 CollectionType::Season => (
@@ -343,7 +343,7 @@ impl<'a> Collection<'a> {
                                 ("page_num", page_str.as_str()),
                                 ("page_size", "30"),
                             ],
-                            MIXIN_KEY.load().as_deref(),
+                            self.client.mixin_key().as_deref(),
                         ),
                         CollectionEpisodeOrderStrategy::SeasonHeadTailOldestFirst => encoded_query(
                             vec![
@@ -352,7 +352,7 @@ impl<'a> Collection<'a> {
                                 ("page_num", page_str.as_str()),
                                 ("page_size", "30"),
                             ],
-                            MIXIN_KEY.load().as_deref(),
+                            self.client.mixin_key().as_deref(),
                         ),
                     },
                 ),
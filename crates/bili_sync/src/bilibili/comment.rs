@@ -0,0 +1,416 @@
+//! 评论区归档
+//!
+//! 按热度拉取视频的置顶评论与前 N 条热门评论，并为每条评论补齐最多若干条回复，
+//! 结果可序列化为 JSON 或渲染为可离线浏览的 HTML 页面。
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::bilibili::{BiliClient, Validate};
+use crate::utils::time_format::timestamp_to_beijing_string;
+
+/// 评论与回复接口单页的最大条数
+const PAGE_SIZE: usize = 20;
+/// 评论区已关闭、UP 主精选评论等无法获取评论的错误码
+const COMMENTS_UNAVAILABLE_CODES: [i64; 2] = [12002, 12061];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommentArchiveOption {
+    /// 每个视频归档的热门评论数量（不含置顶评论）
+    pub top_n: usize,
+    /// 每条评论最多归档的回复数量，0 表示不归档回复
+    pub reply_count: usize,
+    /// 每轮扫描最多归档的视频数量，避免一次性请求过多评论接口
+    pub max_videos_per_run: usize,
+}
+
+impl Default for CommentArchiveOption {
+    fn default() -> Self {
+        Self {
+            top_n: 20,
+            reply_count: 10,
+            max_videos_per_run: 50,
+        }
+    }
+}
+
+impl CommentArchiveOption {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=200).contains(&self.top_n) {
+            bail!("评论归档数量需要在 1-200 之间");
+        }
+        if self.reply_count > 100 {
+            bail!("每条评论归档的回复数量不能超过 100");
+        }
+        if self.max_videos_per_run == 0 {
+            bail!("每轮归档的视频数量需要大于 0");
+        }
+        Ok(())
+    }
+}
+
+/// 一条评论，回复只保留一层（B站的楼中楼本身也只有一层）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub rpid: i64,
+    pub mid: i64,
+    pub uname: String,
+    pub message: String,
+    /// 发布时间（Unix 秒）
+    pub ctime: i64,
+    pub like: i64,
+    /// B站统计的回复总数，可能多于已归档的回复
+    pub reply_count: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<Comment>,
+}
+
+/// 一个视频的评论区快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentArchive {
+    pub bvid: String,
+    pub title: String,
+    pub archived_at: String,
+    /// B站统计的评论总数（含回复）
+    pub total: i64,
+    pub pinned: Vec<Comment>,
+    pub hot: Vec<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawReply {
+    rpid: i64,
+    #[serde(default)]
+    mid: i64,
+    #[serde(default)]
+    ctime: i64,
+    #[serde(default)]
+    like: i64,
+    #[serde(default)]
+    rcount: i64,
+    #[serde(default)]
+    member: RawMember,
+    #[serde(default)]
+    content: RawContent,
+    #[serde(default)]
+    replies: Option<Vec<RawReply>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawMember {
+    #[serde(default)]
+    uname: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawContent {
+    #[serde(default)]
+    message: String,
+}
+
+impl From<RawReply> for Comment {
+    fn from(raw: RawReply) -> Self {
+        Self {
+            rpid: raw.rpid,
+            mid: raw.mid,
+            uname: raw.member.uname,
+            message: raw.content.message,
+            ctime: raw.ctime,
+            like: raw.like,
+            reply_count: raw.rcount,
+            replies: raw.replies.unwrap_or_default().into_iter().map(Comment::from).collect(),
+        }
+    }
+}
+
+/// 评论列表接口单页的解析结果
+#[derive(Debug, Default)]
+struct MainPage {
+    pinned: Vec<Comment>,
+    hot: Vec<Comment>,
+    total: i64,
+    is_end: bool,
+}
+
+fn parse_replies(value: &mut Value) -> Vec<Comment> {
+    serde_json::from_value::<Option<Vec<RawReply>>>(value.take())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Comment::from)
+        .collect()
+}
+
+fn parse_main_page(mut data: Value) -> MainPage {
+    let hot = parse_replies(&mut data["replies"]);
+    MainPage {
+        pinned: parse_replies(&mut data["top_replies"]),
+        is_end: data["cursor"]["is_end"].as_bool().unwrap_or(hot.is_empty()),
+        total: data["cursor"]["all_count"].as_i64().unwrap_or_default(),
+        hot,
+    }
+}
+
+pub struct VideoComments<'a> {
+    client: &'a BiliClient,
+    aid: String,
+}
+
+impl<'a> VideoComments<'a> {
+    pub fn new(client: &'a BiliClient, aid: String) -> Self {
+        Self { client, aid }
+    }
+
+    /// 获取置顶评论与前 top_n 条热门评论，评论区关闭时返回空列表
+    pub async fn fetch(&self, option: &CommentArchiveOption) -> Result<(Vec<Comment>, Vec<Comment>, i64)> {
+        let mut pinned = Vec::new();
+        let mut hot = Vec::new();
+        let mut total = 0;
+        let mut next = 1;
+        while hot.len() < option.top_n {
+            let Some(data) = self
+                .get(
+                    "https://api.bilibili.com/x/v2/reply/main",
+                    &[("mode", "3"), ("next", &next.to_string())],
+                )
+                .await?
+            else {
+                break;
+            };
+            let page = parse_main_page(data);
+            if next == 1 {
+                pinned = page.pinned;
+                total = page.total;
+            }
+            let is_end = page.is_end || page.hot.is_empty();
+            hot.extend(page.hot);
+            if is_end {
+                break;
+            }
+            next += 1;
+        }
+
+        // 置顶评论可能同时出现在热门列表中
+        let pinned_ids: HashSet<i64> = pinned.iter().map(|comment| comment.rpid).collect();
+        hot.retain(|comment| !pinned_ids.contains(&comment.rpid));
+        hot.truncate(option.top_n);
+        for comment in pinned.iter_mut().chain(hot.iter_mut()) {
+            self.fill_replies(comment, option.reply_count).await?;
+        }
+        Ok((pinned, hot, total))
+    }
+
+    /// 列表接口只附带少量回复预览，不够时按时间顺序补齐
+    async fn fill_replies(&self, comment: &mut Comment, reply_count: usize) -> Result<()> {
+        let wanted = reply_count.min(comment.reply_count.max(0) as usize);
+        if comment.replies.len() >= wanted {
+            comment.replies.truncate(wanted);
+            return Ok(());
+        }
+        let root = comment.rpid.to_string();
+        let mut replies = Vec::with_capacity(wanted);
+        let mut pn = 1;
+        while replies.len() < wanted {
+            let Some(mut data) = self
+                .get(
+                    "https://api.bilibili.com/x/v2/reply/reply",
+                    &[("root", &root), ("pn", &pn.to_string())],
+                )
+                .await?
+            else {
+                break;
+            };
+            let page = parse_replies(&mut data["replies"]);
+            if page.is_empty() {
+                break;
+            }
+            replies.extend(page);
+            pn += 1;
+        }
+        replies.truncate(wanted);
+        comment.replies = replies;
+        Ok(())
+    }
+
+    async fn get(&self, url: &str, params: &[(&str, &str)]) -> Result<Option<Value>> {
+        let page_size = PAGE_SIZE.to_string();
+        let mut res = self
+            .client
+            .request(Method::GET, url)
+            .await
+            .query(&[("type", "1"), ("oid", self.aid.as_str()), ("ps", page_size.as_str())])
+            .query(params)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+        if res["code"]
+            .as_i64()
+            .is_some_and(|code| COMMENTS_UNAVAILABLE_CODES.contains(&code))
+        {
+            return Ok(None);
+        }
+        res = res.validate()?;
+        Ok(Some(res["data"].take()))
+    }
+}
+
+impl CommentArchive {
+    /// 归档的评论条数（含回复）
+    pub fn comment_count(&self) -> usize {
+        self.pinned
+            .iter()
+            .chain(&self.hot)
+            .map(|comment| 1 + comment.replies.len())
+            .sum()
+    }
+
+    /// 渲染为不依赖外部资源的 HTML 页面
+    pub fn render_html(&self) -> String {
+        let mut html = String::with_capacity(4096);
+        html.push_str("<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!(
+            "<title>{} - 评论区</title>\n",
+            html_escape::encode_text(&self.title)
+        ));
+        html.push_str(
+            "<style>body{font-family:sans-serif;max-width:860px;margin:24px auto;padding:0 16px;color:#18191c}\
+             .comment{border-bottom:1px solid #e3e5e7;padding:12px 0}.replies{margin-left:32px}\
+             .replies .comment{border:none;padding:6px 0}.meta{color:#9499a0;font-size:12px}\
+             .uname{font-weight:bold;margin-right:8px}.pinned{color:#fb7299;margin-right:8px}\
+             .message{white-space:pre-wrap;margin:4px 0}</style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!(
+            "<h1>{}</h1>\n<p class=\"meta\">{} · 共 {} 条评论 · 归档于 {}</p>\n",
+            html_escape::encode_text(&self.title),
+            html_escape::encode_text(&self.bvid),
+            self.total,
+            html_escape::encode_text(&self.archived_at)
+        ));
+        for comment in &self.pinned {
+            render_comment(&mut html, comment, true);
+        }
+        for comment in &self.hot {
+            render_comment(&mut html, comment, false);
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn render_comment(html: &mut String, comment: &Comment, pinned: bool) {
+    html.push_str("<div class=\"comment\">\n<div>");
+    if pinned {
+        html.push_str("<span class=\"pinned\">置顶</span>");
+    }
+    html.push_str(&format!(
+        "<span class=\"uname\">{}</span><span class=\"meta\">{} · {} 赞</span></div>\n<div class=\"message\">{}</div>\n",
+        html_escape::encode_text(&comment.uname),
+        timestamp_to_beijing_string(comment.ctime),
+        comment.like,
+        html_escape::encode_text(&comment.message)
+    ));
+    if !comment.replies.is_empty() {
+        html.push_str("<div class=\"replies\">\n");
+        for reply in &comment.replies {
+            render_comment(html, reply, false);
+        }
+        if comment.reply_count > comment.replies.len() as i64 {
+            html.push_str(&format!(
+                "<div class=\"meta\">共 {} 条回复，已归档 {} 条</div>\n",
+                comment.reply_count,
+                comment.replies.len()
+            ));
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn raw_reply(rpid: i64, message: &str, rcount: i64, replies: Value) -> Value {
+        json!({
+            "rpid": rpid,
+            "mid": 100 + rpid,
+            "ctime": 1_700_000_000,
+            "like": 5,
+            "rcount": rcount,
+            "member": {"uname": format!("用户{}", rpid)},
+            "content": {"message": message},
+            "replies": replies,
+        })
+    }
+
+    #[test]
+    fn parse_main_page_collects_pinned_and_hot() {
+        let page = parse_main_page(json!({
+            "cursor": {"is_end": false, "all_count": 321},
+            "top_replies": [raw_reply(1, "置顶", 0, Value::Null)],
+            "replies": [
+                raw_reply(2, "热门", 2, json!([raw_reply(20, "回复", 0, Value::Null)])),
+                raw_reply(3, "第二", 0, Value::Null),
+            ],
+        }));
+        assert_eq!(page.total, 321);
+        assert!(!page.is_end);
+        assert_eq!(page.pinned.len(), 1);
+        assert_eq!(page.pinned[0].uname, "用户1");
+        assert_eq!(page.hot.len(), 2);
+        assert_eq!(page.hot[0].replies[0].message, "回复");
+        assert_eq!(page.hot[0].reply_count, 2);
+
+        // 评论区为空时 replies 为 null
+        let empty = parse_main_page(json!({"replies": null, "top_replies": null}));
+        assert!(empty.is_end);
+        assert!(empty.pinned.is_empty() && empty.hot.is_empty());
+    }
+
+    #[test]
+    fn html_escapes_content_and_counts_comments() {
+        let archive = CommentArchive {
+            bvid: "BV1xx411c7mD".to_string(),
+            title: "<测试>".to_string(),
+            archived_at: "2026-10-19 12:00:00".to_string(),
+            total: 10,
+            pinned: vec![Comment::from(
+                serde_json::from_value::<RawReply>(raw_reply(1, "<script>alert(1)</script>", 3, Value::Null)).unwrap(),
+            )],
+            hot: vec![Comment::from(
+                serde_json::from_value::<RawReply>(raw_reply(
+                    2,
+                    "更正：第 3 分钟",
+                    1,
+                    json!([raw_reply(3, "感谢", 0, Value::Null)]),
+                ))
+                .unwrap(),
+            )],
+        };
+        assert_eq!(archive.comment_count(), 3);
+        let html = archive.render_html();
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;测试&gt; - 评论区"));
+        assert!(html.contains("置顶"));
+        assert!(html.contains("感谢"));
+    }
+
+    #[test]
+    fn option_validation() {
+        assert!(CommentArchiveOption::default().validate().is_ok());
+        let invalid = CommentArchiveOption {
+            top_n: 0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! 原始弹幕归档：在 ASS 旁保存B站兼容的 XML 与 JSON Lines，按 dmid 增量追加，
+//! 便于之后用不同的 `DanmakuOption` 重新渲染，或交给其它播放器使用
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::bilibili::danmaku::DanmakuElem;
+
+const ASS_SUFFIX: &str = ".zh-CN.default.ass";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DanmakuArchiveOption {
+    /// 保存B站兼容的 XML（`<分页名>.xml`，可被弹弹play 等播放器直接加载）
+    pub xml: bool,
+    /// 保存 JSON Lines（`<分页名>.danmaku.jsonl`），每行一条完整的原始弹幕
+    pub jsonl: bool,
+}
+
+impl DanmakuArchiveOption {
+    pub fn enabled(&self) -> bool {
+        self.xml || self.jsonl
+    }
+}
+
+/// 由 ASS 路径推导归档文件路径，返回 (xml, jsonl)
+pub fn archive_paths(danmaku_path: &Path) -> (PathBuf, PathBuf) {
+    let file_name = danmaku_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let base = file_name
+        .strip_suffix(ASS_SUFFIX)
+        .or_else(|| file_name.strip_suffix(".ass"))
+        .unwrap_or(&file_name);
+    (
+        danmaku_path.with_file_name(format!("{base}.xml")),
+        danmaku_path.with_file_name(format!("{base}.danmaku.jsonl")),
+    )
+}
+
+/// 把本次拉取的弹幕写入归档，已归档的 dmid 会被跳过；reset 为 true 时（如 cid 变化）丢弃旧归档。
+/// 返回新增的弹幕条数
+pub async fn archive_danmaku(
+    option: &DanmakuArchiveOption,
+    danmaku_path: &Path,
+    cid: i64,
+    elems: &[DanmakuElem],
+    reset: bool,
+) -> Result<usize> {
+    let (xml_path, jsonl_path) = archive_paths(danmaku_path);
+    let mut added = 0;
+    if option.jsonl {
+        added = added.max(append_jsonl(&jsonl_path, elems, reset).await?);
+    }
+    if option.xml {
+        added = added.max(merge_xml(&xml_path, cid, elems, reset).await?);
+    }
+    Ok(added)
+}
+
+/// 读取归档的原始弹幕，优先使用 JSON Lines，其次是 XML；没有任何归档时返回 None
+pub async fn load_archived_danmaku(danmaku_path: &Path) -> Result<Option<Vec<DanmakuElem>>> {
+    let (xml_path, jsonl_path) = archive_paths(danmaku_path);
+    if let Some(content) = read_optional(&jsonl_path).await? {
+        return Ok(Some(
+            content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .filter_map(|line| serde_json::from_str::<DanmakuElem>(line).ok())
+                .collect(),
+        ));
+    }
+    if let Some(content) = read_optional(&xml_path).await? {
+        return Ok(Some(content.lines().filter_map(parse_xml_line).collect()));
+    }
+    Ok(None)
+}
+
+/// 弹幕的去重键：优先使用 dmid，缺失时退化为出现位置 + 发送时间 + 发送者（不含逗号，可直接写入 XML 的 p 属性）
+fn archive_key(elem: &DanmakuElem) -> String {
+    if !elem.dmid_str.is_empty() {
+        elem.dmid_str.clone()
+    } else if elem.id > 0 {
+        elem.id.to_string()
+    } else {
+        format!("{}:{}:{}", elem.progress, elem.ctime, elem.mid_hash)
+    }
+}
+
+async fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read(path).await {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("读取弹幕归档 {} 失败", path.display())),
+    }
+}
+
+async fn append_jsonl(path: &Path, elems: &[DanmakuElem], reset: bool) -> Result<usize> {
+    let mut known = HashSet::new();
+    if !reset {
+        if let Some(content) = read_optional(path).await? {
+            known.extend(
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<DanmakuElem>(line).ok())
+                    .map(|elem| archive_key(&elem)),
+            );
+        }
+    }
+    let mut buffer = String::new();
+    let mut added = 0;
+    for elem in elems {
+        if known.insert(archive_key(elem)) {
+            buffer.push_str(&serde_json::to_string(elem)?);
+            buffer.push('\n');
+            added += 1;
+        }
+    }
+    if added == 0 && !reset {
+        return Ok(0);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!reset)
+        .truncate(reset)
+        .open(path)
+        .await
+        .with_context(|| format!("打开弹幕归档 {} 失败", path.display()))?;
+    file.write_all(buffer.as_bytes()).await?;
+    file.flush().await?;
+    Ok(added)
+}
+
+/// XML 需要闭合标签，无法直接追加：保留已有的 `<d>` 行，补上新弹幕后整体重写
+async fn merge_xml(path: &Path, cid: i64, elems: &[DanmakuElem], reset: bool) -> Result<usize> {
+    let mut lines = Vec::new();
+    let mut known = HashSet::new();
+    if !reset {
+        if let Some(content) = read_optional(path).await? {
+            for line in content.lines().map(str::trim).filter(|line| line.starts_with("<d ")) {
+                if let Some(key) = xml_line_key(line) {
+                    known.insert(key);
+                }
+                lines.push(line.to_string());
+            }
+        }
+    }
+    let mut added = 0;
+    for elem in elems {
+        if known.insert(archive_key(elem)) {
+            lines.push(xml_line(elem));
+            added += 1;
+        }
+    }
+    if added == 0 && !reset {
+        return Ok(0);
+    }
+    let mut document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<i>\n<chatserver>chat.bilibili.com</chatserver>\n<chatid>{cid}</chatid>\n<mission>0</mission>\n<maxlimit>{}</maxlimit>\n<state>0</state>\n<real_name>0</real_name>\n<source>k-v</source>\n",
+        lines.len()
+    );
+    for line in &lines {
+        document.push_str(line);
+        document.push('\n');
+    }
+    document.push_str("</i>\n");
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    fs::write(&tmp_path, document).await?;
+    fs::rename(&tmp_path, path)
+        .await
+        .with_context(|| format!("写入弹幕归档 {} 失败", path.display()))?;
+    Ok(added)
+}
+
+/// B站 XML 格式：`<d p="出现时间,类型,字号,颜色,发送时间,弹幕池,发送者mid_hash,dmid,权重">内容</d>`
+fn xml_line(elem: &DanmakuElem) -> String {
+    format!(
+        "<d p=\"{:.5},{},{},{},{},{},{},{},{}\">{}</d>",
+        elem.progress as f64 / 1000.0,
+        elem.mode,
+        elem.fontsize,
+        elem.color,
+        elem.ctime,
+        elem.pool,
+        escape_xml(&elem.mid_hash),
+        escape_xml(&archive_key(elem)),
+        elem.weight,
+        escape_xml(&elem.content)
+    )
+}
+
+fn parse_xml_line(line: &str) -> Option<DanmakuElem> {
+    let rest = line.trim().strip_prefix("<d p=\"")?;
+    let (attr, rest) = rest.split_once("\">")?;
+    let content = rest.strip_suffix("</d>")?;
+    let fields: Vec<&str> = attr.split(',').collect();
+    if fields.len() < 9 {
+        return None;
+    }
+    let dmid = unescape_xml(fields[7]);
+    Some(DanmakuElem {
+        id: dmid.parse().unwrap_or_default(),
+        progress: (fields[0].parse::<f64>().ok()? * 1000.0).round() as i32,
+        mode: fields[1].parse().ok()?,
+        fontsize: fields[2].parse().unwrap_or(25),
+        color: fields[3].parse().unwrap_or(0xFFFFFF),
+        ctime: fields[4].parse().unwrap_or_default(),
+        pool: fields[5].parse().unwrap_or_default(),
+        mid_hash: unescape_xml(fields[6]),
+        dmid_str: dmid,
+        weight: fields[8].parse().unwrap_or_default(),
+        content: unescape_xml(content),
+        ..Default::default()
+    })
+}
+
+fn xml_line_key(line: &str) -> Option<String> {
+    let attr = line.strip_prefix("<d p=\"")?;
+    let attr = &attr[..attr.find('"')?];
+    attr.split(',').nth(7).map(unescape_xml)
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 不允许的控制字符直接丢弃，换行统一转义，保证每条弹幕占一行
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push('\t'),
+            ch if (ch as u32) < 0x20 => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elem(dmid: &str, ctime: i64, content: &str) -> DanmakuElem {
+        DanmakuElem {
+            dmid_str: dmid.to_string(),
+            progress: 12_345,
+            mode: 1,
+            fontsize: 25,
+            color: 0xFFFFFF,
+            ctime,
+            content: content.to_string(),
+            mid_hash: "abc123".to_string(),
+            weight: 7,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn archive_paths_follow_page_name() {
+        let (xml, jsonl) = archive_paths(Path::new("/videos/up/P01.zh-CN.default.ass"));
+        assert_eq!(xml, PathBuf::from("/videos/up/P01.xml"));
+        assert_eq!(jsonl, PathBuf::from("/videos/up/P01.danmaku.jsonl"));
+    }
+
+    #[test]
+    fn xml_line_escapes_content_and_keeps_dmid() {
+        let line = xml_line(&elem("42", 1_700_000_000, "<a & \"b\">\n"));
+        assert_eq!(
+            line,
+            "<d p=\"12.34500,1,25,16777215,1700000000,0,abc123,42,7\">&lt;a &amp; &quot;b&quot;&gt;&#10;</d>"
+        );
+        assert_eq!(xml_line_key(&line).as_deref(), Some("42"));
+
+        let parsed = parse_xml_line(&line).unwrap();
+        assert_eq!(parsed.progress, 12_345);
+        assert_eq!(parsed.dmid_str, "42");
+        assert_eq!(parsed.mid_hash, "abc123");
+        assert_eq!(parsed.weight, 7);
+        assert_eq!(parsed.content, "<a & \"b\">\n");
+    }
+
+    #[tokio::test]
+    async fn archives_append_incrementally_by_dmid() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-danmaku-archive-{}", uuid::Uuid::new_v4()));
+        let danmaku_path = dir.join("P01.zh-CN.default.ass");
+        let option = DanmakuArchiveOption { xml: true, jsonl: true };
+
+        let first = vec![elem("1", 10, "第一条"), elem("2", 20, "第二条")];
+        assert_eq!(
+            archive_danmaku(&option, &danmaku_path, 100, &first, false)
+                .await
+                .unwrap(),
+            2
+        );
+        let second = vec![elem("2", 20, "第二条"), elem("3", 30, "第三条")];
+        assert_eq!(
+            archive_danmaku(&option, &danmaku_path, 100, &second, false)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            archive_danmaku(&option, &danmaku_path, 100, &second, false)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let (xml_path, jsonl_path) = archive_paths(&danmaku_path);
+        let jsonl = std::fs::read_to_string(&jsonl_path).unwrap();
+        let archived: Vec<DanmakuElem> = jsonl.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(
+            archived.iter().map(|elem| elem.content.as_str()).collect::<Vec<_>>(),
+            vec!["第一条", "第二条", "第三条"]
+        );
+        let xml = std::fs::read_to_string(&xml_path).unwrap();
+        assert_eq!(xml.matches("<d p=").count(), 3);
+        assert!(xml.contains("<maxlimit>3</maxlimit>"));
+        assert!(xml.trim_end().ends_with("</i>"));
+        let loaded = load_archived_danmaku(&danmaku_path).await.unwrap().unwrap();
+        assert_eq!(loaded.len(), 3);
+
+        // 只有 XML 归档时也能读回
+        std::fs::remove_file(&jsonl_path).unwrap();
+        let loaded = load_archived_danmaku(&danmaku_path).await.unwrap().unwrap();
+        assert_eq!(
+            loaded.iter().map(|elem| elem.content.as_str()).collect::<Vec<_>>(),
+            vec!["第一条", "第二条", "第三条"]
+        );
+
+        // cid 变化后重置归档
+        let reset = vec![elem("9", 90, "新分P")];
+        assert_eq!(
+            archive_danmaku(&option, &danmaku_path, 200, &reset, true)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(std::fs::read_to_string(&jsonl_path).unwrap().lines().count(), 1);
+        let xml = std::fs::read_to_string(&xml_path).unwrap();
+        assert_eq!(xml.matches("<d p=").count(), 1);
+        assert!(xml.contains("<chatid>200</chatid>"));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -25,10 +25,10 @@ impl fmt::Display for TimePoint {
     }
 }
 
-struct AssEffect {
-    effect: DrawEffect,
+struct AssEffect<'a> {
+    effect: &'a DrawEffect,
 }
-impl fmt::Display for AssEffect {
+impl fmt::Display for AssEffect<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.effect {
             DrawEffect::Move { start, end } => {
@@ -36,6 +36,39 @@ impl fmt::Display for AssEffect {
                 let (x1, y1) = end;
                 write!(f, "\\move({x0}, {y0}, {x1}, {y1})")
             }
+            DrawEffect::Positioned(effect) => {
+                let (x0, y0) = effect.start;
+                match effect.movement {
+                    Some(((x1, y1), t1, t2)) => write!(f, "\\move({x0}, {y0}, {x1}, {y1}, {t1}, {t2})")?,
+                    None => write!(f, "\\pos({x0}, {y0})")?,
+                }
+                write!(f, "\\fs{}", effect.font_size)?;
+                if let Some(font) = &effect.font {
+                    write!(f, "\\fn{font}")?;
+                }
+                if !effect.border {
+                    write!(f, "\\bord0")?;
+                }
+                // Flash 的旋转方向与 ASS 相反
+                if effect.rotate_z != 0.0 {
+                    write!(f, "\\frz{}", -effect.rotate_z)?;
+                }
+                if effect.rotate_y != 0.0 {
+                    write!(f, "\\fry{}", effect.rotate_y)?;
+                }
+                match effect.alpha {
+                    (from, to) if from == to => write!(f, "\\alpha&H{from:02X}&"),
+                    // 渐隐：从设定透明度淡出到完全透明
+                    (from, 255) => write!(f, "\\alpha&H{from:02X}&\\fad(0, {})", effect.lifetime_ms),
+                    // 渐显：从完全透明淡入到设定透明度
+                    (255, to) => write!(f, "\\alpha&H{to:02X}&\\fad({}, 0)", effect.lifetime_ms),
+                    (from, to) => write!(
+                        f,
+                        "\\alpha&H{from:02X}&\\t(0, {}, \\alpha&H{to:02X}&)",
+                        effect.lifetime_ms
+                    ),
+                }
+            }
         }
     }
 }
@@ -153,32 +186,7 @@ impl<W: AsyncWrite> AssWriter<W> {
     }
 
     pub async fn write(&mut self, drawable: Drawable) -> Result<()> {
-        self.f
-            .write_all(
-                format!(
-                    // Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
-                    "Dialogue: 2,{start},{end},{style},{name},0,0,0,,{{{effect}\\c&H{b:02x}{g:02x}{r:02x}&}}{text}\n",
-                    start = TimePoint {
-                        t: drawable.danmu.timeline_s
-                    },
-                    end = TimePoint {
-                        t: drawable.danmu.timeline_s + drawable.duration
-                    },
-                    style = drawable.style_name,
-                    name = build_event_name(&drawable.danmu),
-                    effect = AssEffect {
-                        effect: drawable.effect
-                    },
-                    b = drawable.danmu.rgb.2,
-                    g = drawable.danmu.rgb.1,
-                    r = drawable.danmu.rgb.0,
-                    text = escape_text(&drawable.danmu.content),
-                    // text = (0..drawable.danmu.content.chars().count()).map(|_| '晚').collect::<String>(),
-                )
-                .into_bytes()
-                .as_slice(),
-            )
-            .await?;
+        self.f.write_all(dialogue_line(&drawable).as_bytes()).await?;
         Ok(())
     }
 
@@ -187,6 +195,29 @@ impl<W: AsyncWrite> AssWriter<W> {
     }
 }
 
+fn dialogue_line(drawable: &Drawable) -> String {
+    format!(
+        // Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+        "Dialogue: 2,{start},{end},{style},{name},0,0,0,,{{{effect}\\c&H{b:02x}{g:02x}{r:02x}&}}{text}\n",
+        start = TimePoint {
+            t: drawable.danmu.timeline_s
+        },
+        end = TimePoint {
+            t: drawable.danmu.timeline_s + drawable.duration
+        },
+        style = drawable.style_name,
+        name = build_event_name(&drawable.danmu),
+        effect = AssEffect {
+            effect: &drawable.effect
+        },
+        b = drawable.danmu.rgb.2,
+        g = drawable.danmu.rgb.1,
+        r = drawable.danmu.rgb.0,
+        text = escape_text(&drawable.danmu.content),
+        // text = (0..drawable.danmu.content.chars().count()).map(|_| '晚').collect::<String>(),
+    )
+}
+
 fn build_event_name(danmu: &crate::bilibili::danmaku::Danmu) -> String {
     match (danmu.source_id.as_deref(), danmu.sent_at) {
         (Some(source_id), Some(sent_at)) if !source_id.is_empty() => {
@@ -265,4 +296,73 @@ mod tests {
         });
         assert_eq!(parse_event_name(&name), Some(("123456", 1710000000)));
     }
+
+    fn test_canvas() -> crate::bilibili::danmaku::canvas::Canvas {
+        crate::bilibili::danmaku::canvas::CanvasConfig {
+            width: 1280,
+            height: 720,
+            danmaku_option: Box::leak(Box::new(DanmakuOption::default())),
+            metrics: None,
+        }
+        .canvas()
+    }
+
+    fn danmu(mode: i32, content: &str) -> crate::bilibili::danmaku::Danmu {
+        crate::bilibili::danmaku::Danmu {
+            timeline_s: 1.0,
+            content: content.to_string(),
+            r#type: crate::bilibili::danmaku::danmu::DanmuType::from_num(mode).unwrap_or_default(),
+            fontsize: 25,
+            rgb: (255, 255, 255),
+            ..Default::default()
+        }
+    }
+
+    fn render(mode: i32, content: &str) -> Option<String> {
+        test_canvas()
+            .draw(danmu(mode, content))
+            .unwrap()
+            .map(|drawable| dialogue_line(&drawable))
+    }
+
+    #[test]
+    fn advanced_danmaku_renders_moving_fading_rotated_event() {
+        let line = render(
+            7,
+            r#"[0.5,0.25,"1-0","3","MAD/n字幕",30,0,"0.5","0.5",1000,500,0,"微软雅黑"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            line,
+            "Dialogue: 2,0:00:01.00,0:00:04.00,Float,,0,0,0,,\
+            {\\move(640, 180, 640, 360, 500, 1500)\\fs41\\fn微软雅黑\\bord0\\frz-30\\alpha&H00&\\fad(0, 3000)\\c&Hffffff&}\
+            MAD\\N字幕\n"
+        );
+    }
+
+    #[test]
+    fn advanced_danmaku_with_pixel_position_uses_pos() {
+        let line = render(7, r#"[100,50,"0.5-0.5",2,"静止"]"#).unwrap();
+        assert_eq!(
+            line,
+            "Dialogue: 2,0:00:01.00,0:00:03.00,Float,,0,0,0,,{\\pos(252, 82)\\fs41\\alpha&H80&\\c&Hffffff&}静止\n"
+        );
+    }
+
+    #[test]
+    fn code_and_malformed_advanced_danmaku_are_skipped() {
+        assert_eq!(render(8, "var a = 1;"), None);
+        assert_eq!(render(9, "def text t1 {}"), None);
+        assert_eq!(render(7, "not json"), None);
+        assert_eq!(render(7, r#"[0.1,0.1,"1-1",3]"#), None);
+    }
+
+    #[test]
+    fn reverse_danmaku_moves_left_to_right_in_its_own_lanes() {
+        let mut canvas = test_canvas();
+        let float = canvas.draw(danmu(1, "abc")).unwrap().unwrap();
+        let reverse = canvas.draw(danmu(6, "abc")).unwrap().unwrap();
+        assert!(dialogue_line(&float).contains("{\\move(1280, 0, -60, 0)"));
+        assert!(dialogue_line(&reverse).contains("{\\move(-60, 0, 1280, 0)"));
+    }
 }
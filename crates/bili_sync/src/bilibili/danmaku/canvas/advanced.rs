@@ -0,0 +1,140 @@
+//! 高级弹幕（mode 7）解析
+//!
+//! 弹幕内容是一个 JSON 数组：
+//! `[x, y, "起始透明度-结束透明度", 存活秒数, 文本, z轴旋转, y轴旋转, 终点x, 终点y, 移动耗时ms, 移动延迟ms, 是否描边, 字体, 线性加速]`，
+//! 坐标为小数（不大于 1）时表示相对播放器宽高的比例，否则为播放器上的像素值。
+use serde_json::Value;
+
+use crate::bilibili::danmaku::canvas::CanvasConfig;
+use crate::bilibili::danmaku::{Danmu, DrawEffect, Drawable};
+
+/// B站播放器的参考尺寸，像素坐标按此等比缩放到画布
+const PLAYER_SIZE: (f64, f64) = (672.0, 438.0);
+
+/// 高级弹幕的定位、移动、透明度与旋转
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedEffect {
+    pub start: (i32, i32),
+    /// 终点与移动的起止时间（ms），没有移动时为 None
+    pub movement: Option<((i32, i32), u32, u32)>,
+    /// 起始与结束的 ASS 透明度（0 为不透明，255 为完全透明）
+    pub alpha: (u8, u8),
+    /// 存活时间（ms），用于透明度渐变
+    pub lifetime_ms: u32,
+    pub rotate_z: f64,
+    pub rotate_y: f64,
+    pub font_size: u32,
+    pub font: Option<String>,
+    pub border: bool,
+}
+
+/// 解析高级弹幕，格式不正确时返回 None
+pub fn draw_advanced(mut danmu: Danmu, config: &CanvasConfig) -> Option<Drawable> {
+    let args: Vec<Value> = serde_json::from_str(danmu.content.trim()).ok()?;
+    let text = args.get(4).and_then(as_text)?;
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    let scale = (config.width as f64 / PLAYER_SIZE.0).min(config.height as f64 / PLAYER_SIZE.1);
+    let offset = (
+        (config.width as f64 - PLAYER_SIZE.0 * scale) / 2.0,
+        (config.height as f64 - PLAYER_SIZE.1 * scale) / 2.0,
+    );
+    let position = |value: Option<&Value>, vertical: bool| -> Option<i32> {
+        let raw = value.map_or(Some(0.0), as_number)?;
+        let player = if vertical { PLAYER_SIZE.1 } else { PLAYER_SIZE.0 };
+        let offset = if vertical { offset.1 } else { offset.0 };
+        let is_ratio = raw <= 1.0 && value.is_some_and(is_fractional);
+        let pixels = if is_ratio { raw * player } else { raw };
+        Some((pixels * scale + offset).round() as i32)
+    };
+
+    let start = (position(args.first(), false)?, position(args.get(1), true)?);
+    let (from_alpha, to_alpha) = args.get(2).and_then(as_text).map_or((1.0, 1.0), |value| {
+        let mut parts = value.split('-').map(|part| part.trim().parse::<f64>().unwrap_or(1.0));
+        let from = parts.next().unwrap_or(1.0);
+        (from, parts.next().unwrap_or(from))
+    });
+    let lifetime_s = args
+        .get(3)
+        .and_then(as_number)
+        .filter(|value| *value > 0.0)
+        .unwrap_or(4.5);
+    let lifetime_ms = (lifetime_s * 1000.0).round() as u32;
+
+    let movement = if args.len() >= 9 {
+        let end = (position(args.get(7), false)?, position(args.get(8), true)?);
+        let duration_ms = args
+            .get(9)
+            .and_then(as_number)
+            .map_or(lifetime_ms, |v| v.max(0.0) as u32);
+        let delay_ms = args.get(10).and_then(as_number).map_or(0, |v| v.max(0.0) as u32);
+        (end != start).then_some((end, delay_ms, delay_ms.saturating_add(duration_ms)))
+    } else {
+        None
+    };
+
+    let effect = PositionedEffect {
+        start,
+        movement,
+        alpha: (ass_alpha(from_alpha), ass_alpha(to_alpha)),
+        lifetime_ms,
+        rotate_z: args.get(5).and_then(as_number).unwrap_or(0.0),
+        rotate_y: args.get(6).and_then(as_number).unwrap_or(0.0),
+        font_size: ((danmu.fontsize.max(1) as f64) * scale).round() as u32,
+        font: args
+            .get(12)
+            .and_then(as_text)
+            .map(|font| font.trim().to_string())
+            .filter(|font| !font.is_empty()),
+        border: args.get(11).is_none_or(as_bool),
+    };
+    // B站用 "/n" 表示换行
+    danmu.content = text.replace("/n", "\n");
+    Some(Drawable::new(
+        danmu,
+        lifetime_s,
+        "Float",
+        DrawEffect::Positioned(Box::new(effect)),
+    ))
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|value: &f64| value.is_finite())
+}
+
+fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn as_bool(value: &Value) -> bool {
+    match value {
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64() != Some(0.0),
+        Value::String(text) => !matches!(text.trim(), "false" | "0"),
+        _ => true,
+    }
+}
+
+/// 坐标写成小数（如 0.5、"0.5"）才按比例处理，整数 1 表示 1 像素
+fn is_fractional(value: &Value) -> bool {
+    match value {
+        Value::Number(number) => number.is_f64(),
+        Value::String(text) => text.contains('.'),
+        _ => false,
+    }
+}
+
+fn ass_alpha(opacity: f64) -> u8 {
+    ((1.0 - opacity.clamp(0.0, 1.0)) * 255.0).round() as u8
+}
@@ -0,0 +1,158 @@
+//! 弹幕宽度测量
+//!
+//! 配置了字体文件时按字形的实际前进宽度计算弹幕长度，字体中缺失的字符与未配置字体时
+//! 沿用原先的估算（ASCII 算 2/3 宽，其余算一个全宽）。
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+/// 合成粗体时每个字形增加的前进宽度（em），与 FreeType 的 FT_GlyphSlot_Embolden 一致
+const SYNTHETIC_BOLD_ADVANCE: f64 = 1.0 / 24.0;
+
+/// 已加载的字体度量，按字体路径缓存并记录加载时文件的修改时间。
+/// 加载失败的结果同样缓存，避免每个分页都重复读取和告警；文件被替换或修复后修改时间变化，会重新加载
+static FONT_METRICS: Lazy<Mutex<HashMap<String, CachedMetrics>>> = Lazy::new(Default::default);
+
+struct CachedMetrics {
+    modified: Option<SystemTime>,
+    metrics: Option<Arc<FontMetrics>>,
+}
+
+/// 字体中每个字符的前进宽度，以 em 为单位（字号为 1 时的像素宽度）
+#[derive(Debug, Default)]
+pub struct FontMetrics {
+    advances: HashMap<char, f64>,
+    /// 字体本身是否为粗体，非粗体字体在加粗显示时由渲染器合成粗体，字形会变宽
+    bold: bool,
+}
+
+impl FontMetrics {
+    /// 解析字体文件（TTF/OTF，TTC 取第一个字体）
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let face = ttf_parser::Face::parse(data, 0).map_err(|e| anyhow!("无法解析字体: {}", e))?;
+        let units_per_em = face.units_per_em() as f64;
+        let cmap = face.tables().cmap.context("字体缺少 cmap 表")?;
+        let mut advances = HashMap::new();
+        for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+            subtable.codepoints(|codepoint| {
+                let Some(ch) = char::from_u32(codepoint) else {
+                    return;
+                };
+                if advances.contains_key(&ch) {
+                    return;
+                }
+                if let Some(advance) = subtable
+                    .glyph_index(codepoint)
+                    .and_then(|glyph| face.glyph_hor_advance(glyph))
+                {
+                    advances.insert(ch, advance as f64 / units_per_em);
+                }
+            });
+        }
+        if advances.is_empty() {
+            return Err(anyhow!("字体中没有可用的 Unicode 字形"));
+        }
+        let bold = face.is_bold() || face.weight().to_number() >= 700;
+        Ok(Self { advances, bold })
+    }
+
+    /// 读取并缓存字体文件，文件修改时间不变时复用上次的结果，失败时记录警告并返回 None
+    pub fn load(path: &str) -> Option<Arc<Self>> {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut cache = FONT_METRICS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(path).filter(|cached| cached.modified == modified) {
+            return cached.metrics.clone();
+        }
+        let metrics = match std::fs::read(path)
+            .with_context(|| format!("读取字体文件 {} 失败", path))
+            .and_then(|data| Self::parse(&data))
+        {
+            Ok(metrics) => Some(Arc::new(metrics)),
+            Err(e) => {
+                warn!("弹幕字体度量加载失败，回退到估算宽度: {:#}", e);
+                None
+            }
+        };
+        cache.insert(
+            path.to_string(),
+            CachedMetrics {
+                modified,
+                metrics: metrics.clone(),
+            },
+        );
+        metrics
+    }
+
+    /// 文本在字号为 1 时的宽度，字体中缺失的字符按估算宽度计算
+    ///
+    /// `bold` 为弹幕是否加粗显示，字体本身不是粗体时按合成粗体加宽每个字形
+    pub fn measure(&self, text: &str, bold: bool) -> f64 {
+        let embolden = if bold && !self.bold {
+            SYNTHETIC_BOLD_ADVANCE
+        } else {
+            0.0
+        };
+        text.chars()
+            .map(|ch| match self.advances.get(&ch) {
+                Some(advance) => advance + embolden,
+                None => estimate_units(ch) as f64 / 3.0,
+            })
+            .sum()
+    }
+}
+
+/// 估算宽度，单位为 1/3 em：ASCII 算 2/3 宽，其余算一个全宽
+pub fn estimate_units(ch: char) -> u32 {
+    if ch.is_ascii() {
+        2
+    } else {
+        3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_falls_back_for_missing_glyphs() {
+        let metrics = FontMetrics {
+            advances: HashMap::from([('a', 0.5), ('한', 0.9), ('！', 1.0)]),
+            bold: false,
+        };
+        assert!((metrics.measure("a한！", false) - 2.4).abs() < 1e-9);
+        // 字体中没有的字符按估算宽度：ASCII 2/3，其余 1
+        assert!((metrics.measure("b中", false) - (2.0 / 3.0 + 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn synthetic_bold_widens_regular_fonts_only() {
+        let mut metrics = FontMetrics {
+            advances: HashMap::from([('a', 0.5), ('한', 0.9)]),
+            bold: false,
+        };
+        let expected = 1.4 + 2.0 * SYNTHETIC_BOLD_ADVANCE;
+        assert!((metrics.measure("a한", true) - expected).abs() < 1e-9);
+        metrics.bold = true;
+        assert!((metrics.measure("a한", true) - 1.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn failed_load_is_retried_after_the_file_changes() {
+        assert!(FontMetrics::parse(b"not a font").is_err());
+        let path = std::env::temp_dir().join(format!("bili-sync-danmaku-font-{}.ttf", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        assert!(FontMetrics::load(path).is_none());
+        assert!(FONT_METRICS.lock().unwrap().contains_key(path));
+
+        std::fs::write(path, b"not a font").unwrap();
+        assert!(FontMetrics::load(path).is_none());
+        let cached = FONT_METRICS.lock().unwrap().get(path).unwrap().modified;
+        assert!(cached.is_some());
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -1,10 +1,17 @@
 //! 决定绘画策略
+mod advanced;
 mod lane;
+mod metrics;
+
+use std::sync::Arc;
 
 use anyhow::Result;
 use float_ord::FloatOrd;
 use lane::Lane;
 
+pub use self::advanced::PositionedEffect;
+pub use self::metrics::{estimate_units, FontMetrics};
+
 use crate::bilibili::danmaku::canvas::lane::Collision;
 use crate::bilibili::danmaku::danmu::DanmuType;
 use crate::bilibili::danmaku::{Danmu, DrawEffect, Drawable};
@@ -32,6 +39,9 @@ pub struct DanmakuOption {
     pub outline: f64,
     /// 时间轴偏移
     pub time_offset: f64,
+    /// 用于测量弹幕宽度的字体文件（TTF/OTF/TTC），未设置时按字符类别估算宽度
+    #[serde(default)]
+    pub font_path: Option<String>,
 }
 
 impl Default for DanmakuOption {
@@ -49,6 +59,7 @@ impl Default for DanmakuOption {
             bold: true,
             outline: 0.8,
             time_offset: 0.0,
+            font_path: None,
         }
     }
 }
@@ -58,14 +69,23 @@ pub struct CanvasConfig {
     pub width: u64,
     pub height: u64,
     pub danmaku_option: &'static DanmakuOption,
+    /// 配置字体的字形宽度，用于计算弹幕长度
+    pub metrics: Option<Arc<FontMetrics>>,
 }
 impl CanvasConfig {
     pub fn new(danmaku_option: &'static DanmakuOption, page: &PageInfo) -> Self {
         let (width, height) = Self::dimension(page);
+        let metrics = danmaku_option
+            .font_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .and_then(FontMetrics::load);
         Self {
             width,
             height,
             danmaku_option,
+            metrics,
         }
     }
 
@@ -93,6 +113,7 @@ impl CanvasConfig {
         Canvas {
             config: self,
             float_lanes: vec![None; float_lanes_cnt],
+            reverse_lanes: vec![None; float_lanes_cnt],
         }
     }
 }
@@ -100,6 +121,8 @@ impl CanvasConfig {
 pub struct Canvas {
     pub config: CanvasConfig,
     pub float_lanes: Vec<Option<Lane>>,
+    /// 逆向弹幕从左向右移动，单独占用一组槽位
+    pub reverse_lanes: Vec<Option<Lane>>,
 }
 
 impl Canvas {
@@ -109,29 +132,38 @@ impl Canvas {
             return Ok(None);
         }
         match danmu.r#type {
-            DanmuType::Float => Ok(self.draw_float(danmu)),
-            DanmuType::Bottom | DanmuType::Top | DanmuType::Reverse => {
+            DanmuType::Float => Ok(self.draw_float(danmu, false)),
+            DanmuType::Reverse => Ok(self.draw_float(danmu, true)),
+            DanmuType::Bottom | DanmuType::Top => {
                 // 不喜欢底部弹幕，直接转成 Bottom
                 // 这是 feature 不是 bug
                 danmu.r#type = DanmuType::Float;
-                Ok(self.draw_float(danmu))
+                Ok(self.draw_float(danmu, false))
             }
+            DanmuType::Advanced => Ok(advanced::draw_advanced(danmu, &self.config)),
+            // 代码弹幕依赖播放器执行脚本，直接跳过
+            DanmuType::Code => Ok(None),
         }
     }
 
-    fn draw_float(&mut self, mut danmu: Danmu) -> Option<Drawable> {
-        let mut collisions = Vec::with_capacity(self.float_lanes.len());
-        for (idx, lane) in self.float_lanes.iter_mut().enumerate() {
+    fn draw_float(&mut self, mut danmu: Danmu, reverse: bool) -> Option<Drawable> {
+        let lanes = if reverse {
+            &self.reverse_lanes
+        } else {
+            &self.float_lanes
+        };
+        let mut collisions = Vec::with_capacity(lanes.len());
+        for (idx, lane) in lanes.iter().enumerate() {
             match lane {
                 // 优先画不存在的槽位
                 None => {
-                    return Some(self.draw_float_in_lane(danmu, idx));
+                    return Some(self.draw_float_in_lane(danmu, idx, reverse));
                 }
                 Some(l) => {
                     let col = l.available_for(&danmu, &self.config);
                     match col {
                         Collision::Separate | Collision::NotEnoughTime => {
-                            return Some(self.draw_float_in_lane(danmu, idx));
+                            return Some(self.draw_float_in_lane(danmu, idx, reverse));
                         }
                         Collision::Collide { time_needed } => {
                             collisions.push((FloatOrd(time_needed), idx));
@@ -148,25 +180,32 @@ impl Canvas {
                 // debug!("延迟弹幕 {} 秒", time_need);
                 // 只允许延迟 1s
                 danmu.timeline_s += time_need + 0.01; // 间隔也不要太小了
-                return Some(self.draw_float_in_lane(danmu, lane_idx));
+                return Some(self.draw_float_in_lane(danmu, lane_idx, reverse));
             }
         }
         // debug!("skipping danmu: {}", danmu.content);
         None
     }
 
-    fn draw_float_in_lane(&mut self, danmu: Danmu, lane_idx: usize) -> Drawable {
-        self.float_lanes[lane_idx] = Some(Lane::draw(&danmu, &self.config));
+    fn draw_float_in_lane(&mut self, danmu: Danmu, lane_idx: usize, reverse: bool) -> Drawable {
+        let lane = Some(Lane::draw(&danmu, &self.config));
+        if reverse {
+            self.reverse_lanes[lane_idx] = lane;
+        } else {
+            self.float_lanes[lane_idx] = lane;
+        }
         let y = lane_idx as i32 * self.config.danmaku_option.lane_size as i32;
         let l = danmu.length(&self.config);
+        let (start, end) = if reverse {
+            ((-(l as i32), y), (self.config.width as i32, y))
+        } else {
+            ((self.config.width as i32, y), (-(l as i32), y))
+        };
         Drawable::new(
             danmu,
             self.config.danmaku_option.duration,
             "Float",
-            DrawEffect::Move {
-                start: (self.config.width as i32, y),
-                end: (-(l as i32), y),
-            },
+            DrawEffect::Move { start, end },
         )
     }
 }
@@ -1,7 +1,7 @@
 //! 一个弹幕实例，但是没有位置信息
 use anyhow::{bail, Result};
 
-use crate::bilibili::danmaku::canvas::CanvasConfig;
+use crate::bilibili::danmaku::canvas::{estimate_units, CanvasConfig};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DanmuType {
@@ -10,6 +10,10 @@ pub enum DanmuType {
     Top,
     Bottom,
     Reverse,
+    /// 高级弹幕（mode 7），内容为描述位置、移动与旋转的 JSON
+    Advanced,
+    /// 代码弹幕（mode 8）与 BAS 弹幕（mode 9），依赖播放器脚本，无法转换为 ASS
+    Code,
 }
 
 impl DanmuType {
@@ -19,7 +23,9 @@ impl DanmuType {
             4 => DanmuType::Bottom,
             5 => DanmuType::Top,
             6 => DanmuType::Reverse,
-            // 高级弹幕、代码弹幕等，不支持，这里 return error，外面 unwrap_or_default 当成 Float 处理
+            7 => DanmuType::Advanced,
+            8 | 9 => DanmuType::Code,
+            // 其它未知类型，这里 return error，外面 unwrap_or_default 当成 Float 处理
             _ => bail!("UnSupported danmu type"),
         })
     }
@@ -36,21 +42,23 @@ pub struct Danmu {
     pub rgb: (u8, u8, u8),
     pub sent_at: Option<i64>,
     pub source_id: Option<String>,
+    /// 发送者 mid 的哈希，用于按发送者屏蔽
+    pub mid_hash: String,
+    /// B站智能屏蔽使用的权重（0-10）
+    pub weight: i32,
 }
 
 impl Danmu {
     /// 计算弹幕的“像素长度”，会乘上一个缩放因子
     ///
-    /// 汉字算一个全宽，英文算2/3宽
+    /// 配置了字体时按字形的实际宽度计算，否则汉字算一个全宽，英文算2/3宽
     pub fn length(&self, config: &CanvasConfig) -> f64 {
-        let pts = config.danmaku_option.font_size
-            * self
-                .content
-                .chars()
-                .map(|ch| if ch.is_ascii() { 2 } else { 3 })
-                .sum::<u32>()
-            / 3;
+        let font_size = config.danmaku_option.font_size;
+        let pts = match &config.metrics {
+            Some(metrics) => font_size as f64 * metrics.measure(&self.content, config.danmaku_option.bold),
+            None => (font_size * self.content.chars().map(estimate_units).sum::<u32>() / 3) as f64,
+        };
 
-        pts as f64 * config.danmaku_option.width_ratio
+        pts * config.danmaku_option.width_ratio
     }
 }
@@ -1,5 +1,6 @@
 //! 可以绘制的实体
 
+use crate::bilibili::danmaku::canvas::PositionedEffect;
 use crate::bilibili::danmaku::Danmu;
 
 /// 弹幕开始绘制的时间就是 danmu 的时间
@@ -24,5 +25,10 @@ impl Drawable {
 }
 
 pub enum DrawEffect {
-    Move { start: (i32, i32), end: (i32, i32) },
+    Move {
+        start: (i32, i32),
+        end: (i32, i32),
+    },
+    /// 高级弹幕：定位/移动、透明度渐变与旋转
+    Positioned(Box<PositionedEffect>),
 }
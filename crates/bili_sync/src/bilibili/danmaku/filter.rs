@@ -0,0 +1,511 @@
+//! 绘制前的弹幕内容过滤：关键词/正则/发送者屏蔽、智能屏蔽等级、相似弹幕合并与密度上限
+use std::collections::VecDeque;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::bilibili::danmaku::danmu::DanmuType;
+use crate::bilibili::danmaku::Danmu;
+
+/// 支持单独设置弹幕过滤规则的视频源类型
+const SOURCE_TYPES: [&str; 6] = [
+    "favorite",
+    "collection",
+    "submission",
+    "watch_later",
+    "bangumi",
+    "ranking",
+];
+
+/// 一组弹幕过滤规则，所有字段取默认值时不过滤任何弹幕
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DanmakuFilterRules {
+    /// 关键词屏蔽，弹幕内容包含任一关键词即丢弃（英文不区分大小写）
+    pub keyword_blocklist: Vec<String>,
+    /// 正则屏蔽，弹幕内容匹配任一正则即丢弃
+    pub regex_blocklist: Vec<String>,
+    /// 按发送者屏蔽，填写弹幕中的 mid_hash
+    pub mid_hash_blocklist: Vec<String>,
+    /// 智能屏蔽等级（0-10），丢弃 weight 低于该值的弹幕，0 表示不启用
+    pub min_weight: i32,
+    /// 合并时间窗口（秒），窗口内相同或相近的弹幕只保留第一条并追加「×N」，0 表示不合并
+    pub merge_window_s: f64,
+    /// 合并时的相似度阈值（0-1），1 表示仅合并规范化后完全相同的弹幕
+    pub merge_similarity: f64,
+    /// 每 density_window_s 秒内最多保留的弹幕数，0 表示不限制
+    pub max_per_window: u32,
+    /// 密度统计窗口（秒）
+    pub density_window_s: f64,
+}
+
+impl Default for DanmakuFilterRules {
+    fn default() -> Self {
+        Self {
+            keyword_blocklist: Vec::new(),
+            regex_blocklist: Vec::new(),
+            mid_hash_blocklist: Vec::new(),
+            min_weight: 0,
+            merge_window_s: 0.0,
+            merge_similarity: 1.0,
+            max_per_window: 0,
+            density_window_s: 1.0,
+        }
+    }
+}
+
+/// 单个视频源的弹幕过滤规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DanmakuFilterOverride {
+    /// favorite / collection / submission / watch_later / bangumi / ranking
+    pub source_type: String,
+    pub source_id: i32,
+    /// 是否同时沿用全局的关键词、正则与发送者屏蔽列表
+    #[serde(default = "default_inherit_blocklists")]
+    pub inherit_blocklists: bool,
+    #[serde(flatten)]
+    pub rules: DanmakuFilterRules,
+}
+
+fn default_inherit_blocklists() -> bool {
+    true
+}
+
+/// 弹幕过滤配置：全局规则加上按视频源覆盖的规则
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DanmakuFilterOption {
+    #[serde(flatten)]
+    pub rules: DanmakuFilterRules,
+    #[serde(default)]
+    pub source_overrides: Vec<DanmakuFilterOverride>,
+}
+
+impl DanmakuFilterOption {
+    /// 取视频源实际生效的规则，没有单独设置时使用全局规则
+    pub fn rules_for(&self, source_type: Option<&str>, source_id: Option<i32>) -> DanmakuFilterRules {
+        let matched = self
+            .source_overrides
+            .iter()
+            .find(|item| Some(item.source_type.as_str()) == source_type && Some(item.source_id) == source_id);
+        let Some(item) = matched else {
+            return self.rules.clone();
+        };
+        let mut rules = item.rules.clone();
+        if item.inherit_blocklists {
+            let extend = |target: &mut Vec<String>, global: &[String]| {
+                for value in global {
+                    if !target.contains(value) {
+                        target.push(value.clone());
+                    }
+                }
+            };
+            extend(&mut rules.keyword_blocklist, &self.rules.keyword_blocklist);
+            extend(&mut rules.regex_blocklist, &self.rules.regex_blocklist);
+            extend(&mut rules.mid_hash_blocklist, &self.rules.mid_hash_blocklist);
+        }
+        rules
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.rules.validate().context("全局规则无效")?;
+        let mut seen = std::collections::HashSet::new();
+        for item in &self.source_overrides {
+            if !SOURCE_TYPES.contains(&item.source_type.as_str()) {
+                bail!("未知的视频源类型「{}」", item.source_type);
+            }
+            if !seen.insert((item.source_type.as_str(), item.source_id)) {
+                bail!("视频源 {}:{} 的弹幕过滤规则重复", item.source_type, item.source_id);
+            }
+            item.rules
+                .validate()
+                .with_context(|| format!("视频源 {}:{} 的规则无效", item.source_type, item.source_id))?;
+        }
+        Ok(())
+    }
+}
+
+impl DanmakuFilterRules {
+    pub fn validate(&self) -> Result<()> {
+        for pattern in &self.regex_blocklist {
+            Regex::new(pattern).with_context(|| format!("正则「{}」无效", pattern))?;
+        }
+        if !(0..=10).contains(&self.min_weight) {
+            bail!("min_weight 必须在 0 到 10 之间");
+        }
+        if self.merge_window_s < 0.0 {
+            bail!("merge_window_s 不能为负数");
+        }
+        if self.merge_window_s > 0.0 && !(self.merge_similarity > 0.0 && self.merge_similarity <= 1.0) {
+            bail!("merge_similarity 必须在 0（不含）到 1 之间");
+        }
+        if self.max_per_window > 0 && self.density_window_s <= 0.0 {
+            bail!("限制弹幕密度时 density_window_s 必须大于 0");
+        }
+        Ok(())
+    }
+}
+
+/// 编译后的弹幕过滤器
+pub struct DanmakuFilter {
+    keywords: Vec<String>,
+    regexes: Vec<Regex>,
+    mid_hashes: Vec<String>,
+    min_weight: i32,
+    merge_window_s: f64,
+    merge_similarity: f64,
+    max_per_window: usize,
+    density_window_s: f64,
+}
+
+/// 正在合并中的一组弹幕
+struct MergeGroup {
+    index: usize,
+    start_s: f64,
+    key: Vec<char>,
+    count: usize,
+}
+
+impl DanmakuFilter {
+    pub fn new(rules: &DanmakuFilterRules) -> Result<Self> {
+        rules.validate()?;
+        Ok(Self {
+            keywords: rules
+                .keyword_blocklist
+                .iter()
+                .map(|keyword| keyword.trim().to_lowercase())
+                .filter(|keyword| !keyword.is_empty())
+                .collect(),
+            regexes: rules
+                .regex_blocklist
+                .iter()
+                .map(|pattern| Regex::new(pattern))
+                .collect::<Result<_, _>>()?,
+            mid_hashes: rules.mid_hash_blocklist.clone(),
+            min_weight: rules.min_weight,
+            merge_window_s: rules.merge_window_s,
+            merge_similarity: rules.merge_similarity,
+            max_per_window: rules.max_per_window as usize,
+            density_window_s: rules.density_window_s,
+        })
+    }
+
+    /// 依次执行屏蔽、合并与密度限制，返回按时间排序的结果
+    pub fn apply(&self, mut danmaku: Vec<Danmu>) -> Vec<Danmu> {
+        danmaku.retain(|danmu| !self.blocked(danmu));
+        danmaku.sort_by(|a, b| a.timeline_s.total_cmp(&b.timeline_s));
+        if self.merge_window_s > 0.0 {
+            danmaku = self.merge(danmaku);
+        }
+        if self.max_per_window > 0 {
+            danmaku = self.limit_density(danmaku);
+        }
+        danmaku
+    }
+
+    fn blocked(&self, danmu: &Danmu) -> bool {
+        if self.min_weight > 0 && danmu.weight < self.min_weight {
+            return true;
+        }
+        if !danmu.mid_hash.is_empty() && self.mid_hashes.contains(&danmu.mid_hash) {
+            return true;
+        }
+        if !self.keywords.is_empty() {
+            let content = danmu.content.to_lowercase();
+            if self.keywords.iter().any(|keyword| content.contains(keyword.as_str())) {
+                return true;
+            }
+        }
+        self.regexes.iter().any(|regex| regex.is_match(&danmu.content))
+    }
+
+    fn merge(&self, danmaku: Vec<Danmu>) -> Vec<Danmu> {
+        let mut result: Vec<Danmu> = Vec::with_capacity(danmaku.len());
+        let mut groups: VecDeque<MergeGroup> = VecDeque::new();
+        let mut closed = Vec::new();
+        for danmu in danmaku {
+            // 高级弹幕的内容是 JSON，不参与合并
+            if !is_plain(&danmu) {
+                result.push(danmu);
+                continue;
+            }
+            while groups
+                .front()
+                .is_some_and(|group| danmu.timeline_s - group.start_s > self.merge_window_s)
+            {
+                closed.extend(groups.pop_front());
+            }
+            let key = merge_key(&danmu.content);
+            if let Some(group) = groups
+                .iter_mut()
+                .find(|group| similarity(&group.key, &key) >= self.merge_similarity)
+            {
+                group.count += 1;
+                continue;
+            }
+            groups.push_back(MergeGroup {
+                index: result.len(),
+                start_s: danmu.timeline_s,
+                key,
+                count: 1,
+            });
+            result.push(danmu);
+        }
+        closed.extend(groups);
+        for group in closed.into_iter().filter(|group| group.count > 1) {
+            let content = &mut result[group.index].content;
+            *content = format!("{} ×{}", content, group.count);
+        }
+        result
+    }
+
+    fn limit_density(&self, danmaku: Vec<Danmu>) -> Vec<Danmu> {
+        let mut window: VecDeque<f64> = VecDeque::new();
+        danmaku
+            .into_iter()
+            .filter(|danmu| {
+                // 高级弹幕通常是成组出现的特效，不计入密度
+                if !is_plain(danmu) {
+                    return true;
+                }
+                while window
+                    .front()
+                    .is_some_and(|start| danmu.timeline_s - start >= self.density_window_s)
+                {
+                    window.pop_front();
+                }
+                if window.len() >= self.max_per_window {
+                    return false;
+                }
+                window.push_back(danmu.timeline_s);
+                true
+            })
+            .collect()
+    }
+}
+
+/// 普通的滚动/顶部/底部弹幕，高级弹幕与代码弹幕不参与合并与密度限制
+fn is_plain(danmu: &Danmu) -> bool {
+    !matches!(danmu.r#type, DanmuType::Advanced | DanmuType::Code)
+}
+
+/// 合并比较用的规范化内容：忽略大小写、空白与标点，连续重复的字符最多保留两个（「哈哈哈哈」与「哈哈哈」视为相同）
+fn merge_key(content: &str) -> Vec<char> {
+    let mut key: Vec<char> = Vec::with_capacity(content.len());
+    for ch in content.chars().flat_map(char::to_lowercase) {
+        if ch.is_whitespace() || ch.is_ascii_punctuation() || is_cjk_punctuation(ch) {
+            continue;
+        }
+        if key.len() >= 2 && key[key.len() - 1] == ch && key[key.len() - 2] == ch {
+            continue;
+        }
+        key.push(ch);
+    }
+    if key.is_empty() {
+        // 纯标点的弹幕按原文比较
+        key = content.chars().collect();
+    }
+    key
+}
+
+fn is_cjk_punctuation(ch: char) -> bool {
+    matches!(
+        ch,
+        '，' | '。' | '！' | '？' | '、' | '～' | '…' | '：' | '；' | '“' | '”' | '‘' | '’' | '（' | '）'
+    )
+}
+
+/// 基于编辑距离的相似度，取值 0-1
+fn similarity(a: &[char], b: &[char]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    // 长度差距过大时不可能达到阈值，避免无意义的计算
+    let min_len = a.len().min(b.len());
+    if (min_len as f64) / (max_len as f64) < 0.5 {
+        return 0.0;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    1.0 - prev[b.len()] as f64 / max_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn danmu(timeline_s: f64, content: &str) -> Danmu {
+        Danmu {
+            timeline_s,
+            content: content.to_string(),
+            weight: 10,
+            ..Default::default()
+        }
+    }
+
+    fn contents(danmaku: &[Danmu]) -> Vec<&str> {
+        danmaku.iter().map(|danmu| danmu.content.as_str()).collect()
+    }
+
+    #[test]
+    fn default_rules_keep_everything() {
+        let filter = DanmakuFilter::new(&DanmakuFilterRules::default()).unwrap();
+        let input = vec![danmu(2.0, "b"), danmu(1.0, "a"), danmu(1.5, "a")];
+        assert_eq!(contents(&filter.apply(input)), vec!["a", "a", "b"]);
+    }
+
+    #[test]
+    fn blocklists_and_weight_drop_danmaku() {
+        let filter = DanmakuFilter::new(&DanmakuFilterRules {
+            keyword_blocklist: vec!["SPAM".to_string()],
+            regex_blocklist: vec![r"^\d+$".to_string()],
+            mid_hash_blocklist: vec!["deadbeef".to_string()],
+            min_weight: 3,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut from_blocked_user = danmu(1.0, "正常内容");
+        from_blocked_user.mid_hash = "deadbeef".to_string();
+        let mut low_weight = danmu(2.0, "低权重");
+        low_weight.weight = 1;
+        let input = vec![
+            danmu(0.0, "this is spam"),
+            danmu(0.5, "23333"),
+            from_blocked_user,
+            low_weight,
+            danmu(3.0, "留下"),
+        ];
+        assert_eq!(contents(&filter.apply(input)), vec!["留下"]);
+    }
+
+    #[test]
+    fn similar_danmaku_are_merged_within_window() {
+        let filter = DanmakuFilter::new(&DanmakuFilterRules {
+            merge_window_s: 5.0,
+            ..Default::default()
+        })
+        .unwrap();
+        let input = vec![
+            danmu(0.0, "前方高能"),
+            danmu(1.0, "前方高能！！"),
+            danmu(2.0, "哈哈哈哈哈"),
+            danmu(3.0, "前方 高能"),
+            danmu(4.0, "哈哈哈"),
+            // 超出窗口，开始新的一组
+            danmu(6.0, "前方高能"),
+        ];
+        assert_eq!(
+            contents(&filter.apply(input)),
+            vec!["前方高能 ×3", "哈哈哈哈哈 ×2", "前方高能"]
+        );
+    }
+
+    #[test]
+    fn advanced_danmaku_are_not_merged() {
+        let filter = DanmakuFilter::new(&DanmakuFilterRules {
+            merge_window_s: 5.0,
+            ..Default::default()
+        })
+        .unwrap();
+        let advanced = |timeline_s| Danmu {
+            r#type: DanmuType::Advanced,
+            ..danmu(timeline_s, r#"[0.5,0.5,"1-1",3,"特效"]"#)
+        };
+        let input = vec![advanced(0.0), advanced(1.0)];
+        assert_eq!(
+            contents(&filter.apply(input)),
+            vec![r#"[0.5,0.5,"1-1",3,"特效"]"#, r#"[0.5,0.5,"1-1",3,"特效"]"#]
+        );
+    }
+
+    #[test]
+    fn near_identical_danmaku_merge_with_lower_similarity() {
+        let rules = DanmakuFilterRules {
+            merge_window_s: 5.0,
+            merge_similarity: 0.75,
+            ..Default::default()
+        };
+        let filter = DanmakuFilter::new(&rules).unwrap();
+        let input = vec![danmu(0.0, "爷青回爷青回"), danmu(1.0, "爷青回了爷青回")];
+        assert_eq!(contents(&filter.apply(input)), vec!["爷青回爷青回 ×2"]);
+    }
+
+    #[test]
+    fn density_cap_limits_danmaku_per_window() {
+        let filter = DanmakuFilter::new(&DanmakuFilterRules {
+            max_per_window: 2,
+            density_window_s: 1.0,
+            ..Default::default()
+        })
+        .unwrap();
+        let input = vec![
+            danmu(0.0, "1"),
+            danmu(0.2, "2"),
+            danmu(0.4, "3"),
+            danmu(1.0, "4"),
+            danmu(1.1, "5"),
+            danmu(1.3, "6"),
+        ];
+        assert_eq!(contents(&filter.apply(input)), vec!["1", "2", "4", "6"]);
+    }
+
+    #[test]
+    fn source_override_replaces_rules_and_inherits_blocklists() {
+        let option: DanmakuFilterOption = serde_json::from_value(serde_json::json!({
+            "keyword_blocklist": ["全局"],
+            "min_weight": 5,
+            "source_overrides": [
+                {"source_type": "favorite", "source_id": 1, "keyword_blocklist": ["局部"]},
+                {"source_type": "collection", "source_id": 2, "inherit_blocklists": false},
+            ],
+        }))
+        .unwrap();
+        option.validate().unwrap();
+
+        assert_eq!(option.rules_for(Some("submission"), Some(1)), option.rules);
+        let favorite = option.rules_for(Some("favorite"), Some(1));
+        assert_eq!(favorite.keyword_blocklist, vec!["局部", "全局"]);
+        assert_eq!(favorite.min_weight, 0);
+        assert!(option
+            .rules_for(Some("collection"), Some(2))
+            .keyword_blocklist
+            .is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_bad_rules() {
+        let bad_regex = DanmakuFilterRules {
+            regex_blocklist: vec!["(".to_string()],
+            ..Default::default()
+        };
+        assert!(bad_regex.validate().is_err());
+        let bad_density = DanmakuFilterRules {
+            max_per_window: 3,
+            density_window_s: 0.0,
+            ..Default::default()
+        };
+        assert!(bad_density.validate().is_err());
+        let option = DanmakuFilterOption {
+            source_overrides: vec![DanmakuFilterOverride {
+                source_type: "unknown".to_string(),
+                source_id: 1,
+                inherit_blocklists: true,
+                rules: DanmakuFilterRules::default(),
+            }],
+            ..Default::default()
+        };
+        assert!(option.validate().is_err());
+    }
+}
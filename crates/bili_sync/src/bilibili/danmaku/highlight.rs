@@ -0,0 +1,270 @@
+//! 弹幕密度统计与高能时刻检测
+//!
+//! 按固定时长的时间桶统计弹幕数量，在平滑后的密度曲线上寻找局部峰值，
+//! 取弹幕最密集的若干时刻作为高能时刻，可选地生成章节标记。
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::bilibili::danmaku::DanmakuElem;
+
+/// 峰值至少需要达到平均密度的倍数
+const PEAK_MIN_RATIO: f64 = 1.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DanmakuHighlightOption {
+    /// 直方图时间桶长度（秒）
+    pub bucket_seconds: u32,
+    /// 生成章节时选取的高能时刻数量
+    pub chapter_count: usize,
+    /// 两个高能时刻之间的最小间隔（秒）
+    pub min_gap_seconds: u32,
+    /// UP 主没有设置章节时，按高能时刻生成章节并写入 NFO 与视频文件
+    pub generate_chapters: bool,
+}
+
+impl Default for DanmakuHighlightOption {
+    fn default() -> Self {
+        Self {
+            bucket_seconds: 10,
+            chapter_count: 5,
+            min_gap_seconds: 60,
+            generate_chapters: false,
+        }
+    }
+}
+
+impl DanmakuHighlightOption {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=600).contains(&self.bucket_seconds) {
+            bail!("弹幕直方图时间桶长度需要在 1-600 秒之间");
+        }
+        if self.chapter_count == 0 || self.chapter_count > 50 {
+            bail!("高能章节数量需要在 1-50 之间");
+        }
+        Ok(())
+    }
+}
+
+/// 分页的弹幕密度直方图，第 i 个桶统计 `[i * bucket_seconds, (i + 1) * bucket_seconds)` 内的弹幕
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DanmakuHistogram {
+    pub bucket_seconds: u32,
+    pub counts: Vec<u32>,
+}
+
+/// 一个高能时刻
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DanmakuPeak {
+    /// 起止时间（秒）
+    pub start_s: u32,
+    pub end_s: u32,
+    /// 峰值桶及相邻桶的弹幕数
+    pub count: u32,
+    /// 相对于平均密度的倍数
+    pub ratio: f64,
+}
+
+/// 按高能时刻生成的章节
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HighlightChapter {
+    pub start_s: u32,
+    pub end_s: u32,
+    pub title: String,
+}
+
+impl DanmakuHistogram {
+    pub fn from_elems(elems: &[DanmakuElem], duration_s: u32, bucket_seconds: u32) -> Self {
+        let bucket_seconds = bucket_seconds.max(1);
+        let bucket_ms = bucket_seconds as u64 * 1000;
+        let mut counts = vec![0u32; duration_s.div_ceil(bucket_seconds).max(1) as usize];
+        for elem in elems {
+            let index = (elem.progress.max(0) as u64 / bucket_ms) as usize;
+            // 超出时长的弹幕（时长未知或分段误差）扩展直方图而不是丢弃
+            if index >= counts.len() {
+                counts.resize(index + 1, 0);
+            }
+            counts[index] += 1;
+        }
+        Self { bucket_seconds, counts }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// 取弹幕最密集的 top_n 个时刻，按时间排序，相邻时刻至少间隔 min_gap_seconds
+    pub fn peaks(&self, top_n: usize, min_gap_seconds: u32) -> Vec<DanmakuPeak> {
+        let len = self.counts.len();
+        if len == 0 || top_n == 0 {
+            return Vec::new();
+        }
+        // 相邻三个桶求和，避免弹幕恰好落在桶边界时峰值被拆散
+        let smoothed: Vec<u32> = (0..len)
+            .map(|i| self.counts[i.saturating_sub(1)..(i + 2).min(len)].iter().sum())
+            .collect();
+        let mean = smoothed.iter().sum::<u32>() as f64 / len as f64;
+        if mean <= 0.0 {
+            return Vec::new();
+        }
+
+        // 平滑后的曲线在峰值附近会出现平台，用原始计数确定峰值所在的桶
+        let is_local_max = |values: &[u32], i: usize| {
+            (i == 0 || values[i] >= values[i - 1]) && (i + 1 == len || values[i] >= values[i + 1])
+        };
+        let mut candidates: Vec<usize> = (0..len)
+            .filter(|&i| {
+                smoothed[i] as f64 >= mean * PEAK_MIN_RATIO
+                    && is_local_max(&smoothed, i)
+                    && is_local_max(&self.counts, i)
+            })
+            .collect();
+        candidates.sort_by(|a, b| smoothed[*b].cmp(&smoothed[*a]).then(a.cmp(b)));
+
+        let min_gap_buckets = min_gap_seconds.div_ceil(self.bucket_seconds) as usize;
+        let mut selected: Vec<usize> = Vec::with_capacity(top_n);
+        for index in candidates {
+            if selected.len() >= top_n {
+                break;
+            }
+            if selected
+                .iter()
+                .all(|&other| index.abs_diff(other) >= min_gap_buckets.max(1))
+            {
+                selected.push(index);
+            }
+        }
+        selected.sort_unstable();
+
+        selected
+            .into_iter()
+            .map(|index| DanmakuPeak {
+                start_s: index as u32 * self.bucket_seconds,
+                end_s: (index as u32 + 1) * self.bucket_seconds,
+                count: smoothed[index],
+                ratio: (smoothed[index] as f64 / mean * 100.0).round() / 100.0,
+            })
+            .collect()
+    }
+}
+
+/// 以高能时刻为起点切分章节，第一个高能时刻之前的部分作为“开头”
+pub fn highlight_chapters(peaks: &[DanmakuPeak], duration_s: u32) -> Vec<HighlightChapter> {
+    let mut starts: Vec<(u32, String)> = Vec::with_capacity(peaks.len() + 1);
+    for (index, peak) in peaks.iter().enumerate() {
+        if peak.start_s >= duration_s {
+            break;
+        }
+        starts.push((peak.start_s, format!("高能 {} ({}条弹幕)", index + 1, peak.count)));
+    }
+    if starts.is_empty() {
+        return Vec::new();
+    }
+    if starts[0].0 > 0 {
+        starts.insert(0, (0, "开头".to_string()));
+    }
+
+    let ends: Vec<u32> = starts
+        .iter()
+        .skip(1)
+        .map(|(start, _)| *start)
+        .chain(std::iter::once(duration_s))
+        .collect();
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(|((start_s, title), end_s)| HighlightChapter { start_s, end_s, title })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elems_at(seconds: &[u32]) -> Vec<DanmakuElem> {
+        seconds
+            .iter()
+            .map(|second| DanmakuElem {
+                progress: (*second * 1000) as i32,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn histogram_counts_buckets() {
+        let histogram = DanmakuHistogram::from_elems(&elems_at(&[0, 5, 9, 10, 35, 70]), 40, 10);
+        // 超出时长的弹幕会扩展直方图
+        assert_eq!(histogram.counts, vec![3, 1, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(histogram.total(), 6);
+    }
+
+    #[test]
+    fn peaks_respect_gap_and_order() {
+        let mut counts = vec![1u32; 60];
+        counts[10] = 30;
+        counts[11] = 25;
+        counts[40] = 20;
+        counts[45] = 18;
+        let histogram = DanmakuHistogram {
+            bucket_seconds: 10,
+            counts,
+        };
+        let peaks = histogram.peaks(3, 100);
+        let starts: Vec<u32> = peaks.iter().map(|peak| peak.start_s).collect();
+        // 第 45 个桶离第 40 个桶太近，被忽略
+        assert_eq!(starts, vec![100, 400]);
+        assert!(peaks[0].count > peaks[1].count);
+        assert!(peaks.iter().all(|peak| peak.ratio >= PEAK_MIN_RATIO));
+    }
+
+    #[test]
+    fn flat_histogram_has_no_peaks() {
+        let histogram = DanmakuHistogram {
+            bucket_seconds: 10,
+            counts: vec![3; 20],
+        };
+        assert!(histogram.peaks(5, 30).is_empty());
+        assert!(DanmakuHistogram::from_elems(&[], 0, 10).peaks(5, 30).is_empty());
+    }
+
+    #[test]
+    fn option_validation() {
+        assert!(DanmakuHighlightOption::default().validate().is_ok());
+        let invalid = DanmakuHighlightOption {
+            bucket_seconds: 0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn chapters_cover_whole_duration() {
+        let peaks = vec![
+            DanmakuPeak {
+                start_s: 120,
+                end_s: 130,
+                count: 40,
+                ratio: 4.0,
+            },
+            DanmakuPeak {
+                start_s: 300,
+                end_s: 310,
+                count: 20,
+                ratio: 2.0,
+            },
+        ];
+        let chapters = highlight_chapters(&peaks, 600);
+        assert_eq!(
+            chapters
+                .iter()
+                .map(|chapter| (chapter.start_s, chapter.end_s))
+                .collect::<Vec<_>>(),
+            vec![(0, 120), (120, 300), (300, 600)]
+        );
+        assert_eq!(chapters[0].title, "开头");
+        assert_eq!(chapters[1].title, "高能 1 (40条弹幕)");
+        assert!(highlight_chapters(&[], 600).is_empty());
+    }
+}
@@ -1,13 +1,19 @@
+mod archive;
 mod ass_writer;
 mod canvas;
 mod danmu;
 mod drawable;
+mod filter;
+mod highlight;
 mod model;
 mod writer;
 
+pub use archive::{archive_danmaku, load_archived_danmaku, DanmakuArchiveOption};
 pub use ass_writer::{parse_event_name, AssWriter};
 pub use canvas::DanmakuOption;
 pub use danmu::Danmu;
 pub use drawable::{DrawEffect, Drawable};
+pub use filter::{DanmakuFilter, DanmakuFilterOption};
+pub use highlight::{highlight_chapters, DanmakuHighlightOption, DanmakuHistogram, DanmakuPeak, HighlightChapter};
 pub use model::{DanmakuElem, DmSegMobileReply};
 pub use writer::DanmakuWriter;
@@ -3,10 +3,12 @@
 //! 可以看旁边的 dm.proto
 
 use prost::Message;
+use serde::{Deserialize, Serialize};
 
 use crate::bilibili::danmaku::danmu::{Danmu, DanmuType};
-/// 弹幕 pb 定义
-#[derive(Clone, Message)]
+/// 弹幕 pb 定义，同时用于原始弹幕的 JSON Lines 归档
+#[derive(Clone, Message, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DanmakuElem {
     /// 弹幕 dmid
     #[prost(int64, tag = "1")]
@@ -85,6 +87,8 @@ impl From<DanmakuElem> for Danmu {
             } else {
                 Some(elem.dmid_str)
             },
+            mid_hash: elem.mid_hash,
+            weight: elem.weight,
         }
     }
 }
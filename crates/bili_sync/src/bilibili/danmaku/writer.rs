@@ -4,17 +4,28 @@ use anyhow::Result;
 use tokio::fs::{self, File, OpenOptions};
 
 use crate::bilibili::danmaku::canvas::{CanvasConfig, DanmakuOption};
-use crate::bilibili::danmaku::{AssWriter, Danmu};
+use crate::bilibili::danmaku::{AssWriter, DanmakuFilter, Danmu};
 use crate::bilibili::PageInfo;
 
 pub struct DanmakuWriter<'a> {
     page: &'a PageInfo,
     danmaku: Vec<Danmu>,
+    filter: Option<DanmakuFilter>,
 }
 
 impl<'a> DanmakuWriter<'a> {
     pub fn new(page: &'a PageInfo, danmaku: Vec<Danmu>) -> Self {
-        DanmakuWriter { page, danmaku }
+        DanmakuWriter {
+            page,
+            danmaku,
+            filter: None,
+        }
+    }
+
+    /// 绘制前先用过滤器处理弹幕
+    pub fn with_filter(mut self, filter: DanmakuFilter) -> Self {
+        self.filter = Some(filter);
+        self
     }
 
     pub async fn write(self, path: PathBuf) -> Result<()> {
@@ -45,7 +56,11 @@ impl<'a> DanmakuWriter<'a> {
             AssWriter::new(file, self.page.name.clone(), canvas_config.clone())
         };
         let mut canvas = canvas_config.canvas();
-        for danmuku in self.danmaku {
+        let danmaku = match &self.filter {
+            Some(filter) => filter.apply(self.danmaku),
+            None => self.danmaku,
+        };
+        for danmuku in danmaku {
             if let Some(drawable) = canvas.draw(danmuku)? {
                 writer.write(drawable).await?;
             }
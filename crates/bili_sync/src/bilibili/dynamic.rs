@@ -8,7 +8,7 @@ use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
 use crate::bilibili::credential::encoded_query;
-use crate::bilibili::{BiliClient, Validate, VideoInfo, MIXIN_KEY};
+use crate::bilibili::{BiliClient, Validate, VideoInfo};
 use crate::config::SubmissionRiskControlConfig;
 
 pub struct Dynamic<'a> {
@@ -34,7 +34,7 @@ impl<'a> Dynamic<'a> {
                     ("offset", offset.unwrap_or("")),
                     ("type", "video"),
                 ],
-                MIXIN_KEY.load().as_deref(),
+                self.client.mixin_key().as_deref(),
             ))
             .send()
             .await?
@@ -1,4 +1,5 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 pub use analyzer::{AudioQuality, BestStream, FilterOption, PageAnalyzer, Stream, VideoCodecs, VideoQuality};
 use anyhow::{bail, ensure, Result};
@@ -9,13 +10,19 @@ use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
 pub use client::{BiliClient, Client, SearchResult};
 pub use collection::{Collection, CollectionEpisodeOrderStrategy, CollectionItem, CollectionType};
+pub use comment::{CommentArchive, CommentArchiveOption, VideoComments};
 pub use credential::Credential;
-pub use danmaku::{parse_event_name, DanmakuElem, DanmakuOption, DanmakuWriter};
+pub use danmaku::{
+    archive_danmaku, highlight_chapters, load_archived_danmaku, parse_event_name, DanmakuArchiveOption, DanmakuElem,
+    DanmakuFilter, DanmakuFilterOption, DanmakuHighlightOption, DanmakuHistogram, DanmakuOption, DanmakuPeak,
+    DanmakuWriter, HighlightChapter,
+};
 pub use dynamic::Dynamic;
 pub use error::BiliError;
 pub use favorite_list::FavoriteList;
 use favorite_list::Upper;
 use once_cell::sync::Lazy;
+pub use ranking::{Ranking, RankingType};
 pub use risk_control::{CaptchaInfo, CaptchaResult, GeetestInfo, RiskControl};
 use serde::{Deserialize, Deserializer};
 pub use submission::Submission;
@@ -33,11 +40,13 @@ mod captcha_server;
 mod captcha_solver;
 mod client;
 mod collection;
+mod comment;
 mod credential;
 mod danmaku;
 mod dynamic;
 mod error;
 mod favorite_list;
+mod ranking;
 mod risk_control;
 pub mod submission;
 mod subtitle;
@@ -46,11 +55,32 @@ mod video;
 mod watch_later;
 
 static MIXIN_KEY: Lazy<ArcSwapOption<String>> = Lazy::new(Default::default);
+/// 额外账号各自的 WBI 签名缓存，键为账号 ID
+static ACCOUNT_MIXIN_KEYS: Lazy<RwLock<HashMap<i32, Arc<String>>>> = Lazy::new(Default::default);
 
 pub(crate) fn set_global_mixin_key(key: String) {
     MIXIN_KEY.store(Some(Arc::new(key)));
 }
 
+/// 获取指定账号的 mixin key，`None` 表示默认账号
+pub(crate) fn mixin_key_for(credential_id: Option<i32>) -> Option<Arc<String>> {
+    match credential_id {
+        None => MIXIN_KEY.load_full(),
+        Some(id) => ACCOUNT_MIXIN_KEYS.read().ok()?.get(&id).cloned(),
+    }
+}
+
+pub(crate) fn set_mixin_key_for(credential_id: Option<i32>, key: String) {
+    match credential_id {
+        None => set_global_mixin_key(key),
+        Some(id) => {
+            if let Ok(mut keys) = ACCOUNT_MIXIN_KEYS.write() {
+                keys.insert(id, Arc::new(key));
+            }
+        }
+    }
+}
+
 fn parse_duration_to_seconds(raw: &str) -> Option<i32> {
     let normalized = raw.trim();
     if normalized.is_empty() {
@@ -290,4 +320,18 @@ pub enum VideoInfo {
         /// 演员信息字符串，从API获取
         actors: Option<String>,
     },
+    /// 从热门/每周必看/排行榜接口获取的视频信息
+    /// 这些接口的字段与其它列表接口高度重叠，不参与 untagged 匹配，由 Ranking 显式构造
+    #[serde(skip_deserializing)]
+    Ranking {
+        title: String,
+        bvid: String,
+        intro: String,
+        cover: String,
+        upper: Upper<i64>,
+        ctime: DateTime<Utc>,
+        pubtime: DateTime<Utc>,
+        duration: Option<i32>,
+        state: i32,
+    },
 }
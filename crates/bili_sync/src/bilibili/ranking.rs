@@ -0,0 +1,301 @@
+use anyhow::{anyhow, bail, Context, Result};
+use async_stream::try_stream;
+use chrono::serde::ts_seconds;
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::bilibili::credential::encoded_query;
+use crate::bilibili::favorite_list::Upper;
+use crate::bilibili::{BiliClient, Validate, VideoInfo};
+
+/// 综合热门每页条数（接口上限 50）
+const POPULAR_PAGE_SIZE: u32 = 50;
+/// 综合热门最多拉取的页数，避免一次订阅拉下整个热门池
+const POPULAR_MAX_PAGES: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingType {
+    /// 综合热门
+    Popular,
+    /// 每周必看（最新一期）
+    Weekly,
+    /// 分区排行榜
+    Rank,
+}
+
+impl RankingType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RankingType::Popular => "popular",
+            RankingType::Weekly => "weekly",
+            RankingType::Rank => "rank",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RankingType::Popular => "综合热门",
+            RankingType::Weekly => "每周必看",
+            RankingType::Rank => "排行榜",
+        }
+    }
+
+    /// 榜单源的默认名称，包含榜单范围（分区）与统计时间窗口
+    pub fn source_name(&self, rid: i32) -> String {
+        match self {
+            RankingType::Popular => format!("{}（实时）", self.display_name()),
+            RankingType::Weekly => format!("{}（最新一期）", self.display_name()),
+            RankingType::Rank => format!("{}·{}（近三日）", self.display_name(), rank_zone_name(rid)),
+        }
+    }
+}
+
+/// 排行榜接口支持的分区名称，未收录的分区以ID显示
+fn rank_zone_name(rid: i32) -> String {
+    let name = match rid {
+        0 => "全站",
+        1 => "动画",
+        3 => "音乐",
+        4 => "游戏",
+        5 => "娱乐",
+        36 => "知识",
+        119 => "鬼畜",
+        129 => "舞蹈",
+        155 => "时尚",
+        160 => "生活",
+        168 => "国创相关",
+        181 => "影视",
+        188 => "科技",
+        211 => "美食",
+        217 => "动物圈",
+        223 => "汽车",
+        234 => "运动",
+        _ => return format!("分区{}", rid),
+    };
+    name.to_string()
+}
+
+impl std::str::FromStr for RankingType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "popular" => Ok(RankingType::Popular),
+            "weekly" => Ok(RankingType::Weekly),
+            "rank" => Ok(RankingType::Rank),
+            _ => bail!("不支持的榜单类型: {}", s),
+        }
+    }
+}
+
+/// 热门/每周必看/排行榜接口返回的视频条目，三者字段基本一致
+#[derive(Debug, serde::Deserialize)]
+struct RankingItem {
+    title: String,
+    bvid: String,
+    #[serde(default)]
+    desc: String,
+    pic: String,
+    owner: Upper<i64>,
+    #[serde(with = "ts_seconds")]
+    ctime: DateTime<Utc>,
+    #[serde(with = "ts_seconds")]
+    pubdate: DateTime<Utc>,
+    #[serde(default)]
+    duration: Option<i32>,
+    #[serde(default)]
+    state: i32,
+}
+
+impl From<RankingItem> for VideoInfo {
+    fn from(item: RankingItem) -> Self {
+        VideoInfo::Ranking {
+            title: item.title,
+            bvid: item.bvid,
+            intro: item.desc,
+            cover: item.pic,
+            upper: item.owner,
+            ctime: item.ctime,
+            pubtime: item.pubdate,
+            duration: item.duration,
+            state: item.state,
+        }
+    }
+}
+
+pub struct Ranking<'a> {
+    client: &'a BiliClient,
+    ranking_type: RankingType,
+    rid: i32,
+}
+
+impl<'a> Ranking<'a> {
+    pub fn new(client: &'a BiliClient, ranking_type: RankingType, rid: i32) -> Self {
+        Self {
+            client,
+            ranking_type,
+            rid,
+        }
+    }
+
+    async fn get_json(&self, url: &str, query: Vec<(&str, String)>) -> Result<Value> {
+        self.client
+            .request(Method::GET, url)
+            .await
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?
+            .validate()
+    }
+
+    async fn get_popular_page(&self, page: u32) -> Result<Value> {
+        self.get_json(
+            "https://api.bilibili.com/x/web-interface/popular",
+            vec![("pn", page.to_string()), ("ps", POPULAR_PAGE_SIZE.to_string())],
+        )
+        .await
+    }
+
+    /// 获取每周必看的最新期数
+    async fn get_latest_weekly_number(&self) -> Result<i64> {
+        let res = self
+            .get_json("https://api.bilibili.com/x/web-interface/popular/series/list", vec![])
+            .await?;
+        res["data"]["list"]
+            .as_array()
+            .and_then(|list| list.iter().filter_map(|item| item["number"].as_i64()).max())
+            .ok_or_else(|| anyhow!("每周必看期数列表为空"))
+    }
+
+    async fn get_weekly(&self, number: i64) -> Result<Value> {
+        self.get_json(
+            "https://api.bilibili.com/x/web-interface/popular/series/one",
+            vec![("number", number.to_string())],
+        )
+        .await
+    }
+
+    async fn get_rank(&self) -> Result<Value> {
+        let rid = self.rid.to_string();
+        self.client
+            .request(Method::GET, "https://api.bilibili.com/x/web-interface/ranking/v2")
+            .await
+            .query(&encoded_query(
+                vec![("rid", rid.as_str()), ("type", "all"), ("web_location", "333.934")],
+                self.client.mixin_key().as_deref(),
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?
+            .validate()
+    }
+
+    fn parse_list(list: &mut Value) -> Result<Vec<VideoInfo>> {
+        if list.is_null() {
+            return Ok(Vec::new());
+        }
+        let items: Vec<RankingItem> = serde_json::from_value(list.take()).context("解析榜单视频列表失败")?;
+        Ok(items.into_iter().map(VideoInfo::from).collect())
+    }
+
+    pub fn into_video_stream(self) -> impl Stream<Item = Result<VideoInfo>> + 'a {
+        try_stream! {
+            match self.ranking_type {
+                RankingType::Popular => {
+                    for page in 1..=POPULAR_MAX_PAGES {
+                        let mut res = self
+                            .get_popular_page(page)
+                            .await
+                            .with_context(|| format!("failed to get popular videos page {}", page))?;
+                        for video_info in Self::parse_list(&mut res["data"]["list"])? {
+                            yield video_info;
+                        }
+                        if res["data"]["no_more"].as_bool().unwrap_or(true) {
+                            break;
+                        }
+                    }
+                }
+                RankingType::Weekly => {
+                    let number = self.get_latest_weekly_number().await?;
+                    let mut res = self
+                        .get_weekly(number)
+                        .await
+                        .with_context(|| format!("failed to get weekly series {}", number))?;
+                    for video_info in Self::parse_list(&mut res["data"]["list"])? {
+                        yield video_info;
+                    }
+                }
+                RankingType::Rank => {
+                    let mut res = self
+                        .get_rank()
+                        .await
+                        .with_context(|| format!("failed to get ranking of rid {}", self.rid))?;
+                    for video_info in Self::parse_list(&mut res["data"]["list"])? {
+                        yield video_info;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ranking_list() {
+        let mut list = serde_json::json!([
+            {
+                "aid": 1,
+                "bvid": "BV1xx411c7mD",
+                "title": "每周必看测试",
+                "desc": "简介",
+                "pic": "https://i0.hdslb.com/test.jpg",
+                "owner": {"mid": 123, "name": "UP", "face": "https://i0.hdslb.com/face.jpg"},
+                "ctime": 1700000000,
+                "pubdate": 1700000100,
+                "duration": 321,
+                "state": 0,
+                "rcmd_reason": {"content": "百万播放"}
+            }
+        ]);
+        let videos = Ranking::parse_list(&mut list).unwrap();
+        assert_eq!(videos.len(), 1);
+        match &videos[0] {
+            VideoInfo::Ranking {
+                bvid,
+                upper,
+                duration,
+                pubtime,
+                ..
+            } => {
+                assert_eq!(bvid, "BV1xx411c7mD");
+                assert_eq!(upper.mid, 123);
+                assert_eq!(*duration, Some(321));
+                assert_eq!(pubtime.timestamp(), 1700000100);
+            }
+            _ => panic!("应解析为 Ranking 视频"),
+        }
+        assert!(Ranking::parse_list(&mut Value::Null).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ranking_type_round_trip() {
+        for ranking_type in [RankingType::Popular, RankingType::Weekly, RankingType::Rank] {
+            assert_eq!(ranking_type.as_str().parse::<RankingType>().unwrap(), ranking_type);
+        }
+        assert!("unknown".parse::<RankingType>().is_err());
+        assert_eq!(RankingType::Rank.source_name(0), "排行榜·全站（近三日）");
+        assert_eq!(RankingType::Rank.source_name(188), "排行榜·科技（近三日）");
+        assert_eq!(RankingType::Rank.source_name(999), "排行榜·分区999（近三日）");
+        assert_eq!(RankingType::Weekly.source_name(0), "每周必看（最新一期）");
+    }
+}
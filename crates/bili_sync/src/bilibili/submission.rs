@@ -1,5 +1,4 @@
 use anyhow::{anyhow, Context, Result};
-use arc_swap::access::Access;
 use async_stream::try_stream;
 use futures::Stream;
 use once_cell::sync::Lazy;
@@ -12,7 +11,7 @@ use tracing::{debug, info, warn};
 
 use crate::bilibili::credential::encoded_query;
 use crate::bilibili::favorite_list::Upper;
-use crate::bilibili::{BiliClient, Validate, VideoInfo, MIXIN_KEY};
+use crate::bilibili::{BiliClient, Validate, VideoInfo};
 use crate::config::SubmissionRiskControlConfig;
 use crate::database::get_global_db;
 use crate::utils::submission_checkpoint;
@@ -86,7 +85,7 @@ impl<'a> Submission<'a> {
                     ("web_location", "1550101"),
                     ("token", ""),
                 ],
-                MIXIN_KEY.load().as_deref(),
+                self.client.mixin_key().as_deref(),
             ))
             .send()
             .await?
@@ -111,7 +110,7 @@ impl<'a> Submission<'a> {
                     ("pn", page.to_string().as_str()),
                     ("ps", "30"),
                 ],
-                MIXIN_KEY.load().as_deref(),
+                self.client.mixin_key().as_deref(),
             ))
             .send()
             .await?
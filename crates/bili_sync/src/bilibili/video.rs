@@ -16,7 +16,7 @@ use crate::bilibili::client::BiliClient;
 use crate::bilibili::credential::encoded_query;
 use crate::bilibili::danmaku::{DanmakuElem, DmSegMobileReply};
 use crate::bilibili::subtitle::{SubTitle, SubTitleBody, SubTitleInfo, SubTitlesInfo, SubtitleDownloadOptions};
-use crate::bilibili::{Validate, VideoInfo, MIXIN_KEY};
+use crate::bilibili::{Validate, VideoInfo};
 use crate::hardware::HardwareFingerprint;
 use crate::http::headers::create_api_headers;
 
@@ -275,21 +275,21 @@ impl<'a> Video<'a> {
         Ok(true)
     }
 
-    async fn ensure_global_mixin_key(&self) -> Result<()> {
-        if MIXIN_KEY.load().is_some() {
+    async fn ensure_mixin_key(&self) -> Result<()> {
+        if self.client.mixin_key().is_some() {
             return Ok(());
         }
         tracing::debug!("mixin_key 未初始化，尝试获取 wbi_img 以初始化签名");
-        self.refresh_global_mixin_key().await
+        self.refresh_mixin_key().await
     }
 
-    async fn refresh_global_mixin_key(&self) -> Result<()> {
+    async fn refresh_mixin_key(&self) -> Result<()> {
         let wbi_img = self.client.wbi_img().await?;
         let mixin_key: Option<String> = wbi_img.into();
         let Some(mixin_key) = mixin_key else {
             bail!("解析 mixin key 失败");
         };
-        crate::bilibili::set_global_mixin_key(mixin_key);
+        self.client.set_mixin_key(mixin_key);
         Ok(())
     }
 
@@ -1114,7 +1114,7 @@ impl<'a> Video<'a> {
         let cid_string = page.cid.to_string();
 
         // 生成硬件指纹
-        let fingerprint = HardwareFingerprint::default();
+        let fingerprint = HardwareFingerprint::for_account(self.client.credential_id());
         let hardware = fingerprint.get_hardware();
 
         // 生成弹幕防挡参数（使用会话固定的硬件指纹）
@@ -1141,8 +1141,8 @@ impl<'a> Video<'a> {
             ("dm_img_inter", dm_img_inter.as_str()),         // 弹幕交互统计
         ];
 
-        self.ensure_global_mixin_key().await?;
-        let mut encoded_params = encoded_query(params.clone(), MIXIN_KEY.load().as_deref());
+        self.ensure_mixin_key().await?;
+        let mut encoded_params = encoded_query(params.clone(), self.client.mixin_key().as_deref());
         tracing::debug!("API参数: {:?}", params);
         tracing::debug!("编码后参数: {:?}", encoded_params);
 
@@ -1182,11 +1182,11 @@ impl<'a> Video<'a> {
 
             if response.status() == StatusCode::PRECONDITION_FAILED && !did_refresh_wbi {
                 tracing::warn!("playurl 返回 412，尝试刷新 mixin_key 后重试一次");
-                if let Err(e) = self.refresh_global_mixin_key().await {
+                if let Err(e) = self.refresh_mixin_key().await {
                     tracing::warn!("刷新 mixin_key 失败，继续按原错误处理: {:#}", e);
                 } else {
                     did_refresh_wbi = true;
-                    encoded_params = encoded_query(params.clone(), MIXIN_KEY.load().as_deref());
+                    encoded_params = encoded_query(params.clone(), self.client.mixin_key().as_deref());
                     continue;
                 }
             }
@@ -1507,7 +1507,7 @@ impl<'a> Video<'a> {
         let cid_string = page.cid.to_string();
 
         // 生成硬件指纹
-        let fingerprint = HardwareFingerprint::default();
+        let fingerprint = HardwareFingerprint::for_account(self.client.credential_id());
         let hardware = fingerprint.get_hardware();
 
         // 生成弹幕防挡参数（使用会话固定的硬件指纹）
@@ -1660,7 +1660,7 @@ impl<'a> Video<'a> {
             .await
             .query(&encoded_query(
                 vec![("cid", &page.cid.to_string()), ("bvid", &self.bvid), ("aid", &self.aid)],
-                MIXIN_KEY.load().as_deref(),
+                self.client.mixin_key().as_deref(),
             ))
             .send()
             .await?
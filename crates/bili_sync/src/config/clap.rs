@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::path::PathBuf;
 
 use clap::Parser;
 
@@ -10,6 +11,14 @@ pub struct Args {
 
     #[arg(short, long, default_value = "None,bili_sync=info", env = "RUST_LOG")]
     pub log_level: String,
+
+    /// 数据库地址，支持 sqlite://、postgres://、mysql://，未设置时使用配置目录下的 data.sqlite
+    #[arg(long, env = "DATABASE_URL")]
+    pub database_url: Option<String>,
+
+    /// 声明式配置文件（YAML 或 TOML），其中声明的配置项与视频源在启动及文件变化时同步到数据库
+    #[arg(long, env = "BILI_SYNC_CONFIG_FILE")]
+    pub config_file: Option<PathBuf>,
 }
 
 mod built_info {
@@ -0,0 +1,406 @@
+//! 声明式配置文件（GitOps）
+//!
+//! 通过 `--config-file` 指定的 YAML/TOML 文件声明配置项与视频源，格式与导出包的 `config`、`sources` 相同。
+//! 启动时及文件变化时与数据库对账：新视频源被创建，已有视频源的设置被覆盖，从文件中移除的视频源被禁用。
+//! 由文件管理的配置项和视频源在 API 中只读，需通过修改配置文件变更。
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use bili_sync_entity::{collection, favorite, ranking, submission, video_source, watch_later};
+use once_cell::sync::Lazy;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+use crate::api::error::InnerApiError;
+use crate::api::response::ImportBundleResponse;
+use crate::utils::source_bundle::{self, BundleSource, ConflictStrategy, SourceBundle, BUNDLE_VERSION};
+use crate::utils::time_format::now_standard_string;
+
+/// 保存上次对账结果的配置项，用于识别从文件中移除的视频源
+pub const DECLARATIVE_STATE_KEY: &str = "declarative_state";
+
+/// 检查配置文件是否变化的间隔
+const WATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+tokio::task_local! {
+    /// 对账过程中写入受管理的配置项不受只读限制
+    static RECONCILING: ();
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeclarativeFile {
+    pub config: BTreeMap<String, Value>,
+    pub sources: Vec<BundleSource>,
+}
+
+impl DeclarativeFile {
+    /// 按扩展名解析：`.toml` 使用 TOML，其余按 YAML（兼容 JSON）解析
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        let is_toml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let file: DeclarativeFile = if is_toml {
+            toml::from_str(content).context("TOML 格式无效")?
+        } else {
+            serde_yaml::from_str(content).context("YAML 格式无效")?
+        };
+
+        let mut seen = HashSet::new();
+        for source in &file.sources {
+            let key = (
+                source.source_type.as_str(),
+                source.source_id.trim(),
+                source.up_id.as_deref(),
+            );
+            if !seen.insert(key) {
+                bail!("视频源重复声明: {} {}", source.source_type, source.source_id);
+            }
+        }
+        Ok(file)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ManagedSource {
+    pub source_type: String,
+    pub id: i32,
+    pub name: String,
+}
+
+/// 由配置文件管理的配置项与视频源
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct ManagedState {
+    pub config_keys: Vec<String>,
+    pub sources: Vec<ManagedSource>,
+}
+
+impl ManagedState {
+    /// 上次由文件管理、本次不再声明的视频源
+    fn removed_sources<'a>(&'a self, current: &ManagedState) -> Vec<&'a ManagedSource> {
+        self.sources
+            .iter()
+            .filter(|old| {
+                !current
+                    .sources
+                    .iter()
+                    .any(|new| new.source_type == old.source_type && new.id == old.id)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DeclarativeStatus {
+    pub path: Option<PathBuf>,
+    pub state: ManagedState,
+    pub last_reconciled_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+static STATUS: Lazy<RwLock<DeclarativeStatus>> = Lazy::new(|| RwLock::new(DeclarativeStatus::default()));
+
+pub fn declarative_status() -> DeclarativeStatus {
+    STATUS.read().unwrap().clone()
+}
+
+fn is_reconciling() -> bool {
+    RECONCILING.try_with(|_| ()).is_ok()
+}
+
+fn managed_by() -> String {
+    STATUS
+        .read()
+        .unwrap()
+        .path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
+
+/// 配置项是否由声明式配置文件管理（对账过程中始终返回 false）
+pub fn is_config_key_managed(key: &str) -> bool {
+    !is_reconciling() && STATUS.read().unwrap().state.config_keys.iter().any(|k| k == key)
+}
+
+/// 视频源是否由声明式配置文件管理（对账过程中始终返回 false）
+pub fn is_source_managed(source_type: &str, id: i32) -> bool {
+    !is_reconciling()
+        && STATUS
+            .read()
+            .unwrap()
+            .state
+            .sources
+            .iter()
+            .any(|source| source.source_type == source_type && source.id == id)
+}
+
+/// 拒绝通过 API 修改由配置文件管理的配置项
+pub fn ensure_config_writable<'a>(keys: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    for key in keys {
+        if key == DECLARATIVE_STATE_KEY && !is_reconciling() {
+            return Err(InnerApiError::Forbidden(format!("配置项 {} 由程序内部维护", key)).into());
+        }
+        if is_config_key_managed(key) {
+            return Err(InnerApiError::Forbidden(format!(
+                "配置项 {} 由声明式配置文件 {} 管理，请修改配置文件",
+                key,
+                managed_by()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// 拒绝通过 API 修改或删除由配置文件管理的视频源
+pub fn ensure_source_writable(source_type: &str, id: i32) -> Result<()> {
+    if is_source_managed(source_type, id) {
+        return Err(InnerApiError::Forbidden(format!(
+            "视频源 {} {} 由声明式配置文件 {} 管理，请修改配置文件",
+            source_type,
+            id,
+            managed_by()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// 配置更新接口中的字段名对应的配置项
+pub fn config_key_of_field(field: &str) -> &str {
+    match field {
+        "serverchan_key"
+        | "enable_scan_notifications"
+        | "notification_min_videos"
+        | "notification_timeout"
+        | "notification_retry_count" => "notification",
+        _ => field.split('.').next().unwrap_or(field),
+    }
+}
+
+async fn disable_source(db: &DatabaseConnection, source: &ManagedSource) -> Result<()> {
+    macro_rules! disable {
+        ($entity:ident) => {
+            $entity::Entity::update_many()
+                .col_expr($entity::Column::Enabled, Expr::value(false))
+                .filter($entity::Column::Id.eq(source.id))
+                .exec(db)
+                .await?
+        };
+    }
+    match source.source_type.as_str() {
+        "favorite" => disable!(favorite),
+        "collection" => disable!(collection),
+        "submission" => disable!(submission),
+        "watch_later" => disable!(watch_later),
+        "bangumi" => disable!(video_source),
+        "ranking" => disable!(ranking),
+        other => bail!("不支持的视频源类型: {}", other),
+    };
+    Ok(())
+}
+
+async fn load_state(db: &DatabaseConnection) -> Result<ManagedState> {
+    let manager = crate::config::ConfigManager::new(db.clone());
+    Ok(match manager.get_config_item(DECLARATIVE_STATE_KEY).await? {
+        Some(value) => serde_json::from_value(value).context("解析声明式配置状态失败")?,
+        None => ManagedState::default(),
+    })
+}
+
+/// 将配置文件同步到数据库，返回导入结果与被禁用的视频源数量
+pub async fn reconcile(db: Arc<DatabaseConnection>, file: DeclarativeFile) -> Result<(ImportBundleResponse, usize)> {
+    RECONCILING
+        .scope((), async move {
+            let previous = load_state(&db).await?;
+            let declared_keys: Vec<String> = file.config.keys().cloned().collect();
+            let bundle = SourceBundle {
+                version: BUNDLE_VERSION,
+                app_version: String::new(),
+                exported_at: String::new(),
+                sources: file.sources.clone(),
+                config: file.config,
+            };
+            let response =
+                source_bundle::import_bundle(db.clone(), bundle, ConflictStrategy::Overwrite, true, false).await?;
+
+            let ignored: HashSet<&str> = response
+                .config
+                .iter()
+                .filter(|result| result.action == "ignored")
+                .map(|result| result.key.as_str())
+                .collect();
+            let mut state = ManagedState {
+                config_keys: declared_keys
+                    .into_iter()
+                    .filter(|key| !ignored.contains(key.as_str()))
+                    .collect(),
+                sources: Vec::new(),
+            };
+            for source in &file.sources {
+                if let Some((id, current)) = source_bundle::find_existing(&db, source).await? {
+                    state.sources.push(ManagedSource {
+                        source_type: source.source_type.clone(),
+                        id,
+                        name: current.name,
+                    });
+                }
+            }
+
+            let removed = previous.removed_sources(&state);
+            for source in &removed {
+                disable_source(&db, source).await?;
+                info!(
+                    "视频源 {} {}（{}）已从声明式配置文件中移除，已禁用",
+                    source.source_type, source.id, source.name
+                );
+                crate::events::publish(
+                    crate::events::EventKind::SourceDisabled,
+                    serde_json::json!({
+                        "source_type": source.source_type,
+                        "source_id": source.id,
+                        "name": source.name,
+                        "reason": "declarative_config",
+                    }),
+                );
+            }
+
+            crate::config::ConfigManager::new(db.as_ref().clone())
+                .update_config_item(DECLARATIVE_STATE_KEY, serde_json::to_value(&state)?)
+                .await?;
+            let disabled = removed.len();
+            STATUS.write().unwrap().state = state;
+            Ok((response, disabled))
+        })
+        .await
+}
+
+async fn reconcile_path(db: &Arc<DatabaseConnection>, path: &Path) -> Result<()> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("读取声明式配置文件 {} 失败", path.display()))?;
+    let file = DeclarativeFile::parse(path, &content)?;
+    let (response, disabled) = reconcile(db.clone(), file).await?;
+    for result in response.sources.iter().filter(|result| result.error.is_some()) {
+        warn!(
+            "声明式配置中的视频源 {} {} 同步失败: {}",
+            result.source_type,
+            result.source_id,
+            result.error.as_deref().unwrap_or_default()
+        );
+    }
+    info!(
+        "声明式配置已同步：新增 {} 个、更新 {} 个、禁用 {} 个视频源，失败 {} 个",
+        response.created, response.updated, disabled, response.failed
+    );
+    Ok(())
+}
+
+/// 启动时及配置文件变化时同步声明式配置，未指定配置文件时不做任何事
+pub async fn declarative_config_watcher(db: Arc<DatabaseConnection>) {
+    let Some(path) = crate::config::ARGS.config_file.clone() else {
+        return std::future::pending().await;
+    };
+    info!("启用声明式配置文件: {}", path.display());
+
+    // 先恢复上次的管理范围，保证文件暂时无法解析时受管理的字段仍为只读
+    let state = load_state(&db).await.unwrap_or_else(|e| {
+        warn!("{:#}", e);
+        ManagedState::default()
+    });
+    {
+        let mut status = STATUS.write().unwrap();
+        status.path = Some(path.clone());
+        status.state = state;
+    }
+
+    let mut last_modified: Option<SystemTime> = None;
+    loop {
+        match tokio::fs::metadata(&path).await.and_then(|meta| meta.modified()) {
+            Ok(modified) if last_modified != Some(modified) => {
+                if crate::task::is_scanning() {
+                    // 扫描期间添加的视频源会进入队列，等扫描结束后再同步
+                } else {
+                    last_modified = Some(modified);
+                    let result = reconcile_path(&db, &path).await;
+                    let mut status = STATUS.write().unwrap();
+                    status.last_reconciled_at = Some(now_standard_string());
+                    status.last_error = result.err().map(|e| {
+                        error!("同步声明式配置文件失败: {:#}", e);
+                        format!("{:#}", e)
+                    });
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let message = format!("无法读取声明式配置文件 {}: {}", path.display(), e);
+                let mut status = STATUS.write().unwrap();
+                if status.last_error.as_deref() != Some(message.as_str()) {
+                    warn!("{}", message);
+                    status.last_error = Some(message);
+                }
+            }
+        }
+        tokio::time::sleep(WATCH_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_yaml_and_toml() {
+        let yaml = "config:\n  interval: 1200\nsources:\n  - source_type: favorite\n    source_id: \"1\"\n    name: 收藏\n    path: /media/fav\n";
+        let file = DeclarativeFile::parse(Path::new("bili-sync.yaml"), yaml).unwrap();
+        assert_eq!(file.config["interval"], json!(1200));
+        assert_eq!(file.sources[0].source_id, "1");
+        assert!(file.sources[0].settings.enabled);
+
+        let toml = "[config]\ninterval = 1200\n\n[[sources]]\nsource_type = \"watch_later\"\nname = \"稍后再看\"\npath = \"/media/wl\"\n\n[sources.settings]\naudio_only = true\n";
+        let file = DeclarativeFile::parse(Path::new("bili-sync.TOML"), toml).unwrap();
+        assert_eq!(file.config["interval"], json!(1200));
+        assert!(file.sources[0].settings.audio_only);
+
+        assert!(DeclarativeFile::parse(Path::new("a.yaml"), "unknown: 1\n").is_err());
+        let duplicated = format!("{}{}", yaml, &yaml[yaml.find("  - ").unwrap()..]);
+        assert!(DeclarativeFile::parse(Path::new("a.yaml"), &duplicated).is_err());
+    }
+
+    #[test]
+    fn removed_sources_are_detected_by_type_and_id() {
+        let source = |source_type: &str, id| ManagedSource {
+            source_type: source_type.to_string(),
+            id,
+            name: String::new(),
+        };
+        let previous = ManagedState {
+            config_keys: Vec::new(),
+            sources: vec![source("favorite", 1), source("submission", 1), source("ranking", 2)],
+        };
+        let current = ManagedState {
+            config_keys: Vec::new(),
+            sources: vec![source("favorite", 1), source("ranking", 3)],
+        };
+        let removed: Vec<_> = previous.removed_sources(&current).into_iter().cloned().collect();
+        assert_eq!(removed, vec![source("submission", 1), source("ranking", 2)]);
+    }
+
+    #[test]
+    fn config_fields_map_to_stored_keys() {
+        assert_eq!(config_key_of_field("serverchan_key"), "notification");
+        assert_eq!(config_key_of_field("risk_control.mode"), "risk_control");
+        assert_eq!(config_key_of_field("interval"), "interval");
+    }
+}
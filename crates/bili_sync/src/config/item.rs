@@ -1,5 +1,8 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::bilibili::{Credential, FilterOption};
 
 use crate::utils::filenamify::filenamify;
 
@@ -375,6 +378,296 @@ impl DanmakuUpdatePolicy {
     }
 }
 
+/// 画质升级重下载策略：新投稿刚发布时往往只有 1080P，数小时后才出现 4K/HDR 等更高画质。
+/// 开启后，对发布时间在 `window_hours` 小时内、视频文件已下载完成的分页，
+/// 每隔 `check_interval_hours` 小时重新获取一次播放地址，发现更高画质/更优编码时重新下载并替换原文件。
+/// 待复查的分页进入独立队列在后台处理，最多同时运行 `max_concurrent` 个，不阻塞扫描。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct QualityUpgradePolicy {
+    pub enabled: bool,
+    pub window_hours: u32,
+    pub check_interval_hours: u32,
+    pub max_pages_per_run: u32,
+    pub max_concurrent: usize,
+}
+
+impl Default for QualityUpgradePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_hours: 72,
+            check_interval_hours: 6,
+            max_pages_per_run: 20,
+            max_concurrent: 1,
+        }
+    }
+}
+
+impl QualityUpgradePolicy {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.window_hours == 0 {
+            return Err("window_hours 必须大于 0");
+        }
+        if self.check_interval_hours == 0 {
+            return Err("check_interval_hours 必须大于 0");
+        }
+        if self.max_pages_per_run == 0 {
+            return Err("max_pages_per_run 必须大于 0");
+        }
+        if !(1..=4).contains(&self.max_concurrent) {
+            return Err("max_concurrent 需要在 1-4 之间");
+        }
+        Ok(())
+    }
+}
+
+/// 本地语音识别字幕：B站没有提供 CC/AI 字幕时，调用本地 whisper 兼容命令行（如 whisper.cpp 的 `whisper-cli`，仅使用 CPU）
+/// 识别已下载的音频，生成 `<文件名>.whisper-<language>.srt`（可选 `.vtt`），与下载的字幕使用相同的命名方式。
+/// 识别任务进入独立队列，最多同时运行 `max_concurrent` 个，避免占满 CPU 影响下载。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SubtitleGenerationOption {
+    pub enabled: bool,
+    /// whisper 兼容命令行的路径，需支持 `-m/-f/-l/-t/-osrt/-ovtt/-of` 参数
+    pub binary_path: String,
+    /// 模型文件路径
+    pub model_path: String,
+    /// 识别语言，`auto` 表示自动检测
+    pub language: String,
+    /// 每个识别任务使用的 CPU 线程数
+    pub threads: u32,
+    /// 同时运行的识别任务数
+    pub max_concurrent: usize,
+    /// 额外生成 WebVTT 字幕
+    pub output_vtt: bool,
+    /// 单个分页识别的超时时间（分钟）
+    pub timeout_minutes: u32,
+    /// 追加到命令行末尾的额外参数
+    pub extra_args: Vec<String>,
+}
+
+impl Default for SubtitleGenerationOption {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            binary_path: "whisper-cli".to_string(),
+            model_path: String::new(),
+            language: "zh".to_string(),
+            threads: 4,
+            max_concurrent: 1,
+            output_vtt: false,
+            timeout_minutes: 120,
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+impl SubtitleGenerationOption {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.binary_path.trim().is_empty() {
+            return Err("binary_path 不能为空");
+        }
+        if self.model_path.trim().is_empty() {
+            return Err("model_path 不能为空");
+        }
+        if self.language.trim().is_empty() {
+            return Err("language 不能为空");
+        }
+        if self.threads == 0 {
+            return Err("threads 必须大于 0");
+        }
+        if !(1..=8).contains(&self.max_concurrent) {
+            return Err("max_concurrent 需要在 1-8 之间");
+        }
+        if self.timeout_minutes == 0 {
+            return Err("timeout_minutes 必须大于 0");
+        }
+        Ok(())
+    }
+
+    /// 生成字幕在文件名中的语言标记，与B站字幕的 `<文件名>.<lan>.srt` 命名方式一致
+    pub fn subtitle_lan(&self) -> String {
+        format!("whisper-{}", self.language.trim())
+    }
+}
+
+/// 字幕翻译：使用 `ai_rename` 中配置的 AI 服务，把已下载的字幕（B站字幕、AI 字幕或本地识别字幕）
+/// 按批次逐条翻译，保留原有时间轴，写入 `<文件名>.<语言>.srt`。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SubtitleTranslationOption {
+    pub enabled: bool,
+    /// 目标语言代码，如 `en`、`ja`、`zh-Hant`，也用作字幕文件名中的语言标记
+    pub target_languages: Vec<String>,
+    /// 每次请求翻译的字幕条数
+    pub batch_size: usize,
+    /// 每轮扫描最多生成的译文字幕数
+    pub max_files_per_run: usize,
+}
+
+impl Default for SubtitleTranslationOption {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_languages: vec!["en".to_string()],
+            batch_size: 30,
+            max_files_per_run: 20,
+        }
+    }
+}
+
+impl SubtitleTranslationOption {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.target_languages.is_empty() {
+            return Err("target_languages 不能为空");
+        }
+        if self.target_languages.iter().any(|lang| {
+            lang.is_empty()
+                || !lang
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        }) {
+            return Err("target_languages 只能包含字母、数字、- 和 _");
+        }
+        if !(1..=200).contains(&self.batch_size) {
+            return Err("batch_size 需要在 1-200 之间");
+        }
+        if self.max_files_per_run == 0 {
+            return Err("max_files_per_run 必须大于 0");
+        }
+        Ok(())
+    }
+}
+
+/// AI 视频摘要：使用 `ai_rename` 中配置的 AI 服务，根据字幕（没有字幕时使用简介）生成简短摘要和标签，
+/// 保存到数据库，写入 NFO 的 `<plot>` / `<tag>`，并附在新视频推送中。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct VideoSummaryOption {
+    pub enabled: bool,
+    /// 摘要的最大字数
+    pub max_summary_chars: usize,
+    /// 最多生成的标签数
+    pub max_tags: usize,
+    /// 发送给 AI 的字幕/简介最大字数，超出部分截断
+    pub max_input_chars: usize,
+    /// 每轮扫描最多生成摘要的视频数
+    pub max_videos_per_run: usize,
+}
+
+impl Default for VideoSummaryOption {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_summary_chars: 200,
+            max_tags: 5,
+            max_input_chars: 6000,
+            max_videos_per_run: 20,
+        }
+    }
+}
+
+impl VideoSummaryOption {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(20..=2000).contains(&self.max_summary_chars) {
+            return Err("max_summary_chars 需要在 20-2000 之间");
+        }
+        if self.max_tags > 20 {
+            return Err("max_tags 不能超过 20");
+        }
+        if !(200..=50000).contains(&self.max_input_chars) {
+            return Err("max_input_chars 需要在 200-50000 之间");
+        }
+        if self.max_videos_per_run == 0 {
+            return Err("max_videos_per_run 必须大于 0");
+        }
+        Ok(())
+    }
+}
+
+/// 命名画质档案：可复用的流过滤配置（含回退阶梯），视频源可按名称引用，
+/// 例如 "archive"（4K HEVC HDR，否则最佳）或 "mobile"（720P AVC）
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct QualityProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub filter_option: FilterOption,
+}
+
+/// 校验画质档案列表：名称非空且不重复，各档案的筛选条件自洽
+pub fn validate_quality_profiles(profiles: &[QualityProfile]) -> Result<()> {
+    let mut names = std::collections::HashSet::new();
+    for profile in profiles {
+        let name = profile.name.trim();
+        if name.is_empty() {
+            bail!("画质档案名称不能为空");
+        }
+        if name != profile.name {
+            bail!("画质档案名称「{}」不能包含首尾空白", profile.name);
+        }
+        if !names.insert(name) {
+            bail!("画质档案名称「{}」重复", name);
+        }
+        profile
+            .filter_option
+            .validate()
+            .with_context(|| format!("画质档案「{}」无效", name))?;
+    }
+    Ok(())
+}
+
+/// 额外绑定的B站账号：默认账号仍保存在 `credential` 中，视频源通过 `credential_id` 选择账号
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BiliAccount {
+    pub id: i32,
+    /// 便于区分账号的备注名
+    #[serde(default)]
+    pub name: String,
+    pub credential: Credential,
+}
+
+impl BiliAccount {
+    /// 账号对应的B站用户ID（DedeUserID）
+    pub fn user_id(&self) -> Option<i64> {
+        self.credential.dedeuserid.parse::<i64>().ok()
+    }
+}
+
+/// 视频源 `filter_option` 列中保存的流过滤设置：引用命名画质档案，或直接保存自定义筛选条件
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum SourceFilterOption {
+    Profile { profile: String },
+    Custom(FilterOption),
+}
+
+impl SourceFilterOption {
+    pub fn from_json(value: &serde_json::Value) -> Result<Self> {
+        serde_json::from_value(value.clone()).context("视频源流过滤设置格式无效")
+    }
+
+    pub fn profile_name(&self) -> Option<&str> {
+        match self {
+            SourceFilterOption::Profile { profile } => Some(profile),
+            SourceFilterOption::Custom(_) => None,
+        }
+    }
+}
+
 fn default_large_submission_threshold() -> usize {
     80
 }
@@ -596,3 +889,41 @@ impl PathSafeTemplate for handlebars::Handlebars<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod quality_profile_tests {
+    use super::{validate_quality_profiles, QualityProfile, SourceFilterOption};
+    use crate::bilibili::{FilterOption, VideoQuality};
+
+    fn profile(name: &str) -> QualityProfile {
+        QualityProfile {
+            name: name.to_string(),
+            description: String::new(),
+            filter_option: FilterOption::default(),
+        }
+    }
+
+    #[test]
+    fn source_filter_option_distinguishes_profile_reference_and_custom_filter() {
+        let reference = SourceFilterOption::from_json(&serde_json::json!({"profile": "archive"}))
+            .expect("profile reference should parse");
+        assert_eq!(reference.profile_name(), Some("archive"));
+
+        let custom = SourceFilterOption::from_json(&serde_json::to_value(FilterOption::default()).unwrap())
+            .expect("custom filter should parse");
+        assert!(custom.profile_name().is_none());
+    }
+
+    #[test]
+    fn validate_quality_profiles_rejects_bad_names_and_filters() {
+        assert!(validate_quality_profiles(&[profile("archive"), profile("mobile")]).is_ok());
+        assert!(validate_quality_profiles(&[profile("archive"), profile("archive")]).is_err());
+        assert!(validate_quality_profiles(&[profile(" archive")]).is_err());
+        assert!(validate_quality_profiles(&[profile("")]).is_err());
+
+        let mut inverted = profile("inverted");
+        inverted.filter_option.video_max_quality = VideoQuality::Quality360p;
+        inverted.filter_option.video_min_quality = VideoQuality::Quality1080p;
+        assert!(validate_quality_profiles(&[inverted]).is_err());
+    }
+}
@@ -1,9 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::{anyhow, Context, Result};
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, Statement,
-};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde::Serialize;
 use serde_json::{Map, Value};
 use tracing::{debug, error, info, warn};
@@ -50,11 +48,23 @@ struct LegacyMigrationMeta {
 pub(crate) fn describe_config_key(key: &str) -> &'static str {
     match key {
         "auth_token" => "管理页访问密钥",
+        "stream_link_secret" => "视频分享链接签名密钥",
         "bind_address" => "服务监听地址",
         "credential" => "B站登录凭证",
+        "bili_accounts" => "B站多账号",
+        "bili_account_next_id" => "B站账号ID序列",
         "filter_option" => "画质与编码过滤",
         "danmaku_option" => "弹幕下载/样式设置",
+        "danmaku_filter" => "弹幕内容过滤",
+        "danmaku_archive" => "原始弹幕归档",
+        "danmaku_highlight" => "弹幕高能时刻",
+        "comment_archive" => "评论区归档",
         "danmaku_update_policy" => "弹幕增量更新策略",
+        "quality_upgrade_policy" => "画质升级重下载策略",
+        "subtitle_generation" => "本地语音识别字幕",
+        "subtitle_translation" => "字幕翻译",
+        "video_summary" => "AI 视频摘要",
+        "quality_profiles" => "画质档案",
         "video_name" => "视频命名模板",
         "page_name" => "分页命名模板",
         "multi_page_name" => "多P分页命名模板",
@@ -79,6 +89,7 @@ pub(crate) fn describe_config_key(key: &str) -> &'static str {
         "submission_risk_control" => "UP主投稿风控配置",
         "submission_scan_strategy" => "UP主投稿源扫描策略（分批/自适应）",
         "scan_deleted_videos" => "扫描已删除视频",
+        "cross_source_dedup" => "跨视频源去重（硬链接复用）",
         "enable_aria2_health_check" => "aria2健康检查",
         "enable_aria2_auto_restart" => "aria2自动重启",
         "aria2_health_check_interval" => "aria2健康检查间隔",
@@ -93,6 +104,8 @@ pub(crate) fn describe_config_key(key: &str) -> &'static str {
         "submission_default_path" => "投稿默认路径模板（旧版）",
         "version" => "旧版配置版本号",
         "risk_control" => "风控验证配置",
+        "backup" => "数据库备份配置",
+        "declarative_state" => "声明式配置文件管理范围",
         "ai_rename" => "AI重命名配置",
         _ => "未知/未定义",
     }
@@ -342,31 +355,44 @@ impl ConfigManager {
     pub async fn ensure_tables_exist(&self) -> Result<()> {
         debug!("检查配置表是否存在...");
 
+        use sea_orm::sea_query::{Alias, ColumnDef, Table};
+
+        let backend = self.db.get_database_backend();
+
         // 创建config_items表
-        let create_config_items = "
-            CREATE TABLE IF NOT EXISTS config_items (
-                key_name TEXT PRIMARY KEY NOT NULL,
-                value_json TEXT NOT NULL,
-                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
-            )";
+        let create_config_items = Table::create()
+            .table(Alias::new("config_items"))
+            .if_not_exists()
+            .col(ColumnDef::new(Alias::new("key_name")).string().not_null().primary_key())
+            .col(ColumnDef::new(Alias::new("value_json")).text().not_null())
+            .col(ColumnDef::new(Alias::new("updated_at")).string().not_null())
+            .to_owned();
 
         // 创建config_changes表
-        let create_config_changes = "
-            CREATE TABLE IF NOT EXISTS config_changes (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                key_name TEXT NOT NULL,
-                old_value TEXT,
-                new_value TEXT NOT NULL,
-                changed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
-            )";
+        let create_config_changes = Table::create()
+            .table(Alias::new("config_changes"))
+            .if_not_exists()
+            .col(
+                ColumnDef::new(Alias::new("id"))
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(Alias::new("key_name")).string().not_null())
+            .col(ColumnDef::new(Alias::new("old_value")).text())
+            .col(ColumnDef::new(Alias::new("new_value")).text().not_null())
+            .col(ColumnDef::new(Alias::new("changed_at")).string().not_null())
+            .col(ColumnDef::new(Alias::new("changed_by")).text())
+            .to_owned();
 
         // 执行SQL创建表
         self.db
-            .execute_unprepared(create_config_items)
+            .execute(backend.build(&create_config_items))
             .await
             .context("创建config_items表失败")?;
         self.db
-            .execute_unprepared(create_config_changes)
+            .execute(backend.build(&create_config_changes))
             .await
             .context("创建config_changes表失败")?;
 
@@ -501,16 +527,15 @@ impl ConfigManager {
     }
 
     async fn legacy_config_exists(&self) -> Result<bool> {
-        let backend = self.db.get_database_backend();
-        let exists_sql = "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='config'";
-        let exists_row = self.db.query_one(Statement::from_string(backend, exists_sql)).await?;
-        let exists: i64 = exists_row.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0);
-        if exists == 0 {
+        if !crate::database::dialect::current()
+            .table_exists(&self.db, "config")
+            .await?
+        {
             return Ok(false);
         }
 
         let count_sql = "SELECT COUNT(*) FROM config";
-        let count_row = self.db.query_one(Statement::from_string(backend, count_sql)).await?;
+        let count_row = self.db.query_one(crate::database::raw_statement(count_sql)).await?;
         let count: i64 = count_row.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0);
         Ok(count > 0)
     }
@@ -519,9 +544,8 @@ impl ConfigManager {
         if !self.legacy_config_exists().await? {
             return Ok(None);
         }
-        let backend = self.db.get_database_backend();
         let data_sql = "SELECT data FROM config LIMIT 1";
-        let data_row = self.db.query_one(Statement::from_string(backend, data_sql)).await?;
+        let data_row = self.db.query_one(crate::database::raw_statement(data_sql)).await?;
         let data: Option<String> = match data_row {
             Some(row) => row.try_get_by_index(0).ok(),
             None => None,
@@ -660,14 +684,21 @@ impl ConfigManager {
 
         let mut updates = Vec::new();
         if no_danmaku {
-            updates.push("download_danmaku = 0");
+            updates.push("download_danmaku = FALSE");
         }
         if no_subtitle {
-            updates.push("download_subtitle = 0");
+            updates.push("download_subtitle = FALSE");
         }
         let set_clause = updates.join(", ");
 
-        for table in ["collection", "favorite", "submission", "watch_later", "video_source"] {
+        for table in [
+            "collection",
+            "favorite",
+            "submission",
+            "watch_later",
+            "ranking",
+            "video_source",
+        ] {
             let sql = format!("UPDATE {} SET {}", table, set_clause);
             if let Err(e) = self.db.execute_unprepared(&sql).await {
                 warn!("更新表 {} 的下载开关失败: {}", table, e);
@@ -766,6 +797,8 @@ impl ConfigManager {
             warn!("拒绝写入嵌套的notification字段: {}，请使用完整的notification对象", key);
             return Ok(()); // 静默忽略，不返回错误
         }
+        // 由声明式配置文件管理的配置项只能通过修改文件变更
+        crate::config::ensure_config_writable([key])?;
 
         let value_json = serde_json::to_string(&value)?;
 
@@ -835,16 +868,17 @@ impl ConfigManager {
 
     /// 记录配置变更历史 (使用原生SQL)
     async fn record_config_change(&self, key: &str, old_value: Option<&str>, new_value: &str) -> Result<()> {
-        let sql = "INSERT INTO config_changes (key_name, old_value, new_value, changed_at) VALUES (?, ?, ?, ?)";
+        let sql =
+            "INSERT INTO config_changes (key_name, old_value, new_value, changed_at, changed_by) VALUES (?, ?, ?, ?, ?)";
 
-        let stmt = sea_orm::Statement::from_sql_and_values(
-            sea_orm::DatabaseBackend::Sqlite,
+        let stmt = crate::database::statement(
             sql,
             vec![
                 key.into(),
                 old_value.into(),
                 new_value.into(),
                 now_standard_string().into(),
+                crate::auth::users::current_actor().into(),
             ],
         );
 
@@ -852,7 +886,7 @@ impl ConfigManager {
 
         // 记录当前config_changes表的记录数，用于监控
         let count_sql = "SELECT COUNT(*) as count FROM config_changes";
-        let count_stmt = sea_orm::Statement::from_string(sea_orm::DatabaseBackend::Sqlite, count_sql);
+        let count_stmt = crate::database::raw_statement(count_sql);
         let count_result = self.db.query_one(count_stmt).await?;
 
         if let Some(row) = count_result {
@@ -885,7 +919,8 @@ impl ConfigManager {
         key: Option<&str>,
         limit: Option<u64>,
     ) -> Result<Vec<config_item::ConfigChangeModel>> {
-        let mut sql = "SELECT id, key_name, old_value, new_value, changed_at FROM config_changes".to_string();
+        let mut sql =
+            "SELECT id, key_name, old_value, new_value, changed_at, changed_by FROM config_changes".to_string();
         let mut values = Vec::new();
 
         if let Some(key) = key {
@@ -900,7 +935,7 @@ impl ConfigManager {
             values.push(limit.into());
         }
 
-        let stmt = sea_orm::Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, &sql, values);
+        let stmt = crate::database::statement(&sql, values);
 
         let query_result = self.db.query_all(stmt).await?;
 
@@ -912,6 +947,7 @@ impl ConfigManager {
                 old_value: row.try_get::<Option<String>>("", "old_value")?,
                 new_value: row.try_get::<String>("", "new_value")?,
                 changed_at: row.try_get::<String>("", "changed_at")?,
+                changed_by: row.try_get::<Option<String>>("", "changed_by")?,
             };
             changes.push(change);
         }
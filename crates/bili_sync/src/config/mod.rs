@@ -7,21 +7,30 @@ use serde::{Deserialize, Serialize};
 
 mod bundle;
 mod clap;
+mod declarative;
 mod global;
 mod item;
 mod manager;
 
-use crate::bilibili::{Credential, DanmakuOption, FilterOption};
+use crate::bilibili::{
+    CommentArchiveOption, Credential, DanmakuArchiveOption, DanmakuFilterOption, DanmakuHighlightOption, DanmakuOption,
+    FilterOption,
+};
 pub use crate::config::bundle::ConfigBundle;
 pub use crate::config::clap::version;
+pub use crate::config::declarative::{
+    config_key_of_field, declarative_config_watcher, declarative_status, ensure_config_writable,
+    ensure_source_writable, is_config_key_managed, is_source_managed,
+};
 pub use crate::config::global::{
     get_config_manager, init_config_with_database, reload_config, reload_config_bundle, with_config, ARGS,
     CONFIG_BUNDLE, CONFIG_DIR,
 };
 use crate::config::item::ConcurrentLimit;
 pub use crate::config::item::{
-    DanmakuUpdatePolicy, EmptyUpperStrategy, NFOConfig, NFOTimeType, PathSafeTemplate, RateLimit,
-    SubmissionRiskControlConfig, SubmissionScanStrategyConfig,
+    validate_quality_profiles, BiliAccount, DanmakuUpdatePolicy, EmptyUpperStrategy, NFOConfig, NFOTimeType,
+    PathSafeTemplate, QualityProfile, QualityUpgradePolicy, RateLimit, SourceFilterOption, SubmissionRiskControlConfig,
+    SubmissionScanStrategyConfig, SubtitleGenerationOption, SubtitleTranslationOption, VideoSummaryOption,
 };
 pub(crate) use crate::config::manager::describe_config_key;
 pub use crate::config::manager::ConfigManager;
@@ -162,16 +171,47 @@ fn default_collection_folder_mode() -> Cow<'static, str> {
 pub struct Config {
     #[serde(default = "default_auth_token")]
     pub auth_token: Option<String>,
+    /// 视频分享链接的 HMAC 签名密钥，首次生成分享链接时自动创建，更换后所有已分享链接失效
+    #[serde(default)]
+    pub stream_link_secret: String,
     #[serde(default = "default_bind_address")]
     pub bind_address: String,
     #[serde(default)]
     pub credential: ArcSwapOption<Credential>,
+    /// 额外的B站账号，视频源可通过 `credential_id` 绑定
+    #[serde(default)]
+    pub bili_accounts: Vec<BiliAccount>,
+    /// 下一个新增账号使用的ID，只增不减，删除账号后其ID不会被复用
+    #[serde(default)]
+    pub bili_account_next_id: i32,
     #[serde(default)]
     pub filter_option: FilterOption,
     #[serde(default)]
     pub danmaku_option: DanmakuOption,
+    /// 弹幕内容过滤（屏蔽词、合并、密度限制），可按视频源单独设置
+    #[serde(default)]
+    pub danmaku_filter: DanmakuFilterOption,
+    /// 在 ASS 旁归档原始弹幕（XML / JSON Lines）
+    #[serde(default)]
+    pub danmaku_archive: DanmakuArchiveOption,
+    /// 弹幕密度统计与高能章节
+    #[serde(default)]
+    pub danmaku_highlight: DanmakuHighlightOption,
     #[serde(default)]
     pub danmaku_update_policy: DanmakuUpdatePolicy,
+    /// 评论区归档数量，按视频源开关启用，刷新节奏沿用弹幕增量更新策略
+    #[serde(default)]
+    pub comment_archive: CommentArchiveOption,
+    #[serde(default)]
+    pub quality_upgrade_policy: QualityUpgradePolicy,
+    #[serde(default)]
+    pub quality_profiles: Vec<QualityProfile>,
+    #[serde(default)]
+    pub subtitle_generation: SubtitleGenerationOption,
+    #[serde(default)]
+    pub subtitle_translation: SubtitleTranslationOption,
+    #[serde(default)]
+    pub video_summary: VideoSummaryOption,
     #[serde(default = "default_video_name")]
     pub video_name: Cow<'static, str>,
     #[serde(default = "default_page_name")]
@@ -222,6 +262,9 @@ pub struct Config {
     pub submission_scan_strategy: SubmissionScanStrategyConfig,
     #[serde(default)]
     pub scan_deleted_videos: bool,
+    /// 跨视频源去重：同一分页已在其它视频源下载过相同画质时，以硬链接方式复用而非重新下载（默认关闭）
+    #[serde(default = "default_cross_source_dedup")]
+    pub cross_source_dedup: bool,
     // 番剧预告片过滤配置
     #[serde(default = "default_skip_bangumi_preview")]
     pub skip_bangumi_preview: bool,
@@ -256,6 +299,9 @@ pub struct Config {
     // 风控验证配置
     #[serde(default)]
     pub risk_control: RiskControlConfig,
+    // 数据库定时备份配置
+    #[serde(default)]
+    pub backup: BackupConfig,
 
     /// AI 自动重命名配置（OpenAI 兼容接口）
     #[serde(default)]
@@ -266,6 +312,10 @@ fn default_skip_bangumi_preview() -> bool {
     true // 默认跳过预告片
 }
 
+fn default_cross_source_dedup() -> bool {
+    false // 默认关闭跨源去重，避免升级后各视频源的文件变成彼此的硬链接
+}
+
 fn default_aria2_health_check_interval() -> u64 {
     300 // 默认5分钟
 }
@@ -619,6 +669,58 @@ impl RiskControlConfig {
     }
 }
 
+// 数据库备份配置结构体
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupConfig {
+    /// 是否启用定时备份
+    #[serde(default = "default_backup_enabled")]
+    pub enabled: bool,
+    /// 定时备份间隔（小时）
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u64,
+    /// 保留的备份数量，超出后删除最旧的备份
+    #[serde(default = "default_backup_keep")]
+    pub keep: usize,
+    /// 备份目录，未设置时使用配置目录下的 backups
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+fn default_backup_enabled() -> bool {
+    true // 默认启用，避免升级或磁盘故障时丢失订阅
+}
+
+fn default_backup_interval_hours() -> u64 {
+    24 // 默认每天备份一次
+}
+
+fn default_backup_keep() -> usize {
+    7 // 默认保留最近7份
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_backup_enabled(),
+            interval_hours: default_backup_interval_hours(),
+            keep: default_backup_keep(),
+            dir: None,
+        }
+    }
+}
+
+impl BackupConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_hours == 0 || self.interval_hours > 24 * 30 {
+            return Err("备份间隔必须在1-720小时之间".to_string());
+        }
+        if self.keep == 0 || self.keep > 100 {
+            return Err("备份保留数量必须在1-100之间".to_string());
+        }
+        Ok(())
+    }
+}
+
 impl AutoSolveConfig {
     pub fn validate(&self) -> Result<(), String> {
         if !matches!(self.service.as_str(), "2captcha" | "anticaptcha") {
@@ -645,8 +747,11 @@ impl Clone for Config {
     fn clone(&self) -> Self {
         Self {
             auth_token: self.auth_token.clone(),
+            stream_link_secret: self.stream_link_secret.clone(),
             bind_address: self.bind_address.clone(),
             credential: ArcSwapOption::from(self.credential.load_full()),
+            bili_accounts: self.bili_accounts.clone(),
+            bili_account_next_id: self.bili_account_next_id,
             filter_option: FilterOption {
                 video_max_quality: self.filter_option.video_max_quality,
                 video_min_quality: self.filter_option.video_min_quality,
@@ -657,6 +762,7 @@ impl Clone for Config {
                 no_dolby_audio: self.filter_option.no_dolby_audio,
                 no_hdr: self.filter_option.no_hdr,
                 no_hires: self.filter_option.no_hires,
+                fallback_rules: self.filter_option.fallback_rules.clone(),
             },
             danmaku_option: DanmakuOption {
                 duration: self.danmaku_option.duration,
@@ -671,8 +777,18 @@ impl Clone for Config {
                 bold: self.danmaku_option.bold,
                 outline: self.danmaku_option.outline,
                 time_offset: self.danmaku_option.time_offset,
+                font_path: self.danmaku_option.font_path.clone(),
             },
+            danmaku_filter: self.danmaku_filter.clone(),
+            danmaku_archive: self.danmaku_archive.clone(),
+            danmaku_highlight: self.danmaku_highlight.clone(),
+            comment_archive: self.comment_archive.clone(),
             danmaku_update_policy: self.danmaku_update_policy.clone(),
+            quality_upgrade_policy: self.quality_upgrade_policy.clone(),
+            subtitle_generation: self.subtitle_generation.clone(),
+            subtitle_translation: self.subtitle_translation.clone(),
+            video_summary: self.video_summary.clone(),
+            quality_profiles: self.quality_profiles.clone(),
             video_name: self.video_name.clone(),
             page_name: self.page_name.clone(),
             multi_page_name: self.multi_page_name.clone(),
@@ -697,6 +813,7 @@ impl Clone for Config {
             submission_risk_control: self.submission_risk_control.clone(),
             submission_scan_strategy: self.submission_scan_strategy.clone(),
             scan_deleted_videos: self.scan_deleted_videos,
+            cross_source_dedup: self.cross_source_dedup,
             skip_bangumi_preview: self.skip_bangumi_preview,
             enable_aria2_health_check: self.enable_aria2_health_check,
             enable_aria2_auto_restart: self.enable_aria2_auto_restart,
@@ -709,6 +826,7 @@ impl Clone for Config {
             enable_startup_data_fix: self.enable_startup_data_fix,
             enable_cid_population: self.enable_cid_population,
             risk_control: self.risk_control.clone(),
+            backup: self.backup.clone(),
             ai_rename: self.ai_rename.clone(),
         }
     }
@@ -718,11 +836,23 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             auth_token: None,
+            stream_link_secret: String::new(),
             bind_address: default_bind_address(),
             credential: ArcSwapOption::from(Some(Arc::new(Credential::default()))),
+            bili_accounts: Vec::new(),
+            bili_account_next_id: 0,
             filter_option: FilterOption::default(),
             danmaku_option: DanmakuOption::default(),
+            danmaku_filter: DanmakuFilterOption::default(),
+            danmaku_archive: DanmakuArchiveOption::default(),
+            danmaku_highlight: DanmakuHighlightOption::default(),
+            comment_archive: CommentArchiveOption::default(),
             danmaku_update_policy: DanmakuUpdatePolicy::default(),
+            quality_upgrade_policy: QualityUpgradePolicy::default(),
+            subtitle_generation: SubtitleGenerationOption::default(),
+            subtitle_translation: SubtitleTranslationOption::default(),
+            video_summary: VideoSummaryOption::default(),
+            quality_profiles: Vec::new(),
             video_name: Cow::Borrowed("{{upper_name}}/{{title}}"),
             page_name: Cow::Borrowed("{{pubtime}}-{{bvid}}"),
             multi_page_name: Cow::Borrowed("P{{pid_pad}}.{{ptitle}}"),
@@ -747,6 +877,7 @@ impl Default for Config {
             submission_risk_control: crate::config::item::SubmissionRiskControlConfig::default(),
             submission_scan_strategy: SubmissionScanStrategyConfig::default(),
             scan_deleted_videos: false,
+            cross_source_dedup: default_cross_source_dedup(),
             skip_bangumi_preview: default_skip_bangumi_preview(),
             enable_aria2_health_check: false,
             enable_aria2_auto_restart: false,
@@ -759,12 +890,56 @@ impl Default for Config {
             enable_startup_data_fix: false, // 默认关闭，减少不必要的日志
             enable_cid_population: false,   // 默认关闭，减少不必要的日志
             risk_control: RiskControlConfig::default(),
+            backup: BackupConfig::default(),
             ai_rename: crate::utils::ai_rename::AiRenameConfig::default(),
         }
     }
 }
 
 impl Config {
+    /// 按视频源绑定的账号选择凭据：未绑定时使用默认账号，绑定的账号不存在时回退为默认账号
+    pub fn credential_for(&self, credential_id: Option<i32>) -> Option<Arc<Credential>> {
+        let Some(id) = credential_id else {
+            return self.credential.load_full();
+        };
+        match self.bili_account(id) {
+            Some(account) => Some(Arc::new(account.credential.clone())),
+            None => {
+                warn!("B站账号 {} 不存在，回退为默认账号", id);
+                self.credential.load_full()
+            }
+        }
+    }
+
+    pub fn bili_account(&self, id: i32) -> Option<&BiliAccount> {
+        self.bili_accounts.iter().find(|account| account.id == id)
+    }
+
+    /// 为新增账号分配ID：取序列值与现有最大ID+1中的较大者，兼容序列字段出现之前保存的账号
+    pub fn next_bili_account_id(&self) -> i32 {
+        let after_existing = self.bili_accounts.iter().map(|account| account.id).max().unwrap_or(0) + 1;
+        self.bili_account_next_id.max(after_existing)
+    }
+
+    /// 解析视频源保存的流过滤设置：引用画质档案时展开为档案配置，未设置时使用全局配置
+    pub fn resolve_source_filter_option(&self, value: Option<&serde_json::Value>) -> anyhow::Result<FilterOption> {
+        let Some(value) = value else {
+            return Ok(self.filter_option.clone());
+        };
+        match SourceFilterOption::from_json(value)? {
+            SourceFilterOption::Custom(filter_option) => Ok(filter_option),
+            SourceFilterOption::Profile { profile } => {
+                match self.quality_profiles.iter().find(|item| item.name == profile) {
+                    Some(item) => Ok(item.filter_option.clone()),
+                    None => {
+                        warn!("画质档案「{}」不存在，回退为全局流过滤配置", profile);
+                        Ok(self.filter_option.clone())
+                    }
+                }
+            }
+        }
+    }
+
     #[cfg(not(test))]
     pub fn check(&self) -> bool {
         let mut ok = true;
@@ -848,11 +1023,51 @@ impl Config {
             error!("video 和 page 允许的并发数必须大于 0");
         }
 
+        if let Err(err) = self.danmaku_filter.validate() {
+            ok = false;
+            error!("弹幕过滤配置无效：{:#}", err);
+        }
+
+        if let Err(err) = self.danmaku_highlight.validate() {
+            ok = false;
+            error!("弹幕高能时刻配置无效：{:#}", err);
+        }
+
+        if let Err(err) = self.comment_archive.validate() {
+            ok = false;
+            error!("评论区归档配置无效：{:#}", err);
+        }
+
         if let Err(err) = self.danmaku_update_policy.validate() {
             ok = false;
             error!("弹幕增量更新策略无效：{}", err);
         }
 
+        if let Err(err) = self.quality_upgrade_policy.validate() {
+            ok = false;
+            error!("画质升级重下载策略无效：{}", err);
+        }
+
+        if let Err(err) = self.subtitle_generation.validate() {
+            ok = false;
+            error!("本地语音识别字幕配置无效：{}", err);
+        }
+
+        if let Err(err) = self.subtitle_translation.validate() {
+            ok = false;
+            error!("字幕翻译配置无效：{}", err);
+        }
+
+        if let Err(err) = self.video_summary.validate() {
+            ok = false;
+            error!("AI 视频摘要配置无效：{}", err);
+        }
+
+        if let Err(err) = validate_quality_profiles(&self.quality_profiles) {
+            ok = false;
+            error!("画质档案配置无效：{:#}", err);
+        }
+
         if critical_error {
             warn!("配置中检测到凭证未设置，程序将继续运行但功能受限");
             warn!("请通过Web管理界面添加B站登录凭证以启用完整功能");
@@ -864,6 +1079,61 @@ impl Config {
             warn!("通知配置无效：{}", e);
         }
 
+        if let Err(e) = self.backup.validate() {
+            ok = false;
+            warn!("数据库备份配置无效：{}", e);
+        }
+
         ok
     }
 }
+
+#[cfg(test)]
+mod bili_account_tests {
+    use super::{BiliAccount, Config};
+    use crate::bilibili::Credential;
+
+    fn credential(dedeuserid: &str) -> Credential {
+        Credential {
+            dedeuserid: dedeuserid.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn credential_for_selects_bound_account_and_falls_back_to_default() {
+        let config = Config {
+            bili_accounts: vec![BiliAccount {
+                id: 2,
+                name: "colleague".to_string(),
+                credential: credential("200"),
+            }],
+            ..Default::default()
+        };
+        config.credential.store(Some(std::sync::Arc::new(credential("100"))));
+
+        let dedeuserid = |id| config.credential_for(id).map(|cred| cred.dedeuserid.clone());
+        assert_eq!(dedeuserid(None).as_deref(), Some("100"));
+        assert_eq!(dedeuserid(Some(2)).as_deref(), Some("200"));
+        assert_eq!(dedeuserid(Some(3)).as_deref(), Some("100"));
+    }
+
+    #[test]
+    fn next_bili_account_id_never_reuses_deleted_ids() {
+        let mut config = Config {
+            bili_accounts: vec![BiliAccount {
+                id: 2,
+                name: String::new(),
+                credential: credential("200"),
+            }],
+            ..Default::default()
+        };
+        // 序列字段出现之前保存的账号：从现有最大ID之后继续分配
+        assert_eq!(config.next_bili_account_id(), 3);
+
+        // 删除最新的账号后，序列仍指向其后的ID
+        config.bili_account_next_id = 4;
+        config.bili_accounts.clear();
+        assert_eq!(config.next_bili_account_id(), 4);
+    }
+}
@@ -0,0 +1,407 @@
+//! SQLite 数据库在线备份与恢复
+//!
+//! 备份使用 `VACUUM INTO` 在不阻塞写入的情况下生成一致性快照；
+//! 恢复不会直接替换正在使用的数据库文件，而是校验后暂存为 `data.sqlite.restore`，
+//! 在下次启动、建立连接池之前完成替换，再由迁移把旧备份升级到当前版本。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bili_sync_migration::{Migrator, MigratorTrait};
+use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sea_orm::sqlx::{self, Row};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend};
+use tracing::{debug, info, warn};
+
+use super::{database_path, dialect};
+use crate::config::CONFIG_DIR;
+use crate::utils::time_format::{beijing_now, timestamp_to_beijing_string};
+
+const BACKUP_PREFIX: &str = "data-";
+const BACKUP_SUFFIX: &str = ".sqlite";
+/// 定时备份任务检查间隔
+const SCHEDULER_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 备份触发方式，写入文件名便于区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupKind {
+    Scheduled,
+    Manual,
+    PreRestore,
+}
+
+impl BackupKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            BackupKind::Scheduled => "auto",
+            BackupKind::Manual => "manual",
+            BackupKind::PreRestore => "pre-restore",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    pub name: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+/// 备份文件中记录的迁移版本
+#[derive(Debug, Clone)]
+pub struct BackupSchema {
+    /// 备份中最新的迁移版本
+    pub latest_migration: String,
+    /// 恢复后启动时需要补齐的迁移数量
+    pub pending_migrations: usize,
+}
+
+/// 内置备份只支持 SQLite，外部数据库请使用 pg_dump / mysqldump
+pub fn ensure_supported() -> Result<()> {
+    match dialect::current_backend() {
+        DbBackend::Sqlite => Ok(()),
+        backend => bail!("当前使用外部数据库（{:?}），请使用数据库自带的工具进行备份", backend),
+    }
+}
+
+pub fn backup_dir() -> PathBuf {
+    match crate::config::reload_config().backup.dir {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir.trim()),
+        _ => CONFIG_DIR.join("backups"),
+    }
+}
+
+/// 只接受由本模块生成的文件名，避免通过接口访问任意路径
+pub fn is_backup_file_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX)
+        && name.ends_with(BACKUP_SUFFIX)
+        && name.len() > BACKUP_PREFIX.len() + BACKUP_SUFFIX.len()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.contains("..")
+}
+
+fn pending_restore_path() -> PathBuf {
+    let mut path = database_path().into_os_string();
+    path.push(".restore");
+    PathBuf::from(path)
+}
+
+/// 是否已有等待下次启动时应用的恢复
+pub fn restore_pending() -> bool {
+    pending_restore_path().exists()
+}
+
+fn backup_file_info(path: &Path) -> Option<BackupFile> {
+    let name = path.file_name()?.to_str()?;
+    if !is_backup_file_name(name) {
+        return None;
+    }
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default();
+    Some(BackupFile {
+        name: name.to_string(),
+        size: metadata.len(),
+        created_at: timestamp_to_beijing_string(modified),
+    })
+}
+
+/// 列出目录中的备份，按时间从新到旧排列
+pub fn list_backups_in(dir: &Path) -> Result<Vec<BackupFile>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<BackupFile> = std::fs::read_dir(dir)
+        .with_context(|| format!("读取备份目录 {} 失败", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| backup_file_info(&entry.path()))
+        .collect();
+    // 文件名以时间戳开头，按名称倒序即为从新到旧
+    backups.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(backups)
+}
+
+pub fn list_backups() -> Result<Vec<BackupFile>> {
+    list_backups_in(&backup_dir())
+}
+
+/// 仅保留最新的 `keep` 份备份，返回被删除的文件名
+pub fn rotate_backups_in(dir: &Path, keep: usize) -> Result<Vec<String>> {
+    let mut removed = Vec::new();
+    for backup in list_backups_in(dir)?.into_iter().skip(keep.max(1)) {
+        std::fs::remove_file(dir.join(&backup.name)).with_context(|| format!("删除过期备份 {} 失败", backup.name))?;
+        removed.push(backup.name);
+    }
+    Ok(removed)
+}
+
+/// 生成一份一致性快照并按配置轮转旧备份
+pub async fn create_backup(db: &DatabaseConnection, kind: BackupKind) -> Result<BackupFile> {
+    ensure_supported()?;
+    let dir = backup_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("创建备份目录 {} 失败", dir.display()))?;
+
+    let name = format!(
+        "{}{}-{}{}",
+        BACKUP_PREFIX,
+        beijing_now().format("%Y%m%d-%H%M%S"),
+        kind.as_str(),
+        BACKUP_SUFFIX
+    );
+    let path = dir.join(&name);
+    if path.exists() {
+        bail!("备份 {} 已存在，请稍后再试", name);
+    }
+
+    // VACUUM INTO 在读事务中完成，不会阻塞其它连接的写入
+    let target = path.to_string_lossy().replace('\'', "''");
+    db.execute_unprepared(&format!("VACUUM INTO '{}'", target))
+        .await
+        .with_context(|| format!("写入备份 {} 失败", path.display()))?;
+
+    let backup = backup_file_info(&path).ok_or_else(|| anyhow!("备份 {} 写入后无法读取", name))?;
+    info!("数据库备份完成: {}（{} 字节）", backup.name, backup.size);
+
+    let keep = crate::config::reload_config().backup.keep;
+    match rotate_backups_in(&dir, keep) {
+        Ok(removed) if !removed.is_empty() => info!("已轮转删除 {} 份旧备份: {}", removed.len(), removed.join(", ")),
+        Ok(_) => {}
+        Err(e) => warn!("轮转旧备份失败: {:#}", e),
+    }
+    Ok(backup)
+}
+
+/// 校验备份完整性及迁移版本，拒绝来自更新版本程序的备份
+pub async fn inspect_backup(path: &Path) -> Result<BackupSchema> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .with_context(|| format!("打开备份 {} 失败", path.display()))?;
+
+    let result = async {
+        let check: String = sqlx::query("PRAGMA quick_check").fetch_one(&pool).await?.try_get(0)?;
+        if check != "ok" {
+            bail!("备份完整性检查失败: {}", check);
+        }
+        let versions: Vec<String> = sqlx::query("SELECT version FROM seaql_migrations ORDER BY version")
+            .fetch_all(&pool)
+            .await
+            .context("备份中没有迁移记录，不是有效的 bili-sync 数据库")?
+            .iter()
+            .map(|row| row.try_get::<String, _>(0))
+            .collect::<Result<_, _>>()?;
+        check_migration_versions(&versions)
+    }
+    .await;
+
+    pool.close().await;
+    result
+}
+
+fn check_migration_versions(versions: &[String]) -> Result<BackupSchema> {
+    let known: Vec<String> = Migrator::migrations().iter().map(|m| m.name().to_string()).collect();
+    if let Some(unknown) = versions.iter().find(|version| !known.contains(version)) {
+        bail!("备份包含当前程序未知的迁移 {}，请先升级程序后再恢复", unknown);
+    }
+    let latest_migration = versions
+        .iter()
+        .max()
+        .cloned()
+        .ok_or_else(|| anyhow!("备份中没有迁移记录，不是有效的 bili-sync 数据库"))?;
+    Ok(BackupSchema {
+        latest_migration,
+        pending_migrations: known.len() - versions.len(),
+    })
+}
+
+/// 校验备份并暂存，下次启动时替换当前数据库
+///
+/// 同时对当前数据库做一次 pre-restore 备份，恢复出错时可以再恢复回来
+pub async fn stage_restore(db: &DatabaseConnection, name: &str) -> Result<BackupSchema> {
+    ensure_supported()?;
+    if !is_backup_file_name(name) {
+        bail!("无效的备份文件名: {}", name);
+    }
+    let path = backup_dir().join(name);
+    if !path.is_file() {
+        bail!("备份 {} 不存在", name);
+    }
+    let schema = inspect_backup(&path).await?;
+
+    // 先暂存再做 pre-restore 备份，避免轮转时删掉要恢复的备份
+    let staged = pending_restore_path();
+    tokio::fs::copy(&path, &staged)
+        .await
+        .with_context(|| format!("暂存备份到 {} 失败", staged.display()))?;
+    if let Err(e) = create_backup(db, BackupKind::PreRestore).await {
+        let _ = tokio::fs::remove_file(&staged).await;
+        return Err(e.context("恢复前备份当前数据库失败，已取消恢复"));
+    }
+    info!(
+        "备份 {} 已通过校验（迁移版本 {}），将在重启后恢复",
+        name, schema.latest_migration
+    );
+    Ok(schema)
+}
+
+/// 启动时、打开连接池之前应用暂存的恢复
+pub(super) async fn apply_pending_restore() -> Result<()> {
+    let staged = pending_restore_path();
+    if !staged.exists() {
+        return Ok(());
+    }
+    // 暂存后程序可能已被降级，替换前再校验一次
+    if let Err(e) = inspect_backup(&staged).await {
+        let rejected = staged.with_extension("restore.rejected");
+        let _ = tokio::fs::rename(&staged, &rejected).await;
+        return Err(e.context(format!("暂存的备份未通过校验，已移至 {}", rejected.display())));
+    }
+
+    let db_path = database_path();
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.clone().into_os_string();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            tokio::fs::remove_file(&sidecar)
+                .await
+                .with_context(|| format!("删除 {} 失败", sidecar.display()))?;
+        }
+    }
+    tokio::fs::rename(&staged, &db_path)
+        .await
+        .with_context(|| format!("替换数据库文件 {} 失败", db_path.display()))?;
+    info!("已从备份恢复数据库: {}", db_path.display());
+    Ok(())
+}
+
+pub fn delete_backup(name: &str) -> Result<()> {
+    if !is_backup_file_name(name) {
+        bail!("无效的备份文件名: {}", name);
+    }
+    let path = backup_dir().join(name);
+    if !path.is_file() {
+        bail!("备份 {} 不存在", name);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("删除备份 {} 失败", name))?;
+    info!("已删除数据库备份 {}", name);
+    Ok(())
+}
+
+fn latest_backup_age(dir: &Path) -> Option<Duration> {
+    let latest = list_backups_in(dir).ok()?.into_iter().next()?;
+    std::fs::metadata(dir.join(latest.name))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+}
+
+/// 定时备份任务，外部数据库下不做任何事
+pub async fn backup_scheduler(db: Arc<DatabaseConnection>) {
+    if ensure_supported().is_err() {
+        debug!("使用外部数据库，跳过内置定时备份");
+        return std::future::pending().await;
+    }
+    loop {
+        let config = crate::config::reload_config().backup;
+        if config.enabled {
+            let interval = Duration::from_secs(config.interval_hours.max(1) * 3600);
+            let due = latest_backup_age(&backup_dir()).is_none_or(|age| age >= interval);
+            if due {
+                if let Err(e) = create_backup(&db, BackupKind::Scheduled).await {
+                    warn!("定时备份数据库失败: {:#}", e);
+                }
+            }
+        }
+        tokio::time::sleep(SCHEDULER_CHECK_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_backup_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bili-sync-backup-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn backup_file_names_reject_paths() {
+        assert!(is_backup_file_name("data-20261019-120000-auto.sqlite"));
+        assert!(!is_backup_file_name("data-.sqlite"));
+        assert!(!is_backup_file_name("../data.sqlite"));
+        assert!(!is_backup_file_name("data-../../etc.sqlite"));
+        assert!(!is_backup_file_name("data-20261019/x.sqlite"));
+        assert!(!is_backup_file_name("other-20261019.sqlite"));
+    }
+
+    #[test]
+    fn rotation_keeps_newest_backups() {
+        let dir = temp_backup_dir();
+        for name in [
+            "data-20261017-000000-auto.sqlite",
+            "data-20261018-000000-manual.sqlite",
+            "data-20261019-000000-auto.sqlite",
+            "unrelated.txt",
+        ] {
+            std::fs::write(dir.join(name), b"x").unwrap();
+        }
+
+        let removed = rotate_backups_in(&dir, 2).unwrap();
+        assert_eq!(removed, vec!["data-20261017-000000-auto.sqlite".to_string()]);
+        let names: Vec<String> = list_backups_in(&dir).unwrap().into_iter().map(|b| b.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "data-20261019-000000-auto.sqlite".to_string(),
+                "data-20261018-000000-manual.sqlite".to_string()
+            ]
+        );
+        assert!(dir.join("unrelated.txt").exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn backup_config_bounds() {
+        let mut config = crate::config::BackupConfig::default();
+        assert!(config.validate().is_ok());
+        config.keep = 0;
+        assert!(config.validate().is_err());
+        config.keep = 7;
+        config.interval_hours = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn migration_check_rejects_backups_from_newer_builds() {
+        let known: Vec<String> = Migrator::migrations().iter().map(|m| m.name().to_string()).collect();
+
+        let schema = check_migration_versions(&known[..known.len() - 1]).unwrap();
+        assert_eq!(schema.latest_migration, known[known.len() - 2]);
+        assert_eq!(schema.pending_migrations, 1);
+
+        let mut newer = known.clone();
+        newer.push("m20991231_000001_from_the_future".to_string());
+        let err = check_migration_versions(&newer).unwrap_err();
+        assert!(err.to_string().contains("m20991231_000001_from_the_future"));
+
+        assert!(check_migration_versions(&[]).is_err());
+    }
+}
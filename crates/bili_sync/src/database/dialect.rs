@@ -0,0 +1,371 @@
+//! 不同数据库后端之间的 SQL 差异
+//!
+//! 业务代码中的原生 SQL 统一按 SQLite 语法书写（`?` 占位符、`ON CONFLICT ... DO UPDATE`），
+//! 通过 [`statement`] 构造语句时由当前后端的 [`SqlDialect`] 改写；表结构探测、写锁与空间回收等
+//! 无法改写的部分则由各后端分别实现。
+
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, Statement, Value};
+use tracing::{debug, info, warn};
+
+static CURRENT_BACKEND: OnceLock<DbBackend> = OnceLock::new();
+
+#[async_trait]
+pub trait SqlDialect: Send + Sync {
+    fn backend(&self) -> DbBackend;
+
+    /// 将按 SQLite 语法书写的 SQL 改写为当前后端可执行的形式
+    fn rewrite_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(sql)
+    }
+
+    /// 在原生 SQL 中对 `u32` 列做位运算时使用的列表达式
+    fn unsigned_column<'a>(&self, column: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(column)
+    }
+
+    async fn table_exists(&self, db: &DatabaseConnection, table: &str) -> Result<bool>;
+
+    async fn table_has_column(&self, db: &DatabaseConnection, table: &str, column: &str) -> Result<bool>;
+
+    /// 在事务开始前准备写锁所需的资源
+    async fn prepare_write_lock(&self, _db: &DatabaseConnection) -> Result<(), DbErr> {
+        Ok(())
+    }
+
+    /// 在事务内立即获取全局写锁，使并发的写事务排队执行
+    async fn acquire_write_lock(&self, txn: &DatabaseTransaction) -> Result<(), DbErr>;
+
+    /// 启动时回收数据库空间，默认不做任何事
+    async fn compact(&self, _db: &DatabaseConnection) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct SqliteDialect;
+pub struct PostgresDialect;
+pub struct MySqlDialect;
+
+/// 查询结果第一列的计数
+async fn query_count(db: &DatabaseConnection, stmt: Statement) -> Result<i64> {
+    Ok(db
+        .query_one(stmt)
+        .await?
+        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
+        .unwrap_or(0))
+}
+
+#[async_trait]
+impl SqlDialect for SqliteDialect {
+    fn backend(&self) -> DbBackend {
+        DbBackend::Sqlite
+    }
+
+    async fn table_exists(&self, db: &DatabaseConnection, table: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn table_has_column(&self, db: &DatabaseConnection, table: &str, column: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?",
+            [table.into(), column.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn prepare_write_lock(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        db.execute_unprepared("CREATE TABLE IF NOT EXISTS _write_lock (id INTEGER PRIMARY KEY, ts INTEGER)")
+            .await?;
+        db.execute_unprepared("INSERT OR IGNORE INTO _write_lock (id, ts) VALUES (1, 0)")
+            .await?;
+        Ok(())
+    }
+
+    async fn acquire_write_lock(&self, txn: &DatabaseTransaction) -> Result<(), DbErr> {
+        // 立即更新锁定表，强制获取写锁，避免 SQLITE_BUSY_SNAPSHOT 问题
+        // 如果其他事务持有锁，这里会等待 busy_timeout
+        txn.execute_unprepared("UPDATE _write_lock SET ts = strftime('%s', 'now') WHERE id = 1")
+            .await?;
+        Ok(())
+    }
+
+    /// 清理图片代理缓存中遗留的图片数据，空闲页较多时执行 VACUUM
+    async fn compact(&self, connection: &DatabaseConnection) -> Result<()> {
+        const MIN_FREE_PAGES_FOR_VACUUM: i64 = 8192; // ~32MB when page_size=4KB
+        const MIN_FREE_RATIO_FOR_VACUUM: f64 = 0.20;
+
+        let backend = DbBackend::Sqlite;
+        if !self.table_exists(connection, "image_proxy_cache").await? {
+            return Ok(());
+        }
+
+        if self
+            .table_has_column(connection, "image_proxy_cache", "image_data")
+            .await?
+        {
+            connection
+                .execute(Statement::from_string(
+                    backend,
+                    "UPDATE image_proxy_cache SET image_data = X'' WHERE length(image_data) > 0",
+                ))
+                .await?;
+        }
+
+        let freelist_count = connection
+            .query_one(Statement::from_string(backend, "PRAGMA freelist_count"))
+            .await?
+            .and_then(|row| row.try_get_by_index::<i64>(0).ok())
+            .unwrap_or(0);
+        let page_count = connection
+            .query_one(Statement::from_string(backend, "PRAGMA page_count"))
+            .await?
+            .and_then(|row| row.try_get_by_index::<i64>(0).ok())
+            .unwrap_or(0);
+        let page_size = connection
+            .query_one(Statement::from_string(backend, "PRAGMA page_size"))
+            .await?
+            .and_then(|row| row.try_get_by_index::<i64>(0).ok())
+            .unwrap_or(4096);
+
+        if page_count <= 0 {
+            return Ok(());
+        }
+
+        let free_ratio = freelist_count as f64 / page_count as f64;
+        let reclaimable_mb = freelist_count.saturating_mul(page_size) / (1024 * 1024);
+        if freelist_count < MIN_FREE_PAGES_FOR_VACUUM && free_ratio < MIN_FREE_RATIO_FOR_VACUUM {
+            debug!(
+                "跳过自动VACUUM: freelist_count={}, page_count={}, free_ratio={:.2}%, reclaimable={}MB",
+                freelist_count,
+                page_count,
+                free_ratio * 100.0,
+                reclaimable_mb
+            );
+            return Ok(());
+        }
+
+        info!(
+            "触发自动VACUUM: freelist_count={}, page_count={}, free_ratio={:.2}%, reclaimable={}MB",
+            freelist_count,
+            page_count,
+            free_ratio * 100.0,
+            reclaimable_mb
+        );
+
+        if let Err(e) = connection.execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE)").await {
+            debug!("自动VACUUM前WAL checkpoint失败（继续尝试VACUUM）: {}", e);
+        }
+
+        if let Err(e) = connection.execute_unprepared("VACUUM").await {
+            warn!("自动VACUUM执行失败（不影响启动）: {}", e);
+            return Ok(());
+        }
+
+        info!("自动VACUUM执行完成");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SqlDialect for PostgresDialect {
+    fn backend(&self) -> DbBackend {
+        DbBackend::Postgres
+    }
+
+    fn rewrite_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        numbered_placeholders(sql)
+    }
+
+    fn unsigned_column<'a>(&self, column: &'a str) -> Cow<'a, str> {
+        // u32 列在 PostgreSQL 上是 oid 类型，没有位运算符
+        Cow::Owned(format!("CAST({} AS BIGINT)", column))
+    }
+
+    async fn table_exists(&self, db: &DatabaseConnection, table: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = $1",
+            [table.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn table_has_column(&self, db: &DatabaseConnection, table: &str, column: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT COUNT(*) FROM information_schema.columns WHERE table_schema = current_schema() AND table_name = $1 AND column_name = $2",
+            [table.into(), column.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn acquire_write_lock(&self, txn: &DatabaseTransaction) -> Result<(), DbErr> {
+        // 事务级 advisory lock，随事务提交或回滚自动释放
+        txn.execute_unprepared("SELECT pg_advisory_xact_lock(7428190315)")
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SqlDialect for MySqlDialect {
+    fn backend(&self) -> DbBackend {
+        DbBackend::MySql
+    }
+
+    fn rewrite_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        upsert_to_on_duplicate_key(sql)
+    }
+
+    async fn table_exists(&self, db: &DatabaseConnection, table: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::MySql,
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?",
+            [table.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn table_has_column(&self, db: &DatabaseConnection, table: &str, column: &str) -> Result<bool> {
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::MySql,
+            "SELECT COUNT(*) FROM information_schema.columns WHERE table_schema = DATABASE() AND table_name = ? AND column_name = ?",
+            [table.into(), column.into()],
+        );
+        Ok(query_count(db, stmt).await? >= 1)
+    }
+
+    async fn prepare_write_lock(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        db.execute_unprepared("CREATE TABLE IF NOT EXISTS _write_lock (id INT PRIMARY KEY, ts BIGINT)")
+            .await?;
+        db.execute_unprepared("INSERT IGNORE INTO _write_lock (id, ts) VALUES (1, 0)")
+            .await?;
+        Ok(())
+    }
+
+    async fn acquire_write_lock(&self, txn: &DatabaseTransaction) -> Result<(), DbErr> {
+        // InnoDB 行锁，持有到事务结束
+        txn.execute_unprepared("UPDATE _write_lock SET ts = UNIX_TIMESTAMP() WHERE id = 1")
+            .await?;
+        Ok(())
+    }
+}
+
+pub fn dialect_for(backend: DbBackend) -> &'static dyn SqlDialect {
+    match backend {
+        DbBackend::Sqlite => &SqliteDialect,
+        DbBackend::Postgres => &PostgresDialect,
+        DbBackend::MySql => &MySqlDialect,
+    }
+}
+
+/// 记录本次运行使用的数据库后端，只在建立连接时调用一次
+pub(super) fn set_current_backend(backend: DbBackend) {
+    if CURRENT_BACKEND.set(backend).is_err() && current_backend() != backend {
+        tracing::warn!("数据库后端已设置为 {:?}，忽略 {:?}", current_backend(), backend);
+    }
+}
+
+/// 当前使用的数据库后端，未建立连接时（如单元测试）视为 SQLite
+pub fn current_backend() -> DbBackend {
+    CURRENT_BACKEND.get().copied().unwrap_or(DbBackend::Sqlite)
+}
+
+pub fn current() -> &'static dyn SqlDialect {
+    dialect_for(current_backend())
+}
+
+/// 以 SQLite 语法书写的带参数语句，按当前后端改写
+pub fn statement<I>(sql: impl AsRef<str>, values: I) -> Statement
+where
+    I: IntoIterator<Item = Value>,
+{
+    let dialect = current();
+    Statement::from_sql_and_values(dialect.backend(), dialect.rewrite_sql(sql.as_ref()), values)
+}
+
+/// 以 SQLite 语法书写的无参数语句，按当前后端改写
+pub fn raw_statement(sql: impl AsRef<str>) -> Statement {
+    let dialect = current();
+    Statement::from_string(dialect.backend(), dialect.rewrite_sql(sql.as_ref()).into_owned())
+}
+
+/// 将字符串字面量与引号标识符之外的 `?` 依次替换为 `$1`、`$2` ...
+fn numbered_placeholders(sql: &str) -> Cow<'_, str> {
+    if !sql.contains('?') {
+        return Cow::Borrowed(sql);
+    }
+    let mut result = String::with_capacity(sql.len() + 16);
+    let mut quote: Option<char> = None;
+    let mut index = 0;
+    for ch in sql.chars() {
+        match (quote, ch) {
+            (None, '\'' | '"' | '`') => {
+                quote = Some(ch);
+                result.push(ch);
+            }
+            (Some(q), _) if q == ch => {
+                quote = None;
+                result.push(ch);
+            }
+            (None, '?') => {
+                index += 1;
+                result.push('$');
+                result.push_str(&index.to_string());
+            }
+            _ => result.push(ch),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// 将 `ON CONFLICT(...) DO UPDATE SET col = excluded.col` 改写为 MySQL 的
+/// `ON DUPLICATE KEY UPDATE col = VALUES(col)`
+fn upsert_to_on_duplicate_key(sql: &str) -> Cow<'_, str> {
+    static CONFLICT: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+        regex::Regex::new(r"(?i)ON\s+CONFLICT\s*\([^)]*\)\s*DO\s+UPDATE\s+SET").expect("正则表达式有效")
+    });
+    static EXCLUDED: std::sync::LazyLock<regex::Regex> =
+        std::sync::LazyLock::new(|| regex::Regex::new(r"(?i)\bexcluded\.(\w+)").expect("正则表达式有效"));
+
+    if !CONFLICT.is_match(sql) {
+        return Cow::Borrowed(sql);
+    }
+    let sql = CONFLICT.replace_all(sql, "ON DUPLICATE KEY UPDATE");
+    Cow::Owned(EXCLUDED.replace_all(&sql, "VALUES($1)").into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postgres_placeholders_skip_quoted_text() {
+        let sql = "SELECT * FROM video WHERE name = '?' AND id = ? AND bvid IN (?, ?)";
+        assert_eq!(
+            PostgresDialect.rewrite_sql(sql),
+            "SELECT * FROM video WHERE name = '?' AND id = $1 AND bvid IN ($2, $3)"
+        );
+        assert!(matches!(PostgresDialect.rewrite_sql("SELECT 1"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn mysql_rewrites_sqlite_upsert() {
+        let sql = "INSERT INTO t (k, v) VALUES (?, ?) ON CONFLICT(k) DO UPDATE SET v = excluded.v, updated_at = CURRENT_TIMESTAMP";
+        assert_eq!(
+            MySqlDialect.rewrite_sql(sql),
+            "INSERT INTO t (k, v) VALUES (?, ?) ON DUPLICATE KEY UPDATE v = VALUES(v), updated_at = CURRENT_TIMESTAMP"
+        );
+        assert_eq!(SqliteDialect.rewrite_sql(sql), sql);
+    }
+}
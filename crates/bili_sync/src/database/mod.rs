@@ -3,8 +3,8 @@ use bili_sync_migration::{Migrator, MigratorTrait};
 use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use sea_orm::sqlx::{self, Executor};
 use sea_orm::{
-    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, ExecResult, QueryResult, SqlxSqliteConnector, Statement,
-    StreamTrait,
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, DbErr, ExecResult, QueryResult,
+    SqlxSqliteConnector, Statement, StreamTrait,
 };
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
@@ -15,7 +15,12 @@ use std::time::Instant;
 use tokio::sync::OnceCell;
 use tracing::{debug, info, warn};
 
-use crate::config::CONFIG_DIR;
+use crate::config::{ARGS, CONFIG_DIR};
+
+pub mod backup;
+pub mod dialect;
+
+pub use dialect::{raw_statement, statement};
 
 static GLOBAL_DB: OnceCell<Arc<DatabaseConnection>> = OnceCell::const_new();
 static ACTIVE_DB_OPERATIONS: LazyLock<Mutex<HashMap<u64, ActiveDbOperation>>> =
@@ -428,7 +433,25 @@ pub async fn begin_traced_transaction(
     }
 }
 
+/// 外部数据库地址，未设置或指向 SQLite 时返回 None
+fn external_database_url() -> Option<String> {
+    let url = ARGS.database_url.as_deref()?.trim();
+    if url.is_empty() || url.starts_with("sqlite:") {
+        return None;
+    }
+    Some(url.to_string())
+}
+
 fn database_path() -> std::path::PathBuf {
+    // DATABASE_URL=sqlite://path/to/data.sqlite 时使用指定的文件
+    if let Some(url) = ARGS.database_url.as_deref() {
+        if let Some(path) = url.trim().strip_prefix("sqlite:") {
+            let path = path.trim_start_matches("//").split('?').next().unwrap_or_default();
+            if !path.is_empty() {
+                return std::path::PathBuf::from(path);
+            }
+        }
+    }
     // 确保配置目录存在
     if !CONFIG_DIR.exists() {
         std::fs::create_dir_all(&*CONFIG_DIR).expect("创建配置目录失败");
@@ -451,7 +474,28 @@ fn create_sqlite_options() -> SqliteConnectOptions {
         .pragma("wal_autocheckpoint", "1000")
 }
 
+/// 连接 PostgreSQL / MySQL
+async fn external_database_connection(url: &str, max_connections: u32) -> Result<DatabaseConnection> {
+    let mut options = ConnectOptions::new(url.to_string());
+    options
+        .max_connections(max_connections)
+        .min_connections(1)
+        .acquire_timeout(std::time::Duration::from_secs(90))
+        .idle_timeout(std::time::Duration::from_secs(600))
+        .max_lifetime(std::time::Duration::from_secs(3600))
+        .sqlx_logging(false);
+    let connection = Database::connect(options).await?;
+    dialect::set_current_backend(connection.get_database_backend());
+    Ok(connection)
+}
+
 async fn database_connection() -> Result<DatabaseConnection> {
+    if let Some(url) = external_database_url() {
+        let connection = external_database_connection(&url, 50).await?;
+        debug!("{:?} 连接池已创建", connection.get_database_backend());
+        return Ok(connection);
+    }
+
     // 创建连接池，使用 after_connect 回调确保每个连接都执行额外的PRAGMA
     let pool = SqlitePoolOptions::new()
         .max_connections(50)  // 与上游一致
@@ -495,8 +539,24 @@ async fn database_connection() -> Result<DatabaseConnection> {
 }
 
 async fn migrate_database() -> Result<()> {
+    if let Some(url) = external_database_url() {
+        let connection = external_database_connection(&url, 1).await?;
+        info!("使用外部数据库: {:?}", connection.get_database_backend());
+        Migrator::up(&connection, None).await?;
+        dialect::current().compact(&connection).await?;
+        connection.close().await?;
+        debug!("迁移完成，已关闭迁移连接池");
+        return Ok(());
+    }
+    dialect::set_current_backend(DbBackend::Sqlite);
+
+    // 应用通过管理页暂存的备份恢复，失败时继续使用当前数据库
+    if let Err(e) = backup::apply_pending_restore().await {
+        warn!("从备份恢复数据库失败: {:#}", e);
+    }
+
     // 检查数据库文件是否存在，不存在则会在连接时自动创建
-    let db_path = CONFIG_DIR.join("data.sqlite");
+    let db_path = database_path();
     debug!("数据库文件路径: {}", db_path.display());
     if !db_path.exists() {
         debug!("数据库文件不存在，将创建新的数据库");
@@ -515,7 +575,7 @@ async fn migrate_database() -> Result<()> {
 
     // 确保所有迁移都应用
     Migrator::up(&connection, None).await?;
-    auto_compact_image_proxy_cache(&connection).await?;
+    dialect::current().compact(&connection).await?;
 
     // 显式关闭连接池，确保释放所有数据库锁
     pool.close().await;
@@ -524,118 +584,18 @@ async fn migrate_database() -> Result<()> {
     Ok(())
 }
 
-/// 确保 page 表有 ai_renamed 字段
-async fn auto_compact_image_proxy_cache(connection: &DatabaseConnection) -> Result<()> {
-    const MIN_FREE_PAGES_FOR_VACUUM: i64 = 8192; // ~32MB when page_size=4KB
-    const MIN_FREE_RATIO_FOR_VACUUM: f64 = 0.20;
-
-    let backend = connection.get_database_backend();
-    let table_exists_sql = "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='image_proxy_cache'";
-    let table_exists = connection
-        .query_one(sea_orm::Statement::from_string(backend, table_exists_sql))
-        .await?
-        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
-        .unwrap_or(0)
-        >= 1;
-    if !table_exists {
-        return Ok(());
-    }
-
-    if sqlite_table_has_column(connection, "image_proxy_cache", "image_data").await? {
-        connection
-            .execute(sea_orm::Statement::from_string(
-                backend,
-                "UPDATE image_proxy_cache SET image_data = X'' WHERE length(image_data) > 0",
-            ))
-            .await?;
-    }
-
-    let freelist_count = connection
-        .query_one(sea_orm::Statement::from_string(backend, "PRAGMA freelist_count"))
-        .await?
-        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
-        .unwrap_or(0);
-    let page_count = connection
-        .query_one(sea_orm::Statement::from_string(backend, "PRAGMA page_count"))
-        .await?
-        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
-        .unwrap_or(0);
-    let page_size = connection
-        .query_one(sea_orm::Statement::from_string(backend, "PRAGMA page_size"))
-        .await?
-        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
-        .unwrap_or(4096);
-
-    if page_count <= 0 {
-        return Ok(());
-    }
-
-    let free_ratio = freelist_count as f64 / page_count as f64;
-    let reclaimable_mb = freelist_count.saturating_mul(page_size) / (1024 * 1024);
-    if freelist_count < MIN_FREE_PAGES_FOR_VACUUM && free_ratio < MIN_FREE_RATIO_FOR_VACUUM {
-        debug!(
-            "跳过自动VACUUM: freelist_count={}, page_count={}, free_ratio={:.2}%, reclaimable={}MB",
-            freelist_count,
-            page_count,
-            free_ratio * 100.0,
-            reclaimable_mb
-        );
-        return Ok(());
-    }
-
-    info!(
-        "触发自动VACUUM: freelist_count={}, page_count={}, free_ratio={:.2}%, reclaimable={}MB",
-        freelist_count,
-        page_count,
-        free_ratio * 100.0,
-        reclaimable_mb
-    );
-
-    if let Err(e) = connection.execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE)").await {
-        debug!("自动VACUUM前WAL checkpoint失败（继续尝试VACUUM）: {}", e);
-    }
-
-    if let Err(e) = connection.execute_unprepared("VACUUM").await {
-        warn!("自动VACUUM执行失败（不影响启动）: {}", e);
-        return Ok(());
-    }
-
-    info!("自动VACUUM执行完成");
-    Ok(())
-}
-
-async fn sqlite_table_has_column(connection: &DatabaseConnection, table_name: &str, column_name: &str) -> Result<bool> {
-    let backend = connection.get_database_backend();
-    let sql = format!(
-        "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = '{}'",
-        table_name.replace('\'', "''"),
-        column_name.replace('\'', "''")
-    );
-    let count = connection
-        .query_one(sea_orm::Statement::from_string(backend, sql))
-        .await?
-        .and_then(|row| row.try_get_by_index::<i64>(0).ok())
-        .unwrap_or(0);
-    Ok(count >= 1)
-}
-
 async fn ensure_ai_renamed_column(connection: &DatabaseConnection) -> Result<()> {
     use sea_orm::ConnectionTrait;
 
     let backend = connection.get_database_backend();
 
     // 检查是否已有 ai_renamed 字段
-    let check_sql = "SELECT COUNT(*) FROM pragma_table_info('page') WHERE name = 'ai_renamed'";
-    let result: Option<i32> = connection
-        .query_one(sea_orm::Statement::from_string(backend, check_sql))
+    if dialect::current()
+        .table_has_column(connection, "page", "ai_renamed")
         .await?
-        .and_then(|row| row.try_get_by_index(0).ok());
-
-    if let Some(count) = result {
-        if count >= 1 {
-            debug!("page.ai_renamed 字段已存在");
-            return Ok(());
-        }
+    {
+        debug!("page.ai_renamed 字段已存在");
+        return Ok(());
     }
 
     // 添加 ai_renamed 字段
@@ -660,17 +620,12 @@ async fn ensure_danmaku_last_write_count_column(connection: &DatabaseConnection)
 
     let backend = connection.get_database_backend();
 
-    let check_sql = "SELECT COUNT(*) FROM pragma_table_info('page') WHERE name = 'danmaku_last_write_count'";
-    let result: Option<i32> = connection
-        .query_one(sea_orm::Statement::from_string(backend, check_sql))
+    if dialect::current()
+        .table_has_column(connection, "page", "danmaku_last_write_count")
         .await?
-        .and_then(|row| row.try_get_by_index(0).ok());
-
-    if let Some(count) = result {
-        if count >= 1 {
-            debug!("page.danmaku_last_write_count 字段已存在");
-            return Ok(());
-        }
+    {
+        debug!("page.danmaku_last_write_count 字段已存在");
+        return Ok(());
     }
 
     let add_sql = "ALTER TABLE page ADD COLUMN danmaku_last_write_count INTEGER NOT NULL DEFAULT 0";
@@ -767,23 +722,20 @@ pub fn get_global_db() -> Option<Arc<DatabaseConnection>> {
 }
 
 /// 开始一个事务并立即获取写锁
-/// 通过更新锁定表来强制获取写锁，避免 SQLITE_BUSY_SNAPSHOT 问题
+/// 具体加锁方式由当前数据库后端的 [`dialect::SqlDialect`] 决定
 pub async fn begin_write_transaction(
     connection: &sea_orm::DatabaseConnection,
     operation_name: impl Into<String>,
 ) -> anyhow::Result<TracedDatabaseTransaction> {
-    use sea_orm::{ConnectionTrait, TransactionTrait};
+    use sea_orm::TransactionTrait;
 
     let operation_name = operation_name.into();
     let guard = DbOperationGuard::begin(format!("{} [write-lock]", operation_name));
 
+    let dialect = dialect::current();
+
     // 确保锁定表存在
-    let _ = connection
-        .execute_unprepared("CREATE TABLE IF NOT EXISTS _write_lock (id INTEGER PRIMARY KEY, ts INTEGER)")
-        .await;
-    let _ = connection
-        .execute_unprepared("INSERT OR IGNORE INTO _write_lock (id, ts) VALUES (1, 0)")
-        .await;
+    let _ = dialect.prepare_write_lock(connection).await;
 
     // 开始事务
     let txn = match connection.begin().await {
@@ -794,12 +746,8 @@ pub async fn begin_write_transaction(
         }
     };
 
-    // 立即更新锁定表，强制获取写锁
-    // 如果其他事务持有锁，这里会等待 busy_timeout
-    if let Err(error) = txn
-        .execute_unprepared("UPDATE _write_lock SET ts = strftime('%s', 'now') WHERE id = 1")
-        .await
-    {
+    // 立即获取写锁，其他事务持有锁时在此等待
+    if let Err(error) = dialect.acquire_write_lock(&txn).await {
         guard.on_error("acquire_write_lock", &error);
         return Err(error.into());
     }
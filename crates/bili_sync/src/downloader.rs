@@ -12,7 +12,7 @@ use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 use tokio_util::io::StreamReader;
 use tracing::{debug, error, info, warn};
 
-use crate::bilibili::Client;
+use crate::bilibili::{Client, HighlightChapter};
 pub struct Downloader {
     client: Client,
 }
@@ -618,6 +618,25 @@ fn unique_temp_path_for_media(input_path: &Path, label: &str, extension: &str) -
     parent.join(format!(".{}.{}.{}.{}", file_name, label, timestamp_ms, extension))
 }
 
+/// 用 `replacement` 原子替换 `target`：先把原文件硬链接（不支持时复制）为 `backup`，
+/// 再把新文件直接 rename 到原路径上，整个过程中原路径始终存在；替换成功后才删除备份
+pub async fn replace_file_atomically(target: &Path, replacement: &Path, backup: &Path) -> Result<()> {
+    let _ = fs::remove_file(backup).await;
+    if fs::hard_link(target, backup).await.is_err() {
+        fs::copy(target, backup)
+            .await
+            .with_context(|| format!("备份原文件失败: {}", target.display()))?;
+    }
+    if let Err(e) = fs::rename(replacement, target).await {
+        let _ = fs::remove_file(backup).await;
+        return Err(anyhow!(e)).with_context(|| format!("替换文件失败: {}", target.display()));
+    }
+    if let Err(e) = fs::remove_file(backup).await {
+        warn!("删除原文件备份失败: {}: {}", backup.display(), e);
+    }
+    Ok(())
+}
+
 fn audio_codec_needs_mp4_muxer_for_m4a(codec_name: &str) -> bool {
     codec_name.eq_ignore_ascii_case("flac")
 }
@@ -803,6 +822,173 @@ pub async fn embed_cover_into_m4a_with_ffmpeg(audio_path: &Path, cover_path: &Pa
     Ok(())
 }
 
+/// 生成 ffmpeg 的 FFMETADATA 章节描述，时间单位为毫秒
+fn build_chapter_metadata(chapters: &[HighlightChapter]) -> String {
+    let escape = |value: &str| {
+        let mut escaped = String::with_capacity(value.len());
+        for ch in value.chars() {
+            match ch {
+                '=' | ';' | '#' | '\\' => {
+                    escaped.push('\\');
+                    escaped.push(ch);
+                }
+                '\n' | '\r' => escaped.push(' '),
+                _ => escaped.push(ch),
+            }
+        }
+        escaped
+    };
+    let mut metadata = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        metadata.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            chapter.start_s as u64 * 1000,
+            chapter.end_s as u64 * 1000,
+            escape(&chapter.title)
+        ));
+    }
+    metadata
+}
+
+/// 用 ffmpeg 重新封装视频，替换其中的章节（流直接复制，不重新编码），返回替换后的文件大小
+pub async fn embed_chapters_with_ffmpeg(media_path: &Path, chapters: &[HighlightChapter]) -> Result<u64> {
+    ensure!(
+        tokio::fs::metadata(media_path).await.is_ok(),
+        "视频文件不存在: {}",
+        media_path.display()
+    );
+    let extension = media_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_else(|| "mp4".to_string());
+    let metadata_path = unique_temp_path_for_media(media_path, "chapters", "txt");
+    let tmp_output_path = unique_temp_path_for_media(media_path, "chapters", &extension);
+    let backup_path = unique_temp_path_for_media(media_path, "backup", &extension);
+
+    fs::write(&metadata_path, build_chapter_metadata(chapters))
+        .await
+        .with_context(|| format!("写入章节元数据失败: {}", metadata_path.display()))?;
+    let output = tokio::process::Command::new(resolve_media_tool_path("ffmpeg"))
+        .arg("-i")
+        .arg(media_path)
+        .args(["-f", "ffmetadata", "-i"])
+        .arg(&metadata_path)
+        .args([
+            "-map",
+            "0",
+            "-map_metadata",
+            "0",
+            "-map_chapters",
+            "1",
+            "-c",
+            "copy",
+            "-y",
+        ])
+        .arg(&tmp_output_path)
+        .output()
+        .await;
+    let _ = fs::remove_file(&metadata_path).await;
+    let output = output?;
+    if !output.status.success() {
+        let stderr = str::from_utf8(&output.stderr).unwrap_or("unknown");
+        let _ = fs::remove_file(&tmp_output_path).await;
+        bail!("ffmpeg chapter embed error: {}", stderr.trim());
+    }
+
+    let output_size = tokio::fs::metadata(&tmp_output_path)
+        .await
+        .with_context(|| format!("无法读取写入章节后的临时文件: {}", tmp_output_path.display()))?
+        .len();
+    ensure!(
+        output_size > 0,
+        "写入章节后的临时文件为空: {}",
+        tmp_output_path.display()
+    );
+
+    if let Err(e) = replace_file_atomically(media_path, &tmp_output_path, &backup_path).await {
+        let _ = fs::remove_file(&tmp_output_path).await;
+        return Err(e).context("替换写入章节后的视频文件失败");
+    }
+    Ok(output_size)
+}
+
+/// ffprobe 探测到的媒体概要，用于比较替换前后的文件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaProbe {
+    pub duration_secs: f64,
+    pub video_streams: usize,
+    pub audio_streams: usize,
+}
+
+fn parse_media_probe(stdout: &str) -> Result<MediaProbe> {
+    let json: serde_json::Value = serde_json::from_str(stdout).context("解析 ffprobe 输出失败")?;
+    let duration_secs = json["format"]["duration"]
+        .as_str()
+        .and_then(|duration| duration.parse::<f64>().ok())
+        .ok_or_else(|| anyhow!("ffprobe 未返回媒体时长"))?;
+    let streams = json["streams"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let count = |codec_type: &str| {
+        streams
+            .iter()
+            .filter(|stream| stream["codec_type"].as_str() == Some(codec_type))
+            .count()
+    };
+    Ok(MediaProbe {
+        duration_secs,
+        video_streams: count("video"),
+        audio_streams: count("audio"),
+    })
+}
+
+/// 用 ffprobe 读取媒体时长与音视频流数量，ffprobe 不可用或文件无法解析时返回错误
+pub async fn probe_media(media_path: &Path) -> Result<MediaProbe> {
+    let output = tokio::process::Command::new(resolve_media_tool_path("ffprobe"))
+        .args([
+            "-v",
+            "error",
+            "-print_format",
+            "json",
+            "-show_entries",
+            "format=duration:stream=codec_type",
+        ])
+        .arg(media_path)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .context("无法启动 ffprobe")?;
+    if !output.status.success() {
+        let stderr = str::from_utf8(&output.stderr).unwrap_or("unknown");
+        bail!("ffprobe 探测失败: {}: {}", media_path.display(), stderr.trim());
+    }
+    parse_media_probe(str::from_utf8(&output.stdout).unwrap_or_default())
+        .with_context(|| format!("无法读取媒体信息: {}", media_path.display()))
+}
+
+/// 提取语音识别用的 16kHz 单声道 WAV 到媒体文件旁的临时文件，调用方负责删除
+pub async fn extract_speech_audio_with_ffmpeg(media_path: &Path) -> Result<PathBuf> {
+    ensure!(
+        tokio::fs::metadata(media_path).await.is_ok(),
+        "媒体文件不存在: {}",
+        media_path.display()
+    );
+    let wav_path = unique_temp_path_for_media(media_path, "speech", "wav");
+    let output = tokio::process::Command::new(resolve_media_tool_path("ffmpeg"))
+        .arg("-nostdin")
+        .arg("-i")
+        .arg(media_path)
+        .args(["-vn", "-ac", "1", "-ar", "16000", "-c:a", "pcm_s16le", "-y"])
+        .arg(&wav_path)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = str::from_utf8(&output.stderr).unwrap_or("unknown");
+        let _ = fs::remove_file(&wav_path).await;
+        bail!("ffmpeg speech audio extract error: {}", stderr.trim());
+    }
+    Ok(wav_path)
+}
+
 pub async fn split_media_segments_with_ffmpeg(
     input_path: &Path,
     output_paths: &[PathBuf],
@@ -973,6 +1159,33 @@ mod tests {
         assert_eq!(args.last().map(String::as_str), Some("out.m4a"));
     }
 
+    #[test]
+    fn chapter_metadata_escapes_titles() {
+        let metadata = build_chapter_metadata(&[HighlightChapter {
+            start_s: 90,
+            end_s: 120,
+            title: "高能=1;#\\\n".to_string(),
+        }]);
+
+        assert_eq!(
+            metadata,
+            ";FFMETADATA1\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=90000\nEND=120000\ntitle=高能\\=1\\;\\#\\\\ \n"
+        );
+    }
+
+    #[test]
+    fn parses_ffprobe_duration_and_stream_counts() {
+        let probe = parse_media_probe(
+            r#"{"streams":[{"codec_type":"video"},{"codec_type":"audio"}],"format":{"duration":"321.480000"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(probe.video_streams, 1);
+        assert_eq!(probe.audio_streams, 1);
+        assert!((probe.duration_secs - 321.48).abs() < 1e-6);
+        assert!(parse_media_probe(r#"{"streams":[],"format":{}}"#).is_err());
+    }
+
     #[test]
     fn embed_cover_args_keep_default_muxer_for_regular_m4a() {
         let args = build_embed_cover_args("audio.m4a", "cover.jpg", "out.m4a", false);
@@ -0,0 +1,278 @@
+//! 事件总线：把视频发现、下载、视频源变更、风控等内部事件写入 event 表，
+//! 再通过 SSE 推送给前端，并按订阅投递到第三方 Webhook
+//!
+//! `publish` 是同步的，可以在任意位置调用；事件由「事件总线」后台任务按发布顺序落库，
+//! 落库成功后才会广播和创建投递记录，保证 SSE 回放与 Webhook 看到的事件 ID 一致。
+
+mod webhooks;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use bili_sync_entity::{event, video};
+use once_cell::sync::Lazy;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
+
+pub use self::webhooks::{
+    decode_kinds, encode_kinds, generate_secret, retry_delivery, webhook_dispatcher, DELIVERY_FAILED, DELIVERY_PENDING,
+    DELIVERY_SUCCEEDED,
+};
+use crate::utils::time_format::{beijing_now, now_standard_string, to_standard_string};
+
+/// 事件保留天数，过期事件及其投递记录会被定期清理
+const EVENT_RETENTION_DAYS: i64 = 30;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// 扫描发现新视频
+    VideoDiscovered,
+    /// 开始下载视频
+    DownloadStarted,
+    /// 视频下载完成
+    DownloadFinished,
+    /// 视频下载失败（重试后仍失败）
+    DownloadFailed,
+    /// 新增视频源
+    SourceAdded,
+    /// 视频源被停用（手动、声明式配置或自动停用）
+    SourceDisabled,
+    /// 触发B站风控，扫描中断
+    RiskControlTriggered,
+    /// B站凭据已刷新
+    CredentialRefreshed,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 8] = [
+        EventKind::VideoDiscovered,
+        EventKind::DownloadStarted,
+        EventKind::DownloadFinished,
+        EventKind::DownloadFailed,
+        EventKind::SourceAdded,
+        EventKind::SourceDisabled,
+        EventKind::RiskControlTriggered,
+        EventKind::CredentialRefreshed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::VideoDiscovered => "video_discovered",
+            EventKind::DownloadStarted => "download_started",
+            EventKind::DownloadFinished => "download_finished",
+            EventKind::DownloadFailed => "download_failed",
+            EventKind::SourceAdded => "source_added",
+            EventKind::SourceDisabled => "source_disabled",
+            EventKind::RiskControlTriggered => "risk_control_triggered",
+            EventKind::CredentialRefreshed => "credential_refreshed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        EventKind::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// 对外推送的事件，SSE 与 Webhook 使用相同的结构
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EventRecord {
+    pub id: i32,
+    pub kind: EventKind,
+    pub created_at: String,
+    /// 事件数据，不同事件类型字段不同
+    pub data: Value,
+}
+
+impl EventRecord {
+    /// 从数据库记录还原，无法识别的事件类型（来自更新版本的程序）返回 None
+    pub fn from_model(model: event::Model) -> Option<Self> {
+        Some(Self {
+            id: model.id,
+            kind: EventKind::parse(&model.kind)?,
+            created_at: model.created_at,
+            data: serde_json::from_str(&model.payload).unwrap_or(Value::Null),
+        })
+    }
+}
+
+struct PendingEvent {
+    kind: EventKind,
+    data: Value,
+    created_at: String,
+}
+
+type EventQueue = (
+    mpsc::UnboundedSender<PendingEvent>,
+    Mutex<Option<mpsc::UnboundedReceiver<PendingEvent>>>,
+);
+
+static EVENT_QUEUE: Lazy<EventQueue> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    (sender, Mutex::new(Some(receiver)))
+});
+
+static EVENT_BROADCASTER: Lazy<broadcast::Sender<EventRecord>> = Lazy::new(|| broadcast::channel(256).0);
+
+/// 发布事件，事件时间取发布时刻
+pub fn publish(kind: EventKind, data: Value) {
+    debug!("发布事件 {}: {}", kind.as_str(), data);
+    let _ = EVENT_QUEUE.0.send(PendingEvent {
+        kind,
+        data,
+        created_at: now_standard_string(),
+    });
+}
+
+/// 发布与单个视频相关的事件，附带视频所属的视频源
+pub fn publish_video(kind: EventKind, video: &video::Model, extra: Value) {
+    let (source_type, source_id) = crate::adapter::video_source_of(video);
+    let mut data = serde_json::json!({
+        "video_id": video.id,
+        "bvid": video.bvid,
+        "name": video.name,
+        "upper_name": video.upper_name,
+        "source_type": source_type,
+        "source_id": source_id,
+    });
+    if let (Some(data), Value::Object(extra)) = (data.as_object_mut(), extra) {
+        data.extend(extra);
+    }
+    publish(kind, data);
+}
+
+/// 订阅落库后的实时事件
+pub fn subscribe() -> broadcast::Receiver<EventRecord> {
+    EVENT_BROADCASTER.subscribe()
+}
+
+/// 按 ID 顺序读取 after_id 之后的事件，kinds 为空表示不过滤
+pub async fn list_events(
+    db: &DatabaseConnection,
+    after_id: Option<i32>,
+    kinds: &[EventKind],
+    limit: u64,
+) -> Result<Vec<EventRecord>> {
+    let mut query = event::Entity::find().order_by_asc(event::Column::Id);
+    if let Some(after_id) = after_id {
+        query = query.filter(event::Column::Id.gt(after_id));
+    }
+    if !kinds.is_empty() {
+        query = query.filter(event::Column::Kind.is_in(kinds.iter().map(EventKind::as_str)));
+    }
+    Ok(query
+        .limit(limit)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(EventRecord::from_model)
+        .collect())
+}
+
+/// 读取最近的事件，按 ID 倒序
+pub async fn latest_events(db: &DatabaseConnection, kinds: &[EventKind], limit: u64) -> Result<Vec<EventRecord>> {
+    let mut query = event::Entity::find().order_by_desc(event::Column::Id);
+    if !kinds.is_empty() {
+        query = query.filter(event::Column::Kind.is_in(kinds.iter().map(EventKind::as_str)));
+    }
+    Ok(query
+        .limit(limit)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(EventRecord::from_model)
+        .collect())
+}
+
+async fn persist(db: &DatabaseConnection, pending: PendingEvent) -> Result<EventRecord> {
+    let model = event::ActiveModel {
+        kind: Set(pending.kind.as_str().to_string()),
+        payload: Set(pending.data.to_string()),
+        created_at: Set(pending.created_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    webhooks::enqueue_deliveries(db, &model, pending.kind).await?;
+    Ok(EventRecord {
+        id: model.id,
+        kind: pending.kind,
+        created_at: model.created_at,
+        data: pending.data,
+    })
+}
+
+async fn prune_events(db: &DatabaseConnection) -> Result<()> {
+    let cutoff = to_standard_string(beijing_now() - chrono::Duration::days(EVENT_RETENTION_DAYS));
+    let deliveries = webhooks::prune_deliveries(db, &cutoff).await?;
+    let events = event::Entity::delete_many()
+        .filter(event::Column::CreatedAt.lt(cutoff.as_str()))
+        .exec(db)
+        .await?
+        .rows_affected;
+    if events > 0 || deliveries > 0 {
+        info!(
+            "已清理 {} 天前的 {} 条事件与 {} 条投递记录",
+            EVENT_RETENTION_DAYS, events, deliveries
+        );
+    }
+    Ok(())
+}
+
+/// 事件总线后台任务：按发布顺序落库、广播并创建 Webhook 投递记录
+pub async fn event_writer(db: Arc<DatabaseConnection>) {
+    let Some(mut receiver) = EVENT_QUEUE.1.lock().unwrap().take() else {
+        error!("事件总线已在运行");
+        return std::future::pending().await;
+    };
+    let mut prune_interval = tokio::time::interval(PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            pending = receiver.recv() => {
+                // 发送端是全局静态变量，不会被关闭
+                let Some(pending) = pending else {
+                    return std::future::pending().await;
+                };
+                let kind = pending.kind;
+                match persist(&db, pending).await {
+                    Ok(record) => {
+                        let _ = EVENT_BROADCASTER.send(record);
+                        webhooks::wake_dispatcher();
+                    }
+                    Err(e) => warn!("保存事件 {} 失败: {:#}", kind.as_str(), e),
+                }
+            }
+            _ = prune_interval.tick() => {
+                if let Err(e) = prune_events(&db).await {
+                    warn!("清理过期事件失败: {:#}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_kind_round_trips_through_serde_and_str() {
+        for kind in EventKind::ALL {
+            assert_eq!(EventKind::parse(kind.as_str()), Some(kind));
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                Value::String(kind.as_str().to_string())
+            );
+        }
+        assert_eq!(EventKind::parse("unknown"), None);
+    }
+}
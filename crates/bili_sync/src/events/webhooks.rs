@@ -0,0 +1,337 @@
+//! Webhook 投递：每个事件对每个匹配的订阅生成一条投递记录，失败后按指数退避重试
+//!
+//! 请求体为 `EventRecord` 的 JSON，签名为 `sha256=<hex>`，
+//! 内容是 HMAC-SHA256(secret, "{X-BiliSync-Timestamp}.{请求体}")。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use bili_sync_entity::{event, webhook, webhook_delivery};
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use sha2::Sha256;
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+use super::{EventKind, EventRecord};
+use crate::utils::time_format::{beijing_now, now_standard_string, to_standard_string};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const DELIVERY_PENDING: &str = "pending";
+pub const DELIVERY_SUCCEEDED: &str = "succeeded";
+pub const DELIVERY_FAILED: &str = "failed";
+
+/// 包含首次投递在内的最大尝试次数
+const MAX_ATTEMPTS: i32 = 8;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+/// 没有新事件时也定期检查，以便处理到期的重试
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const BATCH_SIZE: u64 = 50;
+/// 记录到投递记录中的响应内容最大长度
+const MAX_ERROR_LEN: usize = 500;
+
+static DISPATCHER_WAKER: Lazy<Notify> = Lazy::new(Notify::new);
+
+pub(super) fn wake_dispatcher() {
+    DISPATCHER_WAKER.notify_one();
+}
+
+pub fn encode_kinds(kinds: &[EventKind]) -> String {
+    let mut names: Vec<&str> = kinds.iter().map(EventKind::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    names.join(",")
+}
+
+/// 解析订阅的事件类型，忽略无法识别的值；空列表表示订阅全部事件
+pub fn decode_kinds(value: &str) -> Vec<EventKind> {
+    value
+        .split(',')
+        .filter_map(|kind| EventKind::parse(kind.trim()))
+        .collect()
+}
+
+fn subscribes_to(model: &webhook::Model, kind: EventKind) -> bool {
+    model.event_kinds.trim().is_empty() || decode_kinds(&model.event_kinds).contains(&kind)
+}
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC 接受任意长度的密钥");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// 第 attempts 次尝试失败后的等待时间
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY.saturating_mul(1 << exponent).min(RETRY_MAX_DELAY)
+}
+
+fn truncate_error(message: String) -> String {
+    if message.chars().count() <= MAX_ERROR_LEN {
+        return message;
+    }
+    let mut truncated: String = message.chars().take(MAX_ERROR_LEN).collect();
+    truncated.push('…');
+    truncated
+}
+
+pub(super) async fn enqueue_deliveries(db: &DatabaseConnection, event: &event::Model, kind: EventKind) -> Result<()> {
+    let webhooks = webhook::Entity::find()
+        .filter(webhook::Column::Enabled.eq(true))
+        .all(db)
+        .await?;
+    let now = now_standard_string();
+    let deliveries: Vec<_> = webhooks
+        .iter()
+        .filter(|model| subscribes_to(model, kind))
+        .map(|model| webhook_delivery::ActiveModel {
+            webhook_id: Set(model.id),
+            event_id: Set(event.id),
+            status: Set(DELIVERY_PENDING.to_string()),
+            attempts: Set(0),
+            next_attempt_at: Set(Some(now.clone())),
+            response_status: Set(None),
+            last_error: Set(None),
+            created_at: Set(now.clone()),
+            updated_at: Set(now.clone()),
+            ..Default::default()
+        })
+        .collect();
+    if !deliveries.is_empty() {
+        webhook_delivery::Entity::insert_many(deliveries).exec(db).await?;
+    }
+    Ok(())
+}
+
+pub(super) async fn prune_deliveries(db: &DatabaseConnection, cutoff: &str) -> Result<u64> {
+    Ok(webhook_delivery::Entity::delete_many()
+        .filter(webhook_delivery::Column::CreatedAt.lt(cutoff))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+/// 重新投递一条记录：重置尝试次数并立即唤醒推送任务
+pub async fn retry_delivery(db: &DatabaseConnection, id: i32) -> Result<Option<webhook_delivery::Model>> {
+    let Some(model) = webhook_delivery::Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    let now = now_standard_string();
+    let mut active = model.into_active_model();
+    active.status = Set(DELIVERY_PENDING.to_string());
+    active.attempts = Set(0);
+    active.next_attempt_at = Set(Some(now.clone()));
+    active.updated_at = Set(now);
+    let model = active.update(db).await?;
+    wake_dispatcher();
+    Ok(Some(model))
+}
+
+/// 发送一次请求，返回响应状态码；非 2xx 视为失败
+async fn send(
+    client: &reqwest::Client,
+    webhook: &webhook::Model,
+    delivery_id: i32,
+    record: &EventRecord,
+) -> Result<u16, (Option<u16>, String)> {
+    let body = serde_json::to_string(record).map_err(|e| (None, e.to_string()))?;
+    let timestamp = chrono::Utc::now().timestamp();
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-BiliSync-Event", record.kind.as_str())
+        .header("X-BiliSync-Event-Id", record.id)
+        .header("X-BiliSync-Delivery", delivery_id)
+        .header("X-BiliSync-Timestamp", timestamp)
+        .header("X-BiliSync-Signature", sign(&webhook.secret, timestamp, &body))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| (None, format!("{:#}", e)))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(status.as_u16());
+    }
+    let text = response.text().await.unwrap_or_default();
+    Err((Some(status.as_u16()), format!("HTTP {}: {}", status, text.trim())))
+}
+
+async fn deliver(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    delivery: webhook_delivery::Model,
+    webhook: Option<&webhook::Model>,
+    record: Option<&EventRecord>,
+) -> Result<()> {
+    let attempts = delivery.attempts + 1;
+    let result = match (webhook, record) {
+        (Some(webhook), Some(record)) if webhook.enabled => send(client, webhook, delivery.id, record).await,
+        (Some(_), Some(_)) => Err((None, "订阅已停用".to_string())),
+        (None, _) => Err((None, "订阅已删除".to_string())),
+        (_, None) => Err((None, "事件已过期清理".to_string())),
+    };
+    // 订阅或事件不存在时无需重试
+    let retryable = webhook.is_some_and(|webhook| webhook.enabled) && record.is_some();
+
+    let mut active = delivery.into_active_model();
+    active.attempts = Set(attempts);
+    active.updated_at = Set(now_standard_string());
+    match result {
+        Ok(status) => {
+            active.status = Set(DELIVERY_SUCCEEDED.to_string());
+            active.next_attempt_at = Set(None);
+            active.response_status = Set(Some(status as i32));
+            active.last_error = Set(None);
+        }
+        Err((status, message)) => {
+            active.response_status = Set(status.map(i32::from));
+            active.last_error = Set(Some(truncate_error(message)));
+            if retryable && attempts < MAX_ATTEMPTS {
+                let delay = chrono::Duration::from_std(retry_delay(attempts)).unwrap_or_default();
+                active.next_attempt_at = Set(Some(to_standard_string(beijing_now() + delay)));
+            } else {
+                active.status = Set(DELIVERY_FAILED.to_string());
+                active.next_attempt_at = Set(None);
+            }
+        }
+    }
+    let model = active.update(db).await?;
+    if model.status == DELIVERY_FAILED {
+        warn!(
+            "Webhook 投递 {} 在 {} 次尝试后失败: {}",
+            model.id,
+            model.attempts,
+            model.last_error.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// 处理一批到期的投递，返回处理的数量
+async fn dispatch_due(db: &DatabaseConnection, client: &reqwest::Client) -> Result<usize> {
+    let deliveries = webhook_delivery::Entity::find()
+        .filter(webhook_delivery::Column::Status.eq(DELIVERY_PENDING))
+        .filter(webhook_delivery::Column::NextAttemptAt.lte(now_standard_string()))
+        .order_by_asc(webhook_delivery::Column::Id)
+        .limit(BATCH_SIZE)
+        .all(db)
+        .await?;
+    if deliveries.is_empty() {
+        return Ok(0);
+    }
+
+    let webhooks: HashMap<i32, webhook::Model> = webhook::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|model| (model.id, model))
+        .collect();
+    let event_ids: Vec<i32> = deliveries.iter().map(|delivery| delivery.event_id).collect();
+    let records: HashMap<i32, EventRecord> = event::Entity::find()
+        .filter(event::Column::Id.is_in(event_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(EventRecord::from_model)
+        .map(|record| (record.id, record))
+        .collect();
+
+    let count = deliveries.len();
+    for delivery in deliveries {
+        let webhook = webhooks.get(&delivery.webhook_id);
+        let record = records.get(&delivery.event_id);
+        debug!("投递事件 {} 到 Webhook {}", delivery.event_id, delivery.webhook_id);
+        deliver(db, client, delivery, webhook, record).await?;
+    }
+    Ok(count)
+}
+
+/// Webhook 推送后台任务
+pub async fn webhook_dispatcher(db: Arc<DatabaseConnection>) {
+    let client = match reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("bili-sync/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("创建 Webhook HTTP 客户端失败")
+    {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("{:#}", e);
+            return std::future::pending().await;
+        }
+    };
+    loop {
+        match dispatch_due(&db, &client).await {
+            // 一批处理满时可能还有到期的投递，立即继续
+            Ok(count) if count as u64 == BATCH_SIZE => continue,
+            Ok(_) => {}
+            Err(e) => warn!("处理 Webhook 投递失败: {:#}", e),
+        }
+        tokio::select! {
+            _ = DISPATCHER_WAKER.notified() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign("whsec_test", 1700000000, r#"{"id":1}"#);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("whsec_test", 1700000000, r#"{"id":1}"#));
+        assert_ne!(signature, sign("whsec_test", 1700000001, r#"{"id":1}"#));
+        assert_ne!(signature, sign("whsec_other", 1700000000, r#"{"id":1}"#));
+    }
+
+    #[test]
+    fn retry_delay_grows_exponentially_and_is_capped() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(MAX_ATTEMPTS), Duration::from_secs(60 * 60));
+        assert_eq!(retry_delay(100), Duration::from_secs(60 * 60));
+    }
+
+    #[test]
+    fn empty_kinds_subscribe_to_everything() {
+        let mut model = webhook::Model {
+            id: 1,
+            name: "n".to_string(),
+            url: "http://127.0.0.1/hook".to_string(),
+            secret: "s".to_string(),
+            event_kinds: String::new(),
+            enabled: true,
+            created_at: String::new(),
+        };
+        assert!(EventKind::ALL.iter().all(|kind| subscribes_to(&model, *kind)));
+
+        model.event_kinds = encode_kinds(&[EventKind::DownloadFinished, EventKind::DownloadFailed]);
+        assert_eq!(model.event_kinds, "download_failed,download_finished");
+        assert!(subscribes_to(&model, EventKind::DownloadFailed));
+        assert!(!subscribes_to(&model, EventKind::VideoDiscovered));
+    }
+}
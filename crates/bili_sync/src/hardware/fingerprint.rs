@@ -1,13 +1,17 @@
 use super::HardwareInfo;
 use anyhow::Result;
+use once_cell::sync::Lazy;
 use rand::Rng;
 use serde_json::json;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
 use tracing::{debug, info, warn};
 
 // 全局硬件指纹和用户ID管理 - 确保会话期间指纹固定
 static GLOBAL_HARDWARE_FINGERPRINT: OnceLock<HardwareFingerprint> = OnceLock::new();
 static CURRENT_USER_ID: OnceLock<i64> = OnceLock::new();
+// 额外B站账号各自的硬件指纹，键为账号 ID
+static ACCOUNT_HARDWARE_FINGERPRINTS: Lazy<RwLock<HashMap<i32, HardwareFingerprint>>> = Lazy::new(Default::default);
 
 #[derive(Debug, Clone)]
 pub struct HardwareFingerprint {
@@ -533,6 +537,30 @@ impl HardwareFingerprint {
         Ok(())
     }
 
+    // 为额外绑定的B站账号加载或创建硬件指纹，账号之间互不影响
+    pub async fn init_for_account(credential_id: i32, user_id: i64, db: &sea_orm::DatabaseConnection) -> Result<()> {
+        let fingerprint = Self::load_or_create_for_user(user_id, db, false).await?;
+        if let Ok(mut fingerprints) = ACCOUNT_HARDWARE_FINGERPRINTS.write() {
+            fingerprints.insert(credential_id, fingerprint);
+        }
+        debug!("B站账号 {}（用户 {}）的硬件指纹已初始化", credential_id, user_id);
+        Ok(())
+    }
+
+    // 移除已删除账号的硬件指纹缓存
+    pub fn forget_account(credential_id: i32) {
+        if let Ok(mut fingerprints) = ACCOUNT_HARDWARE_FINGERPRINTS.write() {
+            fingerprints.remove(&credential_id);
+        }
+    }
+
+    // 获取指定账号的硬件指纹，默认账号或未初始化的账号使用全局指纹
+    pub fn for_account(credential_id: Option<i32>) -> Self {
+        credential_id
+            .and_then(|id| ACCOUNT_HARDWARE_FINGERPRINTS.read().ok()?.get(&id).cloned())
+            .unwrap_or_default()
+    }
+
     // 记录硬件指纹详细信息
     fn log_fingerprint_details(fingerprint: &HardwareFingerprint, is_new: bool) {
         let action = if is_new { "生成" } else { "加载" };
@@ -10,6 +10,7 @@ mod config;
 mod database;
 mod downloader;
 mod error;
+mod events;
 mod hardware;
 mod http;
 mod ingest_log;
@@ -18,7 +19,14 @@ mod task;
 mod unified_downloader;
 mod utils;
 mod workflow;
+mod workflow_comments;
 mod workflow_danmaku;
+mod workflow_highlight;
+mod workflow_preview;
+mod workflow_quality_upgrade;
+mod workflow_subtitle_generation;
+mod workflow_subtitle_translation;
+mod workflow_video_summary;
 
 use std::fmt::Debug;
 use std::future::Future;
@@ -77,6 +85,7 @@ async fn async_main() -> Result<()> {
         debug!("硬件指纹初始化跳过: {}", e);
         // 无有效用户ID时跳过硬件指纹初始化，等待用户登录后再初始化
     }
+    init_hardware_fingerprint_for_accounts(&connection).await;
 
     // 恢复断点信息到内存
     if let Err(e) = crate::utils::submission_checkpoint::restore_checkpoints_from_db(&connection).await {
@@ -166,6 +175,30 @@ async fn async_main() -> Result<()> {
         &tracker,
         token.clone(),
     );
+    spawn_task(
+        "数据库定时备份",
+        crate::database::backup::backup_scheduler(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
+    spawn_task(
+        "声明式配置同步",
+        crate::config::declarative_config_watcher(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
+    spawn_task(
+        "事件总线",
+        crate::events::event_writer(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
+    spawn_task(
+        "Webhook 推送",
+        crate::events::webhook_dispatcher(connection.clone()),
+        &tracker,
+        token.clone(),
+    );
     spawn_task("定时下载", video_downloader(connection), &tracker, token.clone());
 
     tracker.close();
@@ -261,6 +294,20 @@ async fn init_hardware_fingerprint_for_user(connection: &sea_orm::DatabaseConnec
     Ok(())
 }
 
+/// 初始化额外B站账号各自的硬件指纹
+async fn init_hardware_fingerprint_for_accounts(connection: &sea_orm::DatabaseConnection) {
+    let config = crate::config::reload_config();
+    for account in &config.bili_accounts {
+        let Some(user_id) = account.user_id() else {
+            debug!("B站账号 {} 的用户ID无效，跳过硬件指纹初始化", account.id);
+            continue;
+        };
+        if let Err(e) = HardwareFingerprint::init_for_account(account.id, user_id, connection).await {
+            warn!("B站账号 {} 的硬件指纹初始化失败: {:#}", account.id, e);
+        }
+    }
+}
+
 /// 完成全局系统清理
 async fn finalize_global_systems() {
     // 最后一次保存断点信息（双重保险）
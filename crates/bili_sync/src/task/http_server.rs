@@ -8,14 +8,18 @@ use axum::routing::{delete, get, post, put};
 use axum::{middleware, Extension, Router, ServiceExt};
 use reqwest::StatusCode;
 use rust_embed::Embed;
-use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
 use tracing::{debug, error, info, warn};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::{Config, SwaggerUi};
 
-use crate::api::auth;
+use crate::api::auth::{self, RouteAccess};
 use crate::api::handler::{
+    add_api_key,
+    add_api_user,
+    add_bili_account,
     add_video_source,
+    add_webhook,
     ai_rename_history,
     batch_update_config_internal,
     cancel_queue_task,
@@ -23,26 +27,44 @@ use crate::api::handler::{
     clear_ai_rename_cache,
     clear_ai_rename_cache_for_source,
     clear_credential,
+    create_database_backup,
+    create_stream_link,
+    delete_api_user,
+    delete_bili_account,
+    delete_database_backup,
     delete_video,
     delete_video_source,
+    delete_webhook,
     download_log_file,
+    dry_run_quality_profile,
+    export_bundle,
     generate_qr_code,
+    get_api_keys,
+    get_api_users,
+    get_audit_log,
     get_bangumi_seasons,
     get_bangumi_sources_for_merge,
     get_beta_image_update_status,
+    get_bili_accounts,
     get_config,
     get_config_history,
     // 新增配置管理API
     get_config_item,
     get_config_migration_status,
+    get_current_identity,
     get_current_user,
     get_dashboard_data,
+    get_database_backups,
+    get_declarative_config,
+    get_events,
     get_hot_reload_status,
     get_latest_ingests,
     get_log_files,
     get_logs,
     get_notification_config,
     get_notification_status,
+    get_page_danmaku_highlights,
+    get_quality_profiles,
     get_queue_status,
     get_recent_ingests,
     get_submission_videos,
@@ -59,10 +81,16 @@ use crate::api::handler::{
     get_video_source_keyword_filters,
     get_video_sources,
     get_videos,
+    get_webhook_deliveries,
+    get_webhooks,
+    import_bundle,
+    login,
+    logout,
     migrate_config_schema,
     pause_scanning_endpoint,
     poll_qr_status,
     preview_filename_templates,
+    preview_video_source,
     proxy_image,
     proxy_video_stream,
     refresh_page_danmaku,
@@ -70,14 +98,19 @@ use crate::api::handler::{
     refresh_video_danmaku,
     reload_config,
     reload_config_new_internal,
+    rerender_danmaku,
     reset_all_videos,
     reset_specific_tasks,
     reset_video,
     reset_video_source_path,
+    restore_database_backup,
     resume_scanning_endpoint,
     retry_charge_videos_for_source,
+    retry_webhook_delivery,
+    revoke_api_key,
     search_bilibili,
     setup_auth_token,
+    stream_events,
     stream_logs,
     stream_queue_status,
     stream_video_sources,
@@ -85,17 +118,22 @@ use crate::api::handler::{
     test_credential_refresh,
     test_notification_handler,
     test_risk_control_handler,
+    update_api_user,
+    update_bili_account,
     update_config,
     update_config_item_internal,
     update_credential,
     update_notification_config,
+    update_quality_profiles,
     update_submission_selected_videos,
+    update_video_source_credential,
     update_video_source_download_options,
     update_video_source_enabled,
     update_video_source_keyword_filters,
     update_video_source_scan_deleted,
     update_video_source_scan_deleted_once,
     update_video_status,
+    update_webhook,
     validate_config,
     validate_favorite,
     validate_regex_pattern,
@@ -104,7 +142,9 @@ use crate::api::handler::{
 use crate::api::request::{BatchUpdateConfigRequest, UpdateConfigItemRequest};
 use crate::api::video_stream::stream_video;
 use crate::api::wrapper::ApiResponse;
-use crate::api::ws;
+use crate::api::{v1, ws};
+use crate::auth::api_keys::ApiScope;
+use crate::auth::users::Role;
 use crate::bilibili::{get_captcha_info, serve_captcha_page, submit_captcha_result};
 use crate::utils::model::queue_missing_video_file_size_backfill;
 // CONFIG导入已移除 - 现在使用动态配置
@@ -113,34 +153,42 @@ use crate::utils::model::queue_missing_video_file_size_backfill;
 #[folder = "../../web/build"]
 struct Asset;
 
+/// 启动时检查的关键表
+const KEY_TABLES: [&str; 7] = [
+    "collection",
+    "favorite",
+    "submission",
+    "watch_later",
+    "video_source",
+    "video",
+    "page",
+];
+
 /// 测试数据库连接是否有效
 async fn test_db_connection(db: &DatabaseConnection) -> bool {
-    match db
-        .execute(Statement::from_string(DatabaseBackend::Sqlite, "SELECT 1"))
-        .await
-    {
+    match db.execute(crate::database::raw_statement("SELECT 1")).await {
         Ok(_) => {
             debug!("数据库连接测试成功");
 
             // 进一步检查关键表是否存在
-            let table_check_sql = "SELECT name FROM sqlite_master WHERE type='table' AND name IN ('collection','favorite','submission','watch_later','video_source','video','page')";
-            match db
-                .query_all(Statement::from_string(DatabaseBackend::Sqlite, table_check_sql))
-                .await
-            {
-                Ok(tables) => {
-                    debug!("数据库中发现 {} 个关键表", tables.len());
-                    if tables.len() < 7 {
-                        warn!("数据库连接有效但缺少关键表，发现表数量: {}/7", tables.len());
-                        false
-                    } else {
-                        true
+            let dialect = crate::database::dialect::current();
+            let mut found = 0;
+            for table in KEY_TABLES {
+                match dialect.table_exists(db, table).await {
+                    Ok(true) => found += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        warn!("检查数据库表失败: {}", e);
+                        return false;
                     }
                 }
-                Err(e) => {
-                    warn!("检查数据库表失败: {}", e);
-                    false
-                }
+            }
+            debug!("数据库中发现 {} 个关键表", found);
+            if found < KEY_TABLES.len() {
+                warn!("数据库连接有效但缺少关键表，发现表数量: {}/{}", found, KEY_TABLES.len());
+                false
+            } else {
+                true
             }
         }
         Err(e) => {
@@ -165,10 +213,159 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         _database_connection
     };
     let app = Router::new()
+        .merge(public_routes())
+        .merge(viewer_routes())
+        .merge(operator_read_routes())
+        .merge(source_management_routes())
+        .merge(queue_control_routes())
+        .merge(admin_routes())
+        .merge(v1::router())
+        // 先应用认证中间件
+        .layer(Extension(optimized_connection.clone()))
+        .layer(middleware::from_fn(auth::auth))
+        // WebSocket API需要在认证中间件之后
+        .merge(ws::router())
+        .merge(
+            SwaggerUi::new("/swagger-ui/")
+                .url("/api-docs/openapi.json", ApiDoc::openapi())
+                .url("/api-docs/v1/openapi.json", v1::V1ApiDoc::openapi())
+                .config(
+                    Config::default()
+                        .try_it_out_enabled(true)
+                        .persist_authorization(true)
+                        .validator_url("none"),
+                ),
+        )
+        .fallback_service(get(frontend_files));
+    // 使用动态配置而非静态CONFIG
+    // 启动周期性数据库连接健康检查
+    let health_check_connection = optimized_connection.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(120)); // 每2分钟检查一次，更快发现问题
+        loop {
+            interval.tick().await;
+
+            if !test_db_connection(&health_check_connection).await {
+                error!("数据库连接健康检查失败！HTTP API可能无法正常工作");
+
+                // mmap模式下不会有表丢失问题，直接报告错误
+                error!("数据库连接问题，建议检查数据库状态或重启服务");
+            } else {
+                debug!("数据库连接健康检查通过");
+            }
+        }
+    });
+
+    let file_size_backfill_connection = optimized_connection.clone();
+    tokio::spawn(async move {
+        match queue_missing_video_file_size_backfill(file_size_backfill_connection).await {
+            Ok(queued) if queued > 0 => info!("已启动后台文件大小统计，待处理视频 {} 条", queued),
+            Ok(_) => debug!("没有需要后台统计文件大小的视频"),
+            Err(error) => warn!("启动后台文件大小统计失败: {error:#}"),
+        }
+    });
+
+    let config = crate::config::reload_config();
+    let listener = tokio::net::TcpListener::bind(&config.bind_address)
+        .await
+        .context("bind address failed")?;
+    info!("开始运行管理页: http://{}", config.bind_address);
+    Ok(axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await?)
+}
+
+/// 无需登录即可访问的接口（初始设置、扫码登录、播放器取流、风控验证等）
+fn public_routes() -> Router {
+    Router::new()
+        // 初始设置API路由
+        .route("/api/setup/check", get(check_initial_setup))
+        .route("/api/setup/auth-token", post(setup_auth_token))
+        .route("/api/credential", put(update_credential))
+        .route("/api/auth/login", post(login))
+        // 扫码登录API路由
+        .route("/api/auth/qr/generate", post(generate_qr_code))
+        .route("/api/auth/qr/poll", get(poll_qr_status))
+        .route("/api/auth/current-user", get(get_current_user))
+        .route("/api/auth/clear-credential", post(clear_credential))
+        .route("/api/search", get(search_bilibili))
+        .route("/api/proxy/image", get(proxy_image))
+        // 测试API
+        .route("/api/test/risk-control", post(test_risk_control_handler))
+        // 视频流API
+        .route("/api/videos/stream/{video_id}", get(stream_video))
+        .route("/api/videos/{video_id}/cover", get(get_video_local_cover))
+        .route("/api/videos/proxy-stream", get(proxy_video_stream))
+        // 验证码相关API
+        .route("/captcha", get(serve_captcha_page))
+        .route("/api/captcha/info", get(get_captcha_info))
+        .route("/api/captcha/submit", post(submit_captcha_result))
+}
+
+/// 只读用户可访问的浏览与播放接口
+fn viewer_routes() -> Router {
+    Router::new()
+        .route("/api/auth/me", get(get_current_identity))
+        .route("/api/auth/logout", post(logout))
         .route("/api/video-sources", get(get_video_sources))
         .route("/api/video-sources/live", get(stream_video_sources))
-        .route("/api/video-sources", post(add_video_source))
         .route("/api/video-sources/bangumi/list", get(get_bangumi_sources_for_merge))
+        .route(
+            "/api/video-sources/{source_type}/{id}/keyword-filters",
+            get(get_video_source_keyword_filters),
+        )
+        .route("/api/videos", get(get_videos))
+        .route("/api/videos/live", get(stream_videos))
+        .route("/api/videos/{id}", get(get_video))
+        .route("/api/dashboard", get(get_dashboard_data))
+        .route("/api/queue/live", get(stream_queue_status))
+        .route("/api/bangumi/seasons/{season_id}", get(get_bangumi_seasons))
+        .route("/api/user/favorites", get(get_user_favorites))
+        .route("/api/user/{uid}/favorites", get(get_user_favorites_by_uid))
+        .route("/api/favorite/{fid}/validate", get(validate_favorite))
+        .route("/api/user/collections/{mid}", get(get_user_collections))
+        .route("/api/user/followings", get(get_user_followings))
+        .route("/api/user/subscribed-collections", get(get_subscribed_collections))
+        .route("/api/submission/{up_id}/videos", get(get_submission_videos))
+        .route("/api/queue-status", get(get_queue_status))
+        .route("/api/task-control/status", get(get_task_control_status))
+        .route("/api/ingest/latest", get(get_latest_ingests))
+        .route("/api/ingest/recent", get(get_recent_ingests))
+        .route("/api/events", get(get_events))
+        .route("/api/events/stream", get(stream_events))
+        .route("/api/notification/status", get(get_notification_status))
+        // 新增在线播放API
+        .route("/api/videos/{video_id}/play-info", get(get_video_play_info))
+        .route("/api/videos/{video_id}/bvid", get(get_video_bvid))
+        .route("/api/pages/{id}/danmaku/highlights", get(get_page_danmaku_highlights))
+        .route("/api/stream-links", post(create_stream_link))
+        // beta 镜像更新检查（前端角标提示）
+        .route("/api/updates/beta", get(get_beta_image_update_status))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Viewer, Some(ApiScope::ReadOnly)),
+            auth::require_access,
+        ))
+}
+
+/// 操作员可访问的只读诊断接口：日志与画质档案
+fn operator_read_routes() -> Router {
+    Router::new()
+        .route("/api/logs", get(get_logs))
+        .route("/api/logs/stream", get(stream_logs))
+        .route("/api/logs/files", get(get_log_files))
+        .route("/api/logs/download", get(download_log_file))
+        // 画质档案API
+        .route("/api/config/quality-profiles", get(get_quality_profiles))
+        .route("/api/config/quality-profiles/dry-run", post(dry_run_quality_profile))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Operator, Some(ApiScope::ReadOnly)),
+            auth::require_access,
+        ))
+}
+
+/// 操作员可访问的视频源管理接口
+fn source_management_routes() -> Router {
+    Router::new()
+        .route("/api/video-sources", post(add_video_source))
+        .route("/api/video-sources/preview", post(preview_video_source))
         .route(
             "/api/video-sources/{source_type}/{id}/enabled",
             put(update_video_source_enabled),
@@ -181,6 +378,10 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
             "/api/video-sources/{source_type}/{id}/scan-deleted-once",
             put(update_video_source_scan_deleted_once),
         )
+        .route(
+            "/api/video-sources/{source_type}/{id}/credential",
+            put(update_video_source_credential),
+        )
         .route(
             "/api/video-sources/{source_type}/{id}/download-options",
             put(update_video_source_download_options),
@@ -200,28 +401,50 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         )
         .route(
             "/api/video-sources/{source_type}/{id}/keyword-filters",
-            put(update_video_source_keyword_filters).get(get_video_source_keyword_filters),
+            put(update_video_source_keyword_filters),
         )
         .route("/api/validate-regex", post(validate_regex_pattern))
         .route("/api/ai-rename/clear-cache", post(clear_ai_rename_cache))
-        .route("/api/ai-rename/clear-cache/{source_type}/{id}", post(clear_ai_rename_cache_for_source))
+        .route(
+            "/api/ai-rename/clear-cache/{source_type}/{id}",
+            post(clear_ai_rename_cache_for_source),
+        )
         .route("/api/{source_type}/{id}/ai-rename-history", post(ai_rename_history))
-        .route("/api/videos", get(get_videos))
-        .route("/api/videos/live", get(stream_videos))
-        .route("/api/videos/{id}", get(get_video))
         .route("/api/videos/{id}", delete(delete_video))
+        .route("/api/config/name-preview", post(preview_filename_templates))
+        .route_layer(middleware::from_fn(auth::guard_declarative_sources))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Operator, Some(ApiScope::SourceManagement)),
+            auth::require_access,
+        ))
+}
+
+/// 操作员可访问的队列控制接口：暂停/恢复扫描、取消队列任务、重置视频任务
+fn queue_control_routes() -> Router {
+    Router::new()
         .route("/api/videos/{id}/refresh-danmaku", post(refresh_video_danmaku))
         .route("/api/videos/{id}/reset", post(reset_video))
         .route("/api/videos/{id}/update-status", post(update_video_status))
         .route("/api/pages/{id}/refresh-danmaku", post(refresh_page_danmaku))
+        .route("/api/danmaku/rerender", post(rerender_danmaku))
         .route("/api/videos/reset-all", post(reset_all_videos))
         .route("/api/videos/reset-specific-tasks", post(reset_specific_tasks))
-        .route("/api/dashboard", get(get_dashboard_data))
-        .route("/api/queue/live", get(stream_queue_status))
+        .route("/api/queue/tasks/{task_id}", delete(cancel_queue_task))
+        .route("/api/task-control/pause", post(pause_scanning_endpoint))
+        .route("/api/task-control/resume", post(resume_scanning_endpoint))
+        .route("/api/task-control/refresh", post(refresh_scanning_endpoint))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Operator, Some(ApiScope::QueueControl)),
+            auth::require_access,
+        ))
+}
+
+/// 管理员专属接口：配置、B站凭证、推送通知、用户与审计日志
+fn admin_routes() -> Router {
+    Router::new()
         .route("/api/reload-config", post(reload_config))
         .route("/api/config", get(get_config))
         .route("/api/config", put(update_config))
-        .route("/api/config/name-preview", post(preview_filename_templates))
         // 新的配置管理API路由
         .route("/api/config/item/{key}", get(get_config_item))
         .route(
@@ -254,108 +477,40 @@ pub async fn http_server(_database_connection: Arc<DatabaseConnection>) -> Resul
         .route("/api/config/migrate", post(migrate_config_schema))
         .route("/api/config/validate", post(validate_config))
         .route("/api/config/hot-reload/status", get(get_hot_reload_status))
-        // 初始设置API路由
-        .route("/api/setup/check", get(check_initial_setup))
-        .route("/api/setup/auth-token", post(setup_auth_token))
-        .route("/api/credential", put(update_credential))
+        .route("/api/config/declarative", get(get_declarative_config))
         .route("/api/credential/test-refresh", post(test_credential_refresh))
-        // 扫码登录API路由
-        .route("/api/auth/qr/generate", post(generate_qr_code))
-        .route("/api/auth/qr/poll", get(poll_qr_status))
-        .route("/api/auth/current-user", get(get_current_user))
-        .route("/api/auth/clear-credential", post(clear_credential))
-        .route("/api/bangumi/seasons/{season_id}", get(get_bangumi_seasons))
-        .route("/api/search", get(search_bilibili))
-        .route("/api/user/favorites", get(get_user_favorites))
-        .route("/api/user/{uid}/favorites", get(get_user_favorites_by_uid))
-        .route("/api/favorite/{fid}/validate", get(validate_favorite))
-        .route("/api/user/collections/{mid}", get(get_user_collections))
-        .route("/api/user/followings", get(get_user_followings))
-        .route("/api/user/subscribed-collections", get(get_subscribed_collections))
-        .route("/api/submission/{up_id}/videos", get(get_submission_videos))
-        .route("/api/logs", get(get_logs))
-        .route("/api/logs/stream", get(stream_logs))
-        .route("/api/logs/files", get(get_log_files))
-        .route("/api/logs/download", get(download_log_file))
-        .route("/api/queue-status", get(get_queue_status))
-        .route("/api/queue/tasks/{task_id}", delete(cancel_queue_task))
-        .route("/api/proxy/image", get(proxy_image))
-        .route("/api/task-control/status", get(get_task_control_status))
-        .route("/api/task-control/pause", post(pause_scanning_endpoint))
-        .route("/api/task-control/resume", post(resume_scanning_endpoint))
-        .route("/api/task-control/refresh", post(refresh_scanning_endpoint))
-        .route("/api/ingest/latest", get(get_latest_ingests))
-        .route("/api/ingest/recent", get(get_recent_ingests))
+        .route("/api/credential/accounts", get(get_bili_accounts).post(add_bili_account))
+        .route(
+            "/api/credential/accounts/{id}",
+            put(update_bili_account).delete(delete_bili_account),
+        )
         // 推送通知API
         .route("/api/notification/test", post(test_notification_handler))
         .route("/api/config/notification", get(get_notification_config))
         .route("/api/config/notification", post(update_notification_config))
-        .route("/api/notification/status", get(get_notification_status))
-        // 测试API
-        .route("/api/test/risk-control", post(test_risk_control_handler))
-        // 视频流API
-        .route("/api/videos/stream/{video_id}", get(stream_video))
-        // 新增在线播放API
-        .route("/api/videos/{video_id}/cover", get(get_video_local_cover))
-        .route("/api/videos/{video_id}/play-info", get(get_video_play_info))
-        .route("/api/videos/{video_id}/bvid", get(get_video_bvid))
-        .route("/api/videos/proxy-stream", get(proxy_video_stream))
-        // beta 镜像更新检查（前端角标提示）
-        .route("/api/updates/beta", get(get_beta_image_update_status))
-        // 验证码相关API
-        .route("/captcha", get(serve_captcha_page))
-        .route("/api/captcha/info", get(get_captcha_info))
-        .route("/api/captcha/submit", post(submit_captcha_result))
-        // 先应用认证中间件
-        .layer(Extension(optimized_connection.clone()))
-        .layer(middleware::from_fn(auth::auth))
-        // WebSocket API需要在认证中间件之后
-        .merge(ws::router())
-        .merge(
-            SwaggerUi::new("/swagger-ui/")
-                .url("/api-docs/openapi.json", ApiDoc::openapi())
-                .config(
-                    Config::default()
-                        .try_it_out_enabled(true)
-                        .persist_authorization(true)
-                        .validator_url("none"),
-                ),
-        )
-        .fallback_service(get(frontend_files));
-    // 使用动态配置而非静态CONFIG
-    // 启动周期性数据库连接健康检查
-    let health_check_connection = optimized_connection.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(120)); // 每2分钟检查一次，更快发现问题
-        loop {
-            interval.tick().await;
-
-            if !test_db_connection(&health_check_connection).await {
-                error!("数据库连接健康检查失败！HTTP API可能无法正常工作");
-
-                // mmap模式下不会有表丢失问题，直接报告错误
-                error!("数据库连接问题，建议检查数据库状态或重启服务");
-            } else {
-                debug!("数据库连接健康检查通过");
-            }
-        }
-    });
-
-    let file_size_backfill_connection = optimized_connection.clone();
-    tokio::spawn(async move {
-        match queue_missing_video_file_size_backfill(file_size_backfill_connection).await {
-            Ok(queued) if queued > 0 => info!("已启动后台文件大小统计，待处理视频 {} 条", queued),
-            Ok(_) => debug!("没有需要后台统计文件大小的视频"),
-            Err(error) => warn!("启动后台文件大小统计失败: {error:#}"),
-        }
-    });
-
-    let config = crate::config::reload_config();
-    let listener = tokio::net::TcpListener::bind(&config.bind_address)
-        .await
-        .context("bind address failed")?;
-    info!("开始运行管理页: http://{}", config.bind_address);
-    Ok(axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await?)
+        .route("/api/config/quality-profiles", put(update_quality_profiles))
+        // 管理页用户与审计日志
+        .route("/api/users", get(get_api_users).post(add_api_user))
+        .route("/api/users/{id}", put(update_api_user).delete(delete_api_user))
+        .route("/api/api-keys", get(get_api_keys).post(add_api_key))
+        .route("/api/api-keys/{id}", delete(revoke_api_key))
+        .route("/api/audit-log", get(get_audit_log))
+        // 第三方 Webhook 订阅
+        .route("/api/webhooks", get(get_webhooks).post(add_webhook))
+        .route("/api/webhooks/{id}", put(update_webhook).delete(delete_webhook))
+        .route("/api/webhooks/{id}/deliveries", get(get_webhook_deliveries))
+        .route("/api/webhooks/deliveries/{id}/retry", post(retry_webhook_delivery))
+        // 视频源与设置的导出/导入
+        .route("/api/bundle/export", get(export_bundle))
+        .route("/api/bundle/import", post(import_bundle))
+        // 数据库备份与恢复
+        .route("/api/backups", get(get_database_backups).post(create_database_backup))
+        .route("/api/backups/{name}", delete(delete_database_backup))
+        .route("/api/backups/{name}/restore", post(restore_database_backup))
+        .route_layer(middleware::from_fn_with_state(
+            RouteAccess::new(Role::Admin, None),
+            auth::require_access,
+        ))
 }
 
 async fn frontend_files(uri: Uri) -> impl IntoResponse {
@@ -385,3 +540,21 @@ async fn frontend_files(uri: Uri) -> impl IntoResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_route_groups_merge_without_conflicts() {
+        // 同一路径在不同角色分组中注册不同方法时必须能够合并
+        let _ = Router::new()
+            .merge(public_routes())
+            .merge(viewer_routes())
+            .merge(operator_read_routes())
+            .merge(source_management_routes())
+            .merge(queue_control_routes())
+            .merge(admin_routes())
+            .merge(v1::router());
+    }
+}
@@ -6,6 +6,7 @@ pub use video_downloader::{credential_refresh_scheduler, video_downloader};
 
 use crate::utils::live_updates::{notify_queue_status_changed, notify_videos_changed};
 use crate::utils::time_format::now_standard_string;
+use crate::workflow_danmaku::RerenderOutcome;
 use anyhow::Result;
 use bili_sync_entity::task_queue::{self, Entity as TaskQueueEntity, TaskStatus, TaskType};
 use sea_orm::{
@@ -50,8 +51,14 @@ pub struct AddVideoSourceTask {
     pub collection_type: Option<String>,
     pub collection_aggregate_enabled: Option<bool>,
     #[serde(default)]
+    pub ranking_type: Option<String>,
+    #[serde(default)]
     pub filter_option: Option<crate::bilibili::FilterOption>,
     #[serde(default)]
+    pub quality_profile: Option<String>,
+    #[serde(default)]
+    pub credential_id: Option<i32>,
+    #[serde(default)]
     pub download_charge_videos: Option<bool>,
     pub media_id: Option<String>,
     pub ep_id: Option<String>,
@@ -101,6 +108,7 @@ pub struct UpdateConfigTask {
     pub danmaku_bold: Option<bool>,
     pub danmaku_outline: Option<f64>,
     pub danmaku_time_offset: Option<f64>,
+    pub danmaku_font_path: Option<String>,
     pub danmaku_update_enabled: Option<bool>,
     pub danmaku_update_fresh_days: Option<u32>,
     pub danmaku_update_fresh_interval_hours: Option<u32>,
@@ -108,6 +116,10 @@ pub struct UpdateConfigTask {
     pub danmaku_update_mature_interval_days: Option<u32>,
     pub danmaku_update_cold_days: Option<u32>,
     pub danmaku_update_cold_interval_days: Option<u32>,
+    pub quality_upgrade_enabled: Option<bool>,
+    pub quality_upgrade_window_hours: Option<u32>,
+    pub quality_upgrade_check_interval_hours: Option<u32>,
+    pub quality_upgrade_max_pages_per_run: Option<u32>,
     // 并发控制设置
     pub concurrent_video: Option<usize>,
     pub concurrent_page: Option<usize>,
@@ -178,6 +190,55 @@ pub struct RefreshDanmakuTask {
     pub task_id: String, // 唯一任务ID，用于追踪
 }
 
+/// 重新渲染弹幕 ASS 任务结构体，未指定视频源时处理整个媒体库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerenderDanmakuTask {
+    pub source_type: Option<String>,
+    pub source_id: Option<i32>,
+    pub task_id: String, // 唯一任务ID，用于追踪
+}
+
+impl RerenderDanmakuTask {
+    pub fn description(&self) -> String {
+        match (&self.source_type, self.source_id) {
+            (Some(source_type), Some(source_id)) => format!("重新渲染弹幕 {}:{}", source_type, source_id),
+            _ => "重新渲染全部弹幕".to_string(),
+        }
+    }
+}
+
+/// 正在执行的弹幕重新渲染任务进度
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RerenderDanmakuProgress {
+    pub task_id: String,
+    pub description: String,
+    /// 待处理的分页总数
+    pub total: usize,
+    /// 已处理的分页数
+    pub processed: usize,
+    /// 已重新生成 ASS 的分页数
+    pub rendered: usize,
+    /// 没有本地文件而跳过的分页数
+    pub skipped: usize,
+    /// 没有原始弹幕归档而无法重新渲染的分页数
+    pub no_archive: usize,
+    pub failed: usize,
+}
+
+/// 本地语音识别字幕生成任务结构体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateSubtitleTask {
+    pub page_id: i32,
+    pub task_id: String, // 唯一任务ID，用于追踪
+}
+
+/// 画质升级复查任务结构体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradePageQualityTask {
+    pub page_id: i32,
+    pub task_id: String, // 唯一任务ID，用于追踪
+}
+
 /// 删除任务队列管理器
 pub struct DeleteTaskQueue {
     /// 待处理的删除任务队列（内存缓存）
@@ -1132,7 +1193,7 @@ async fn collect_video_source_base_paths_task(
     db: Arc<DatabaseConnection>,
     video: &bili_sync_entity::video::Model,
 ) -> Result<Vec<String>, anyhow::Error> {
-    use bili_sync_entity::{collection, favorite, submission, video_source, watch_later};
+    use bili_sync_entity::{collection, favorite, ranking, submission, video_source, watch_later};
     use std::collections::HashSet;
 
     let mut unique_paths = HashSet::new();
@@ -1185,6 +1246,16 @@ async fn collect_video_source_base_paths_task(
         }
     }
 
+    if let Some(ranking_id) = video.ranking_id {
+        if let Some(ranking) = ranking::Entity::find_by_id(ranking_id)
+            .one(db.as_ref())
+            .await
+            .map_err(|e| anyhow::anyhow!("查询榜单路径失败: {}", e))?
+        {
+            push_path(ranking.path);
+        }
+    }
+
     if let Some(source_id) = video.source_id {
         if let Some(source) = video_source::Entity::find_by_id(source_id)
             .one(db.as_ref())
@@ -1679,6 +1750,607 @@ impl RefreshDanmakuTaskQueue {
     }
 }
 
+/// 弹幕 ASS 重新渲染任务队列管理器
+pub struct RerenderDanmakuTaskQueue {
+    queue: Mutex<VecDeque<RerenderDanmakuTask>>,
+    is_processing: AtomicBool,
+    progress: std::sync::Mutex<Option<RerenderDanmakuProgress>>,
+}
+
+impl RerenderDanmakuTaskQueue {
+    pub fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            is_processing: AtomicBool::new(false),
+            progress: std::sync::Mutex::new(None),
+        }
+    }
+
+    pub async fn enqueue_task(&self, task: RerenderDanmakuTask, connection: &DatabaseConnection) -> Result<bool> {
+        {
+            let queue = self.queue.lock().await;
+            if queue
+                .iter()
+                .any(|item| item.source_type == task.source_type && item.source_id == task.source_id)
+            {
+                debug!("弹幕重新渲染任务已存在，跳过重复创建: {}", task.description());
+                return Ok(false);
+            }
+        }
+
+        let task_data = serde_json::to_string(&task)?;
+        let active_model = task_queue::ActiveModel {
+            task_type: Set(TaskType::RerenderDanmaku),
+            task_data: Set(task_data),
+            status: Set(TaskStatus::Pending),
+            retry_count: Set(0),
+            created_at: Set(now_standard_string()),
+            updated_at: Set(now_standard_string()),
+            ..Default::default()
+        };
+        let result = active_model.insert(connection).await?;
+
+        let mut queue = self.queue.lock().await;
+        info!(
+            "弹幕重新渲染任务已加入队列: {}, 队列长度: {} (数据库ID: {})",
+            task.description(),
+            queue.len() + 1,
+            result.id
+        );
+        queue.push_back(task);
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub async fn dequeue_task(&self) -> Option<RerenderDanmakuTask> {
+        let mut queue = self.queue.lock().await;
+        let task = queue.pop_front();
+        if task.is_some() {
+            notify_queue_status_changed();
+        }
+        task
+    }
+
+    async fn mark_task_status(
+        &self,
+        task: &RerenderDanmakuTask,
+        status: TaskStatus,
+        connection: &DatabaseConnection,
+    ) -> Result<()> {
+        let task_data = serde_json::to_string(task)?;
+        if let Some(db_task) = TaskQueueEntity::find()
+            .filter(task_queue::Column::TaskType.eq(TaskType::RerenderDanmaku))
+            .filter(task_queue::Column::TaskData.eq(&task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .one(connection)
+            .await?
+        {
+            let retry_count = db_task.retry_count;
+            let mut active_model: task_queue::ActiveModel = db_task.into();
+            if status == TaskStatus::Failed {
+                active_model.retry_count = Set(retry_count + 1);
+            }
+            active_model.status = Set(status);
+            active_model.updated_at = Set(now_standard_string());
+            active_model.update(connection).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn list_tasks(&self) -> Vec<RerenderDanmakuTask> {
+        let queue = self.queue.lock().await;
+        queue.iter().cloned().collect()
+    }
+
+    pub async fn cancel_task(&self, task_id: &str, connection: &DatabaseConnection) -> Result<bool> {
+        let removed_task = {
+            let mut queue = self.queue.lock().await;
+            if let Some(index) = queue.iter().position(|task| task.task_id == task_id) {
+                queue.remove(index)
+            } else {
+                None
+            }
+        };
+
+        let Some(task) = removed_task else {
+            return Ok(false);
+        };
+
+        let task_data = serde_json::to_string(&task)?;
+        TaskQueueEntity::delete_many()
+            .filter(task_queue::Column::TaskType.eq(TaskType::RerenderDanmaku))
+            .filter(task_queue::Column::TaskData.eq(task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .exec(connection)
+            .await?;
+
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub fn is_processing(&self) -> bool {
+        self.is_processing.load(Ordering::SeqCst)
+    }
+
+    /// 当前任务的进度，没有正在执行的任务时返回 None
+    pub fn progress(&self) -> Option<RerenderDanmakuProgress> {
+        self.progress.lock().unwrap().clone()
+    }
+
+    fn update_progress(&self, update: impl FnOnce(&mut RerenderDanmakuProgress)) {
+        if let Some(progress) = self.progress.lock().unwrap().as_mut() {
+            update(progress);
+        }
+    }
+
+    pub async fn process_all_tasks(&self, db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+        if self.is_processing.swap(true, Ordering::SeqCst) {
+            debug!("弹幕重新渲染任务队列正在处理中，跳过重复处理");
+            return Ok(0);
+        }
+        notify_queue_status_changed();
+        let mut processed_count = 0u32;
+
+        while let Some(task) = self.dequeue_task().await {
+            let result = self.process_task(&task, db.as_ref()).await;
+            *self.progress.lock().unwrap() = None;
+            match result {
+                Ok(progress) => {
+                    info!(
+                        "弹幕重新渲染任务完成: {}, 共 {} 个分页，重新生成 {} 个，没有原始弹幕归档 {} 个，没有本地文件跳过 {} 个，失败 {} 个",
+                        task.description(),
+                        progress.total,
+                        progress.rendered,
+                        progress.no_archive,
+                        progress.skipped,
+                        progress.failed
+                    );
+                    if progress.no_archive > 0 {
+                        warn!(
+                            "有 {} 个分页没有原始弹幕归档，无法重新渲染；请在配置中开启 danmaku_archive 的 xml 或 jsonl 归档，下次刷新弹幕后即可重新渲染",
+                            progress.no_archive
+                        );
+                    }
+                    processed_count += 1;
+                    if let Err(e) = self.mark_task_status(&task, TaskStatus::Completed, &db).await {
+                        error!("更新弹幕重新渲染任务完成状态失败: {:#}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("弹幕重新渲染任务执行失败: {}, 错误: {:#}", task.description(), e);
+                    if let Err(mark_err) = self.mark_task_status(&task, TaskStatus::Failed, &db).await {
+                        error!("更新弹幕重新渲染任务失败状态失败: {:#}", mark_err);
+                    }
+                }
+            }
+        }
+
+        self.is_processing.store(false, Ordering::SeqCst);
+        notify_queue_status_changed();
+        Ok(processed_count)
+    }
+
+    async fn process_task(
+        &self,
+        task: &RerenderDanmakuTask,
+        connection: &DatabaseConnection,
+    ) -> Result<RerenderDanmakuProgress> {
+        let source = match (&task.source_type, task.source_id) {
+            (Some(source_type), Some(source_id)) => Some((source_type.as_str(), source_id)),
+            _ => None,
+        };
+        let candidates = crate::workflow_danmaku::load_danmaku_rerender_candidates(connection, source).await?;
+        let config = crate::config::reload_config();
+        *self.progress.lock().unwrap() = Some(RerenderDanmakuProgress {
+            task_id: task.task_id.clone(),
+            description: task.description(),
+            total: candidates.iter().map(|(_, pages)| pages.len()).sum(),
+            ..Default::default()
+        });
+        notify_queue_status_changed();
+
+        for (video_model, pages) in candidates {
+            for page_model in pages {
+                let result = crate::workflow_danmaku::rerender_page_danmaku(&config, &video_model, &page_model).await;
+                self.update_progress(|progress| {
+                    progress.processed += 1;
+                    match &result {
+                        Ok(RerenderOutcome::Rendered) => progress.rendered += 1,
+                        Ok(RerenderOutcome::NoLocalFile) => progress.skipped += 1,
+                        Ok(RerenderOutcome::NoArchive) => progress.no_archive += 1,
+                        Err(_) => progress.failed += 1,
+                    }
+                });
+                if let Err(e) = result {
+                    warn!(
+                        "重新渲染视频「{}」分页 pid={} 的弹幕失败: {:#}",
+                        video_model.name, page_model.pid, e
+                    );
+                }
+            }
+            notify_queue_status_changed();
+        }
+
+        Ok(self.progress().unwrap_or_default())
+    }
+}
+
+/// 本地语音识别字幕任务队列管理器
+///
+/// 语音识别非常耗时，队列在后台按 `subtitle_generation.max_concurrent` 并发处理，不阻塞扫描和下载
+pub struct SubtitleGenerationTaskQueue {
+    queue: Mutex<VecDeque<GenerateSubtitleTask>>,
+    is_processing: AtomicBool,
+}
+
+impl SubtitleGenerationTaskQueue {
+    pub fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            is_processing: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn enqueue_task(&self, task: GenerateSubtitleTask, connection: &DatabaseConnection) -> Result<bool> {
+        {
+            let queue = self.queue.lock().await;
+            if queue.iter().any(|item| item.page_id == task.page_id) {
+                debug!("语音识别字幕任务已存在，跳过重复创建: 分页ID={}", task.page_id);
+                return Ok(false);
+            }
+        }
+
+        let task_data = serde_json::to_string(&task)?;
+        let active_model = task_queue::ActiveModel {
+            task_type: Set(TaskType::GenerateSubtitle),
+            task_data: Set(task_data),
+            status: Set(TaskStatus::Pending),
+            retry_count: Set(0),
+            created_at: Set(now_standard_string()),
+            updated_at: Set(now_standard_string()),
+            ..Default::default()
+        };
+        let result = active_model.insert(connection).await?;
+
+        let mut queue = self.queue.lock().await;
+        info!(
+            "语音识别字幕任务已加入队列: 分页ID={}, 队列长度: {} (数据库ID: {})",
+            task.page_id,
+            queue.len() + 1,
+            result.id
+        );
+        queue.push_back(task);
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub async fn dequeue_task(&self) -> Option<GenerateSubtitleTask> {
+        let mut queue = self.queue.lock().await;
+        let task = queue.pop_front();
+        if task.is_some() {
+            notify_queue_status_changed();
+        }
+        task
+    }
+
+    async fn mark_task_status(
+        &self,
+        task: &GenerateSubtitleTask,
+        status: TaskStatus,
+        connection: &DatabaseConnection,
+    ) -> Result<()> {
+        let task_data = serde_json::to_string(task)?;
+        if let Some(db_task) = TaskQueueEntity::find()
+            .filter(task_queue::Column::TaskType.eq(TaskType::GenerateSubtitle))
+            .filter(task_queue::Column::TaskData.eq(&task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .one(connection)
+            .await?
+        {
+            let retry_count = db_task.retry_count;
+            let mut active_model: task_queue::ActiveModel = db_task.into();
+            if status == TaskStatus::Failed {
+                active_model.retry_count = Set(retry_count + 1);
+            }
+            active_model.status = Set(status);
+            active_model.updated_at = Set(now_standard_string());
+            active_model.update(connection).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn list_tasks(&self) -> Vec<GenerateSubtitleTask> {
+        let queue = self.queue.lock().await;
+        queue.iter().cloned().collect()
+    }
+
+    pub async fn cancel_task(&self, task_id: &str, connection: &DatabaseConnection) -> Result<bool> {
+        let removed_task = {
+            let mut queue = self.queue.lock().await;
+            if let Some(index) = queue.iter().position(|task| task.task_id == task_id) {
+                queue.remove(index)
+            } else {
+                None
+            }
+        };
+
+        let Some(task) = removed_task else {
+            return Ok(false);
+        };
+
+        let task_data = serde_json::to_string(&task)?;
+        TaskQueueEntity::delete_many()
+            .filter(task_queue::Column::TaskType.eq(TaskType::GenerateSubtitle))
+            .filter(task_queue::Column::TaskData.eq(task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .exec(connection)
+            .await?;
+
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub fn is_processing(&self) -> bool {
+        self.is_processing.load(Ordering::SeqCst)
+    }
+
+    pub async fn process_all_tasks(&self, db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+        if self.is_processing.swap(true, Ordering::SeqCst) {
+            debug!("语音识别字幕任务队列正在处理中，跳过重复处理");
+            return Ok(0);
+        }
+        notify_queue_status_changed();
+        let max_concurrent = crate::config::reload_config().subtitle_generation.max_concurrent.max(1);
+        let mut running = tokio::task::JoinSet::new();
+        let mut processed_count = 0u32;
+
+        loop {
+            // 暂停期间不再取出新任务，已在运行的识别任务继续完成
+            while running.len() < max_concurrent && !TASK_CONTROLLER.is_paused() {
+                let Some(task) = self.dequeue_task().await else {
+                    break;
+                };
+                let db = db.clone();
+                running.spawn(async move {
+                    let result =
+                        crate::workflow_subtitle_generation::generate_subtitle_for_page(db.as_ref(), task.page_id)
+                            .await;
+                    (task, result)
+                });
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            let (task, result) = match joined {
+                Ok(output) => output,
+                Err(e) => {
+                    error!("语音识别字幕任务异常退出: {:#}", e);
+                    continue;
+                }
+            };
+            match result {
+                Ok(generated) => {
+                    if generated {
+                        info!("语音识别字幕生成完成: 分页ID={}", task.page_id);
+                    } else {
+                        debug!("分页已有字幕或文件不存在，跳过语音识别: 分页ID={}", task.page_id);
+                    }
+                    processed_count += 1;
+                    if let Err(e) = self.mark_task_status(&task, TaskStatus::Completed, &db).await {
+                        error!("更新语音识别字幕任务完成状态失败: {:#}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("语音识别字幕生成失败: 分页ID={}, 错误: {:#}", task.page_id, e);
+                    if let Err(mark_err) = self.mark_task_status(&task, TaskStatus::Failed, &db).await {
+                        error!("更新语音识别字幕任务失败状态失败: {:#}", mark_err);
+                    }
+                }
+            }
+        }
+
+        self.is_processing.store(false, Ordering::SeqCst);
+        notify_queue_status_changed();
+        if processed_count > 0 {
+            info!("语音识别字幕任务队列处理完成，共处理 {} 个任务", processed_count);
+        }
+        Ok(processed_count)
+    }
+}
+
+/// 画质升级任务队列管理器
+///
+/// 复查需要重新取流并整段重新下载，队列在后台按 `quality_upgrade_policy.max_concurrent` 并发处理，不阻塞扫描
+pub struct QualityUpgradeTaskQueue {
+    queue: Mutex<VecDeque<UpgradePageQualityTask>>,
+    is_processing: AtomicBool,
+}
+
+impl QualityUpgradeTaskQueue {
+    pub fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            is_processing: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn enqueue_task(&self, task: UpgradePageQualityTask, connection: &DatabaseConnection) -> Result<bool> {
+        {
+            let queue = self.queue.lock().await;
+            if queue.iter().any(|item| item.page_id == task.page_id) {
+                debug!("画质升级任务已存在，跳过重复创建: 分页ID={}", task.page_id);
+                return Ok(false);
+            }
+        }
+
+        let task_data = serde_json::to_string(&task)?;
+        let active_model = task_queue::ActiveModel {
+            task_type: Set(TaskType::UpgradePageQuality),
+            task_data: Set(task_data),
+            status: Set(TaskStatus::Pending),
+            retry_count: Set(0),
+            created_at: Set(now_standard_string()),
+            updated_at: Set(now_standard_string()),
+            ..Default::default()
+        };
+        let result = active_model.insert(connection).await?;
+
+        let mut queue = self.queue.lock().await;
+        info!(
+            "画质升级任务已加入队列: 分页ID={}, 队列长度: {} (数据库ID: {})",
+            task.page_id,
+            queue.len() + 1,
+            result.id
+        );
+        queue.push_back(task);
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub async fn dequeue_task(&self) -> Option<UpgradePageQualityTask> {
+        let mut queue = self.queue.lock().await;
+        let task = queue.pop_front();
+        if task.is_some() {
+            notify_queue_status_changed();
+        }
+        task
+    }
+
+    async fn mark_task_status(
+        &self,
+        task: &UpgradePageQualityTask,
+        status: TaskStatus,
+        connection: &DatabaseConnection,
+    ) -> Result<()> {
+        let task_data = serde_json::to_string(task)?;
+        if let Some(db_task) = TaskQueueEntity::find()
+            .filter(task_queue::Column::TaskType.eq(TaskType::UpgradePageQuality))
+            .filter(task_queue::Column::TaskData.eq(&task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .one(connection)
+            .await?
+        {
+            let retry_count = db_task.retry_count;
+            let mut active_model: task_queue::ActiveModel = db_task.into();
+            if status == TaskStatus::Failed {
+                active_model.retry_count = Set(retry_count + 1);
+            }
+            active_model.status = Set(status);
+            active_model.updated_at = Set(now_standard_string());
+            active_model.update(connection).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn list_tasks(&self) -> Vec<UpgradePageQualityTask> {
+        let queue = self.queue.lock().await;
+        queue.iter().cloned().collect()
+    }
+
+    pub async fn cancel_task(&self, task_id: &str, connection: &DatabaseConnection) -> Result<bool> {
+        let removed_task = {
+            let mut queue = self.queue.lock().await;
+            if let Some(index) = queue.iter().position(|task| task.task_id == task_id) {
+                queue.remove(index)
+            } else {
+                None
+            }
+        };
+
+        let Some(task) = removed_task else {
+            return Ok(false);
+        };
+
+        let task_data = serde_json::to_string(&task)?;
+        TaskQueueEntity::delete_many()
+            .filter(task_queue::Column::TaskType.eq(TaskType::UpgradePageQuality))
+            .filter(task_queue::Column::TaskData.eq(task_data))
+            .filter(task_queue::Column::Status.eq(TaskStatus::Pending))
+            .exec(connection)
+            .await?;
+
+        notify_queue_status_changed();
+        Ok(true)
+    }
+
+    pub fn is_processing(&self) -> bool {
+        self.is_processing.load(Ordering::SeqCst)
+    }
+
+    pub async fn process_all_tasks(&self, db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+        if self.is_processing.swap(true, Ordering::SeqCst) {
+            debug!("画质升级任务队列正在处理中，跳过重复处理");
+            return Ok(0);
+        }
+        notify_queue_status_changed();
+        let max_concurrent = crate::config::reload_config()
+            .quality_upgrade_policy
+            .max_concurrent
+            .max(1);
+        let bili_client = Arc::new(crate::bilibili::BiliClient::new(String::new()));
+        let mut running = tokio::task::JoinSet::new();
+        let mut processed_count = 0u32;
+
+        loop {
+            // 暂停期间不再取出新任务，已在运行的升级任务继续完成
+            while running.len() < max_concurrent && !TASK_CONTROLLER.is_paused() {
+                let Some(task) = self.dequeue_task().await else {
+                    break;
+                };
+                let db = db.clone();
+                let bili_client = bili_client.clone();
+                running.spawn(async move {
+                    let result = crate::workflow_quality_upgrade::upgrade_page_quality_by_id(
+                        bili_client.as_ref(),
+                        db.as_ref(),
+                        task.page_id,
+                    )
+                    .await;
+                    (task, result)
+                });
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+            let (task, result) = match joined {
+                Ok(output) => output,
+                Err(e) => {
+                    error!("画质升级任务异常退出: {:#}", e);
+                    continue;
+                }
+            };
+            match result {
+                Ok(upgraded) => {
+                    if upgraded {
+                        info!("画质升级完成: 分页ID={}", task.page_id);
+                    } else {
+                        debug!("分页暂无更优的流，无需升级: 分页ID={}", task.page_id);
+                    }
+                    processed_count += 1;
+                    if let Err(e) = self.mark_task_status(&task, TaskStatus::Completed, &db).await {
+                        error!("更新画质升级任务完成状态失败: {:#}", e);
+                    }
+                }
+                Err(e) => {
+                    error!("画质升级失败，保留原文件: 分页ID={}, 错误: {:#}", task.page_id, e);
+                    if let Err(mark_err) = self.mark_task_status(&task, TaskStatus::Failed, &db).await {
+                        error!("更新画质升级任务失败状态失败: {:#}", mark_err);
+                    }
+                }
+            }
+        }
+
+        self.is_processing.store(false, Ordering::SeqCst);
+        notify_queue_status_changed();
+        if processed_count > 0 {
+            info!("画质升级任务队列处理完成，共处理 {} 个任务", processed_count);
+        }
+        Ok(processed_count)
+    }
+}
+
 /// 添加任务队列管理器
 pub struct AddTaskQueue {
     /// 待处理的添加任务队列
@@ -1861,7 +2533,10 @@ impl AddTaskQueue {
                 up_id: task.up_id.clone(),
                 collection_type: task.collection_type.clone(),
                 collection_aggregate_enabled: task.collection_aggregate_enabled,
+                ranking_type: task.ranking_type.clone(),
                 filter_option: task.filter_option.clone(),
+                quality_profile: task.quality_profile.clone(),
+                credential_id: task.credential_id,
                 download_charge_videos: task.download_charge_videos,
                 media_id: task.media_id.clone(),
                 ep_id: task.ep_id.clone(),
@@ -1874,6 +2549,7 @@ impl AddTaskQueue {
                 keyword_filter_mode: None,           // 任务队列中暂时不支持过滤模式
                 audio_only: None,                    // 任务队列中使用默认值
                 download_danmaku: None,              // 任务队列中使用默认值
+                archive_comments: None,              // 任务队列中使用默认值
                 download_subtitle: None,             // 任务队列中使用默认值
                 download_ai_subtitle: None,          // 任务队列中使用默认值
                 ai_subtitle_language: None,          // 任务队列中使用默认值
@@ -2380,6 +3056,7 @@ impl ConfigTaskQueue {
                 danmaku_bold: task.danmaku_bold,
                 danmaku_outline: task.danmaku_outline,
                 danmaku_time_offset: task.danmaku_time_offset,
+                danmaku_font_path: task.danmaku_font_path.clone(),
                 danmaku_update_enabled: task.danmaku_update_enabled,
                 danmaku_update_fresh_days: task.danmaku_update_fresh_days,
                 danmaku_update_fresh_interval_hours: task.danmaku_update_fresh_interval_hours,
@@ -2387,6 +3064,10 @@ impl ConfigTaskQueue {
                 danmaku_update_mature_interval_days: task.danmaku_update_mature_interval_days,
                 danmaku_update_cold_days: task.danmaku_update_cold_days,
                 danmaku_update_cold_interval_days: task.danmaku_update_cold_interval_days,
+                quality_upgrade_enabled: task.quality_upgrade_enabled,
+                quality_upgrade_window_hours: task.quality_upgrade_window_hours,
+                quality_upgrade_check_interval_hours: task.quality_upgrade_check_interval_hours,
+                quality_upgrade_max_pages_per_run: task.quality_upgrade_max_pages_per_run,
                 // 并发控制设置
                 concurrent_video: task.concurrent_video,
                 concurrent_page: task.concurrent_page,
@@ -2427,6 +3108,7 @@ impl ConfigTaskQueue {
                 submission_adaptive_max_hours: task.submission_adaptive_max_hours,
                 // 系统配置相关字段，任务队列中不使用
                 scan_deleted_videos: None,
+                cross_source_dedup: None,
                 // aria2监控配置，任务队列中不使用
                 enable_aria2_health_check: None,
                 enable_aria2_auto_restart: None,
@@ -2729,6 +3411,18 @@ pub static VIDEO_DELETE_TASK_QUEUE: once_cell::sync::Lazy<Arc<VideoDeleteTaskQue
 pub static REFRESH_DANMAKU_TASK_QUEUE: once_cell::sync::Lazy<Arc<RefreshDanmakuTaskQueue>> =
     once_cell::sync::Lazy::new(|| Arc::new(RefreshDanmakuTaskQueue::new()));
 
+/// 全局弹幕重新渲染任务队列实例
+pub static RERENDER_DANMAKU_TASK_QUEUE: once_cell::sync::Lazy<Arc<RerenderDanmakuTaskQueue>> =
+    once_cell::sync::Lazy::new(|| Arc::new(RerenderDanmakuTaskQueue::new()));
+
+/// 全局语音识别字幕任务队列实例
+pub static SUBTITLE_GENERATION_TASK_QUEUE: once_cell::sync::Lazy<Arc<SubtitleGenerationTaskQueue>> =
+    once_cell::sync::Lazy::new(|| Arc::new(SubtitleGenerationTaskQueue::new()));
+
+/// 全局画质升级任务队列实例
+pub static QUALITY_UPGRADE_TASK_QUEUE: once_cell::sync::Lazy<Arc<QualityUpgradeTaskQueue>> =
+    once_cell::sync::Lazy::new(|| Arc::new(QualityUpgradeTaskQueue::new()));
+
 /// 暂停定时扫描任务的便捷函数
 pub async fn pause_scanning() {
     TASK_CONTROLLER.pause().await;
@@ -2889,6 +3583,57 @@ pub async fn process_refresh_danmaku_tasks(db: Arc<DatabaseConnection>) -> Resul
     REFRESH_DANMAKU_TASK_QUEUE.process_all_tasks(db).await
 }
 
+/// 添加弹幕重新渲染任务到队列的便捷函数，已有相同范围的待处理任务时返回 false
+pub async fn enqueue_rerender_danmaku_task(task: RerenderDanmakuTask, connection: &DatabaseConnection) -> Result<bool> {
+    timeout(
+        TASK_ENQUEUE_TIMEOUT,
+        RERENDER_DANMAKU_TASK_QUEUE.enqueue_task(task, connection),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("弹幕重新渲染任务加入队列超时，请稍后重试"))?
+}
+
+/// 处理所有弹幕重新渲染任务的便捷函数
+pub async fn process_rerender_danmaku_tasks(db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+    RERENDER_DANMAKU_TASK_QUEUE.process_all_tasks(db).await
+}
+
+/// 添加语音识别字幕任务到队列的便捷函数，同一分页已在队列中时返回 false
+pub async fn enqueue_generate_subtitle_task(
+    task: GenerateSubtitleTask,
+    connection: &DatabaseConnection,
+) -> Result<bool> {
+    timeout(
+        TASK_ENQUEUE_TIMEOUT,
+        SUBTITLE_GENERATION_TASK_QUEUE.enqueue_task(task, connection),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("语音识别字幕任务加入队列超时，请稍后重试"))?
+}
+
+/// 处理所有语音识别字幕任务的便捷函数
+pub async fn process_generate_subtitle_tasks(db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+    SUBTITLE_GENERATION_TASK_QUEUE.process_all_tasks(db).await
+}
+
+/// 添加画质升级任务到队列的便捷函数，同一分页已在队列中时返回 false
+pub async fn enqueue_upgrade_page_quality_task(
+    task: UpgradePageQualityTask,
+    connection: &DatabaseConnection,
+) -> Result<bool> {
+    timeout(
+        TASK_ENQUEUE_TIMEOUT,
+        QUALITY_UPGRADE_TASK_QUEUE.enqueue_task(task, connection),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("画质升级任务加入队列超时，请稍后重试"))?
+}
+
+/// 处理所有画质升级任务的便捷函数
+pub async fn process_upgrade_page_quality_tasks(db: Arc<DatabaseConnection>) -> Result<u32, anyhow::Error> {
+    QUALITY_UPGRADE_TASK_QUEUE.process_all_tasks(db).await
+}
+
 /// 取消指定任务（仅支持待处理且仍在内存等待队列中的任务）
 pub async fn cancel_pending_task(task_id: &str, connection: &DatabaseConnection) -> Result<bool, anyhow::Error> {
     if DELETE_TASK_QUEUE.cancel_task(task_id, connection).await? {
@@ -2911,6 +3656,18 @@ pub async fn cancel_pending_task(task_id: &str, connection: &DatabaseConnection)
         return Ok(true);
     }
 
+    if RERENDER_DANMAKU_TASK_QUEUE.cancel_task(task_id, connection).await? {
+        return Ok(true);
+    }
+
+    if SUBTITLE_GENERATION_TASK_QUEUE.cancel_task(task_id, connection).await? {
+        return Ok(true);
+    }
+
+    if QUALITY_UPGRADE_TASK_QUEUE.cancel_task(task_id, connection).await? {
+        return Ok(true);
+    }
+
     Ok(false)
 }
 
@@ -2998,6 +3755,36 @@ pub async fn recover_pending_tasks(connection: &DatabaseConnection) -> Result<()
                     error!("反序列化弹幕刷新任务失败: {:#}", e);
                 }
             },
+            TaskType::RerenderDanmaku => match serde_json::from_str::<RerenderDanmakuTask>(task_data) {
+                Ok(task) => {
+                    let mut queue = RERENDER_DANMAKU_TASK_QUEUE.queue.lock().await;
+                    queue.push_back(task);
+                    recovered_count += 1;
+                }
+                Err(e) => {
+                    error!("反序列化弹幕重新渲染任务失败: {:#}", e);
+                }
+            },
+            TaskType::GenerateSubtitle => match serde_json::from_str::<GenerateSubtitleTask>(task_data) {
+                Ok(task) => {
+                    let mut queue = SUBTITLE_GENERATION_TASK_QUEUE.queue.lock().await;
+                    queue.push_back(task);
+                    recovered_count += 1;
+                }
+                Err(e) => {
+                    error!("反序列化语音识别字幕任务失败: {:#}", e);
+                }
+            },
+            TaskType::UpgradePageQuality => match serde_json::from_str::<UpgradePageQualityTask>(task_data) {
+                Ok(task) => {
+                    let mut queue = QUALITY_UPGRADE_TASK_QUEUE.queue.lock().await;
+                    queue.push_back(task);
+                    recovered_count += 1;
+                }
+                Err(e) => {
+                    error!("反序列化画质升级任务失败: {:#}", e);
+                }
+            },
         }
     }
 
@@ -300,9 +300,50 @@ pub async fn credential_refresh_scheduler() {
                 }
             }
         }
+
+        // 额外账号各自刷新，逐个执行以免刷新结果互相覆盖
+        let account_ids: Vec<(i32, String)> = crate::config::reload_config()
+            .bili_accounts
+            .iter()
+            .map(|account| (account.id, account.name.clone()))
+            .collect();
+        for (account_id, account_name) in account_ids {
+            match bili_client
+                .with_credential_id(Some(account_id))
+                .refresh_credential(true)
+                .await
+            {
+                Ok(_) => info!("B站账号「{}」({}) 凭据强制刷新完毕", account_name, account_id),
+                Err(err) => {
+                    let context = format!(
+                        "B站账号「{}」({}) 每日自动刷新失败: {:#}",
+                        account_name, account_id, err
+                    );
+                    warn!("{}", context);
+                    if is_credential_refresh_transient_error(&err) {
+                        record_credential_refresh_network_warning(Some(context.as_str())).await;
+                    } else {
+                        record_login_expired_warning(LOGIN_EXPIRED_NOTIFICATION_MESSAGE, Some(context.as_str())).await;
+                    }
+                }
+            }
+        }
     }
 }
 
+fn publish_source_auto_disabled(source_type: &str, id: i32, name: &str, reason: &str) {
+    crate::events::publish(
+        crate::events::EventKind::SourceDisabled,
+        serde_json::json!({
+            "source_type": source_type,
+            "source_id": id,
+            "name": name,
+            "reason": "auto",
+            "detail": reason,
+        }),
+    );
+}
+
 async fn try_disable_cancelled_submission_source(
     connection: &DatabaseConnection,
     bili_client: &BiliClient,
@@ -391,6 +432,7 @@ async fn try_disable_cancelled_submission_source(
     active.last_scan_at = Set(Some(now_str));
     active.next_scan_at = Set(None);
     active.update(connection).await?;
+    publish_source_auto_disabled("submission", source.id, &resolved_name, disable_reason);
 
     Ok(Some((resolved_name, disable_reason.to_string())))
 }
@@ -415,6 +457,7 @@ async fn try_disable_invalid_collection_source(
     let mut active: entities::collection::ActiveModel = model.into();
     active.enabled = Set(false);
     active.update(connection).await?;
+    publish_source_auto_disabled("collection", source.id, &source_name, "合集已失效");
 
     Ok(Some((source_name, "合集已失效".to_string())))
 }
@@ -438,6 +481,7 @@ async fn try_disable_empty_watch_later_source(
     let mut active: entities::watch_later::ActiveModel = model.into();
     active.enabled = Set(false);
     active.update(connection).await?;
+    publish_source_auto_disabled("watch_later", source.id, "稍后再看", "稍后再看为空");
 
     Ok(Some("稍后再看".to_string()))
 }
@@ -474,6 +518,7 @@ async fn load_video_sources_from_db(
             args: Args::Collection { collection_item },
             path: PathBuf::from(collection.path),
             source_type: SourceType::Collection,
+            credential_id: collection.credential_id,
         });
     }
 
@@ -490,6 +535,7 @@ async fn load_video_sources_from_db(
             args: Args::Favorite { fid },
             path: PathBuf::from(favorite.path),
             source_type: SourceType::Favorite,
+            credential_id: favorite.credential_id,
         });
     }
 
@@ -569,6 +615,7 @@ async fn load_video_sources_from_db(
             args: Args::Submission { upper_id },
             path: PathBuf::from(submission.path),
             source_type: SourceType::Submission,
+            credential_id: submission.credential_id,
         });
     }
 
@@ -584,6 +631,26 @@ async fn load_video_sources_from_db(
             args: Args::WatchLater,
             path: PathBuf::from(watch_later.path),
             source_type: SourceType::WatchLater,
+            credential_id: watch_later.credential_id,
+        });
+    }
+
+    // 加载榜单源（只加载启用的）
+    let ranking_sources = entities::ranking::Entity::find()
+        .filter(entities::ranking::Column::Enabled.eq(true))
+        .all(connection.as_ref())
+        .await?;
+
+    for ranking in ranking_sources {
+        video_sources.push(VideoSourceWithId {
+            id: ranking.id,
+            args: Args::Ranking {
+                ranking_type: ranking.ranking_type,
+                rid: ranking.rid,
+            },
+            path: PathBuf::from(ranking.path),
+            source_type: SourceType::Ranking,
+            credential_id: ranking.credential_id,
         });
     }
 
@@ -604,6 +671,7 @@ async fn load_video_sources_from_db(
             },
             path: PathBuf::from(bangumi.path),
             source_type: SourceType::Bangumi,
+            credential_id: bangumi.credential_id,
         });
     }
 
@@ -639,6 +707,10 @@ async fn count_all_video_sources(
         .await?;
     total_count += bangumi_count as usize;
 
+    // 统计榜单源
+    let ranking_count = entities::ranking::Entity::find().count(connection.as_ref()).await?;
+    total_count += ranking_count as usize;
+
     Ok(total_count)
 }
 
@@ -679,6 +751,12 @@ async fn count_enabled_video_sources(
         .await?;
     total_count += bangumi_count as usize;
 
+    let ranking_count = entities::ranking::Entity::find()
+        .filter(entities::ranking::Column::Enabled.eq(true))
+        .count(connection.as_ref())
+        .await?;
+    total_count += ranking_count as usize;
+
     Ok(total_count)
 }
 
@@ -945,6 +1023,7 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                         crate::adapter::Args::Submission { .. } => "UP主投稿",
                         crate::adapter::Args::WatchLater => "稍后观看",
                         crate::adapter::Args::Bangumi { .. } => "番剧",
+                        crate::adapter::Args::Ranking { .. } => "榜单",
                     };
                     debug!("  - {} (ID: {})", source_name, source.id);
                 }
@@ -970,6 +1049,9 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
             // 定期同步相关变量
             let mut _videos_since_last_sync = 0; // 自上次同步以来处理的视频数（保留以备将来使用）
 
+            // 记录本轮已经获取过 WBI 签名的额外账号，每个账号每轮扫描只需签名一次
+            let mut signed_account_ids = std::collections::HashSet::new();
+
             for source in &ordered_sources {
                 let args = &source.args;
                 let path = &source.path;
@@ -1020,6 +1102,7 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             crate::adapter::Args::Collection { .. } => "合集",
                             crate::adapter::Args::WatchLater => "稍后再看",
                             crate::adapter::Args::Bangumi { .. } => "番剧",
+                            crate::adapter::Args::Ranking { .. } => "榜单",
                         };
 
                         info!("处理下一个{}前延迟 {} 秒，避免触发风控...", source_type, delay_seconds);
@@ -1031,19 +1114,31 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 // 记录源ID
                 max_id_recorder.record(source.source_type, source.id);
 
+                // 绑定额外账号的视频源使用该账号的凭据与 WBI 签名
+                let source_client = bili_client.with_credential_id(source.credential_id);
+                if let Some(account_id) = source.credential_id {
+                    if signed_account_ids.insert(account_id) {
+                        match fetch_wbi_mixin_key_with_retry(&source_client).await {
+                            Ok(Some(mixin_key)) => source_client.set_mixin_key(mixin_key),
+                            Ok(None) => warn!("B站账号 {} 获取签名信息失败：未能解析签名参数", account_id),
+                            Err(e) => warn!("B站账号 {} 获取签名信息失败: {:#}", account_id, e),
+                        }
+                    }
+                }
+
                 // 获取全局取消令牌，用于下载任务控制
                 let cancellation_token = TASK_CONTROLLER.get_cancellation_token().await;
 
                 // 在处理视频源前记录到收集器
                 if let Ok((video_source, _)) =
-                    crate::adapter::video_source_from(args, path, &bili_client, &optimized_connection, None).await
+                    crate::adapter::video_source_from(args, path, &source_client, &optimized_connection, None).await
                 {
                     scan_collector.start_source(&video_source);
                 }
 
                 match process_video_source(
                     args,
-                    &bili_client,
+                    &source_client,
                     path,
                     &optimized_connection,
                     &downloader_arc,
@@ -1118,7 +1213,7 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                                 if let Ok((video_source, _)) = crate::adapter::video_source_from(
                                     args,
                                     path,
-                                    &bili_client,
+                                    &source_client,
                                     &optimized_connection,
                                     None,
                                 )
@@ -1169,7 +1264,7 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                             }
                         }
 
-                        match try_disable_cancelled_submission_source(&optimized_connection, &bili_client, source, &e)
+                        match try_disable_cancelled_submission_source(&optimized_connection, &source_client, source, &e)
                             .await
                         {
                             Ok(Some((source_name, disable_reason))) => {
@@ -1235,6 +1330,14 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                         if classified_error.error_type == crate::error::ErrorType::RiskControl {
                             error!("检测到风控，停止所有后续视频源的扫描: {}", classified_error.message);
                             info!("触发风控的源(ID: {})未完成处理，下次扫描将重新处理该源", source.id);
+                            crate::events::publish(
+                                crate::events::EventKind::RiskControlTriggered,
+                                serde_json::json!({
+                                    "source_type": source.source_type.as_str(),
+                                    "source_id": source.id,
+                                    "message": classified_error.message,
+                                }),
+                            );
                             is_interrupted = true;
                             break; // 跳出循环，停止处理剩余的视频源
                         }
@@ -1289,9 +1392,9 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
 
             // 生成扫描摘要并发送推送通知
             let scan_summary = scan_collector.generate_summary();
-            if let Err(e) = crate::utils::notification::send_scan_notification(scan_summary).await {
-                warn!("发送扫描完成推送失败: {}", e);
-            }
+            // 后台为本轮新视频生成 AI 摘要、补齐历史视频的摘要后再推送，不阻塞后续任务
+            let token = TASK_CONTROLLER.get_cancellation_token().await;
+            crate::workflow_video_summary::spawn_scan_notification(connection.clone(), token, scan_summary);
 
             // 标记任务状态为结束
             crate::utils::task_notifier::TASK_STATUS_NOTIFIER.set_finished();
@@ -1357,6 +1460,46 @@ pub async fn video_downloader(connection: Arc<DatabaseConnection>) {
                 error!("处理弹幕刷新任务队列失败: {:#}", e);
             }
 
+            if let Err(e) = crate::task::process_rerender_danmaku_tasks(connection.clone()).await {
+                error!("处理弹幕重新渲染任务队列失败: {:#}", e);
+            }
+
+            // 把最近发布的分页加入画质升级队列，重新下载在后台进行，不阻塞下一轮扫描
+            if let Err(e) = crate::workflow_quality_upgrade::enqueue_recent_page_upgrades(&connection).await {
+                error!("画质升级检查失败: {:#}", e);
+            }
+            if !crate::task::QUALITY_UPGRADE_TASK_QUEUE.is_processing() {
+                let connection = connection.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = crate::task::process_upgrade_page_quality_tasks(connection).await {
+                        error!("处理画质升级任务队列失败: {:#}", e);
+                    }
+                });
+            }
+
+            // 归档开启了评论区归档的视频源中到期视频的评论区
+            let token = TASK_CONTROLLER.get_cancellation_token().await;
+            if let Err(e) = crate::workflow_comments::archive_due_comments(&bili_client, &connection, token).await {
+                error!("评论区归档失败: {:#}", e);
+            }
+
+            // 为已下载的字幕补齐配置的译文语言
+            let token = TASK_CONTROLLER.get_cancellation_token().await;
+            if let Err(e) = crate::workflow_subtitle_translation::translate_pending_subtitles(&connection, token).await
+            {
+                error!("字幕翻译失败: {:#}", e);
+            }
+
+            // 语音识别耗时较长，在后台处理字幕生成队列，不阻塞下一轮扫描
+            if !crate::task::SUBTITLE_GENERATION_TASK_QUEUE.is_processing() {
+                let connection = connection.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = crate::task::process_generate_subtitle_tasks(connection).await {
+                        error!("处理语音识别字幕任务队列失败: {:#}", e);
+                    }
+                });
+            }
+
             // mmap自动处理数据持久化，不需要手动同步
         } else {
             debug!("任务已暂停，跳过后处理阶段");
@@ -1517,6 +1660,7 @@ mod tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
             blacklist_keywords: Set(None),
@@ -1532,6 +1676,7 @@ mod tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -1558,6 +1703,7 @@ mod tests {
             args: Args::WatchLater,
             path: PathBuf::from("/tmp/watch-later-1"),
             source_type: SourceType::WatchLater,
+            credential_id: None,
         };
         let err = anyhow::anyhow!("No videos found in watch later list");
 
@@ -151,6 +151,9 @@ static DEEPSEEK_SESSION_CACHE: Lazy<Mutex<HashMap<String, DeepSeekSession>>> = L
 /// 防止并发请求导致创建多个会话
 static AI_RENAME_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+/// 对话历史表中 AI 结果缓存使用的 role
+const AI_RESULT_CACHE_ROLE: &str = "cached_result";
+
 /// 对话消息（用于存储历史）
 #[derive(Clone, Debug)]
 struct ConversationMessage {
@@ -708,34 +711,88 @@ async fn ai_generate_filenames_batch_deepseek_web(
 ) -> Result<Vec<String>> {
     let _lock = AI_RENAME_LOCK.lock().await;
 
+    let db = crate::database::get_global_db().ok_or_else(|| anyhow!("数据库连接不可用"))?;
+
+    let response = deepseek_web_send(cfg, db.as_ref(), source_key, prompt, || async {
+        // 获取历史记录作为上下文
+        let history = get_conversation_history(db.as_ref(), source_key).await;
+        let history_context = if !history.is_empty() {
+            let mut ctx = String::from("【之前的命名风格参考】\n");
+            for msg in &history {
+                if msg.role == "assistant" {
+                    // 只保留 assistant 的回复作为命名风格参考
+                    ctx.push_str(&format!("{}\n", msg.content));
+                }
+            }
+            ctx.push_str("\n请严格遵循以上命名风格。\n\n");
+            ctx
+        } else {
+            String::new()
+        };
+        info!("[{}] 使用新会话重试，带上 {} 条历史记录", source_key, history.len());
+        // 构建带历史上下文的新 prompt
+        format!("{}{}", history_context, prompt)
+    })
+    .await?;
+
+    // 保存简化的对话历史（供一致性检查参考命名风格）
+    let simplified_user_msg = format!("为{}个文件生成命名", expected_count);
+    if let Err(e) = add_conversation_message(db.as_ref(), source_key, "user", &simplified_user_msg).await {
+        warn!("保存用户消息失败: {}", e);
+    }
+    // 解析响应并保存文件名列表
+    let parsed_names = parse_batch_response(&response, expected_count);
+    if let Ok(ref names) = parsed_names {
+        let simplified_response = names.join("\n");
+        if let Err(e) = add_conversation_message(db.as_ref(), source_key, "assistant", &simplified_response).await {
+            warn!("保存助手回复失败: {}", e);
+        }
+    }
+
+    parsed_names
+}
+
+/// 通过 DeepSeek Web 发送消息，会话按 `session_key` 复用（内存缓存 + 数据库）。
+///
+/// 会话达到长度上限或响应体解码失败时清除旧会话，用 `rebuild_prompt` 生成的提示词在新会话中重试一次。
+/// 调用方需持有 `AI_RENAME_LOCK`，避免并发请求创建多个会话。
+async fn deepseek_web_send<F, Fut>(
+    cfg: &AiRenameConfig,
+    db: &DatabaseConnection,
+    session_key: &str,
+    prompt: &str,
+    rebuild_prompt: F,
+) -> Result<String>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = String>,
+{
     let token = cfg
         .deepseek_web_token
         .clone()
         .ok_or_else(|| anyhow!("DeepSeek Web Token 未配置"))?;
 
-    let db = crate::database::get_global_db().ok_or_else(|| anyhow!("数据库连接不可用"))?;
-
     // 从缓存获取会话
     let cached_session = {
         let cache = DEEPSEEK_SESSION_CACHE.lock().await;
-        if let Some(session) = cache.get(source_key).cloned() {
+        if let Some(session) = cache.get(session_key).cloned() {
             info!(
                 "会话缓存命中（内存）: source_key='{}', session_id='{}'",
-                source_key, session.session_id
+                session_key, session.session_id
             );
             Some(session)
         } else {
             drop(cache);
-            if let Some(session) = load_deepseek_session(db.as_ref(), source_key).await {
+            if let Some(session) = load_deepseek_session(db, session_key).await {
                 info!(
                     "会话缓存命中（数据库）: source_key='{}', session_id='{}'",
-                    source_key, session.session_id
+                    session_key, session.session_id
                 );
                 let mut cache = DEEPSEEK_SESSION_CACHE.lock().await;
-                cache.insert(source_key.to_string(), session.clone());
+                cache.insert(session_key.to_string(), session.clone());
                 Some(session)
             } else {
-                info!("会话缓存未命中: source_key='{}'，将创建新会话", source_key);
+                info!("会话缓存未命中: source_key='{}'，将创建新会话", session_key);
                 None
             }
         }
@@ -745,82 +802,24 @@ async fn ai_generate_filenames_batch_deepseek_web(
     let result =
         super::deepseek_web::deepseek_web_generate_raw(&token, cached_session, prompt, cfg.timeout_seconds).await;
 
-    // 检查是否是会话长度上限错误，需要重建会话
+    // 检查是否是会话长度上限或响应体错误，需要重建会话
     let (response, new_session) = match result {
         Ok(res) => res,
-        Err(e) if e.to_string().contains("SESSION_LIMIT_REACHED") => {
-            warn!("[{}] DeepSeek 会话达到长度上限，正在重建会话并带上历史...", source_key);
-
-            // 清除旧会话缓存
-            {
-                let mut cache = DEEPSEEK_SESSION_CACHE.lock().await;
-                cache.remove(source_key);
-            }
-
-            // 获取历史记录作为上下文
-            let history = get_conversation_history(db.as_ref(), source_key).await;
-            let history_context = if !history.is_empty() {
-                let mut ctx = String::from("【之前的命名风格参考】\n");
-                for msg in &history {
-                    if msg.role == "assistant" {
-                        // 只保留 assistant 的回复作为命名风格参考
-                        ctx.push_str(&format!("{}\n", msg.content));
-                    }
-                }
-                ctx.push_str("\n请严格遵循以上命名风格。\n\n");
-                ctx
-            } else {
-                String::new()
-            };
-
-            // 构建带历史上下文的新 prompt
-            let new_prompt = format!("{}{}", history_context, prompt);
-
-            // 用新会话重试（session = None 会创建新会话）
-            info!("[{}] 使用新会话重试，带上 {} 条历史记录", source_key, history.len());
-            super::deepseek_web::deepseek_web_generate_raw(
-                &token,
-                None, // 创建新会话
-                &new_prompt,
-                cfg.timeout_seconds,
-            )
-            .await?
-        }
         Err(e)
-            if e.to_string().contains("读取响应体失败") || e.to_string().contains("error decoding response body") =>
+            if e.to_string().contains("SESSION_LIMIT_REACHED")
+                || e.to_string().contains("读取响应体失败")
+                || e.to_string().contains("error decoding response body") =>
         {
-            warn!("[{}] DeepSeek 响应体解码失败，正在重建会话并带上历史...", source_key);
+            warn!("[{}] DeepSeek 会话不可用（{}），正在重建会话...", session_key, e);
 
             // 清除旧会话缓存
             {
                 let mut cache = DEEPSEEK_SESSION_CACHE.lock().await;
-                cache.remove(source_key);
+                cache.remove(session_key);
             }
 
-            // 获取历史记录作为上下文
-            let history = get_conversation_history(db.as_ref(), source_key).await;
-            let history_context = if !history.is_empty() {
-                let mut ctx = String::from("【之前的命名风格参考】\n");
-                for msg in &history {
-                    if msg.role == "assistant" {
-                        ctx.push_str(&format!("{}\n", msg.content));
-                    }
-                }
-                ctx.push_str("\n请严格遵循以上命名风格。\n\n");
-                ctx
-            } else {
-                String::new()
-            };
-
-            // 构建带历史上下文的新 prompt
-            let new_prompt = format!("{}{}", history_context, prompt);
-
-            // 用新会话重试
-            info!(
-                "[{}] 使用新会话重试（响应体错误），带上 {} 条历史记录",
-                source_key,
-                history.len()
-            );
+            // 用新会话重试（session = None 会创建新会话）
+            let new_prompt = rebuild_prompt().await;
             super::deepseek_web::deepseek_web_generate_raw(&token, None, &new_prompt, cfg.timeout_seconds).await?
         }
         Err(e) => return Err(e),
@@ -829,27 +828,13 @@ async fn ai_generate_filenames_batch_deepseek_web(
     // 更新会话缓存
     {
         let mut cache = DEEPSEEK_SESSION_CACHE.lock().await;
-        cache.insert(source_key.to_string(), new_session.clone());
+        cache.insert(session_key.to_string(), new_session.clone());
     }
-    if let Err(e) = save_deepseek_session(db.as_ref(), source_key, &new_session).await {
+    if let Err(e) = save_deepseek_session(db, session_key, &new_session).await {
         warn!("保存 DeepSeek 会话到数据库失败: {}", e);
     }
 
-    // 保存简化的对话历史（供一致性检查参考命名风格）
-    let simplified_user_msg = format!("为{}个文件生成命名", expected_count);
-    if let Err(e) = add_conversation_message(db.as_ref(), source_key, "user", &simplified_user_msg).await {
-        warn!("保存用户消息失败: {}", e);
-    }
-    // 解析响应并保存文件名列表
-    let parsed_names = parse_batch_response(&response, expected_count);
-    if let Ok(ref names) = parsed_names {
-        let simplified_response = names.join("\n");
-        if let Err(e) = add_conversation_message(db.as_ref(), source_key, "assistant", &simplified_response).await {
-            warn!("保存助手回复失败: {}", e);
-        }
-    }
-
-    parsed_names
+    Ok(response)
 }
 
 /// OpenAI 兼容 API 批量生成
@@ -859,8 +844,6 @@ async fn ai_generate_filenames_batch_openai(
     prompt: &str,
     expected_count: usize,
 ) -> Result<Vec<String>> {
-    let api_key = cfg.api_key.clone().ok_or_else(|| anyhow!("API key missing"))?;
-
     let db = crate::database::get_global_db().ok_or_else(|| anyhow!("数据库连接不可用"))?;
 
     let history = get_conversation_history(db.as_ref(), source_key).await;
@@ -887,34 +870,8 @@ async fn ai_generate_filenames_batch_openai(
         content: prompt.to_string(),
     });
 
-    let req_body = ChatRequest {
-        model: cfg.model.clone(),
-        messages,
-        max_tokens: Some(512), // 批量需要更多 token
-        temperature: Some(0.1),
-        thinking: deepseek_thinking_override(cfg),
-    };
-
-    let client = Client::builder()
-        .timeout(Duration::from_secs(cfg.timeout_seconds.max(30)))
-        .build()?;
-
-    let base = cfg.base_url.trim_end_matches('/');
-    let res = client
-        .post(format!("{}/chat/completions", base))
-        .bearer_auth(api_key)
-        .json(&req_body)
-        .send()
-        .await?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        return Err(anyhow!("批量 AI 请求失败: {} {}", status, body));
-    }
-
-    let resp: ChatResponse = res.json().await?;
-    let raw = extract_chat_content(&resp)?;
+    // 批量需要更多 token
+    let raw = openai_chat(cfg, messages, 512).await?;
 
     // 保存简化的对话历史（只保存生成的文件名列表，作为命名风格参考）
     // 不保存完整的 prompt（太长），只保存一个简短的用户消息和生成的文件名
@@ -934,6 +891,137 @@ async fn ai_generate_filenames_batch_openai(
     parsed_names
 }
 
+/// AI 请求的最大尝试次数：请求超时、被限流或服务端出错时按指数退避重试。
+/// 字幕翻译等按轮重试的功能也以此作为连续失败的上限
+pub const AI_MAX_ATTEMPTS: u32 = 3;
+
+/// 第一次重试前的等待时间，之后每次翻倍
+const AI_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// 第 `attempt` 次失败后的退避时间
+pub fn ai_retry_delay(attempt: u32) -> Duration {
+    AI_RETRY_BASE_DELAY * 2u32.saturating_pow(attempt.saturating_sub(1))
+}
+
+/// 调用 OpenAI 兼容的 chat/completions 接口，返回最终回复内容
+async fn openai_chat(cfg: &AiRenameConfig, messages: Vec<ChatMessage>, max_tokens: u32) -> Result<String> {
+    let api_key = cfg.api_key.clone().ok_or_else(|| anyhow!("API key missing"))?;
+
+    let req_body = ChatRequest {
+        model: cfg.model.clone(),
+        messages,
+        max_tokens: Some(max_tokens),
+        temperature: Some(0.1),
+        thinking: deepseek_thinking_override(cfg),
+    };
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(cfg.timeout_seconds.max(30)))
+        .build()?;
+
+    let base = cfg.base_url.trim_end_matches('/');
+    let mut attempt = 1;
+    let res = loop {
+        let err = match client
+            .post(format!("{}/chat/completions", base))
+            .bearer_auth(&api_key)
+            .json(&req_body)
+            .send()
+            .await
+        {
+            Ok(res) if res.status().is_success() => break res,
+            Ok(res) => {
+                let status = res.status();
+                let body = res.text().await.unwrap_or_default();
+                let err = anyhow!("批量 AI 请求失败: {} {}", status, body);
+                // 请求本身有误（如密钥无效）时重试没有意义
+                if !(status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+                    return Err(err);
+                }
+                err
+            }
+            Err(e) if e.is_timeout() || e.is_connect() => e.into(),
+            Err(e) => return Err(e.into()),
+        };
+        if attempt >= AI_MAX_ATTEMPTS {
+            return Err(err);
+        }
+        let delay = ai_retry_delay(attempt);
+        warn!("AI 请求失败，{} 秒后第 {} 次重试: {:#}", delay.as_secs(), attempt, err);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    };
+
+    let resp: ChatResponse = res.json().await?;
+    extract_chat_content(&resp)
+}
+
+/// 使用 AI 重命名配置中的 provider 完成一次独立请求（不写入命名历史），供字幕翻译等功能复用。
+///
+/// DeepSeek Web 会话按 `session_key` 缓存，失效时与 AI 重命名一样自动重建会话重试。
+pub async fn ai_complete(
+    cfg: &AiRenameConfig,
+    session_key: &str,
+    system_prompt: &str,
+    prompt: &str,
+    max_tokens: u32,
+) -> Result<String> {
+    if cfg.provider == "deepseek-web" {
+        let _lock = AI_RENAME_LOCK.lock().await;
+        let db = crate::database::get_global_db().ok_or_else(|| anyhow!("数据库连接不可用"))?;
+        // Web 接口没有 system 角色，把系统提示放在消息开头
+        let full_prompt = format!("{}\n\n{}", system_prompt, prompt);
+        deepseek_web_send(cfg, db.as_ref(), session_key, &full_prompt, || async {
+            full_prompt.clone()
+        })
+        .await
+    } else {
+        let messages = vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt.to_string(),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            },
+        ];
+        openai_chat(cfg, messages, max_tokens).await
+    }
+}
+
+/// 读取缓存的 AI 结果，与 DeepSeek 会话一样存放在对话历史表中
+pub async fn load_cached_ai_result(db: &DatabaseConnection, cache_key: &str) -> Option<String> {
+    ai_conversation_history::Entity::find()
+        .filter(ai_conversation_history::Column::SourceKey.eq(cache_key))
+        .filter(ai_conversation_history::Column::Role.eq(AI_RESULT_CACHE_ROLE))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .map(|record| record.content)
+}
+
+/// 保存 AI 结果缓存，相同 cache_key 只保留最新一条
+pub async fn save_cached_ai_result(db: &DatabaseConnection, cache_key: &str, content: &str) -> Result<()> {
+    ai_conversation_history::Entity::delete_many()
+        .filter(ai_conversation_history::Column::SourceKey.eq(cache_key))
+        .filter(ai_conversation_history::Column::Role.eq(AI_RESULT_CACHE_ROLE))
+        .exec(db)
+        .await?;
+    ai_conversation_history::ActiveModel {
+        source_key: Set(cache_key.to_string()),
+        role: Set(AI_RESULT_CACHE_ROLE.to_string()),
+        content: Set(content.to_string()),
+        order_index: Set(-1), // 特殊标记
+        created_at: Set(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
 fn deepseek_thinking_override(cfg: &AiRenameConfig) -> Option<ThinkingConfig> {
     let provider = cfg.provider.as_str();
     let base_url = cfg.base_url.to_ascii_lowercase();
@@ -1074,11 +1162,21 @@ fn parse_batch_response(response: &str, expected_count: usize) -> Result<Vec<Str
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::parse_batch_response;
     use super::{
-        deepseek_thinking_override, extract_chat_content, AiRenameConfig, ChatMessageResponse, ChatResponse, Choice,
+        ai_retry_delay, deepseek_thinking_override, extract_chat_content, AiRenameConfig, ChatMessageResponse,
+        ChatResponse, Choice,
     };
 
+    #[test]
+    fn ai_retry_delay_doubles_after_each_failure() {
+        assert_eq!(ai_retry_delay(1), Duration::from_secs(2));
+        assert_eq!(ai_retry_delay(2), Duration::from_secs(4));
+        assert_eq!(ai_retry_delay(3), Duration::from_secs(8));
+    }
+
     #[test]
     fn parse_batch_response_accepts_json_array() {
         let names = parse_batch_response(r#"["ZHY20202024-06-09", "三国bigbig2024-06-09"]"#, 2)
@@ -19,27 +19,12 @@ pub async fn ensure_cache_columns(db: &DbConn) -> Result<()> {
     let backend = db.get_database_backend();
 
     // 检查是否已有缓存字段
-    let check_sql = match backend {
-        sea_orm::DatabaseBackend::Sqlite => {
-            "SELECT COUNT(*) FROM pragma_table_info('video_source') WHERE name IN ('cached_episodes', 'cache_updated_at')"
-        }
-        _ => {
-            // 其他数据库暂不支持
-            return Ok(());
-        }
-    };
-
-    let result: Option<i32> = db
-        .query_one(sea_orm::Statement::from_string(backend, check_sql))
-        .await?
-        .and_then(|row| row.try_get_by_index(0).ok());
-
-    // 如果字段已存在，跳过
-    if let Some(count) = result {
-        if count >= 2 {
-            debug!("番剧缓存字段已存在，跳过创建");
-            return Ok(());
-        }
+    let dialect = crate::database::dialect::current();
+    if dialect.table_has_column(db, "video_source", "cached_episodes").await?
+        && dialect.table_has_column(db, "video_source", "cache_updated_at").await?
+    {
+        debug!("番剧缓存字段已存在，跳过创建");
+        return Ok(());
     }
 
     // 添加 cached_episodes 字段
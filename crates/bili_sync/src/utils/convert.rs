@@ -135,6 +135,36 @@ impl VideoInfo {
                 cid: Set(None), // 后续通过get_view_info填充
                 ..default
             },
+            VideoInfo::Ranking {
+                title,
+                bvid,
+                intro,
+                cover,
+                upper,
+                ctime,
+                pubtime,
+                state,
+                ..
+            } => bili_sync_entity::video::ActiveModel {
+                bvid: Set(bvid),
+                name: Set(title),
+                category: Set(2), // 榜单里的内容类型肯定是视频
+                intro: Set(intro),
+                cover: Set(cover),
+                ctime: Set(ctime
+                    .with_timezone(&crate::utils::time_format::beijing_timezone())
+                    .naive_local()),
+                pubtime: Set(pubtime
+                    .with_timezone(&crate::utils::time_format::beijing_timezone())
+                    .naive_local()),
+                download_status: Set(0),
+                valid: Set(state == 0),
+                upper_id: Set(upper.mid),
+                upper_name: Set(upper.name),
+                upper_face: Set(upper.face),
+                cid: Set(None), // 后续通过get_view_info填充
+                ..default
+            },
             VideoInfo::Submission {
                 title,
                 bvid,
@@ -377,6 +407,7 @@ impl VideoInfo {
             | VideoInfo::WatchLater { fav_time: time, .. }
             | VideoInfo::Submission { ctime: time, .. }
             | VideoInfo::Dynamic { pubtime: time, .. }
+            | VideoInfo::Ranking { pubtime: time, .. }
             | VideoInfo::Bangumi { pubtime: time, .. } => time,
             _ => unreachable!(),
         }
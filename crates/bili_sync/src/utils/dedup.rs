@@ -0,0 +1,212 @@
+//! 跨视频源去重：同一个 cid 在多个视频源（收藏夹/合集/投稿等）中重复出现时，
+//! 复用已下载的文件，通过硬链接（失败时回退到 reflink/复制）落到新源的路径上，
+//! 避免重复下载。各视频源仍按自己的命名模板生成路径，NFO 等附属文件照常生成。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use bili_sync_entity::page;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::utils::status::{PageStatus, STATUS_OK};
+
+/// 分页状态中“视频文件”子任务的下标
+const PAGE_VIDEO_TASK_INDEX: usize = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Hardlink,
+    Reflink,
+    Copy,
+}
+
+impl LinkKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LinkKind::Hardlink => "硬链接",
+            LinkKind::Reflink => "reflink",
+            LinkKind::Copy => "复制",
+        }
+    }
+}
+
+/// 由下载播放缓存（play_video_streams / play_audio_streams）计算流签名，
+/// 仅包含画质、编码与音质，不含会过期的 URL
+pub fn stream_cache_signature(video_streams: &[Value], audio_streams: &[Value]) -> Option<String> {
+    let video = video_streams.first()?;
+    let audio_quality = audio_streams
+        .first()
+        .and_then(|audio| audio["quality"].as_u64())
+        .unwrap_or(0);
+    Some(format!(
+        "v{}:{}:{}|a{}",
+        video["quality"].as_u64().unwrap_or(0),
+        video["codecs"].as_str().unwrap_or_default(),
+        video["container"].as_str().unwrap_or_default(),
+        audio_quality
+    ))
+}
+
+fn page_stream_signature(page_model: &page::Model) -> Option<String> {
+    let video_streams: Vec<Value> = serde_json::from_str(page_model.play_video_streams.as_deref()?).ok()?;
+    let audio_streams: Vec<Value> = page_model
+        .play_audio_streams
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    stream_cache_signature(&video_streams, &audio_streams)
+}
+
+/// 查找同一 cid、同一流签名且视频文件已下载成功的其它分页，返回其模型
+pub async fn find_reusable_page_copy(
+    connection: &DatabaseConnection,
+    page_id: i32,
+    cid: i64,
+    signature: &str,
+    target_path: &Path,
+) -> Result<Option<page::Model>> {
+    let candidates = page::Entity::find()
+        .filter(page::Column::Cid.eq(cid))
+        .filter(page::Column::Id.ne(page_id))
+        .filter(page::Column::Path.is_not_null())
+        .filter(page::Column::PlayVideoStreams.is_not_null())
+        .all(connection)
+        .await
+        .context("查询可复用的已下载分页失败")?;
+
+    let target_extension = target_path.extension();
+    for candidate in candidates {
+        if PageStatus::from(candidate.download_status).get(PAGE_VIDEO_TASK_INDEX) != STATUS_OK {
+            continue;
+        }
+        if page_stream_signature(&candidate).as_deref() != Some(signature) {
+            continue;
+        }
+        let Some(candidate_path) = candidate.path.as_deref().map(Path::new) else {
+            continue;
+        };
+        // 仅音频（m4a）与视频（mp4）不能互相复用
+        if candidate_path.extension() != target_extension || candidate_path == target_path {
+            continue;
+        }
+        match tokio::fs::metadata(candidate_path).await {
+            Ok(metadata) if metadata.is_file() && metadata.len() > 0 => return Ok(Some(candidate)),
+            _ => debug!("可复用分页文件不存在或为空，跳过: {}", candidate_path.display()),
+        }
+    }
+    Ok(None)
+}
+
+/// 将已有文件落到目标路径：优先硬链接，跨设备等失败时回退到 reflink，文件系统不支持时复制。
+/// 先落到同目录下的临时文件再重命名覆盖目标，失败时目标路径上的原文件保持不变。
+/// 不使用软链接：源视频被删除后软链接会悬空，而分页状态仍为已完成。
+pub async fn link_existing_file(source: &Path, target: &Path) -> Result<LinkKind> {
+    let temp = temp_link_path(target);
+    let kind = match place_file(source, &temp).await {
+        Ok(kind) => kind,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(e);
+        }
+    };
+    if let Err(e) = tokio::fs::rename(&temp, target).await {
+        let _ = tokio::fs::remove_file(&temp).await;
+        return Err(anyhow!(
+            "替换目标文件失败: {} -> {}: {}",
+            temp.display(),
+            target.display(),
+            e
+        ));
+    }
+    Ok(kind)
+}
+
+/// 与目标同目录的临时文件名，保证随后的重命名不跨设备
+fn temp_link_path(target: &Path) -> PathBuf {
+    let file_name = target.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    target.with_file_name(format!(".{}.dedup-{}", file_name, uuid::Uuid::new_v4().simple()))
+}
+
+async fn place_file(source: &Path, temp: &Path) -> Result<LinkKind> {
+    let hardlink_err = match tokio::fs::hard_link(source, temp).await {
+        Ok(()) => return Ok(LinkKind::Hardlink),
+        Err(e) => e,
+    };
+    debug!(
+        "硬链接失败，尝试 reflink: {} -> {}: {}",
+        source.display(),
+        temp.display(),
+        hardlink_err
+    );
+
+    let (source_owned, temp_owned) = (source.to_path_buf(), temp.to_path_buf());
+    let copied = tokio::task::spawn_blocking(move || reflink_copy::reflink_or_copy(&source_owned, &temp_owned))
+        .await
+        .context("reflink 任务异常退出")?
+        .map_err(|e| {
+            anyhow!(
+                "硬链接/reflink/复制均失败: {} -> {}: 硬链接错误: {}，复制错误: {}",
+                source.display(),
+                temp.display(),
+                hardlink_err,
+                e
+            )
+        })?;
+    // reflink_or_copy 在 reflink 成功时返回 None，回退为普通复制时返回复制的字节数
+    Ok(if copied.is_none() {
+        LinkKind::Reflink
+    } else {
+        LinkKind::Copy
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stream_cache_signature_ignores_urls() {
+        let video_a = vec![json!({"url": "https://a", "quality": 80, "codecs": "AVC", "container": "dash"})];
+        let video_b = vec![json!({"url": "https://b", "quality": 80, "codecs": "AVC", "container": "dash"})];
+        let audio = vec![json!({"url": "https://c", "quality": 30280})];
+        assert_eq!(
+            stream_cache_signature(&video_a, &audio),
+            stream_cache_signature(&video_b, &audio)
+        );
+
+        let video_hevc = vec![json!({"url": "https://a", "quality": 80, "codecs": "HEVC", "container": "dash"})];
+        assert_ne!(
+            stream_cache_signature(&video_a, &audio),
+            stream_cache_signature(&video_hevc, &audio)
+        );
+        assert_eq!(stream_cache_signature(&[], &audio), None);
+    }
+
+    #[tokio::test]
+    async fn test_link_existing_file_hardlinks_and_replaces_target() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-dedup-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let source = dir.join("source.mp4");
+        let target = dir.join("target.mp4");
+        tokio::fs::write(&source, b"video").await.unwrap();
+        tokio::fs::write(&target, b"partial").await.unwrap();
+
+        let kind = link_existing_file(&source, &target).await.unwrap();
+        assert_eq!(kind, LinkKind::Hardlink);
+        assert_eq!(tokio::fs::read(&target).await.unwrap(), b"video");
+
+        // 源文件不存在时链接失败，目标路径上的已有文件保持不变
+        let missing = dir.join("missing.mp4");
+        assert!(link_existing_file(&missing, &target).await.is_err());
+        assert_eq!(tokio::fs::read(&target).await.unwrap(), b"video");
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            assert!(!entry.file_name().to_string_lossy().contains(".dedup-"));
+        }
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+}
@@ -384,6 +384,7 @@ mod tests {
             favorite_id: None,
             watch_later_id: None,
             submission_id: None,
+            ranking_id: None,
             source_id: None,
             source_type: Some(1),
             upper_id: 123456,
@@ -4,6 +4,7 @@ pub mod bangumi_name_extractor;
 pub mod collection_aggregate;
 pub mod convert;
 pub mod danmaku_schedule;
+pub mod dedup;
 pub mod deepseek_pow;
 pub mod deepseek_web;
 pub mod file_logger;
@@ -17,6 +18,7 @@ pub mod notification;
 pub mod scan_collector;
 pub mod scan_id_tracker;
 pub mod signal;
+pub mod source_bundle;
 pub mod status;
 pub mod submission_checkpoint;
 pub mod task_notifier;
@@ -19,26 +19,28 @@ use crate::utils::live_updates::notify_videos_changed;
 use crate::utils::status::STATUS_COMPLETED;
 
 /// 从 VideoInfo 中提取 BVID
-fn extract_bvid(video_info: &VideoInfo) -> String {
+pub(crate) fn extract_bvid(video_info: &VideoInfo) -> String {
     match video_info {
         VideoInfo::Submission { bvid, .. } => bvid.clone(),
         VideoInfo::Dynamic { bvid, .. } => bvid.clone(),
         VideoInfo::Detail { bvid, .. } => bvid.clone(),
         VideoInfo::Favorite { bvid, .. } => bvid.clone(),
         VideoInfo::WatchLater { bvid, .. } => bvid.clone(),
+        VideoInfo::Ranking { bvid, .. } => bvid.clone(),
         VideoInfo::Collection { bvid, .. } => bvid.clone(),
         VideoInfo::Bangumi { bvid, .. } => bvid.clone(),
     }
 }
 
 /// 从 VideoInfo 中提取标题
-fn extract_title(video_info: &VideoInfo) -> String {
+pub(crate) fn extract_title(video_info: &VideoInfo) -> String {
     match video_info {
         VideoInfo::Submission { title, .. } => title.clone(),
         VideoInfo::Dynamic { title, .. } => title.clone(),
         VideoInfo::Detail { title, .. } => title.clone(),
         VideoInfo::Favorite { title, .. } => title.clone(),
         VideoInfo::WatchLater { title, .. } => title.clone(),
+        VideoInfo::Ranking { title, .. } => title.clone(),
         VideoInfo::Collection { title, .. } => title.clone(),
         VideoInfo::Bangumi { title, .. } => title.clone(),
     }
@@ -52,19 +54,21 @@ fn extract_pubtime(video_info: &VideoInfo) -> DateTime<Utc> {
         VideoInfo::Detail { pubtime, .. } => *pubtime,
         VideoInfo::Favorite { pubtime, .. } => *pubtime,
         VideoInfo::WatchLater { pubtime, .. } => *pubtime,
+        VideoInfo::Ranking { pubtime, .. } => *pubtime,
         VideoInfo::Collection { pubtime, .. } => *pubtime,
         VideoInfo::Bangumi { pubtime, .. } => *pubtime,
     }
 }
 
 /// 从 VideoInfo 中提取时长（秒）
-fn extract_duration_seconds(video_info: &VideoInfo) -> Option<i32> {
+pub(crate) fn extract_duration_seconds(video_info: &VideoInfo) -> Option<i32> {
     match video_info {
         VideoInfo::Submission { duration, .. } => *duration,
         VideoInfo::Dynamic { duration, .. } => *duration,
         VideoInfo::Detail { duration, .. } => *duration,
         VideoInfo::Favorite { duration, .. } => *duration,
         VideoInfo::WatchLater { duration, .. } => *duration,
+        VideoInfo::Ranking { duration, .. } => *duration,
         VideoInfo::Collection { duration, arc, .. } => duration.or_else(|| {
             arc.as_ref().and_then(|arc| {
                 arc.get("duration")
@@ -80,10 +84,139 @@ fn extract_duration_seconds(video_info: &VideoInfo) -> Option<i32> {
     }
 }
 
-fn video_info_is_valid(video_info: &VideoInfo) -> bool {
+/// 视频列表在入库前被过滤掉的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VideoSkipReason {
+    /// 未通过关键词黑白名单
+    Keyword,
+    /// 时长不在允许范围内（秒）
+    Duration(i32),
+    /// 发布日期不在允许范围内（北京时间 YYYYmmddHHMMSS）
+    PublishedAt(String),
+}
+
+impl std::fmt::Display for VideoSkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoSkipReason::Keyword => write!(f, "被关键词过滤器过滤"),
+            VideoSkipReason::Duration(seconds) => write!(f, "时长 {} 秒不在过滤范围内", seconds),
+            VideoSkipReason::PublishedAt(time) => write!(f, "发布时间 {} 不在过滤范围内", time),
+        }
+    }
+}
+
+/// 视频源级别的列表过滤条件（关键词黑白名单、时长、发布日期）
+///
+/// 白名单：如果设置了白名单，视频必须匹配其中之一才下载；
+/// 黑名单：匹配黑名单的视频即使通过白名单也不下载。
+#[derive(Debug, Clone, Default)]
+pub struct VideoListFilter {
+    pub blacklist_keywords: Option<String>,
+    pub whitelist_keywords: Option<String>,
+    pub keyword_case_sensitive: bool,
+    /// 向后兼容：旧的单列表模式
+    pub keyword_filters: Option<String>,
+    pub keyword_filter_mode: Option<String>,
+    pub min_duration_seconds: Option<i32>,
+    pub max_duration_seconds: Option<i32>,
+    /// YYYY-MM-DD
+    pub published_after: Option<String>,
+    /// YYYY-MM-DD
+    pub published_before: Option<String>,
+}
+
+impl VideoListFilter {
+    pub fn from_source(video_source: &VideoSourceEnum) -> Self {
+        Self {
+            blacklist_keywords: video_source.get_blacklist_keywords(),
+            whitelist_keywords: video_source.get_whitelist_keywords(),
+            keyword_case_sensitive: video_source.get_keyword_case_sensitive(),
+            keyword_filters: video_source.get_keyword_filters(),
+            keyword_filter_mode: video_source.get_keyword_filter_mode(),
+            min_duration_seconds: video_source.get_min_duration_seconds(),
+            max_duration_seconds: video_source.get_max_duration_seconds(),
+            published_after: video_source.get_published_after(),
+            published_before: video_source.get_published_before(),
+        }
+    }
+
+    fn has_dual_list(&self) -> bool {
+        self.blacklist_keywords.is_some() || self.whitelist_keywords.is_some()
+    }
+
+    fn has_duration_filter(&self) -> bool {
+        self.min_duration_seconds.is_some() || self.max_duration_seconds.is_some()
+    }
+
+    fn has_published_filter(&self) -> bool {
+        self.published_after.is_some() || self.published_before.is_some()
+    }
+
+    /// 是否配置了任何过滤条件
+    pub fn is_active(&self) -> bool {
+        self.has_dual_list()
+            || self.keyword_filters.is_some()
+            || self.has_duration_filter()
+            || self.has_published_filter()
+    }
+
+    /// 判断视频是否应被过滤，返回 `None` 表示保留
+    pub fn skip_reason(&self, video_info: &VideoInfo) -> Option<VideoSkipReason> {
+        use crate::utils::keyword_filter::{should_filter_video_dual_list, should_filter_video_with_mode};
+
+        let title = extract_title(video_info);
+        // 优先使用新的双列表模式，否则向后兼容旧的单列表模式
+        let keyword_filtered = if self.has_dual_list() {
+            should_filter_video_dual_list(
+                &title,
+                &self.blacklist_keywords,
+                &self.whitelist_keywords,
+                self.keyword_case_sensitive,
+            )
+        } else {
+            should_filter_video_with_mode(&title, &self.keyword_filters, &self.keyword_filter_mode)
+        };
+        if keyword_filtered {
+            return Some(VideoSkipReason::Keyword);
+        }
+
+        if self.has_duration_filter() {
+            if let Some(duration_seconds) = extract_duration_seconds(video_info) {
+                if self.min_duration_seconds.is_some_and(|min| duration_seconds < min)
+                    || self.max_duration_seconds.is_some_and(|max| duration_seconds > max)
+                {
+                    return Some(VideoSkipReason::Duration(duration_seconds));
+                }
+            }
+        }
+
+        if self.has_published_filter() {
+            let parse_date = |date: &Option<String>| {
+                date.as_deref()
+                    .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            };
+            let beijing_tz = crate::utils::time_format::beijing_timezone();
+            let published_at = extract_pubtime(video_info).with_timezone(&beijing_tz);
+            let published_date = published_at.date_naive();
+            if parse_date(&self.published_after).is_some_and(|start| published_date < start)
+                || parse_date(&self.published_before).is_some_and(|end| published_date > end)
+            {
+                return Some(VideoSkipReason::PublishedAt(
+                    published_at.format("%Y%m%d%H%M%S").to_string(),
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+pub(crate) fn video_info_is_valid(video_info: &VideoInfo) -> bool {
     match video_info {
         VideoInfo::Favorite { attr, .. } => *attr == 0 || *attr == 4,
-        VideoInfo::WatchLater { state, .. } | VideoInfo::Detail { state, .. } => *state == 0,
+        VideoInfo::WatchLater { state, .. } | VideoInfo::Detail { state, .. } | VideoInfo::Ranking { state, .. } => {
+            *state == 0
+        }
         _ => true,
     }
 }
@@ -251,6 +384,19 @@ pub async fn get_failed_videos_in_current_cycle(
 /// 尝试创建 Video Model，如果发生冲突则忽略
 /// 如果视频源启用了扫描已删除视频设置，则会恢复已删除的视频
 /// 对于选择性下载模式，只存储选中的视频到数据库
+/// 发布新视频入库事件，重新读取记录以带上完整的视频源信息
+async fn publish_video_discovered(connection: &DatabaseConnection, video_id: i32) {
+    match video::Entity::find_by_id(video_id).one(connection).await {
+        Ok(Some(model)) => crate::events::publish_video(
+            crate::events::EventKind::VideoDiscovered,
+            &model,
+            serde_json::json!({ "pubtime": model.pubtime.format(crate::utils::time_format::STANDARD_TIME_FORMAT).to_string() }),
+        ),
+        Ok(None) => {}
+        Err(e) => warn!("读取新视频 {} 失败，跳过发布事件: {}", video_id, e),
+    }
+}
+
 pub async fn create_videos(
     videos_info: Vec<VideoInfo>,
     video_source: &VideoSourceEnum,
@@ -300,87 +446,52 @@ pub async fn create_videos(
         videos_info
     };
 
-    // 关键词过滤：支持双列表模式（黑白名单同时生效）
-    // 白名单：如果设置了白名单，视频必须匹配其中之一才下载
-    // 黑名单：匹配黑名单的视频即使通过白名单也不下载
-    let blacklist_keywords = video_source.get_blacklist_keywords();
-    let whitelist_keywords = video_source.get_whitelist_keywords();
-    let case_sensitive = video_source.get_keyword_case_sensitive();
-    let min_duration_seconds = video_source.get_min_duration_seconds();
-    let max_duration_seconds = video_source.get_max_duration_seconds();
-    let published_after = video_source.get_published_after();
-    let published_before = video_source.get_published_before();
-    // 向后兼容：旧的单列表模式
-    let keyword_filters = video_source.get_keyword_filters();
-    let keyword_filter_mode = video_source.get_keyword_filter_mode();
-
-    // 判断是否有任何过滤配置
-    let has_dual_list = blacklist_keywords.is_some() || whitelist_keywords.is_some();
-    let has_legacy = keyword_filters.is_some();
-    let has_duration_filter = min_duration_seconds.is_some() || max_duration_seconds.is_some();
-    let has_published_filter = published_after.is_some() || published_before.is_some();
-    let published_after_date = published_after
-        .as_deref()
-        .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
-    let published_before_date = published_before
-        .as_deref()
-        .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
-
-    let final_videos_info = if has_dual_list || has_legacy || has_duration_filter || has_published_filter {
-        use crate::utils::keyword_filter::{should_filter_video_dual_list, should_filter_video_with_mode};
+    // 榜单源：按 bvid 跨视频源去重，已被其它视频源收录的视频不再重复入库下载
+    let final_videos_info = if let VideoSourceEnum::Ranking(ranking) = video_source {
+        let all_bvids: Vec<String> = final_videos_info.iter().map(extract_bvid).collect();
+        let archived_bvids: HashSet<String> = video::Entity::find()
+            .filter(video::Column::Bvid.is_in(all_bvids))
+            .all(connection)
+            .await?
+            .into_iter()
+            .filter(|v| v.ranking_id != Some(ranking.id))
+            .map(|v| v.bvid)
+            .collect();
 
+        if archived_bvids.is_empty() {
+            final_videos_info
+        } else {
+            info!(
+                "榜单「{}」跨源去重：{} 个视频已被其它视频源收录，跳过",
+                ranking.name,
+                archived_bvids.len()
+            );
+            final_videos_info
+                .into_iter()
+                .filter(|info| !archived_bvids.contains(&extract_bvid(info)))
+                .collect()
+        }
+    } else {
+        final_videos_info
+    };
+
+    // 关键词 / 时长 / 发布日期过滤
+    let list_filter = VideoListFilter::from_source(video_source);
+    let final_videos_info = if list_filter.is_active() {
         let before_count = final_videos_info.len();
         let filtered_videos: Vec<VideoInfo> = final_videos_info
             .into_iter()
-            .filter(|info| {
-                let title = extract_title(info);
-                let bvid = extract_bvid(info);
-
-                // 优先使用新的双列表模式
-                let mut should_filter = if has_dual_list {
-                    should_filter_video_dual_list(&title, &blacklist_keywords, &whitelist_keywords, case_sensitive)
-                } else {
-                    // 向后兼容：使用旧的单列表模式
-                    should_filter_video_with_mode(&title, &keyword_filters, &keyword_filter_mode)
-                };
-
-                if !should_filter && has_duration_filter {
-                    let duration_seconds = extract_duration_seconds(info);
-                    if let Some(duration_seconds) = duration_seconds {
-                        if min_duration_seconds.map(|min| duration_seconds < min).unwrap_or(false)
-                            || max_duration_seconds.map(|max| duration_seconds > max).unwrap_or(false)
-                        {
-                            info!(
-                                "视频 '{}' 时长 {} 秒不在过滤范围内，跳过: {}",
-                                title, duration_seconds, bvid
-                            );
-                            should_filter = true;
-                        }
-                    }
-                }
-
-                if !should_filter && has_published_filter {
-                    let beijing_tz = crate::utils::time_format::beijing_timezone();
-                    let published_at = extract_pubtime(info).with_timezone(&beijing_tz);
-                    let published_date = published_at.date_naive();
-                    let published_time = published_at.format("%Y%m%d%H%M%S").to_string();
-                    if published_after_date
-                        .map(|start| published_date < start)
-                        .unwrap_or(false)
-                        || published_before_date.map(|end| published_date > end).unwrap_or(false)
-                    {
-                        info!(
-                            "视频 '{}' 发布时间 {} 不在过滤范围内，跳过: {}",
-                            title, published_time, bvid
-                        );
-                        should_filter = true;
-                    }
-                }
-
-                if should_filter && (has_dual_list || has_legacy) {
-                    info!("视频 '{}' 被关键词过滤器过滤，跳过: {}", title, bvid);
+            .filter(|info| match list_filter.skip_reason(info) {
+                Some(reason) => {
+                    info!(
+                        "视频 '{}' {}，跳过: {}",
+                        extract_title(info),
+                        reason,
+                        extract_bvid(info)
+                    );
+                    false
                 }
-                !should_filter
+                None => true,
             })
             .collect();
 
@@ -673,14 +784,18 @@ pub async fn create_videos(
                 }
             } else {
                 // 视频不存在，正常插入
-                crate::database::run_traced_db_operation("utils.model.insert_video(scan_deleted=true)", async {
-                    video::Entity::insert(model)
-                        .on_conflict(OnConflict::new().do_nothing().to_owned())
-                        .do_nothing()
-                        .exec(connection)
-                        .await
-                })
-                .await?;
+                let insert_result =
+                    crate::database::run_traced_db_operation("utils.model.insert_video(scan_deleted=true)", async {
+                        video::Entity::insert(model)
+                            .on_conflict(OnConflict::new().do_nothing().to_owned())
+                            .do_nothing()
+                            .exec(connection)
+                            .await
+                    })
+                    .await?;
+                if let sea_orm::TryInsertResult::Inserted(inserted) = insert_result {
+                    publish_video_discovered(connection, inserted.last_insert_id).await;
+                }
             }
         }
     } else {
@@ -788,6 +903,9 @@ pub async fn create_videos(
 
             // 如果插入没有影响任何行（即记录已存在），检查是否需要更新 share_copy
             if let Ok(insert_res) = insert_result {
+                if let sea_orm::TryInsertResult::Inserted(inserted) = &insert_res {
+                    publish_video_discovered(connection, inserted.last_insert_id).await;
+                }
                 // 检查插入是否真的生效，如果没有生效说明记录已存在
                 let insert_success = match &insert_res {
                     sea_orm::TryInsertResult::Inserted(_) => true,
@@ -1465,6 +1583,7 @@ mod tests {
     use sea_orm::sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
     use sea_orm::{
         ActiveModelTrait, ActiveValue::NotSet, ConnectionTrait, DbBackend, QueryOrder, SqlxSqliteConnector, Statement,
+        TransactionTrait,
     };
     use std::fs;
     use std::path::{Path, PathBuf};
@@ -1511,6 +1630,7 @@ mod tests {
             favorite_id: Set(None),
             watch_later_id: Set(None),
             submission_id: Set(Some(1)),
+            ranking_id: Set(None),
             source_id: Set(None),
             source_type: Set(Some(4)),
             upper_id: Set(2000 + i64::from(id)),
@@ -1579,6 +1699,7 @@ mod tests {
             danmaku_cid_snapshot: Set(None),
             danmaku_last_write_count: Set(0),
             ai_renamed: NotSet,
+            quality_checked_at: Set(None),
         }
         .insert(db)
         .await
@@ -1603,6 +1724,7 @@ mod tests {
             scan_deleted_videos: false,
             scan_deleted_videos_once: false,
             filter_option: None,
+            credential_id: None,
             selected_videos: Some("[]".to_string()),
             keyword_filters: None,
             keyword_filter_mode: None,
@@ -1619,6 +1741,7 @@ mod tests {
             split_chapters_after_download: false,
             download_charge_videos: true,
             download_danmaku: true,
+            archive_comments: false,
             download_subtitle: true,
             download_ai_subtitle: true,
             ai_subtitle_language: "zh-CN".to_string(),
@@ -1823,4 +1946,137 @@ mod tests {
         assert_eq!(updated.path, "/tmp/video-1-updated");
         assert_eq!(updated.total_file_size_bytes, Some(128));
     }
+
+    #[test]
+    fn video_list_filter_reports_first_matching_skip_reason() {
+        let submission = |title: &str, duration: i32, rfc3339: &str| crate::bilibili::VideoInfo::Submission {
+            title: title.to_string(),
+            bvid: "BV1ListFilter".to_string(),
+            intro: String::new(),
+            cover: String::new(),
+            ctime: chrono::DateTime::parse_from_rfc3339(rfc3339)
+                .expect("测试时间应合法")
+                .with_timezone(&chrono::Utc),
+            duration: Some(duration),
+            season_id: None,
+        };
+        let filter = VideoListFilter {
+            blacklist_keywords: Some(r#"["直播回放"]"#.to_string()),
+            min_duration_seconds: Some(60),
+            published_after: Some("2026-01-01".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.is_active());
+        assert!(!VideoListFilter::default().is_active());
+
+        assert_eq!(
+            filter.skip_reason(&submission("直播回放 第1期", 600, "2026-03-01T00:00:00Z")),
+            Some(VideoSkipReason::Keyword)
+        );
+        assert_eq!(
+            filter.skip_reason(&submission("正片", 30, "2026-03-01T00:00:00Z")),
+            Some(VideoSkipReason::Duration(30))
+        );
+        // 2025-12-31 16:30 UTC 即北京时间 2026-01-01 00:30，应视为在起始日期之内
+        assert_eq!(
+            filter.skip_reason(&submission("正片", 600, "2025-12-31T16:30:00Z")),
+            None
+        );
+        assert_eq!(
+            filter.skip_reason(&submission("正片", 600, "2025-12-31T15:30:00Z")),
+            Some(VideoSkipReason::PublishedAt("20251231233000".to_string()))
+        );
+    }
+
+    /// 在外部数据库上走一遍迁移和基本的入库流程，由 CI 提供 PostgreSQL / MySQL 连接
+    #[tokio::test]
+    async fn basic_sync_round_trip_on_external_database() {
+        let Ok(url) = std::env::var("BILI_SYNC_TEST_DATABASE_URL") else {
+            return;
+        };
+        let db = sea_orm::Database::connect(&url).await.expect("应能连接外部测试数据库");
+        Migrator::up(&db, None).await.expect("应能完成外部数据库迁移");
+        db.execute_unprepared("ALTER TABLE page ADD COLUMN ai_renamed INTEGER DEFAULT 0")
+            .await
+            .ok();
+
+        let VideoSourceEnum::Submission(mut source_model) = selective_submission_source("2026-01-01 00:00:00") else {
+            unreachable!();
+        };
+        source_model.selected_videos = None;
+        let source_model = submission::ActiveModel::from(source_model.clone())
+            .insert(&db)
+            .await
+            .expect("应能插入测试订阅");
+        let source = VideoSourceEnum::Submission(source_model);
+
+        let ctime = chrono::DateTime::parse_from_rfc3339("2026-06-03T14:32:31Z")
+            .expect("测试时间应合法")
+            .with_timezone(&chrono::Utc);
+        create_videos(
+            vec![crate::bilibili::VideoInfo::Submission {
+                title: "外部数据库测试".to_string(),
+                bvid: "BV1ExternalDb".to_string(),
+                intro: String::new(),
+                cover: String::new(),
+                ctime,
+                duration: Some(60),
+                season_id: None,
+            }],
+            &source,
+            &db,
+        )
+        .await
+        .expect("创建视频应成功");
+
+        let mut unfilled = filter_unfilled_videos(source.filter_expr(), &db)
+            .await
+            .expect("应能查询未填充视频");
+        assert_eq!(unfilled.len(), 1);
+        let video_model = unfilled.remove(0);
+
+        let txn = db.begin().await.expect("应能开启事务");
+        create_pages(
+            vec![PageInfo {
+                cid: 30_000_000_000,
+                page: 1,
+                name: "外部数据库测试".to_string(),
+                duration: 60,
+                first_frame: None,
+                dimension: None,
+            }],
+            &video_model,
+            &txn,
+        )
+        .await
+        .expect("创建分页应成功");
+        let mut filled: video::ActiveModel = video_model.into();
+        filled.single_page = Set(Some(true));
+        filled.update(&txn).await.expect("应能更新视频");
+        txn.commit().await.expect("应能提交事务");
+
+        let mut unhandled = filter_unhandled_video_pages(source.filter_expr(), &db)
+            .await
+            .expect("应能查询待下载分页");
+        assert_eq!(unhandled.len(), 1);
+        let (video_model, pages) = unhandled.remove(0);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].cid, 30_000_000_000);
+
+        let mut video_model: video::ActiveModel = video_model.into();
+        video_model.download_status = Set(STATUS_COMPLETED);
+        let mut page_model: page::ActiveModel = pages[0].clone().into();
+        page_model.download_status = Set(STATUS_COMPLETED);
+        update_videos_model(vec![video_model], &db)
+            .await
+            .expect("更新视频状态应成功");
+        update_pages_model(vec![page_model], &db)
+            .await
+            .expect("更新分页状态应成功");
+
+        assert!(filter_unhandled_video_pages(source.filter_expr(), &db)
+            .await
+            .expect("应能再次查询待下载分页")
+            .is_empty());
+    }
 }
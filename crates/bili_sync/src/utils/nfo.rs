@@ -8,6 +8,7 @@ use quick_xml::Error;
 use std::borrow::Cow;
 use tokio::io::{AsyncWriteExt, BufWriter};
 
+use crate::bilibili::HighlightChapter;
 use crate::config::{EmptyUpperStrategy, NFOConfig, NFOTimeType};
 
 #[allow(clippy::upper_case_acronyms)]
@@ -2691,6 +2692,117 @@ impl<'a> Season<'a> {
     }
 }
 
+/// 替换已生成 NFO 中的 `<chapters>` 节点，章节为空时仅移除旧节点
+pub fn replace_nfo_chapters(xml: &str, chapters: &[HighlightChapter]) -> String {
+    let mut xml = xml.to_string();
+    if let (Some(start), Some(end)) = (xml.find("<chapters>"), xml.find("</chapters>")) {
+        if start < end {
+            let line_start = xml[..start].rfind('\n').map_or(start, |idx| idx + 1);
+            let mut block_end = end + "</chapters>".len();
+            if xml[block_end..].starts_with('\n') {
+                block_end += 1;
+            }
+            xml.replace_range(line_start..block_end, "");
+        }
+    }
+    if chapters.is_empty() {
+        return xml;
+    }
+
+    let mut block = String::from("    <chapters>\n");
+    for chapter in chapters {
+        block.push_str(&format!(
+            "        <chapter>\n            <name>{}</name>\n            <start>{}</start>\n            <end>{}</end>\n        </chapter>\n",
+            html_escape::encode_text(&NFO::sanitize_xml_str(&chapter.title)),
+            chapter.start_s,
+            chapter.end_s
+        ));
+    }
+    block.push_str("    </chapters>\n");
+    // 插入到根节点的结束标签之前
+    match xml.trim_end().rfind("</") {
+        Some(idx) => xml.insert_str(idx, &block),
+        None => xml.push_str(&block),
+    }
+    xml
+}
+
+/// AI 摘要在 NFO `<plot>` 中的前缀，再次写入时据此替换旧摘要
+const AI_SUMMARY_PREFIX: &str = "【AI 摘要】";
+
+/// 把 AI 摘要写到已生成 NFO 的 `<plot>` 开头（原简介保留在摘要之后），并补充尚未存在的 `<tag>`
+pub fn apply_nfo_summary(xml: &str, summary: &str, tags: &[String]) -> String {
+    // CDATA 中不能出现结束标记
+    let cdata = |text: &str| NFO::sanitize_xml_str(text).replace("]]>", "]] >");
+    let mut xml = xml.to_string();
+
+    let plot_range = xml
+        .find("<plot>")
+        .and_then(|start| {
+            let end = start + xml[start..].find("</plot>")?;
+            Some((start, end + "</plot>".len(), &xml[start + "<plot>".len()..end]))
+        })
+        .or_else(|| {
+            ["<plot/>", "<plot />"]
+                .iter()
+                .find_map(|empty| xml.find(empty).map(|start| (start, start + empty.len(), "")))
+        });
+    let plot_span = plot_range.map(|(start, end, _)| (start, end));
+    let original = plot_range
+        .map(|(_, _, inner)| {
+            let inner = inner.trim();
+            match inner
+                .strip_prefix("<![CDATA[")
+                .and_then(|rest| rest.strip_suffix("]]>"))
+            {
+                Some(content) => content.to_string(),
+                None => html_escape::decode_html_entities(inner).into_owned(),
+            }
+        })
+        .unwrap_or_default();
+    let original = match original.strip_prefix(AI_SUMMARY_PREFIX) {
+        Some(rest) => rest
+            .split_once("\n\n")
+            .map(|(_, intro)| intro)
+            .unwrap_or("")
+            .to_string(),
+        None => original,
+    };
+    let plot = if original.trim().is_empty() {
+        format!("<plot><![CDATA[{}{}]]></plot>", AI_SUMMARY_PREFIX, cdata(summary))
+    } else {
+        format!(
+            "<plot><![CDATA[{}{}\n\n{}]]></plot>",
+            AI_SUMMARY_PREFIX,
+            cdata(summary),
+            cdata(&original)
+        )
+    };
+
+    let mut block = String::new();
+    match plot_span {
+        Some((start, end)) => xml.replace_range(start..end, &plot),
+        None => block.push_str(&format!("    {}\n", plot)),
+    }
+    for tag in tags {
+        let element = format!(
+            "<tag>{}</tag>",
+            html_escape::encode_text(&NFO::sanitize_xml_str(tag.trim()))
+        );
+        if !tag.trim().is_empty() && !xml.contains(&element) && !block.contains(&element) {
+            block.push_str(&format!("    {}\n", element));
+        }
+    }
+    if !block.is_empty() {
+        // 插入到根节点的结束标签之前
+        match xml.trim_end().rfind("</") {
+            Some(idx) => xml.insert_str(idx, &block),
+            None => xml.push_str(&block),
+        }
+    }
+    xml
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3976,4 +4088,56 @@ mod tests {
 
         println!("NFO演员信息（UP主昵称和角色）测试通过");
     }
+
+    #[test]
+    fn test_replace_nfo_chapters() {
+        let xml = "<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"yes\"?>\n<episodedetails>\n    <title>t</title>\n</episodedetails>";
+        let chapters = vec![
+            HighlightChapter {
+                start_s: 0,
+                end_s: 30,
+                title: "开头".to_string(),
+            },
+            HighlightChapter {
+                start_s: 30,
+                end_s: 60,
+                title: "高能 1 <&>".to_string(),
+            },
+        ];
+
+        let written = replace_nfo_chapters(xml, &chapters);
+        assert!(
+            written.contains("    <title>t</title>\n    <chapters>\n        <chapter>\n            <name>开头</name>")
+        );
+        assert!(written
+            .contains("<name>高能 1 &lt;&amp;&gt;</name>\n            <start>30</start>\n            <end>60</end>"));
+        assert!(written.ends_with("    </chapters>\n</episodedetails>"));
+
+        // 再次写入时替换而不是追加
+        let rewritten = replace_nfo_chapters(&written, &chapters[..1]);
+        assert_eq!(rewritten.matches("<chapters>").count(), 1);
+        assert!(!rewritten.contains("高能 1"));
+        assert_eq!(replace_nfo_chapters(&written, &[]), xml);
+    }
+
+    #[test]
+    fn test_apply_nfo_summary() {
+        let xml = "<movie>\n    <title>t</title>\n    <plot><![CDATA[原简介 <b>]]></plot>\n    <tag>播放量: 1</tag>\n</movie>";
+        let tags = vec!["科普".to_string(), "A&B".to_string(), "科普".to_string()];
+
+        let written = apply_nfo_summary(xml, "第一版摘要", &tags);
+        assert!(written.contains("<plot><![CDATA[【AI 摘要】第一版摘要\n\n原简介 <b>]]></plot>"));
+        assert!(written.ends_with("    <tag>播放量: 1</tag>\n    <tag>科普</tag>\n    <tag>A&amp;B</tag>\n</movie>"));
+
+        // 再次写入时替换旧摘要，保留原简介，不重复添加标签
+        let rewritten = apply_nfo_summary(&written, "第二版摘要", &tags);
+        assert!(rewritten.contains("<plot><![CDATA[【AI 摘要】第二版摘要\n\n原简介 <b>]]></plot>"));
+        assert_eq!(rewritten.matches("<tag>科普</tag>").count(), 1);
+
+        let empty_plot = apply_nfo_summary("<episodedetails>\n    <plot/>\n</episodedetails>", "摘要", &[]);
+        assert_eq!(
+            empty_plot,
+            "<episodedetails>\n    <plot><![CDATA[【AI 摘要】摘要]]></plot>\n</episodedetails>"
+        );
+    }
 }
@@ -103,7 +103,9 @@ pub struct NewVideoInfo {
     pub bvid: String,
     pub pubtime: Option<String>, // 使用字符串格式的北京时间
     pub episode_number: Option<i32>,
-    pub video_id: Option<i32>, // 添加视频ID字段，用于过滤删除队列中的视频
+    pub video_id: Option<i32>,      // 添加视频ID字段，用于过滤删除队列中的视频
+    pub ai_summary: Option<String>, // AI 视频摘要（开启 video_summary 时生成）
+    pub ai_tags: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -845,6 +847,15 @@ impl NotificationClient {
 
                         content.push_str(&video_line);
                         content.push('\n');
+
+                        if let Some(ai_summary) = &video.ai_summary {
+                            let mut summary_line = format!("  > {}", Self::sanitize_for_serverchan(ai_summary));
+                            if !video.ai_tags.is_empty() {
+                                summary_line.push_str(&format!(" 标签: {}", video.ai_tags.join(" / ")));
+                            }
+                            content.push_str(&summary_line);
+                            content.push('\n');
+                        }
                     }
 
                     // 如果有未显示的视频，添加提示
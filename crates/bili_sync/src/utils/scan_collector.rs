@@ -135,5 +135,7 @@ pub fn create_new_video_info(title: &str, bvid: &str) -> NewVideoInfo {
         pubtime: None,
         episode_number: None,
         video_id: None,
+        ai_summary: None,
+        ai_tags: Vec::new(),
     }
 }
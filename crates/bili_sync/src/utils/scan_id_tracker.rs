@@ -19,6 +19,8 @@ pub struct LastScannedIds {
     pub watch_later: Option<i32>,
     #[serde(default)]
     pub bangumi: Option<i32>,
+    #[serde(default)]
+    pub ranking: Option<i32>,
 
     // 记录每种类型源上次处理的ID（用于断点续传）
     #[serde(default)]
@@ -31,6 +33,8 @@ pub struct LastScannedIds {
     pub last_processed_watch_later: Option<i32>,
     #[serde(default)]
     pub last_processed_bangumi: Option<i32>,
+    #[serde(default)]
+    pub last_processed_ranking: Option<i32>,
 }
 
 const CONFIG_KEY: &str = "last_scanned_ids";
@@ -98,6 +102,8 @@ pub struct VideoSourceWithId {
     pub args: crate::adapter::Args,
     pub path: std::path::PathBuf,
     pub source_type: SourceType,
+    /// 视频源绑定的B站账号ID，`None` 表示默认账号
+    pub credential_id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -107,6 +113,21 @@ pub enum SourceType {
     Submission,
     WatchLater,
     Bangumi,
+    Ranking,
+}
+
+impl SourceType {
+    /// 与 API 中 source_type 参数一致的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceType::Collection => "collection",
+            SourceType::Favorite => "favorite",
+            SourceType::Submission => "submission",
+            SourceType::WatchLater => "watch_later",
+            SourceType::Bangumi => "bangumi",
+            SourceType::Ranking => "ranking",
+        }
+    }
 }
 
 /// 将视频源按新旧分组，并支持断点续传
@@ -127,6 +148,7 @@ pub fn group_sources_by_new_old(
                 last_scanned_ids.last_processed_watch_later,
             ),
             SourceType::Bangumi => (last_scanned_ids.bangumi, last_scanned_ids.last_processed_bangumi),
+            SourceType::Ranking => (last_scanned_ids.ranking, last_scanned_ids.last_processed_ranking),
         };
 
         // 如果没有记录（首次运行）或ID大于最大ID，则为新源
@@ -220,6 +242,9 @@ impl MaxIdRecorder {
                 SourceType::Bangumi => {
                     last_scanned_ids.bangumi = Some(max_id.max(last_scanned_ids.bangumi.unwrap_or(0)));
                 }
+                SourceType::Ranking => {
+                    last_scanned_ids.ranking = Some(max_id.max(last_scanned_ids.ranking.unwrap_or(0)));
+                }
             }
         }
 
@@ -241,6 +266,9 @@ impl MaxIdRecorder {
                 SourceType::Bangumi => {
                     last_scanned_ids.last_processed_bangumi = Some(processed_id);
                 }
+                SourceType::Ranking => {
+                    last_scanned_ids.last_processed_ranking = Some(processed_id);
+                }
             }
         }
     }
@@ -254,5 +282,6 @@ impl LastScannedIds {
         self.last_processed_submission = None;
         self.last_processed_watch_later = None;
         self.last_processed_bangumi = None;
+        self.last_processed_ranking = None;
     }
 }
@@ -0,0 +1,1028 @@
+//! 视频源与设置的导出/导入包
+//!
+//! 导出包是带版本号的 JSON/YAML 文档，包含全部视频源（收藏夹、合集、UP主投稿、稍后再看、番剧、榜单）
+//! 的过滤与下载开关，以及去除了密钥的配置项。导入时新视频源复用添加视频源的校验流程，
+//! 已存在的视频源按冲突策略跳过或覆盖设置，并支持只返回差异的预演模式。
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bili_sync_entity::entities::config_item;
+use bili_sync_entity::{collection, favorite, ranking, submission, video_source, watch_later};
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::api::request::AddVideoSourceRequest;
+use crate::api::response::{BundleConfigResult, BundleFieldChange, BundleSourceResult, ImportBundleResponse};
+use crate::bilibili::{FilterOption, DEFAULT_AI_SUBTITLE_LANGUAGE};
+use crate::config::SourceFilterOption;
+
+/// 当前导出包格式版本，格式发生不兼容变化时递增
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// 不导出也不导入的配置项：登录凭据、管理页令牌等
+const EXCLUDED_CONFIG_KEYS: &[&str] = &[
+    "auth_token",
+    "stream_link_secret",
+    "credential",
+    "bili_accounts",
+    "bili_account_next_id",
+    "actors_field_initialized",
+];
+
+const SOURCE_TYPES: &[&str] = &[
+    "favorite",
+    "collection",
+    "submission",
+    "watch_later",
+    "bangumi",
+    "ranking",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SourceBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub exported_at: String,
+    #[serde(default)]
+    pub sources: Vec<BundleSource>,
+    #[serde(default)]
+    pub config: BTreeMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleSource {
+    /// 视频源类型：favorite、collection、submission、watch_later、bangumi、ranking
+    pub source_type: String,
+    /// 收藏夹ID、合集ID、UP主ID、番剧 Season ID 或榜单分区ID
+    #[serde(default)]
+    pub source_id: String,
+    pub name: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub up_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_aggregate_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ranking_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ep_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_all_seasons: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_seasons: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_videos: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_dynamic_api: Option<bool>,
+    #[serde(default)]
+    pub settings: SourceSettings,
+}
+
+/// 各类视频源共有的过滤与下载开关
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SourceSettings {
+    pub enabled: bool,
+    pub scan_deleted_videos: bool,
+    pub filter_option: Option<FilterOption>,
+    pub quality_profile: Option<String>,
+    pub keyword_filters: Option<Vec<String>>,
+    pub keyword_filter_mode: Option<String>,
+    pub blacklist_keywords: Option<Vec<String>>,
+    pub whitelist_keywords: Option<Vec<String>>,
+    pub keyword_case_sensitive: bool,
+    pub min_duration_seconds: Option<i32>,
+    pub max_duration_seconds: Option<i32>,
+    pub published_after: Option<String>,
+    pub published_before: Option<String>,
+    pub audio_only: bool,
+    pub audio_only_m4a_only: bool,
+    pub flat_folder: bool,
+    pub split_chapters_after_download: bool,
+    pub download_charge_videos: bool,
+    pub download_danmaku: bool,
+    pub archive_comments: bool,
+    pub download_subtitle: bool,
+    pub download_ai_subtitle: bool,
+    pub ai_subtitle_language: String,
+    pub ai_rename: bool,
+    pub ai_rename_video_prompt: String,
+    pub ai_rename_audio_prompt: String,
+    pub ai_rename_enable_multi_page: bool,
+    pub ai_rename_enable_collection: bool,
+    pub ai_rename_enable_bangumi: bool,
+    pub ai_rename_rename_parent_dir: bool,
+    pub credential_id: Option<i32>,
+}
+
+impl Default for SourceSettings {
+    // 与添加视频源时的默认值保持一致
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            scan_deleted_videos: false,
+            filter_option: None,
+            quality_profile: None,
+            keyword_filters: None,
+            keyword_filter_mode: None,
+            blacklist_keywords: None,
+            whitelist_keywords: None,
+            keyword_case_sensitive: true,
+            min_duration_seconds: None,
+            max_duration_seconds: None,
+            published_after: None,
+            published_before: None,
+            audio_only: false,
+            audio_only_m4a_only: false,
+            flat_folder: false,
+            split_chapters_after_download: false,
+            download_charge_videos: true,
+            download_danmaku: true,
+            archive_comments: false,
+            download_subtitle: true,
+            download_ai_subtitle: true,
+            ai_subtitle_language: DEFAULT_AI_SUBTITLE_LANGUAGE.to_string(),
+            ai_rename: false,
+            ai_rename_video_prompt: String::new(),
+            ai_rename_audio_prompt: String::new(),
+            ai_rename_enable_multi_page: false,
+            ai_rename_enable_collection: false,
+            ai_rename_enable_bangumi: false,
+            ai_rename_rename_parent_dir: false,
+            credential_id: None,
+        }
+    }
+}
+
+/// 导入时已存在视频源/配置项的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// 保留现有设置
+    #[default]
+    Skip,
+    /// 使用导入包中的设置覆盖
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleFormat {
+    Json,
+    Yaml,
+}
+
+impl BundleFormat {
+    pub fn parse(value: Option<&str>) -> Result<Self> {
+        match value.map(|value| value.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("json") => Ok(BundleFormat::Json),
+            Some("yaml") | Some("yml") => Ok(BundleFormat::Yaml),
+            Some(other) => bail!("不支持的导出格式: {}，可选 json 或 yaml", other),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            BundleFormat::Json => "json",
+            BundleFormat::Yaml => "yaml",
+        }
+    }
+}
+
+impl SourceBundle {
+    pub fn to_text(&self, format: BundleFormat) -> Result<String> {
+        Ok(match format {
+            BundleFormat::Json => serde_json::to_string_pretty(self)?,
+            BundleFormat::Yaml => serde_yaml::to_string(self)?,
+        })
+    }
+
+    /// 解析导出包，JSON 是 YAML 的子集，因此统一按 YAML 解析
+    pub fn parse(content: &str) -> Result<Self> {
+        let bundle: SourceBundle = serde_yaml::from_str(content).context("导入包格式无效")?;
+        if bundle.version == 0 || bundle.version > BUNDLE_VERSION {
+            bail!(
+                "导入包版本 {} 不受支持，当前程序支持的最高版本为 {}",
+                bundle.version,
+                BUNDLE_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+}
+
+fn json_list(value: &Option<String>) -> Option<Vec<String>> {
+    value
+        .as_deref()
+        .and_then(|value| serde_json::from_str::<Vec<String>>(value).ok())
+        .filter(|list| !list.is_empty())
+}
+
+fn list_json(value: &Option<Vec<String>>) -> Option<String> {
+    value
+        .as_ref()
+        .filter(|list| !list.is_empty())
+        .and_then(|list| serde_json::to_string(list).ok())
+}
+
+fn split_filter_option(value: &Option<Value>) -> (Option<FilterOption>, Option<String>) {
+    match value.as_ref().map(SourceFilterOption::from_json) {
+        Some(Ok(SourceFilterOption::Custom(filter_option))) => (Some(filter_option), None),
+        Some(Ok(SourceFilterOption::Profile { profile })) => (None, Some(profile)),
+        _ => (None, None),
+    }
+}
+
+fn settings_filter_json(settings: &SourceSettings) -> Result<Option<Value>> {
+    if let Some(profile) = settings.quality_profile.as_deref().filter(|p| !p.trim().is_empty()) {
+        return Ok(Some(serde_json::to_value(SourceFilterOption::Profile {
+            profile: profile.trim().to_string(),
+        })?));
+    }
+    settings
+        .filter_option
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// 各视频源表的公共列名一致，用宏统一转换
+macro_rules! source_settings {
+    ($model:expr) => {{
+        let model = &$model;
+        let (filter_option, quality_profile) = split_filter_option(&model.filter_option);
+        SourceSettings {
+            enabled: model.enabled,
+            scan_deleted_videos: model.scan_deleted_videos,
+            filter_option,
+            quality_profile,
+            keyword_filters: json_list(&model.keyword_filters),
+            keyword_filter_mode: model.keyword_filter_mode.clone(),
+            blacklist_keywords: json_list(&model.blacklist_keywords),
+            whitelist_keywords: json_list(&model.whitelist_keywords),
+            keyword_case_sensitive: model.keyword_case_sensitive,
+            min_duration_seconds: model.min_duration_seconds,
+            max_duration_seconds: model.max_duration_seconds,
+            published_after: model.published_after.clone(),
+            published_before: model.published_before.clone(),
+            audio_only: model.audio_only,
+            audio_only_m4a_only: model.audio_only_m4a_only,
+            flat_folder: model.flat_folder,
+            split_chapters_after_download: model.split_chapters_after_download,
+            download_charge_videos: model.download_charge_videos,
+            download_danmaku: model.download_danmaku,
+            archive_comments: model.archive_comments,
+            download_subtitle: model.download_subtitle,
+            download_ai_subtitle: model.download_ai_subtitle,
+            ai_subtitle_language: model.ai_subtitle_language.clone(),
+            ai_rename: model.ai_rename,
+            ai_rename_video_prompt: model.ai_rename_video_prompt.clone(),
+            ai_rename_audio_prompt: model.ai_rename_audio_prompt.clone(),
+            ai_rename_enable_multi_page: model.ai_rename_enable_multi_page,
+            ai_rename_enable_collection: model.ai_rename_enable_collection,
+            ai_rename_enable_bangumi: model.ai_rename_enable_bangumi,
+            ai_rename_rename_parent_dir: model.ai_rename_rename_parent_dir,
+            credential_id: model.credential_id,
+        }
+    }};
+}
+
+/// 将导入包中的设置写回视频源表，可选同时更新名称列
+macro_rules! update_source_settings {
+    ($entity:ident, $db:expr, $id:expr, $settings:expr, $filter_option:expr $(, $name_column:ident = $name:expr)?) => {{
+        let settings: &SourceSettings = $settings;
+        $entity::Entity::update($entity::ActiveModel {
+            id: Unchanged($id),
+            $($name_column: Set($name),)?
+            enabled: Set(settings.enabled),
+            scan_deleted_videos: Set(settings.scan_deleted_videos),
+            filter_option: Set($filter_option),
+            keyword_filters: Set(list_json(&settings.keyword_filters)),
+            keyword_filter_mode: Set(settings.keyword_filter_mode.clone()),
+            blacklist_keywords: Set(list_json(&settings.blacklist_keywords)),
+            whitelist_keywords: Set(list_json(&settings.whitelist_keywords)),
+            keyword_case_sensitive: Set(settings.keyword_case_sensitive),
+            min_duration_seconds: Set(settings.min_duration_seconds),
+            max_duration_seconds: Set(settings.max_duration_seconds),
+            published_after: Set(settings.published_after.clone()),
+            published_before: Set(settings.published_before.clone()),
+            audio_only: Set(settings.audio_only),
+            audio_only_m4a_only: Set(settings.audio_only_m4a_only),
+            flat_folder: Set(settings.flat_folder),
+            split_chapters_after_download: Set(settings.split_chapters_after_download),
+            download_charge_videos: Set(settings.download_charge_videos),
+            download_danmaku: Set(settings.download_danmaku),
+            archive_comments: Set(settings.archive_comments),
+            download_subtitle: Set(settings.download_subtitle),
+            download_ai_subtitle: Set(settings.download_ai_subtitle),
+            ai_subtitle_language: Set(settings.ai_subtitle_language.clone()),
+            ai_rename: Set(settings.ai_rename),
+            ai_rename_video_prompt: Set(settings.ai_rename_video_prompt.clone()),
+            ai_rename_audio_prompt: Set(settings.ai_rename_audio_prompt.clone()),
+            ai_rename_enable_multi_page: Set(settings.ai_rename_enable_multi_page),
+            ai_rename_enable_collection: Set(settings.ai_rename_enable_collection),
+            ai_rename_enable_bangumi: Set(settings.ai_rename_enable_bangumi),
+            ai_rename_rename_parent_dir: Set(settings.ai_rename_rename_parent_dir),
+            credential_id: Set(settings.credential_id),
+            ..Default::default()
+        })
+        .exec($db)
+        .await?;
+    }};
+}
+
+fn favorite_source(model: &favorite::Model) -> BundleSource {
+    BundleSource {
+        source_id: model.f_id.to_string(),
+        ..bare_source("favorite", &model.name, &model.path, source_settings!(model))
+    }
+}
+
+fn collection_source(model: &collection::Model) -> BundleSource {
+    BundleSource {
+        source_id: model.s_id.to_string(),
+        up_id: Some(model.m_id.to_string()),
+        collection_type: Some(if model.r#type == 1 { "series" } else { "season" }.to_string()),
+        collection_aggregate_enabled: Some(model.aggregate_enabled),
+        cover: model.cover.clone(),
+        ..bare_source("collection", &model.name, &model.path, source_settings!(model))
+    }
+}
+
+fn submission_source(model: &submission::Model) -> BundleSource {
+    BundleSource {
+        source_id: model.upper_id.to_string(),
+        selected_videos: json_list(&model.selected_videos),
+        use_dynamic_api: Some(model.use_dynamic_api),
+        ..bare_source("submission", &model.upper_name, &model.path, source_settings!(model))
+    }
+}
+
+fn watch_later_source(model: &watch_later::Model) -> BundleSource {
+    bare_source("watch_later", "稍后再看", &model.path, source_settings!(model))
+}
+
+fn bangumi_source(model: &video_source::Model) -> BundleSource {
+    BundleSource {
+        source_id: model.season_id.clone().unwrap_or_default(),
+        media_id: model.media_id.clone(),
+        ep_id: model.ep_id.clone(),
+        download_all_seasons: model.download_all_seasons,
+        selected_seasons: json_list(&model.selected_seasons),
+        ..bare_source("bangumi", &model.name, &model.path, source_settings!(model))
+    }
+}
+
+fn ranking_source(model: &ranking::Model) -> BundleSource {
+    BundleSource {
+        source_id: model.rid.to_string(),
+        ranking_type: Some(model.ranking_type.clone()),
+        ..bare_source("ranking", &model.name, &model.path, source_settings!(model))
+    }
+}
+
+fn bare_source(source_type: &str, name: &str, path: &str, settings: SourceSettings) -> BundleSource {
+    BundleSource {
+        source_type: source_type.to_string(),
+        source_id: String::new(),
+        name: name.to_string(),
+        path: path.to_string(),
+        up_id: None,
+        collection_type: None,
+        collection_aggregate_enabled: None,
+        cover: None,
+        ranking_type: None,
+        media_id: None,
+        ep_id: None,
+        download_all_seasons: None,
+        selected_seasons: None,
+        selected_videos: None,
+        use_dynamic_api: None,
+        settings,
+    }
+}
+
+/// 字段名带有这些特征的配置值视为密钥，导出时移除
+fn is_secret_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name == "key"
+        || name.ends_with("_key")
+        || name.ends_with("sendkey")
+        || name.ends_with("webhook_url")
+        || name.ends_with("custom_headers")
+        || ["token", "secret", "password", "cookie", "sessdata"]
+            .iter()
+            .any(|marker| name.contains(marker))
+}
+
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !is_secret_field(key));
+            map.values_mut().for_each(redact_secrets);
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// 用导入值覆盖现有值，导入包中缺失的字段（包括被移除的密钥）保持不变
+fn merge_preserving_secrets(existing: &mut Value, incoming: &Value) {
+    match (existing, incoming) {
+        (Value::Object(existing), Value::Object(incoming)) => {
+            for (key, value) in incoming {
+                match existing.get_mut(key) {
+                    Some(current) => merge_preserving_secrets(current, value),
+                    None => {
+                        existing.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (existing, incoming) => *existing = incoming.clone(),
+    }
+}
+
+fn exportable_config() -> Result<BTreeMap<String, Value>> {
+    let Value::Object(map) = serde_json::to_value(crate::config::reload_config())? else {
+        bail!("配置必须是JSON对象");
+    };
+    Ok(map
+        .into_iter()
+        .filter(|(key, _)| !EXCLUDED_CONFIG_KEYS.contains(&key.as_str()))
+        .map(|(key, mut value)| {
+            redact_secrets(&mut value);
+            (key, value)
+        })
+        .collect())
+}
+
+/// 导出全部视频源与去除密钥后的配置
+pub async fn export_bundle(db: &DatabaseConnection) -> Result<SourceBundle> {
+    let mut sources = Vec::new();
+    for model in favorite::Entity::find()
+        .order_by_asc(favorite::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(favorite_source(&model));
+    }
+    for model in collection::Entity::find()
+        .order_by_asc(collection::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(collection_source(&model));
+    }
+    for model in submission::Entity::find()
+        .order_by_asc(submission::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(submission_source(&model));
+    }
+    for model in watch_later::Entity::find()
+        .order_by_asc(watch_later::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(watch_later_source(&model));
+    }
+    for model in video_source::Entity::find()
+        .filter(video_source::Column::Type.eq(1)) // 番剧类型
+        .order_by_asc(video_source::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(bangumi_source(&model));
+    }
+    for model in ranking::Entity::find()
+        .order_by_asc(ranking::Column::Id)
+        .all(db)
+        .await?
+    {
+        sources.push(ranking_source(&model));
+    }
+
+    Ok(SourceBundle {
+        version: BUNDLE_VERSION,
+        app_version: crate::config::version().to_string(),
+        exported_at: crate::utils::time_format::now_standard_string(),
+        sources,
+        config: exportable_config()?,
+    })
+}
+
+/// 按各类型的唯一键查找已存在的视频源
+pub(crate) async fn find_existing(
+    db: &DatabaseConnection,
+    source: &BundleSource,
+) -> Result<Option<(i32, BundleSource)>> {
+    let source_id = source.source_id.trim();
+    Ok(match source.source_type.as_str() {
+        "favorite" => {
+            let f_id = source_id.parse::<i64>().map_err(|_| anyhow!("无效的收藏夹ID"))?;
+            favorite::Entity::find()
+                .filter(favorite::Column::FId.eq(f_id))
+                .one(db)
+                .await?
+                .map(|model| (model.id, favorite_source(&model)))
+        }
+        "collection" => {
+            let s_id = source_id.parse::<i64>().map_err(|_| anyhow!("无效的合集ID"))?;
+            let m_id = source
+                .up_id
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| anyhow!("无效的UP主ID"))?;
+            let collection_type = if source.collection_type.as_deref() == Some("series") {
+                1
+            } else {
+                2
+            };
+            collection::Entity::find()
+                .filter(collection::Column::SId.eq(s_id))
+                .filter(collection::Column::MId.eq(m_id))
+                .filter(collection::Column::Type.eq(collection_type))
+                .one(db)
+                .await?
+                .map(|model| (model.id, collection_source(&model)))
+        }
+        "submission" => {
+            let upper_id = source_id.parse::<i64>().map_err(|_| anyhow!("无效的UP主ID"))?;
+            submission::Entity::find()
+                .filter(submission::Column::UpperId.eq(upper_id))
+                .one(db)
+                .await?
+                .map(|model| (model.id, submission_source(&model)))
+        }
+        "watch_later" => watch_later::Entity::find()
+            .one(db)
+            .await?
+            .map(|model| (model.id, watch_later_source(&model))),
+        "bangumi" => {
+            let mut query = video_source::Entity::find().filter(video_source::Column::Type.eq(1));
+            query = if !source_id.is_empty() {
+                query.filter(video_source::Column::SeasonId.eq(source_id))
+            } else if let Some(media_id) = source.media_id.as_deref() {
+                query.filter(video_source::Column::MediaId.eq(media_id))
+            } else if let Some(ep_id) = source.ep_id.as_deref() {
+                query.filter(video_source::Column::EpId.eq(ep_id))
+            } else {
+                bail!("番剧需要提供 Season ID、Media ID 或 Episode ID");
+            };
+            query.one(db).await?.map(|model| (model.id, bangumi_source(&model)))
+        }
+        "ranking" => {
+            let ranking_type = source
+                .ranking_type
+                .as_deref()
+                .unwrap_or("popular")
+                .parse::<crate::bilibili::RankingType>()?;
+            let rid = match ranking_type {
+                crate::bilibili::RankingType::Rank if !source_id.is_empty() => {
+                    source_id.parse::<i32>().map_err(|_| anyhow!("无效的排行榜分区ID"))?
+                }
+                _ => 0,
+            };
+            ranking::Entity::find()
+                .filter(ranking::Column::RankingType.eq(ranking_type.as_str()))
+                .filter(ranking::Column::Rid.eq(rid))
+                .one(db)
+                .await?
+                .map(|model| (model.id, ranking_source(&model)))
+        }
+        other => bail!("不支持的视频源类型: {}", other),
+    })
+}
+
+/// 与关键词过滤器接口一致的校验；返回需要提示给用户的说明
+fn validate_settings(settings: &mut SourceSettings) -> Result<Option<String>> {
+    use crate::utils::keyword_filter::validate_regex;
+    use chrono::NaiveDate;
+
+    for (label, list) in [
+        ("黑名单", &settings.blacklist_keywords),
+        ("白名单", &settings.whitelist_keywords),
+        ("关键词", &settings.keyword_filters),
+    ] {
+        for pattern in list.iter().flatten() {
+            validate_regex(pattern).map_err(|e| anyhow!("{}正则表达式验证失败: {} - {}", label, pattern, e))?;
+        }
+    }
+    if settings.min_duration_seconds.is_some_and(|value| value < 0)
+        || settings.max_duration_seconds.is_some_and(|value| value < 0)
+    {
+        bail!("时长过滤不能小于 0 秒");
+    }
+    if let (Some(min), Some(max)) = (settings.min_duration_seconds, settings.max_duration_seconds) {
+        if min > max {
+            bail!("最短时长不能大于最长时长");
+        }
+    }
+    for date in [&settings.published_after, &settings.published_before]
+        .into_iter()
+        .flatten()
+    {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| anyhow!("投稿日期格式无效，必须为 YYYY-MM-DD"))?;
+    }
+    if let Some(filter_option) = &settings.filter_option {
+        filter_option.validate()?;
+    }
+
+    let config = crate::config::reload_config();
+    if let Some(profile) = settings.quality_profile.as_deref() {
+        if !config.quality_profiles.iter().any(|item| item.name == profile) {
+            bail!("画质档案「{}」不存在", profile);
+        }
+    }
+    // 账号ID在不同部署间不一定相同，找不到时回退为默认账号而不是拒绝导入
+    if let Some(id) = settings.credential_id {
+        if config.bili_account(id).is_none() {
+            settings.credential_id = None;
+            return Ok(Some(format!("B站账号 {} 不存在，已改为使用默认账号", id)));
+        }
+    }
+    Ok(None)
+}
+
+fn diff_sources(current: &BundleSource, incoming: &BundleSource) -> Vec<BundleFieldChange> {
+    let mut changes = Vec::new();
+    if current.name != incoming.name && !incoming.name.trim().is_empty() {
+        changes.push(BundleFieldChange {
+            field: "name".to_string(),
+            current: Value::String(current.name.clone()),
+            incoming: Value::String(incoming.name.clone()),
+        });
+    }
+    let (Ok(Value::Object(current_settings)), Ok(Value::Object(incoming_settings))) = (
+        serde_json::to_value(&current.settings),
+        serde_json::to_value(&incoming.settings),
+    ) else {
+        return changes;
+    };
+    for (field, incoming_value) in incoming_settings {
+        let current_value = current_settings.get(&field).cloned().unwrap_or(Value::Null);
+        if current_value != incoming_value {
+            changes.push(BundleFieldChange {
+                field: format!("settings.{}", field),
+                current: current_value,
+                incoming: incoming_value,
+            });
+        }
+    }
+    changes
+}
+
+fn add_request(source: &BundleSource) -> AddVideoSourceRequest {
+    let settings = &source.settings;
+    AddVideoSourceRequest {
+        source_type: source.source_type.clone(),
+        source_id: source.source_id.clone(),
+        up_id: source.up_id.clone(),
+        name: source.name.clone(),
+        path: source.path.clone(),
+        collection_type: source.collection_type.clone(),
+        ranking_type: source.ranking_type.clone(),
+        collection_aggregate_enabled: source.collection_aggregate_enabled,
+        filter_option: settings.filter_option.clone(),
+        quality_profile: settings.quality_profile.clone(),
+        credential_id: settings.credential_id,
+        media_id: source.media_id.clone(),
+        ep_id: source.ep_id.clone(),
+        download_all_seasons: source.download_all_seasons,
+        selected_seasons: source.selected_seasons.clone(),
+        selected_videos: source.selected_videos.clone(),
+        cover: source.cover.clone(),
+        merge_to_source_id: None,
+        keyword_filters: settings.keyword_filters.clone(),
+        keyword_filter_mode: settings.keyword_filter_mode.clone(),
+        audio_only: Some(settings.audio_only),
+        download_danmaku: Some(settings.download_danmaku),
+        archive_comments: Some(settings.archive_comments),
+        download_subtitle: Some(settings.download_subtitle),
+        download_ai_subtitle: Some(settings.download_ai_subtitle),
+        ai_subtitle_language: Some(settings.ai_subtitle_language.clone()),
+        ai_rename: Some(settings.ai_rename),
+        ai_rename_video_prompt: Some(settings.ai_rename_video_prompt.clone()),
+        ai_rename_audio_prompt: Some(settings.ai_rename_audio_prompt.clone()),
+        ai_rename_enable_multi_page: Some(settings.ai_rename_enable_multi_page),
+        ai_rename_enable_collection: Some(settings.ai_rename_enable_collection),
+        ai_rename_enable_bangumi: Some(settings.ai_rename_enable_bangumi),
+        ai_rename_rename_parent_dir: Some(settings.ai_rename_rename_parent_dir),
+        audio_only_m4a_only: Some(settings.audio_only_m4a_only),
+        flat_folder: Some(settings.flat_folder),
+        split_chapters_after_download: Some(settings.split_chapters_after_download),
+        download_charge_videos: Some(settings.download_charge_videos),
+        use_dynamic_api: source.use_dynamic_api,
+    }
+}
+
+/// 写入添加视频源接口未覆盖的设置（启用状态、黑白名单、时长与日期过滤等），或覆盖已有视频源的设置
+async fn apply_settings(db: &DatabaseConnection, source_type: &str, id: i32, source: &BundleSource) -> Result<()> {
+    let settings = &source.settings;
+    let filter_option = settings_filter_json(settings)?;
+    let name = source.name.clone();
+    match source_type {
+        "favorite" => update_source_settings!(favorite, db, id, settings, filter_option, name = name),
+        "collection" => update_source_settings!(collection, db, id, settings, filter_option, name = name),
+        "submission" => update_source_settings!(submission, db, id, settings, filter_option, upper_name = name),
+        "watch_later" => update_source_settings!(watch_later, db, id, settings, filter_option),
+        "bangumi" => update_source_settings!(video_source, db, id, settings, filter_option, name = name),
+        "ranking" => update_source_settings!(ranking, db, id, settings, filter_option, name = name),
+        other => bail!("不支持的视频源类型: {}", other),
+    }
+    Ok(())
+}
+
+async fn import_source(
+    db: &Arc<DatabaseConnection>,
+    mut source: BundleSource,
+    strategy: ConflictStrategy,
+    dry_run: bool,
+) -> BundleSourceResult {
+    let mut result = BundleSourceResult {
+        source_type: source.source_type.clone(),
+        source_id: source.source_id.clone(),
+        name: source.name.clone(),
+        action: "failed".to_string(),
+        changes: Vec::new(),
+        note: None,
+        error: None,
+    };
+
+    let outcome: Result<()> = async {
+        if !SOURCE_TYPES.contains(&source.source_type.as_str()) {
+            bail!("不支持的视频源类型: {}", source.source_type);
+        }
+        result.note = validate_settings(&mut source.settings)?;
+        match find_existing(db, &source).await? {
+            None => {
+                result.action = "create".to_string();
+                if !dry_run {
+                    let response = crate::api::handler::add_video_source_internal(db.clone(), add_request(&source))
+                        .await
+                        .map_err(|e| e.into_inner())?;
+                    apply_settings(db, &source.source_type, response.source_id, &source).await?;
+                }
+            }
+            Some((id, current)) => {
+                result.changes = diff_sources(&current, &source);
+                if current.path != source.path {
+                    result.note = Some(format!(
+                        "保存路径不同（当前 {}），导入不会修改已有视频源的路径",
+                        current.path
+                    ));
+                }
+                if result.changes.is_empty() {
+                    result.action = "unchanged".to_string();
+                } else if crate::config::is_source_managed(&source.source_type, id) {
+                    result.action = "skipped".to_string();
+                    result.note = Some("该视频源由声明式配置文件管理".to_string());
+                } else if strategy == ConflictStrategy::Skip {
+                    result.action = "skipped".to_string();
+                } else {
+                    result.action = "update".to_string();
+                    if !dry_run {
+                        if source.name.trim().is_empty() {
+                            source.name = current.name.clone();
+                        }
+                        apply_settings(db, &source.source_type, id, &source).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = outcome {
+        result.action = "failed".to_string();
+        result.error = Some(format!("{:#}", e));
+    }
+    result
+}
+
+/// 计划并（非预演时）写入配置项变更
+async fn import_config(
+    db: &DatabaseConnection,
+    incoming: &BTreeMap<String, Value>,
+    strategy: ConflictStrategy,
+    dry_run: bool,
+) -> Result<Vec<BundleConfigResult>> {
+    let current = exportable_config()?;
+    let stored_keys: HashSet<String> = config_item::Entity::find()
+        .select_only()
+        .column(config_item::Column::KeyName)
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut results = Vec::new();
+    let mut updates = Vec::new();
+    for (key, value) in incoming {
+        let (action, note) = if EXCLUDED_CONFIG_KEYS.contains(&key.as_str()) {
+            ("ignored", Some("凭据类配置不会被导入".to_string()))
+        } else if !current.contains_key(key) {
+            ("ignored", Some("当前版本不存在该配置项".to_string()))
+        } else if crate::config::is_config_key_managed(key) {
+            ("ignored", Some("该配置项由声明式配置文件管理".to_string()))
+        } else if current.get(key) == Some(value) {
+            continue;
+        } else if stored_keys.contains(key) && strategy == ConflictStrategy::Skip {
+            ("skipped", None)
+        } else {
+            updates.push(key.clone());
+            ("update", None)
+        };
+        results.push(BundleConfigResult {
+            key: key.clone(),
+            label: crate::config::describe_config_key(key).to_string(),
+            action: action.to_string(),
+            note,
+        });
+    }
+
+    if dry_run || updates.is_empty() {
+        return Ok(results);
+    }
+
+    // 先在完整配置上合并并校验，全部通过后再逐项写入
+    let mut merged = serde_json::to_value(crate::config::reload_config())?;
+    for key in &updates {
+        if let Some(target) = merged.get_mut(key) {
+            merge_preserving_secrets(target, &incoming[key]);
+        }
+    }
+    serde_json::from_value::<crate::config::Config>(merged.clone()).context("导入的配置无效")?;
+
+    let manager = crate::config::ConfigManager::new(db.clone());
+    for key in &updates {
+        manager.update_config_item(key, merged[key].clone()).await?;
+    }
+    crate::config::reload_config_bundle().await?;
+    info!("已从导入包更新 {} 个配置项", updates.len());
+    Ok(results)
+}
+
+/// 导入视频源与配置，配置先于视频源导入，以便视频源引用导入包中的画质档案
+pub async fn import_bundle(
+    db: Arc<DatabaseConnection>,
+    bundle: SourceBundle,
+    strategy: ConflictStrategy,
+    include_config: bool,
+    dry_run: bool,
+) -> Result<ImportBundleResponse> {
+    let config = if include_config {
+        import_config(&db, &bundle.config, strategy, dry_run).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut sources = Vec::with_capacity(bundle.sources.len());
+    for source in bundle.sources {
+        let result = import_source(&db, source, strategy, dry_run).await;
+        if let Some(error) = &result.error {
+            warn!(
+                "导入视频源失败: {} {} {} - {}",
+                result.source_type, result.source_id, result.name, error
+            );
+        }
+        sources.push(result);
+    }
+
+    let count = |action: &str| sources.iter().filter(|result| result.action == action).count();
+    let response = ImportBundleResponse {
+        dry_run,
+        strategy,
+        created: count("create"),
+        updated: count("update"),
+        skipped: count("skipped"),
+        unchanged: count("unchanged"),
+        failed: count("failed"),
+        sources,
+        config,
+    };
+    if !dry_run {
+        info!(
+            "导入完成：新增 {} 个、更新 {} 个、跳过 {} 个、失败 {} 个视频源",
+            response.created, response.updated, response.skipped, response.failed
+        );
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redact_secrets_removes_nested_keys_and_tokens() {
+        let mut value = json!({
+            "active_channel": "webhook",
+            "serverchan_key": "SCT123",
+            "serverchan3_sendkey": "abc",
+            "webhook_url": "https://example.com/hook",
+            "webhook_bearer_token": "t",
+            "ai": {"api_key": "sk-1", "model": "gpt", "deepseek_web_token": "x"},
+            "keyword_filters": ["a"],
+        });
+        redact_secrets(&mut value);
+        assert_eq!(
+            value,
+            json!({"active_channel": "webhook", "ai": {"model": "gpt"}, "keyword_filters": ["a"]})
+        );
+    }
+
+    #[test]
+    fn merge_keeps_existing_secrets() {
+        let mut existing = json!({"api_key": "sk-1", "model": "old", "timeout_seconds": 30});
+        merge_preserving_secrets(&mut existing, &json!({"model": "new"}));
+        assert_eq!(
+            existing,
+            json!({"api_key": "sk-1", "model": "new", "timeout_seconds": 30})
+        );
+    }
+
+    #[test]
+    fn bundle_round_trips_through_yaml_and_json() {
+        let mut source = bare_source("favorite", "收藏", "/media/fav", SourceSettings::default());
+        source.source_id = "123".to_string();
+        source.settings.blacklist_keywords = Some(vec!["预告".to_string()]);
+        let bundle = SourceBundle {
+            version: BUNDLE_VERSION,
+            app_version: "test".to_string(),
+            exported_at: String::new(),
+            sources: vec![source],
+            config: BTreeMap::from([("interval".to_string(), json!(1200))]),
+        };
+
+        for format in [BundleFormat::Json, BundleFormat::Yaml] {
+            let parsed = SourceBundle::parse(&bundle.to_text(format).unwrap()).unwrap();
+            assert_eq!(parsed.sources.len(), 1);
+            assert_eq!(parsed.sources[0].source_id, "123");
+            assert_eq!(
+                parsed.sources[0].settings.blacklist_keywords,
+                Some(vec!["预告".to_string()])
+            );
+            assert_eq!(parsed.config["interval"], json!(1200));
+        }
+
+        let newer = SourceBundle {
+            version: BUNDLE_VERSION + 1,
+            ..bundle
+        };
+        assert!(SourceBundle::parse(&newer.to_text(BundleFormat::Json).unwrap()).is_err());
+    }
+
+    #[test]
+    fn missing_settings_fall_back_to_add_defaults() {
+        let parsed = SourceBundle::parse(
+            "version: 1\nsources:\n  - source_type: watch_later\n    name: 稍后再看\n    path: /media/wl\n",
+        )
+        .unwrap();
+        let settings = &parsed.sources[0].settings;
+        assert!(settings.enabled);
+        assert!(settings.download_danmaku);
+        assert!(settings.keyword_case_sensitive);
+        assert_eq!(settings.ai_subtitle_language, DEFAULT_AI_SUBTITLE_LANGUAGE);
+    }
+
+    #[test]
+    fn diff_reports_changed_settings_only() {
+        let current = bare_source("submission", "UP", "/a", SourceSettings::default());
+        let mut incoming = current.clone();
+        incoming.settings.audio_only = true;
+        incoming.settings.min_duration_seconds = Some(60);
+
+        let fields: Vec<String> = diff_sources(&current, &incoming)
+            .into_iter()
+            .map(|change| change.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "settings.min_duration_seconds".to_string(),
+                "settings.audio_only".to_string()
+            ]
+        );
+    }
+}
@@ -13,7 +13,7 @@ use futures::stream::FuturesUnordered;
 use futures::{Stream, StreamExt, TryStreamExt};
 use sea_orm::entity::prelude::*;
 use sea_orm::ActiveValue::Set;
-use sea_orm::{DatabaseBackend, JoinType, QuerySelect, Statement};
+use sea_orm::{JoinType, QuerySelect};
 use tokio::fs;
 use tokio::sync::{Mutex as TokioMutex, Semaphore};
 use tokio::time::{sleep, Duration};
@@ -241,11 +241,7 @@ async fn update_submission_membership_state_batch(
                 upper_id,
                 chunk.len()
             ),
-            async move {
-                connection
-                    .execute(Statement::from_sql_and_values(DatabaseBackend::Sqlite, sql, values))
-                    .await
-            },
+            async move { connection.execute(crate::database::statement(sql, values)).await },
         )
         .await?;
     }
@@ -525,6 +521,7 @@ fn download_risk_control_resume_source_key(video_source: &VideoSourceEnum) -> St
         VideoSourceEnum::Submission(source) => format!("submission:{}", source.id),
         VideoSourceEnum::WatchLater(source) => format!("watch_later:{}", source.id),
         VideoSourceEnum::BangumiSource(source) => format!("bangumi:{}", source.id),
+        VideoSourceEnum::Ranking(source) => format!("ranking:{}", source.id),
     }
 }
 
@@ -1153,7 +1150,7 @@ pub struct EpisodeInfo {
     pub duration: u32, // 秒
 }
 
-fn page_info_from_page_model(page_model: &page::Model) -> PageInfo {
+pub(crate) fn page_info_from_page_model(page_model: &page::Model) -> PageInfo {
     let dimension = match (page_model.width, page_model.height) {
         (Some(width), Some(height)) => Some(Dimension {
             width,
@@ -1581,6 +1578,9 @@ pub async fn refresh_video_source<'a>(
                 VideoInfo::WatchLater { title, bvid, upper, .. } => {
                     (title.clone(), bvid.clone(), upper.name.clone(), None, None)
                 }
+                VideoInfo::Ranking { title, bvid, upper, .. } => {
+                    (title.clone(), bvid.clone(), upper.name.clone(), None, None)
+                }
                 VideoInfo::Submission { title, bvid, .. } => {
                     // Submission 没有 upper 信息，使用默认值
                     (title.clone(), bvid.clone(), "未知".to_string(), None, None)
@@ -1614,6 +1614,7 @@ pub async fn refresh_video_source<'a>(
                 VideoInfo::Favorite { bvid, .. } => bvid.clone(),
                 VideoInfo::Collection { bvid, .. } => bvid.clone(),
                 VideoInfo::WatchLater { bvid, .. } => bvid.clone(),
+                VideoInfo::Ranking { bvid, .. } => bvid.clone(),
                 VideoInfo::Submission { bvid, .. } => bvid.clone(),
                 VideoInfo::Dynamic { bvid, .. } => bvid.clone(),
                 VideoInfo::Bangumi { bvid, .. } => bvid.clone(),
@@ -2590,10 +2591,8 @@ pub async fn download_unprocessed_videos(
     }
     video_source.log_download_video_start();
     let current_config = crate::config::reload_config();
-    let source_filter_option = video_source
-        .filter_option()
-        .map(|value| serde_json::from_value::<FilterOption>(value.clone()))
-        .transpose()
+    let effective_filter_option = current_config
+        .resolve_source_filter_option(video_source.filter_option())
         .with_context(|| {
             format!(
                 "解析{}「{}」自定义流过滤设置失败",
@@ -2601,7 +2600,6 @@ pub async fn download_unprocessed_videos(
                 video_source.source_name_display()
             )
         })?;
-    let effective_filter_option = source_filter_option.unwrap_or_else(|| current_config.filter_option.clone());
     let mut unhandled_videos_pages = filter_unhandled_video_pages(video_source.filter_expr(), connection).await?;
     let original_unhandled_video_count = unhandled_videos_pages.len();
     let original_unhandled_page_count = unhandled_videos_pages
@@ -2931,6 +2929,7 @@ pub async fn batch_ai_rename_for_source(video_source: &VideoSourceEnum, connecti
         VideoSourceEnum::Submission(_) => "投稿",
         VideoSourceEnum::WatchLater(_) => "稍后再看",
         VideoSourceEnum::BangumiSource(_) => "番剧",
+        VideoSourceEnum::Ranking(_) => "榜单",
     };
 
     let mut renamed_count = 0;
@@ -3378,10 +3377,8 @@ pub async fn retry_failed_videos_once(
         return Ok(());
     }
     let current_config = crate::config::reload_config();
-    let source_filter_option = video_source
-        .filter_option()
-        .map(|value| serde_json::from_value::<FilterOption>(value.clone()))
-        .transpose()
+    let effective_filter_option = current_config
+        .resolve_source_filter_option(video_source.filter_option())
         .with_context(|| {
             format!(
                 "解析{}「{}」自定义流过滤设置失败",
@@ -3389,7 +3386,6 @@ pub async fn retry_failed_videos_once(
                 video_source.source_name_display()
             )
         })?;
-    let effective_filter_option = source_filter_option.unwrap_or_else(|| current_config.filter_option.clone());
     let mut failed_videos_pages = get_failed_videos_in_current_cycle(video_source.filter_expr(), connection).await?;
 
     if failed_videos_pages.is_empty() {
@@ -3619,6 +3615,11 @@ async fn download_video_pages(
         _ = token.cancelled() => return Err(anyhow!("Download cancelled")),
         permit = semaphore.acquire() => permit.context("acquire semaphore failed")?,
     };
+    crate::events::publish_video(
+        crate::events::EventKind::DownloadStarted,
+        &video_model,
+        serde_json::json!({}),
+    );
     let mut status = VideoStatus::from(video_model.download_status);
     let separate_status = status.should_run();
     let should_run_video_nfo = video_status_should_run_nfo(&separate_status);
@@ -3897,6 +3898,7 @@ async fn download_video_pages(
                 danmaku_cid_snapshot: None,
                 danmaku_last_write_count: 0,
                 ai_renamed: None,
+                quality_checked_at: None,
             };
 
             // 获取真实的番剧标题（从缓存或API）
@@ -6103,6 +6105,18 @@ async fn download_video_pages(
         } else {
             IngestStatus::Failed
         };
+        let event_kind = match ingest_status {
+            IngestStatus::Success => Some(crate::events::EventKind::DownloadFinished),
+            IngestStatus::Failed => Some(crate::events::EventKind::DownloadFailed),
+            IngestStatus::Deleted => None,
+        };
+        if let Some(event_kind) = event_kind {
+            crate::events::publish_video(
+                event_kind,
+                &video_model,
+                serde_json::json!({ "path": path_to_save, "status": bits }),
+            );
+        }
         crate::ingest_log::INGEST_LOG
             .finish_video(
                 ingest_video_id,
@@ -7560,6 +7574,42 @@ async fn download_page(
         }
     }
 
+    // UP 主没有设置章节时，按弹幕高能时刻生成章节；已按章节切分的视频不再处理
+    if let Some(sync_update) = danmaku_sync_update.as_ref() {
+        if !split_chapters && inaccessible_reason.is_none() && !skip_charge_video_media_download {
+            if let Err(err) = crate::workflow_highlight::write_highlight_chapters(
+                connection,
+                bili_client,
+                &danmaku_config,
+                video_model,
+                &page_model,
+                &page_info,
+                &sync_update.histogram,
+                &video_path,
+                token.clone(),
+            )
+            .await
+            {
+                warn!(
+                    "写入弹幕高能章节失败，不影响下载结果: 视频「{}」第{}页: {:#}",
+                    &video_model.name, page_model.pid, err
+                );
+            }
+        }
+    }
+
+    // 没有任何字幕的分页交给本地语音识别队列，在后台生成字幕
+    if !split_chapters && inaccessible_reason.is_none() && !skip_charge_video_media_download {
+        if let Err(err) =
+            crate::workflow_subtitle_generation::enqueue_if_missing(connection, page_model.id, &video_path).await
+        {
+            warn!(
+                "加入语音识别字幕队列失败，不影响下载结果: 视频「{}」第{}页: {:#}",
+                &video_model.name, page_model.pid, err
+            );
+        }
+    }
+
     // AI 重命名已移至视频源下载完成后批量执行（batch_ai_rename_for_source）
     // 此处仅保存原始文件路径，批量重命名时会更新
     let final_video_path = chapter_primary_path.unwrap_or_else(|| video_path.clone());
@@ -7702,7 +7752,7 @@ async fn download_stream(downloader: &UnifiedDownloader, video_id: i32, urls: &[
     }
 }
 
-fn get_cached_video_quality_description(quality: crate::bilibili::VideoQuality) -> &'static str {
+pub(crate) fn get_cached_video_quality_description(quality: crate::bilibili::VideoQuality) -> &'static str {
     use crate::bilibili::VideoQuality;
     match quality {
         VideoQuality::Quality360p => "360P",
@@ -7718,7 +7768,7 @@ fn get_cached_video_quality_description(quality: crate::bilibili::VideoQuality)
     }
 }
 
-fn get_cached_audio_quality_description(quality: crate::bilibili::AudioQuality) -> &'static str {
+pub(crate) fn get_cached_audio_quality_description(quality: crate::bilibili::AudioQuality) -> &'static str {
     use crate::bilibili::AudioQuality;
     match quality {
         AudioQuality::Quality64k => "64K",
@@ -7729,7 +7779,7 @@ fn get_cached_audio_quality_description(quality: crate::bilibili::AudioQuality)
     }
 }
 
-fn get_cached_video_codecs_description(codecs: crate::bilibili::VideoCodecs) -> &'static str {
+pub(crate) fn get_cached_video_codecs_description(codecs: crate::bilibili::VideoCodecs) -> &'static str {
     use crate::bilibili::VideoCodecs;
     match codecs {
         VideoCodecs::AVC => "AVC/H.264",
@@ -7738,11 +7788,9 @@ fn get_cached_video_codecs_description(codecs: crate::bilibili::VideoCodecs) ->
     }
 }
 
-async fn save_download_play_stream_cache(
-    connection: &DatabaseConnection,
-    page_id: i32,
+pub(crate) fn build_download_play_stream_cache(
     best_stream: &BestStream,
-) -> Result<()> {
+) -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
     let mut video_streams = Vec::new();
     let mut audio_streams = Vec::new();
 
@@ -7801,6 +7849,15 @@ async fn save_download_play_stream_cache(
         }
     }
 
+    (video_streams, audio_streams)
+}
+
+pub(crate) async fn save_download_play_stream_cache(
+    connection: &DatabaseConnection,
+    page_id: i32,
+    video_streams: &[serde_json::Value],
+    audio_streams: &[serde_json::Value],
+) -> Result<()> {
     if video_streams.is_empty() {
         return Ok(());
     }
@@ -7995,11 +8052,11 @@ async fn create_charge_video_placeholder(
     Ok(ExecutionStatus::Succeeded)
 }
 
-struct PageVideoFetchResult {
-    status: ExecutionStatus,
-    file_size_bytes: Option<i64>,
-    video_stream_size_bytes: Option<i64>,
-    audio_stream_size_bytes: Option<i64>,
+pub(crate) struct PageVideoFetchResult {
+    pub(crate) status: ExecutionStatus,
+    pub(crate) file_size_bytes: Option<i64>,
+    pub(crate) video_stream_size_bytes: Option<i64>,
+    pub(crate) audio_stream_size_bytes: Option<i64>,
 }
 
 fn to_db_file_size(size: u64) -> i64 {
@@ -8288,7 +8345,64 @@ async fn maybe_embed_cover_into_chapter_audio_outputs(
     }
 }
 
-async fn download_page_video_from_streams(
+/// 跨视频源去重：同一分页已在其它视频源以相同的流下载成功时，直接链接已有文件而不是重新下载。
+/// 返回 None 表示没有可复用的副本（或链接失败），调用方继续正常下载。
+async fn reuse_downloaded_page_copy(
+    connection: &DatabaseConnection,
+    page_id: i32,
+    video_model: &video::Model,
+    page_info: &PageInfo,
+    page_path: &Path,
+    cached_video_streams: &[serde_json::Value],
+    cached_audio_streams: &[serde_json::Value],
+) -> Option<PageVideoFetchResult> {
+    if !crate::config::reload_config().cross_source_dedup {
+        return None;
+    }
+    let signature = crate::utils::dedup::stream_cache_signature(cached_video_streams, cached_audio_streams)?;
+    let candidate =
+        match crate::utils::dedup::find_reusable_page_copy(connection, page_id, page_info.cid, &signature, page_path)
+            .await
+        {
+            Ok(candidate) => candidate?,
+            Err(e) => {
+                debug!(
+                    "查找跨源可复用分页失败，继续正常下载: page_id={}, error={:#}",
+                    page_id, e
+                );
+                return None;
+            }
+        };
+    let source_path = PathBuf::from(candidate.path.as_deref()?);
+
+    match crate::utils::dedup::link_existing_file(&source_path, page_path).await {
+        Ok(kind) => {
+            info!(
+                "跨源去重：视频「{}」第{}页复用已下载文件（{}）: {} -> {}",
+                &video_model.name,
+                page_info.page,
+                kind.display_name(),
+                source_path.display(),
+                page_path.display()
+            );
+            Some(PageVideoFetchResult {
+                status: ExecutionStatus::Succeeded,
+                file_size_bytes: candidate.file_size_bytes,
+                video_stream_size_bytes: candidate.video_stream_size_bytes,
+                audio_stream_size_bytes: candidate.audio_stream_size_bytes,
+            })
+        }
+        Err(e) => {
+            warn!(
+                "跨源去重链接失败，回退为正常下载: 视频「{}」第{}页: {:#}",
+                &video_model.name, page_info.page, e
+            );
+            None
+        }
+    }
+}
+
+pub(crate) async fn download_page_video_from_streams(
     streams: &mut PageAnalyzer,
     connection: &DatabaseConnection,
     page_id: i32,
@@ -8360,10 +8474,27 @@ async fn download_page_video_from_streams(
 
     // 根据流类型进行不同处理
     let best_stream_result = streams.best_stream(filter_option)?;
-    if let Err(e) = save_download_play_stream_cache(connection, page_id, &best_stream_result).await {
+    let (cached_video_streams, cached_audio_streams) = build_download_play_stream_cache(&best_stream_result);
+    if let Err(e) =
+        save_download_play_stream_cache(connection, page_id, &cached_video_streams, &cached_audio_streams).await
+    {
         debug!("写入下载播放缓存失败（不影响下载）: page_id={}, error={}", page_id, e);
     }
 
+    if let Some(result) = reuse_downloaded_page_copy(
+        connection,
+        page_id,
+        video_model,
+        page_info_for_download,
+        page_path,
+        &cached_video_streams,
+        &cached_audio_streams,
+    )
+    .await
+    {
+        return Ok(result);
+    }
+
     // 添加流选择结果日志和质量分析
     debug!("=== 流选择结果 ===");
     match &best_stream_result {
@@ -9937,7 +10068,7 @@ pub async fn fetch_page_danmaku(
     bili_client: &BiliClient,
     video_model: &video::Model,
     page_model: &page::Model,
-    _connection: &DatabaseConnection,
+    connection: &DatabaseConnection,
     config: &crate::config::Config,
     page_info: &PageInfo,
     danmaku_path: PathBuf,
@@ -10019,6 +10150,20 @@ pub async fn fetch_page_danmaku(
             }
         },
     };
+    // 直方图只用于统计与高能章节，保存失败不影响弹幕下载结果
+    if let Err(e) = crate::workflow_highlight::save_danmaku_histogram(
+        connection,
+        page_model.id,
+        video_model.id,
+        &sync_update.histogram,
+    )
+    .await
+    {
+        warn!(
+            "保存视频「{}」第 {} 页的弹幕直方图失败: {:#}",
+            &video_model.name, page_info.page, e
+        );
+    }
     Ok(PageDanmakuFetchResult {
         status: ExecutionStatus::Succeeded,
         sync_update: Some(sync_update),
@@ -10086,7 +10231,7 @@ pub async fn generate_page_nfo(
     video_model: &video::Model,
     page_model: &page::Model,
     nfo_path: PathBuf,
-    _connection: &DatabaseConnection,
+    connection: &DatabaseConnection,
     season_number_override: Option<i32>,
     episode_number_override: Option<i32>,
 ) -> Result<ExecutionStatus> {
@@ -10117,7 +10262,7 @@ pub async fn generate_page_nfo(
                     {
                         if let Some(col_id) = video_model.collection_id {
                             if let Ok(ep_no) =
-                                get_collection_video_episode_number(_connection, col_id, &video_model.bvid).await
+                                get_collection_video_episode_number(connection, col_id, &video_model.bvid).await
                             {
                                 episode.episode_number = ep_no;
                             }
@@ -10132,8 +10277,7 @@ pub async fn generate_page_nfo(
                         && video_model.episode_number.is_none()
                         && episode_number_override.is_none()
                     {
-                        if let Ok(ep_no) =
-                            get_submission_collection_video_episode_number(_connection, video_model).await
+                        if let Ok(ep_no) = get_submission_collection_video_episode_number(connection, video_model).await
                         {
                             episode.episode_number = ep_no;
                         }
@@ -10170,7 +10314,7 @@ pub async fn generate_page_nfo(
                 if let Some(col_id) = video_model.collection_id {
                     if video_model.episode_number.is_none() {
                         if let Ok(ep_no) =
-                            get_collection_video_episode_number(_connection, col_id, &video_model.bvid).await
+                            get_collection_video_episode_number(connection, col_id, &video_model.bvid).await
                         {
                             episode.episode_number = ep_no;
                         }
@@ -10180,7 +10324,11 @@ pub async fn generate_page_nfo(
             NFO::Episode(episode)
         }
     };
-    generate_nfo(nfo, nfo_path).await?;
+    generate_nfo(nfo, nfo_path.clone()).await?;
+    // 视频已有 AI 摘要时重新写入，避免刷新 NFO 时丢失
+    if let Err(e) = crate::workflow_video_summary::reapply_video_summary(connection, video_model.id, &nfo_path).await {
+        warn!("写入 NFO AI 摘要失败: {:#}", e);
+    }
     Ok(ExecutionStatus::Succeeded)
 }
 
@@ -11771,8 +11919,7 @@ async fn get_submission_collection_video_episode_number(
     let pubtime_text = video_model.pubtime.format("%Y-%m-%d %H:%M:%S").to_string();
 
     let row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*)
             FROM video
@@ -11814,8 +11961,7 @@ async fn get_submission_multipage_episode_number(
     let pubtime_text = video_model.pubtime.format("%Y-%m-%d %H:%M:%S").to_string();
 
     let row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*)
             FROM video
@@ -11849,8 +11995,7 @@ async fn get_page_index_within_video(connection: &DatabaseConnection, page_model
     use sea_orm::*;
 
     let row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*)
             FROM page
@@ -11896,8 +12041,7 @@ async fn get_collection_page_episode_number(
     let page_index = get_page_index_within_video(connection, page_model).await?;
 
     let row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(p.id)
             FROM video v
@@ -11946,8 +12090,7 @@ async fn get_submission_page_episode_number(
         let pubtime_text = video_model.pubtime.format("%Y-%m-%d %H:%M:%S").to_string();
 
         let prev_pages_row = connection
-            .query_one(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_one(crate::database::statement(
                 r#"
                 SELECT COUNT(p.id)
                 FROM video v
@@ -12024,8 +12167,7 @@ async fn get_submission_single_collection_like_season_number(
     let mapping_collection_id = derive_collection_mapping_key(&mapping_key);
 
     let mapping_row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id, up_mid
             FROM collection_season_mapping
@@ -12047,8 +12189,7 @@ async fn get_submission_single_collection_like_season_number(
             mapped_season_id.unwrap()
         } else {
             let max_row = connection
-                .query_one(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .query_one(crate::database::statement(
                     r#"
                     SELECT COALESCE(MAX(season_id), 0)
                     FROM collection_season_mapping
@@ -12066,8 +12207,7 @@ async fn get_submission_single_collection_like_season_number(
         }
     } else {
         let max_row = connection
-            .query_one(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_one(crate::database::statement(
                 r#"
                 SELECT COALESCE(MAX(season_id), 0)
                 FROM collection_season_mapping
@@ -12086,9 +12226,7 @@ async fn get_submission_single_collection_like_season_number(
 
     let reference_pubtime_text = reference_pubtime.format("%Y-%m-%d %H:%M:%S").to_string();
     connection
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            r#"
+        .execute(crate::database::statement(r#"
             INSERT INTO collection_season_mapping (
                 collection_id, up_mid, base_path, pub_year, pub_quarter, season_id, reference_pubtime, created_at, updated_at
             )
@@ -12146,8 +12284,7 @@ async fn get_submission_multipage_season_number(
 
     // 1) 已有映射（同UP）则直接复用，保证“一个多P一个季”
     let mapping_row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id, up_mid
             FROM collection_season_mapping
@@ -12169,8 +12306,7 @@ async fn get_submission_multipage_season_number(
             mapped_season_id.unwrap()
         } else {
             let max_row = connection
-                .query_one(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .query_one(crate::database::statement(
                     r#"
                     SELECT COALESCE(MAX(season_id), 0)
                     FROM collection_season_mapping
@@ -12189,8 +12325,7 @@ async fn get_submission_multipage_season_number(
     } else {
         // 2) 未命中已有映射时，分配下一个季号（不再按季度复用）
         let max_row = connection
-            .query_one(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_one(crate::database::statement(
                 r#"
                 SELECT COALESCE(MAX(season_id), 0)
                 FROM collection_season_mapping
@@ -12211,9 +12346,7 @@ async fn get_submission_multipage_season_number(
 
     // 4) 写回映射，保持稳定
     connection
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            r#"
+        .execute(crate::database::statement(r#"
             INSERT INTO collection_season_mapping (
                 collection_id, up_mid, base_path, pub_year, pub_quarter, season_id, reference_pubtime, created_at, updated_at
             )
@@ -12366,9 +12499,7 @@ async fn preallocate_submission_up_seasonal_mappings(
             placeholders
         );
         let values = chunk.iter().map(|id| (*id).into()).collect::<Vec<_>>();
-        let rows = connection
-            .query_all(Statement::from_sql_and_values(DatabaseBackend::Sqlite, sql, values))
-            .await?;
+        let rows = connection.query_all(crate::database::statement(sql, values)).await?;
         for row in rows {
             let collection_id = row
                 .try_get_by_index::<i64>(0)
@@ -12400,8 +12531,7 @@ async fn preallocate_submission_up_seasonal_mappings(
     let mut repaired = 0usize;
     let mut created = 0usize;
     let max_existing_row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COALESCE(MAX(season_id), 0)
             FROM collection_season_mapping
@@ -12444,9 +12574,7 @@ async fn preallocate_submission_up_seasonal_mappings(
         let reference_pubtime_text = earliest_pubtime.format("%Y-%m-%d %H:%M:%S").to_string();
 
         connection
-            .execute(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
-                r#"
+            .execute(crate::database::statement(r#"
                 INSERT INTO collection_season_mapping (
                     collection_id, up_mid, base_path, pub_year, pub_quarter, season_id, reference_pubtime, created_at, updated_at
                 )
@@ -12490,8 +12618,7 @@ async fn cleanup_stale_submission_season_mappings(
     use sea_orm::*;
 
     let existing_rows = connection
-        .query_all(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_all(crate::database::statement(
             r#"
             SELECT collection_id
             FROM collection_season_mapping
@@ -12547,11 +12674,7 @@ async fn cleanup_stale_submission_season_mappings(
                 base_path,
                 chunk.len()
             ),
-            async move {
-                connection
-                    .execute(Statement::from_sql_and_values(DatabaseBackend::Sqlite, sql, values))
-                    .await
-            },
+            async move { connection.execute(crate::database::statement(sql, values)).await },
         )
         .await?;
         removed += result.rows_affected() as usize;
@@ -12726,9 +12849,7 @@ struct CollectionNfoStats {
 async fn query_count_i32(connection: &DatabaseConnection, sql: &str, params: Vec<sea_orm::Value>) -> Result<i32> {
     use sea_orm::*;
 
-    let row = connection
-        .query_one(Statement::from_sql_and_values(DatabaseBackend::Sqlite, sql, params))
-        .await?;
+    let row = connection.query_one(crate::database::statement(sql, params)).await?;
     let value = row.and_then(|r| r.try_get_by_index::<i64>(0).ok()).unwrap_or(0);
     Ok(i32::try_from(value).unwrap_or(0))
 }
@@ -13012,8 +13133,7 @@ async fn get_submission_up_seasonal_nfo_stats(
     .await?;
 
     let mapping_rows = connection
-        .query_all(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_all(crate::database::statement(
             r#"
             SELECT collection_id, season_id
             FROM collection_season_mapping
@@ -13041,8 +13161,7 @@ async fn get_submission_up_seasonal_nfo_stats(
     }
 
     let rows = connection
-        .query_all(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_all(crate::database::statement(
             r#"
             SELECT v.id, v.season_id, v.single_page, v.pubtime, COUNT(p.id), v.bvid, v.name
             FROM video v
@@ -13172,8 +13291,7 @@ async fn backfill_submission_collection_membership(
 
     for (bvid, (collection_key, episode_number)) in membership {
         let result = txn
-            .execute(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .execute(crate::database::statement(
                 r#"
                 UPDATE video
                 SET season_id = ?, episode_number = COALESCE(episode_number, ?)
@@ -13227,8 +13345,7 @@ async fn get_submission_source_season_number(
         .to_string_lossy()
         .to_string();
     let reference_pubtime = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT MIN(pubtime)
             FROM video
@@ -13247,8 +13364,7 @@ async fn get_submission_source_season_number(
 
     // 1) 先检查该UGC合集ID是否已有映射（同UP则直接复用，保证“一个合集/系列一个季”）
     let mapping_row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id, up_mid
             FROM collection_season_mapping
@@ -13270,8 +13386,7 @@ async fn get_submission_source_season_number(
             mapped_season_id.unwrap()
         } else {
             let max_row = connection
-                .query_one(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .query_one(crate::database::statement(
                     r#"
                     SELECT COALESCE(MAX(season_id), 0)
                     FROM collection_season_mapping
@@ -13290,8 +13405,7 @@ async fn get_submission_source_season_number(
     } else {
         // 2) 未命中已有映射时，分配下一个季号（不再按季度复用）
         let max_row = connection
-            .query_one(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_one(crate::database::statement(
                 r#"
                 SELECT COALESCE(MAX(season_id), 0)
                 FROM collection_season_mapping
@@ -13312,9 +13426,7 @@ async fn get_submission_source_season_number(
 
     // 4) 写回映射，保证后续稳定
     connection
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            r#"
+        .execute(crate::database::statement(r#"
             INSERT INTO collection_season_mapping (
                 collection_id, up_mid, base_path, pub_year, pub_quarter, season_id, reference_pubtime, created_at, updated_at
             )
@@ -13341,8 +13453,7 @@ async fn get_submission_source_season_number(
         .await?;
 
     let final_season_number = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id
             FROM collection_season_mapping
@@ -13442,8 +13553,7 @@ async fn get_collection_source_season_number(
 
     // 1) 先尝试读取当前合集已有映射（同UP则直接复用，保证“一个合集一个季”）
     let mapping_row = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id, up_mid
             FROM collection_season_mapping
@@ -13465,8 +13575,7 @@ async fn get_collection_source_season_number(
             mapped_season_id.unwrap()
         } else {
             let max_row = connection
-                .query_one(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .query_one(crate::database::statement(
                     r#"
                     SELECT COALESCE(MAX(season_id), 0)
                     FROM collection_season_mapping
@@ -13485,8 +13594,7 @@ async fn get_collection_source_season_number(
     } else {
         // 2) 未命中已有映射时，分配下一个season_id（不再按季度复用）
         let max_row = connection
-            .query_one(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_one(crate::database::statement(
                 r#"
                 SELECT COALESCE(MAX(season_id), 0)
                 FROM collection_season_mapping
@@ -13507,9 +13615,7 @@ async fn get_collection_source_season_number(
 
     // 4) 持久化映射（按collection_id幂等更新）
     connection
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            r#"
+        .execute(crate::database::statement(r#"
             INSERT INTO collection_season_mapping (
                 collection_id, up_mid, base_path, pub_year, pub_quarter, season_id, reference_pubtime, created_at, updated_at
             )
@@ -13536,8 +13642,7 @@ async fn get_collection_source_season_number(
         .await?;
 
     let final_season_id = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT season_id
             FROM collection_season_mapping
@@ -13646,8 +13751,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
     // 1. 首先处理cid不匹配的记录 - 这些应该删除
     let cid_mismatch_count: i64 = txn
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*) as count 
             FROM page p 
@@ -13673,8 +13777,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
         // 先收集这些记录对应的video_id（用于后续设置auto_download=0）
         let mismatch_videos = txn
-            .query_all(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_all(crate::database::statement(
                 r#"
                 SELECT DISTINCT p.video_id
                 FROM page p 
@@ -13695,8 +13798,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
         // 删除cid不匹配的page记录
         let delete_mismatch_result = txn
-            .execute(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .execute(crate::database::statement(
                 r#"
                 DELETE FROM page 
                 WHERE id IN (
@@ -13720,8 +13822,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
     // 2. 然后统计有多少page记录需要修复video_id
     let wrong_pages_count: i64 = txn
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*) as count 
             FROM page p 
@@ -13750,13 +13851,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
         WHERE video_id NOT IN (SELECT id FROM video)
     "#;
 
-    let phase1_result = txn
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            set_temp_id_sql,
-            vec![],
-        ))
-        .await?;
+    let phase1_result = txn.execute(crate::database::statement(set_temp_id_sql, vec![])).await?;
 
     info!("已将 {} 条记录设置为临时video_id", phase1_result.rows_affected());
 
@@ -13786,19 +13881,14 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
     "#;
 
     let no_conflict_result = txn
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            fix_no_conflict_sql,
-            vec![],
-        ))
+        .execute(crate::database::statement(fix_no_conflict_sql, vec![]))
         .await?;
 
     info!("修复了 {} 条不冲突的单P视频记录", no_conflict_result.rows_affected());
 
     // 处理会冲突的记录 - 这些需要特殊处理
     let conflicting_pages = txn
-        .query_all(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_all(crate::database::statement(
             r#"
             SELECT p1.id, p1.cid, v.id as correct_video_id, p2.id as existing_id, p2.cid as existing_cid
             FROM page p1
@@ -13824,8 +13914,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             // 只有当两个page的cid相同时，才是真正的重复记录
             if page_cid == existing_cid {
                 // 真正的重复，删除临时ID的那条
-                txn.execute(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                txn.execute(crate::database::statement(
                     r#"DELETE FROM page WHERE id = ?"#,
                     vec![page_id.into()],
                 ))
@@ -13874,20 +13963,13 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
         )
     "#;
 
-    let multi_p_result = txn
-        .execute(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
-            fix_multi_p_sql,
-            vec![],
-        ))
-        .await?;
+    let multi_p_result = txn.execute(crate::database::statement(fix_multi_p_sql, vec![])).await?;
 
     info!("修复了 {} 条多P视频的page记录", multi_p_result.rows_affected());
 
     // 5. 处理无法修复的记录（video_id仍为负数的）
     let orphan_count: i64 = txn
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*) as count 
             FROM page 
@@ -13907,8 +13989,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
         // 删除无法修复的孤立记录
         let delete_result = txn
-            .execute(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .execute(crate::database::statement(
                 r#"DELETE FROM page WHERE video_id < 0"#,
                 vec![],
             ))
@@ -13924,8 +14005,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
         // 收集所有已修复的video_id（这些不应该被标记为已删除）
         let fixed_videos = txn
-            .query_all(Statement::from_sql_and_values(
-                DatabaseBackend::Sqlite,
+            .query_all(crate::database::statement(
                 r#"
                 SELECT DISTINCT video_id 
                 FROM page 
@@ -13951,8 +14031,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             }
 
             let update_result = txn
-                .execute(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .execute(crate::database::statement(
                     r#"UPDATE video SET deleted = 1 WHERE id = ?"#,
                     vec![(*video_id).into()],
                 ))
@@ -13971,14 +14050,14 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
             // 查询刚刚被标记为已删除的视频的源信息
             let deleted_videos_sources = txn
-                .query_all(Statement::from_sql_and_values(
-                    DatabaseBackend::Sqlite,
+                .query_all(crate::database::statement(
                     r#"
                     SELECT DISTINCT 
                         submission_id,
                         collection_id,
                         favorite_id,
                         watch_later_id,
+                        ranking_id,
                         source_id,
                         source_type
                     FROM video 
@@ -13994,6 +14073,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             let mut collection_ids = std::collections::HashSet::new();
             let mut favorite_ids = std::collections::HashSet::new();
             let mut watch_later_ids = std::collections::HashSet::new();
+            let mut ranking_ids = std::collections::HashSet::new();
             let mut bangumi_source_ids = std::collections::HashSet::new();
 
             for row in deleted_videos_sources {
@@ -14009,6 +14089,9 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
                 if let Ok(Some(id)) = row.try_get::<Option<i32>>("", "watch_later_id") {
                     watch_later_ids.insert(id);
                 }
+                if let Ok(Some(id)) = row.try_get::<Option<i32>>("", "ranking_id") {
+                    ranking_ids.insert(id);
+                }
                 // 番剧通过source_id和source_type=1判断
                 if let (Ok(Some(source_id)), Ok(Some(source_type))) = (
                     row.try_get::<Option<i32>>("", "source_id"),
@@ -14027,8 +14110,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             if !submission_ids.is_empty() {
                 let placeholders = submission_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
                 let result = txn
-                    .execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
+                    .execute(crate::database::statement(
                         format!(
                             "UPDATE submission SET scan_deleted_videos_once = 1
                              WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
@@ -14046,8 +14128,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             if !collection_ids.is_empty() {
                 let placeholders = collection_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
                 let result = txn
-                    .execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
+                    .execute(crate::database::statement(
                         format!(
                             "UPDATE collection SET scan_deleted_videos_once = 1
                              WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
@@ -14065,8 +14146,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             if !favorite_ids.is_empty() {
                 let placeholders = favorite_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
                 let result = txn
-                    .execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
+                    .execute(crate::database::statement(
                         format!(
                             "UPDATE favorite SET scan_deleted_videos_once = 1
                              WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
@@ -14084,8 +14164,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
             if !watch_later_ids.is_empty() {
                 let placeholders = watch_later_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
                 let result = txn
-                    .execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
+                    .execute(crate::database::statement(
                         format!(
                             "UPDATE watch_later SET scan_deleted_videos_once = 1
                              WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
@@ -14099,12 +14178,29 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
                 }
             }
 
+            // 榜单
+            if !ranking_ids.is_empty() {
+                let placeholders = ranking_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+                let result = txn
+                    .execute(crate::database::statement(
+                        format!(
+                            "UPDATE ranking SET scan_deleted_videos_once = 1
+                             WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
+                            placeholders
+                        ),
+                        ranking_ids.iter().map(|id| (*id).into()).collect::<Vec<_>>(),
+                    ))
+                    .await?;
+                if result.rows_affected() > 0 {
+                    enabled_sources.push(format!("{}个榜单", result.rows_affected()));
+                }
+            }
+
             // 番剧
             if !bangumi_source_ids.is_empty() {
                 let placeholders = bangumi_source_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
                 let result = txn
-                    .execute(Statement::from_sql_and_values(
-                        DatabaseBackend::Sqlite,
+                    .execute(crate::database::statement(
                         format!(
                             "UPDATE video_source SET scan_deleted_videos_once = 1
                              WHERE id IN ({}) AND scan_deleted_videos = 0 AND scan_deleted_videos_once = 0",
@@ -14133,8 +14229,7 @@ pub async fn fix_page_video_ids(connection: &DatabaseConnection) -> Result<()> {
 
     // 8. 最终验证
     let final_check: i64 = connection
-        .query_one(Statement::from_sql_and_values(
-            DatabaseBackend::Sqlite,
+        .query_one(crate::database::statement(
             r#"
             SELECT COUNT(*) as count 
             FROM page p 
@@ -14772,6 +14867,7 @@ mod tests {
             favorite_id: Set(None),
             watch_later_id: Set(None),
             submission_id: Set(Some(submission_id)),
+            ranking_id: Set(None),
             source_id: Set(None),
             source_type: Set(Some(4)),
             upper_id: Set(1000 + i64::from(submission_id)),
@@ -14840,6 +14936,7 @@ mod tests {
             danmaku_cid_snapshot: Set(None),
             danmaku_last_write_count: Set(0),
             ai_renamed: Set(Some(0)),
+            quality_checked_at: Set(None),
         }
         .insert(db)
         .await
@@ -15180,8 +15277,7 @@ mod tests {
 
         Migrator::up(&db, None).await.expect("应能完成测试数据库迁移");
         if let Err(err) = db
-            .execute(Statement::from_string(
-                DatabaseBackend::Sqlite,
+            .execute(crate::database::raw_statement(
                 "ALTER TABLE page ADD COLUMN ai_renamed INTEGER DEFAULT 0",
             ))
             .await
@@ -15256,6 +15352,7 @@ mod tests {
             scan_deleted_videos: Set(scan_deleted_videos),
             scan_deleted_videos_once: Set(scan_deleted_videos_once),
             filter_option: Set(None),
+            credential_id: Set(None),
             selected_videos: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
@@ -15272,6 +15369,7 @@ mod tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(true),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -15661,6 +15759,7 @@ mod tests {
             favorite_id: None,
             watch_later_id: None,
             submission_id: Some(1),
+            ranking_id: None,
             source_id: None,
             source_type: Some(4),
             upper_id: 1,
@@ -15729,6 +15828,7 @@ mod tests {
             danmaku_cid_snapshot: None,
             danmaku_last_write_count: 0,
             ai_renamed: Some(0),
+            quality_checked_at: None,
         }
     }
 
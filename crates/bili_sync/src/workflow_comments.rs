@@ -0,0 +1,622 @@
+//! 评论区归档工作流。
+//!
+//! 视频源开启 `archive_comments` 后，每轮扫描结束时为已下载的视频拉取置顶评论与热门评论（含回复），
+//! 以 JSON 与 HTML 侧车文件保存在视频旁，并按弹幕增量更新策略的分阶段节奏刷新。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use bili_sync_entity::{
+    collection, comment_archive, favorite, page, ranking, submission, video, video_source, watch_later,
+};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, OnConflict, Query, SimpleExpr};
+use sea_orm::{Condition, QueryOrder, QuerySelect, Set};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::bilibili::{BiliClient, CommentArchive, VideoComments};
+use crate::config::DanmakuUpdatePolicy;
+use crate::utils::danmaku_schedule::{should_sync_danmaku, stage_for_age, Decision, Stage};
+use crate::utils::status::{PageStatus, STATUS_OK};
+use crate::utils::time_format::{
+    beijing_timezone, now_standard_string, parse_time_string, stored_beijing_naive_to_utc, to_standard_string,
+    STANDARD_TIME_FORMAT,
+};
+
+/// 分页状态中“视频文件”子任务的下标
+const PAGE_VIDEO_TASK_INDEX: usize = 1;
+
+/// 每批加载的候选视频数，避免把所有到期视频连同分页一次性读入内存
+const CANDIDATE_BATCH_SIZE: usize = 50;
+
+/// 开启了评论区归档的视频源，值为视频源绑定的B站账号
+#[derive(Debug, Default)]
+struct ArchiveSources {
+    collection: HashMap<i32, Option<i32>>,
+    favorite: HashMap<i32, Option<i32>>,
+    submission: HashMap<i32, Option<i32>>,
+    watch_later: HashMap<i32, Option<i32>>,
+    bangumi: HashMap<i32, Option<i32>>,
+    ranking: HashMap<i32, Option<i32>>,
+}
+
+impl ArchiveSources {
+    async fn load(connection: &DatabaseConnection) -> Result<Self> {
+        Ok(Self {
+            collection: collection::Entity::find()
+                .filter(collection::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+            favorite: favorite::Entity::find()
+                .filter(favorite::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+            submission: submission::Entity::find()
+                .filter(submission::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+            watch_later: watch_later::Entity::find()
+                .filter(watch_later::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+            bangumi: video_source::Entity::find()
+                .filter(video_source::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+            ranking: ranking::Entity::find()
+                .filter(ranking::Column::ArchiveComments.eq(true))
+                .all(connection)
+                .await?
+                .into_iter()
+                .map(|m| (m.id, m.credential_id))
+                .collect(),
+        })
+    }
+
+    fn sources(&self) -> [(video::Column, &HashMap<i32, Option<i32>>); 6] {
+        [
+            (video::Column::CollectionId, &self.collection),
+            (video::Column::FavoriteId, &self.favorite),
+            (video::Column::SubmissionId, &self.submission),
+            (video::Column::WatchLaterId, &self.watch_later),
+            (video::Column::SourceId, &self.bangumi),
+            (video::Column::RankingId, &self.ranking),
+        ]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sources().iter().all(|(_, ids)| ids.is_empty())
+    }
+
+    fn video_filter(&self) -> Condition {
+        self.sources()
+            .into_iter()
+            .filter(|(_, ids)| !ids.is_empty())
+            .fold(Condition::any(), |condition, (column, ids)| {
+                condition.add(column.is_in(ids.keys().copied()))
+            })
+    }
+
+    /// 视频所属视频源绑定的账号，视频源未开启归档时返回 None
+    fn credential_for(&self, video_model: &video::Model) -> Option<Option<i32>> {
+        [
+            (video_model.collection_id, &self.collection),
+            (video_model.favorite_id, &self.favorite),
+            (video_model.submission_id, &self.submission),
+            (video_model.watch_later_id, &self.watch_later),
+            (video_model.source_id, &self.bangumi),
+            (video_model.ranking_id, &self.ranking),
+        ]
+        .into_iter()
+        .find_map(|(id, ids)| ids.get(&id?).copied())
+    }
+}
+
+/// 首次归档总是执行；之后按弹幕增量更新策略判断是否到期，策略关闭时不再刷新
+fn comment_sync_decision(
+    policy: &DanmakuUpdatePolicy,
+    pubtime: DateTime<Utc>,
+    record: Option<&comment_archive::Model>,
+    now: DateTime<Utc>,
+) -> Decision {
+    match record {
+        None => Decision::Sync {
+            next_stage: stage_for_age(policy, pubtime, now, false),
+        },
+        Some(record) => should_sync_danmaku(
+            policy,
+            pubtime,
+            parse_time_string(&record.last_synced_at).map(stored_beijing_naive_to_utc),
+            record.sync_generation,
+            now,
+        ),
+    }
+}
+
+/// 已归档且尚未到期的视频，与 [`comment_sync_decision`] 的判断一致，用于在数据库中预先排除
+fn archived_not_due_condition(policy: &DanmakuUpdatePolicy, now: NaiveDateTime) -> Condition {
+    let dialect = crate::database::dialect::current();
+    // 已归档到不低于 stage 的阶段，且最近一次归档晚于 synced_after
+    let archived = |stage: Option<Stage>, synced_after: Option<NaiveDateTime>| {
+        let mut query = Query::select();
+        query
+            .column(comment_archive::Column::VideoId)
+            .from(comment_archive::Entity);
+        if let Some(stage) = stage {
+            query.and_where(Expr::cust(format!(
+                "{} >= {}",
+                dialect.unsigned_column("sync_generation"),
+                stage.as_generation()
+            )));
+        }
+        if let Some(synced_after) = synced_after {
+            query.and_where(
+                comment_archive::Column::LastSyncedAt.gt(synced_after.format(STANDARD_TIME_FORMAT).to_string()),
+            );
+        }
+        video::Column::Id.in_subquery(query.to_owned())
+    };
+    if !policy.enabled {
+        return Condition::all().add(archived(None, None));
+    }
+    let younger_than = |days: u32| video::Column::Pubtime.gt(now - Duration::days(days as i64));
+    let older_than = |days: u32| video::Column::Pubtime.lte(now - Duration::days(days as i64));
+    Condition::any()
+        .add(archived(Some(Stage::Frozen), None))
+        .add(
+            Condition::all()
+                .add(younger_than(policy.cold_days))
+                .add(younger_than(policy.fresh_days))
+                .add(archived(
+                    Some(Stage::Fresh),
+                    Some(now - Duration::hours(policy.fresh_interval_hours as i64)),
+                )),
+        )
+        .add(
+            Condition::all()
+                .add(younger_than(policy.cold_days))
+                .add(older_than(policy.fresh_days))
+                .add(younger_than(policy.mature_days))
+                .add(archived(
+                    Some(Stage::Mature),
+                    Some(now - Duration::days(policy.mature_interval_days as i64)),
+                )),
+        )
+        .add(
+            Condition::all()
+                .add(younger_than(policy.cold_days))
+                .add(older_than(policy.fresh_days))
+                .add(older_than(policy.mature_days))
+                .add(archived(
+                    Some(Stage::Cold),
+                    Some(now - Duration::days(policy.cold_interval_days as i64)),
+                )),
+        )
+}
+
+/// 视频文件已下载完成的视频
+fn downloaded_video_condition() -> SimpleExpr {
+    video::Column::Id.in_subquery(
+        Query::select()
+            .column(page::Column::VideoId)
+            .from(page::Entity)
+            .and_where(page::Column::Path.is_not_null())
+            .and_where(Expr::cust(format!(
+                "(({} >> {}) & 7) = {}",
+                crate::database::dialect::current().unsigned_column("download_status"),
+                3 * PAGE_VIDEO_TASK_INDEX,
+                STATUS_OK
+            )))
+            .to_owned(),
+    )
+}
+
+/// 按发布时间倒序列出到期需要归档的视频
+async fn load_due_video_ids(
+    connection: &DatabaseConnection,
+    sources: &ArchiveSources,
+    policy: &DanmakuUpdatePolicy,
+    now: DateTime<Utc>,
+) -> Result<Vec<i32>> {
+    let now = now.with_timezone(&beijing_timezone()).naive_local();
+    Ok(video::Entity::find()
+        .select_only()
+        .column(video::Column::Id)
+        .filter(video::Column::Valid.eq(true))
+        .filter(sources.video_filter())
+        .filter(downloaded_video_condition())
+        .filter(archived_not_due_condition(policy, now).not())
+        .order_by_desc(video::Column::Pubtime)
+        .order_by_desc(video::Column::Id)
+        .into_tuple()
+        .all(connection)
+        .await?)
+}
+
+/// 侧车文件路径的公共前缀：单P视频为 `<视频文件名>.comments`，多P视频为视频目录下的 `comments`
+fn comment_sidecar_base(video_model: &video::Model, pages: &[page::Model]) -> Option<PathBuf> {
+    if video_model.single_page.unwrap_or(pages.len() == 1) {
+        let page_path = pages.iter().find_map(|page_model| page_model.path.as_deref())?;
+        return Some(Path::new(page_path).with_extension("comments"));
+    }
+    (!video_model.path.is_empty()).then(|| Path::new(&video_model.path).join("comments"))
+}
+
+fn sidecar_path(base: &Path, ext: &str) -> PathBuf {
+    let mut path = base.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
+
+async fn write_comment_sidecars(base: &Path, archive: &CommentArchive) -> Result<()> {
+    let json_path = sidecar_path(base, "json");
+    let html_path = sidecar_path(base, "html");
+    tokio::fs::write(&json_path, serde_json::to_string_pretty(archive)?)
+        .await
+        .with_context(|| format!("写入评论归档失败: {}", json_path.display()))?;
+    tokio::fs::write(&html_path, archive.render_html())
+        .await
+        .with_context(|| format!("写入评论归档失败: {}", html_path.display()))?;
+    Ok(())
+}
+
+async fn save_comment_archive_record(
+    connection: &DatabaseConnection,
+    video_id: i32,
+    last_synced_at: String,
+    sync_generation: u32,
+    comment_count: u32,
+) -> Result<()> {
+    comment_archive::Entity::insert(comment_archive::ActiveModel {
+        video_id: Set(video_id),
+        last_synced_at: Set(last_synced_at),
+        sync_generation: Set(sync_generation),
+        comment_count: Set(comment_count),
+        updated_at: Set(now_standard_string()),
+    })
+    .on_conflict(
+        OnConflict::column(comment_archive::Column::VideoId)
+            .update_columns([
+                comment_archive::Column::LastSyncedAt,
+                comment_archive::Column::SyncGeneration,
+                comment_archive::Column::CommentCount,
+                comment_archive::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(connection)
+    .await?;
+    Ok(())
+}
+
+/// 为开启评论区归档的视频源归档到期视频的评论区，返回本轮归档的视频数
+pub async fn archive_due_comments(
+    bili_client: &BiliClient,
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+) -> Result<usize> {
+    let sources = ArchiveSources::load(connection).await?;
+    if sources.is_empty() {
+        return Ok(0);
+    }
+    let config = crate::config::reload_config();
+    let option = &config.comment_archive;
+
+    let now = Utc::now();
+    let due_ids = load_due_video_ids(connection, &sources, &config.danmaku_update_policy, now)
+        .await
+        .context("查询评论区归档候选视频失败")?;
+
+    let mut archived = 0;
+    'batches: for batch in due_ids.chunks(CANDIDATE_BATCH_SIZE) {
+        let videos = video::Entity::find()
+            .filter(video::Column::Id.is_in(batch.iter().copied()))
+            .order_by_desc(video::Column::Pubtime)
+            .order_by_desc(video::Column::Id)
+            .find_with_related(page::Entity)
+            .all(connection)
+            .await
+            .context("加载评论区归档候选视频失败")?;
+        let records: HashMap<i32, comment_archive::Model> = comment_archive::Entity::find()
+            .filter(comment_archive::Column::VideoId.is_in(batch.iter().copied()))
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|record| (record.video_id, record))
+            .collect();
+
+        for (video_model, pages) in videos {
+            if archived >= option.max_videos_per_run {
+                debug!("评论区归档：本轮已达到 {} 个视频的上限", option.max_videos_per_run);
+                break 'batches;
+            }
+            if token.is_cancelled() || crate::task::TASK_CONTROLLER.is_paused() {
+                info!("任务已暂停/取消，中止本轮评论区归档");
+                break 'batches;
+            }
+            let Some(credential_id) = sources.credential_for(&video_model) else {
+                continue;
+            };
+            let pages: Vec<page::Model> = pages
+                .into_iter()
+                .filter(|page_model| {
+                    page_model.path.is_some()
+                        && PageStatus::from(page_model.download_status).get(PAGE_VIDEO_TASK_INDEX) == STATUS_OK
+                })
+                .collect();
+            if pages.is_empty() {
+                continue;
+            }
+            let pubtime = stored_beijing_naive_to_utc(video_model.pubtime);
+            let Decision::Sync { next_stage } = comment_sync_decision(
+                &config.danmaku_update_policy,
+                pubtime,
+                records.get(&video_model.id),
+                now,
+            ) else {
+                continue;
+            };
+            let Some(base) = comment_sidecar_base(&video_model, &pages) else {
+                continue;
+            };
+            if !base.parent().is_some_and(Path::is_dir) {
+                debug!("评论区归档：视频目录不存在，跳过: {}", base.display());
+                continue;
+            }
+
+            let client = bili_client.with_credential_id(credential_id);
+            let fetcher = VideoComments::new(&client, crate::bilibili::bvid_to_aid(&video_model.bvid).to_string());
+            let fetched = tokio::select! {
+                biased;
+                _ = token.cancelled() => break 'batches,
+                res = fetcher.fetch(option) => res,
+            };
+            let (pinned, hot, total) = match fetched {
+                Ok(fetched) => fetched,
+                Err(e) => {
+                    warn!("获取视频「{}」的评论区失败: {:#}", video_model.name, e);
+                    continue;
+                }
+            };
+            let archive = CommentArchive {
+                bvid: video_model.bvid.clone(),
+                title: video_model.name.clone(),
+                archived_at: now_standard_string(),
+                total,
+                pinned,
+                hot,
+            };
+            if let Err(e) = write_comment_sidecars(&base, &archive).await {
+                warn!("保存视频「{}」的评论区归档失败: {:#}", video_model.name, e);
+                continue;
+            }
+            save_comment_archive_record(
+                connection,
+                video_model.id,
+                to_standard_string(now.with_timezone(&beijing_timezone())),
+                next_stage.as_generation(),
+                archive.comment_count() as u32,
+            )
+            .await?;
+            debug!(
+                "已归档视频「{}」的 {} 条评论（{}）",
+                video_model.name,
+                archive.comment_count(),
+                next_stage.label()
+            );
+            archived += 1;
+        }
+    }
+
+    if archived > 0 {
+        info!("评论区归档：本轮共归档 {} 个视频的评论区", archived);
+    }
+    Ok(archived)
+}
+
+#[cfg(test)]
+mod tests {
+    use bili_sync_migration::{Migrator, MigratorTrait};
+    use chrono::TimeZone;
+    use sea_orm::sqlx::sqlite::SqlitePoolOptions;
+    use sea_orm::{ActiveModelTrait, ConnectionTrait, DbBackend, IntoActiveModel, SqlxSqliteConnector, Statement};
+
+    use super::*;
+    use crate::utils::status::STATUS_COMPLETED;
+
+    fn record(last_synced_at: &str, sync_generation: u32) -> comment_archive::Model {
+        comment_archive::Model {
+            video_id: 1,
+            last_synced_at: last_synced_at.to_string(),
+            sync_generation,
+            comment_count: 0,
+            updated_at: last_synced_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn first_archive_ignores_disabled_policy() {
+        let policy = DanmakuUpdatePolicy {
+            enabled: false,
+            ..Default::default()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        let pubtime = now - chrono::Duration::days(1);
+        assert!(matches!(
+            comment_sync_decision(&policy, pubtime, None, now),
+            Decision::Sync {
+                next_stage: Stage::Fresh
+            }
+        ));
+        // 已归档过且策略关闭时不再刷新
+        assert_eq!(
+            comment_sync_decision(&policy, pubtime, Some(&record("2026-10-18 08:00:00", 1)), now),
+            Decision::Skip
+        );
+    }
+
+    #[test]
+    fn refresh_follows_danmaku_schedule() {
+        let policy = DanmakuUpdatePolicy {
+            enabled: true,
+            ..Default::default()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        let pubtime = now - chrono::Duration::days(1);
+        // 刚归档过的新视频不需要刷新
+        let just_synced = to_standard_string(now.with_timezone(&beijing_timezone()));
+        assert_eq!(
+            comment_sync_decision(&policy, pubtime, Some(&record(&just_synced, 1)), now),
+            Decision::Skip
+        );
+        // 超过新鲜期间隔后重新归档
+        assert!(matches!(
+            comment_sync_decision(&policy, pubtime, Some(&record("2026-10-01 08:00:00", 1)), now),
+            Decision::Sync { .. }
+        ));
+    }
+
+    #[test]
+    fn sidecar_paths_follow_page_layout() {
+        let video_model = video::Model {
+            path: "/media/up/多P视频".to_string(),
+            single_page: Some(true),
+            ..Default::default()
+        };
+        let page_model = page::Model {
+            path: Some("/media/up/单P视频.mp4".to_string()),
+            ..Default::default()
+        };
+        let base = comment_sidecar_base(&video_model, std::slice::from_ref(&page_model)).unwrap();
+        assert_eq!(
+            sidecar_path(&base, "json"),
+            PathBuf::from("/media/up/单P视频.comments.json")
+        );
+
+        let multi_page = video::Model {
+            single_page: Some(false),
+            ..video_model
+        };
+        let base = comment_sidecar_base(&multi_page, &[page_model.clone(), page_model]).unwrap();
+        assert_eq!(
+            sidecar_path(&base, "html"),
+            PathBuf::from("/media/up/多P视频/comments.html")
+        );
+    }
+
+    async fn insert_video(db: &DatabaseConnection, id: i32, pubtime: DateTime<Utc>, video_downloaded: bool) {
+        video::Model {
+            id,
+            submission_id: Some(1),
+            bvid: format!("BV{id}"),
+            pubtime: pubtime.with_timezone(&beijing_timezone()).naive_local(),
+            valid: true,
+            ..Default::default()
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(db)
+        .await
+        .unwrap();
+        let status = if video_downloaded {
+            STATUS_COMPLETED | (STATUS_OK << (3 * PAGE_VIDEO_TASK_INDEX))
+        } else {
+            0
+        };
+        page::Model {
+            id,
+            video_id: id,
+            pid: 1,
+            path: Some(format!("/media/{id}.mp4")),
+            download_status: status,
+            ..Default::default()
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn due_videos_are_selected_in_database() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let db = SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
+        Migrator::up(&db, None).await.unwrap();
+        db.execute(Statement::from_string(
+            DbBackend::Sqlite,
+            "ALTER TABLE page ADD COLUMN ai_renamed INTEGER",
+        ))
+        .await
+        .ok();
+
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap();
+        let synced =
+            |hours_ago: i64| to_standard_string((now - Duration::hours(hours_ago)).with_timezone(&beijing_timezone()));
+        // 1: 未归档；2: 视频文件未下载；3: 新鲜期内刚归档；4: 新鲜期内归档已超过间隔
+        // 5: 刚归档但已进入成熟期；6: 已冻结
+        insert_video(&db, 1, now - Duration::days(1), true).await;
+        insert_video(&db, 2, now - Duration::days(1), false).await;
+        insert_video(&db, 3, now - Duration::days(1), true).await;
+        insert_video(&db, 4, now - Duration::days(2), true).await;
+        insert_video(&db, 5, now - Duration::days(10), true).await;
+        insert_video(&db, 6, now - Duration::days(400), true).await;
+        save_comment_archive_record(&db, 3, synced(1), Stage::Fresh.as_generation(), 0)
+            .await
+            .unwrap();
+        save_comment_archive_record(&db, 4, synced(24), Stage::Fresh.as_generation(), 0)
+            .await
+            .unwrap();
+        save_comment_archive_record(&db, 5, synced(1), Stage::Fresh.as_generation(), 0)
+            .await
+            .unwrap();
+        save_comment_archive_record(&db, 6, synced(24 * 30), Stage::Frozen.as_generation(), 0)
+            .await
+            .unwrap();
+
+        let sources = ArchiveSources {
+            submission: HashMap::from([(1, None)]),
+            ..Default::default()
+        };
+        let policy = DanmakuUpdatePolicy {
+            enabled: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            load_due_video_ids(&db, &sources, &policy, now).await.unwrap(),
+            vec![1, 4, 5]
+        );
+        // 策略关闭时只归档从未归档过的视频
+        let disabled = DanmakuUpdatePolicy {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            load_due_video_ids(&db, &sources, &disabled, now).await.unwrap(),
+            vec![1]
+        );
+    }
+}
@@ -4,15 +4,18 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Context, Result};
-use bili_sync_entity::{collection, favorite, page, submission, video, video_source, watch_later};
+use bili_sync_entity::{collection, favorite, page, ranking, submission, video, video_source, watch_later};
 use chrono::{DateTime, TimeZone, Utc};
 use sea_orm::entity::prelude::*;
 use sea_orm::sea_query::SimpleExpr;
 use sea_orm::{ActiveModelTrait, Condition, QueryFilter, QuerySelect, Set};
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::bilibili::{parse_event_name, DanmakuElem, DanmakuWriter, Dimension, PageInfo as BiliPageInfo, Video};
+use crate::bilibili::{
+    archive_danmaku, load_archived_danmaku, parse_event_name, DanmakuElem, DanmakuFilter, DanmakuHistogram,
+    DanmakuWriter, Dimension, PageInfo as BiliPageInfo, Video,
+};
 use crate::config::Config;
 use crate::utils::danmaku_schedule::{should_sync_danmaku, stage_for_age, Decision, Stage};
 use crate::utils::status::{PageStatus, VideoStatus, STATUS_OK};
@@ -37,6 +40,8 @@ pub struct PageDanmakuSyncUpdate {
     pub width: Option<Option<u32>>,
     pub height: Option<Option<u32>>,
     pub name: Option<String>,
+    /// 本次拉取到的全部弹幕的密度直方图
+    pub histogram: DanmakuHistogram,
 }
 
 impl PageDanmakuSyncUpdate {
@@ -78,7 +83,6 @@ fn should_fallback_to_stored_pages(err: &anyhow::Error) -> bool {
     is_bili_request_failed_with_codes(err, &[-404, 62002, 62012])
 }
 
-#[cfg(test)]
 fn build_stored_page_info(page_model: &page::Model) -> Result<BiliPageInfo> {
     let cid = page_model.danmaku_cid_snapshot.unwrap_or(page_model.cid);
     if cid <= 0 {
@@ -341,6 +345,15 @@ async fn load_candidate_videos(connection: &DatabaseConnection) -> Result<Vec<(v
         .all(connection)
         .await
         .context("加载启用的稍后再看源失败")?;
+    let ranking_ids: Vec<i32> = ranking::Entity::find()
+        .filter(ranking::Column::Enabled.eq(true))
+        .filter(ranking::Column::DownloadDanmaku.eq(true))
+        .select_only()
+        .column(ranking::Column::Id)
+        .into_tuple()
+        .all(connection)
+        .await
+        .context("加载启用的榜单源失败")?;
     let bangumi_ids: Vec<i32> = video_source::Entity::find()
         .filter(video_source::Column::Type.eq(1))
         .filter(video_source::Column::Enabled.eq(true))
@@ -356,6 +369,7 @@ async fn load_candidate_videos(connection: &DatabaseConnection) -> Result<Vec<(v
         && collection_ids.is_empty()
         && submission_ids.is_empty()
         && watch_later_ids.is_empty()
+        && ranking_ids.is_empty()
         && bangumi_ids.is_empty()
     {
         return Ok(Vec::new());
@@ -374,6 +388,9 @@ async fn load_candidate_videos(connection: &DatabaseConnection) -> Result<Vec<(v
     if !watch_later_ids.is_empty() {
         source_filter = source_filter.add(video::Column::WatchLaterId.is_in(watch_later_ids));
     }
+    if !ranking_ids.is_empty() {
+        source_filter = source_filter.add(video::Column::RankingId.is_in(ranking_ids));
+    }
     if !bangumi_ids.is_empty() {
         source_filter = source_filter.add(
             Condition::all()
@@ -475,6 +492,28 @@ pub async fn sync_page_danmaku(
         .as_deref()
         .and_then(parse_stored_datetime);
     let danmaku_elems = bili_video.get_danmaku_elements(&page_info_for_danmaku, token).await?;
+    let histogram =
+        DanmakuHistogram::from_elems(&danmaku_elems, fresh_duration, config.danmaku_highlight.bucket_seconds);
+    let (source_type, source_id) = crate::adapter::video_source_of(video_model);
+    let filter_rules = config.danmaku_filter.rules_for(source_type.as_deref(), source_id);
+    let danmaku_filter = DanmakuFilter::new(&filter_rules).context("弹幕过滤规则无效")?;
+    if config.danmaku_archive.enabled() {
+        // 归档失败不影响 ASS 的生成，下次同步时会按 dmid 补齐
+        if let Err(e) = archive_danmaku(
+            &config.danmaku_archive,
+            danmaku_path,
+            fresh.cid,
+            &danmaku_elems,
+            cid_changed,
+        )
+        .await
+        {
+            warn!(
+                "视频「{}」({}) 分页 pid={} 原始弹幕归档失败: {:#}",
+                video_model.name, video_model.bvid, fresh.page, e
+            );
+        }
+    }
     let file_exists = tokio::fs::metadata(&danmaku_path).await.is_ok();
     let fetched_danmaku_count = danmaku_elems.len() as u32;
     let last_write_count = if file_exists && !cid_changed {
@@ -486,7 +525,8 @@ pub async fn sync_page_danmaku(
             let writer = DanmakuWriter::new(
                 &page_info_for_danmaku,
                 incremental_elems.into_iter().map(Into::into).collect(),
-            );
+            )
+            .with_filter(danmaku_filter);
             writer.append(danmaku_path.to_path_buf()).await?;
         }
         incremental_count
@@ -495,7 +535,8 @@ pub async fn sync_page_danmaku(
         let writer = DanmakuWriter::new(
             &page_info_for_danmaku,
             danmaku_elems.into_iter().map(Into::into).collect(),
-        );
+        )
+        .with_filter(danmaku_filter);
         writer.write(tmp_path.clone()).await?;
         tokio::fs::rename(&tmp_path, &danmaku_path)
             .await
@@ -521,9 +562,69 @@ pub async fn sync_page_danmaku(
         width: (!cid_changed && dimension_changed).then_some(fresh_width),
         height: (!cid_changed && dimension_changed).then_some(fresh_height),
         name: (!cid_changed && name_changed).then_some(fresh_name),
+        histogram,
     })
 }
 
+/// 加载需要重新渲染弹幕的视频与分页，source 为空时表示整个媒体库
+pub async fn load_danmaku_rerender_candidates(
+    connection: &DatabaseConnection,
+    source: Option<(&str, i32)>,
+) -> Result<Vec<(video::Model, Vec<page::Model>)>> {
+    let mut filter = Condition::all();
+    if let Some((source_type, source_id)) = source {
+        filter = filter.add(
+            crate::adapter::video_source_condition(source_type, source_id)
+                .with_context(|| format!("未知的视频源类型 {}", source_type))?,
+        );
+    }
+    load_candidate_videos_with_filter(connection, filter).await
+}
+
+/// 单个分页重新渲染弹幕的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RerenderOutcome {
+    /// 已重新生成 ASS
+    Rendered,
+    /// 分页没有本地文件路径
+    NoLocalFile,
+    /// 没有原始弹幕归档（`danmaku_archive` 默认关闭）
+    NoArchive,
+}
+
+/// 用归档的原始弹幕和当前的 `DanmakuOption` 重新生成分页的 ASS，
+/// 分辨率取数据库中保存的宽高
+pub async fn rerender_page_danmaku(
+    config: &Config,
+    video_model: &video::Model,
+    page_model: &page::Model,
+) -> Result<RerenderOutcome> {
+    let Some(video_path) = page_model.path.as_deref().map(Path::new) else {
+        return Ok(RerenderOutcome::NoLocalFile);
+    };
+    let (Some(parent), Some(stem)) = (video_path.parent(), video_path.file_stem()) else {
+        return Ok(RerenderOutcome::NoLocalFile);
+    };
+    let danmaku_path = parent.join(format!("{}.zh-CN.default.ass", stem.to_string_lossy()));
+    let Some(danmaku_elems) = load_archived_danmaku(&danmaku_path).await? else {
+        return Ok(RerenderOutcome::NoArchive);
+    };
+
+    let page_info = build_stored_page_info(page_model)?;
+    let (source_type, source_id) = crate::adapter::video_source_of(video_model);
+    let filter_rules = config.danmaku_filter.rules_for(source_type.as_deref(), source_id);
+    let danmaku_filter = DanmakuFilter::new(&filter_rules).context("弹幕过滤规则无效")?;
+    let tmp_path = make_tmp_path(&danmaku_path);
+    DanmakuWriter::new(&page_info, danmaku_elems.into_iter().map(Into::into).collect())
+        .with_filter(danmaku_filter)
+        .write(tmp_path.clone())
+        .await?;
+    tokio::fs::rename(&tmp_path, &danmaku_path)
+        .await
+        .with_context(|| format!("重命名弹幕文件 {:?} -> {:?} 失败", tmp_path, danmaku_path))?;
+    Ok(RerenderOutcome::Rendered)
+}
+
 fn extract_dimension(dimension: Option<&Dimension>) -> (Option<u32>, Option<u32>) {
     match dimension {
         Some(dimension) if dimension.rotate == 0 => (Some(dimension.width), Some(dimension.height)),
@@ -693,6 +794,7 @@ mod tests {
             scan_deleted_videos: Set(false),
             scan_deleted_videos_once: Set(false),
             filter_option: Set(None),
+            credential_id: Set(None),
             selected_videos: Set(None),
             keyword_filters: Set(None),
             keyword_filter_mode: Set(None),
@@ -709,6 +811,7 @@ mod tests {
             split_chapters_after_download: Set(false),
             download_charge_videos: Set(true),
             download_danmaku: Set(true),
+            archive_comments: Set(false),
             download_subtitle: Set(false),
             download_ai_subtitle: Set(true),
             ai_subtitle_language: Set("zh-CN".to_string()),
@@ -745,6 +848,7 @@ mod tests {
             favorite_id: Set(None),
             watch_later_id: Set(None),
             submission_id: Set(Some(submission_id)),
+            ranking_id: Set(None),
             source_id: Set(None),
             source_type: Set(None),
             upper_id: Set(10_000 + i64::from(submission_id)),
@@ -822,6 +926,7 @@ mod tests {
             danmaku_cid_snapshot: Set(Some(cid)),
             danmaku_last_write_count: Set(0),
             ai_renamed: Set(Some(0)),
+            quality_checked_at: Set(None),
         }
         .insert(db)
         .await
@@ -1102,6 +1207,34 @@ mod tests {
         assert!(filtered.iter().any(|elem| elem.dmid_str == "102"));
     }
 
+    #[tokio::test]
+    async fn rerender_reports_pages_without_archive_separately() {
+        let media_dir = unique_temp_dir("rerender-no-archive");
+        fs::create_dir_all(&media_dir).expect("应能创建测试媒体目录");
+        let video_path = media_dir.join("no-archive.mp4");
+        fs::write(&video_path, []).expect("应能创建测试视频文件");
+
+        let config = Config::default();
+        let video_model = video::Model::default();
+        let without_path = page::Model::default();
+        let without_archive = page::Model {
+            path: Some(video_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            rerender_page_danmaku(&config, &video_model, &without_path)
+                .await
+                .unwrap(),
+            RerenderOutcome::NoLocalFile
+        );
+        assert_eq!(
+            rerender_page_danmaku(&config, &video_model, &without_archive)
+                .await
+                .unwrap(),
+            RerenderOutcome::NoArchive
+        );
+    }
+
     #[test]
     fn should_fallback_to_stored_pages_accepts_62012() {
         let err = anyhow!(crate::bilibili::BiliError::RequestFailed(62012, "62012".to_string()));
@@ -0,0 +1,196 @@
+//! 弹幕密度统计与高能章节工作流。
+//!
+//! 每次同步弹幕时保存分页的弹幕密度直方图；开启 `danmaku_highlight.generate_chapters` 后，
+//! UP 主没有设置章节的分页会以弹幕高能时刻生成章节，写入 NFO 与视频文件。
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use bili_sync_entity::{danmaku_histogram, page, video};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{Set, Unchanged};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+use crate::bilibili::{highlight_chapters, BiliClient, DanmakuHistogram, HighlightChapter, PageInfo, Video};
+use crate::config::Config;
+use crate::utils::time_format::now_standard_string;
+
+/// 支持写入章节的容器格式
+const CHAPTER_CONTAINERS: [&str; 5] = ["mp4", "m4a", "m4v", "mkv", "mov"];
+
+/// 保存分页的弹幕直方图，已写入的章节记录保持不变
+pub async fn save_danmaku_histogram(
+    connection: &DatabaseConnection,
+    page_id: i32,
+    video_id: i32,
+    histogram: &DanmakuHistogram,
+) -> Result<()> {
+    danmaku_histogram::Entity::insert(danmaku_histogram::ActiveModel {
+        page_id: Set(page_id),
+        video_id: Set(video_id),
+        bucket_seconds: Set(histogram.bucket_seconds),
+        counts: Set(serde_json::to_string(&histogram.counts)?),
+        total: Set(histogram.total()),
+        chapters: Set(None),
+        updated_at: Set(now_standard_string()),
+    })
+    .on_conflict(
+        OnConflict::column(danmaku_histogram::Column::PageId)
+            .update_columns([
+                danmaku_histogram::Column::VideoId,
+                danmaku_histogram::Column::BucketSeconds,
+                danmaku_histogram::Column::Counts,
+                danmaku_histogram::Column::Total,
+                danmaku_histogram::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(connection)
+    .await?;
+    Ok(())
+}
+
+/// 章节数量变化，或任一章节的起点移动超过 `min_gap_seconds` 时才需要重新写入；
+/// 弹幕增多后高能时刻的小幅漂移不会触发整个视频文件的重新封装
+fn chapters_changed(written: &[HighlightChapter], chapters: &[HighlightChapter], min_gap_seconds: u32) -> bool {
+    written.len() != chapters.len()
+        || written
+            .iter()
+            .zip(chapters)
+            .any(|(old, new)| old.start_s.abs_diff(new.start_s) > min_gap_seconds)
+}
+
+/// 读取分页的弹幕直方图与最近一次写入的章节
+pub async fn load_danmaku_histogram(
+    connection: &DatabaseConnection,
+    page_id: i32,
+) -> Result<Option<(danmaku_histogram::Model, DanmakuHistogram, Vec<HighlightChapter>)>> {
+    let Some(model) = danmaku_histogram::Entity::find_by_id(page_id).one(connection).await? else {
+        return Ok(None);
+    };
+    let histogram = DanmakuHistogram {
+        bucket_seconds: model.bucket_seconds,
+        counts: serde_json::from_str(&model.counts).context("弹幕直方图数据损坏")?,
+    };
+    let chapters = model
+        .chapters
+        .as_deref()
+        .and_then(|chapters| serde_json::from_str(chapters).ok())
+        .unwrap_or_default();
+    Ok(Some((model, histogram, chapters)))
+}
+
+/// UP 主没有设置章节时，按弹幕高能时刻生成章节写入视频文件与同名 NFO。
+/// 章节与上次写入的相比没有明显变化时跳过，返回是否写入了章节
+#[allow(clippy::too_many_arguments)]
+pub async fn write_highlight_chapters(
+    connection: &DatabaseConnection,
+    bili_client: &BiliClient,
+    config: &Config,
+    video_model: &video::Model,
+    page_model: &page::Model,
+    page_info: &PageInfo,
+    histogram: &DanmakuHistogram,
+    video_path: &Path,
+    token: CancellationToken,
+) -> Result<bool> {
+    let option = &config.danmaku_highlight;
+    if !option.generate_chapters || !video_path.exists() {
+        return Ok(false);
+    }
+    let peaks = histogram.peaks(option.chapter_count, option.min_gap_seconds);
+    let chapters = highlight_chapters(&peaks, page_info.duration);
+    if chapters.is_empty() {
+        return Ok(false);
+    }
+    let serialized = serde_json::to_string(&chapters)?;
+    let written: Option<Vec<HighlightChapter>> = danmaku_histogram::Entity::find_by_id(page_model.id)
+        .one(connection)
+        .await?
+        .and_then(|model| model.chapters)
+        .and_then(|chapters| serde_json::from_str(&chapters).ok());
+    if written.is_some_and(|written| !chapters_changed(&written, &chapters, option.min_gap_seconds)) {
+        return Ok(false);
+    }
+
+    let bili_video = Video::new(bili_client, video_model.bvid.clone());
+    let uploader_chapters = tokio::select! {
+        biased;
+        _ = token.cancelled() => return Ok(false),
+        res = bili_video.get_chapters(page_info) => res?,
+    };
+    if !uploader_chapters.is_empty() {
+        debug!(
+            "视频「{}」第 {} 页已有 UP 主设置的章节，跳过高能章节",
+            video_model.name, page_model.pid
+        );
+        return Ok(false);
+    }
+
+    let is_container_supported = video_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| CHAPTER_CONTAINERS.contains(&ext.to_ascii_lowercase().as_str()));
+    if is_container_supported {
+        let file_size = crate::downloader::embed_chapters_with_ffmpeg(video_path, &chapters).await?;
+        page::Entity::update(page::ActiveModel {
+            id: Unchanged(page_model.id),
+            file_size_bytes: Set(Some(file_size as i64)),
+            ..Default::default()
+        })
+        .exec(connection)
+        .await?;
+    }
+    let nfo_path = video_path.with_extension("nfo");
+    if let Ok(xml) = tokio::fs::read_to_string(&nfo_path).await {
+        tokio::fs::write(&nfo_path, crate::utils::nfo::replace_nfo_chapters(&xml, &chapters))
+            .await
+            .with_context(|| format!("写入 NFO 章节失败: {}", nfo_path.display()))?;
+    }
+
+    danmaku_histogram::Entity::update(danmaku_histogram::ActiveModel {
+        page_id: Unchanged(page_model.id),
+        chapters: Set(Some(serialized)),
+        ..Default::default()
+    })
+    .exec(connection)
+    .await?;
+    info!(
+        "视频「{}」第 {} 页已按弹幕高能时刻写入 {} 个章节",
+        video_model.name,
+        page_model.pid,
+        chapters.len()
+    );
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(start_s: u32) -> HighlightChapter {
+        HighlightChapter {
+            start_s,
+            end_s: start_s + 30,
+            title: format!("高能 {}", start_s),
+        }
+    }
+
+    #[test]
+    fn small_peak_shifts_do_not_rewrite_chapters() {
+        let written = vec![chapter(0), chapter(120), chapter(300)];
+        assert!(!chapters_changed(
+            &written,
+            &[chapter(0), chapter(150), chapter(290)],
+            30
+        ));
+        assert!(chapters_changed(
+            &written,
+            &[chapter(0), chapter(160), chapter(300)],
+            30
+        ));
+        assert!(chapters_changed(&written, &[chapter(0), chapter(120)], 30));
+    }
+}
@@ -0,0 +1,348 @@
+//! 视频源订阅前预览。
+//!
+//! 只读取B站列表接口，在内存中套用与正式扫描相同的关键词/时长/发布日期过滤和命名模板，
+//! 输出将会生成的目录结构、被跳过的视频及原因、按典型码率估算的体积。
+//! 不写数据库、不创建目录；UP主投稿统一走动态接口，避免触碰投稿扫描的断点记录。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use anyhow::{anyhow, Result};
+use bili_sync_entity::{page, video};
+use futures::{Stream, StreamExt};
+use sea_orm::entity::prelude::*;
+use sea_orm::{QuerySelect, Set, TryIntoModel};
+use tokio_util::sync::CancellationToken;
+
+use crate::api::response::{PreviewPageFile, PreviewSkippedItem, PreviewVideoItem, VideoSourcePreviewResponse};
+use crate::bilibili::{
+    AudioQuality, BiliClient, Collection, CollectionItem, Dynamic, FavoriteList, FilterOption, PageInfo, Ranking,
+    RankingType, Submission, Video, VideoInfo, VideoQuality, WatchLater,
+};
+use crate::utils::format_arg::{page_format_args, video_format_args};
+use crate::utils::model::{
+    extract_bvid, extract_duration_seconds, extract_title, video_info_is_valid, VideoListFilter,
+};
+use crate::utils::time_format::STANDARD_TIME_FORMAT;
+
+/// 单次预览最多检查的列表条目数
+pub const MAX_PREVIEW_LIMIT: usize = 200;
+
+/// 预览的列表来源，与 `adapter::Args` 对应但不依赖数据库中的视频源记录
+pub enum PreviewSourceArgs {
+    Favorite { fid: String },
+    Collection { collection_item: CollectionItem },
+    Submission { upper_id: String },
+    WatchLater,
+    Ranking { ranking_type: RankingType, rid: i32 },
+}
+
+pub struct SourcePreviewOptions {
+    pub path: PathBuf,
+    pub list_filter: VideoListFilter,
+    pub limit: usize,
+    pub fetch_pages: bool,
+    pub audio_only: bool,
+    pub flat_folder: bool,
+    /// 用于估算体积的流过滤设置
+    pub filter_option: FilterOption,
+}
+
+/// 各画质的典型视频码率（kbps），仅用于体积估算
+fn nominal_video_kbps(quality: VideoQuality) -> u64 {
+    match quality {
+        VideoQuality::Quality360p => 400,
+        VideoQuality::Quality480p => 800,
+        VideoQuality::Quality720p => 1500,
+        VideoQuality::Quality1080p => 2500,
+        VideoQuality::Quality1080pPLUS | VideoQuality::Quality1080p60 => 5000,
+        VideoQuality::Quality4k => 12000,
+        VideoQuality::QualityHdr | VideoQuality::QualityDolby => 15000,
+        VideoQuality::Quality8k => 30000,
+    }
+}
+
+fn nominal_audio_kbps(quality: AudioQuality) -> u64 {
+    match quality {
+        AudioQuality::Quality64k => 64,
+        AudioQuality::Quality132k => 132,
+        AudioQuality::Quality192k => 192,
+        AudioQuality::QualityDolby | AudioQuality::QualityDolbyBangumi => 384,
+        AudioQuality::QualityHiRES => 1000,
+    }
+}
+
+/// 按最高允许画质/音质的典型码率估算体积（字节）
+fn estimate_bytes(duration_seconds: u32, filter_option: &FilterOption, audio_only: bool) -> u64 {
+    let mut kbps = nominal_audio_kbps(filter_option.audio_max_quality);
+    if !audio_only {
+        kbps += nominal_video_kbps(filter_option.video_max_quality);
+    }
+    duration_seconds as u64 * kbps * 1000 / 8
+}
+
+fn estimate_basis(filter_option: &FilterOption, audio_only: bool) -> String {
+    if audio_only {
+        format!(
+            "仅音频，按音质上限 {:?} 的典型码率估算",
+            filter_option.audio_max_quality
+        )
+    } else {
+        format!(
+            "按画质上限 {} 与音质上限 {:?} 的典型码率估算，实际体积取决于可用流",
+            filter_option.video_max_quality, filter_option.audio_max_quality
+        )
+    }
+}
+
+type PreviewStream<'a> = Pin<Box<dyn Stream<Item = Result<VideoInfo>> + 'a + Send>>;
+
+/// 获取列表流及视频源名称；合集统一模式下额外返回统一目录名
+async fn preview_stream_from<'a>(
+    args: &'a PreviewSourceArgs,
+    bili_client: &'a BiliClient,
+    token: CancellationToken,
+) -> Result<(Option<String>, Option<String>, PreviewStream<'a>)> {
+    Ok(match args {
+        PreviewSourceArgs::Favorite { fid } => {
+            let favorite = FavoriteList::new(bili_client, fid.clone());
+            let info = favorite.get_info().await?;
+            (Some(info.title), None, Box::pin(favorite.into_video_stream()))
+        }
+        PreviewSourceArgs::Collection { collection_item } => {
+            let collection = Collection::new(bili_client, collection_item);
+            let info = collection.get_info().await?;
+            let unified_folder = (crate::config::reload_config().collection_folder_mode.as_ref() == "unified")
+                .then(|| crate::utils::filenamify::filenamify(&info.name));
+            (
+                Some(info.name),
+                unified_folder,
+                Box::pin(collection.into_video_stream()),
+            )
+        }
+        PreviewSourceArgs::Submission { upper_id } => {
+            let upper_name = Submission::new(bili_client, upper_id.clone())
+                .get_info()
+                .await
+                .map(|upper| upper.name)
+                .ok();
+            (
+                upper_name,
+                None,
+                Box::pin(Dynamic::new(bili_client, upper_id.clone()).into_video_stream(token)),
+            )
+        }
+        PreviewSourceArgs::WatchLater => (
+            Some("稍后再看".to_string()),
+            None,
+            Box::pin(WatchLater::new(bili_client).into_video_stream()),
+        ),
+        PreviewSourceArgs::Ranking { ranking_type, rid } => (
+            Some(ranking_type.source_name(*rid)),
+            None,
+            Box::pin(Ranking::new(bili_client, *ranking_type, *rid).into_video_stream()),
+        ),
+    })
+}
+
+/// 渲染单个视频将生成的目录与分页文件
+fn render_preview_item(
+    video_info: VideoInfo,
+    pages: &[PageInfo],
+    page_error: Option<String>,
+    options: &SourcePreviewOptions,
+    unified_folder: Option<&str>,
+    multi_page_use_season_structure: bool,
+) -> Result<PreviewVideoItem> {
+    let duration_seconds = extract_duration_seconds(&video_info);
+    let mut active_model = video_info.into_simple_model();
+    active_model.id = Set(0);
+    let mut video_model = active_model.try_into_model()?;
+    let is_single_page = pages.len() <= 1;
+    video_model.single_page = Some(is_single_page);
+
+    let folder = if options.flat_folder {
+        options.path.clone()
+    } else if let Some(unified_folder) = unified_folder {
+        options.path.join(unified_folder)
+    } else {
+        let folder_name =
+            crate::config::with_config(|bundle| bundle.render_video_template(&video_format_args(&video_model)))
+                .map_err(|e| anyhow!("模板渲染失败: {}", e))?;
+        options.path.join(folder_name)
+    };
+    let page_dir = if !options.flat_folder && !is_single_page && multi_page_use_season_structure {
+        folder.join("Season 01")
+    } else {
+        folder.clone()
+    };
+
+    let extension = if options.audio_only { "m4a" } else { "mp4" };
+    let mut files = Vec::with_capacity(pages.len());
+    for page_info in pages {
+        let page_model = page::Model {
+            pid: page_info.page,
+            cid: page_info.cid,
+            name: page_info.name.clone(),
+            duration: page_info.duration,
+            width: page_info.dimension.as_ref().map(|d| d.width),
+            height: page_info.dimension.as_ref().map(|d| d.height),
+            ..Default::default()
+        };
+        let args = page_format_args(&video_model, &page_model);
+        let name = crate::config::with_config(|bundle| {
+            if is_single_page {
+                bundle.render_page_template(&args)
+            } else {
+                bundle.render_multi_page_template(&args)
+            }
+        })
+        .map_err(|e| anyhow!("模板渲染失败: {}", e))?;
+        let file_name = format!("{}.{}", name, extension);
+        files.push(PreviewPageFile {
+            pid: page_info.page,
+            path: display_path(&page_dir.join(&file_name)),
+            name: file_name,
+            duration_seconds: page_info.duration,
+            estimated_bytes: estimate_bytes(page_info.duration, &options.filter_option, options.audio_only),
+        });
+    }
+
+    Ok(PreviewVideoItem {
+        estimated_bytes: files.iter().map(|file| file.estimated_bytes).sum(),
+        bvid: video_model.bvid,
+        title: video_model.name,
+        upper_name: video_model.upper_name,
+        pubtime: video_model.pubtime.format(STANDARD_TIME_FORMAT).to_string(),
+        duration_seconds,
+        folder: display_path(&folder),
+        files,
+        already_in_library: false,
+        page_error,
+    })
+}
+
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// 在不落库、不落盘的前提下预览视频源将会产生的文件
+pub async fn preview_video_source(
+    args: &PreviewSourceArgs,
+    options: &SourcePreviewOptions,
+    bili_client: &BiliClient,
+    connection: &DatabaseConnection,
+) -> Result<VideoSourcePreviewResponse> {
+    let token = CancellationToken::new();
+    let (source_name, unified_folder, mut stream) = preview_stream_from(args, bili_client, token.clone()).await?;
+    let multi_page_use_season_structure = crate::config::reload_config().multi_page_use_season_structure;
+
+    let mut scanned = 0;
+    let mut truncated = false;
+    let mut videos = Vec::new();
+    let mut skipped = Vec::new();
+    while let Some(video_info) = stream.next().await {
+        if scanned >= options.limit {
+            truncated = true;
+            break;
+        }
+        let video_info = video_info?;
+        scanned += 1;
+
+        let bvid = extract_bvid(&video_info);
+        let title = extract_title(&video_info);
+        if bvid.trim().is_empty() || !video_info_is_valid(&video_info) {
+            skipped.push(PreviewSkippedItem {
+                bvid,
+                title,
+                reason: "视频已失效".to_string(),
+            });
+            continue;
+        }
+        if let Some(reason) = options.list_filter.skip_reason(&video_info) {
+            skipped.push(PreviewSkippedItem {
+                bvid,
+                title,
+                reason: reason.to_string(),
+            });
+            continue;
+        }
+
+        let listed_duration = extract_duration_seconds(&video_info).unwrap_or(0).max(0) as u32;
+        let fallback_pages = || {
+            vec![PageInfo {
+                page: 1,
+                name: title.clone(),
+                duration: listed_duration,
+                ..Default::default()
+            }]
+        };
+        let (pages, page_error) = if options.fetch_pages {
+            match Video::new(bili_client, bvid.clone()).get_pages().await {
+                Ok(pages) if !pages.is_empty() => (pages, None),
+                Ok(_) => (fallback_pages(), Some("分P列表为空".to_string())),
+                Err(e) => (fallback_pages(), Some(format!("{:#}", e))),
+            }
+        } else {
+            (fallback_pages(), None)
+        };
+
+        videos.push(render_preview_item(
+            video_info,
+            &pages,
+            page_error,
+            options,
+            unified_folder.as_deref(),
+            multi_page_use_season_structure,
+        )?);
+    }
+    // 提前结束时让投稿动态流尽快停下
+    token.cancel();
+
+    let bvids: Vec<String> = videos.iter().map(|item| item.bvid.clone()).collect();
+    if !bvids.is_empty() {
+        let existing: HashSet<String> = video::Entity::find()
+            .select_only()
+            .column(video::Column::Bvid)
+            .filter(video::Column::Bvid.is_in(bvids))
+            .filter(video::Column::Deleted.eq(0))
+            .into_tuple::<String>()
+            .all(connection)
+            .await?
+            .into_iter()
+            .collect();
+        for item in videos.iter_mut() {
+            item.already_in_library = existing.contains(&item.bvid);
+        }
+    }
+
+    Ok(VideoSourcePreviewResponse {
+        source_name,
+        scanned,
+        truncated,
+        estimated_total_bytes: videos.iter().map(|item| item.estimated_bytes).sum(),
+        estimate_basis: estimate_basis(&options.filter_option, options.audio_only),
+        videos,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_bytes_uses_quality_ceiling_and_respects_audio_only() {
+        let filter_option = FilterOption {
+            video_max_quality: VideoQuality::Quality1080p,
+            audio_max_quality: AudioQuality::Quality192k,
+            ..Default::default()
+        };
+        // (2500 + 192) kbps * 60s = 161520 kbit = 20190000 bytes
+        assert_eq!(estimate_bytes(60, &filter_option, false), 20_190_000);
+        // 192 kbps * 60s = 1440000 bytes
+        assert_eq!(estimate_bytes(60, &filter_option, true), 1_440_000);
+        assert_eq!(estimate_bytes(0, &filter_option, false), 0);
+    }
+}
@@ -0,0 +1,557 @@
+//! 画质升级重下载工作流。
+//!
+//! 新投稿刚发布时往往只有 1080P，数小时后才出现 4K/HDR 等更高画质。
+//! 本模块定期把最近发布且视频文件已下载完成的分页加入画质升级队列，后台按视频源的流过滤设置重新选流，
+//! 若能选到严格更优的画质/编码，则下载到临时文件，经 ffprobe 与原文件比对通过后再原子替换原文件。
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use bili_sync_entity::{collection, favorite, page, ranking, submission, video, video_source, watch_later};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveModelTrait, Condition, QueryFilter, QueryOrder, Set, Unchanged};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::bilibili::{BiliClient, FilterOption, PageAnalyzer, Video};
+use crate::config::{Config, QualityUpgradePolicy};
+use crate::downloader::{probe_media, replace_file_atomically, MediaProbe};
+use crate::unified_downloader::UnifiedDownloader;
+use crate::utils::status::{PageStatus, STATUS_OK};
+use crate::utils::time_format::{now_naive, now_standard_string, STANDARD_TIME_FORMAT};
+use crate::workflow::{
+    build_download_play_stream_cache, download_page_video_from_streams, get_cached_video_codecs_description,
+    page_info_from_page_model,
+};
+
+/// 分页状态中“视频文件”子任务的下标
+const PAGE_VIDEO_TASK_INDEX: usize = 1;
+
+/// 已缓存/新选出的视频流在过滤设置下的排序依据
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StreamRank {
+    quality: u64,
+    /// 编码在 `FilterOption::codecs` 中的位置，越小越优先；不在列表中时为列表长度
+    codec_rank: usize,
+}
+
+fn stream_rank(video_streams: &[Value], filter_option: &FilterOption) -> Option<StreamRank> {
+    let stream = video_streams.first()?;
+    let quality = stream["quality"].as_u64()?;
+    let codecs = stream["codecs"].as_str().unwrap_or_default();
+    let codec_rank = filter_option
+        .codecs
+        .iter()
+        .position(|codec| get_cached_video_codecs_description(*codec) == codecs)
+        .unwrap_or(filter_option.codecs.len());
+    Some(StreamRank { quality, codec_rank })
+}
+
+/// 仅当画质更高，或画质相同但编码更靠前时才视为升级；混合流（quality 为 0）无法比较，不参与升级
+fn is_strictly_better(candidate: StreamRank, current: StreamRank) -> bool {
+    if candidate.quality == 0 {
+        return false;
+    }
+    candidate.quality > current.quality
+        || (candidate.quality == current.quality && candidate.codec_rank < current.codec_rank)
+}
+
+/// 升级下载使用的临时文件：与原文件同目录、同扩展名，保证最终 rename 不跨设备
+fn upgrade_temp_path(page_path: &Path) -> PathBuf {
+    let stem = page_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "video".to_string());
+    let file_name = match page_path.extension() {
+        Some(ext) => format!("{}.upgrade.{}", stem, ext.to_string_lossy()),
+        None => format!("{}.upgrade", stem),
+    };
+    page_path.with_file_name(file_name)
+}
+
+fn backup_path(page_path: &Path) -> PathBuf {
+    let mut file_name = page_path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".bak");
+    page_path.with_file_name(file_name)
+}
+
+/// 用新文件原子替换原文件：原文件先硬链接为 .bak，新文件直接 rename 到原路径上，替换成功后删除备份
+async fn replace_with_upgraded_file(page_path: &Path, upgraded_path: &Path) -> Result<()> {
+    replace_file_atomically(page_path, upgraded_path, &backup_path(page_path))
+        .await
+        .context("替换为升级后的文件失败")
+}
+
+/// 升级后的文件与原文件的时长允许相差 1%，且至少允许 2 秒（不同画质的封装时长略有出入）
+fn duration_tolerance_secs(original_secs: f64) -> f64 {
+    (original_secs * 0.01).max(2.0)
+}
+
+/// 比对 ffprobe 结果：新文件必须有视频流、音频流不少于原文件，且时长与原文件一致
+fn check_upgraded_probe(original: &MediaProbe, upgraded: &MediaProbe) -> Result<()> {
+    if upgraded.video_streams == 0 {
+        bail!("升级后的文件没有视频流");
+    }
+    if upgraded.audio_streams < original.audio_streams {
+        bail!(
+            "升级后的文件音频流数量减少（{} -> {}）",
+            original.audio_streams,
+            upgraded.audio_streams
+        );
+    }
+    let diff = (upgraded.duration_secs - original.duration_secs).abs();
+    if diff > duration_tolerance_secs(original.duration_secs) {
+        bail!(
+            "升级后的文件时长与原文件不一致（{:.1}s -> {:.1}s）",
+            original.duration_secs,
+            upgraded.duration_secs
+        );
+    }
+    Ok(())
+}
+
+/// 替换前用 ffprobe 校验升级后的文件；任一文件无法探测时都不替换
+async fn verify_upgraded_file(page_path: &Path, upgraded_path: &Path) -> Result<()> {
+    let original = probe_media(page_path).await.context("探测原文件失败")?;
+    let upgraded = probe_media(upgraded_path).await.context("探测升级后的文件失败")?;
+    check_upgraded_probe(&original, &upgraded)
+        .with_context(|| format!("升级后的文件校验未通过: {}", upgraded_path.display()))
+}
+
+/// 读取视频所属视频源的流过滤设置（展开画质档案）、“仅音频”开关与绑定的B站账号；未设置时使用全局配置
+async fn resolve_source_filter_option(
+    connection: &DatabaseConnection,
+    video_model: &video::Model,
+    config: &Config,
+) -> Result<Option<(FilterOption, bool, Option<i32>)>> {
+    let source = if let Some(id) = video_model.favorite_id {
+        favorite::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else if let Some(id) = video_model.collection_id {
+        collection::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else if let Some(id) = video_model.watch_later_id {
+        watch_later::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else if let Some(id) = video_model.submission_id {
+        submission::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else if let Some(id) = video_model.source_id {
+        video_source::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else if let Some(id) = video_model.ranking_id {
+        ranking::Entity::find_by_id(id)
+            .one(connection)
+            .await?
+            .map(|m| (m.filter_option, m.audio_only, m.credential_id))
+    } else {
+        None
+    };
+
+    let Some((filter_option, audio_only, credential_id)) = source else {
+        return Ok(None);
+    };
+    let filter_option = config
+        .resolve_source_filter_option(filter_option.as_ref())
+        .context("解析视频源自定义流过滤设置失败")?;
+    Ok(Some((filter_option, audio_only, credential_id)))
+}
+
+async fn fetch_page_analyzer(
+    bili_client: &BiliClient,
+    video_model: &video::Model,
+    page_model: &page::Model,
+    filter_option: &FilterOption,
+    audio_only_use_low_qn_for_playurl: bool,
+) -> Result<PageAnalyzer> {
+    let (max_qn, min_qn) = crate::bilibili::effective_playurl_qn_range(
+        filter_option.video_max_quality as u32,
+        filter_option.video_min_quality as u32,
+        false,
+        audio_only_use_low_qn_for_playurl,
+    );
+    let bili_video = Video::new(bili_client, video_model.bvid.clone());
+    let page_info = page_info_from_page_model(page_model);
+    match (video_model.source_type, video_model.ep_id.as_deref()) {
+        (Some(1), Some(ep_id)) => {
+            bili_video
+                .get_bangumi_page_analyzer_with_fallback_in_range(&page_info, ep_id, max_qn, min_qn)
+                .await
+        }
+        (_, ep_id) => {
+            bili_video
+                .get_page_analyzer_with_api_fallback_in_range(&page_info, ep_id, max_qn, min_qn)
+                .await
+        }
+    }
+}
+
+async fn mark_quality_checked(connection: &DatabaseConnection, page_id: i32) -> Result<()> {
+    page::ActiveModel {
+        id: Unchanged(page_id),
+        quality_checked_at: Set(Some(now_standard_string())),
+        ..Default::default()
+    }
+    .update(connection)
+    .await
+    .context("写入画质检查时间失败")?;
+    Ok(())
+}
+
+/// 重新下载失败时，把播放缓存还原为旧文件对应的流，避免后续比较与跨源去重误判
+async fn restore_play_stream_cache(connection: &DatabaseConnection, page_model: &page::Model) {
+    let result = page::ActiveModel {
+        id: Unchanged(page_model.id),
+        play_video_streams: Set(page_model.play_video_streams.clone()),
+        play_audio_streams: Set(page_model.play_audio_streams.clone()),
+        play_streams_updated_at: Set(page_model.play_streams_updated_at.clone()),
+        ..Default::default()
+    }
+    .update(connection)
+    .await;
+    if let Err(e) = result {
+        warn!("画质升级：还原播放缓存失败: page_id={}, error={:#}", page_model.id, e);
+    }
+}
+
+async fn refresh_video_total_file_size(connection: &DatabaseConnection, video_id: i32) -> Result<()> {
+    let total = page::Entity::find()
+        .filter(page::Column::VideoId.eq(video_id))
+        .all(connection)
+        .await?
+        .iter()
+        .filter_map(|page| page.file_size_bytes)
+        .sum::<i64>();
+    video::ActiveModel {
+        id: Unchanged(video_id),
+        total_file_size_bytes: Set(Some(total)),
+        ..Default::default()
+    }
+    .update(connection)
+    .await
+    .context("更新视频总文件大小失败")?;
+    Ok(())
+}
+
+/// 对单个分页执行一次画质复查，返回是否完成了升级替换
+async fn upgrade_page_quality(
+    bili_client: &BiliClient,
+    connection: &DatabaseConnection,
+    downloader: &UnifiedDownloader,
+    video_model: &video::Model,
+    page_model: &page::Model,
+    page_path: &Path,
+) -> Result<bool> {
+    let config = crate::config::reload_config();
+    let Some((filter_option, audio_only, credential_id)) =
+        resolve_source_filter_option(connection, video_model, &config).await?
+    else {
+        debug!("画质升级：视频「{}」所属视频源不存在，跳过", video_model.name);
+        return Ok(false);
+    };
+    if audio_only {
+        return Ok(false);
+    }
+
+    let cached_video_streams: Vec<Value> = page_model
+        .play_video_streams
+        .as_deref()
+        .and_then(|raw| serde_json::from_str(raw).ok())
+        .unwrap_or_default();
+    let Some(current_rank) = stream_rank(&cached_video_streams, &filter_option) else {
+        return Ok(false);
+    };
+
+    let bili_client = bili_client.with_credential_id(credential_id);
+    let mut streams = fetch_page_analyzer(
+        &bili_client,
+        video_model,
+        page_model,
+        &filter_option,
+        config.submission_risk_control.audio_only_use_low_qn_for_playurl,
+    )
+    .await?;
+    let (candidate_video_streams, _) = build_download_play_stream_cache(&streams.best_stream(&filter_option)?);
+    let Some(candidate_rank) = stream_rank(&candidate_video_streams, &filter_option) else {
+        return Ok(false);
+    };
+    if !is_strictly_better(candidate_rank, current_rank) {
+        debug!(
+            "画质升级：视频「{}」第{}页暂无更优的流（当前 {:?}，可用 {:?}）",
+            video_model.name, page_model.pid, current_rank, candidate_rank
+        );
+        return Ok(false);
+    }
+
+    info!(
+        "画质升级：视频「{}」第{}页发现更优的流（画质 {} -> {}），开始重新下载",
+        video_model.name, page_model.pid, current_rank.quality, candidate_rank.quality
+    );
+    let upgraded_path = upgrade_temp_path(page_path);
+    let page_info = page_info_from_page_model(page_model);
+    let download_result = download_page_video_from_streams(
+        &mut streams,
+        connection,
+        page_model.id,
+        downloader,
+        video_model,
+        &page_info,
+        &upgraded_path,
+        false,
+        &filter_option,
+    )
+    .await;
+
+    let verified = match download_result {
+        Ok(result) => verify_upgraded_file(page_path, &upgraded_path).await.map(|_| result),
+        Err(e) => Err(e),
+    };
+    let result = match verified {
+        Ok(result) => replace_with_upgraded_file(page_path, &upgraded_path)
+            .await
+            .map(|_| result),
+        Err(e) => Err(e),
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&upgraded_path).await;
+            restore_play_stream_cache(connection, page_model).await;
+            return Err(e);
+        }
+    };
+
+    page::ActiveModel {
+        id: Unchanged(page_model.id),
+        file_size_bytes: Set(result.file_size_bytes),
+        video_stream_size_bytes: Set(result.video_stream_size_bytes),
+        audio_stream_size_bytes: Set(result.audio_stream_size_bytes),
+        ..Default::default()
+    }
+    .update(connection)
+    .await
+    .context("更新升级后的分页文件大小失败")?;
+    refresh_video_total_file_size(connection, video_model.id).await?;
+
+    info!(
+        "画质升级完成：视频「{}」第{}页已替换为更高画质版本: {}",
+        video_model.name,
+        page_model.pid,
+        page_path.display()
+    );
+    Ok(true)
+}
+
+async fn load_candidate_pages(
+    connection: &DatabaseConnection,
+    policy: &QualityUpgradePolicy,
+) -> Result<Vec<(page::Model, video::Model)>> {
+    let now = now_naive();
+    let published_after = now - chrono::Duration::hours(i64::from(policy.window_hours));
+    let checked_before = (now - chrono::Duration::hours(i64::from(policy.check_interval_hours)))
+        .format(STANDARD_TIME_FORMAT)
+        .to_string();
+
+    let rows = page::Entity::find()
+        .find_also_related(video::Entity)
+        .filter(video::Column::Pubtime.gte(published_after))
+        .filter(video::Column::Valid.eq(true))
+        .filter(page::Column::Path.is_not_null())
+        .filter(page::Column::PlayVideoStreams.is_not_null())
+        .filter(
+            Condition::any()
+                .add(page::Column::QualityCheckedAt.is_null())
+                .add(page::Column::QualityCheckedAt.lt(checked_before)),
+        )
+        .order_by_desc(video::Column::Pubtime)
+        .all(connection)
+        .await
+        .context("查询画质升级候选分页失败")?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(page_model, video_model)| Some((page_model, video_model?)))
+        .filter(|(page_model, video_model)| {
+            PageStatus::from(page_model.download_status).get(PAGE_VIDEO_TASK_INDEX) == STATUS_OK
+                && !(video_model.is_charge_video && !video_model.charge_can_play)
+        })
+        .take(policy.max_pages_per_run as usize)
+        .collect())
+}
+
+/// 把最近发布、到了复查时间的已下载分页加入画质升级队列，返回本轮新加入的任务数
+pub async fn enqueue_recent_page_upgrades(connection: &DatabaseConnection) -> Result<usize> {
+    let policy = crate::config::reload_config().quality_upgrade_policy.clone();
+    if !policy.enabled {
+        return Ok(0);
+    }
+
+    let mut enqueued = 0;
+    for (page_model, _) in load_candidate_pages(connection, &policy).await? {
+        let task = crate::task::UpgradePageQualityTask {
+            page_id: page_model.id,
+            task_id: uuid::Uuid::new_v4().to_string(),
+        };
+        if crate::task::enqueue_upgrade_page_quality_task(task, connection).await? {
+            enqueued += 1;
+        }
+    }
+    if enqueued > 0 {
+        debug!("画质升级：本轮加入 {} 个待复查分页", enqueued);
+    }
+    Ok(enqueued)
+}
+
+/// 执行一个分页的画质升级任务，返回是否完成了升级替换；无论结果如何都记录检查时间，
+/// 失败的分页在 `check_interval_hours` 之后才会再次复查
+pub async fn upgrade_page_quality_by_id(
+    bili_client: &BiliClient,
+    connection: &DatabaseConnection,
+    page_id: i32,
+) -> Result<bool> {
+    if !crate::config::reload_config().quality_upgrade_policy.enabled {
+        return Ok(false);
+    }
+    let Some((page_model, Some(video_model))) = page::Entity::find_by_id(page_id)
+        .find_also_related(video::Entity)
+        .one(connection)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let Some(page_path) = page_model.path.as_deref().map(PathBuf::from) else {
+        return Ok(false);
+    };
+    let Some(downloader) = crate::task::TASK_CONTROLLER.get_downloader().await else {
+        bail!("下载器尚未初始化");
+    };
+
+    let result = if tokio::fs::metadata(&page_path).await.is_ok_and(|m| m.is_file()) {
+        upgrade_page_quality(
+            bili_client,
+            connection,
+            &downloader,
+            &video_model,
+            &page_model,
+            &page_path,
+        )
+        .await
+    } else {
+        debug!("画质升级：分页文件不存在，跳过: {}", page_path.display());
+        Ok(false)
+    };
+    mark_quality_checked(connection, page_id).await?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bilibili::VideoCodecs;
+    use serde_json::json;
+
+    #[test]
+    fn test_strictly_better_stream_comparison() {
+        let filter_option = FilterOption {
+            codecs: vec![VideoCodecs::HEV, VideoCodecs::AVC],
+            ..Default::default()
+        };
+        let rank = |quality: u64, codecs: &str| {
+            stream_rank(&[json!({"quality": quality, "codecs": codecs})], &filter_option).unwrap()
+        };
+
+        assert!(is_strictly_better(rank(120, "AVC/H.264"), rank(80, "HEVC/H.265")));
+        assert!(is_strictly_better(rank(80, "HEVC/H.265"), rank(80, "AVC/H.264")));
+        assert!(!is_strictly_better(rank(80, "AVC/H.264"), rank(80, "HEVC/H.265")));
+        assert!(!is_strictly_better(rank(80, "HEVC/H.265"), rank(80, "HEVC/H.265")));
+        // 不在偏好列表中的编码排在最后
+        assert!(is_strictly_better(rank(80, "AVC/H.264"), rank(80, "AV1")));
+        // 混合流无法判断画质，不触发升级
+        assert!(!is_strictly_better(rank(0, "未知"), rank(80, "AVC/H.264")));
+        assert_eq!(stream_rank(&[], &filter_option), None);
+    }
+
+    #[test]
+    fn test_upgraded_file_probe_check() {
+        let probe = |duration_secs: f64, video_streams: usize, audio_streams: usize| MediaProbe {
+            duration_secs,
+            video_streams,
+            audio_streams,
+        };
+        let original = probe(600.0, 1, 1);
+
+        assert!(check_upgraded_probe(&original, &probe(601.5, 1, 1)).is_ok());
+        assert!(check_upgraded_probe(&original, &probe(605.9, 1, 2)).is_ok());
+        // 下载中断导致时长不足、丢失音轨或只有音频都不能替换原文件
+        assert!(check_upgraded_probe(&original, &probe(300.0, 1, 1)).is_err());
+        assert!(check_upgraded_probe(&original, &probe(600.0, 1, 0)).is_err());
+        assert!(check_upgraded_probe(&original, &probe(600.0, 0, 1)).is_err());
+        // 短视频至少允许 2 秒误差
+        assert!(check_upgraded_probe(&probe(30.0, 1, 1), &probe(31.9, 1, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_upgrade_and_backup_paths_stay_in_same_directory() {
+        let page_path = Path::new("/media/up/视频 - S01E01.mp4");
+        assert_eq!(
+            upgrade_temp_path(page_path),
+            PathBuf::from("/media/up/视频 - S01E01.upgrade.mp4")
+        );
+        assert_eq!(backup_path(page_path), PathBuf::from("/media/up/视频 - S01E01.mp4.bak"));
+    }
+
+    #[tokio::test]
+    async fn test_replace_keeps_library_path_present() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let dir = std::env::temp_dir().join(format!("bili-sync-upgrade-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let page_path = dir.join("视频.mp4");
+        std::fs::write(&page_path, b"original").unwrap();
+
+        // 替换过程中另起线程不停检查原路径，任何时刻都不能缺失
+        let done = Arc::new(AtomicBool::new(false));
+        let watcher = {
+            let (done, page_path) = (done.clone(), page_path.clone());
+            std::thread::spawn(move || {
+                let mut missing = 0;
+                while !done.load(Ordering::Relaxed) {
+                    if !page_path.exists() {
+                        missing += 1;
+                    }
+                }
+                missing
+            })
+        };
+        for round in 0..50 {
+            let upgraded_path = upgrade_temp_path(&page_path);
+            std::fs::write(&upgraded_path, format!("upgraded {round}")).unwrap();
+            replace_with_upgraded_file(&page_path, &upgraded_path).await.unwrap();
+            assert!(!upgraded_path.exists());
+            assert!(!backup_path(&page_path).exists());
+        }
+        done.store(true, Ordering::Relaxed);
+        assert_eq!(watcher.join().unwrap(), 0);
+        assert_eq!(std::fs::read_to_string(&page_path).unwrap(), "upgraded 49");
+
+        // 新文件无法就位时原文件保持不变，也不留下备份
+        assert!(replace_with_upgraded_file(&page_path, &dir.join("missing.mp4"))
+            .await
+            .is_err());
+        assert_eq!(std::fs::read_to_string(&page_path).unwrap(), "upgraded 49");
+        assert!(!backup_path(&page_path).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! 本地语音识别字幕工作流。
+//!
+//! 开启 `subtitle_generation` 后，下载完成却没有任何字幕的分页会加入语音识别队列，
+//! 由 whisper 兼容命令行在本地（仅 CPU）识别音轨，生成与B站字幕相同命名方式的
+//! `<文件名>.whisper-<语言>.srt`，媒体服务器会把它识别为一条额外的字幕语言。
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use bili_sync_entity::page;
+use sea_orm::entity::prelude::*;
+use tracing::{debug, warn};
+
+use crate::config::SubtitleGenerationOption;
+
+/// 识别结果的输出前缀，whisper 会在其后追加 `.srt` / `.vtt`
+fn output_prefix(media_path: &Path, lan: &str) -> PathBuf {
+    media_path.with_extension(lan)
+}
+
+fn build_whisper_args(option: &SubtitleGenerationOption, wav_path: &Path, output_prefix: &Path) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec![
+        "-m".into(),
+        option.model_path.trim().into(),
+        "-f".into(),
+        wav_path.into(),
+        "-l".into(),
+        option.language.trim().into(),
+        "-t".into(),
+        option.threads.to_string().into(),
+        "-osrt".into(),
+    ];
+    if option.output_vtt {
+        args.push("-ovtt".into());
+    }
+    args.push("-of".into());
+    args.push(output_prefix.into());
+    args.extend(option.extra_args.iter().map(OsString::from));
+    args
+}
+
+/// 媒体文件旁是否已有任意语言的字幕（B站字幕、AI 字幕或此前生成的识别字幕）
+pub fn has_existing_subtitle(media_path: &Path) -> bool {
+    let (Some(parent), Some(stem)) = (media_path.parent(), media_path.file_stem()) else {
+        return false;
+    };
+    let prefix = format!("{}.", stem.to_string_lossy());
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        let is_subtitle = matches!(
+            path.extension().and_then(OsStr::to_str),
+            Some("srt") | Some("vtt") | Some("ass")
+        );
+        // 弹幕 ASS 使用 `.zh-CN.default.ass`，不算作字幕
+        is_subtitle
+            && !entry.file_name().to_string_lossy().ends_with(".default.ass")
+            && entry.file_name().to_string_lossy().starts_with(&prefix)
+    })
+}
+
+/// 分页下载完成后调用：已开启识别且没有任何字幕时加入语音识别队列
+pub async fn enqueue_if_missing(connection: &DatabaseConnection, page_id: i32, media_path: &Path) -> Result<bool> {
+    if !crate::config::reload_config().subtitle_generation.enabled
+        || !media_path.exists()
+        || has_existing_subtitle(media_path)
+    {
+        return Ok(false);
+    }
+    let task = crate::task::GenerateSubtitleTask {
+        page_id,
+        task_id: uuid::Uuid::new_v4().to_string(),
+    };
+    crate::task::enqueue_generate_subtitle_task(task, connection).await
+}
+
+/// 为分页执行一次语音识别，分页已有字幕或文件不存在时返回 false
+pub async fn generate_subtitle_for_page(connection: &DatabaseConnection, page_id: i32) -> Result<bool> {
+    let option = crate::config::reload_config().subtitle_generation;
+    if !option.enabled {
+        debug!("本地语音识别字幕已关闭，跳过分页ID={}", page_id);
+        return Ok(false);
+    }
+    let Some(page_model) = page::Entity::find_by_id(page_id).one(connection).await? else {
+        return Ok(false);
+    };
+    let Some(media_path) = page_model.path.as_deref().map(Path::new) else {
+        return Ok(false);
+    };
+    if !media_path.exists() || has_existing_subtitle(media_path) {
+        return Ok(false);
+    }
+    generate_page_subtitle(&option, media_path).await?;
+    Ok(true)
+}
+
+async fn generate_page_subtitle(option: &SubtitleGenerationOption, media_path: &Path) -> Result<()> {
+    let wav_path = crate::downloader::extract_speech_audio_with_ffmpeg(media_path).await?;
+    let lan = option.subtitle_lan();
+    let prefix = output_prefix(media_path, &lan);
+    let args = build_whisper_args(option, &wav_path, &prefix);
+    let result = tokio::time::timeout(
+        Duration::from_secs(u64::from(option.timeout_minutes) * 60),
+        tokio::process::Command::new(option.binary_path.trim())
+            .args(&args)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+    if let Err(e) = tokio::fs::remove_file(&wav_path).await {
+        warn!("删除语音识别临时音频 {:?} 失败: {:#}", wav_path, e);
+    }
+
+    let output = result
+        .map_err(|_| anyhow::anyhow!("语音识别超过 {} 分钟未完成", option.timeout_minutes))?
+        .with_context(|| format!("无法启动语音识别程序 {}", option.binary_path))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("语音识别程序执行失败: {}", stderr.trim());
+    }
+    let srt_path = media_path.with_extension(format!("{}.srt", lan));
+    if !srt_path.exists() {
+        bail!("语音识别程序未生成字幕文件 {:?}", srt_path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whisper_args_and_output_naming() {
+        let option = SubtitleGenerationOption {
+            enabled: true,
+            model_path: "/models/ggml-base.bin".to_string(),
+            output_vtt: true,
+            extra_args: vec!["--max-len".to_string(), "40".to_string()],
+            ..Default::default()
+        };
+        assert!(option.validate().is_ok());
+
+        let media = Path::new("/lib/视频/视频 - S01E01.mp4");
+        let prefix = output_prefix(media, &option.subtitle_lan());
+        assert_eq!(prefix, Path::new("/lib/视频/视频 - S01E01.whisper-zh"));
+
+        let args = build_whisper_args(&option, Path::new("/tmp/a.wav"), &prefix);
+        let args: Vec<_> = args.iter().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        assert_eq!(
+            args,
+            [
+                "-m",
+                "/models/ggml-base.bin",
+                "-f",
+                "/tmp/a.wav",
+                "-l",
+                "zh",
+                "-t",
+                "4",
+                "-osrt",
+                "-ovtt",
+                "-of",
+                "/lib/视频/视频 - S01E01.whisper-zh",
+                "--max-len",
+                "40",
+            ]
+        );
+
+        let invalid = SubtitleGenerationOption {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_has_existing_subtitle() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-whisper-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let media = dir.join("视频.mp4");
+        std::fs::write(&media, b"").unwrap();
+        std::fs::write(dir.join("视频.zh-CN.default.ass"), b"").unwrap();
+        std::fs::write(dir.join("其他视频.zh-CN.srt"), b"").unwrap();
+        assert!(!has_existing_subtitle(&media));
+
+        std::fs::write(dir.join("视频.ai-zh.srt"), b"").unwrap();
+        assert!(has_existing_subtitle(&media));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
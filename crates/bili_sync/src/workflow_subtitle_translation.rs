@@ -0,0 +1,577 @@
+//! 字幕翻译工作流。
+//!
+//! 开启 `subtitle_translation` 后，每轮扫描结束时为已下载字幕的分页生成 `target_languages` 中缺少的译文：
+//! 原字幕按条切成批次交给 `ai_rename` 中配置的 AI 服务翻译，时间轴原样保留，写入 `<文件名>.<语言>.srt`。
+//! 每个批次的译文按内容缓存，某个批次失败时整个文件留到下一轮重试，已完成的批次不会重复请求。
+//! 每个分页每种目标语言的处理结果记录在 subtitle_translation 表，已完成或连续失败
+//! [`ai_rename::AI_MAX_ATTEMPTS`] 轮的不再处理。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bili_sync_entity::{page, subtitle_translation, video};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, Func, OnConflict, Query, SimpleExpr};
+use sea_orm::{Condition, QueryOrder, QuerySelect, Set};
+use sha2::{Digest, Sha256};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::config::SubtitleTranslationOption;
+use crate::utils::ai_rename::{self, AiRenameConfig};
+use crate::utils::time_format::now_standard_string;
+
+/// 单次翻译请求允许 AI 返回的最大 token 数
+const TRANSLATION_MAX_TOKENS: u32 = 4096;
+
+/// SRT 中的一条字幕，序号和时间轴保留原始文本
+#[derive(Debug, Clone, PartialEq)]
+struct SrtCue {
+    index: String,
+    timing: String,
+    text: String,
+}
+
+fn parse_srt(content: &str) -> Vec<SrtCue> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+    for block in content.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| line.trim().is_empty());
+        let Some(first) = lines.next() else {
+            continue;
+        };
+        let (index, timing) = if first.contains("-->") {
+            (String::new(), first.trim().to_string())
+        } else {
+            match lines.next() {
+                Some(timing) if timing.contains("-->") => (first.trim().to_string(), timing.trim().to_string()),
+                _ => continue,
+            }
+        };
+        cues.push(SrtCue {
+            index,
+            timing,
+            text: lines.collect::<Vec<_>>().join("\n"),
+        });
+    }
+    cues
+}
+
+fn format_srt(cues: &[SrtCue]) -> String {
+    let mut output = String::new();
+    for (idx, cue) in cues.iter().enumerate() {
+        if cue.index.is_empty() {
+            output.push_str(&(idx + 1).to_string());
+        } else {
+            output.push_str(&cue.index);
+        }
+        output.push('\n');
+        output.push_str(&cue.timing);
+        output.push('\n');
+        output.push_str(&cue.text);
+        output.push_str("\n\n");
+    }
+    output
+}
+
+/// 去掉 `ai-` / `whisper-` 前缀后的语言标记，用于判断原字幕是否已经是目标语言
+fn base_language(lan: &str) -> String {
+    let lan = lan.to_ascii_lowercase();
+    lan.strip_prefix("ai-")
+        .or_else(|| lan.strip_prefix("whisper-"))
+        .unwrap_or(&lan)
+        .to_string()
+}
+
+/// 选择翻译用的原字幕：优先人工字幕，其次B站 AI 字幕，最后本地识别字幕；已有的译文不作为原字幕
+async fn pick_source_subtitle(media_path: &Path, target_languages: &[String]) -> Option<(String, PathBuf)> {
+    let parent = media_path.parent()?;
+    let prefix = format!("{}.", media_path.file_stem()?.to_string_lossy());
+    let mut entries = tokio::fs::read_dir(parent).await.ok()?;
+    let mut candidates: Vec<(u8, String, PathBuf)> = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(lan) = name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".srt")) else {
+            continue;
+        };
+        if lan.is_empty() || lan.contains('.') {
+            continue;
+        }
+        if target_languages.iter().any(|target| target.eq_ignore_ascii_case(lan)) {
+            continue;
+        }
+        let rank = if lan.starts_with("ai-") {
+            1
+        } else if lan.starts_with("whisper-") {
+            2
+        } else {
+            0
+        };
+        candidates.push((rank, lan.to_string(), entry.path()));
+    }
+    candidates.sort();
+    candidates.into_iter().next().map(|(_, lan, path)| (lan, path))
+}
+
+/// 读取分页原字幕（与翻译选用同一份）的逐条文本，供 AI 摘要等功能复用
+pub async fn read_source_subtitle_lines(media_path: &Path, excluded_languages: &[String]) -> Option<Vec<String>> {
+    let (_, source_path) = pick_source_subtitle(media_path, excluded_languages).await?;
+    let content = tokio::fs::read_to_string(source_path).await.ok()?;
+    let lines: Vec<String> = parse_srt(&content)
+        .into_iter()
+        .map(|cue| cue.text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect();
+    (!lines.is_empty()).then_some(lines)
+}
+
+fn build_translation_prompt(target_language: &str, texts: &[&str]) -> String {
+    format!(
+        "请把下面 JSON 数组中的 {} 条视频字幕逐条翻译为语言代码 {} 对应的语言。\n\
+        【要求】结合上下文保持语义连贯；每条字幕单独翻译，不要合并或拆分，条数必须与原文一致；\
+        保留原文中的换行；人名、专有名词可保留原文。\n\
+        严格按照 JSON 数组格式返回译文，不要解释：\n\
+        [\"译文1\", \"译文2\", ...]\n\n\
+        原文：\n{}",
+        texts.len(),
+        target_language,
+        serde_json::to_string(texts).unwrap_or_default()
+    )
+}
+
+fn parse_translation_response(response: &str, expected_count: usize) -> Result<Vec<String>> {
+    let response = response.trim();
+    let json_str = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response,
+    };
+    let texts: Vec<String> = serde_json::from_str(json_str)
+        .map_err(|e| anyhow!("解析译文 JSON 数组失败: {} - 原始响应: {}", e, response))?;
+    if texts.len() != expected_count {
+        bail!("AI 返回 {} 条译文，期望 {} 条", texts.len(), expected_count);
+    }
+    Ok(texts.into_iter().map(|text| text.replace('\r', "")).collect())
+}
+
+fn batch_cache_key(cfg: &AiRenameConfig, target_language: &str, texts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(cfg.provider.as_bytes());
+    hasher.update([0]);
+    hasher.update(cfg.model.as_bytes());
+    hasher.update([0]);
+    hasher.update(target_language.as_bytes());
+    hasher.update([0]);
+    hasher.update(serde_json::to_string(texts).unwrap_or_default().as_bytes());
+    format!("subtitle-translation:{}", hex::encode(hasher.finalize()))
+}
+
+async fn translate_batch(
+    connection: &DatabaseConnection,
+    cfg: &AiRenameConfig,
+    target_language: &str,
+    texts: &[&str],
+) -> Result<Vec<String>> {
+    let cache_key = batch_cache_key(cfg, target_language, texts);
+    if let Some(cached) = ai_rename::load_cached_ai_result(connection, &cache_key).await {
+        if let Ok(translated) = serde_json::from_str::<Vec<String>>(&cached) {
+            if translated.len() == texts.len() {
+                return Ok(translated);
+            }
+        }
+    }
+
+    let response = ai_rename::ai_complete(
+        cfg,
+        &format!("subtitle-translation-{}", target_language),
+        "你是一个专业的视频字幕翻译助手。只返回 JSON 数组格式的译文，不要解释。",
+        &build_translation_prompt(target_language, texts),
+        TRANSLATION_MAX_TOKENS,
+    )
+    .await?;
+    let translated = parse_translation_response(&response, texts.len())?;
+    if let Err(e) = ai_rename::save_cached_ai_result(connection, &cache_key, &serde_json::to_string(&translated)?).await
+    {
+        warn!("保存字幕翻译缓存失败: {:#}", e);
+    }
+    Ok(translated)
+}
+
+/// 把原字幕翻译为目标语言并写入 `<文件名>.<语言>.srt`
+async fn translate_subtitle_file(
+    connection: &DatabaseConnection,
+    cfg: &AiRenameConfig,
+    option: &SubtitleTranslationOption,
+    source_path: &Path,
+    target_language: &str,
+    output_path: &Path,
+) -> Result<()> {
+    let content = tokio::fs::read_to_string(source_path)
+        .await
+        .with_context(|| format!("读取字幕 {:?} 失败", source_path))?;
+    let mut cues = parse_srt(&content);
+    if cues.is_empty() {
+        bail!("字幕 {:?} 中没有可翻译的内容", source_path);
+    }
+
+    for batch in cues.chunks_mut(option.batch_size.max(1)) {
+        let texts: Vec<&str> = batch.iter().map(|cue| cue.text.as_str()).collect();
+        let translated = translate_batch(connection, cfg, target_language, &texts).await?;
+        for (cue, text) in batch.iter_mut().zip(translated) {
+            cue.text = text;
+        }
+    }
+
+    let mut tmp_path = output_path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    tokio::fs::write(&tmp_path, format_srt(&cues)).await?;
+    tokio::fs::rename(&tmp_path, output_path)
+        .await
+        .with_context(|| format!("重命名字幕文件 {:?} -> {:?} 失败", tmp_path, output_path))?;
+    Ok(())
+}
+
+/// 译文已完成或连续失败次数已达上限，不再处理
+fn is_settled(state: &subtitle_translation::Model) -> bool {
+    state.completed_at.is_some() || state.failed_attempts >= ai_rename::AI_MAX_ATTEMPTS
+}
+
+/// 所有目标语言都已不再处理的分页，与 [`is_settled`] 的判断一致，用于在数据库中预先排除
+fn settled_pages_condition(target_languages: &[String]) -> SimpleExpr {
+    page::Column::Id.in_subquery(
+        Query::select()
+            .column(subtitle_translation::Column::PageId)
+            .from(subtitle_translation::Entity)
+            .and_where(subtitle_translation::Column::TargetLanguage.is_in(target_languages.iter().cloned()))
+            .cond_where(
+                Condition::any()
+                    .add(subtitle_translation::Column::CompletedAt.is_not_null())
+                    .add(Expr::cust(format!(
+                        "{} >= {}",
+                        crate::database::dialect::current().unsigned_column("failed_attempts"),
+                        ai_rename::AI_MAX_ATTEMPTS
+                    ))),
+            )
+            .group_by_col(subtitle_translation::Column::PageId)
+            .and_having(
+                Expr::expr(Func::count(Expr::col(subtitle_translation::Column::TargetLanguage)))
+                    .gte(target_languages.len() as i64),
+            )
+            .to_owned(),
+    )
+}
+
+async fn record_completed(connection: &DatabaseConnection, page_id: i32, target_language: &str) -> Result<()> {
+    let now = now_standard_string();
+    subtitle_translation::Entity::insert(subtitle_translation::ActiveModel {
+        page_id: Set(page_id),
+        target_language: Set(target_language.to_string()),
+        completed_at: Set(Some(now.clone())),
+        failed_attempts: Set(0),
+        last_error: Set(None),
+        updated_at: Set(now),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            subtitle_translation::Column::PageId,
+            subtitle_translation::Column::TargetLanguage,
+        ])
+        .update_columns([
+            subtitle_translation::Column::CompletedAt,
+            subtitle_translation::Column::FailedAttempts,
+            subtitle_translation::Column::LastError,
+            subtitle_translation::Column::UpdatedAt,
+        ])
+        .to_owned(),
+    )
+    .exec(connection)
+    .await?;
+    Ok(())
+}
+
+async fn record_failed(
+    connection: &DatabaseConnection,
+    page_id: i32,
+    target_language: &str,
+    failed_attempts: u32,
+    error: &anyhow::Error,
+) -> Result<()> {
+    subtitle_translation::Entity::insert(subtitle_translation::ActiveModel {
+        page_id: Set(page_id),
+        target_language: Set(target_language.to_string()),
+        completed_at: Set(None),
+        failed_attempts: Set(failed_attempts),
+        last_error: Set(Some(format!("{:#}", error))),
+        updated_at: Set(now_standard_string()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            subtitle_translation::Column::PageId,
+            subtitle_translation::Column::TargetLanguage,
+        ])
+        .update_columns([
+            subtitle_translation::Column::FailedAttempts,
+            subtitle_translation::Column::LastError,
+            subtitle_translation::Column::UpdatedAt,
+        ])
+        .to_owned(),
+    )
+    .exec(connection)
+    .await?;
+    Ok(())
+}
+
+/// 已下载且还有目标语言需要处理的分页
+async fn load_candidate_pages(
+    connection: &DatabaseConnection,
+    target_languages: &[String],
+) -> Result<Vec<(i32, Option<String>)>> {
+    Ok(page::Entity::find()
+        .select_only()
+        .columns([page::Column::Id, page::Column::Path])
+        .inner_join(video::Entity)
+        .filter(video::Column::Valid.eq(true))
+        .filter(video::Column::Deleted.eq(0))
+        .filter(page::Column::Path.is_not_null())
+        .filter(settled_pages_condition(target_languages).not())
+        .order_by_desc(page::Column::Id)
+        .into_tuple()
+        .all(connection)
+        .await?)
+}
+
+/// 为已下载字幕的分页生成缺少的译文字幕，返回本轮生成的字幕数
+pub async fn translate_pending_subtitles(connection: &DatabaseConnection, token: CancellationToken) -> Result<usize> {
+    let config = crate::config::reload_config();
+    let option = &config.subtitle_translation;
+    if !option.enabled || option.target_languages.is_empty() {
+        return Ok(0);
+    }
+
+    let pages = load_candidate_pages(connection, &option.target_languages).await?;
+
+    let mut translated = 0usize;
+    let mut failed = 0usize;
+    'pages: for (page_id, path) in pages {
+        let Some(media_path) = path.as_deref().filter(|path| !path.is_empty()).map(Path::new) else {
+            continue;
+        };
+        let states: HashMap<String, subtitle_translation::Model> = subtitle_translation::Entity::find()
+            .filter(subtitle_translation::Column::PageId.eq(page_id))
+            .all(connection)
+            .await?
+            .into_iter()
+            .map(|state| (state.target_language.clone(), state))
+            .collect();
+        let mut missing: Vec<(&String, PathBuf, u32)> = Vec::new();
+        for target_language in &option.target_languages {
+            let state = states.get(target_language);
+            if state.is_some_and(is_settled) {
+                continue;
+            }
+            let output_path = media_path.with_extension(format!("{}.srt", target_language));
+            if tokio::fs::try_exists(&output_path).await.unwrap_or(false) {
+                record_completed(connection, page_id, target_language).await?;
+                continue;
+            }
+            missing.push((
+                target_language,
+                output_path,
+                state.map_or(0, |state| state.failed_attempts),
+            ));
+        }
+        if missing.is_empty() {
+            continue;
+        }
+        let Some((source_lan, source_path)) = pick_source_subtitle(media_path, &option.target_languages).await else {
+            continue;
+        };
+
+        for (target_language, output_path, failed_attempts) in missing {
+            if token.is_cancelled() || translated + failed >= option.max_files_per_run {
+                break 'pages;
+            }
+            if base_language(&source_lan) == base_language(target_language) {
+                record_completed(connection, page_id, target_language).await?;
+                continue;
+            }
+            match translate_subtitle_file(
+                connection,
+                &config.ai_rename,
+                option,
+                &source_path,
+                target_language,
+                &output_path,
+            )
+            .await
+            {
+                Ok(()) => {
+                    debug!(
+                        "字幕翻译完成: 分页ID={}, {} -> {}",
+                        page_id, source_lan, target_language
+                    );
+                    record_completed(connection, page_id, target_language).await?;
+                    translated += 1;
+                }
+                Err(e) => {
+                    let failed_attempts = failed_attempts + 1;
+                    if failed_attempts >= ai_rename::AI_MAX_ATTEMPTS {
+                        warn!(
+                            "字幕翻译连续失败 {} 轮，不再重试: 分页ID={}, {} -> {}: {:#}",
+                            failed_attempts, page_id, source_lan, target_language, e
+                        );
+                    } else {
+                        warn!(
+                            "字幕翻译失败，下一轮扫描重试: 分页ID={}, {} -> {}: {:#}",
+                            page_id, source_lan, target_language, e
+                        );
+                    }
+                    record_failed(connection, page_id, target_language, failed_attempts, &e).await?;
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    if translated > 0 || failed > 0 {
+        info!("字幕翻译完成 {} 个，失败 {} 个", translated, failed);
+    }
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use bili_sync_migration::{Migrator, MigratorTrait};
+    use sea_orm::sqlx::sqlite::SqlitePoolOptions;
+    use sea_orm::{ActiveModelTrait, ConnectionTrait, DbBackend, IntoActiveModel, SqlxSqliteConnector, Statement};
+
+    use super::*;
+
+    #[test]
+    fn test_srt_round_trip_preserves_timing() {
+        let content = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\n你好\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\n第一行\r\n第二行\r\n\r\n\r\n";
+        let mut cues = parse_srt(content);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[1].timing, "00:00:03,000 --> 00:00:04,000");
+        assert_eq!(cues[1].text, "第一行\n第二行");
+
+        let translated = parse_translation_response("好的：\n[\"Hello\", \"Line one\\nLine two\"]", 2).unwrap();
+        for (cue, text) in cues.iter_mut().zip(translated) {
+            cue.text = text;
+        }
+        assert_eq!(
+            format_srt(&cues),
+            "1\n00:00:01,000 --> 00:00:02,500\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nLine one\nLine two\n\n"
+        );
+        assert!(parse_translation_response("[\"Hello\"]", 2).is_err());
+
+        let option = SubtitleTranslationOption {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(option.validate().is_ok());
+        let invalid = SubtitleTranslationOption {
+            enabled: true,
+            target_languages: vec!["en/../x".to_string()],
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_failed_translations_settle_after_retry_limit() {
+        let mut state = subtitle_translation::Model {
+            page_id: 1,
+            target_language: "en".to_string(),
+            completed_at: None,
+            failed_attempts: ai_rename::AI_MAX_ATTEMPTS - 1,
+            last_error: Some("timeout".to_string()),
+            updated_at: "2026-10-19 08:00:00".to_string(),
+        };
+        assert!(!is_settled(&state));
+        state.failed_attempts += 1;
+        assert!(is_settled(&state));
+        state.failed_attempts = 0;
+        state.completed_at = Some("2026-10-19 09:00:00".to_string());
+        assert!(is_settled(&state));
+    }
+
+    #[tokio::test]
+    async fn test_settled_pages_are_excluded_from_candidates() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let db = SqlxSqliteConnector::from_sqlx_sqlite_pool(pool);
+        Migrator::up(&db, None).await.unwrap();
+        db.execute(Statement::from_string(
+            DbBackend::Sqlite,
+            "ALTER TABLE page ADD COLUMN ai_renamed INTEGER",
+        ))
+        .await
+        .ok();
+        video::Model {
+            id: 1,
+            valid: true,
+            ..Default::default()
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&db)
+        .await
+        .unwrap();
+        for page_id in 1..=3 {
+            page::Model {
+                id: page_id,
+                video_id: 1,
+                pid: page_id,
+                path: Some(format!("/media/P{page_id}.mp4")),
+                ..Default::default()
+            }
+            .into_active_model()
+            .reset_all()
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+        // 1: 两种语言都已完成或放弃；2: 还有一种语言未处理；3: 失败但未达到上限
+        let error = anyhow!("timeout");
+        record_completed(&db, 1, "en").await.unwrap();
+        record_failed(&db, 1, "ja", ai_rename::AI_MAX_ATTEMPTS, &error)
+            .await
+            .unwrap();
+        record_completed(&db, 2, "en").await.unwrap();
+        record_failed(&db, 3, "en", 1, &error).await.unwrap();
+        record_completed(&db, 3, "ja").await.unwrap();
+
+        let targets = vec!["en".to_string(), "ja".to_string()];
+        let candidates: Vec<i32> = load_candidate_pages(&db, &targets)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(page_id, _)| page_id)
+            .collect();
+        assert_eq!(candidates, vec![3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_pick_source_subtitle_prefers_manual_subtitles() {
+        let dir = std::env::temp_dir().join(format!("bili-sync-translate-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let media = dir.join("视频.mp4");
+        let targets = vec!["en".to_string()];
+        std::fs::write(dir.join("视频.en.srt"), b"").unwrap();
+        std::fs::write(dir.join("视频.whisper-zh.srt"), b"").unwrap();
+        assert_eq!(pick_source_subtitle(&media, &targets).await.unwrap().0, "whisper-zh");
+
+        std::fs::write(dir.join("视频.ai-zh.srt"), b"").unwrap();
+        assert_eq!(pick_source_subtitle(&media, &targets).await.unwrap().0, "ai-zh");
+
+        std::fs::write(dir.join("视频.zh-CN.srt"), b"").unwrap();
+        assert_eq!(pick_source_subtitle(&media, &targets).await.unwrap().0, "zh-CN");
+        assert_eq!(base_language("ai-zh"), base_language("whisper-zh"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
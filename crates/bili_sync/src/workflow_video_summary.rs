@@ -0,0 +1,433 @@
+//! AI 视频摘要工作流。
+//!
+//! 开启 `video_summary` 后，每轮扫描结束时在后台为已下载的视频生成简短摘要和标签，生成完再发送新视频推送：
+//! 优先使用分页字幕，没有字幕时使用视频简介，通过 `ai_rename` 中配置的 AI 服务生成。
+//! 结果保存在 video_summary 表，写入各分页 NFO 的 `<plot>` / `<tag>`，本轮新视频的摘要会附在推送中。
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bili_sync_entity::{page, video, video_summary};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::{QueryOrder, QuerySelect, Set};
+use serde::Deserialize;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::config::{Config, VideoSummaryOption};
+use crate::utils::ai_rename::{self, AiRenameContext};
+use crate::utils::notification::ScanSummary;
+use crate::utils::time_format::now_standard_string;
+
+/// 摘要依据字幕生成
+const SOURCE_SUBTITLE: &str = "subtitle";
+/// 摘要依据视频简介生成
+const SOURCE_DESCRIPTION: &str = "description";
+/// 既没有字幕也没有简介，记录下来避免每轮重复检查
+const SOURCE_NONE: &str = "none";
+
+/// 各轮扫描的摘要任务依次执行，避免重复请求 AI，推送顺序也与扫描顺序一致
+static SUMMARY_JOB_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedSummary {
+    pub summary: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct SummaryResponse {
+    summary: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+/// 拼接各分页的字幕文本，连续重复的字幕只保留一条，超出长度时截断
+async fn collect_subtitle_text(
+    pages: &[page::Model],
+    excluded_languages: &[String],
+    max_chars: usize,
+) -> Option<String> {
+    let mut text = String::new();
+    for page_model in pages {
+        let Some(media_path) = page_model
+            .path
+            .as_deref()
+            .filter(|path| !path.is_empty())
+            .map(Path::new)
+        else {
+            continue;
+        };
+        let Some(mut lines) =
+            crate::workflow_subtitle_translation::read_source_subtitle_lines(media_path, excluded_languages).await
+        else {
+            continue;
+        };
+        lines.dedup();
+        if pages.len() > 1 {
+            text.push_str(&format!("P{} {}:\n", page_model.pid, page_model.name));
+        }
+        text.push_str(&lines.join("\n"));
+        text.push('\n');
+        if text.chars().count() >= max_chars {
+            break;
+        }
+    }
+    let text = text.trim();
+    (!text.is_empty()).then(|| truncate_chars(text, max_chars))
+}
+
+fn build_summary_prompt(video_info: &str, content_kind: &str, content: &str, option: &VideoSummaryOption) -> String {
+    format!(
+        "请根据下面的视频信息和{}，用中文为视频写一段不超过 {} 字的内容摘要，并给出不超过 {} 个内容标签。\n\
+        【要求】摘要客观概括视频讲了什么，不要复述标题，不使用表情；标签为简短的名词或短语，不带 # 号。\n\
+        严格按照 JSON 对象格式返回，不要解释：\n\
+        {{\"summary\": \"摘要\", \"tags\": [\"标签1\", \"标签2\"]}}\n\n\
+        视频信息：{}\n\n\
+        {}：\n{}",
+        content_kind, option.max_summary_chars, option.max_tags, video_info, content_kind, content
+    )
+}
+
+fn parse_summary_response(response: &str, option: &VideoSummaryOption) -> Result<GeneratedSummary> {
+    let response = response.trim();
+    let json_str = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => response,
+    };
+    let parsed: SummaryResponse =
+        serde_json::from_str(json_str).map_err(|e| anyhow!("解析摘要 JSON 失败: {} - 原始响应: {}", e, response))?;
+    let summary = truncate_chars(parsed.summary.trim(), option.max_summary_chars);
+    if summary.is_empty() {
+        bail!("AI 返回的摘要为空");
+    }
+
+    let mut seen = HashSet::new();
+    let tags = parsed
+        .tags
+        .iter()
+        .map(|tag| truncate_chars(tag.trim().trim_start_matches('#').trim(), 20))
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.clone()))
+        .take(option.max_tags)
+        .collect();
+    Ok(GeneratedSummary { summary, tags })
+}
+
+fn summary_from_model(model: &video_summary::Model) -> Option<GeneratedSummary> {
+    if model.summary.is_empty() {
+        return None;
+    }
+    Some(GeneratedSummary {
+        summary: model.summary.clone(),
+        tags: serde_json::from_str(&model.tags).unwrap_or_default(),
+    })
+}
+
+async fn save_summary(
+    connection: &DatabaseConnection,
+    video_id: i32,
+    summary: &str,
+    tags: &[String],
+    source: &str,
+) -> Result<()> {
+    let now = now_standard_string();
+    video_summary::Entity::insert(video_summary::ActiveModel {
+        video_id: Set(video_id),
+        summary: Set(summary.to_string()),
+        tags: Set(serde_json::to_string(tags)?),
+        source: Set(source.to_string()),
+        created_at: Set(now.clone()),
+        updated_at: Set(now),
+    })
+    .on_conflict(
+        OnConflict::column(video_summary::Column::VideoId)
+            .update_columns([
+                video_summary::Column::Summary,
+                video_summary::Column::Tags,
+                video_summary::Column::Source,
+                video_summary::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(connection)
+    .await?;
+    Ok(())
+}
+
+async fn apply_summary_to_nfo(nfo_path: &Path, summary: &GeneratedSummary) -> Result<()> {
+    let Ok(xml) = tokio::fs::read_to_string(nfo_path).await else {
+        return Ok(());
+    };
+    tokio::fs::write(
+        nfo_path,
+        crate::utils::nfo::apply_nfo_summary(&xml, &summary.summary, &summary.tags),
+    )
+    .await
+    .with_context(|| format!("写入 NFO 摘要失败: {}", nfo_path.display()))
+}
+
+/// 重新生成分页 NFO 后调用：视频已有 AI 摘要时重新写入，避免刷新 NFO 时丢失
+pub async fn reapply_video_summary(connection: &DatabaseConnection, video_id: i32, nfo_path: &Path) -> Result<()> {
+    let Some(model) = video_summary::Entity::find_by_id(video_id).one(connection).await? else {
+        return Ok(());
+    };
+    match summary_from_model(&model) {
+        Some(summary) => apply_summary_to_nfo(nfo_path, &summary).await,
+        None => Ok(()),
+    }
+}
+
+/// 为单个视频生成摘要，没有可用的字幕和简介时返回 None
+async fn summarize_video(
+    connection: &DatabaseConnection,
+    config: &Config,
+    video_model: &video::Model,
+    pages: &[page::Model],
+) -> Result<Option<GeneratedSummary>> {
+    let option = &config.video_summary;
+    let excluded_languages = if config.subtitle_translation.enabled {
+        config.subtitle_translation.target_languages.clone()
+    } else {
+        Vec::new()
+    };
+    let intro = video_model.intro.trim();
+    let (source, content_kind, content) =
+        match collect_subtitle_text(pages, &excluded_languages, option.max_input_chars).await {
+            Some(text) => (SOURCE_SUBTITLE, "字幕", text),
+            None if !intro.is_empty() && intro != "-" => (
+                SOURCE_DESCRIPTION,
+                "视频简介",
+                truncate_chars(intro, option.max_input_chars),
+            ),
+            None => {
+                save_summary(connection, video_model.id, "", &[], SOURCE_NONE).await?;
+                return Ok(None);
+            }
+        };
+
+    let ctx = AiRenameContext {
+        title: video_model.name.clone(),
+        // 依据简介生成时简介已在正文中，不再重复
+        desc: if source == SOURCE_SUBTITLE {
+            video_model.intro.clone()
+        } else {
+            String::new()
+        },
+        owner: video_model.upper_name.clone(),
+        duration: pages.iter().map(|page_model| page_model.duration).sum(),
+        pubdate: video_model.pubtime.format("%Y-%m-%d").to_string(),
+        bvid: video_model.bvid.clone(),
+        ..Default::default()
+    };
+    let response = ai_rename::ai_complete(
+        &config.ai_rename,
+        "video-summary",
+        "你是一个视频内容摘要助手。只返回 JSON 对象，不要解释。",
+        &build_summary_prompt(&ctx.to_json_string().replace('\n', " "), content_kind, &content, option),
+        1024,
+    )
+    .await?;
+    let summary = parse_summary_response(&response, option)?;
+    save_summary(connection, video_model.id, &summary.summary, &summary.tags, source).await?;
+
+    for page_model in pages {
+        if let Some(path) = page_model.path.as_deref().filter(|path| !path.is_empty()) {
+            if let Err(e) = apply_summary_to_nfo(&Path::new(path).with_extension("nfo"), &summary).await {
+                warn!(
+                    "写入视频「{}」分页 pid={} 的 NFO 摘要失败: {:#}",
+                    video_model.name, page_model.pid, e
+                );
+            }
+        }
+    }
+    Ok(Some(summary))
+}
+
+/// 为已下载的视频生成摘要：优先处理 `priority_video_ids`（本轮新视频），剩余额度补齐历史视频。
+/// 返回 `priority_video_ids` 中已有摘要的视频，供新视频推送使用
+pub async fn summarize_pending_videos(
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+    priority_video_ids: &[i32],
+) -> Result<HashMap<i32, GeneratedSummary>> {
+    let config = crate::config::reload_config();
+    let option = &config.video_summary;
+    let mut summaries = HashMap::new();
+    if !option.enabled {
+        return Ok(summaries);
+    }
+
+    let existing: HashMap<i32, video_summary::Model> = video_summary::Entity::find()
+        .filter(video_summary::Column::VideoId.is_in(priority_video_ids.iter().copied()))
+        .all(connection)
+        .await?
+        .into_iter()
+        .map(|model| (model.video_id, model))
+        .collect();
+    for (video_id, model) in &existing {
+        if let Some(summary) = summary_from_model(model) {
+            summaries.insert(*video_id, summary);
+        }
+    }
+
+    let backlog: Vec<i32> = video::Entity::find()
+        .select_only()
+        .column(video::Column::Id)
+        .filter(video::Column::Valid.eq(true))
+        .filter(video::Column::Deleted.eq(0))
+        .filter(
+            video::Column::Id.not_in_subquery(
+                Query::select()
+                    .column(video_summary::Column::VideoId)
+                    .from(video_summary::Entity)
+                    .to_owned(),
+            ),
+        )
+        .order_by_desc(video::Column::Id)
+        .limit((option.max_videos_per_run * 5) as u64)
+        .into_tuple()
+        .all(connection)
+        .await?;
+
+    let mut seen = HashSet::new();
+    let candidates = priority_video_ids
+        .iter()
+        .copied()
+        .filter(|video_id| !existing.contains_key(video_id))
+        .chain(backlog)
+        .filter(|video_id| seen.insert(*video_id));
+
+    let mut generated = 0usize;
+    let mut failed = 0usize;
+    for video_id in candidates {
+        if token.is_cancelled() || generated + failed >= option.max_videos_per_run {
+            break;
+        }
+        let Some((video_model, mut pages)) = video::Entity::find_by_id(video_id)
+            .find_with_related(page::Entity)
+            .all(connection)
+            .await?
+            .into_iter()
+            .next()
+        else {
+            continue;
+        };
+        // 分页尚未下载完成时留到之后处理
+        let mut downloaded = !pages.is_empty();
+        for page_model in &pages {
+            let Some(path) = page_model.path.as_deref() else {
+                downloaded = false;
+                break;
+            };
+            if !tokio::fs::try_exists(path).await.unwrap_or(false) {
+                downloaded = false;
+                break;
+            }
+        }
+        if !downloaded {
+            continue;
+        }
+        pages.sort_by_key(|page_model| page_model.pid);
+
+        match summarize_video(connection, &config, &video_model, &pages).await {
+            Ok(Some(summary)) => {
+                debug!("生成视频「{}」的 AI 摘要: {}", video_model.name, summary.summary);
+                generated += 1;
+                summaries.insert(video_id, summary);
+            }
+            Ok(None) => debug!("视频「{}」没有字幕和简介，跳过 AI 摘要", video_model.name),
+            Err(e) => {
+                warn!(
+                    "生成视频「{}」的 AI 摘要失败，下一轮扫描重试: {:#}",
+                    video_model.name, e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if generated > 0 || failed > 0 {
+        info!("AI 视频摘要生成 {} 个，失败 {} 个", generated, failed);
+    }
+    summaries.retain(|video_id, _| priority_video_ids.contains(video_id));
+    Ok(summaries)
+}
+
+/// 为本轮扫描的新视频生成摘要，并附到扫描推送中
+async fn attach_summaries_to_scan(
+    connection: &DatabaseConnection,
+    token: CancellationToken,
+    scan_summary: &mut ScanSummary,
+) {
+    let new_video_ids: Vec<i32> = scan_summary
+        .source_results
+        .iter()
+        .flat_map(|result| result.new_videos.iter().filter_map(|video| video.video_id))
+        .collect();
+    let summaries = match summarize_pending_videos(connection, token, &new_video_ids).await {
+        Ok(summaries) => summaries,
+        Err(e) => {
+            warn!("生成 AI 视频摘要失败: {:#}", e);
+            return;
+        }
+    };
+    for new_video in scan_summary
+        .source_results
+        .iter_mut()
+        .flat_map(|result| result.new_videos.iter_mut())
+    {
+        if let Some(summary) = new_video.video_id.and_then(|video_id| summaries.get(&video_id)) {
+            new_video.ai_summary = Some(summary.summary.clone());
+            new_video.ai_tags = summary.tags.clone();
+        }
+    }
+}
+
+/// 在后台为本轮新视频生成摘要后发送扫描推送，不阻塞扫描循环
+pub fn spawn_scan_notification(
+    connection: Arc<DatabaseConnection>,
+    token: CancellationToken,
+    mut scan_summary: ScanSummary,
+) {
+    tokio::spawn(async move {
+        let _guard = SUMMARY_JOB_LOCK.lock().await;
+        attach_summaries_to_scan(&connection, token, &mut scan_summary).await;
+        if let Err(e) = crate::utils::notification::send_scan_notification(scan_summary).await {
+            warn!("发送扫描完成推送失败: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_summary_response() {
+        let option = VideoSummaryOption {
+            enabled: true,
+            max_summary_chars: 20,
+            max_tags: 2,
+            ..Default::default()
+        };
+        assert!(option.validate().is_ok());
+
+        let parsed = parse_summary_response(
+            "```json\n{\"summary\": \"  介绍如何在家里用面粉和酵母烤出松软的面包，并演示了整形和发酵。 \", \"tags\": [\"#烘焙\", \"面包\", \"烘焙\", \"美食\"]}\n```",
+            &option,
+        )
+        .unwrap();
+        assert_eq!(parsed.summary, "介绍如何在家里用面粉和酵母烤出松软的面包");
+        assert_eq!(parsed.tags, vec!["烘焙", "面包"]);
+
+        assert!(parse_summary_response("{\"summary\": \" \", \"tags\": []}", &option).is_err());
+        assert!(parse_summary_response("无法生成", &option).is_err());
+    }
+}
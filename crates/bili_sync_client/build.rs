@@ -0,0 +1,318 @@
+//! 根据 openapi.json 生成模型与接口方法
+//!
+//! 只支持 bili_sync v1 接口用到的 OpenAPI 子集：对象（含 `#[serde(flatten)]` 产生的 `allOf`）、字符串枚举、
+//! 数组、可空类型与 `$ref`。
+//! 响应统一包装在 `ApiResponse_*` 中，按 utoipa 的命名规则还原为数据类型，`Page_*` 对应泛型 `Page<T>`。
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=openapi.json");
+
+    let spec: Value = serde_json::from_str(&fs::read_to_string("openapi.json").expect("读取 openapi.json 失败"))
+        .expect("openapi.json 格式无效");
+    let mut out = String::from("// 由 build.rs 根据 openapi.json 生成，请勿手动修改\n\n");
+    generate_models(&spec, &mut out);
+    generate_operations(&spec, &mut out);
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("generated.rs");
+    fs::write(dest, out).expect("写入生成代码失败");
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(['_', '-', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().unwrap().to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+fn ref_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap()
+}
+
+/// 由 `ApiResponse_Page_Video` 这样的组件名还原数据类型
+fn data_type(schema_name: &str) -> String {
+    let name = schema_name.strip_prefix("ApiResponse_").unwrap_or(schema_name);
+    match name.strip_prefix("Page_") {
+        Some(inner) => format!("Page<{}>", data_type(inner)),
+        None => name.to_string(),
+    }
+}
+
+fn doc_comment(schema: &Value, indent: &str, out: &mut String) {
+    for key in ["summary", "description"] {
+        if let Some(text) = schema.get(key).and_then(Value::as_str).filter(|text| !text.is_empty()) {
+            for line in text.lines() {
+                writeln!(out, "{indent}/// {line}").unwrap();
+            }
+            return;
+        }
+    }
+}
+
+/// 返回 Rust 类型以及是否可空
+fn rust_type(schema: &Value) -> (String, bool) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return (data_type(ref_name(reference)), false);
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        let mut nullable = false;
+        let mut inner = None;
+        for variant in variants {
+            if variant.get("type").and_then(Value::as_str) == Some("null") {
+                nullable = true;
+            } else {
+                inner = Some(rust_type(variant).0);
+            }
+        }
+        return (inner.unwrap_or_else(|| "serde_json::Value".to_string()), nullable);
+    }
+
+    let (ty, nullable) = match schema.get("type") {
+        Some(Value::String(ty)) => (ty.as_str(), false),
+        Some(Value::Array(types)) => {
+            let ty = types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|ty| *ty != "null")
+                .unwrap_or("object");
+            (ty, types.iter().any(|ty| ty == "null"))
+        }
+        _ => ("object", false),
+    };
+    let unsigned = schema
+        .get("minimum")
+        .and_then(Value::as_f64)
+        .is_some_and(|min| min >= 0.0);
+    let rust = match (ty, schema.get("format").and_then(Value::as_str)) {
+        ("string", _) => "String".to_string(),
+        ("boolean", _) => "bool".to_string(),
+        ("integer", Some("int64")) if unsigned => "u64".to_string(),
+        ("integer", Some("int64")) => "i64".to_string(),
+        ("integer", _) if unsigned => "u32".to_string(),
+        ("integer", _) => "i32".to_string(),
+        ("number", _) => "f64".to_string(),
+        ("array", _) => format!("Vec<{}>", rust_type(&schema["items"]).0),
+        _ => "serde_json::Value".to_string(),
+    };
+    (rust, nullable)
+}
+
+fn generate_models(spec: &Value, out: &mut String) {
+    let schemas = spec["components"]["schemas"].as_object().cloned().unwrap_or_default();
+    for (name, schema) in &schemas {
+        if name.starts_with("ApiResponse_") {
+            continue;
+        }
+        doc_comment(schema, "", out);
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            writeln!(
+                out,
+                "#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]"
+            )
+            .unwrap();
+            writeln!(out, "pub enum {name} {{").unwrap();
+            for variant in variants.iter().filter_map(Value::as_str) {
+                writeln!(
+                    out,
+                    "    #[serde(rename = \"{variant}\")]\n    {},",
+                    pascal_case(variant)
+                )
+                .unwrap();
+            }
+            writeln!(out, "}}\n").unwrap();
+            writeln!(
+                out,
+                "impl {name} {{\n    pub fn as_str(&self) -> &'static str {{\n        match self {{"
+            )
+            .unwrap();
+            for variant in variants.iter().filter_map(Value::as_str) {
+                writeln!(out, "            {name}::{} => \"{variant}\",", pascal_case(variant)).unwrap();
+            }
+            writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+            continue;
+        }
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        collect_fields(&schemas, schema, &mut properties, &mut required);
+        writeln!(out, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]").unwrap();
+        writeln!(out, "pub struct {name} {{").unwrap();
+        for (field, property) in &properties {
+            let (ty, nullable) = rust_type(property);
+            doc_comment(property, "    ", out);
+            if nullable || !required.contains(field) {
+                // 未设置的字段不发送，交给服务端的默认值处理
+                writeln!(
+                    out,
+                    "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub {field}: Option<{ty}>,"
+                )
+                .unwrap();
+            } else {
+                writeln!(out, "    pub {field}: {ty},").unwrap();
+            }
+        }
+        writeln!(out, "}}\n").unwrap();
+    }
+}
+
+/// 收集对象的字段，`allOf` 中引用的其它对象会被展开合并
+fn collect_fields(
+    schemas: &Map<String, Value>,
+    schema: &Value,
+    properties: &mut Map<String, Value>,
+    required: &mut Vec<String>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = schemas.get(ref_name(reference)).expect("allOf 引用了不存在的组件");
+        collect_fields(schemas, target, properties, required);
+        return;
+    }
+    for part in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+        collect_fields(schemas, part, properties, required);
+    }
+    if let Some(items) = schema.get("properties").and_then(Value::as_object) {
+        properties.extend(items.clone());
+    }
+    if let Some(items) = schema.get("required").and_then(Value::as_array) {
+        required.extend(items.iter().filter_map(Value::as_str).map(str::to_string));
+    }
+}
+
+struct Parameter {
+    name: String,
+    location: String,
+    ty: String,
+    required: bool,
+    description: Option<String>,
+}
+
+fn parameters(operation: &Value) -> Vec<Parameter> {
+    operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .map(|parameter| Parameter {
+                    name: parameter["name"].as_str().unwrap().to_string(),
+                    location: parameter["in"].as_str().unwrap().to_string(),
+                    ty: rust_type(&parameter["schema"]).0,
+                    required: parameter["required"].as_bool().unwrap_or(false),
+                    description: parameter["description"].as_str().map(str::to_string),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn generate_operations(spec: &Value, out: &mut String) {
+    // 按 operationId 排序，保证生成结果稳定
+    let mut operations = BTreeMap::new();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            let id = operation["operationId"].as_str().expect("缺少 operationId").to_string();
+            operations.insert(id, (path.clone(), method.clone(), operation.clone()));
+        }
+    }
+
+    // 查询参数生成独立的结构体
+    for (id, (_, _, operation)) in &operations {
+        let query: Vec<Parameter> = parameters(operation)
+            .into_iter()
+            .filter(|parameter| parameter.location == "query")
+            .collect();
+        if query.is_empty() {
+            continue;
+        }
+        writeln!(out, "/// `{id}` 的查询参数").unwrap();
+        writeln!(out, "#[derive(Debug, Clone, Default, PartialEq, Serialize)]").unwrap();
+        writeln!(out, "pub struct {}Query {{", pascal_case(id)).unwrap();
+        for parameter in query {
+            if let Some(description) = &parameter.description {
+                writeln!(out, "    /// {description}").unwrap();
+            }
+            writeln!(
+                out,
+                "    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub {}: Option<{}>,",
+                parameter.name, parameter.ty
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}\n").unwrap();
+    }
+
+    writeln!(out, "impl Client {{").unwrap();
+    for (id, (path, method, operation)) in &operations {
+        let params = parameters(operation);
+        let mut args = Vec::new();
+        for parameter in params.iter().filter(|parameter| parameter.location == "path") {
+            let ty = if parameter.ty == "String" {
+                "&str"
+            } else {
+                &parameter.ty
+            };
+            args.push(format!("{}: {ty}", parameter.name));
+        }
+        let has_query = params.iter().any(|parameter| parameter.location == "query");
+        if has_query {
+            args.push(format!("query: &{}Query", pascal_case(id)));
+        }
+        let body = operation
+            .pointer("/requestBody/content/application~1json/schema")
+            .map(|schema| rust_type(schema).0);
+        if let Some(body) = &body {
+            args.push(format!("body: &{body}"));
+        }
+        let response = operation
+            .pointer("/responses/200/content/application~1json/schema/$ref")
+            .and_then(Value::as_str)
+            .map(|reference| data_type(ref_name(reference)))
+            .unwrap_or_else(|| "serde_json::Value".to_string());
+
+        doc_comment(operation, "    ", out);
+        writeln!(
+            out,
+            "    pub async fn {id}(&self, {}) -> Result<{response}> {{",
+            args.join(", ")
+        )
+        .unwrap();
+        let path_expr = if path.contains('{') {
+            format!("&format!(\"{path}\")")
+        } else {
+            format!("\"{path}\"")
+        };
+        writeln!(
+            out,
+            "        let request = self.request(reqwest::Method::{}, {path_expr});",
+            method.to_uppercase()
+        )
+        .unwrap();
+        if has_query {
+            writeln!(out, "        let request = request.query(query);").unwrap();
+        }
+        if body.is_some() {
+            writeln!(out, "        let request = request.json(body);").unwrap();
+        }
+        writeln!(out, "        self.send(request).await\n    }}\n").unwrap();
+        // 路径参数通过 format! 内联到路径模板中，必须与模板中的占位符一致
+        for parameter in params.iter().filter(|parameter| parameter.location == "path") {
+            assert!(
+                path.contains(&format!("{{{}}}", parameter.name)) && parameter.required,
+                "{id} 的路径参数 {} 无效",
+                parameter.name
+            );
+        }
+    }
+    writeln!(out, "}}").unwrap();
+}
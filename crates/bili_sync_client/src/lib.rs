@@ -0,0 +1,166 @@
+//! bili-sync `/api/v1` 的 Rust 客户端
+//!
+//! 模型与接口方法由 `build.rs` 根据 `openapi.json` 生成，`openapi.json` 由 bili_sync 的测试从服务端代码导出，
+//! 服务端接口变化后运行 `UPDATE_OPENAPI=1 cargo test -p bili_sync client_openapi` 即可同步。
+//!
+//! ```no_run
+//! # async fn run() -> bili_sync_client::Result<()> {
+//! use bili_sync_client::{Client, ListVideosQuery, VideoStatusFilter};
+//!
+//! let client = Client::new("http://127.0.0.1:12345").with_token("your-api-key");
+//! let failed = client
+//!     .list_videos(&ListVideosQuery {
+//!         status: Some(VideoStatusFilter::Failed),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! for video in failed.items {
+//!     client.reset_video(video.id, &Default::default()).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("接口返回错误 {status}（{}）: {message}", code.map(|code| code.as_str()).unwrap_or("unknown"))]
+    Api {
+        status: u16,
+        code: Option<ErrorCode>,
+        message: String,
+    },
+    #[error("请求失败: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("解析响应失败: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+/// 列表接口的分页结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+}
+
+/// 服务端统一的响应包装
+#[derive(Deserialize)]
+struct Envelope<T> {
+    #[serde(default)]
+    code: Option<ErrorCode>,
+    data: T,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    token: Option<String>,
+    http: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+            http,
+        }
+    }
+
+    /// 管理页令牌、会话令牌或 API 密钥
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => request.header(reqwest::header::AUTHORIZATION, token),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await?;
+        decode(status, &body)
+    }
+}
+
+fn decode<T: DeserializeOwned>(status: u16, body: &[u8]) -> Result<T> {
+    if (200..300).contains(&status) {
+        return Ok(serde_json::from_slice::<Envelope<T>>(body)?.data);
+    }
+    let (code, message) = match serde_json::from_slice::<Envelope<serde_json::Value>>(body) {
+        Ok(Envelope { code, data }) => (
+            code,
+            data.as_str().map(str::to_string).unwrap_or_else(|| data.to_string()),
+        ),
+        Err(_) => (None, String::from_utf8_lossy(body).into_owned()),
+    };
+    Err(Error::Api { status, code, message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_success_and_error_envelopes() {
+        let info: ApiInfo = decode(
+            200,
+            br#"{"status_code":200,"data":{"api_version":"v1","app_version":"3.0.9"}}"#,
+        )
+        .unwrap();
+        assert_eq!(info.api_version, "v1");
+
+        let page: Page<Source> = decode(
+            200,
+            br#"{"status_code":200,"data":{"items":[{"source_type":"favorite","id":1,"remote_id":"9","name":"n","path":"/p","enabled":true,"created_at":"t"}],"total":1,"page":1,"page_size":20,"total_pages":1}}"#,
+        )
+        .unwrap();
+        assert_eq!(page.items[0].remote_id, "9");
+
+        let error = decode::<Source>(
+            404,
+            br#"{"status_code":404,"code":"not_found","data":"Primary key not found: 3"}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Api {
+                status: 404,
+                code: Some(ErrorCode::NotFound),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn query_parameters_skip_unset_fields() {
+        let query = ListVideosQuery {
+            page: Some(2),
+            order: Some(SortOrder::Asc),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&query).unwrap(),
+            serde_json::json!({"page": 2, "order": "asc"})
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+/// 自动化脚本使用的 API 密钥实体，只保存密钥的 SHA-256 哈希
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "api_key")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    /// 密钥明文的前几位，便于在列表中辨认
+    pub key_prefix: String,
+    #[sea_orm(unique)]
+    pub key_hash: String,
+    /// 逗号分隔的权限范围
+    pub scopes: String,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
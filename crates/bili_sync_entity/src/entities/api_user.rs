@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+/// 管理页用户实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "api_user")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub username: String,
+    /// PBKDF2 密码哈希，格式为 `pbkdf2_sha256$迭代次数$盐$哈希`
+    pub password_hash: String,
+    /// 角色：admin / operator / viewer
+    pub role: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
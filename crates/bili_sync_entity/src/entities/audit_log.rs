@@ -0,0 +1,21 @@
+use sea_orm::entity::prelude::*;
+
+/// 写操作审计日志实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// 操作者：用户名，或使用全局 auth_token 时为 `auth_token`
+    pub actor: String,
+    pub role: String,
+    pub method: String,
+    pub path: String,
+    pub status_code: i32,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
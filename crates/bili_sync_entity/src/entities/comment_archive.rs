@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// 视频评论区的归档状态，sync_generation 与 page.danmaku_sync_generation 含义相同
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "comment_archive")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub video_id: i32,
+    pub last_synced_at: String,
+    pub sync_generation: u32,
+    /// 最近一次归档的评论数（含楼中楼回复）
+    pub comment_count: u32,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -23,6 +23,8 @@ pub struct ConfigChangeModel {
     pub old_value: Option<String>,
     pub new_value: String,
     pub changed_at: String,
+    /// 发起变更的用户，后台任务写入时为空
+    pub changed_by: Option<String>,
 }
 
 // 配置值的类型化包装器
@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+/// 分页的弹幕密度直方图，counts 为每个时间桶弹幕数的 JSON 数组
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "danmaku_histogram")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub page_id: i32,
+    pub video_id: i32,
+    pub bucket_seconds: u32,
+    #[sea_orm(column_type = "Text")]
+    pub counts: String,
+    pub total: u32,
+    /// 最近一次写入 NFO 与视频文件的高能章节（JSON），用于避免重复写入
+    #[sea_orm(column_type = "Text", nullable)]
+    pub chapters: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+
+/// 持久化的事件实体，payload 为 JSON 文本
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub kind: String,
+    #[sea_orm(column_type = "Text")]
+    pub payload: String,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -3,12 +3,24 @@
 pub mod prelude;
 
 pub mod ai_conversation_history;
+pub mod api_key;
+pub mod api_session;
+pub mod api_user;
+pub mod audit_log;
 pub mod collection;
+pub mod comment_archive;
 pub mod config_item;
+pub mod danmaku_histogram;
+pub mod event;
 pub mod favorite;
 pub mod page;
+pub mod ranking;
 pub mod submission;
+pub mod subtitle_translation;
 pub mod task_queue;
 pub mod video;
 pub mod video_source;
+pub mod video_summary;
 pub mod watch_later;
+pub mod webhook;
+pub mod webhook_delivery;
@@ -34,6 +34,8 @@ pub struct Model {
     /// 是否已被 AI 重命名
     #[sea_orm(default_value = "0")]
     pub ai_renamed: Option<i32>,
+    /// 最近一次画质升级检查时间
+    pub quality_checked_at: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -4,6 +4,7 @@ pub use super::ai_conversation_history::Entity as AiConversationHistory;
 pub use super::config_item::Entity as ConfigItem;
 pub use super::favorite::Entity as Favorite;
 pub use super::page::Entity as Page;
+pub use super::ranking::Entity as Ranking;
 pub use super::task_queue::Entity as TaskQueue;
 pub use super::video::Entity as Video;
 pub use super::video_source::Entity as VideoSource;
@@ -0,0 +1,54 @@
+//! 排行榜/热门订阅实体定义
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Default)]
+#[sea_orm(table_name = "ranking")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    /// 榜单类型：popular（综合热门）/ weekly（每周必看）/ rank（分区排行榜）
+    pub ranking_type: String,
+    /// 分区 ID，仅对 rank 类型有效，0 表示全站
+    pub rid: i32,
+    pub path: String,
+    pub created_at: String,
+    pub latest_row_at: String,
+    pub enabled: bool,
+    pub scan_deleted_videos: bool,
+    pub scan_deleted_videos_once: bool,
+    pub filter_option: Option<serde_json::Value>,
+    pub keyword_filters: Option<String>,
+    pub keyword_filter_mode: Option<String>,
+    pub blacklist_keywords: Option<String>,
+    pub whitelist_keywords: Option<String>,
+    pub keyword_case_sensitive: bool,
+    pub min_duration_seconds: Option<i32>,
+    pub max_duration_seconds: Option<i32>,
+    pub published_after: Option<String>,
+    pub published_before: Option<String>,
+    pub audio_only: bool,
+    pub audio_only_m4a_only: bool,
+    pub flat_folder: bool,
+    pub split_chapters_after_download: bool,
+    pub download_charge_videos: bool,
+    pub download_danmaku: bool,
+    pub archive_comments: bool,
+    pub download_subtitle: bool,
+    pub download_ai_subtitle: bool,
+    pub ai_subtitle_language: String,
+    pub ai_rename: bool,
+    pub ai_rename_video_prompt: String,
+    pub ai_rename_audio_prompt: String,
+    pub ai_rename_enable_multi_page: bool,
+    pub ai_rename_enable_collection: bool,
+    pub ai_rename_enable_bangumi: bool,
+    pub ai_rename_rename_parent_dir: bool,
+    pub credential_id: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -32,6 +32,7 @@ pub struct Model {
     pub split_chapters_after_download: bool,
     pub download_charge_videos: bool,
     pub download_danmaku: bool,
+    pub archive_comments: bool,
     pub download_subtitle: bool,
     pub download_ai_subtitle: bool,
     pub ai_subtitle_language: String,
@@ -47,6 +48,7 @@ pub struct Model {
     pub last_scan_at: Option<String>,
     pub next_scan_at: Option<String>,
     pub no_update_streak: i32,
+    pub credential_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
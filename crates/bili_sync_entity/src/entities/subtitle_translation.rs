@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+/// 分页字幕翻译到某种目标语言的状态
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "subtitle_translation")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub page_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub target_language: String,
+    /// 译文已生成（或原字幕已是目标语言）的时间
+    pub completed_at: Option<String>,
+    /// 连续翻译失败的轮数
+    pub failed_attempts: u32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -20,6 +20,12 @@ pub enum TaskType {
     ReloadConfig,
     #[sea_orm(string_value = "refresh_danmaku")]
     RefreshDanmaku,
+    #[sea_orm(string_value = "rerender_danmaku")]
+    RerenderDanmaku,
+    #[sea_orm(string_value = "generate_subtitle")]
+    GenerateSubtitle,
+    #[sea_orm(string_value = "upgrade_page_quality")]
+    UpgradePageQuality,
 }
 
 /// 任务状态枚举
@@ -11,6 +11,7 @@ pub struct Model {
     pub favorite_id: Option<i32>,
     pub watch_later_id: Option<i32>,
     pub submission_id: Option<i32>,
+    pub ranking_id: Option<i32>,
     pub source_id: Option<i32>,
     pub source_type: Option<i32>,
     pub upper_id: i64,
@@ -50,6 +50,7 @@ pub struct Model {
     pub split_chapters_after_download: bool,
     pub download_charge_videos: bool,
     pub download_danmaku: bool,
+    pub archive_comments: bool,
     pub download_subtitle: bool,
     pub download_ai_subtitle: bool,
     pub ai_subtitle_language: String,
@@ -60,6 +61,7 @@ pub struct Model {
     pub ai_rename_enable_collection: bool,
     pub ai_rename_enable_bangumi: bool,
     pub ai_rename_rename_parent_dir: bool,
+    pub credential_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+
+/// 视频的 AI 摘要与标签，tags 为 JSON 数组
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "video_summary")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub video_id: i32,
+    #[sea_orm(column_type = "Text")]
+    pub summary: String,
+    #[sea_orm(column_type = "Text")]
+    pub tags: String,
+    /// 摘要依据的内容：subtitle（字幕）或 description（简介）
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+
+/// 第三方 Webhook 订阅实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "webhook")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    /// HMAC-SHA256 签名密钥
+    pub secret: String,
+    /// 逗号分隔的事件类型，为空表示订阅全部事件
+    pub event_kinds: String,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
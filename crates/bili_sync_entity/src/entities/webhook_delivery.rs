@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+/// Webhook 投递记录实体
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "webhook_delivery")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_id: i32,
+    /// pending / succeeded / failed
+    pub status: String,
+    pub attempts: i32,
+    /// 下一次尝试投递的时间，投递结束后为空
+    pub next_attempt_at: Option<String>,
+    pub response_status: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,82 @@
+//! 迁移中与数据库后端相关的辅助函数
+//!
+//! 早期迁移只面向 SQLite，这里集中处理各后端在表达式索引等方面的语法差异。
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+pub(crate) fn backend(manager: &SchemaManager) -> DbBackend {
+    manager.get_connection().get_database_backend()
+}
+
+/// 仅用于修复旧版 SQLite 数据库的迁移在其它后端上直接跳过
+pub(crate) fn is_sqlite(manager: &SchemaManager) -> bool {
+    backend(manager) == DbBackend::Sqlite
+}
+
+/// 对应实体中 `u32` 字段的列类型
+///
+/// SeaORM 在 PostgreSQL 上按 OID 解码 `u32`，普通整数列无法读回，因此在该后端使用 `oid` 类型；
+/// 其它后端仍为无符号整数。
+pub(crate) fn unsigned(manager: &SchemaManager) -> ColumnType {
+    match backend(manager) {
+        DbBackend::Postgres => ColumnType::Custom(Alias::new("oid").into_iden()),
+        _ => ColumnType::Unsigned,
+    }
+}
+
+fn quote(backend: DbBackend, ident: &str) -> String {
+    match backend {
+        DbBackend::MySql => format!("`{}`", ident),
+        _ => format!("\"{}\"", ident),
+    }
+}
+
+/// 创建允许列为空的唯一索引
+///
+/// 在唯一索引中 NULL 不等于 NULL，`(列名, Some(默认值))` 会以 `COALESCE(列名, 默认值)` 参与索引，
+/// `(列名, None)` 则直接使用该列。
+pub(crate) async fn create_null_safe_unique_index(
+    manager: &SchemaManager<'_>,
+    name: &str,
+    table: &str,
+    parts: &[(&str, Option<&str>)],
+) -> Result<(), DbErr> {
+    let backend = backend(manager);
+    let parts = parts
+        .iter()
+        .map(|(column, fallback)| match (fallback, backend) {
+            (None, _) => quote(backend, column),
+            // MySQL 的函数索引需要额外的一层括号
+            (Some(fallback), DbBackend::MySql) => format!("(COALESCE({}, {}))", quote(backend, column), fallback),
+            (Some(fallback), _) => format!("COALESCE({}, {})", quote(backend, column), fallback),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    manager
+        .get_connection()
+        .execute_unprepared(&format!(
+            "CREATE UNIQUE INDEX {} ON {} ({})",
+            quote(backend, name),
+            quote(backend, table),
+            parts
+        ))
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn drop_index_if_exists(manager: &SchemaManager<'_>, table: &str, name: &str) -> Result<(), DbErr> {
+    let backend = backend(manager);
+    let sql = match backend {
+        // MySQL 的 DROP INDEX 不支持 IF EXISTS
+        DbBackend::MySql => {
+            if !manager.has_index(table, name).await? {
+                return Ok(());
+            }
+            format!("DROP INDEX {} ON {}", quote(backend, name), quote(backend, table))
+        }
+        _ => format!("DROP INDEX IF EXISTS {}", quote(backend, name)),
+    };
+    manager.get_connection().execute_unprepared(&sql).await?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub use sea_orm_migration::prelude::*;
 
+mod dialect;
 mod m20240322_000001_create_table;
 mod m20240505_130850_add_collection;
 mod m20240709_130914_watch_later;
@@ -69,6 +70,16 @@ mod m20260415_000001_add_split_chapters_to_sources;
 mod m20260704_000001_add_ai_subtitle_settings;
 mod m20260718_000001_add_source_filter_option;
 mod m20260719_000001_add_source_download_charge_videos;
+mod m20261019_000001_create_ranking;
+mod m20261019_000002_add_page_quality_checked_at;
+mod m20261019_000003_add_source_credential_id;
+mod m20261019_000004_create_api_users;
+mod m20261019_000005_create_api_keys;
+mod m20261019_000006_create_events;
+mod m20261019_000007_create_danmaku_histogram;
+mod m20261019_000008_add_comment_archive;
+mod m20261019_000009_create_video_summary;
+mod m20261019_000010_create_subtitle_translation;
 
 pub struct Migrator;
 
@@ -145,6 +156,16 @@ impl MigratorTrait for Migrator {
             Box::new(m20260704_000001_add_ai_subtitle_settings::Migration),
             Box::new(m20260718_000001_add_source_filter_option::Migration),
             Box::new(m20260719_000001_add_source_download_charge_videos::Migration),
+            Box::new(m20261019_000001_create_ranking::Migration),
+            Box::new(m20261019_000002_add_page_quality_checked_at::Migration),
+            Box::new(m20261019_000003_add_source_credential_id::Migration),
+            Box::new(m20261019_000004_create_api_users::Migration),
+            Box::new(m20261019_000005_create_api_keys::Migration),
+            Box::new(m20261019_000006_create_events::Migration),
+            Box::new(m20261019_000007_create_danmaku_histogram::Migration),
+            Box::new(m20261019_000008_add_comment_archive::Migration),
+            Box::new(m20261019_000009_create_video_summary::Migration),
+            Box::new(m20261019_000010_create_subtitle_translation::Migration),
         ]
     }
 }
@@ -14,15 +14,15 @@ impl MigrationTrait for Migration {
                     .if_not_exists()
                     .col(
                         ColumnDef::new(Collection::Id)
-                            .unsigned()
+                            .integer()
                             .not_null()
                             .auto_increment()
                             .primary_key(),
                     )
-                    .col(ColumnDef::new(Collection::SId).unsigned().not_null())
-                    .col(ColumnDef::new(Collection::MId).unsigned().not_null())
+                    .col(ColumnDef::new(Collection::SId).big_integer().not_null())
+                    .col(ColumnDef::new(Collection::MId).big_integer().not_null())
                     .col(ColumnDef::new(Collection::Name).string().not_null())
-                    .col(ColumnDef::new(Collection::Type).small_unsigned().not_null())
+                    .col(ColumnDef::new(Collection::Type).integer().not_null())
                     .col(ColumnDef::new(Collection::Path).string().not_null())
                     .col(
                         ColumnDef::new(Collection::CreatedAt)
@@ -57,7 +57,7 @@ impl MigrationTrait for Migration {
             .alter_table(
                 Table::alter()
                     .table(Video::Table)
-                    .add_column(ColumnDef::new(Video::CollectionId).unsigned().null())
+                    .add_column(ColumnDef::new(Video::CollectionId).integer().null())
                     .to_owned(),
             )
             .await?;
@@ -65,7 +65,7 @@ impl MigrationTrait for Migration {
             .alter_table(
                 Table::alter()
                     .table(Video::Table)
-                    .add_column(ColumnDef::new(Video::TempFavoriteId).unsigned().null())
+                    .add_column(ColumnDef::new(Video::TempFavoriteId).integer().null())
                     .to_owned(),
             )
             .await?;
@@ -87,9 +87,18 @@ impl MigrationTrait for Migration {
                     .to_owned(),
             )
             .await?;
-        // 在唯一索引中，NULL 不等于 NULL，所以需要使用 ifnull 函数排除空的情况
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_cid_fid_bvid` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), `bvid`)")
-            .await?;
+        // 在唯一索引中，NULL 不等于 NULL，所以需要使用 COALESCE 函数排除空的情况
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_cid_fid_bvid",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
         Ok(())
     }
 
@@ -110,7 +119,7 @@ impl MigrationTrait for Migration {
                 Table::alter()
                     .table(Video::Table)
                     // 向存在记录的表中添加非空列时，必须提供默认值
-                    .add_column(ColumnDef::new(Video::TempFavoriteId).unsigned().not_null().default(0))
+                    .add_column(ColumnDef::new(Video::TempFavoriteId).integer().not_null().default(0))
                     .to_owned(),
             )
             .await?;
@@ -6,7 +6,6 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
         manager
             .create_table(
                 Table::create()
@@ -14,7 +13,7 @@ impl MigrationTrait for Migration {
                     .if_not_exists()
                     .col(
                         ColumnDef::new(WatchLater::Id)
-                            .unsigned()
+                            .integer()
                             .not_null()
                             .auto_increment()
                             .primary_key(),
@@ -41,12 +40,22 @@ impl MigrationTrait for Migration {
             .alter_table(
                 Table::alter()
                     .table(Video::Table)
-                    .add_column(ColumnDef::new(Video::WatchLaterId).unsigned().null())
+                    .add_column(ColumnDef::new(Video::WatchLaterId).integer().null())
                     .to_owned(),
             )
             .await?;
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_unique` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), ifnull(`watch_later_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
         Ok(())
     }
 
@@ -65,8 +74,17 @@ impl MigrationTrait for Migration {
                     .to_owned(),
             )
             .await?;
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_cid_fid_bvid` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_cid_fid_bvid",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
         manager
             .drop_table(Table::drop().table(WatchLater::Table).to_owned())
             .await
@@ -6,7 +6,6 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
         manager
             .create_table(
                 Table::create()
@@ -14,12 +13,17 @@ impl MigrationTrait for Migration {
                     .if_not_exists()
                     .col(
                         ColumnDef::new(Submission::Id)
-                            .unsigned()
+                            .integer()
                             .not_null()
                             .auto_increment()
                             .primary_key(),
                     )
-                    .col(ColumnDef::new(Submission::UpperId).unique_key().unsigned().not_null())
+                    .col(
+                        ColumnDef::new(Submission::UpperId)
+                            .unique_key()
+                            .big_integer()
+                            .not_null(),
+                    )
                     .col(ColumnDef::new(Submission::UpperName).string().not_null())
                     .col(ColumnDef::new(Submission::Path).string().not_null())
                     .col(
@@ -38,12 +42,23 @@ impl MigrationTrait for Migration {
             .alter_table(
                 Table::alter()
                     .table(Video::Table)
-                    .add_column(ColumnDef::new(Video::SubmissionId).unsigned().null())
+                    .add_column(ColumnDef::new(Video::SubmissionId).integer().null())
                     .to_owned(),
             )
             .await?;
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_unique` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), ifnull(`watch_later_id`, -1), ifnull(`submission_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("submission_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
         Ok(())
     }
 
@@ -62,8 +77,18 @@ impl MigrationTrait for Migration {
                     .to_owned(),
             )
             .await?;
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_unique` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), ifnull(`watch_later_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
         manager
             .drop_table(Table::drop().table(Submission::Table).to_owned())
             .await
@@ -53,7 +53,7 @@ impl MigrationTrait for Migration {
                     .name("idx_page_status_query")
                     .table(Page::Table)
                     .col(Page::VideoId)
-                    .col(Page::Status)
+                    .col(Page::DownloadStatus)
                     .to_owned(),
             )
             .await?;
@@ -92,5 +92,5 @@ enum Video {
 enum Page {
     Table,
     VideoId,
-    Status,
+    DownloadStatus,
 }
@@ -43,19 +43,19 @@ impl MigrationTrait for Migration {
         // 手动写 SQL 更新这四张表的 latest 字段到当前取值
         let db = manager.get_connection();
         db.execute_unprepared(
-            "UPDATE favorite SET latest_row_at = (SELECT IFNULL(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE favorite_id = favorite.id)",
+            "UPDATE favorite SET latest_row_at = (SELECT COALESCE(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE favorite_id = favorite.id)",
         )
         .await?;
         db.execute_unprepared(
-            "UPDATE collection SET latest_row_at = (SELECT IFNULL(MAX(pubtime), '1970-01-01 00:00:00') FROM video WHERE collection_id = collection.id)",
+            "UPDATE collection SET latest_row_at = (SELECT COALESCE(MAX(pubtime), '1970-01-01 00:00:00') FROM video WHERE collection_id = collection.id)",
         )
         .await?;
         db.execute_unprepared(
-            "UPDATE watch_later SET latest_row_at = (SELECT IFNULL(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE watch_later_id = watch_later.id)",
+            "UPDATE watch_later SET latest_row_at = (SELECT COALESCE(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE watch_later_id = watch_later.id)",
         )
         .await?;
         db.execute_unprepared(
-            "UPDATE submission SET latest_row_at = (SELECT IFNULL(MAX(ctime), '1970-01-01 00:00:00') FROM video WHERE submission_id = submission.id)",
+            "UPDATE submission SET latest_row_at = (SELECT COALESCE(MAX(ctime), '1970-01-01 00:00:00') FROM video WHERE submission_id = submission.id)",
         )
         .await?;
         Ok(())
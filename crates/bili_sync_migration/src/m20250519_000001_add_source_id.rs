@@ -6,8 +6,6 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
         // 创建 video_source 表
         manager
             .create_table(
@@ -16,7 +14,7 @@ impl MigrationTrait for Migration {
                     .if_not_exists()
                     .col(
                         ColumnDef::new(VideoSource::Id)
-                            .unsigned()
+                            .integer()
                             .not_null()
                             .auto_increment()
                             .primary_key(),
@@ -45,18 +43,16 @@ impl MigrationTrait for Migration {
 
         // 修改 video 表，添加相关列
         // 使用 SQL 语句直接删除索引，加上 IF EXISTS 子句
-        db.execute_unprepared("DROP INDEX IF EXISTS idx_video_unique").await?;
-        db.execute_unprepared("DROP INDEX IF EXISTS idx_video_cid_fid_bvid")
-            .await?;
-        db.execute_unprepared("DROP INDEX IF EXISTS idx_video_favorite_id_bvid")
-            .await?;
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_unique").await?;
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_cid_fid_bvid").await?;
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_favorite_id_bvid").await?;
 
         // SQLite 不支持在单个 ALTER TABLE 语句中添加多个列，所以分开执行
         manager
             .alter_table(
                 Table::alter()
                     .table(Video::Table)
-                    .add_column(ColumnDef::new(Video::SourceId).unsigned().null())
+                    .add_column(ColumnDef::new(Video::SourceId).integer().null())
                     .to_owned(),
             )
             .await?;
@@ -98,8 +94,20 @@ impl MigrationTrait for Migration {
             .await?;
 
         // 更新唯一索引
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_unique` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), ifnull(`watch_later_id`, -1), ifnull(`submission_id`, -1), ifnull(`source_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("submission_id", Some("-1")),
+                ("source_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
 
         Ok(())
     }
@@ -108,7 +116,7 @@ impl MigrationTrait for Migration {
         let db = manager.get_connection();
 
         // 删除索引，再删除列
-        db.execute_unprepared("DROP INDEX IF EXISTS idx_video_unique").await?;
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_unique").await?;
 
         // 删除所有使用 source_id 的记录
         db.execute_unprepared("DELETE FROM video WHERE source_id IS NOT NULL")
@@ -156,8 +164,19 @@ impl MigrationTrait for Migration {
             .await?;
 
         // 恢复不包含 source_id 的索引
-        db.execute_unprepared("CREATE UNIQUE INDEX `idx_video_unique` ON `video` (ifnull(`collection_id`, -1), ifnull(`favorite_id`, -1), ifnull(`watch_later_id`, -1), ifnull(`submission_id`, -1), `bvid`)")
-            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("submission_id", Some("-1")),
+                ("bvid", None),
+            ],
+        )
+        .await?;
 
         // 删除 video_source 表
         manager
@@ -6,6 +6,13 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !crate::dialect::is_sqlite(manager) {
+            return modify_fid_column(
+                manager,
+                ColumnDef::new(Favorite::FId).big_integer().not_null().to_owned(),
+            )
+            .await;
+        }
         let db = manager.get_connection();
 
         // 检查 latest_row_at 列是否存在
@@ -75,6 +82,9 @@ impl MigrationTrait for Migration {
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !crate::dialect::is_sqlite(manager) {
+            return modify_fid_column(manager, ColumnDef::new(Favorite::FId).integer().not_null().to_owned()).await;
+        }
         let db = manager.get_connection();
 
         // 回滚：将 BIGINT 改回 INTEGER，但保留 latest_row_at 列
@@ -108,3 +118,16 @@ impl MigrationTrait for Migration {
         Ok(())
     }
 }
+
+/// 其它数据库支持直接修改列类型，无需重建表
+async fn modify_fid_column(manager: &SchemaManager<'_>, column: ColumnDef) -> Result<(), DbErr> {
+    manager
+        .alter_table(Table::alter().table(Favorite::Table).modify_column(column).to_owned())
+        .await
+}
+
+#[derive(DeriveIden)]
+enum Favorite {
+    Table,
+    FId,
+}
@@ -6,6 +6,11 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 仅修复旧版 SQLite 数据库；其它数据库由前面的迁移完整建表，
+        // 且失败的语句会中止整个事务，不能像这里一样忽略错误
+        if !crate::dialect::is_sqlite(manager) {
+            return Ok(());
+        }
         let db = manager.get_connection();
 
         // 检查并修复 favorite 表的兼容性问题
@@ -49,10 +54,10 @@ impl MigrationTrait for Migration {
 
         // 3. 更新默认值（如果列刚被添加）
         let update_queries = vec![
-            "UPDATE favorite SET latest_row_at = (SELECT IFNULL(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE favorite_id = favorite.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
-            "UPDATE collection SET latest_row_at = (SELECT IFNULL(MAX(pubtime), '1970-01-01 00:00:00') FROM video WHERE collection_id = collection.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
-            "UPDATE watch_later SET latest_row_at = (SELECT IFNULL(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE watch_later_id = watch_later.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
-            "UPDATE submission SET latest_row_at = (SELECT IFNULL(MAX(ctime), '1970-01-01 00:00:00') FROM video WHERE submission_id = submission.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
+            "UPDATE favorite SET latest_row_at = (SELECT COALESCE(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE favorite_id = favorite.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
+            "UPDATE collection SET latest_row_at = (SELECT COALESCE(MAX(pubtime), '1970-01-01 00:00:00') FROM video WHERE collection_id = collection.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
+            "UPDATE watch_later SET latest_row_at = (SELECT COALESCE(MAX(favtime), '1970-01-01 00:00:00') FROM video WHERE watch_later_id = watch_later.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
+            "UPDATE submission SET latest_row_at = (SELECT COALESCE(MAX(ctime), '1970-01-01 00:00:00') FROM video WHERE submission_id = submission.id) WHERE latest_row_at = '1970-01-01 00:00:00'",
         ];
 
         for query in update_queries {
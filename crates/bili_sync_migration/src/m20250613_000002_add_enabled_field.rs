@@ -1,4 +1,3 @@
-use sea_orm::ConnectionTrait;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -8,8 +7,7 @@ pub struct Migration;
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
         // 为 video_source 表添加 enabled 字段
-        let db = manager.get_connection();
-        if table_exists(db, "video_source").await? && !column_exists(db, "video_source", "enabled").await? {
+        if manager.has_table("video_source").await? && !manager.has_column("video_source", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -28,7 +26,7 @@ impl MigrationTrait for Migration {
 
         // 为其他视频源表也添加 enabled 字段
         // 收藏夹表
-        if table_exists(db, "favorite").await? && !column_exists(db, "favorite", "enabled").await? {
+        if manager.has_table("favorite").await? && !manager.has_column("favorite", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -46,7 +44,7 @@ impl MigrationTrait for Migration {
         }
 
         // 合集表
-        if table_exists(db, "collection").await? && !column_exists(db, "collection", "enabled").await? {
+        if manager.has_table("collection").await? && !manager.has_column("collection", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -64,7 +62,7 @@ impl MigrationTrait for Migration {
         }
 
         // UP主投稿表
-        if table_exists(db, "submission").await? && !column_exists(db, "submission", "enabled").await? {
+        if manager.has_table("submission").await? && !manager.has_column("submission", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -82,7 +80,7 @@ impl MigrationTrait for Migration {
         }
 
         // 稍后观看表
-        if table_exists(db, "watch_later").await? && !column_exists(db, "watch_later", "enabled").await? {
+        if manager.has_table("watch_later").await? && !manager.has_column("watch_later", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -104,8 +102,7 @@ impl MigrationTrait for Migration {
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
         // 删除 enabled 字段
-        let db = manager.get_connection();
-        if table_exists(db, "video_source").await? && column_exists(db, "video_source", "enabled").await? {
+        if manager.has_table("video_source").await? && manager.has_column("video_source", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -116,7 +113,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if table_exists(db, "favorite").await? && column_exists(db, "favorite", "enabled").await? {
+        if manager.has_table("favorite").await? && manager.has_column("favorite", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -127,7 +124,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if table_exists(db, "collection").await? && column_exists(db, "collection", "enabled").await? {
+        if manager.has_table("collection").await? && manager.has_column("collection", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -138,7 +135,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if table_exists(db, "submission").await? && column_exists(db, "submission", "enabled").await? {
+        if manager.has_table("submission").await? && manager.has_column("submission", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -149,7 +146,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if table_exists(db, "watch_later").await? && column_exists(db, "watch_later", "enabled").await? {
+        if manager.has_table("watch_later").await? && manager.has_column("watch_later", "enabled").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -193,32 +190,3 @@ enum WatchLater {
     Table,
     Enabled,
 }
-
-/// 检查SQLite表是否存在
-async fn table_exists<C: ConnectionTrait>(db: &C, table_name: &str) -> Result<bool, DbErr> {
-    use sea_orm::Statement;
-
-    let sql = "SELECT 1 FROM sqlite_master WHERE type='table' AND name=? LIMIT 1";
-    let stmt = Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, sql, vec![table_name.into()]);
-    let result = db.query_one(stmt).await?;
-    Ok(result.is_some())
-}
-
-/// 检查SQLite表中是否存在指定列
-async fn column_exists<C: ConnectionTrait>(db: &C, table_name: &str, column_name: &str) -> Result<bool, DbErr> {
-    use sea_orm::Statement;
-
-    let sql = format!("PRAGMA table_info({})", table_name);
-    let result = db
-        .query_all(Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql))
-        .await?;
-
-    for row in result {
-        let name: String = row.try_get("", "name")?;
-        if name == column_name {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
-}
@@ -6,6 +6,10 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !crate::dialect::is_sqlite(manager) {
+            return convert_time_columns_in_place(manager).await;
+        }
+
         // 1. 修改所有 DateTime 类型字段为 String 类型
         // 注意：SQLite 不支持直接修改列类型，需要重建表
 
@@ -413,6 +417,48 @@ impl MigrationTrait for Migration {
     }
 }
 
+/// 其它数据库支持直接修改列类型，且不存在旧版本写入的 UTC 时间，只需调整列类型
+async fn convert_time_columns_in_place(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let columns: [(&str, &str, bool); 17] = [
+        ("video_source", "latest_row_at", true),
+        ("video_source", "created_at", true),
+        ("video_source", "cache_updated_at", false),
+        ("submission", "created_at", true),
+        ("submission", "latest_row_at", true),
+        ("collection", "created_at", true),
+        ("collection", "latest_row_at", true),
+        ("favorite", "created_at", true),
+        ("favorite", "latest_row_at", true),
+        ("watch_later", "created_at", true),
+        ("watch_later", "latest_row_at", true),
+        ("task_queue", "created_at", true),
+        ("task_queue", "updated_at", true),
+        ("config_items", "updated_at", true),
+        ("config_changes", "changed_at", true),
+        ("video", "created_at", true),
+        ("page", "created_at", true),
+    ];
+    for (table, column, not_null) in columns {
+        if !manager.has_column(table, column).await? {
+            continue;
+        }
+        let mut column_def = ColumnDef::new(Alias::new(column));
+        column_def.string();
+        if not_null {
+            column_def.not_null();
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new(table))
+                    .modify_column(column_def)
+                    .to_owned(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
 // 定义表和列的枚举
 
 #[derive(Iden)]
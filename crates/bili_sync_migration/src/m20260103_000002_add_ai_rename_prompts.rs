@@ -1,4 +1,3 @@
-use sea_orm::ConnectionTrait;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -8,12 +7,10 @@ pub struct Migration;
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
         // SQLite 不支持一次添加多列，需要分开添加
-        // 使用原生SQL检查列是否存在，避免重复添加
-
-        let db = manager.get_connection();
+        // 检查列是否存在，避免重复添加
 
         // 为收藏夹表添加AI重命名提示词字段
-        if !column_exists(db, "favorite", "ai_rename_video_prompt").await? {
+        if !manager.has_column("favorite", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -29,7 +26,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "favorite", "ai_rename_audio_prompt").await? {
+        if !manager.has_column("favorite", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -46,7 +43,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为合集表添加AI重命名提示词字段
-        if !column_exists(db, "collection", "ai_rename_video_prompt").await? {
+        if !manager.has_column("collection", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -62,7 +59,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "collection", "ai_rename_audio_prompt").await? {
+        if !manager.has_column("collection", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -79,7 +76,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为UP主投稿表添加AI重命名提示词字段
-        if !column_exists(db, "submission", "ai_rename_video_prompt").await? {
+        if !manager.has_column("submission", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -95,7 +92,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "submission", "ai_rename_audio_prompt").await? {
+        if !manager.has_column("submission", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -112,7 +109,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为稍后再看表添加AI重命名提示词字段
-        if !column_exists(db, "watch_later", "ai_rename_video_prompt").await? {
+        if !manager.has_column("watch_later", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -128,7 +125,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "watch_later", "ai_rename_audio_prompt").await? {
+        if !manager.has_column("watch_later", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -145,7 +142,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为番剧表添加AI重命名提示词字段
-        if !column_exists(db, "video_source", "ai_rename_video_prompt").await? {
+        if !manager.has_column("video_source", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -161,7 +158,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "video_source", "ai_rename_audio_prompt").await? {
+        if !manager.has_column("video_source", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -181,10 +178,8 @@ impl MigrationTrait for Migration {
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
         // 删除收藏夹表的AI重命名提示词字段
-        if column_exists(db, "favorite", "ai_rename_video_prompt").await? {
+        if manager.has_column("favorite", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -195,7 +190,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "favorite", "ai_rename_audio_prompt").await? {
+        if manager.has_column("favorite", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -207,7 +202,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除合集表的AI重命名提示词字段
-        if column_exists(db, "collection", "ai_rename_video_prompt").await? {
+        if manager.has_column("collection", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -218,7 +213,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "collection", "ai_rename_audio_prompt").await? {
+        if manager.has_column("collection", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -230,7 +225,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除UP主投稿表的AI重命名提示词字段
-        if column_exists(db, "submission", "ai_rename_video_prompt").await? {
+        if manager.has_column("submission", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -241,7 +236,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "submission", "ai_rename_audio_prompt").await? {
+        if manager.has_column("submission", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -253,7 +248,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除稍后再看表的AI重命名提示词字段
-        if column_exists(db, "watch_later", "ai_rename_video_prompt").await? {
+        if manager.has_column("watch_later", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -264,7 +259,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "watch_later", "ai_rename_audio_prompt").await? {
+        if manager.has_column("watch_later", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -276,7 +271,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除番剧表的AI重命名提示词字段
-        if column_exists(db, "video_source", "ai_rename_video_prompt").await? {
+        if manager.has_column("video_source", "ai_rename_video_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -287,7 +282,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "video_source", "ai_rename_audio_prompt").await? {
+        if manager.has_column("video_source", "ai_rename_audio_prompt").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -302,25 +297,6 @@ impl MigrationTrait for Migration {
     }
 }
 
-/// 检查SQLite表中是否存在指定列
-async fn column_exists<C: ConnectionTrait>(db: &C, table_name: &str, column_name: &str) -> Result<bool, DbErr> {
-    use sea_orm::Statement;
-
-    let sql = format!("PRAGMA table_info({})", table_name);
-    let result = db
-        .query_all(Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql))
-        .await?;
-
-    for row in result {
-        let name: String = row.try_get("", "name")?;
-        if name == column_name {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
-}
-
 #[derive(Iden)]
 enum Favorite {
     Table,
@@ -25,7 +25,7 @@ impl MigrationTrait for Migration {
                     .col(ColumnDef::new(AiConversationHistory::OrderIndex).integer().not_null())
                     .col(
                         ColumnDef::new(AiConversationHistory::CreatedAt)
-                            .timestamp()
+                            .string()
                             .not_null()
                             .default(Expr::current_timestamp()),
                     )
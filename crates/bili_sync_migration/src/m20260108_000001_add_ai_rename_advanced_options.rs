@@ -1,4 +1,3 @@
-use sea_orm::ConnectionTrait;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -7,10 +6,8 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
         // 为收藏夹表添加AI重命名高级选项字段
-        if !column_exists(db, "favorite", "ai_rename_enable_multi_page").await? {
+        if !manager.has_column("favorite", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -26,7 +23,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "favorite", "ai_rename_enable_collection").await? {
+        if !manager.has_column("favorite", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -42,7 +39,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "favorite", "ai_rename_enable_bangumi").await? {
+        if !manager.has_column("favorite", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -59,7 +56,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为合集表添加AI重命名高级选项字段
-        if !column_exists(db, "collection", "ai_rename_enable_multi_page").await? {
+        if !manager.has_column("collection", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -75,7 +72,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "collection", "ai_rename_enable_collection").await? {
+        if !manager.has_column("collection", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -91,7 +88,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "collection", "ai_rename_enable_bangumi").await? {
+        if !manager.has_column("collection", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -108,7 +105,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为UP主投稿表添加AI重命名高级选项字段
-        if !column_exists(db, "submission", "ai_rename_enable_multi_page").await? {
+        if !manager.has_column("submission", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -124,7 +121,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "submission", "ai_rename_enable_collection").await? {
+        if !manager.has_column("submission", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -140,7 +137,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "submission", "ai_rename_enable_bangumi").await? {
+        if !manager.has_column("submission", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -157,7 +154,7 @@ impl MigrationTrait for Migration {
         }
 
         // 为稍后再看表添加AI重命名高级选项字段
-        if !column_exists(db, "watch_later", "ai_rename_enable_multi_page").await? {
+        if !manager.has_column("watch_later", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -173,7 +170,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "watch_later", "ai_rename_enable_collection").await? {
+        if !manager.has_column("watch_later", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -189,7 +186,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "watch_later", "ai_rename_enable_bangumi").await? {
+        if !manager.has_column("watch_later", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -206,7 +203,10 @@ impl MigrationTrait for Migration {
         }
 
         // 为番剧表添加AI重命名高级选项字段
-        if !column_exists(db, "video_source", "ai_rename_enable_multi_page").await? {
+        if !manager
+            .has_column("video_source", "ai_rename_enable_multi_page")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -222,7 +222,10 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "video_source", "ai_rename_enable_collection").await? {
+        if !manager
+            .has_column("video_source", "ai_rename_enable_collection")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -238,7 +241,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "video_source", "ai_rename_enable_bangumi").await? {
+        if !manager.has_column("video_source", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -258,10 +261,8 @@ impl MigrationTrait for Migration {
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
         // 删除收藏夹表的AI重命名高级选项字段
-        if column_exists(db, "favorite", "ai_rename_enable_multi_page").await? {
+        if manager.has_column("favorite", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -272,7 +273,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "favorite", "ai_rename_enable_collection").await? {
+        if manager.has_column("favorite", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -283,7 +284,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "favorite", "ai_rename_enable_bangumi").await? {
+        if manager.has_column("favorite", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -295,7 +296,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除合集表的AI重命名高级选项字段
-        if column_exists(db, "collection", "ai_rename_enable_multi_page").await? {
+        if manager.has_column("collection", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -306,7 +307,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "collection", "ai_rename_enable_collection").await? {
+        if manager.has_column("collection", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -317,7 +318,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "collection", "ai_rename_enable_bangumi").await? {
+        if manager.has_column("collection", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -329,7 +330,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除UP主投稿表的AI重命名高级选项字段
-        if column_exists(db, "submission", "ai_rename_enable_multi_page").await? {
+        if manager.has_column("submission", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -340,7 +341,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "submission", "ai_rename_enable_collection").await? {
+        if manager.has_column("submission", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -351,7 +352,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "submission", "ai_rename_enable_bangumi").await? {
+        if manager.has_column("submission", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -363,7 +364,7 @@ impl MigrationTrait for Migration {
         }
 
         // 删除稍后再看表的AI重命名高级选项字段
-        if column_exists(db, "watch_later", "ai_rename_enable_multi_page").await? {
+        if manager.has_column("watch_later", "ai_rename_enable_multi_page").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -374,7 +375,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "watch_later", "ai_rename_enable_collection").await? {
+        if manager.has_column("watch_later", "ai_rename_enable_collection").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -385,7 +386,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "watch_later", "ai_rename_enable_bangumi").await? {
+        if manager.has_column("watch_later", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -397,7 +398,10 @@ impl MigrationTrait for Migration {
         }
 
         // 删除番剧表的AI重命名高级选项字段
-        if column_exists(db, "video_source", "ai_rename_enable_multi_page").await? {
+        if manager
+            .has_column("video_source", "ai_rename_enable_multi_page")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -408,7 +412,10 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "video_source", "ai_rename_enable_collection").await? {
+        if manager
+            .has_column("video_source", "ai_rename_enable_collection")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -419,7 +426,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "video_source", "ai_rename_enable_bangumi").await? {
+        if manager.has_column("video_source", "ai_rename_enable_bangumi").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -434,25 +441,6 @@ impl MigrationTrait for Migration {
     }
 }
 
-/// 检查SQLite表中是否存在指定列
-async fn column_exists<C: ConnectionTrait>(db: &C, table_name: &str, column_name: &str) -> Result<bool, DbErr> {
-    use sea_orm::Statement;
-
-    let sql = format!("PRAGMA table_info({})", table_name);
-    let result = db
-        .query_all(Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql))
-        .await?;
-
-    for row in result {
-        let name: String = row.try_get("", "name")?;
-        if name == column_name {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
-}
-
 #[derive(Iden)]
 enum Favorite {
     Table,
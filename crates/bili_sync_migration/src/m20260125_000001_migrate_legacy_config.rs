@@ -8,6 +8,10 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 旧版 config 表只存在于 SQLite 数据库
+        if !crate::dialect::is_sqlite(manager) {
+            return Ok(());
+        }
         let db = manager.get_connection();
 
         // 仅在存在旧版 config 表且新配置表为空时执行迁移
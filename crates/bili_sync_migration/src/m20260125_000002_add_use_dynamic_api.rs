@@ -1,4 +1,3 @@
-use sea_orm::Statement;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -52,11 +51,5 @@ enum Submission {
 }
 
 async fn submission_has_use_dynamic_api(manager: &SchemaManager<'_>) -> Result<bool, DbErr> {
-    let backend = manager.get_connection().get_database_backend();
-    let sql = "SELECT COUNT(*) FROM pragma_table_info('submission') WHERE name = 'use_dynamic_api'";
-    let result = manager
-        .get_connection()
-        .query_one(Statement::from_string(backend, sql.to_string()))
-        .await?;
-    Ok(result.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0) >= 1)
+    manager.has_column("submission", "use_dynamic_api").await
 }
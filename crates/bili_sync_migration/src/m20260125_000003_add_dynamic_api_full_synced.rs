@@ -30,7 +30,7 @@ impl MigrationTrait for Migration {
             .get_connection()
             .execute(Statement::from_string(
                 backend,
-                "UPDATE submission SET dynamic_api_full_synced = 1 WHERE use_dynamic_api = 1".to_string(),
+                "UPDATE submission SET dynamic_api_full_synced = TRUE WHERE use_dynamic_api = TRUE".to_string(),
             ))
             .await?;
 
@@ -61,11 +61,5 @@ enum Submission {
 }
 
 async fn submission_has_dynamic_api_full_synced(manager: &SchemaManager<'_>) -> Result<bool, DbErr> {
-    let backend = manager.get_connection().get_database_backend();
-    let sql = "SELECT COUNT(*) FROM pragma_table_info('submission') WHERE name = 'dynamic_api_full_synced'";
-    let result = manager
-        .get_connection()
-        .query_one(Statement::from_string(backend, sql.to_string()))
-        .await?;
-    Ok(result.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0) >= 1)
+    manager.has_column("submission", "dynamic_api_full_synced").await
 }
@@ -1,4 +1,3 @@
-use sea_orm::Statement;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -95,14 +94,5 @@ enum Submission {
 }
 
 async fn submission_has_column(manager: &SchemaManager<'_>, column: &str) -> Result<bool, DbErr> {
-    let backend = manager.get_connection().get_database_backend();
-    let sql = format!(
-        "SELECT COUNT(*) FROM pragma_table_info('submission') WHERE name = '{}'",
-        column.replace('\'', "''")
-    );
-    let result = manager
-        .get_connection()
-        .query_one(Statement::from_string(backend, sql))
-        .await?;
-    Ok(result.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0) >= 1)
+    manager.has_column("submission", column).await
 }
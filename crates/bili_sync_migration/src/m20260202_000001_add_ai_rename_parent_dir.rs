@@ -1,4 +1,3 @@
-use sea_orm::ConnectionTrait;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -7,9 +6,7 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
-        if !column_exists(db, "favorite", "ai_rename_rename_parent_dir").await? {
+        if !manager.has_column("favorite", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -25,7 +22,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "collection", "ai_rename_rename_parent_dir").await? {
+        if !manager.has_column("collection", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -41,7 +38,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "submission", "ai_rename_rename_parent_dir").await? {
+        if !manager.has_column("submission", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -57,7 +54,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "watch_later", "ai_rename_rename_parent_dir").await? {
+        if !manager.has_column("watch_later", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -73,7 +70,10 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if !column_exists(db, "video_source", "ai_rename_rename_parent_dir").await? {
+        if !manager
+            .has_column("video_source", "ai_rename_rename_parent_dir")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -93,9 +93,7 @@ impl MigrationTrait for Migration {
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let db = manager.get_connection();
-
-        if column_exists(db, "favorite", "ai_rename_rename_parent_dir").await? {
+        if manager.has_column("favorite", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -106,7 +104,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "collection", "ai_rename_rename_parent_dir").await? {
+        if manager.has_column("collection", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -117,7 +115,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "submission", "ai_rename_rename_parent_dir").await? {
+        if manager.has_column("submission", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -128,7 +126,7 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "watch_later", "ai_rename_rename_parent_dir").await? {
+        if manager.has_column("watch_later", "ai_rename_rename_parent_dir").await? {
             manager
                 .alter_table(
                     Table::alter()
@@ -139,7 +137,10 @@ impl MigrationTrait for Migration {
                 .await?;
         }
 
-        if column_exists(db, "video_source", "ai_rename_rename_parent_dir").await? {
+        if manager
+            .has_column("video_source", "ai_rename_rename_parent_dir")
+            .await?
+        {
             manager
                 .alter_table(
                     Table::alter()
@@ -154,24 +155,6 @@ impl MigrationTrait for Migration {
     }
 }
 
-async fn column_exists<C: ConnectionTrait>(db: &C, table_name: &str, column_name: &str) -> Result<bool, DbErr> {
-    use sea_orm::Statement;
-
-    let sql = format!("PRAGMA table_info({})", table_name);
-    let result = db
-        .query_all(Statement::from_string(sea_orm::DatabaseBackend::Sqlite, sql))
-        .await?;
-
-    for row in result {
-        let name: String = row.try_get("", "name")?;
-        if name == column_name {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
-}
-
 #[derive(Iden)]
 enum Favorite {
     Table,
@@ -1,4 +1,3 @@
-use sea_orm::Statement;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -113,14 +112,5 @@ enum Page {
 }
 
 async fn page_has_column(manager: &SchemaManager<'_>, column: &str) -> Result<bool, DbErr> {
-    let backend = manager.get_connection().get_database_backend();
-    let sql = format!(
-        "SELECT COUNT(*) FROM pragma_table_info('page') WHERE name = '{}'",
-        column.replace('\'', "''")
-    );
-    let result = manager
-        .get_connection()
-        .query_one(Statement::from_string(backend, sql))
-        .await?;
-    Ok(result.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0) >= 1)
+    manager.has_column("page", column).await
 }
@@ -1,4 +1,3 @@
-use sea_orm::Statement;
 use sea_orm_migration::prelude::*;
 
 #[derive(DeriveMigrationName)]
@@ -153,17 +152,7 @@ where
 }
 
 async fn table_has_column(manager: &SchemaManager<'_>, table_name: &str, column_name: &str) -> Result<bool, DbErr> {
-    let backend = manager.get_connection().get_database_backend();
-    let sql = format!(
-        "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = '{}'",
-        table_name.replace('\'', "''"),
-        column_name.replace('\'', "''")
-    );
-    let result = manager
-        .get_connection()
-        .query_one(Statement::from_string(backend, sql))
-        .await?;
-    Ok(result.and_then(|row| row.try_get_by_index(0).ok()).unwrap_or(0) >= 1)
+    manager.has_column(table_name, column_name).await
 }
 
 #[derive(Iden, Clone)]
@@ -0,0 +1,290 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建排行榜/热门订阅表，下载相关开关与其它视频源保持一致
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ranking::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Ranking::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Ranking::Name).string().not_null())
+                    .col(ColumnDef::new(Ranking::Kind).string().not_null())
+                    .col(ColumnDef::new(Ranking::Rid).integer().not_null().default(0))
+                    .col(ColumnDef::new(Ranking::Path).string().not_null())
+                    .col(ColumnDef::new(Ranking::CreatedAt).string().not_null())
+                    .col(
+                        ColumnDef::new(Ranking::LatestRowAt)
+                            .string()
+                            .not_null()
+                            .default("1970-01-01 00:00:00"),
+                    )
+                    .col(ColumnDef::new(Ranking::Enabled).boolean().not_null().default(true))
+                    .col(
+                        ColumnDef::new(Ranking::ScanDeletedVideos)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::ScanDeletedVideosOnce)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Ranking::FilterOption).json().null())
+                    .col(ColumnDef::new(Ranking::KeywordFilters).text().null())
+                    .col(ColumnDef::new(Ranking::KeywordFilterMode).string().null())
+                    .col(ColumnDef::new(Ranking::BlacklistKeywords).text().null())
+                    .col(ColumnDef::new(Ranking::WhitelistKeywords).text().null())
+                    .col(
+                        ColumnDef::new(Ranking::KeywordCaseSensitive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(Ranking::MinDurationSeconds).integer().null())
+                    .col(ColumnDef::new(Ranking::MaxDurationSeconds).integer().null())
+                    .col(ColumnDef::new(Ranking::PublishedAfter).string().null())
+                    .col(ColumnDef::new(Ranking::PublishedBefore).string().null())
+                    .col(ColumnDef::new(Ranking::AudioOnly).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Ranking::AudioOnlyM4aOnly)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Ranking::FlatFolder).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Ranking::SplitChaptersAfterDownload)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::DownloadChargeVideos)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::DownloadDanmaku)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::DownloadSubtitle)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::DownloadAiSubtitle)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiSubtitleLanguage)
+                            .string()
+                            .not_null()
+                            .default("zh-CN"),
+                    )
+                    .col(ColumnDef::new(Ranking::AiRename).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameVideoPrompt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameAudioPrompt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameEnableMultiPage)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameEnableCollection)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameEnableBangumi)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Ranking::AiRenameRenameParentDir)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 同一类型 + 分区只允许订阅一次
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ranking_type_rid")
+                    .table(Ranking::Table)
+                    .col(Ranking::Kind)
+                    .col(Ranking::Rid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Video::Table)
+                    .add_column(ColumnDef::new(Video::RankingId).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 唯一索引需要包含 ranking_id，否则同一 bvid 无法同时属于排行榜源与其它源
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_unique").await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("submission_id", Some("-1")),
+                ("source_id", Some("-1")),
+                ("ranking_id", Some("-1")),
+                ("bvid", None),
+                ("ep_id", Some("''")),
+            ],
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_video_ranking_id")
+                    .table(Video::Table)
+                    .col(Video::RankingId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_video_ranking_id")
+                    .table(Video::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        crate::dialect::drop_index_if_exists(manager, "video", "idx_video_unique").await?;
+        db.execute_unprepared("DELETE FROM video WHERE ranking_id IS NOT NULL")
+            .await?;
+        crate::dialect::create_null_safe_unique_index(
+            manager,
+            "idx_video_unique",
+            "video",
+            &[
+                ("collection_id", Some("-1")),
+                ("favorite_id", Some("-1")),
+                ("watch_later_id", Some("-1")),
+                ("submission_id", Some("-1")),
+                ("source_id", Some("-1")),
+                ("bvid", None),
+                ("ep_id", Some("''")),
+            ],
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Video::Table)
+                    .drop_column(Video::RankingId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.drop_table(Table::drop().table(Ranking::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Ranking {
+    Table,
+    Id,
+    Name,
+    #[sea_orm(iden = "ranking_type")]
+    Kind,
+    Rid,
+    Path,
+    CreatedAt,
+    LatestRowAt,
+    Enabled,
+    ScanDeletedVideos,
+    ScanDeletedVideosOnce,
+    FilterOption,
+    KeywordFilters,
+    KeywordFilterMode,
+    BlacklistKeywords,
+    WhitelistKeywords,
+    KeywordCaseSensitive,
+    MinDurationSeconds,
+    MaxDurationSeconds,
+    PublishedAfter,
+    PublishedBefore,
+    AudioOnly,
+    AudioOnlyM4aOnly,
+    FlatFolder,
+    SplitChaptersAfterDownload,
+    DownloadChargeVideos,
+    DownloadDanmaku,
+    DownloadSubtitle,
+    DownloadAiSubtitle,
+    AiSubtitleLanguage,
+    AiRename,
+    AiRenameVideoPrompt,
+    AiRenameAudioPrompt,
+    AiRenameEnableMultiPage,
+    AiRenameEnableCollection,
+    AiRenameEnableBangumi,
+    AiRenameRenameParentDir,
+}
+
+#[derive(DeriveIden)]
+enum Video {
+    Table,
+    RankingId,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 记录画质升级检查的最近时间，避免后台任务反复请求同一分页的播放地址
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Page::Table)
+                    .add_column(ColumnDef::new(Page::QualityCheckedAt).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Page::Table)
+                    .drop_column(Page::QualityCheckedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Page {
+    Table,
+    QualityCheckedAt,
+}
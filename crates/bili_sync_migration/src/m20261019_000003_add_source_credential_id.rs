@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 视频源绑定的B站账号，NULL 表示使用默认账号
+        for table in VideoSourceTable::tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(VideoSourceTable::CredentialId).integer().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in VideoSourceTable::tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(VideoSourceTable::CredentialId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum VideoSourceTable {
+    Collection,
+    Favorite,
+    Submission,
+    WatchLater,
+    VideoSource,
+    Ranking,
+    CredentialId,
+}
+
+impl VideoSourceTable {
+    fn tables() -> [Self; 6] {
+        [
+            Self::Collection,
+            Self::Favorite,
+            Self::Submission,
+            Self::WatchLater,
+            Self::VideoSource,
+            Self::Ranking,
+        ]
+    }
+}
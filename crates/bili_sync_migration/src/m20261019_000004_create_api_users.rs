@@ -0,0 +1,171 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 管理页用户：密码仅保存哈希，角色为 admin/operator/viewer
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiUser::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApiUser::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ApiUser::Username).string().not_null().unique_key())
+                    .col(ColumnDef::new(ApiUser::PasswordHash).string().not_null())
+                    .col(ColumnDef::new(ApiUser::Role).string().not_null())
+                    .col(ColumnDef::new(ApiUser::Enabled).boolean().not_null().default(true))
+                    .col(ColumnDef::new(ApiUser::CreatedAt).string().not_null())
+                    .col(ColumnDef::new(ApiUser::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 登录会话：只保存令牌哈希
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiSession::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApiSession::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ApiSession::TokenHash).string().not_null().unique_key())
+                    .col(ColumnDef::new(ApiSession::UserId).integer().not_null())
+                    .col(ColumnDef::new(ApiSession::ExpiresAt).string().not_null())
+                    .col(ColumnDef::new(ApiSession::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_api_session_user_id")
+                    .table(ApiSession::Table)
+                    .col(ApiSession::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 操作审计日志：记录谁在什么时候调用了哪个写接口
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLog::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditLog::Actor).string().not_null())
+                    .col(ColumnDef::new(AuditLog::Role).string().not_null())
+                    .col(ColumnDef::new(AuditLog::Method).string().not_null())
+                    .col(ColumnDef::new(AuditLog::Path).string().not_null())
+                    .col(ColumnDef::new(AuditLog::StatusCode).integer().not_null())
+                    .col(ColumnDef::new(AuditLog::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 配置变更历史记录修改人
+        if manager.has_table("config_changes").await? && !manager.has_column("config_changes", "changed_by").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ConfigChange::Table)
+                        .add_column(ColumnDef::new(ConfigChange::ChangedBy).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("config_changes", "changed_by").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(ConfigChange::Table)
+                        .drop_column(ConfigChange::ChangedBy)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ApiSession::Table).to_owned())
+            .await?;
+        manager.drop_table(Table::drop().table(ApiUser::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ApiUser {
+    Table,
+    Id,
+    Username,
+    PasswordHash,
+    Role,
+    Enabled,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ApiSession {
+    Table,
+    Id,
+    TokenHash,
+    UserId,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    Actor,
+    Role,
+    Method,
+    Path,
+    StatusCode,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ConfigChange {
+    #[sea_orm(iden = "config_changes")]
+    Table,
+    ChangedBy,
+}
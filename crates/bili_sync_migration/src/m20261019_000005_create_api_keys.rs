@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 自动化脚本使用的 API 密钥：只保存哈希，scopes 为逗号分隔的权限范围
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApiKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApiKey::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ApiKey::Name).string().not_null())
+                    .col(ColumnDef::new(ApiKey::KeyPrefix).string().not_null())
+                    .col(ColumnDef::new(ApiKey::KeyHash).string().not_null().unique_key())
+                    .col(ColumnDef::new(ApiKey::Scopes).string().not_null())
+                    .col(ColumnDef::new(ApiKey::ExpiresAt).string().null())
+                    .col(ColumnDef::new(ApiKey::LastUsedAt).string().null())
+                    .col(ColumnDef::new(ApiKey::RevokedAt).string().null())
+                    .col(ColumnDef::new(ApiKey::CreatedBy).string().not_null())
+                    .col(ColumnDef::new(ApiKey::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ApiKey::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ApiKey {
+    Table,
+    Id,
+    Name,
+    KeyPrefix,
+    KeyHash,
+    Scopes,
+    ExpiresAt,
+    LastUsedAt,
+    RevokedAt,
+    CreatedBy,
+    CreatedAt,
+}
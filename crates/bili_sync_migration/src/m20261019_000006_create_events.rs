@@ -0,0 +1,157 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 持久化的事件流：供 SSE 回放与 Webhook 推送使用，payload 为 JSON 文本
+        manager
+            .create_table(
+                Table::create()
+                    .table(Event::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Event::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Event::Kind).string().not_null())
+                    .col(ColumnDef::new(Event::Payload).text().not_null())
+                    .col(ColumnDef::new(Event::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_event_created_at")
+                    .table(Event::Table)
+                    .col(Event::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 第三方 Webhook 订阅：event_kinds 为逗号分隔的事件类型，为空表示订阅全部
+        manager
+            .create_table(
+                Table::create()
+                    .table(Webhook::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Webhook::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Webhook::Name).string().not_null())
+                    .col(ColumnDef::new(Webhook::Url).string().not_null())
+                    .col(ColumnDef::new(Webhook::Secret).string().not_null())
+                    .col(ColumnDef::new(Webhook::EventKinds).string().not_null().default(""))
+                    .col(ColumnDef::new(Webhook::Enabled).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Webhook::CreatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // 每个事件对每个订阅的投递记录，失败后按 next_attempt_at 重试
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::WebhookId).integer().not_null())
+                    .col(ColumnDef::new(WebhookDelivery::EventId).integer().not_null())
+                    .col(ColumnDef::new(WebhookDelivery::Status).string().not_null())
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::NextAttemptAt).string().null())
+                    .col(ColumnDef::new(WebhookDelivery::ResponseStatus).integer().null())
+                    .col(ColumnDef::new(WebhookDelivery::LastError).text().null())
+                    .col(ColumnDef::new(WebhookDelivery::CreatedAt).string().not_null())
+                    .col(ColumnDef::new(WebhookDelivery::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_status")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::Status)
+                    .col(WebhookDelivery::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_webhook_id")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::WebhookId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Webhook::Table).to_owned())
+            .await?;
+        manager.drop_table(Table::drop().table(Event::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    Kind,
+    Payload,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Webhook {
+    Table,
+    Id,
+    Name,
+    Url,
+    Secret,
+    EventKinds,
+    Enabled,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebhookDelivery {
+    Table,
+    Id,
+    WebhookId,
+    EventId,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    ResponseStatus,
+    LastError,
+    CreatedAt,
+    UpdatedAt,
+}
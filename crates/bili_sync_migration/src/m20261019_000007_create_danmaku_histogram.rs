@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 每个分页的弹幕密度直方图，counts 为 JSON 数组，chapters 记录最近一次写入的高能章节
+        manager
+            .create_table(
+                Table::create()
+                    .table(DanmakuHistogram::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DanmakuHistogram::PageId)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DanmakuHistogram::VideoId).integer().not_null())
+                    .col(
+                        ColumnDef::new_with_type(DanmakuHistogram::BucketSeconds, crate::dialect::unsigned(manager))
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DanmakuHistogram::Counts).text().not_null())
+                    .col(
+                        ColumnDef::new_with_type(DanmakuHistogram::Total, crate::dialect::unsigned(manager))
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(DanmakuHistogram::Chapters).text().null())
+                    .col(ColumnDef::new(DanmakuHistogram::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_danmaku_histogram_video_id")
+                    .table(DanmakuHistogram::Table)
+                    .col(DanmakuHistogram::VideoId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DanmakuHistogram::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DanmakuHistogram {
+    Table,
+    PageId,
+    VideoId,
+    BucketSeconds,
+    Counts,
+    Total,
+    Chapters,
+    UpdatedAt,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 视频源是否归档评论区，默认关闭
+        for table in VideoSourceTable::tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(VideoSourceTable::ArchiveComments)
+                                .boolean()
+                                .not_null()
+                                .default(false),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // 每个视频的评论区归档状态，沿用弹幕的分阶段同步策略
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentArchive::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentArchive::VideoId)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CommentArchive::LastSyncedAt).string().not_null())
+                    .col(
+                        ColumnDef::new_with_type(CommentArchive::SyncGeneration, crate::dialect::unsigned(manager))
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new_with_type(CommentArchive::CommentCount, crate::dialect::unsigned(manager))
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(CommentArchive::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CommentArchive::Table).to_owned())
+            .await?;
+        for table in VideoSourceTable::tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(VideoSourceTable::ArchiveComments)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum VideoSourceTable {
+    Collection,
+    Favorite,
+    Submission,
+    WatchLater,
+    VideoSource,
+    Ranking,
+    ArchiveComments,
+}
+
+impl VideoSourceTable {
+    fn tables() -> [Self; 6] {
+        [
+            Self::Collection,
+            Self::Favorite,
+            Self::Submission,
+            Self::WatchLater,
+            Self::VideoSource,
+            Self::Ranking,
+        ]
+    }
+}
+
+#[derive(DeriveIden)]
+enum CommentArchive {
+    Table,
+    VideoId,
+    LastSyncedAt,
+    SyncGeneration,
+    CommentCount,
+    UpdatedAt,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 每个视频的 AI 摘要，tags 为 JSON 数组，source 记录摘要依据的是字幕还是简介
+        manager
+            .create_table(
+                Table::create()
+                    .table(VideoSummary::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VideoSummary::VideoId).integer().not_null().primary_key())
+                    .col(ColumnDef::new(VideoSummary::Summary).text().not_null())
+                    .col(ColumnDef::new(VideoSummary::Tags).text().not_null())
+                    .col(ColumnDef::new(VideoSummary::Source).string().not_null())
+                    .col(ColumnDef::new(VideoSummary::CreatedAt).string().not_null())
+                    .col(ColumnDef::new(VideoSummary::UpdatedAt).string().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VideoSummary::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VideoSummary {
+    Table,
+    VideoId,
+    Summary,
+    Tags,
+    Source,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 每个分页每种目标语言的字幕翻译状态，已完成或失败次数达到上限的不再处理
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubtitleTranslation::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SubtitleTranslation::PageId).integer().not_null())
+                    .col(ColumnDef::new(SubtitleTranslation::TargetLanguage).string().not_null())
+                    .col(ColumnDef::new(SubtitleTranslation::CompletedAt).string().null())
+                    .col(
+                        ColumnDef::new_with_type(
+                            SubtitleTranslation::FailedAttempts,
+                            crate::dialect::unsigned(manager),
+                        )
+                        .not_null()
+                        .default(0),
+                    )
+                    .col(ColumnDef::new(SubtitleTranslation::LastError).text().null())
+                    .col(ColumnDef::new(SubtitleTranslation::UpdatedAt).string().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(SubtitleTranslation::PageId)
+                            .col(SubtitleTranslation::TargetLanguage),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SubtitleTranslation::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SubtitleTranslation {
+    Table,
+    PageId,
+    TargetLanguage,
+    CompletedAt,
+    FailedAttempts,
+    LastError,
+    UpdatedAt,
+}
@@ -18,6 +18,10 @@ import type {
 	FilenamePreviewRequest,
 	FilenamePreviewResponse,
 	RefreshDanmakuResponse,
+	RerenderDanmakuRequest,
+	RerenderDanmakuResponse,
+	DanmakuHighlightsRequest,
+	DanmakuHighlightsResponse,
 	UpdateConfigRequest,
 	UpdateConfigResponse,
 	SearchRequest,
@@ -53,6 +57,8 @@ import type {
 	TaskStatus,
 	BangumiSeasonsResponse,
 	VideoBvidResponse,
+	CreateStreamLinkRequest,
+	StreamLinkResponse,
 	LatestIngestResponse,
 	BetaImageUpdateStatusResponse
 } from './types';
@@ -252,6 +258,20 @@ class ApiClient {
 		return this.post<RefreshDanmakuResponse>(`/pages/${id}/refresh-danmaku`);
 	}
 
+	async rerenderDanmaku(request: RerenderDanmakuRequest = {}): Promise<ApiResponse<RerenderDanmakuResponse>> {
+		return this.post<RerenderDanmakuResponse>('/danmaku/rerender', request);
+	}
+
+	async getPageDanmakuHighlights(
+		id: number,
+		params: DanmakuHighlightsRequest = {}
+	): Promise<ApiResponse<DanmakuHighlightsResponse>> {
+		return this.get<DanmakuHighlightsResponse>(
+			`/pages/${id}/danmaku/highlights`,
+			params as Record<string, unknown>
+		);
+	}
+
 	/**
 	 * 重置视频下载状态
 	 * @param id 视频 ID
@@ -414,6 +434,7 @@ class ApiClient {
 			split_chapters_after_download?: boolean;
 			download_charge_videos?: boolean;
 			download_danmaku?: boolean;
+			archive_comments?: boolean;
 			download_subtitle?: boolean;
 			download_ai_subtitle?: boolean;
 			ai_subtitle_language?: string;
@@ -439,6 +460,7 @@ class ApiClient {
 			split_chapters_after_download: boolean;
 			download_charge_videos: boolean;
 			download_danmaku: boolean;
+			archive_comments: boolean;
 			download_subtitle: boolean;
 			download_ai_subtitle: boolean;
 			ai_subtitle_language: string;
@@ -466,6 +488,7 @@ class ApiClient {
 			split_chapters_after_download: boolean;
 			download_charge_videos: boolean;
 			download_danmaku: boolean;
+			archive_comments: boolean;
 			download_subtitle: boolean;
 			download_ai_subtitle: boolean;
 			ai_subtitle_language: string;
@@ -866,7 +889,17 @@ class ApiClient {
 	}
 
 	/**
-	 * 获取代理视频流URL
+	 * 生成带签名、会过期的视频流链接（播放器与分享使用）
+	 * @param request 本地视频ID或需要代理的视频流URL
+	 */
+	async createStreamLink(
+		request: CreateStreamLinkRequest
+	): Promise<ApiResponse<StreamLinkResponse>> {
+		return this.post<StreamLinkResponse>('/stream-links', request);
+	}
+
+	/**
+	 * 获取代理视频流URL（需已登录，未登录时请使用 createStreamLink 生成签名链接）
 	 * @param streamUrl 原始视频流URL
 	 */
 	getProxyStreamUrl(streamUrl: string, options?: { transmux?: boolean }): string {
@@ -1070,6 +1103,11 @@ export const api = {
 
 	refreshPageDanmaku: (id: number) => apiClient.refreshPageDanmaku(id),
 
+	rerenderDanmaku: (request?: RerenderDanmakuRequest) => apiClient.rerenderDanmaku(request),
+
+	getPageDanmakuHighlights: (id: number, params?: DanmakuHighlightsRequest) =>
+		apiClient.getPageDanmakuHighlights(id, params),
+
 	/**
 	 * 重置视频下载状态
 	 */
@@ -1234,6 +1272,7 @@ export const api = {
 			split_chapters_after_download?: boolean;
 			download_charge_videos?: boolean;
 			download_danmaku?: boolean;
+			archive_comments?: boolean;
 			download_subtitle?: boolean;
 			download_ai_subtitle?: boolean;
 			ai_subtitle_language?: string;
@@ -1387,6 +1426,11 @@ export const api = {
 	 */
 	getVideoBvid: (videoId: string | number) => apiClient.getVideoBvid(videoId),
 
+	/**
+	 * 生成带签名、会过期的视频流链接
+	 */
+	createStreamLink: (request: CreateStreamLinkRequest) => apiClient.createStreamLink(request),
+
 	/**
 	 * 获取代理视频流URL
 	 */
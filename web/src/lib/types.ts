@@ -108,6 +108,7 @@ export interface VideoSource {
 	split_chapters_after_download: boolean; // 下载后按播放器章节切分为独立视频
 	download_charge_videos: boolean; // 是否下载充电视频
 	download_danmaku: boolean; // 是否下载弹幕
+	archive_comments: boolean; // 是否归档评论区
 	download_subtitle: boolean; // 是否下载字幕
 	download_ai_subtitle: boolean; // 是否下载 B 站 AI 字幕
 	ai_subtitle_language: string; // AI 字幕优先语言
@@ -286,6 +287,7 @@ export interface AddVideoSourceRequest {
 	split_chapters_after_download?: boolean; // 下载后按播放器章节切分为独立视频
 	download_charge_videos?: boolean; // 是否下载充电视频（默认true）
 	download_danmaku?: boolean; // 是否下载弹幕（默认true）
+	archive_comments?: boolean; // 是否归档评论区（默认false）
 	download_subtitle?: boolean; // 是否下载字幕（默认true）
 	download_ai_subtitle?: boolean; // 是否下载 B 站 AI 字幕（默认true）
 	ai_subtitle_language?: string; // AI 字幕优先语言（默认 zh-CN）
@@ -361,6 +363,7 @@ export interface ConfigResponse {
 	danmaku_bold?: boolean;
 	danmaku_outline?: number;
 	danmaku_time_offset?: number;
+	danmaku_font_path?: string | null;
 	danmaku_update_enabled?: boolean;
 	danmaku_update_fresh_days?: number;
 	danmaku_update_fresh_interval_hours?: number;
@@ -409,6 +412,8 @@ export interface ConfigResponse {
 	submission_adaptive_max_hours?: number;
 	// 扫描已删除视频设置
 	scan_deleted_videos?: boolean;
+	// 跨视频源去重（硬链接复用），默认关闭
+	cross_source_dedup?: boolean;
 	// aria2监控配置
 	enable_aria2_health_check?: boolean;
 	enable_aria2_auto_restart?: boolean;
@@ -544,6 +549,7 @@ export interface UpdateConfigRequest {
 	danmaku_bold?: boolean;
 	danmaku_outline?: number;
 	danmaku_time_offset?: number;
+	danmaku_font_path?: string | null;
 	danmaku_update_enabled?: boolean;
 	danmaku_update_fresh_days?: number;
 	danmaku_update_fresh_interval_hours?: number;
@@ -592,6 +598,8 @@ export interface UpdateConfigRequest {
 	submission_adaptive_max_hours?: number;
 	// 扫描已删除视频设置
 	scan_deleted_videos?: boolean;
+	// 跨视频源去重（硬链接复用），默认关闭
+	cross_source_dedup?: boolean;
 	// aria2监控配置
 	enable_aria2_health_check?: boolean;
 	enable_aria2_auto_restart?: boolean;
@@ -650,6 +658,59 @@ export interface RefreshDanmakuResponse {
 	message: string;
 }
 
+// 重新渲染弹幕 ASS，不指定视频源时处理整个媒体库
+export interface RerenderDanmakuRequest {
+	source_type?: string;
+	source_id?: number;
+}
+
+export interface RerenderDanmakuResponse {
+	success: boolean;
+	task_id: string | null;
+	message: string;
+}
+
+// 分页的弹幕密度直方图与高能时刻
+export interface DanmakuHighlightsRequest {
+	top?: number;
+	min_gap_seconds?: number;
+}
+
+export interface DanmakuPeak {
+	start_s: number;
+	end_s: number;
+	count: number;
+	ratio: number;
+}
+
+export interface HighlightChapter {
+	start_s: number;
+	end_s: number;
+	title: string;
+}
+
+export interface DanmakuHighlightsResponse {
+	page_id: number;
+	video_id: number;
+	bucket_seconds: number;
+	total: number;
+	histogram: number[];
+	peaks: DanmakuPeak[];
+	chapters: HighlightChapter[];
+	updated_at: string | null;
+}
+
+export interface RerenderDanmakuProgress {
+	task_id: string;
+	description: string;
+	total: number;
+	processed: number;
+	rendered: number;
+	skipped: number;
+	no_archive: number;
+	failed: number;
+}
+
 // 搜索请求类型
 export interface SearchRequest {
 	keyword: string;
@@ -822,6 +883,10 @@ export interface QueueStatusResponse {
 	video_delete_queue: QueueInfo;
 	add_queue: QueueInfo;
 	danmaku_queue: QueueInfo;
+	danmaku_rerender_queue: QueueInfo;
+	danmaku_rerender_progress: RerenderDanmakuProgress | null;
+	subtitle_generation_queue: QueueInfo;
+	quality_upgrade_queue: QueueInfo;
 	config_queue: ConfigQueueInfo;
 }
 
@@ -1047,6 +1112,18 @@ export interface VideoBvidResponse {
 	bilibili_url: string;
 }
 
+// 视频流分享链接
+export interface CreateStreamLinkRequest {
+	video_id?: string;
+	url?: string;
+	expires_in_hours?: number;
+}
+
+export interface StreamLinkResponse {
+	path: string;
+	expires_at: string;
+}
+
 // 视频流信息类型
 export interface VideoStreamInfo {
 	url: string;